@@ -0,0 +1,311 @@
+//! `.loreignore` rules that keep sessions out of the database.
+//!
+//! A `.loreignore` file lists glob patterns, one per line, matched against
+//! session working directories with gitignore semantics:
+//!
+//! - Blank lines and lines starting with `#` are ignored.
+//! - A pattern starting with `!` re-includes directories an earlier
+//!   pattern ignored. The last matching pattern wins.
+//! - A pattern without a slash (other than a trailing one) matches any
+//!   directory name, so `secret-*` ignores `~/src/secret-client/app`.
+//! - A pattern with a slash is anchored to the file's directory; `*` does
+//!   not cross a slash, while `**` does.
+//! - Everything below an ignored directory is ignored, and cannot be
+//!   re-included by a later `!` pattern.
+//!
+//! The global file, `~/.lore/.loreignore`, is anchored at the filesystem
+//! root and may start patterns with `~/` for the home directory. A
+//! `.loreignore` in a session's working directory or one of its ancestors
+//! applies below that directory. As with gitignore, a file without patterns
+//! ignores nothing. A `*` or `**` pattern also matches the file's own
+//! directory, so a `.loreignore` containing just `*` opts one repository out.
+//!
+//! Sessions from ignored directories are skipped by `lore import` and by the
+//! daemon, so they are never stored.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+
+/// Name of an ignore file, globally and in repositories.
+pub const IGNORE_FILE: &str = ".loreignore";
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// One pattern line of an ignore file.
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: Pattern,
+    negated: bool,
+    /// Whether the pattern is matched against the whole relative path
+    /// rather than against a single directory name.
+    anchored: bool,
+}
+
+impl Rule {
+    /// Parses one line, returning `None` for blank lines, comments, and
+    /// invalid patterns.
+    fn parse(line: &str, home: Option<&Path>) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, mut text) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest.to_string()),
+            None => (false, line.strip_prefix('\\').unwrap_or(line).to_string()),
+        };
+        if let (Some(rest), Some(home)) = (text.strip_prefix("~/"), home) {
+            text = format!("{}/{rest}", home.display());
+        }
+        while text.len() > 1 && text.ends_with('/') {
+            text.pop();
+        }
+
+        let anchored = text.contains('/');
+        let text = text.trim_start_matches('/');
+        if text.is_empty() {
+            return None;
+        }
+        match Pattern::new(text) {
+            Ok(pattern) => Some(Self {
+                pattern,
+                negated,
+                anchored,
+            }),
+            Err(e) => {
+                tracing::warn!("Skipping invalid {IGNORE_FILE} pattern '{line}': {e}");
+                None
+            }
+        }
+    }
+
+    /// Returns true for `*` and `**`, which also match the file's own
+    /// directory.
+    fn matches_everything(&self) -> bool {
+        matches!(self.pattern.as_str(), "*" | "**")
+    }
+
+    /// Tests the rule against a directory path relative to the file's base.
+    fn matches(&self, relative: &str) -> bool {
+        if self.anchored {
+            self.pattern.matches_with(relative, MATCH_OPTIONS)
+        } else {
+            let name = relative.rsplit('/').next().unwrap_or(relative);
+            self.pattern.matches_with(name, MATCH_OPTIONS)
+        }
+    }
+}
+
+/// The parsed patterns of one `.loreignore` file.
+#[derive(Debug, Clone)]
+pub struct IgnoreFile {
+    /// Directory the patterns are relative to.
+    base: PathBuf,
+    rules: Vec<Rule>,
+}
+
+impl IgnoreFile {
+    /// Parses ignore file `contents` whose patterns are relative to `base`.
+    ///
+    /// Patterns starting with `~/` are expanded to `home` when it is given.
+    pub fn parse(base: &Path, contents: &str, home: Option<&Path>) -> Self {
+        Self {
+            base: base.to_path_buf(),
+            rules: contents
+                .lines()
+                .filter_map(|line| Rule::parse(line, home))
+                .collect(),
+        }
+    }
+
+    /// Reads and parses the ignore file at `path`, if it can be read.
+    fn read(path: &Path, base: &Path, home: Option<&Path>) -> Option<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Some(Self::parse(base, &contents, home)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                tracing::warn!("Failed to read {}: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Returns the verdict of the last rule matching `dir`, if any rule
+    /// matches: true to ignore, false to re-include. Only `*` and `**`
+    /// match the base directory itself.
+    fn verdict(&self, dir: &Path) -> Option<bool> {
+        let relative = dir.strip_prefix(&self.base).ok()?;
+        let relative: Vec<String> = relative
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        if relative.is_empty() {
+            return self
+                .rules
+                .iter()
+                .rev()
+                .find(|rule| rule.matches_everything())
+                .map(|rule| !rule.negated);
+        }
+        let relative = relative.join("/");
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(&relative))
+            .map(|rule| !rule.negated)
+    }
+}
+
+/// The global ignore rules, combined with per-repository files on lookup.
+#[derive(Debug, Clone)]
+pub struct LoreIgnore {
+    global: Option<IgnoreFile>,
+}
+
+impl LoreIgnore {
+    /// Creates rules from an already parsed global file.
+    pub fn new(global: Option<IgnoreFile>) -> Self {
+        Self { global }
+    }
+
+    /// Loads `~/.lore/.loreignore`, if it exists.
+    ///
+    /// A missing or unreadable file means no global rules; per-repository
+    /// files still apply.
+    pub fn load() -> Self {
+        let home = dirs::home_dir();
+        let global = home.as_deref().and_then(|home| {
+            let path = home.join(".lore").join(IGNORE_FILE);
+            IgnoreFile::read(&path, Path::new("/"), Some(home))
+        });
+        Self::new(global)
+    }
+
+    /// Returns true if sessions recorded in `working_directory` should be
+    /// skipped.
+    ///
+    /// The global rules are applied first and each `.loreignore` found in
+    /// the directory or its ancestors after them, outermost first, so the
+    /// file closest to the directory has the last word.
+    pub fn is_ignored(&self, working_directory: &str) -> bool {
+        let dir = Path::new(working_directory);
+        if !dir.is_absolute() {
+            return false;
+        }
+
+        let mut local: Vec<IgnoreFile> = dir
+            .ancestors()
+            .filter_map(|base| IgnoreFile::read(&base.join(IGNORE_FILE), base, None))
+            .collect();
+        local.reverse();
+        let files: Vec<&IgnoreFile> = self.global.iter().chain(local.iter()).collect();
+
+        // A directory below an ignored one is ignored too, so test each
+        // ancestor from the outermost in
+        let mut ancestors: Vec<&Path> = dir.ancestors().collect();
+        ancestors.reverse();
+        ancestors.into_iter().any(|ancestor| {
+            files
+                .iter()
+                .rev()
+                .find_map(|file| file.verdict(ancestor))
+                .unwrap_or(false)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn global(contents: &str) -> LoreIgnore {
+        LoreIgnore::new(Some(IgnoreFile::parse(
+            Path::new("/"),
+            contents,
+            Some(Path::new("/home/me")),
+        )))
+    }
+
+    #[test]
+    fn test_anchored_and_unanchored_patterns() {
+        let ignore = global(
+            "# client work\n\
+             ~/clients/*\n\
+             \n\
+             secret-*/\n",
+        );
+
+        assert!(ignore.is_ignored("/home/me/clients/acme"));
+        assert!(ignore.is_ignored("/home/me/clients/acme/backend"));
+        assert!(!ignore.is_ignored("/home/me/clients"));
+        assert!(ignore.is_ignored("/home/me/src/secret-proj"));
+        assert!(ignore.is_ignored("/srv/secret-proj/app"));
+        assert!(!ignore.is_ignored("/home/me/src/lore"));
+        assert!(!ignore.is_ignored("relative/secret-proj"));
+    }
+
+    #[test]
+    fn test_negation_re_includes_until_a_parent_is_ignored() {
+        let ignore = global(
+            "/home/me/clients/*\n\
+             !/home/me/clients/open-source\n",
+        );
+
+        assert!(ignore.is_ignored("/home/me/clients/acme"));
+        assert!(!ignore.is_ignored("/home/me/clients/open-source"));
+        assert!(!ignore.is_ignored("/home/me/clients/open-source/lib"));
+
+        // A later negation cannot re-include below an ignored directory
+        let ignore = global("/srv\n!/srv/public\n");
+        assert!(ignore.is_ignored("/srv/public"));
+    }
+
+    #[test]
+    fn test_repository_ignore_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let repo = root.join("repo");
+        let opted_out = root.join("opted-out");
+        fs::create_dir_all(repo.join("vendor/lib")).unwrap();
+        fs::create_dir_all(opted_out.join("src")).unwrap();
+        fs::write(repo.join(IGNORE_FILE), "vendor\n").unwrap();
+        fs::write(opted_out.join(IGNORE_FILE), "# keep this repo out\n*\n").unwrap();
+
+        let ignore = LoreIgnore::new(None);
+        let path = |p: &Path| p.to_string_lossy().into_owned();
+        assert!(!ignore.is_ignored(&path(&repo)));
+        assert!(ignore.is_ignored(&path(&repo.join("vendor/lib"))));
+        assert!(ignore.is_ignored(&path(&opted_out)));
+        assert!(ignore.is_ignored(&path(&opted_out.join("src"))));
+
+        // A repository file can re-include what the global file ignores
+        fs::write(repo.join(IGNORE_FILE), "!vendor\n").unwrap();
+        let ignore = global("vendor\n");
+        assert!(!ignore.is_ignored(&path(&repo.join("vendor"))));
+    }
+
+    #[test]
+    fn test_empty_ignore_file_ignores_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let project = root.join("project");
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::write(root.join(IGNORE_FILE), "").unwrap();
+        fs::write(project.join(IGNORE_FILE), "# nothing yet\n\n").unwrap();
+
+        let ignore = LoreIgnore::new(None);
+        let path = |p: &Path| p.to_string_lossy().into_owned();
+        assert!(!ignore.is_ignored(&path(&root)));
+        assert!(!ignore.is_ignored(&path(&project)));
+        assert!(!ignore.is_ignored(&path(&project.join("src"))));
+    }
+}
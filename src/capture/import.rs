@@ -0,0 +1,1151 @@
+//! Session import from the registered watchers.
+//!
+//! [`import_all`] runs the import loop behind `lore import` as a library
+//! call: it lists each watcher's session files, skips files unchanged since
+//! their last import, parses the rest on a pool of worker threads, and
+//! stores the sessions from the calling thread. Grown files gain only their
+//! new messages: the messages past the number already stored. Sessions whose working directory matches a `.loreignore`
+//! rule are skipped, and nothing is printed; callers that want progress use
+//! [`import_all_with_progress`].
+
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::capture::ignore::LoreIgnore;
+use crate::capture::redact::SecretRedactor;
+use crate::capture::repositories::RepositoryRecorder;
+use crate::capture::watchers::{Watcher, WatcherRegistry};
+use crate::config::Config;
+use crate::git::DEFAULT_REMOTE_NAMES;
+use crate::storage::{session_dedup_key, Database, Message, Session, SourceStamp};
+
+/// Settings for [`import_all`].
+pub struct ImportOptions {
+    /// Names of the watchers to import from, in order. Names that are not
+    /// registered, or whose tool is not available on this system, are left
+    /// out.
+    pub watchers: Vec<String>,
+    /// Re-parse every file, even those unchanged since their last import.
+    pub force: bool,
+    /// Count what would be imported without writing to the database.
+    pub dry_run: bool,
+    /// Maximum number of files parsed at a time.
+    pub jobs: usize,
+    /// Redaction applied to messages before they are stored, if any.
+    pub redactor: Option<SecretRedactor>,
+    /// Rules for working directories whose sessions are skipped.
+    pub ignore: LoreIgnore,
+    /// Git remote names tried, in order, for each repository's remote URL.
+    pub remote_names: Vec<String>,
+}
+
+impl ImportOptions {
+    /// Creates options that import `watchers` with one parse thread per
+    /// CPU, no redaction, no ignore rules, and the default remote names.
+    pub fn new(watchers: Vec<String>) -> Self {
+        Self {
+            watchers,
+            force: false,
+            dry_run: false,
+            jobs: default_jobs(),
+            redactor: None,
+            ignore: LoreIgnore::new(None),
+            remote_names: DEFAULT_REMOTE_NAMES.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Creates options for the watchers enabled in `config`, with its
+    /// redaction and remote settings and the user's `.loreignore` rules.
+    ///
+    /// Returns an error if a configured redaction pattern is invalid.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(Self {
+            redactor: SecretRedactor::from_config(config)?,
+            ignore: LoreIgnore::load(),
+            remote_names: config.git_remotes.clone(),
+            ..Self::new(config.watchers.clone())
+        })
+    }
+}
+
+/// Counts returned by [`import_all`].
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Number of sessions imported.
+    pub imported: usize,
+    /// Number of sessions skipped.
+    pub skipped: usize,
+    /// Number of errors encountered.
+    pub errors: usize,
+    /// Number of tools that had sessions imported.
+    pub tools_count: usize,
+    /// Counts for each tool that had source files, in import order.
+    pub by_tool: Vec<ToolImportStats>,
+    /// Number of watchers the import ran.
+    pub tools_attempted: usize,
+    /// Watchers whose import failed, with the error that stopped them.
+    pub failed: Vec<(String, String)>,
+}
+
+impl ImportReport {
+    /// Returns true if every watcher the import ran failed.
+    pub fn all_failed(&self) -> bool {
+        self.tools_attempted > 0 && self.failed.len() == self.tools_attempted
+    }
+}
+
+/// Import counts for a single tool.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ToolImportStats {
+    /// Watcher name (e.g., "claude-code").
+    pub tool: String,
+    /// Number of source files found.
+    pub files: usize,
+    /// Number of sessions imported.
+    pub imported: usize,
+    /// Number of sessions or files skipped.
+    pub skipped: usize,
+    /// Number of files that failed to parse.
+    pub errors: usize,
+    /// Sessions skipped by `.loreignore` rules, also counted in `skipped`.
+    pub ignored: Vec<IgnoredSession>,
+}
+
+/// A session left out of the import by a `.loreignore` rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoredSession {
+    /// Session ID.
+    pub id: uuid::Uuid,
+    /// The working directory the rule matched.
+    pub working_directory: String,
+}
+
+/// Progress reported by [`import_all_with_progress`] as an import runs.
+#[derive(Debug)]
+#[allow(dead_code)]
+pub enum ImportEvent<'a> {
+    /// A watcher's import is starting.
+    WatcherStarted {
+        /// Watcher name.
+        tool: &'static str,
+    },
+    /// A watcher's source files were listed. Nothing more is reported for
+    /// a watcher with no files.
+    SourcesFound {
+        /// Watcher name.
+        tool: &'static str,
+        /// Number of source files found.
+        files: usize,
+    },
+    /// A source file was skipped as unchanged, parsed, or failed to parse.
+    FileDone {
+        /// Number of files processed so far.
+        done: usize,
+        /// Number of files found.
+        total: usize,
+        /// The tool's counts so far.
+        counts: &'a ToolImportStats,
+    },
+    /// A source file could not be parsed and was skipped.
+    Unreadable {
+        /// The file.
+        path: &'a Path,
+        /// Why it could not be parsed.
+        error: &'a anyhow::Error,
+    },
+    /// A dry run found a session it would import.
+    WouldImport {
+        /// The session.
+        session: &'a Session,
+        /// Number of messages in the session.
+        messages: usize,
+    },
+    /// A watcher's files have all been processed, or processing stopped.
+    WatcherFinished {
+        /// Watcher name.
+        tool: &'static str,
+    },
+    /// A watcher's import failed. The import continues with the next
+    /// watcher.
+    WatcherFailed {
+        /// Watcher name.
+        tool: &'static str,
+        /// The error that stopped it.
+        error: &'a anyhow::Error,
+    },
+}
+
+/// Returns the default number of parse threads: the number of CPUs.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Imports sessions from the watchers named in `opts` into `db`.
+///
+/// Watchers are isolated from each other: an error in one, such as a locked
+/// database or a failing store, is recorded in [`ImportReport::failed`] and
+/// the import continues with the next watcher. Sessions stored before the
+/// failure are kept. A file that fails to parse is counted as an error and
+/// skipped.
+///
+/// # Example
+///
+/// ```no_run
+/// use lore_cli::capture::watchers::default_registry;
+/// use lore_cli::capture::{import_all, ImportOptions};
+/// use lore_cli::storage::Database;
+///
+/// let db = Database::open_default()?;
+/// let options = ImportOptions::new(vec!["claude-code".to_string()]);
+/// let report = import_all(&db, &default_registry(), options);
+/// println!("{} imported, {} skipped", report.imported, report.skipped);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[allow(dead_code)]
+pub fn import_all(db: &Database, registry: &WatcherRegistry, opts: ImportOptions) -> ImportReport {
+    import_all_with_progress(db, registry, opts, |_| {})
+}
+
+/// Imports like [`import_all`], passing each [`ImportEvent`] to `progress`
+/// as it happens.
+pub fn import_all_with_progress(
+    db: &Database,
+    registry: &WatcherRegistry,
+    opts: ImportOptions,
+    mut progress: impl FnMut(ImportEvent<'_>),
+) -> ImportReport {
+    let watchers = registry.enabled_watchers(&opts.watchers);
+    let mut repositories = RepositoryRecorder::with_remote_names(opts.remote_names.clone());
+    let mut report = ImportReport {
+        tools_attempted: watchers.len(),
+        ..Default::default()
+    };
+
+    for watcher in watchers {
+        let name = watcher.info().name;
+        progress(ImportEvent::WatcherStarted { tool: name });
+
+        let mut tool = ToolImportStats {
+            tool: name.to_string(),
+            ..Default::default()
+        };
+        let result = import_watcher(
+            db,
+            &opts,
+            &mut repositories,
+            watcher,
+            &mut tool,
+            &mut progress,
+        );
+        if let Err(e) = result {
+            tracing::warn!("Import from {name} failed: {e:#}");
+            progress(ImportEvent::WatcherFailed {
+                tool: name,
+                error: &e,
+            });
+            report.errors += 1;
+            report.failed.push((name.to_string(), format!("{e:#}")));
+        }
+
+        if tool.files == 0 {
+            continue;
+        }
+        if tool.imported > 0 {
+            report.tools_count += 1;
+        }
+        report.imported += tool.imported;
+        report.skipped += tool.skipped;
+        report.errors += tool.errors;
+        report.by_tool.push(tool);
+    }
+
+    report
+}
+
+/// Imports one watcher's sources, counting into `tool` as it goes.
+///
+/// A file that fails to parse is counted as an error in `tool` and skipped.
+/// Failing to list the sources or to store sessions returns an error.
+fn import_watcher(
+    db: &Database,
+    opts: &ImportOptions,
+    repositories: &mut RepositoryRecorder,
+    watcher: &dyn Watcher,
+    tool: &mut ToolImportStats,
+    progress: &mut dyn FnMut(ImportEvent<'_>),
+) -> Result<()> {
+    let name = watcher.info().name;
+    let sources = guard_panic(name, || watcher.find_sources()).context("Failed to find sources")?;
+
+    progress(ImportEvent::SourcesFound {
+        tool: name,
+        files: sources.len(),
+    });
+    if sources.is_empty() {
+        return Ok(());
+    }
+    tool.files = sources.len();
+    let total = sources.len();
+    let mut done = 0;
+
+    let mut pending = Vec::new();
+    for path in &sources {
+        let stamp = SourceStamp::of(path).ok();
+        if !opts.force && source_unchanged(db, &path.to_string_lossy(), stamp.as_ref())? {
+            if let (Some(stamp), false) = (&stamp, opts.dry_run) {
+                db.backfill_source_stamp(&path.to_string_lossy(), stamp)?;
+            }
+            tool.skipped += 1;
+            tracing::debug!("Skipping unchanged source: {}", path.display());
+            done += 1;
+            progress(ImportEvent::FileDone {
+                done,
+                total,
+                counts: tool,
+            });
+            continue;
+        }
+        pending.push((path, stamp));
+    }
+
+    let result = parse_in_parallel(
+        &pending,
+        opts.jobs,
+        |(path, _)| guard_panic(name, || watcher.parse_source(path)),
+        |(path, stamp), parsed| {
+            let path_str = path.to_string_lossy();
+            done += 1;
+
+            // A corrupt file is reported and skipped so it never aborts
+            // the rest of the batch.
+            let sessions = match parsed {
+                Ok(sessions) => sessions,
+                Err(e) => {
+                    tracing::debug!("Failed to parse {}: {}", path_str, e);
+                    progress(ImportEvent::Unreadable { path, error: &e });
+                    tool.errors += 1;
+                    progress(ImportEvent::FileDone {
+                        done,
+                        total,
+                        counts: tool,
+                    });
+                    return Ok(());
+                }
+            };
+
+            if sessions.is_empty() {
+                tracing::debug!("No sessions in source: {}", path_str);
+                tool.skipped += 1;
+            }
+            let ignored_before = tool.ignored.len();
+            store_sessions(
+                db,
+                opts.redactor.as_ref(),
+                &opts.ignore,
+                repositories,
+                sessions,
+                opts.dry_run,
+                tool,
+                progress,
+            )?;
+            // A file with ignored sessions is not stamped, so the sessions
+            // are picked up once the rule that skipped them is removed
+            if let (Some(stamp), false) = (stamp, opts.dry_run) {
+                if tool.ignored.len() == ignored_before {
+                    db.set_source_stamp(&path_str, stamp)?;
+                }
+            }
+
+            progress(ImportEvent::FileDone {
+                done,
+                total,
+                counts: tool,
+            });
+            Ok(())
+        },
+    );
+    progress(ImportEvent::WatcherFinished { tool: name });
+    result?.log(name, pending.len(), opts.jobs);
+    Ok(())
+}
+
+/// Runs a watcher call, turning a panic into an error so that one broken
+/// parser cannot take down the whole import.
+pub(crate) fn guard_panic<T>(name: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(anyhow!("{name} watcher panicked: {message}"))
+    })
+}
+
+/// Time spent parsing and storing during one import pass.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct ImportTiming {
+    /// Parse time summed across all worker threads.
+    parse: Duration,
+    /// Time spent storing results on the calling thread.
+    store: Duration,
+}
+
+impl ImportTiming {
+    /// Logs the timing at debug level, to show whether parsing or database
+    /// writes dominate an import.
+    pub(crate) fn log(&self, source: &str, files: usize, jobs: usize) {
+        if files > 0 {
+            tracing::debug!(
+                "{source}: parsed {files} files in {:?} (summed over up to {jobs} threads), stored in {:?}",
+                self.parse,
+                self.store
+            );
+        }
+    }
+}
+
+/// Parses `items` on up to `jobs` threads and stores each result on the
+/// calling thread, in the order of `items`.
+///
+/// Parsing reads and decodes files independently, so it runs in parallel.
+/// Storing writes to the database and stays on one thread, which avoids
+/// SQLite lock contention and keeps skip and duplicate handling identical to
+/// a serial import. A result that finishes early waits until every item
+/// before it is stored. Stops at the first error from `store`.
+pub(crate) fn parse_in_parallel<I, P>(
+    items: &[I],
+    jobs: usize,
+    parse: impl Fn(&I) -> P + Sync,
+    mut store: impl FnMut(&I, P) -> Result<()>,
+) -> Result<ImportTiming>
+where
+    I: Sync,
+    P: Send,
+{
+    let mut timing = ImportTiming::default();
+    let jobs = jobs.clamp(1, items.len().max(1));
+
+    if jobs == 1 {
+        for item in items {
+            let started = Instant::now();
+            let parsed = parse(item);
+            timing.parse += started.elapsed();
+
+            let started = Instant::now();
+            store(item, parsed)?;
+            timing.store += started.elapsed();
+        }
+        return Ok(timing);
+    }
+
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::sync_channel(jobs);
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (next, parse) = (&next, &parse);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let started = Instant::now();
+                let parsed = parse(item);
+                // The receiver is gone once storing has failed
+                if sender.send((index, started.elapsed(), parsed)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished = BTreeMap::new();
+        let mut stored = 0;
+        for (index, elapsed, parsed) in receiver {
+            timing.parse += elapsed;
+            finished.insert(index, parsed);
+            while let Some(parsed) = finished.remove(&stored) {
+                let started = Instant::now();
+                store(&items[stored], parsed)?;
+                timing.store += started.elapsed();
+                stored += 1;
+            }
+        }
+        Ok(timing)
+    })
+}
+
+/// Returns true if `path_str` was imported before with the same stamp.
+///
+/// A file whose stamp cannot be read is never considered unchanged.
+pub(crate) fn source_unchanged(
+    db: &Database,
+    path_str: &str,
+    stamp: Option<&SourceStamp>,
+) -> Result<bool> {
+    match stamp {
+        Some(stamp) => db.source_unchanged(path_str, stamp),
+        None => Ok(false),
+    }
+}
+
+/// Returns true if `session` was merged into another session or had others
+/// merged into it, so it must not be stored again.
+///
+/// A merge reindexes the messages of all sessions involved, so messages
+/// parsed from their sources can no longer be matched to stored ones. A
+/// warning is logged when the source has messages from after the merge,
+/// since those are not imported.
+pub(crate) fn skip_merged(db: &Database, session: &Session) -> Result<bool> {
+    let ended_at = session.ended_at.unwrap_or(session.started_at);
+    if let Some(merged_at) = db.merged_away_at(&session.id)? {
+        if ended_at > merged_at {
+            tracing::warn!(
+                "Session {} was merged into another session; messages added since are not imported",
+                session.id
+            );
+        } else {
+            tracing::debug!("Skipping session merged into another: {}", session.id);
+        }
+        return Ok(true);
+    }
+    if let Some(merged_at) = db.last_merged_into_at(&session.id)? {
+        if ended_at > merged_at {
+            tracing::warn!(
+                "Other sessions were merged into session {}; messages added since are not imported",
+                session.id
+            );
+        } else {
+            tracing::debug!(
+                "Skipping session with sessions merged into it: {}",
+                session.id
+            );
+        }
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+/// Sessions parsed from one source file, with their messages.
+pub(crate) type ParsedSessions = Vec<(Session, Vec<Message>)>;
+
+/// Stores parsed sessions, or reports them to `progress` when `dry_run` is
+/// set.
+///
+/// Sessions without messages are counted as skipped, as are sessions that
+/// were merged into another or pruned by retention, sessions that
+/// already have as many stored messages as were parsed, sessions whose
+/// content was already stored from another path or under another ID (see
+/// [`session_dedup_key`]), and sessions whose working directory is ignored
+/// by `ignore`. Source files only grow at the end, so for a session already
+/// stored, the first messages parsed are the ones it has and only the rest
+/// are inserted. Several watchers give messages a new random ID on every
+/// parse, so the ID alone cannot tell a stored message apart. Sessions that
+/// took part in a merge are not updated; see [`skip_merged`]. Messages are
+/// redacted before they are written when secret redaction is enabled, and
+/// each session's working directory is recorded in the repositories table.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn store_sessions(
+    db: &Database,
+    redactor: Option<&SecretRedactor>,
+    ignore: &LoreIgnore,
+    repositories: &mut RepositoryRecorder,
+    sessions: ParsedSessions,
+    dry_run: bool,
+    tool: &mut ToolImportStats,
+    progress: &mut dyn FnMut(ImportEvent<'_>),
+) -> Result<()> {
+    for (session, mut messages) in sessions {
+        if messages.is_empty() {
+            tracing::debug!("Skipping empty session: {}", session.id);
+            tool.skipped += 1;
+            continue;
+        }
+        if skip_merged(db, &session)? {
+            tool.skipped += 1;
+            continue;
+        }
+        if db.is_pruned_session(&session.id)? {
+            tracing::debug!("Skipping session pruned by retention: {}", session.id);
+            tool.skipped += 1;
+            continue;
+        }
+        if ignore.is_ignored(&session.working_directory) {
+            tracing::debug!(
+                "Skipping session {} in ignored directory {}",
+                session.id,
+                session.working_directory
+            );
+            tool.skipped += 1;
+            tool.ignored.push(IgnoredSession {
+                id: session.id,
+                working_directory: session.working_directory,
+            });
+            continue;
+        }
+
+        let dedup_key = session_dedup_key(&session, &messages);
+        let stored = match db.get_session(&session.id)? {
+            Some(_) => db.session_message_count(&session.id)?,
+            None if db.session_exists_by_key(&dedup_key)? => {
+                tracing::debug!(
+                    "Skipping session {} already imported from another path",
+                    session.id
+                );
+                tool.skipped += 1;
+                continue;
+            }
+            None => 0,
+        };
+        if stored > 0 && stored >= messages.len() {
+            tool.skipped += 1;
+            continue;
+        }
+        let mut messages = messages.split_off(stored);
+
+        if dry_run {
+            progress(ImportEvent::WouldImport {
+                session: &session,
+                messages: messages.len(),
+            });
+        } else {
+            if let Some(redactor) = redactor {
+                redactor.redact_messages(&mut messages);
+            }
+
+            db.insert_session(&session)?;
+            db.set_session_dedup_key(&session.id, &dedup_key)?;
+            repositories.record(db, &session)?;
+
+            for msg in &messages {
+                db.insert_message(msg)?;
+            }
+            db.recount_session_messages(&session.id)?;
+        }
+        tool.imported += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::watchers::default_registry;
+    use chrono::Utc;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_import_report_default() {
+        let report = ImportReport::default();
+        assert_eq!(report.imported, 0);
+        assert_eq!(report.skipped, 0);
+        assert_eq!(report.errors, 0);
+        assert_eq!(report.tools_count, 0);
+        assert!(report.by_tool.is_empty());
+        assert!(!report.all_failed());
+    }
+
+    #[test]
+    fn test_import_report_fields_are_accessible() {
+        let report = ImportReport {
+            imported: 10,
+            skipped: 5,
+            errors: 2,
+            tools_count: 3,
+            by_tool: Vec::new(),
+            tools_attempted: 4,
+            failed: Vec::new(),
+        };
+        assert_eq!(report.imported, 10);
+        assert_eq!(report.skipped, 5);
+        assert_eq!(report.errors, 2);
+        assert_eq!(report.tools_count, 3);
+    }
+
+    /// A watcher with fixed sources that parses them as Claude Code files,
+    /// or fails in the requested way.
+    struct StubWatcher {
+        name: &'static str,
+        sources: Option<Vec<PathBuf>>,
+        panic_on_parse: bool,
+        available: bool,
+    }
+
+    impl Watcher for StubWatcher {
+        fn info(&self) -> crate::capture::watchers::WatcherInfo {
+            crate::capture::watchers::WatcherInfo {
+                name: self.name,
+                description: "Test",
+                default_paths: vec![],
+            }
+        }
+        fn is_available(&self) -> bool {
+            self.available
+        }
+        fn find_sources(&self) -> Result<Vec<PathBuf>> {
+            self.sources.clone().context("database is locked")
+        }
+        fn parse_source(&self, path: &Path) -> Result<ParsedSessions> {
+            if self.panic_on_parse {
+                panic!("unexpected schema");
+            }
+            default_registry()
+                .get_watcher("claude-code")
+                .unwrap()
+                .parse_source(path)
+        }
+        fn watch_paths(&self) -> Vec<PathBuf> {
+            vec![]
+        }
+    }
+
+    fn stub(
+        name: &'static str,
+        sources: Option<Vec<PathBuf>>,
+        panic_on_parse: bool,
+    ) -> Box<StubWatcher> {
+        Box::new(StubWatcher {
+            name,
+            sources,
+            panic_on_parse,
+            available: true,
+        })
+    }
+
+    fn options(watchers: &[&str]) -> ImportOptions {
+        ImportOptions {
+            jobs: 2,
+            ..ImportOptions::new(watchers.iter().map(|s| s.to_string()).collect())
+        }
+    }
+
+    #[test]
+    fn test_failing_watcher_does_not_stop_the_others() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("lore.db")).unwrap();
+        let file = dir.path().join("session.jsonl");
+        fs::write(
+            &file,
+            concat!(
+                r#"{"type":"user","sessionId":"6f1b2c3d-0000-4000-8000-000000000001","uuid":"6f1b2c3d-0000-4000-8000-000000000002","timestamp":"2025-01-15T10:00:00.000Z","cwd":"/archive/project","message":{"role":"user","content":"hello"}}"#,
+                "\n"
+            ),
+        )
+        .unwrap();
+
+        let mut registry = WatcherRegistry::new();
+        registry.register(stub("locked", None, false));
+        registry.register(stub("panicking", Some(vec![file.clone()]), true));
+        registry.register(stub("working", Some(vec![file]), false));
+
+        let report = import_all(&db, &registry, options(&["locked", "panicking", "working"]));
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "locked");
+        assert!(report.failed[0].1.contains("database is locked"));
+        assert!(!report.all_failed());
+        // A panicking parser only costs its own file
+        assert_eq!(report.by_tool[0].tool, "panicking");
+        assert_eq!(report.by_tool[0].errors, 1);
+        assert_eq!(report.by_tool[1].tool, "working");
+        assert_eq!(report.by_tool[1].imported, 1);
+        assert_eq!(db.session_count().unwrap(), 1);
+
+        let report = import_all(&db, &registry, options(&["locked"]));
+        assert!(report.all_failed());
+    }
+
+    #[test]
+    fn test_import_all_skips_unchanged_files_and_reports_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("lore.db")).unwrap();
+        let file = dir.path().join("session.jsonl");
+        fs::write(
+            &file,
+            concat!(
+                r#"{"type":"user","sessionId":"6f1b2c3d-0000-4000-8000-000000000004","uuid":"6f1b2c3d-0000-4000-8000-000000000041","timestamp":"2025-01-15T10:00:00.000Z","cwd":"/archive/project","message":{"role":"user","content":"hello"}}"#,
+                "\n"
+            ),
+        )
+        .unwrap();
+
+        let mut registry = WatcherRegistry::new();
+        registry.register(stub("stub", Some(vec![file]), false));
+        registry.register(Box::new(StubWatcher {
+            name: "missing",
+            sources: Some(vec![]),
+            panic_on_parse: false,
+            available: false,
+        }));
+
+        let dry_run = ImportOptions {
+            dry_run: true,
+            ..options(&["stub", "missing", "unknown"])
+        };
+        let mut would_import = Vec::new();
+        let report = import_all_with_progress(&db, &registry, dry_run, |event| {
+            if let ImportEvent::WouldImport { session, messages } = event {
+                would_import.push((session.id.to_string(), messages));
+            }
+        });
+        // Unavailable and unknown watchers are not run
+        assert_eq!(report.tools_attempted, 1);
+        assert_eq!(report.imported, 1);
+        assert_eq!(
+            would_import,
+            vec![("6f1b2c3d-0000-4000-8000-000000000004".to_string(), 1)]
+        );
+        assert_eq!(db.session_count().unwrap(), 0);
+
+        let report = import_all(&db, &registry, options(&["stub"]));
+        assert_eq!((report.imported, report.skipped), (1, 0));
+        assert_eq!(db.session_count().unwrap(), 1);
+
+        let report = import_all(&db, &registry, options(&["stub"]));
+        assert_eq!((report.imported, report.skipped), (0, 1));
+
+        let forced = ImportOptions {
+            force: true,
+            ..options(&["stub"])
+        };
+        let report = import_all(&db, &registry, forced);
+        assert_eq!(report.by_tool[0].files, 1);
+        assert_eq!(db.session_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_pruned_session_is_not_imported_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("lore.db")).unwrap();
+        let file = dir.path().join("session.jsonl");
+        fs::write(
+            &file,
+            concat!(
+                r#"{"type":"user","sessionId":"6f1b2c3d-0000-4000-8000-000000000007","uuid":"6f1b2c3d-0000-4000-8000-000000000071","timestamp":"2025-01-15T10:00:00.000Z","cwd":"/archive/project","message":{"role":"user","content":"hello"}}"#,
+                "\n"
+            ),
+        )
+        .unwrap();
+
+        let mut registry = WatcherRegistry::new();
+        registry.register(stub("stub", Some(vec![file]), false));
+        let report = import_all(&db, &registry, options(&["stub"]));
+        assert_eq!(report.imported, 1);
+
+        // What retention pruning does to a session past the cutoff
+        let session = db.list_sessions(1, None).unwrap().remove(0);
+        db.delete_session(&session.id).unwrap();
+        db.record_pruned_session(&session.id, Utc::now()).unwrap();
+
+        let forced = ImportOptions {
+            force: true,
+            ..options(&["stub"])
+        };
+        let report = import_all(&db, &registry, forced);
+        assert_eq!((report.imported, report.skipped), (0, 1));
+        assert_eq!(db.session_count().unwrap(), 0);
+    }
+
+    /// Imports `file` the way `run_import` does, returning its counts.
+    fn import_file(db: &Database, watcher: &dyn Watcher, file: &Path) -> ToolImportStats {
+        let path_str = file.to_string_lossy();
+        let mut tool = ToolImportStats::default();
+        let stamp = SourceStamp::of(file).unwrap();
+        if source_unchanged(db, &path_str, Some(&stamp)).unwrap() {
+            db.backfill_source_stamp(&path_str, &stamp).unwrap();
+            tool.skipped += 1;
+            return tool;
+        }
+        store_sessions(
+            db,
+            None,
+            &LoreIgnore::new(None),
+            &mut RepositoryRecorder::default(),
+            watcher.parse_source(file).unwrap(),
+            false,
+            &mut tool,
+            &mut |_| {},
+        )
+        .unwrap();
+        db.set_source_stamp(&path_str, &stamp).unwrap();
+        tool
+    }
+
+    #[test]
+    fn test_grown_source_is_imported_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("lore.db")).unwrap();
+        let registry = default_registry();
+        let watcher = registry.get_watcher("claude-code").unwrap();
+        let file = dir.path().join("session.jsonl");
+        let path_str = file.to_string_lossy().to_string();
+        let line = |n: u32, role: &str| {
+            format!(
+                r#"{{"type":"{role}","sessionId":"6f1b2c3d-0000-4000-8000-000000000001","uuid":"6f1b2c3d-0000-4000-8000-00000000001{n}","timestamp":"2025-01-15T10:0{n}:00.000Z","cwd":"/archive/project","message":{{"role":"{role}","content":"message {n}"}}}}"#
+            ) + "\n"
+        };
+        let import = |db: &Database| import_file(db, watcher, &file);
+
+        fs::write(&file, line(1, "user") + &line(2, "assistant")).unwrap();
+        assert_eq!(import(&db).imported, 1);
+        assert_eq!(import(&db).skipped, 1);
+
+        // The session gains messages on a later day
+        let mut contents = fs::read_to_string(&file).unwrap();
+        contents += &line(3, "user");
+        contents += &line(4, "assistant");
+        fs::write(&file, contents).unwrap();
+
+        assert_eq!(import(&db).imported, 1);
+        let session = db.get_session_by_source(&path_str).unwrap().unwrap();
+        assert_eq!(session.message_count, 4);
+        assert_eq!(db.get_messages(&session.id).unwrap().len(), 4);
+        assert_eq!(
+            session.ended_at.unwrap().to_rfc3339(),
+            "2025-01-15T10:04:00+00:00"
+        );
+        assert_eq!(import(&db).skipped, 1);
+    }
+
+    #[test]
+    fn test_merged_sources_are_not_imported_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = Database::open(&dir.path().join("lore.db")).unwrap();
+        let registry = default_registry();
+        let watcher = registry.get_watcher("claude-code").unwrap();
+        let line = |session: u32, n: u32, role: &str| {
+            format!(
+                r#"{{"type":"{role}","sessionId":"6f1b2c3d-0000-4000-8000-00000000000{session}","uuid":"6f1b2c3d-0000-4000-8000-0000000000{session}{n}","timestamp":"2025-01-15T1{session}:0{n}:00.000Z","cwd":"/archive/project","message":{{"role":"{role}","content":"message {n}"}}}}"#
+            ) + "\n"
+        };
+        let files: Vec<PathBuf> = (1..=2)
+            .map(|session| {
+                let file = dir.path().join(format!("session-{session}.jsonl"));
+                fs::write(
+                    &file,
+                    line(session, 1, "user") + &line(session, 2, "assistant"),
+                )
+                .unwrap();
+                assert_eq!(import_file(&db, watcher, &file).imported, 1);
+                file
+            })
+            .collect();
+
+        let primary = db
+            .get_session_by_source(&files[0].to_string_lossy())
+            .unwrap()
+            .unwrap();
+        let other = db
+            .get_session_by_source(&files[1].to_string_lossy())
+            .unwrap()
+            .unwrap();
+        db.merge_sessions(&primary.id, &[other.id]).unwrap();
+
+        // Both sources gain messages, the primary's more than it had merged
+        // in, then are imported again
+        for (session, file) in (1..=2).zip(&files) {
+            let mut contents = fs::read_to_string(file).unwrap();
+            for n in 3..=5 {
+                contents += &line(session, n, "user");
+            }
+            fs::write(file, contents).unwrap();
+            assert_eq!(import_file(&db, watcher, file).skipped, 1);
+        }
+
+        let merged = db.get_session(&primary.id).unwrap().unwrap();
+        assert_eq!(merged.message_count, 4);
+        assert_eq!(db.session_message_count(&primary.id).unwrap(), 4);
+        assert!(db.get_session(&other.id).unwrap().is_none());
+        assert!(db.verify().unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_grown_codex_source_keeps_stored_messages() {
+        // Codex messages get a new random ID on every parse
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("lore.db")).unwrap();
+        let registry = default_registry();
+        let watcher = registry.get_watcher("codex").unwrap();
+        let file = dir.path().join("rollout-2025-12-18T22-53-29.jsonl");
+        let message = |n: u32, role: &str, kind: &str| {
+            format!(
+                r#"{{"timestamp":"2025-12-18T22:5{n}:00.000Z","type":"response_item","payload":{{"type":"message","role":"{role}","content":[{{"type":"{kind}","text":"message {n}"}}]}}}}"#
+            ) + "\n"
+        };
+        let meta = r#"{"timestamp":"2025-12-18T22:53:29.406Z","type":"session_meta","payload":{"id":"019b33ab-179f-7802-88a6-16557b4b7603","timestamp":"2025-12-18T22:53:29.377Z","cwd":"/archive/project","originator":"codex_cli_rs","cli_version":"0.63.0","model_provider":"openai"}}"#;
+
+        let mut contents = format!("{meta}\n")
+            + &message(4, "user", "input_text")
+            + &message(5, "assistant", "text");
+        fs::write(&file, &contents).unwrap();
+        assert_eq!(import_file(&db, watcher, &file).imported, 1);
+
+        contents += &message(6, "user", "input_text");
+        fs::write(&file, &contents).unwrap();
+        assert_eq!(import_file(&db, watcher, &file).imported, 1);
+
+        let session = db
+            .get_session_by_source(&file.to_string_lossy())
+            .unwrap()
+            .unwrap();
+        assert_eq!(db.session_message_count(&session.id).unwrap(), 3);
+        assert_eq!(session.message_count, 3);
+
+        // Touching the file without new content stores nothing
+        fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(import_file(&db, watcher, &file).skipped, 1);
+        assert_eq!(db.session_message_count(&session.id).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_moved_source_is_not_imported_twice() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("lore.db")).unwrap();
+        let registry = default_registry();
+        let watcher = registry.get_watcher("claude-code").unwrap();
+        let contents = concat!(
+            r#"{"type":"user","sessionId":"6f1b2c3d-0000-4000-8000-000000000003","uuid":"6f1b2c3d-0000-4000-8000-000000000031","timestamp":"2025-01-15T10:00:00.000Z","cwd":"/archive/project","message":{"role":"user","content":"hello"}}"#,
+            "\n",
+            r#"{"type":"assistant","sessionId":"6f1b2c3d-0000-4000-8000-000000000003","uuid":"6f1b2c3d-0000-4000-8000-000000000032","timestamp":"2025-01-15T10:01:00.000Z","cwd":"/archive/project","message":{"role":"assistant","content":"hi"}}"#,
+            "\n"
+        );
+        let old_path = dir.path().join("old/.claude/session.jsonl");
+        let new_path = dir.path().join("new/.claude/session.jsonl");
+        for path in [&old_path, &new_path] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        let import = |sessions: ParsedSessions| {
+            let mut tool = ToolImportStats::default();
+            store_sessions(
+                &db,
+                None,
+                &LoreIgnore::new(None),
+                &mut RepositoryRecorder::default(),
+                sessions,
+                false,
+                &mut tool,
+                &mut |_| {},
+            )
+            .unwrap();
+            tool
+        };
+
+        assert_eq!(import(watcher.parse_source(&old_path).unwrap()).imported, 1);
+
+        // The moved copy is skipped even when its parser assigns a new ID
+        let mut moved = watcher.parse_source(&new_path).unwrap();
+        let new_id = uuid::Uuid::new_v4();
+        for (session, messages) in &mut moved {
+            session.id = new_id;
+            for message in messages.iter_mut() {
+                message.id = uuid::Uuid::new_v4();
+                message.session_id = new_id;
+            }
+        }
+        let tool = import(moved);
+        assert_eq!((tool.imported, tool.skipped), (0, 1));
+        assert_eq!(db.session_count().unwrap(), 1);
+        assert!(db.get_session(&new_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_ignored_sessions_are_not_stored() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("lore.db")).unwrap();
+        let registry = default_registry();
+        let watcher = registry.get_watcher("claude-code").unwrap();
+        let file = dir.path().join("session.jsonl");
+        fs::write(
+            &file,
+            r#"{"type":"user","sessionId":"6f1b2c3d-0000-4000-8000-000000000002","uuid":"6f1b2c3d-0000-4000-8000-000000000021","timestamp":"2025-01-15T10:01:00.000Z","cwd":"/clients/acme","message":{"role":"user","content":"hello"}}"#,
+        )
+        .unwrap();
+
+        let ignore = LoreIgnore::new(Some(crate::capture::ignore::IgnoreFile::parse(
+            Path::new("/"),
+            "/clients/*\n",
+            None,
+        )));
+        let mut tool = ToolImportStats::default();
+        store_sessions(
+            &db,
+            None,
+            &ignore,
+            &mut RepositoryRecorder::default(),
+            watcher.parse_source(&file).unwrap(),
+            false,
+            &mut tool,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert_eq!((tool.imported, tool.skipped), (0, 1));
+        assert_eq!(tool.ignored.len(), 1);
+        assert_eq!(tool.ignored[0].working_directory, "/clients/acme");
+        assert_eq!(db.session_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_parse_in_parallel_stores_in_order_on_calling_thread() {
+        let items: Vec<u64> = (0..50).collect();
+        let caller = thread::current().id();
+
+        for jobs in [1, 4] {
+            let mut stored = Vec::new();
+            parse_in_parallel(
+                &items,
+                jobs,
+                |n| {
+                    // Later items finish first, so results arrive out of order
+                    thread::sleep(Duration::from_micros(50 - n));
+                    n * 2
+                },
+                |item, parsed| {
+                    assert_eq!(thread::current().id(), caller);
+                    stored.push((*item, parsed));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+            let expected: Vec<(u64, u64)> = items.iter().map(|n| (*n, n * 2)).collect();
+            assert_eq!(stored, expected, "jobs = {jobs}");
+        }
+    }
+
+    #[test]
+    fn test_parse_in_parallel_stops_at_store_error() {
+        let items: Vec<u32> = (0..100).collect();
+        let mut stored = 0;
+        let result = parse_in_parallel(
+            &items,
+            4,
+            |n| *n,
+            |_, n| {
+                if n == 10 {
+                    anyhow::bail!("disk full");
+                }
+                stored += 1;
+                Ok(())
+            },
+        );
+
+        assert_eq!(result.unwrap_err().to_string(), "disk full");
+        assert_eq!(stored, 10);
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! This module provides parsers for importing sessions from various AI
 //! coding assistants. Each tool has its own session format and storage
-//! location.
+//! location. [`import_all`] runs every enabled watcher and stores what they
+//! find, for the CLI and for programs using Lore as a library.
 //!
 //! # Supported Tools
 //!
@@ -12,8 +13,25 @@
 //!
 //! - GitHub Copilot - Will parse from Copilot's logs
 
+/// Importing sessions from the registered watchers into the database.
+pub mod import;
+
+/// `.loreignore` rules for working directories that are never captured.
+pub mod ignore;
+
 /// Read-only mirror of a coding tool's per-project memory store.
 pub mod memory;
 
+/// Secret redaction applied to messages before they are stored.
+pub mod redact;
+
+/// Repository records for imported sessions.
+pub mod repositories;
+
 /// Tool-specific session parsers.
 pub mod watchers;
+
+// `import_all` is the entry point for library users; the CLI reports
+// progress through `import_all_with_progress`.
+#[allow(unused_imports)]
+pub use import::{import_all, ImportOptions, ImportReport};
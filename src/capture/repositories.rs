@@ -0,0 +1,117 @@
+//! Repository records for imported sessions.
+//!
+//! Each imported session's working directory is upserted into the
+//! `repositories` table so repo-level queries have a row per directory,
+//! named after its last path component and carrying the URL of its
+//! preferred git remote when the directory is inside a git repository.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::git::{repo_info_with_remotes, DEFAULT_REMOTE_NAMES};
+use crate::storage::db::repository_name;
+use crate::storage::{Database, Session};
+
+/// Records the repository of each session passed to it.
+///
+/// Looks up the git remote only the first time a directory is seen, so a
+/// batch import does not open the same repository for every session.
+#[derive(Debug)]
+pub struct RepositoryRecorder {
+    seen: HashSet<String>,
+    remote_names: Vec<String>,
+}
+
+impl Default for RepositoryRecorder {
+    fn default() -> Self {
+        Self::with_remote_names(DEFAULT_REMOTE_NAMES.iter().map(|s| s.to_string()).collect())
+    }
+}
+
+impl RepositoryRecorder {
+    /// Creates a recorder that has seen no directories yet and reads the
+    /// remote URL from the first of `remote_names` each repository has, as
+    /// configured by `git_remotes`.
+    pub fn with_remote_names(remote_names: Vec<String>) -> Self {
+        Self {
+            seen: HashSet::new(),
+            remote_names,
+        }
+    }
+
+    /// Upserts the repository row for the session's working directory.
+    pub fn record(&mut self, db: &Database, session: &Session) -> Result<()> {
+        let path = &session.working_directory;
+        let remote_url = if self.seen.insert(path.clone()) {
+            repo_info_with_remotes(Path::new(path), &self.remote_names)
+                .ok()
+                .and_then(|info| info.remote_url)
+        } else {
+            None
+        };
+        db.upsert_repository(
+            path,
+            &repository_name(path),
+            remote_url.as_deref(),
+            session.started_at,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn session(working_directory: &str) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at: Utc::now(),
+            ended_at: None,
+            model: None,
+            working_directory: working_directory.to_string(),
+            git_branch: None,
+            source_path: None,
+            message_count: 0,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_record_derives_name_and_remote() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        let repo_dir = dir.path().join("project");
+        let plain_dir = dir.path().join("notes");
+        std::fs::create_dir_all(&plain_dir).unwrap();
+        let repo = git2::Repository::init(&repo_dir).unwrap();
+        repo.remote("origin", "https://github.com/varalys/project.git")
+            .unwrap();
+
+        let mut recorder = RepositoryRecorder::default();
+        for path in [&repo_dir, &repo_dir, &plain_dir] {
+            recorder
+                .record(&db, &session(&path.to_string_lossy()))
+                .unwrap();
+        }
+
+        let mut repos = db.list_repositories().unwrap();
+        repos.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0].name, "notes");
+        assert_eq!(repos[0].remote_url, None);
+        assert_eq!(repos[1].name, "project");
+        assert_eq!(
+            repos[1].remote_url.as_deref(),
+            Some("https://github.com/varalys/project.git")
+        );
+    }
+}
@@ -5,7 +5,9 @@
 //!
 //! The format uses level 4 headings (`####`) for user messages, with assistant
 //! responses following as regular markdown text. Tool outputs are prefixed with
-//! `>` blockquotes.
+//! `>` blockquotes. Each run of aider appends a `# aider chat started at`
+//! heading to the file, so one history file holds many sessions; every run
+//! is imported as its own session.
 //!
 //! By default, Aider stores history in the project's root directory as
 //! `.aider.chat.history.md`. Users can configure a different location using
@@ -13,7 +15,7 @@
 //! variable.
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
 use std::fs;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
@@ -58,6 +60,20 @@ impl Watcher for AiderWatcher {
         find_aider_history_files()
     }
 
+    fn session_count(&self) -> Result<usize> {
+        // A history file holds one session per chat run
+        Ok(find_aider_history_files()?
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .map(|content| {
+                split_runs(&content)
+                    .iter()
+                    .filter(|r| r.has_content())
+                    .count()
+            })
+            .sum())
+    }
+
     fn parse_source(&self, path: &Path) -> Result<Vec<(Session, Vec<Message>)>> {
         let parsed = parse_aider_history(path)?;
         if parsed.is_empty() {
@@ -242,15 +258,71 @@ fn scan_directory_recursive<F>(
     }
 }
 
+/// Heading aider writes at the start of each chat run.
+const RUN_HEADING: &str = "# aider chat started at ";
+
+/// One aider chat run: the lines between two run headings.
+struct ChatRun<'a> {
+    /// Start time recorded in the run heading, if there is one.
+    started_at: Option<DateTime<Utc>>,
+    lines: Vec<&'a str>,
+}
+
+impl ChatRun<'_> {
+    /// Returns true if the run has any text besides its heading. Aider
+    /// writes a heading even when it exits without a chat.
+    fn has_content(&self) -> bool {
+        self.lines.iter().any(|line| !line.trim().is_empty())
+    }
+}
+
+/// Splits a history file into chat runs at their headings.
+///
+/// Content before the first heading, from files written by older aider
+/// versions, is a run without a start time; it is left out when empty.
+fn split_runs(content: &str) -> Vec<ChatRun<'_>> {
+    let mut runs = Vec::new();
+    let mut current = ChatRun {
+        started_at: None,
+        lines: Vec::new(),
+    };
+    for line in content.lines() {
+        if let Some(time) = line.strip_prefix(RUN_HEADING) {
+            let next = ChatRun {
+                started_at: parse_run_time(time),
+                lines: Vec::new(),
+            };
+            runs.push(std::mem::replace(&mut current, next));
+        } else {
+            current.lines.push(line);
+        }
+    }
+    runs.push(current);
+    if runs[0].started_at.is_none() && !runs[0].has_content() {
+        runs.remove(0);
+    }
+    runs
+}
+
+/// Parses the local time in a run heading, such as `2024-01-15 10:30:45`.
+fn parse_run_time(text: &str) -> Option<DateTime<Utc>> {
+    let naive = NaiveDateTime::parse_from_str(text.trim(), "%Y-%m-%d %H:%M:%S").ok()?;
+    Local
+        .from_local_datetime(&naive)
+        .single()
+        .map(|time| time.with_timezone(&Utc))
+}
+
 /// Parses an Aider chat history markdown file.
 ///
 /// The format consists of:
+/// - `# aider chat started at` headings starting each chat run
 /// - `####` headings for user messages
 /// - Regular text for assistant responses
 /// - `>` blockquotes for tool output
 ///
-/// Each contiguous conversation (no blank lines between user/assistant) is
-/// treated as a session.
+/// Each chat run is returned as a session. A run ends when the next one
+/// starts, and the last run when the file was last modified.
 fn parse_aider_history(path: &Path) -> Result<Vec<(Session, Vec<Message>)>> {
     let content = fs::read_to_string(path).context("Failed to read Aider history file")?;
 
@@ -258,14 +330,44 @@ fn parse_aider_history(path: &Path) -> Result<Vec<(Session, Vec<Message>)>> {
         .parent()
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_else(|| ".".to_string());
+    let modified = fs::metadata(path)
+        .ok()
+        .and_then(|m| m.modified().ok())
+        .map(DateTime::<Utc>::from);
 
+    let runs = split_runs(&content);
     let mut sessions = Vec::new();
+    for (i, run) in runs.iter().enumerate() {
+        let parsed = parse_messages(&run.lines);
+        if parsed.is_empty() {
+            continue;
+        }
+        let ended_at = match runs.get(i + 1) {
+            Some(next) => next.started_at,
+            None => modified,
+        };
+        let session = create_session(
+            path,
+            &working_directory,
+            parsed.len(),
+            run.started_at,
+            ended_at,
+        );
+        let messages = create_messages(&session, &parsed);
+        sessions.push((session, messages));
+    }
+
+    Ok(sessions)
+}
+
+/// Parses the messages of one chat run.
+fn parse_messages(lines: &[&str]) -> Vec<ParsedMessage> {
     let mut current_messages: Vec<ParsedMessage> = Vec::new();
     let mut current_role: Option<MessageRole> = None;
     let mut current_content = String::new();
     let mut in_tool_output = false;
 
-    for line in content.lines() {
+    for &line in lines {
         // User message starts with ####
         if line.starts_with("#### ") {
             // Save any pending content
@@ -366,14 +468,7 @@ fn parse_aider_history(path: &Path) -> Result<Vec<(Session, Vec<Message>)>> {
         }
     }
 
-    // Convert parsed messages to session
-    if !current_messages.is_empty() {
-        let session = create_session(path, &working_directory, current_messages.len());
-        let messages = create_messages(&session, &current_messages);
-        sessions.push((session, messages));
-    }
-
-    Ok(sessions)
+    current_messages
 }
 
 /// A parsed message from Aider history.
@@ -382,17 +477,19 @@ struct ParsedMessage {
     content: String,
 }
 
-/// Creates a Session from parsed Aider history.
-fn create_session(path: &Path, working_directory: &str, message_count: usize) -> Session {
-    // Use file modification time as session end time
-    let ended_at = fs::metadata(path)
-        .ok()
-        .and_then(|m| m.modified().ok())
-        .map(DateTime::<Utc>::from);
-
-    // Estimate start time as a bit before end time based on message count
-    let started_at = ended_at
-        .map(|t| t - chrono::Duration::minutes(message_count as i64 * 2))
+/// Creates a Session from a parsed Aider chat run.
+///
+/// Without a start time from the run heading, the start is estimated as a
+/// bit before the end based on the message count.
+fn create_session(
+    path: &Path,
+    working_directory: &str,
+    message_count: usize,
+    started_at: Option<DateTime<Utc>>,
+    ended_at: Option<DateTime<Utc>>,
+) -> Session {
+    let started_at = started_at
+        .or_else(|| ended_at.map(|t| t - chrono::Duration::minutes(message_count as i64 * 2)))
         .unwrap_or_else(Utc::now);
 
     Session {
@@ -407,6 +504,9 @@ fn create_session(path: &Path, working_directory: &str, message_count: usize) ->
         source_path: Some(path.to_string_lossy().to_string()),
         message_count: message_count as i32,
         machine_id: crate::storage::get_machine_id(),
+        input_tokens: None,
+        output_tokens: None,
+        parent_session_id: None,
     }
 }
 
@@ -564,6 +664,63 @@ Third answer
         assert!(session.ended_at.is_some());
     }
 
+    #[test]
+    fn test_parse_one_session_per_chat_run() {
+        let content = "\
+# aider chat started at 2024-01-15 10:30:45
+
+> Aider v0.50.0
+
+#### Add a test
+
+Added one.
+
+# aider chat started at 2024-01-15 14:00:00
+
+# aider chat started at 2024-01-16 09:15:00
+
+#### Fix the build
+
+Fixed.
+";
+
+        let file = create_temp_history_file(content);
+        let result = parse_aider_history(file.path()).expect("Should parse");
+
+        // The empty middle run is left out
+        assert_eq!(result.len(), 2);
+        let (first, first_messages) = &result[0];
+        let (second, second_messages) = &result[1];
+        assert_eq!(
+            first.started_at,
+            parse_run_time("2024-01-15 10:30:45").unwrap()
+        );
+        assert_eq!(first.ended_at, parse_run_time("2024-01-15 14:00:00"));
+        assert_eq!(
+            second.started_at,
+            parse_run_time("2024-01-16 09:15:00").unwrap()
+        );
+        assert!(second.ended_at.is_some());
+        assert_ne!(first.id, second.id);
+        assert_eq!(first_messages[1].content.text(), "Add a test");
+        assert_eq!(second_messages[0].content.text(), "Fix the build");
+    }
+
+    #[test]
+    fn test_split_runs_at_headings() {
+        let content = "#### Before any heading\n\
+                       # aider chat started at 2024-01-15 10:30:45\n\
+                       \n\
+                       # aider chat started at 2024-01-15 11:00:00\n\
+                       #### Hello\n";
+        let runs = split_runs(content);
+        assert_eq!(runs.len(), 3);
+        assert!(runs[0].started_at.is_none());
+        assert!(!runs[1].has_content());
+        assert_eq!(runs[2].started_at, parse_run_time("2024-01-15 11:00:00"));
+        assert!(split_runs("").is_empty());
+    }
+
     #[test]
     fn test_find_aider_history_files_returns_ok() {
         // Should not error even if no files exist
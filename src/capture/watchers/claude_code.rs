@@ -4,13 +4,22 @@
 //! Session files are stored in `~/.claude/projects/<project-hash>/<session-uuid>.jsonl`.
 //!
 //! Each line in a JSONL file represents a message or system event. This parser
-//! extracts user and assistant messages while skipping file history snapshots
-//! and sidechain (agent) messages.
+//! extracts user and assistant messages while skipping file history snapshots.
+//! Token usage reported on assistant messages is summed into the session's
+//! input and output token counts.
+//!
+//! Subagents (sidechains) spawned by a session are written to their own
+//! `agent-<id>.jsonl` files, either next to the session file or under
+//! `<session-uuid>/subagents/`. Every line of such a file is marked
+//! `isSidechain` and carries the parent's `sessionId`, so a subagent file is
+//! imported as its own session whose `parent_session_id` is that parent.
+//! Sidechain lines found inside a main session file are skipped.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
@@ -93,7 +102,6 @@ struct RawMessage {
 
     // For agent/sidechain messages
     #[serde(default)]
-    #[allow(dead_code)]
     agent_id: Option<String>,
 
     #[serde(default)]
@@ -109,6 +117,27 @@ struct RawMessageContent {
     model: Option<String>,
 
     content: RawContent,
+
+    /// API message ID; streamed content blocks of one response share it.
+    #[serde(default)]
+    id: Option<String>,
+
+    #[serde(default)]
+    usage: Option<RawUsage>,
+}
+
+/// Token usage reported by the API for one assistant response.
+#[derive(Debug, Default, Deserialize)]
+struct RawUsage {
+    #[serde(default)]
+    input_tokens: i64,
+    /// Prompt tokens written to the cache, billed at about the input rate.
+    /// Cache reads (`cache_read_input_tokens`), billed at about a tenth of
+    /// the input rate, are not read.
+    #[serde(default)]
+    cache_creation_input_tokens: i64,
+    #[serde(default)]
+    output_tokens: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -146,7 +175,10 @@ enum RawContentBlock {
 /// Parses a Claude Code JSONL session file.
 ///
 /// Reads each line of the file and extracts user and assistant messages.
-/// Skips file history snapshots, sidechain messages, and malformed lines.
+/// Skips file history snapshots and malformed lines. A file holding only
+/// sidechain messages is a subagent session: it gets an ID derived from the
+/// parent session and agent ID, and records the parent. In any other file,
+/// sidechain messages are skipped.
 ///
 /// # Errors
 ///
@@ -156,12 +188,16 @@ pub fn parse_session_file(path: &Path) -> Result<ParsedSession> {
     let file = File::open(path).context("Failed to open session file")?;
     let reader = BufReader::new(file);
 
+    let mut raws: Vec<RawMessage> = Vec::new();
     let mut messages: Vec<ParsedMessage> = Vec::new();
     let mut session_id: Option<String> = None;
     let mut tool_version: Option<String> = None;
     let mut cwd: Option<String> = None;
     let mut git_branch: Option<String> = None;
     let mut model: Option<String> = None;
+    let mut input_tokens: Option<i64> = None;
+    let mut output_tokens: Option<i64> = None;
+    let mut usage_message_ids: HashSet<String> = HashSet::new();
 
     for (line_num, line) in reader.lines().enumerate() {
         let line = line.context(format!("Failed to read line {}", line_num + 1))?;
@@ -184,10 +220,21 @@ pub fn parse_session_file(path: &Path) -> Result<ParsedSession> {
             continue;
         }
 
-        // Skip sidechain/agent messages for now (they're in separate files anyway)
-        if raw.is_sidechain.unwrap_or(false) {
+        raws.push(raw);
+    }
+
+    let subagent = !raws.is_empty() && raws.iter().all(|raw| raw.is_sidechain.unwrap_or(false));
+    let mut agent_id: Option<String> = None;
+
+    for raw in raws {
+        // Sidechain messages in a main session file belong to a subagent
+        // file of their own
+        if raw.is_sidechain.unwrap_or(false) != subagent {
             continue;
         }
+        if agent_id.is_none() {
+            agent_id = raw.agent_id.clone();
+        }
 
         // Extract session metadata from first message
         if session_id.is_none() {
@@ -210,8 +257,27 @@ pub fn parse_session_file(path: &Path) -> Result<ParsedSession> {
                 model = msg_content.model.clone();
             }
 
+            // Each content block of a streamed response is written as its own
+            // line repeating the response's usage, so count each ID once
+            if let Some(usage) = &msg_content.usage {
+                let first_seen = match &msg_content.id {
+                    Some(id) => usage_message_ids.insert(id.clone()),
+                    None => true,
+                };
+                if first_seen {
+                    // Cache reads are left out: priced at the input rate
+                    // they would overstate the cost of cache-heavy sessions
+                    let input = usage.input_tokens + usage.cache_creation_input_tokens;
+                    *input_tokens.get_or_insert(0) += input;
+                    *output_tokens.get_or_insert(0) += usage.output_tokens;
+                }
+            }
+
             let content = parse_content(&msg_content.content);
             let role = match msg_content.role.as_str() {
+                // Claude Code reports tool output as a user turn holding only
+                // tool_result blocks.
+                "user" if is_tool_result_only(&content) => MessageRole::Tool,
                 "user" => MessageRole::User,
                 "assistant" => MessageRole::Assistant,
                 "system" => MessageRole::System,
@@ -235,23 +301,61 @@ pub fn parse_session_file(path: &Path) -> Result<ParsedSession> {
         }
     }
 
+    let file_stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    // A subagent's lines carry the parent's session ID
+    let (session_id, parent_session_id) = match session_id {
+        Some(parent) if subagent => {
+            let agent = agent_id.unwrap_or_else(|| file_stem.clone());
+            (
+                subagent_session_id(&parent, &agent).to_string(),
+                Some(parent),
+            )
+        }
+        // Try to get from filename
+        id => (id.unwrap_or(file_stem), None),
+    };
+
     Ok(ParsedSession {
-        session_id: session_id.unwrap_or_else(|| {
-            // Try to get from filename
-            path.file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("unknown")
-                .to_string()
-        }),
+        session_id,
+        parent_session_id,
         tool_version,
         cwd: cwd.unwrap_or_else(|| ".".to_string()),
         git_branch,
         model,
+        input_tokens,
+        output_tokens,
         messages,
         source_path: path.to_string_lossy().to_string(),
     })
 }
 
+/// Derives a stable session ID for a subagent from its parent session ID and
+/// agent ID, so re-importing the subagent file updates the same session.
+fn subagent_session_id(parent_session_id: &str, agent_id: &str) -> Uuid {
+    let digest = Sha256::digest(format!("{parent_session_id}/{agent_id}"));
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+/// Returns true if the content is a non-empty list of tool_result blocks.
+fn is_tool_result_only(content: &MessageContent) -> bool {
+    match content {
+        MessageContent::Blocks(blocks) => {
+            !blocks.is_empty()
+                && blocks
+                    .iter()
+                    .all(|b| matches!(b, ContentBlock::ToolResult { .. }))
+        }
+        MessageContent::Text(_) => false,
+    }
+}
+
 fn parse_content(raw: &RawContent) -> MessageContent {
     match raw {
         RawContent::Text(s) => MessageContent::Text(s.clone()),
@@ -292,10 +396,14 @@ fn parse_content(raw: &RawContent) -> MessageContent {
 #[derive(Debug)]
 pub struct ParsedSession {
     pub session_id: String,
+    /// The session that spawned this one, for subagent files.
+    pub parent_session_id: Option<String>,
     pub tool_version: Option<String>,
     pub cwd: String,
     pub git_branch: Option<String>,
     pub model: Option<String>,
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
     pub messages: Vec<ParsedMessage>,
     pub source_path: String,
 }
@@ -329,6 +437,12 @@ impl ParsedSession {
             source_path: Some(self.source_path.clone()),
             message_count: self.messages.len() as i32,
             machine_id: crate::storage::get_machine_id(),
+            input_tokens: self.input_tokens,
+            output_tokens: self.output_tokens,
+            parent_session_id: self
+                .parent_session_id
+                .as_deref()
+                .and_then(|id| Uuid::parse_str(id).ok()),
         };
 
         // Build UUID map for parent lookups
@@ -389,9 +503,9 @@ pub struct ParsedMessage {
 
 /// Discovers all Claude Code session files in `~/.claude/projects/`.
 ///
-/// Scans project directories for UUID-named JSONL files, excluding
-/// agent sidechain files. Returns an empty vector if the Claude
-/// directory does not exist.
+/// Scans project directories for UUID-named JSONL files and `agent-*.jsonl`
+/// subagent files, including those under `<session-uuid>/subagents/`.
+/// Returns an empty vector if the Claude directory does not exist.
 pub fn find_session_files() -> Result<Vec<PathBuf>> {
     let claude_dir = claude_projects_dir();
 
@@ -406,21 +520,27 @@ pub fn find_session_files() -> Result<Vec<PathBuf>> {
         let path = entry.path();
 
         if path.is_dir() {
-            // Look for UUID-named JSONL files (not agent-* files for now)
             for file_entry in std::fs::read_dir(&path)? {
-                let file_entry = file_entry?;
-                let file_path = file_entry.path();
-
-                if let Some(name) = file_path.file_name().and_then(|n| n.to_str()) {
-                    // Skip agent files and non-jsonl files
-                    if name.starts_with("agent-") {
-                        continue;
-                    }
-                    if !name.ends_with(".jsonl") {
-                        continue;
+                let file_path = file_entry?.path();
+
+                if file_path.is_dir() {
+                    let subagents = file_path.join("subagents");
+                    if subagents.is_dir() {
+                        for agent_entry in std::fs::read_dir(&subagents)? {
+                            let agent_path = agent_entry?.path();
+                            if is_agent_file(&agent_path) {
+                                files.push(agent_path);
+                            }
+                        }
                     }
+                    continue;
+                }
+
+                if is_agent_file(&file_path) {
+                    files.push(file_path);
+                } else if let Some(name) = file_path.file_name().and_then(|n| n.to_str()) {
                     // Check if it looks like a UUID
-                    if name.len() > 40 {
+                    if name.ends_with(".jsonl") && name.len() > 40 {
                         files.push(file_path);
                     }
                 }
@@ -431,6 +551,13 @@ pub fn find_session_files() -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
+/// Returns true if `path` is a subagent session file, `agent-<id>.jsonl`.
+fn is_agent_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| name.starts_with("agent-") && name.ends_with(".jsonl"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -686,6 +813,47 @@ mod tests {
         assert_eq!(parsed.messages[0].uuid, user_uuid);
     }
 
+    /// A subagent file: every line is a sidechain carrying the parent's
+    /// session ID and the agent's ID.
+    const SUBAGENT_FIXTURE: &str = r#"{"type":"user","sessionId":"550e8400-e29b-41d4-a716-446655440000","uuid":"990e8400-e29b-41d4-a716-446655440001","timestamp":"2025-01-15T10:02:00.000Z","cwd":"/test/project","gitBranch":"main","version":"2.0.72","isSidechain":true,"agentId":"a1b2c3d4","message":{"role":"user","content":"Find the config loader"}}
+{"type":"assistant","sessionId":"550e8400-e29b-41d4-a716-446655440000","uuid":"990e8400-e29b-41d4-a716-446655440002","parentUuid":"990e8400-e29b-41d4-a716-446655440001","timestamp":"2025-01-15T10:03:00.000Z","cwd":"/test/project","gitBranch":"main","isSidechain":true,"agentId":"a1b2c3d4","message":{"role":"assistant","model":"claude-haiku-4","content":"It is in src/config/mod.rs"}}
+"#;
+
+    #[test]
+    fn test_subagent_file_links_to_parent_session() {
+        let parent_id = "550e8400-e29b-41d4-a716-446655440000";
+        let parent_line = make_user_message(
+            parent_id,
+            "660e8400-e29b-41d4-a716-446655440001",
+            None,
+            "Hello",
+        );
+        let parent_file = create_temp_session_file(&[&parent_line]);
+        let agent_lines: Vec<&str> = SUBAGENT_FIXTURE.lines().collect();
+        let agent_file = create_temp_session_file(&agent_lines);
+
+        let (parent, _) = parse_session_file(parent_file.path())
+            .unwrap()
+            .to_storage_models();
+        let parsed = parse_session_file(agent_file.path()).unwrap();
+        let (subagent, messages) = parsed.to_storage_models();
+
+        // The subagent keeps its own messages and is importable standalone,
+        // under an ID of its own that points back at the parent
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].parent_id, Some(messages[0].id));
+        assert_eq!(subagent.model, Some("claude-haiku-4".to_string()));
+        assert_ne!(subagent.id, parent.id);
+        assert_eq!(subagent.parent_session_id, Some(parent.id));
+        assert_eq!(parent.parent_session_id, None);
+
+        // Re-importing the file yields the same session ID
+        let (again, _) = parse_session_file(agent_file.path())
+            .unwrap()
+            .to_storage_models();
+        assert_eq!(again.id, subagent.id);
+    }
+
     // =========================================================================
     // Unit tests for message type parsing
     // =========================================================================
@@ -784,6 +952,8 @@ mod tests {
         let parsed = parse_session_file(file.path()).expect("Failed to parse");
 
         assert_eq!(parsed.messages.len(), 1);
+        // A user turn holding only tool output is classified as a tool turn
+        assert_eq!(parsed.messages[0].role, MessageRole::Tool);
         if let MessageContent::Blocks(blocks) = &parsed.messages[0].content {
             assert_eq!(blocks.len(), 1);
 
@@ -863,6 +1033,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_token_usage_summed_once_per_response() {
+        let session_id = "550e8400-e29b-41d4-a716-446655440000";
+        let usage_line = |uuid: &str, msg_id: &str, text: &str| {
+            format!(
+                r#"{{"type":"assistant","sessionId":"{session_id}","uuid":"{uuid}","timestamp":"2025-01-15T10:01:00.000Z","message":{{"id":"{msg_id}","role":"assistant","model":"claude-opus-4","content":"{text}","usage":{{"input_tokens":10,"cache_creation_input_tokens":100,"cache_read_input_tokens":1000,"output_tokens":50}}}}}}"#
+            )
+        };
+        let user_line = make_user_message(session_id, "u1", None, "Hello");
+        // Two lines of one streamed response repeat its usage
+        let first = usage_line("a1", "msg_1", "Part one");
+        let second = usage_line("a2", "msg_1", "Part two");
+        let third = usage_line("a3", "msg_2", "Another reply");
+
+        let file = create_temp_session_file(&[&user_line, &first, &second, &third]);
+        let (session, _) = parse_session_file(file.path())
+            .expect("Failed to parse")
+            .to_storage_models();
+
+        // Cache reads are not counted as input
+        assert_eq!(session.input_tokens, Some(2 * 110));
+        assert_eq!(session.output_tokens, Some(2 * 50));
+
+        // claude-opus-4 is $15 input and $75 output per million tokens
+        let cost = crate::config::Config::default()
+            .estimate_cost(
+                session.model.as_deref(),
+                session.input_tokens.unwrap(),
+                session.output_tokens.unwrap(),
+            )
+            .expect("opus has a rate");
+        assert!((cost - (220.0 * 15.0 + 100.0 * 75.0) / 1_000_000.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_token_usage_absent_is_none() {
+        let session_id = "550e8400-e29b-41d4-a716-446655440000";
+        let user_line = make_user_message(session_id, "u1", None, "Hello");
+        let assistant_line =
+            make_assistant_message(session_id, "a1", Some("u1"), "claude-opus-4", "Hi");
+
+        let file = create_temp_session_file(&[&user_line, &assistant_line]);
+        let (session, _) = parse_session_file(file.path())
+            .expect("Failed to parse")
+            .to_storage_models();
+
+        assert_eq!(session.input_tokens, None);
+        assert_eq!(session.output_tokens, None);
+    }
+
     // =========================================================================
     // Unit tests for session file discovery
     // =========================================================================
@@ -5,7 +5,9 @@
 //!
 //! Each line in a JSONL file has a `type` field:
 //! - `session_meta`: Contains session metadata (id, cwd, model, git info)
-//! - `response_item`: Contains messages with role and content
+//! - `response_item`: Contains messages with role and content, and the
+//!   model's reasoning, which is attached to the next assistant message as
+//!   a thinking block
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -15,7 +17,7 @@ use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::storage::models::{Message, MessageContent, MessageRole, Session};
+use crate::storage::models::{ContentBlock, Message, MessageContent, MessageRole, Session};
 
 use super::{Watcher, WatcherInfo};
 
@@ -104,8 +106,26 @@ struct RawResponseItem {
     #[serde(rename = "type")]
     item_type: Option<String>,
     role: Option<String>,
+    /// Message text, or reasoning text for `reasoning` items. Reasoning items
+    /// often send `null` here.
     #[serde(default)]
-    content: Vec<RawContentItem>,
+    content: Option<Vec<RawContentItem>>,
+    /// Reasoning summary for `reasoning` items.
+    #[serde(default)]
+    summary: Option<Vec<RawContentItem>>,
+}
+
+impl RawResponseItem {
+    /// Returns the text of the content items of the given types, one per line.
+    fn text_of(items: &Option<Vec<RawContentItem>>, types: &[&str]) -> String {
+        items
+            .iter()
+            .flatten()
+            .filter(|c| types.contains(&c.content_type.as_str()))
+            .filter_map(|c| c.text.clone())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 /// Raw content item within a response.
@@ -135,6 +155,8 @@ pub fn parse_codex_session_file(path: &Path) -> Result<ParsedCodexSession> {
     let mut git_branch: Option<String> = None;
     let mut model_provider: Option<String> = None;
     let mut messages: Vec<ParsedCodexMessage> = Vec::new();
+    // Reasoning seen since the last assistant message, in order
+    let mut pending_thinking: Vec<String> = Vec::new();
 
     for (line_num, line) in reader.lines().enumerate() {
         let line = match line {
@@ -182,9 +204,24 @@ pub fn parse_codex_session_file(path: &Path) -> Result<ParsedCodexSession> {
             "response_item" => {
                 if let Some(payload) = entry.payload {
                     if let Ok(item) = serde_json::from_value::<RawResponseItem>(payload) {
-                        // Only process message types
-                        if item.item_type.as_deref() != Some("message") {
-                            continue;
+                        match item.item_type.as_deref() {
+                            Some("message") => {}
+                            Some("reasoning") => {
+                                // Prefer the full reasoning text when the CLI
+                                // recorded it; otherwise keep the summary
+                                let mut thinking =
+                                    RawResponseItem::text_of(&item.content, &["reasoning_text"]);
+                                if thinking.trim().is_empty() {
+                                    thinking =
+                                        RawResponseItem::text_of(&item.summary, &["summary_text"]);
+                                }
+                                if !thinking.trim().is_empty() {
+                                    pending_thinking.push(thinking);
+                                }
+                                continue;
+                            }
+                            // Tool calls and other item types are not captured
+                            _ => continue,
                         }
 
                         let role = match item.role.as_deref() {
@@ -194,24 +231,26 @@ pub fn parse_codex_session_file(path: &Path) -> Result<ParsedCodexSession> {
                             _ => continue,
                         };
 
-                        // Extract text content from content array
-                        let text: String = item
-                            .content
-                            .iter()
-                            .filter_map(|c| {
-                                if c.content_type == "input_text" || c.content_type == "text" {
-                                    c.text.clone()
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect::<Vec<_>>()
-                            .join("\n");
+                        let text = RawResponseItem::text_of(
+                            &item.content,
+                            &["input_text", "text", "output_text"],
+                        );
 
                         if text.trim().is_empty() {
                             continue;
                         }
 
+                        // Reasoning belongs to the assistant reply that follows
+                        // it; a new user turn means the reply never came
+                        let thinking = match role {
+                            MessageRole::Assistant => std::mem::take(&mut pending_thinking),
+                            MessageRole::User => {
+                                pending_thinking.clear();
+                                Vec::new()
+                            }
+                            _ => Vec::new(),
+                        };
+
                         let timestamp = DateTime::parse_from_rfc3339(&entry.timestamp)
                             .map(|t| t.with_timezone(&Utc))
                             .unwrap_or_else(|_| Utc::now());
@@ -220,6 +259,7 @@ pub fn parse_codex_session_file(path: &Path) -> Result<ParsedCodexSession> {
                             timestamp,
                             role,
                             content: text,
+                            thinking,
                         });
                     }
                 }
@@ -283,6 +323,9 @@ impl ParsedCodexSession {
             source_path: Some(self.source_path.clone()),
             message_count: self.messages.len() as i32,
             machine_id: crate::storage::get_machine_id(),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
 
         let messages: Vec<Message> = self
@@ -296,7 +339,7 @@ impl ParsedCodexSession {
                 index: idx as i32,
                 timestamp: m.timestamp,
                 role: m.role.clone(),
-                content: MessageContent::Text(m.content.clone()),
+                content: m.storage_content(),
                 model: self.model_provider.clone(),
                 git_branch: self.git_branch.clone(),
                 cwd: Some(self.cwd.clone()),
@@ -313,6 +356,28 @@ pub struct ParsedCodexMessage {
     pub timestamp: DateTime<Utc>,
     pub role: MessageRole,
     pub content: String,
+    /// Reasoning the model produced before this message, in order.
+    pub thinking: Vec<String>,
+}
+
+impl ParsedCodexMessage {
+    /// Returns the message content, with any reasoning as leading thinking blocks.
+    fn storage_content(&self) -> MessageContent {
+        if self.thinking.is_empty() {
+            return MessageContent::Text(self.content.clone());
+        }
+        let mut blocks: Vec<ContentBlock> = self
+            .thinking
+            .iter()
+            .map(|thinking| ContentBlock::Thinking {
+                thinking: thinking.clone(),
+            })
+            .collect();
+        blocks.push(ContentBlock::Text {
+            text: self.content.clone(),
+        });
+        MessageContent::Blocks(blocks)
+    }
 }
 
 /// Discovers all Codex session files.
@@ -531,6 +596,65 @@ mod tests {
         assert_eq!(parsed.messages[0].role, MessageRole::User);
     }
 
+    #[test]
+    fn test_reasoning_attached_to_next_assistant_message() {
+        let session_id = "019b33ab-179f-7802-88a6-16557b4b7603";
+        let meta_line = make_session_meta(session_id, "/test", "0.63.0");
+        let user_line = make_user_message("Fix the failing test");
+        let reasoning = r#"{"timestamp":"2025-12-18T22:54:10.000Z","type":"response_item","payload":{"type":"reasoning","id":"rs_1","summary":[{"type":"summary_text","text":"**Plan** read the test first"}],"content":null,"encrypted_content":"gAAAA"}}"#;
+        let tool_call = r#"{"timestamp":"2025-12-18T22:54:20.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{}","call_id":"c1"}}"#;
+        let assistant = r#"{"timestamp":"2025-12-18T22:55:00.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"Fixed it."}]}}"#;
+
+        let file =
+            create_temp_session_file(&[&meta_line, &user_line, reasoning, tool_call, assistant]);
+        let parsed = parse_codex_session_file(file.path()).expect("Failed to parse");
+
+        assert_eq!(parsed.messages.len(), 2);
+        assert!(parsed.messages[0].thinking.is_empty());
+        assert_eq!(parsed.messages[1].content, "Fixed it.");
+        assert_eq!(
+            parsed.messages[1].thinking,
+            vec!["**Plan** read the test first"]
+        );
+
+        let (_, messages) = parsed.to_storage_models();
+        match &messages[1].content {
+            MessageContent::Blocks(blocks) => {
+                assert!(matches!(
+                    &blocks[0],
+                    ContentBlock::Thinking { thinking } if thinking == "**Plan** read the test first"
+                ));
+                assert!(matches!(&blocks[1], ContentBlock::Text { text } if text == "Fixed it."));
+            }
+            other => panic!("expected blocks, got {other:?}"),
+        }
+        assert!(matches!(messages[0].content, MessageContent::Text(_)));
+    }
+
+    #[test]
+    fn test_unanswered_or_malformed_reasoning_skipped() {
+        let session_id = "019b33ab-179f-7802-88a6-16557b4b7603";
+        let meta_line = make_session_meta(session_id, "/test", "0.63.0");
+        let reasoning = r#"{"timestamp":"2025-12-18T22:54:10.000Z","type":"response_item","payload":{"type":"reasoning","summary":[{"type":"summary_text","text":"Abandoned plan"}]}}"#;
+        let malformed = r#"{"timestamp":"2025-12-18T22:54:11.000Z","type":"response_item","payload":{"type":"reasoning","summary":"not a list"}}"#;
+        let unknown = r#"{"timestamp":"2025-12-18T22:54:12.000Z","type":"response_item","payload":{"type":"web_search_call","status":"completed"}}"#;
+        let user_line = make_user_message("Never mind");
+        let assistant_line = make_assistant_message("OK");
+
+        let file = create_temp_session_file(&[
+            &meta_line,
+            reasoning,
+            malformed,
+            unknown,
+            &user_line,
+            &assistant_line,
+        ]);
+        let parsed = parse_codex_session_file(file.path()).expect("Failed to parse");
+
+        assert_eq!(parsed.messages.len(), 2);
+        assert!(parsed.messages.iter().all(|m| m.thinking.is_empty()));
+    }
+
     #[test]
     fn test_empty_content_skipped() {
         let session_id = "019b33ab-179f-7802-88a6-16557b4b7603";
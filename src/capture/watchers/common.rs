@@ -49,6 +49,7 @@ pub fn vscode_global_storage() -> PathBuf {
 /// - "user", "human" -> MessageRole::User
 /// - "assistant" -> MessageRole::Assistant
 /// - "system" -> MessageRole::System
+/// - "tool" -> MessageRole::Tool
 ///
 /// Returns None for unrecognized roles.
 pub fn parse_role(role: &str) -> Option<MessageRole> {
@@ -56,6 +57,7 @@ pub fn parse_role(role: &str) -> Option<MessageRole> {
         "user" | "human" => Some(MessageRole::User),
         "assistant" => Some(MessageRole::Assistant),
         "system" => Some(MessageRole::System),
+        "tool" => Some(MessageRole::Tool),
         _ => None,
     }
 }
@@ -116,12 +118,16 @@ mod tests {
         assert_eq!(parse_role("system"), Some(MessageRole::System));
     }
 
+    #[test]
+    fn test_parse_role_tool() {
+        assert_eq!(parse_role("tool"), Some(MessageRole::Tool));
+    }
+
     #[test]
     fn test_parse_role_unknown() {
         assert_eq!(parse_role("unknown"), None);
         assert_eq!(parse_role(""), None);
         assert_eq!(parse_role("thinking"), None);
-        assert_eq!(parse_role("tool"), None);
     }
 
     #[test]
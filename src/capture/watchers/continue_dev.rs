@@ -8,6 +8,11 @@
 //! - Working directory
 //! - Chat history with messages and context
 //! - Optional model and usage information
+//!
+//! Each history item may carry prompt logs naming the model that produced
+//! the reply, and context items for the files attached to a message. The
+//! model becomes the message's model (and the session's), and attached files
+//! are recorded as `Read` tool calls so they count toward auto-linking.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -16,7 +21,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::storage::models::{Message, MessageContent, MessageRole, Session};
+use crate::storage::models::{ContentBlock, Message, MessageContent, MessageRole, Session};
 
 use super::{Watcher, WatcherInfo};
 
@@ -120,6 +125,110 @@ struct ContinueSession {
 struct ContinueChatHistoryItem {
     /// The message
     message: ContinueChatMessage,
+
+    /// Files, selections, and other context attached to the message
+    #[serde(default)]
+    context_items: Vec<ContinueContextItem>,
+
+    /// Prompts sent to the model for this item, with the model used
+    #[serde(default)]
+    prompt_logs: Vec<ContinuePromptLog>,
+}
+
+/// A context item attached to a Continue.dev message.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContinueContextItem {
+    /// Human-readable description; the relative path for file items
+    #[serde(default)]
+    description: Option<String>,
+
+    /// Which context provider produced the item
+    #[serde(default)]
+    id: Option<ContinueContextItemId>,
+
+    /// Location of the item, for files a `file://` URI
+    #[serde(default)]
+    uri: Option<ContinueContextItemUri>,
+}
+
+/// Identifies the context provider of a context item.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContinueContextItemId {
+    #[serde(default)]
+    provider_title: Option<String>,
+}
+
+/// The location of a context item.
+#[derive(Debug, Deserialize)]
+struct ContinueContextItemUri {
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    value: Option<String>,
+}
+
+impl ContinueContextItem {
+    /// Returns the file path of a file context item.
+    ///
+    /// Uses the `file://` URI when present, and otherwise the description
+    /// of items from the file providers, which holds the relative path.
+    fn file_path(&self) -> Option<String> {
+        if let Some(uri) = &self.uri {
+            if uri.kind.as_deref() == Some("file") {
+                let value = uri.value.as_deref()?;
+                return Some(value.strip_prefix("file://").unwrap_or(value).to_string());
+            }
+            return None;
+        }
+        let provider = self.id.as_ref()?.provider_title.as_deref()?;
+        if matches!(provider, "file" | "currentFile" | "open") {
+            return self.description.clone().filter(|d| !d.is_empty());
+        }
+        None
+    }
+}
+
+/// A prompt Continue.dev sent to a model.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContinuePromptLog {
+    /// The model's display title, such as "Claude 3.5 Sonnet"
+    #[serde(default)]
+    model_title: Option<String>,
+
+    /// The provider, such as "anthropic" or "ollama"
+    #[serde(default)]
+    model_provider: Option<String>,
+
+    #[serde(default)]
+    completion_options: Option<ContinueCompletionOptions>,
+}
+
+/// Completion options recorded with a prompt.
+#[derive(Debug, Deserialize)]
+struct ContinueCompletionOptions {
+    /// The model ID sent to the provider
+    #[serde(default)]
+    model: Option<String>,
+}
+
+impl ContinuePromptLog {
+    /// Returns the model name, preferring the provider's model ID over the
+    /// display title, prefixed with the provider when it is known.
+    fn model(&self) -> Option<String> {
+        let model = self
+            .completion_options
+            .as_ref()
+            .and_then(|o| o.model.clone())
+            .or_else(|| self.model_title.clone())
+            .filter(|m| !m.is_empty())?;
+        match self.model_provider.as_deref().filter(|p| !p.is_empty()) {
+            Some(provider) if !model.contains('/') => Some(format!("{provider}/{model}")),
+            _ => Some(model),
+        }
+    }
 }
 
 /// A message in Continue.dev chat history.
@@ -198,13 +307,22 @@ fn parse_continue_session(path: &Path) -> Result<Option<(Session, Vec<Message>)>
         .map(|t| t - chrono::Duration::minutes(message_count as i64 * 2))
         .unwrap_or_else(Utc::now);
 
+    // The last model that replied names the session's model; the chat model
+    // title is a fallback for sessions without prompt logs
+    let session_model = raw_session
+        .history
+        .iter()
+        .rev()
+        .find_map(|item| item.prompt_logs.iter().rev().find_map(|log| log.model()))
+        .or(raw_session.chat_model_title);
+
     let session = Session {
         id: session_id,
         tool: "continue".to_string(),
         tool_version: None,
         started_at,
         ended_at,
-        model: raw_session.chat_model_title,
+        model: session_model,
         working_directory: raw_session
             .workspace_directory
             .unwrap_or_else(|| ".".to_string()),
@@ -212,6 +330,9 @@ fn parse_continue_session(path: &Path) -> Result<Option<(Session, Vec<Message>)>
         source_path: Some(path.to_string_lossy().to_string()),
         message_count: message_count as i32,
         machine_id: crate::storage::get_machine_id(),
+        input_tokens: None,
+        output_tokens: None,
+        parent_session_id: None,
     };
 
     // Convert messages
@@ -225,15 +346,39 @@ fn parse_continue_session(path: &Path) -> Result<Option<(Session, Vec<Message>)>
             "assistant" => MessageRole::Assistant,
             "system" => MessageRole::System,
             "thinking" => continue, // Skip thinking messages
-            "tool" => continue,     // Skip tool result messages
+            "tool" => MessageRole::Tool,
             _ => continue,
         };
 
-        let content = item.message.content.to_text();
-        if content.trim().is_empty() {
+        let text = item.message.content.to_text();
+        if text.trim().is_empty() {
             continue;
         }
 
+        let attached: Vec<String> = item
+            .context_items
+            .iter()
+            .filter_map(ContinueContextItem::file_path)
+            .collect();
+        let content = if attached.is_empty() {
+            MessageContent::Text(text)
+        } else {
+            let mut blocks = vec![ContentBlock::Text { text }];
+            blocks.extend(attached.into_iter().enumerate().map(|(n, path)| {
+                ContentBlock::ToolUse {
+                    id: format!("continue-context-{idx}-{n}"),
+                    name: "Read".to_string(),
+                    input: serde_json::json!({ "file_path": path }),
+                }
+            }));
+            MessageContent::Blocks(blocks)
+        };
+
+        let model = match role {
+            MessageRole::Assistant => item.prompt_logs.iter().rev().find_map(|log| log.model()),
+            _ => None,
+        };
+
         messages.push(Message {
             id: Uuid::new_v4(),
             session_id,
@@ -241,8 +386,8 @@ fn parse_continue_session(path: &Path) -> Result<Option<(Session, Vec<Message>)>
             index: idx as i32,
             timestamp: current_time,
             role,
-            content: MessageContent::Text(content),
-            model: None,
+            content,
+            model,
             git_branch: None,
             cwd: Some(session.working_directory.clone()),
         });
@@ -334,6 +479,74 @@ mod tests {
         assert_eq!(session.model, Some("GPT-4".to_string()));
     }
 
+    #[test]
+    fn test_parse_models_from_prompt_logs() {
+        let json = r#"{
+            "sessionId": "550e8400-e29b-41d4-a716-446655440001",
+            "chatModelTitle": "Claude Sonnet",
+            "workspaceDirectory": "/home/user/project",
+            "history": [
+                {
+                    "message": {"role": "user", "content": "What does main do?"},
+                    "contextItems": [
+                        {
+                            "name": "main.rs",
+                            "description": "src/main.rs",
+                            "content": "fn main() {}",
+                            "id": {"providerTitle": "file", "itemId": "1"},
+                            "uri": {"type": "file", "value": "file:///home/user/project/src/main.rs"}
+                        },
+                        {
+                            "name": "lib.rs",
+                            "description": "src/lib.rs",
+                            "content": "",
+                            "id": {"providerTitle": "file", "itemId": "2"}
+                        },
+                        {
+                            "name": "Terminal",
+                            "description": "Terminal output",
+                            "content": "",
+                            "id": {"providerTitle": "terminal", "itemId": "3"}
+                        }
+                    ]
+                },
+                {
+                    "message": {"role": "assistant", "content": "It prints nothing."},
+                    "contextItems": [],
+                    "promptLogs": [
+                        {
+                            "modelTitle": "Claude Sonnet",
+                            "modelProvider": "anthropic",
+                            "completionOptions": {"model": "claude-3-5-sonnet-latest"},
+                            "prompt": "...",
+                            "completion": "It prints nothing."
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        let file = create_temp_session_file(json);
+        let (session, messages) = parse_continue_session(file.path())
+            .expect("Should parse")
+            .expect("Should have session");
+
+        assert_eq!(
+            session.model.as_deref(),
+            Some("anthropic/claude-3-5-sonnet-latest")
+        );
+        assert_eq!(messages[0].model, None);
+        assert_eq!(
+            messages[1].model.as_deref(),
+            Some("anthropic/claude-3-5-sonnet-latest")
+        );
+
+        let mut files =
+            crate::storage::extract_session_files(&messages, &session.working_directory);
+        files.sort();
+        assert_eq!(files, vec!["src/lib.rs", "src/main.rs"]);
+    }
+
     #[test]
     fn test_parse_empty_history() {
         let json = r#"{
@@ -217,6 +217,9 @@ impl ParsedGeminiSession {
             source_path: Some(self.source_path.clone()),
             message_count: self.messages.len() as i32,
             machine_id: crate::storage::get_machine_id(),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
 
         let messages: Vec<Message> = self
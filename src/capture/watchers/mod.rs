@@ -94,7 +94,9 @@ pub trait Watcher: Send + Sync {
     /// Checks if this watcher is available.
     ///
     /// A watcher is available if the tool it watches is installed and its
-    /// session storage location exists on this system.
+    /// session storage location exists on this system. An available
+    /// watcher may still have no sessions; see
+    /// [`session_count`](Self::session_count).
     fn is_available(&self) -> bool;
 
     /// Finds all session sources (files or directories) to import.
@@ -103,6 +105,17 @@ pub trait Watcher: Send + Sync {
     /// passed to [`parse_source`](Self::parse_source).
     fn find_sources(&self) -> Result<Vec<PathBuf>>;
 
+    /// Counts the sessions this watcher can import, without parsing them.
+    ///
+    /// Lets `lore status` tell a tool that is installed but has no sessions
+    /// apart from one with history to import. The default counts the
+    /// sources from [`find_sources`](Self::find_sources), which matches
+    /// tools that store one session per file; watchers whose sources hold
+    /// several sessions should override it with a cheap count.
+    fn session_count(&self) -> Result<usize> {
+        Ok(self.find_sources()?.len())
+    }
+
     /// Parses a session source and returns sessions with their messages.
     ///
     /// Each session is returned with its associated messages as a tuple.
@@ -179,7 +192,6 @@ impl WatcherRegistry {
     /// Retrieves a watcher by its name.
     ///
     /// Returns `None` if no watcher with the given name is registered.
-    #[allow(dead_code)]
     pub fn get_watcher(&self, name: &str) -> Option<&dyn Watcher> {
         self.watchers
             .iter()
@@ -402,6 +402,9 @@ impl ParsedOpenCodeSession {
             source_path: Some(self.source_path.clone()),
             message_count: self.messages.len() as i32,
             machine_id: crate::storage::get_machine_id(),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
 
         // Build message ID map for consistent UUIDs
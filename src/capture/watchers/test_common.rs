@@ -78,6 +78,22 @@ fn test_all_watchers_find_sources_handles_missing_dirs() {
     }
 }
 
+/// Tests that session_count never errors when a watcher's sources can be listed.
+#[test]
+fn test_all_watchers_session_count_handles_missing_dirs() {
+    let registry = default_registry();
+
+    for watcher in registry.all_watchers() {
+        let result = watcher.session_count();
+        assert!(
+            result.is_ok(),
+            "Watcher {} session_count should not error when directory is missing: {:?}",
+            watcher.info().name,
+            result.err()
+        );
+    }
+}
+
 /// Tests that watch_paths returns valid paths for watchers that support watching.
 ///
 /// Some watchers (like aider) return empty watch_paths because their files
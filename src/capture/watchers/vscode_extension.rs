@@ -320,6 +320,9 @@ pub fn parse_vscode_task(
         source_path: Some(history_path.to_string_lossy().to_string()),
         message_count: messages.len() as i32,
         machine_id: crate::storage::get_machine_id(),
+        input_tokens: None,
+        output_tokens: None,
+        parent_session_id: None,
     };
 
     Ok(Some((session, messages)))
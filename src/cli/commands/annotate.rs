@@ -3,7 +3,7 @@
 //! Adds an annotation (bookmark or note) to the current active session.
 //! Annotations help mark important moments in a session for later reference.
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 use uuid::Uuid;
@@ -49,7 +49,7 @@ pub fn run(args: Args) -> Result<()> {
     db.insert_annotation(&annotation)?;
 
     let short_id = &session_id.to_string()[..8];
-    println!(
+    note!(
         "{}",
         format!("Annotation added to session {}", short_id.cyan()).green()
     );
@@ -57,31 +57,12 @@ pub fn run(args: Args) -> Result<()> {
     Ok(())
 }
 
-/// Finds a session by ID prefix.
-///
-/// Searches for sessions matching the given ID prefix and returns the full session ID.
+/// Finds a session by ID prefix and returns its full ID.
 fn find_session_by_prefix(db: &Database, id_prefix: &str) -> Result<Uuid> {
-    let sessions = db.list_sessions(100, None)?;
-    let matching: Vec<_> = sessions
-        .iter()
-        .filter(|s| s.id.to_string().starts_with(id_prefix))
-        .collect();
-
-    match matching.len() {
-        0 => bail!("No session found matching '{id_prefix}'"),
-        1 => Ok(matching[0].id),
-        _ => {
-            let ids: Vec<String> = matching
-                .iter()
-                .map(|s| s.id.to_string()[..8].to_string())
-                .collect();
-            bail!(
-                "Ambiguous session prefix '{}'. Matches: {}",
-                id_prefix,
-                ids.join(", ")
-            )
-        }
-    }
+    let session = db
+        .find_session_by_id_prefix(id_prefix)?
+        .with_context(|| format!("No session found matching '{id_prefix}'"))?;
+    Ok(session.id)
 }
 
 /// Gets the current session for the working directory.
@@ -83,12 +83,19 @@ pub fn run(args: Args) -> Result<()> {
 
     // Output results
     match args.format {
-        OutputFormat::Json => {
-            print_json(&file_path, line_num, &blame_info, &session_infos)?;
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            print_json(
+                &file_path,
+                line_num,
+                &blame_info,
+                &session_infos,
+                args.format,
+            )?;
         }
         OutputFormat::Markdown => {
             print_markdown(&file_path, line_num, &blame_info, &session_infos);
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("blame")),
         OutputFormat::Text => {
             print_text(&file_path, line_num, &blame_info, &session_infos);
         }
@@ -316,6 +323,7 @@ fn print_json(
     line_num: usize,
     blame_info: &BlameInfo,
     session_infos: &[(Session, Vec<String>)],
+    format: OutputFormat,
 ) -> Result<()> {
     let output = BlameOutput {
         file: file_path.to_string(),
@@ -338,7 +346,7 @@ fn print_json(
             .collect(),
     };
 
-    let json = serde_json::to_string_pretty(&output)?;
+    let json = format.json_string(&output)?;
     println!("{json}");
     Ok(())
 }
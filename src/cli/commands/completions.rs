@@ -6,6 +6,11 @@
 //! Supports two modes:
 //! - Generate: Output completions to stdout for manual installation
 //! - Install: Automatically install completions to the appropriate location
+//!
+//! Subcommands and options complete in every supported shell. Bash and fish
+//! scripts also complete session ID prefixes for `lore show` and `lore link`
+//! by calling the hidden `lore completions session-ids` helper, which reads
+//! recent sessions from the database and prints nothing if none exists yet.
 
 use anyhow::{anyhow, Context, Result};
 use clap::{Command, Subcommand};
@@ -21,6 +26,9 @@ use std::path::{Path, PathBuf};
     lore completions bash              Output bash completions to stdout\n    \
     lore completions install           Auto-detect shell and install\n    \
     lore completions install --shell fish  Install fish completions\n\n\
+SESSION IDS:\n    \
+    Bash and fish also complete session ID prefixes for 'lore show' and\n    \
+    'lore link'. Other shells complete commands and options only.\n\n\
 INSTALLATION PATHS:\n    \
     Bash:       ~/.local/share/bash-completion/completions/lore\n    \
     Zsh:        ~/.zfunc/_lore\n    \
@@ -54,6 +62,10 @@ pub enum CompletionsCommand {
         to the appropriate location. Creates directories if needed."
     )]
     Install(InstallArgs),
+
+    /// Print recent session ID prefixes, one per line (used by completions)
+    #[command(hide = true)]
+    SessionIds(SessionIdsArgs),
 }
 
 /// Arguments for the install subcommand.
@@ -64,6 +76,72 @@ pub struct InstallArgs {
     pub shell: Option<Shell>,
 }
 
+/// Arguments for the hidden session-ids subcommand.
+#[derive(clap::Args)]
+pub struct SessionIdsArgs {
+    /// Maximum number of session IDs to print
+    #[arg(long, default_value = "50")]
+    pub limit: usize,
+}
+
+/// Length of the session ID prefixes offered as completions.
+const SESSION_ID_PREFIX_LEN: usize = 8;
+
+/// Bash snippet adding session ID completion for `lore show` and `lore link`.
+///
+/// Wraps the generated `_lore` function: when completing a non-flag word
+/// after `show` or `link`, it offers prefixes from
+/// `lore completions session-ids`, and otherwise defers to `_lore`.
+const BASH_SESSION_COMPLETION: &str = r#"
+# Complete session ID prefixes for 'lore show' and 'lore link'.
+_lore_sessions() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    if [[ ${COMP_CWORD} -ge 2 && "${cur}" != -* ]]; then
+        case "${COMP_WORDS[1]}" in
+            show|link)
+                COMPREPLY=( $(compgen -W "$(lore completions session-ids 2>/dev/null)" -- "${cur}") )
+                if [[ ${#COMPREPLY[@]} -gt 0 ]]; then
+                    return 0
+                fi
+                ;;
+        esac
+    fi
+    _lore "$@"
+}
+complete -F _lore_sessions -o bashdefault -o default lore
+"#;
+
+/// Fish snippet adding session ID completion for `lore show` and `lore link`.
+const FISH_SESSION_COMPLETION: &str = r#"
+# Complete session ID prefixes for 'lore show' and 'lore link'.
+complete -c lore -n "__fish_lore_using_subcommand show; or __fish_lore_using_subcommand link" -f -a "(lore completions session-ids 2>/dev/null)"
+"#;
+
+/// Returns the dynamic session ID completion snippet for a shell, if any.
+fn session_completion_snippet(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(BASH_SESSION_COMPLETION),
+        Shell::Fish => Some(FISH_SESSION_COMPLETION),
+        _ => None,
+    }
+}
+
+/// Returns short ID prefixes for the most recent sessions.
+///
+/// Returns an empty list when the database does not exist yet, so completion
+/// works (with no suggestions) before `lore init` or the first import.
+fn recent_session_prefixes(db_path: &Path, limit: usize) -> Result<Vec<String>> {
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+    let db = crate::storage::Database::open(&db_path.to_path_buf())?;
+    Ok(db
+        .list_sessions(limit, None)?
+        .iter()
+        .map(|s| s.id.to_string()[..SESSION_ID_PREFIX_LEN].to_string())
+        .collect())
+}
+
 /// Detects the current shell from the SHELL environment variable.
 ///
 /// Parses the basename of the SHELL path and matches it to a supported shell.
@@ -179,9 +257,14 @@ fn get_activation_instructions(shell: Shell, path: &Path) -> String {
 }
 
 /// Generates completions and writes them to a buffer.
+///
+/// Appends the dynamic session ID snippet for shells that support it.
 fn generate_completions_to_buffer(cmd: &mut Command, shell: Shell) -> Vec<u8> {
     let mut buf = Vec::new();
     generate(shell, cmd, "lore", &mut buf);
+    if let Some(snippet) = session_completion_snippet(shell) {
+        buf.extend_from_slice(snippet.as_bytes());
+    }
     buf
 }
 
@@ -306,11 +389,23 @@ pub fn run(args: Args, cmd: &mut Command) -> Result<()> {
 
             Ok(())
         }
+        Some(CompletionsCommand::SessionIds(ids_args)) => {
+            // Completion must never print errors into the user's prompt, so a
+            // missing or unreadable database simply yields no suggestions.
+            let prefixes = crate::storage::db::default_db_path()
+                .and_then(|path| recent_session_prefixes(&path, ids_args.limit))
+                .unwrap_or_default();
+            let mut stdout = io::stdout().lock();
+            for prefix in prefixes {
+                writeln!(stdout, "{prefix}")?;
+            }
+            Ok(())
+        }
         None => {
             // Backward compatibility: lore completions <shell>
             match args.shell {
                 Some(shell) => {
-                    generate(shell, cmd, "lore", &mut io::stdout());
+                    io::stdout().write_all(&generate_completions_to_buffer(cmd, shell))?;
                     Ok(())
                 }
                 None => Err(anyhow!(
@@ -507,4 +602,57 @@ mod tests {
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("_lore"));
     }
+
+    #[test]
+    fn test_session_completion_snippet_only_for_bash_and_fish() {
+        assert!(session_completion_snippet(Shell::Bash)
+            .unwrap()
+            .contains("lore completions session-ids"));
+        assert!(session_completion_snippet(Shell::Fish)
+            .unwrap()
+            .contains("lore completions session-ids"));
+        assert!(session_completion_snippet(Shell::Zsh).is_none());
+        assert!(session_completion_snippet(Shell::PowerShell).is_none());
+    }
+
+    #[test]
+    fn test_recent_session_prefixes_without_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.db");
+
+        assert!(recent_session_prefixes(&path, 10).unwrap().is_empty());
+        assert!(!path.exists(), "listing must not create the database");
+    }
+
+    #[test]
+    fn test_recent_session_prefixes_lists_short_ids() {
+        use crate::storage::models::Session;
+        use chrono::Utc;
+        use uuid::Uuid;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lore.db");
+        let db = crate::storage::Database::open(&path).unwrap();
+        let session = Session {
+            id: Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at: Utc::now(),
+            ended_at: None,
+            model: None,
+            working_directory: "/project".to_string(),
+            git_branch: None,
+            source_path: None,
+            message_count: 0,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        };
+        db.insert_session(&session).unwrap();
+        drop(db);
+
+        let prefixes = recent_session_prefixes(&path, 10).unwrap();
+        assert_eq!(prefixes, vec![session.id.to_string()[..8].to_string()]);
+    }
 }
@@ -1,16 +1,23 @@
 //! Config command - view and manage Lore configuration.
 //!
-//! Provides subcommands to show, get, and set configuration values.
-//! Configuration is stored in ~/.lore/config.yaml.
+//! Provides subcommands to show, get, set, and unset configuration values, to
+//! locate, edit, and validate the config file, and to list the models offered
+//! by the summary provider.
+//! Configuration is stored in ~/.lore/config.yaml. Values shown are the
+//! effective ones, including `LORE_<KEY>` environment overrides.
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use colored::Colorize;
 use serde::Serialize;
 
+use crate::capture::watchers::default_registry;
 use crate::cli::OutputFormat;
-use crate::config::Config;
+use crate::config::{Config, ConfigProblem, DaemonConfig};
 use crate::storage::db::default_db_path;
 use crate::storage::{Database, Machine};
+use crate::summarize::models::list_models;
+use crate::summarize::provider::default_model;
+use crate::summarize::{resolve_base_url, SummaryProviderKind};
 
 /// Arguments for the config command.
 #[derive(clap::Args)]
@@ -18,6 +25,11 @@ use crate::storage::{Database, Machine};
     lore config                          Show configuration paths and settings\n    \
     lore config get watchers             Get the value of a config key\n    \
     lore config set watchers claude-code,aider  Set enabled watchers\n    \
+    lore config unset watchers           Restore the default watchers\n    \
+    lore config validate                 Check config.yaml for mistakes\n    \
+    lore config path                     Print the config file path\n    \
+    lore config edit                     Open config.yaml in $EDITOR\n    \
+    lore config summary-models           List models for the summary provider\n    \
     lore config --format json            Output as JSON")]
 pub struct Args {
     /// Config subcommand
@@ -44,6 +56,43 @@ pub enum ConfigCommand {
         /// The value to set
         value: String,
     },
+    /// Reset a configuration value to its default
+    Unset {
+        /// The configuration key to reset
+        key: String,
+    },
+    /// Check the config file for mistakes
+    #[command(
+        long_about = "Checks ~/.lore/config.yaml for YAML syntax errors, unknown keys,\n\
+        out-of-range values, and unknown watcher names, and prints each\n\
+        problem with the line it was found on. Exits with status 1 if any\n\
+        problem is found."
+    )]
+    Validate,
+    /// Print the path of the config file
+    Path,
+    /// Open the config file in $EDITOR and validate it afterwards
+    #[command(
+        long_about = "Opens ~/.lore/config.yaml in the editor named by $VISUAL or\n\
+        $EDITOR, writing a file with the default settings first if there is\n\
+        none. When the editor exits the file is validated like 'lore config\n\
+        validate', exiting with status 1 if any problem is found."
+    )]
+    Edit,
+    /// List the models available from a summary provider
+    #[command(
+        long_about = "Fetches and prints the models offered by a summary provider,\n\
+        marking the one currently configured. Uses summary_provider unless\n\
+        --provider is given. Listings are cached for an hour."
+    )]
+    SummaryModels {
+        /// Provider to list models for (anthropic, openai, openrouter, gemini, openai-compatible)
+        #[arg(long, value_name = "PROVIDER")]
+        provider: Option<String>,
+        /// Fetch a fresh list instead of using the cached one
+        #[arg(long)]
+        refresh: bool,
+    },
 }
 
 /// JSON output structure for config show.
@@ -63,17 +112,34 @@ struct ConfigSettings {
     watchers: Vec<String>,
     auto_link: bool,
     auto_link_threshold: f64,
+    auto_link_audit: bool,
     commit_footer: bool,
+    git_remotes: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     summary_provider: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    summary_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     summary_model_anthropic: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     summary_model_openai: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     summary_model_openrouter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary_model_gemini: Option<String>,
     summary_auto: bool,
     summary_auto_threshold: usize,
+    summary_max_attempts: u32,
+    summary_truncation: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary_base_url: Option<String>,
+    redact_secrets: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    redact_patterns: Vec<String>,
+    redact_paths: bool,
+    compress_content: bool,
+    search_tokenizer: String,
+    daemon: DaemonConfig,
 }
 
 /// Executes the config command.
@@ -81,6 +147,13 @@ pub fn run(args: Args) -> Result<()> {
     match args.command {
         Some(ConfigCommand::Get { key }) => run_get(&key, args.format),
         Some(ConfigCommand::Set { key, value }) => run_set(&key, &value),
+        Some(ConfigCommand::Unset { key }) => run_unset(&key),
+        Some(ConfigCommand::Validate) => run_validate(args.format),
+        Some(ConfigCommand::Path) => run_path(args.format),
+        Some(ConfigCommand::Edit) => run_edit(),
+        Some(ConfigCommand::SummaryModels { provider, refresh }) => {
+            run_summary_models(provider, refresh, args.format)
+        }
         None => run_show(args.format),
     }
 }
@@ -93,7 +166,7 @@ fn run_show(format: OutputFormat) -> Result<()> {
     let config = Config::load()?;
 
     match format {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Jsonl => {
             let output = ConfigShowOutput {
                 database_path: db_path.display().to_string(),
                 config_path: config_path.display().to_string(),
@@ -104,18 +177,32 @@ fn run_show(format: OutputFormat) -> Result<()> {
                     watchers: config.watchers.clone(),
                     auto_link: config.auto_link,
                     auto_link_threshold: config.auto_link_threshold,
+                    auto_link_audit: config.auto_link_audit,
                     commit_footer: config.commit_footer,
+                    git_remotes: config.git_remotes.clone(),
                     summary_provider: config.summary_provider.clone(),
+                    summary_model: config.summary_model.clone(),
                     summary_model_anthropic: config.summary_model_anthropic.clone(),
                     summary_model_openai: config.summary_model_openai.clone(),
                     summary_model_openrouter: config.summary_model_openrouter.clone(),
+                    summary_model_gemini: config.summary_model_gemini.clone(),
                     summary_auto: config.summary_auto,
                     summary_auto_threshold: config.summary_auto_threshold,
+                    summary_max_attempts: config.summary_max_attempts,
+                    summary_truncation: config.get("summary_truncation").unwrap_or_default(),
+                    summary_base_url: config.summary_base_url.clone(),
+                    redact_secrets: config.redact_secrets,
+                    redact_patterns: config.redact_patterns.clone(),
+                    redact_paths: config.redact_paths,
+                    compress_content: config.compress_content,
+                    search_tokenizer: config.get("search_tokenizer").unwrap_or_default(),
+                    daemon: config.daemon.clone(),
                 },
             };
-            let json = serde_json::to_string_pretty(&output)?;
+            let json = format.json_string(&output)?;
             println!("{json}");
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("config")),
         OutputFormat::Text | OutputFormat::Markdown => {
             println!("{}", "Lore Configuration".bold());
             println!();
@@ -166,6 +253,14 @@ fn run_show(format: OutputFormat) -> Result<()> {
                 "  auto_link_threshold: {}",
                 format!("{:.1}", config.auto_link_threshold).cyan()
             );
+            println!(
+                "  auto_link_audit:     {}",
+                if config.auto_link_audit {
+                    "true".green()
+                } else {
+                    "false".yellow()
+                }
+            );
             println!(
                 "  commit_footer:       {}",
                 if config.commit_footer {
@@ -174,13 +269,63 @@ fn run_show(format: OutputFormat) -> Result<()> {
                     "false".yellow()
                 }
             );
+            println!(
+                "  git_remotes:         {}",
+                config.git_remotes.join(", ").cyan()
+            );
+            println!(
+                "  redact_secrets:      {}",
+                if config.redact_secrets {
+                    "true".green()
+                } else {
+                    "false".yellow()
+                }
+            );
+            for pattern in &config.redact_patterns {
+                println!("  redact_patterns:     {}", pattern.cyan());
+            }
+            println!(
+                "  redact_paths:        {}",
+                if config.redact_paths {
+                    "true".green()
+                } else {
+                    "false".yellow()
+                }
+            );
+            println!(
+                "  compress_content:    {}",
+                if config.compress_content {
+                    "true".green()
+                } else {
+                    "false".yellow()
+                }
+            );
+            println!(
+                "  search_tokenizer:    {}",
+                config.get("search_tokenizer").unwrap_or_default().cyan()
+            );
+            println!(
+                "  daemon.debounce_ms:  {}",
+                config.daemon.debounce_ms.to_string().cyan()
+            );
+            for path in &config.daemon.extra_watch_paths {
+                println!(
+                    "  daemon.extra_watch_paths: {}",
+                    path.display().to_string().cyan()
+                );
+            }
+            if let Some(days) = config.daemon.retention_days {
+                println!("  daemon.retention_days: {}", days.to_string().cyan());
+            }
             println!();
 
             // Summary settings (only show section if any summary config exists)
             let has_summary_config = config.summary_provider.is_some()
                 || config.summary_api_key_anthropic.is_some()
                 || config.summary_api_key_openai.is_some()
-                || config.summary_api_key_openrouter.is_some();
+                || config.summary_api_key_openrouter.is_some()
+                || config.summary_api_key_gemini.is_some()
+                || config.summary_api_key_openai_compatible.is_some();
 
             if has_summary_config {
                 println!("{}", "Summary:".dimmed());
@@ -198,6 +343,11 @@ fn run_show(format: OutputFormat) -> Result<()> {
                     ("anthropic", &config.summary_api_key_anthropic),
                     ("openai", &config.summary_api_key_openai),
                     ("openrouter", &config.summary_api_key_openrouter),
+                    ("gemini", &config.summary_api_key_gemini),
+                    (
+                        "openai_compatible",
+                        &config.summary_api_key_openai_compatible,
+                    ),
                 ];
                 for (name, key) in &providers {
                     if let Some(k) = key {
@@ -214,12 +364,16 @@ fn run_show(format: OutputFormat) -> Result<()> {
                     ("anthropic", &config.summary_model_anthropic),
                     ("openai", &config.summary_model_openai),
                     ("openrouter", &config.summary_model_openrouter),
+                    ("gemini", &config.summary_model_gemini),
                 ];
                 for (name, model) in &models {
                     if let Some(m) = model {
                         println!("  summary_model_{:<11}: {}", name, m.cyan());
                     }
                 }
+                if let Some(m) = &config.summary_model {
+                    println!("  summary_model:       {}", m.cyan());
+                }
 
                 println!(
                     "  summary_auto:        {}",
@@ -235,6 +389,30 @@ fn run_show(format: OutputFormat) -> Result<()> {
                         config.summary_auto_threshold.to_string().cyan()
                     );
                 }
+                println!(
+                    "  summary_max_attempts: {}",
+                    config.summary_max_attempts.to_string().cyan()
+                );
+                println!(
+                    "  summary_truncation: {}",
+                    config.get("summary_truncation").unwrap_or_default().cyan()
+                );
+                if let Some(ref url) = config.summary_base_url {
+                    println!("  summary_base_url: {}", url.cyan());
+                }
+                println!();
+            }
+
+            let overridden: Vec<String> = config
+                .env_overridden_keys()
+                .map(Config::env_var_name)
+                .collect();
+            if !overridden.is_empty() {
+                println!(
+                    "{} {}",
+                    "Overridden by environment:".dimmed(),
+                    overridden.join(", ")
+                );
                 println!();
             }
 
@@ -264,10 +442,11 @@ fn run_get(key: &str, format: OutputFormat) -> Result<()> {
             };
 
             match format {
-                OutputFormat::Json => {
+                OutputFormat::Json | OutputFormat::Jsonl => {
                     let output = serde_json::json!({ "key": key, "value": display_value });
-                    println!("{}", serde_json::to_string_pretty(&output)?);
+                    println!("{}", format.json_string(&output)?);
                 }
+                OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("config get")),
                 OutputFormat::Text | OutputFormat::Markdown => {
                     println!("{display_value}");
                 }
@@ -289,23 +468,14 @@ fn run_get(key: &str, format: OutputFormat) -> Result<()> {
 fn run_set(key: &str, value: &str) -> Result<()> {
     let config_path = Config::config_path()?;
     let mut config = Config::load()?;
+    let overridden = config.env_overridden_keys().any(|k| k == key);
 
     config.set(key, value)?;
     config.save_to_path(&config_path)?;
 
     // If setting machine_name, also update the machines table
     if key == "machine_name" {
-        if let Ok(machine_id) = config.get_or_create_machine_id() {
-            if let Ok(db) = Database::open_default() {
-                let machine = Machine {
-                    id: machine_id,
-                    name: value.to_string(),
-                    created_at: chrono::Utc::now().to_rfc3339(),
-                };
-                // Ignore errors here since config update was successful
-                let _ = db.upsert_machine(&machine);
-            }
-        }
+        record_machine_name(&mut config, value);
     }
 
     // Mask sensitive values in output
@@ -320,10 +490,367 @@ fn run_set(key: &str, value: &str) -> Result<()> {
         key.cyan(),
         display_value.cyan()
     );
+    if overridden {
+        println!(
+            "{} {} is set and takes precedence over this value",
+            "Note:".yellow(),
+            Config::env_var_name(key)
+        );
+    }
+
+    if let Some(provider) = model_provider_for_key(&config, key, value) {
+        warn_if_unlisted_model(&config, &provider, value);
+    }
+
+    Ok(())
+}
+
+/// Updates this machine's name in the machines table.
+///
+/// Errors are ignored, since the config update itself succeeded.
+fn record_machine_name(config: &mut Config, name: &str) {
+    if let Ok(machine_id) = config.get_or_create_machine_id() {
+        if let Ok(db) = Database::open_default() {
+            let machine = Machine {
+                id: machine_id,
+                name: name.to_string(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+            };
+            let _ = db.upsert_machine(&machine);
+        }
+    }
+}
+
+/// Resets a configuration value to its default.
+fn run_unset(key: &str) -> Result<()> {
+    let config_path = Config::config_path()?;
+    let mut config = Config::load()?;
+    let overridden = config.env_overridden_keys().any(|k| k == key);
+
+    config.unset(key)?;
+    config.save_to_path(&config_path)?;
+
+    if key == "machine_name" {
+        let name = config.get_machine_name();
+        record_machine_name(&mut config, &name);
+    }
+
+    let default = match config.get(key) {
+        Some(value) if !value.is_empty() => value,
+        _ => "unset".to_string(),
+    };
+    println!(
+        "{} {} (now {})",
+        "Reset".green(),
+        key.cyan(),
+        default.cyan()
+    );
+    if overridden {
+        println!(
+            "{} {} is set and takes precedence over this value",
+            "Note:".yellow(),
+            Config::env_var_name(key)
+        );
+    }
+
+    Ok(())
+}
+
+/// JSON output structure for config validate.
+#[derive(Serialize)]
+struct ValidateOutput {
+    config_path: String,
+    config_exists: bool,
+    valid: bool,
+    problems: Vec<ConfigProblem>,
+}
+
+/// Validates the config file, exiting with status 1 if it has problems.
+fn run_validate(format: OutputFormat) -> Result<()> {
+    let config_path = Config::config_path()?;
+    let registry = default_registry();
+    let watcher_names: Vec<&str> = registry
+        .all_watchers()
+        .iter()
+        .map(|w| w.info().name)
+        .collect();
+    let problems = Config::validate_file(&config_path, &watcher_names)?;
+    let valid = problems.is_empty();
+
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            let output = ValidateOutput {
+                config_path: config_path.display().to_string(),
+                config_exists: config_path.exists(),
+                valid,
+                problems,
+            };
+            println!("{}", format.json_string(&output)?);
+        }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("config validate")),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            if !config_path.exists() {
+                println!(
+                    "No config file at {}; defaults are in use.",
+                    config_path.display()
+                );
+                return Ok(());
+            }
+            println!("Checking {}", config_path.display());
+            println!();
+
+            let content = std::fs::read_to_string(&config_path).unwrap_or_default();
+            for line in problem_lines(&problems, &content) {
+                println!("{line}");
+            }
+
+            if valid {
+                println!("{}", "No problems found.".green().bold());
+            } else {
+                println!();
+                let noun = if problems.len() == 1 {
+                    "problem"
+                } else {
+                    "problems"
+                };
+                println!(
+                    "{}",
+                    format!("{} {noun} found.", problems.len()).red().bold()
+                );
+            }
+        }
+    }
+
+    if !valid {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// JSON output structure for config path.
+#[derive(Serialize)]
+struct PathOutput {
+    config_path: String,
+    config_exists: bool,
+}
 
+/// Prints the path of the config file, whether or not it exists.
+fn run_path(format: OutputFormat) -> Result<()> {
+    let config_path = Config::config_path()?;
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            let output = PathOutput {
+                config_path: config_path.display().to_string(),
+                config_exists: config_path.exists(),
+            };
+            println!("{}", format.json_string(&output)?);
+        }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("config path")),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            println!("{}", config_path.display());
+        }
+    }
     Ok(())
 }
 
+/// Opens the config file in the user's editor, then validates it.
+fn run_edit() -> Result<()> {
+    let config_path = Config::config_path()?;
+    let editor = editor_command(|name| std::env::var(name).ok()).ok_or_else(|| {
+        anyhow!("No editor is set. Set $EDITOR (for example, export EDITOR=vim) and try again.")
+    })?;
+
+    if !config_path.exists() {
+        Config::default().save_to_path(&config_path)?;
+        note!(
+            "{}",
+            format!(
+                "Created {} with the default settings",
+                config_path.display()
+            )
+            .dimmed()
+        );
+    }
+
+    let (program, editor_args) = editor;
+    let status = std::process::Command::new(&program)
+        .args(&editor_args)
+        .arg(&config_path)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{program}'"))?;
+    if !status.success() {
+        bail!("Editor '{program}' exited with {status}; the config was not validated");
+    }
+
+    run_validate(OutputFormat::Text)
+}
+
+/// Returns the editor program and its arguments from `$VISUAL` or
+/// `$EDITOR`, read with `lookup`.
+///
+/// The value is split on whitespace so settings like `code --wait` work.
+fn editor_command(lookup: impl Fn(&str) -> Option<String>) -> Option<(String, Vec<String>)> {
+    let value = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| lookup(name))
+        .find(|value| !value.trim().is_empty())?;
+    let mut parts = value.split_whitespace().map(str::to_string);
+    let program = parts.next()?;
+    Some((program, parts.collect()))
+}
+
+/// Formats problems for text output, each followed by its source line.
+fn problem_lines(problems: &[ConfigProblem], content: &str) -> Vec<String> {
+    let source: Vec<&str> = content.lines().collect();
+    let mut lines = Vec::new();
+    for problem in problems {
+        let location = match problem.line {
+            Some(line) => format!("line {line}: "),
+            None => String::new(),
+        };
+        let key = match &problem.key {
+            Some(key) => format!("{}: ", key.cyan()),
+            None => String::new(),
+        };
+        lines.push(format!("  {location}{key}{}", problem.message));
+        if let Some(text) = problem.line.and_then(|line| source.get(line - 1)) {
+            lines.push(format!("      {}", format!("| {text}").dimmed()));
+        }
+    }
+    lines
+}
+
+/// Lists the models offered by a summary provider.
+fn run_summary_models(provider: Option<String>, refresh: bool, format: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+
+    let provider = provider
+        .or_else(|| std::env::var("LORE_SUMMARY_PROVIDER").ok())
+        .or_else(|| config.summary_provider.clone())
+        .ok_or_else(|| {
+            anyhow!(
+                "No summary provider configured. Pass --provider or run \
+                 'lore config set summary_provider <name>'."
+            )
+        })?;
+    let kind: SummaryProviderKind = provider.parse().map_err(|e: String| anyhow!(e))?;
+
+    let api_key = summary_api_key(&config, kind).ok_or_else(|| {
+        anyhow!(
+            "No API key configured for {kind}. Run 'lore config set summary_api_key_{kind} <key>'."
+        )
+    })?;
+
+    let models = list_models(kind, api_key, resolve_base_url(&config)?, refresh)?;
+    let configured = config
+        .summary_model_for_provider(&kind.to_string())
+        .unwrap_or_else(|| default_model(kind).to_string());
+
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            let output = serde_json::json!({
+                "provider": kind.to_string(),
+                "configured_model": configured,
+                "configured_model_listed": models.contains(&configured),
+                "models": models,
+            });
+            println!("{}", format.json_string(&output)?);
+        }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("config summary-models")),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            println!(
+                "{} {}",
+                format!("Models for {kind}").bold(),
+                format!("({})", models.len()).dimmed()
+            );
+            for model in &models {
+                if *model == configured {
+                    println!("{} {}", "*".green(), model.green());
+                } else {
+                    println!("  {model}");
+                }
+            }
+            if let Some(warning) = unlisted_model_warning(kind, &configured, &models) {
+                println!();
+                println!("{} {}", "Warning:".yellow(), warning);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the API key for a provider, preferring `LORE_SUMMARY_API_KEY`.
+///
+/// Providers that work without a key get an empty one when none is set.
+fn summary_api_key(config: &Config, kind: SummaryProviderKind) -> Option<String> {
+    std::env::var("LORE_SUMMARY_API_KEY")
+        .ok()
+        .or_else(|| config.summary_api_key_for_provider(&kind.to_string()))
+        .filter(|key| !key.is_empty())
+        .or_else(|| (!kind.requires_api_key()).then(String::new))
+}
+
+/// Returns the provider whose model list a newly set model key is checked
+/// against.
+///
+/// A `summary_model_<provider>` key names its provider. The plain
+/// `summary_model` key is checked against the active `summary_provider`,
+/// unless that provider has its own model override, since the value is then
+/// not used.
+fn model_provider_for_key(config: &Config, key: &str, value: &str) -> Option<String> {
+    if let Some(provider) = key.strip_prefix("summary_model_") {
+        return Some(provider.to_string());
+    }
+    if key != "summary_model" {
+        return None;
+    }
+    let provider = config.summary_provider.as_deref()?.to_lowercase();
+    (config.summary_model_for_provider(&provider).as_deref() == Some(value)).then_some(provider)
+}
+
+/// Warns when a newly set summary model is missing from the provider's list.
+///
+/// Skipped silently when the provider is unknown, no API key is configured,
+/// or the list cannot be fetched, so `config set` never fails on network
+/// problems.
+fn warn_if_unlisted_model(config: &Config, provider: &str, model: &str) {
+    let Ok(kind) = provider.parse::<SummaryProviderKind>() else {
+        return;
+    };
+    let Some(api_key) = summary_api_key(config, kind) else {
+        return;
+    };
+
+    let Ok(base_url) = resolve_base_url(config) else {
+        return;
+    };
+
+    match list_models(kind, api_key, base_url, false) {
+        Ok(models) => {
+            if let Some(warning) = unlisted_model_warning(kind, model, &models) {
+                println!("{} {}", "Warning:".yellow(), warning);
+            }
+        }
+        Err(e) => tracing::debug!("Could not fetch {kind} models to validate '{model}': {e}"),
+    }
+}
+
+/// Builds the warning shown when `model` is not offered by the provider.
+fn unlisted_model_warning(
+    kind: SummaryProviderKind,
+    model: &str,
+    models: &[String],
+) -> Option<String> {
+    if models.is_empty() || models.iter().any(|m| m == model) {
+        return None;
+    }
+    Some(format!(
+        "'{model}' is not in the {kind} model list. \
+         Run 'lore config summary-models --provider {kind}' to see available models."
+    ))
+}
+
 /// Masks a secret value for display, showing only the first 4 and last 4 characters.
 ///
 /// Short values (12 characters or fewer) are fully masked.
@@ -340,6 +867,33 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_editor_command_prefers_visual_and_splits_arguments() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(editor_command(env(&[])), None);
+        assert_eq!(editor_command(env(&[("EDITOR", "  ")])), None);
+        assert_eq!(
+            editor_command(env(&[("EDITOR", "vim")])),
+            Some(("vim".to_string(), vec![]))
+        );
+        assert_eq!(
+            editor_command(env(&[("EDITOR", "vim"), ("VISUAL", "code --wait")])),
+            Some(("code".to_string(), vec!["--wait".to_string()]))
+        );
+        // An empty VISUAL falls through to EDITOR
+        assert_eq!(
+            editor_command(env(&[("EDITOR", "nano"), ("VISUAL", "")])),
+            Some(("nano".to_string(), vec![]))
+        );
+    }
+
     fn create_test_config(dir: &TempDir) -> (std::path::PathBuf, Config) {
         let config_path = dir.path().join("config.yaml");
         let config = Config::default();
@@ -359,13 +913,26 @@ mod tests {
                 watchers: vec!["claude-code".to_string()],
                 auto_link: false,
                 auto_link_threshold: 0.7,
+                auto_link_audit: false,
                 commit_footer: false,
+                git_remotes: vec!["origin".to_string()],
                 summary_provider: None,
+                summary_model: None,
                 summary_model_anthropic: None,
                 summary_model_openai: None,
                 summary_model_openrouter: None,
+                summary_model_gemini: None,
                 summary_auto: false,
                 summary_auto_threshold: 4,
+                summary_max_attempts: 4,
+                summary_truncation: "head-and-tail".to_string(),
+                summary_base_url: None,
+                redact_secrets: false,
+                redact_patterns: Vec::new(),
+                redact_paths: false,
+                compress_content: false,
+                search_tokenizer: "porter".to_string(),
+                daemon: DaemonConfig::default(),
             },
         };
 
@@ -384,13 +951,29 @@ mod tests {
             watchers: vec!["aider".to_string(), "claude-code".to_string()],
             auto_link: true,
             auto_link_threshold: 0.8,
+            auto_link_audit: false,
             commit_footer: true,
+            git_remotes: vec!["upstream".to_string(), "origin".to_string()],
             summary_provider: Some("anthropic".to_string()),
+            summary_model: None,
             summary_model_anthropic: None,
             summary_model_openai: None,
             summary_model_openrouter: None,
+            summary_model_gemini: None,
             summary_auto: false,
             summary_auto_threshold: 4,
+            summary_max_attempts: 4,
+            summary_truncation: "middle-out".to_string(),
+            summary_base_url: Some("https://llm-gateway.internal/v1".to_string()),
+            redact_secrets: true,
+            redact_patterns: vec!["internal-[0-9]+".to_string()],
+            redact_paths: true,
+            compress_content: true,
+            search_tokenizer: "trigram".to_string(),
+            daemon: DaemonConfig {
+                debounce_ms: 250,
+                ..Default::default()
+            },
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -400,6 +983,8 @@ mod tests {
         assert!(json.contains("0.8"));
         assert!(json.contains("summary_provider"));
         assert!(json.contains("anthropic"));
+        assert!(json.contains("redact_patterns"));
+        assert!(json.contains("\"debounce_ms\":250"));
     }
 
     #[test]
@@ -417,6 +1002,27 @@ mod tests {
         assert!(reloaded.auto_link);
     }
 
+    #[test]
+    fn test_unset_after_set_restores_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let (config_path, _) = create_test_config(&temp_dir);
+
+        let mut config = Config::load_from_path(&config_path).unwrap();
+        config.set("watchers", "aider").unwrap();
+        config.set("auto_link_threshold", "0.9").unwrap();
+        config.save_to_path(&config_path).unwrap();
+
+        let mut config = Config::load_from_path(&config_path).unwrap();
+        config.unset("watchers").unwrap();
+        config.unset("auto_link_threshold").unwrap();
+        config.save_to_path(&config_path).unwrap();
+
+        let reloaded = Config::load_from_path(&config_path).unwrap();
+        let default = Config::default();
+        assert_eq!(reloaded.watchers, default.watchers);
+        assert_eq!(reloaded.auto_link_threshold, default.auto_link_threshold);
+    }
+
     #[test]
     fn test_run_set_watchers() {
         let temp_dir = TempDir::new().unwrap();
@@ -437,6 +1043,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unlisted_model_warning() {
+        let models = vec![
+            "anthropic/claude-haiku-4-5".to_string(),
+            "openai/gpt-4o-mini".to_string(),
+        ];
+        let kind = SummaryProviderKind::OpenRouter;
+
+        assert!(unlisted_model_warning(kind, "openai/gpt-4o-mini", &models).is_none());
+
+        let warning = unlisted_model_warning(kind, "openai/gpt-4o-mni", &models).unwrap();
+        assert!(warning.contains("'openai/gpt-4o-mni'"));
+        assert!(warning.contains("lore config summary-models --provider openrouter"));
+
+        // An empty listing says nothing about validity
+        assert!(unlisted_model_warning(kind, "anything", &[]).is_none());
+    }
+
+    #[test]
+    fn test_model_provider_for_key() {
+        let mut config = Config::default();
+        assert_eq!(
+            model_provider_for_key(&config, "summary_model_gemini", "m").as_deref(),
+            Some("gemini")
+        );
+        assert_eq!(model_provider_for_key(&config, "summary_model", "m"), None);
+        assert_eq!(
+            model_provider_for_key(&config, "summary_provider", "m"),
+            None
+        );
+
+        config.summary_provider = Some("openai-compatible".to_string());
+        config.summary_model = Some("llama3".to_string());
+        assert_eq!(
+            model_provider_for_key(&config, "summary_model", "llama3").as_deref(),
+            Some("openai-compatible")
+        );
+
+        // The active provider's own override wins, so the plain key is unused
+        config.summary_provider = Some("openai".to_string());
+        config.summary_model_openai = Some("gpt-4o-mini".to_string());
+        assert_eq!(
+            model_provider_for_key(&config, "summary_model", "llama3"),
+            None
+        );
+    }
+
     #[test]
     fn test_mask_secret_long_value() {
         let masked = mask_secret("sk-ant-api03-abcdef123456");
@@ -460,4 +1113,32 @@ mod tests {
         let masked = mask_secret("1234567890123");
         assert_eq!(masked, "1234...0123");
     }
+
+    #[test]
+    fn test_problem_lines_show_source_context() {
+        colored::control::set_override(false);
+        let problems = vec![
+            ConfigProblem {
+                key: Some("auto_link_threshold".to_string()),
+                line: Some(2),
+                message: "must be between 0.0 and 1.0, got 1.5".to_string(),
+            },
+            ConfigProblem {
+                key: None,
+                line: None,
+                message: "Invalid value: bad".to_string(),
+            },
+        ];
+
+        let lines = problem_lines(&problems, "auto_link: true\nauto_link_threshold: 1.5\n");
+
+        assert_eq!(
+            lines,
+            vec![
+                "  line 2: auto_link_threshold: must be between 0.0 and 1.0, got 1.5",
+                "      | auto_link_threshold: 1.5",
+                "  Invalid value: bad",
+            ]
+        );
+    }
 }
@@ -1,14 +1,19 @@
 //! Context command - show recent sessions for quick orientation.
 //!
 //! Provides a summary of recent sessions for the current repository,
-//! helping users quickly understand the recent development context.
+//! helping users quickly understand the recent development context. The
+//! CLI counterpart of the MCP `lore_get_context` tool; `--format markdown`
+//! renders the sessions and their summaries for pasting into a new chat.
 
-use anyhow::Result;
+use std::path::Path;
+
+use anyhow::{bail, Result};
 use colored::Colorize;
 use serde::Serialize;
 
 use crate::cli::OutputFormat;
-use crate::storage::{Annotation, Database, MessageContent, Summary, Tag};
+use crate::storage::{Annotation, Database, Message, MessageContent, Session, Summary, Tag};
+use crate::summarize::{generate_summary, SummarizeError};
 
 /// Maximum length for message preview snippets.
 const MESSAGE_PREVIEW_LENGTH: usize = 200;
@@ -16,23 +21,45 @@ const MESSAGE_PREVIEW_LENGTH: usize = 200;
 /// Number of recent messages to show in --last view.
 const LAST_MESSAGES_COUNT: usize = 3;
 
+/// Width a summary is cut to under a row of the session table.
+const SUMMARY_PREVIEW_LENGTH: usize = 100;
+
 /// Arguments for the context command.
 #[derive(clap::Args)]
 #[command(after_help = "EXAMPLES:\n    \
-    lore context              Show last 5 sessions for current repo\n    \
-    lore context --repo /path Show sessions for specific path\n    \
-    lore context --last       Show detailed summary of most recent session\n    \
-    lore context --json       Output as JSON")]
+    lore context                       Show last 5 sessions for current repo\n    \
+    lore context --repo /path          Show sessions for specific path\n    \
+    lore context --limit 10            Show the last 10 sessions\n    \
+    lore context --last                Show detailed summary of most recent session\n    \
+    lore context --format markdown     Render for pasting into a new AI chat\n    \
+    lore context --generate            Generate missing summaries first\n    \
+    lore context --format json         Output as JSON")]
 pub struct Args {
     /// Filter to sessions in this directory (prefix match)
     #[arg(short, long, value_name = "PATH")]
+    #[arg(long_help = "Show sessions recorded in this directory or below it.\n\
+        Defaults to the root of the git repository containing the current\n\
+        directory, or the current directory outside a repository.")]
     pub repo: Option<String>,
 
+    /// Maximum number of sessions to show
+    #[arg(short, long, default_value = "5", conflicts_with = "last")]
+    pub limit: usize,
+
     /// Show detailed summary of only the most recent session
     #[arg(long)]
     pub last: bool,
 
-    /// Output format: text (default), json
+    /// Generate summaries for sessions that have none
+    #[arg(long)]
+    #[arg(
+        long_help = "Generate a summary with the configured summary provider for\n\
+        each listed session that has none, and store it. Without this flag\n\
+        only stored summaries are shown."
+    )]
+    pub generate: bool,
+
+    /// Output format: text (default), json, markdown
     #[arg(short, long, value_enum, default_value = "text")]
     pub format: OutputFormat,
 }
@@ -48,6 +75,7 @@ struct SessionSummary {
     linked_commits: Vec<String>,
     working_directory: String,
     git_branch: Option<String>,
+    summary: Option<String>,
 }
 
 /// JSON output structure for detailed last session view.
@@ -102,23 +130,24 @@ pub fn run(args: Args) -> Result<()> {
             .unwrap_or_else(|_| r.clone()),
         Some(r) => r,
         None => std::env::current_dir()
-            .map(|p| p.to_string_lossy().to_string())
+            .map(|p| repo_root(&p))
             .unwrap_or_default(),
     };
 
-    let limit = if args.last { 1 } else { 5 };
+    let limit = if args.last { 1 } else { args.limit };
     let sessions = db.list_sessions(limit, Some(&working_dir))?;
 
     if sessions.is_empty() {
         match args.format {
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::Jsonl => {
                 let output = ContextOutput {
                     working_directory: working_dir,
                     sessions: vec![],
                 };
-                let json = serde_json::to_string_pretty(&output)?;
+                let json = args.format.json_string(&output)?;
                 println!("{json}");
             }
+            OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("context")),
             OutputFormat::Text | OutputFormat::Markdown => {
                 println!("{}", "No sessions found for this directory.".dimmed());
                 println!();
@@ -128,6 +157,10 @@ pub fn run(args: Args) -> Result<()> {
         return Ok(());
     }
 
+    if args.generate {
+        generate_missing_summaries(&db, &sessions)?;
+    }
+
     // Build session summaries with linked commits
     let mut summaries = Vec::new();
     for session in &sessions {
@@ -147,26 +180,41 @@ pub fn run(args: Args) -> Result<()> {
             linked_commits,
             working_directory: session.working_directory.clone(),
             git_branch: session.git_branch.clone(),
+            summary: db.get_summary(&session.id)?.map(|s| s.content),
         });
     }
 
     match args.format {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Jsonl => {
             if args.last {
                 // Detailed JSON output for --last
                 let session = &sessions[0];
                 let summary_struct = &summaries[0];
-                output_detailed_json(&db, session, summary_struct)?;
+                output_detailed_json(&db, session, summary_struct, args.format)?;
             } else {
                 let output = ContextOutput {
                     working_directory: working_dir,
                     sessions: summaries,
                 };
-                let json = serde_json::to_string_pretty(&output)?;
+                let json = args.format.json_string(&output)?;
                 println!("{json}");
             }
         }
-        OutputFormat::Text | OutputFormat::Markdown => {
+        OutputFormat::Markdown => {
+            let recent_messages = if args.last {
+                let messages = db.get_messages(&sessions[0].id)?;
+                let start = messages.len().saturating_sub(LAST_MESSAGES_COUNT);
+                messages[start..].to_vec()
+            } else {
+                Vec::new()
+            };
+            print!(
+                "{}",
+                render_markdown(&working_dir, &summaries, &recent_messages)
+            );
+        }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("context")),
+        OutputFormat::Text => {
             if args.last {
                 // Detailed view of the most recent session
                 let summary = &summaries[0];
@@ -229,6 +277,7 @@ pub fn run(args: Args) -> Result<()> {
                             crate::storage::MessageRole::User => "Human".green(),
                             crate::storage::MessageRole::Assistant => "Assistant".blue(),
                             crate::storage::MessageRole::System => "System".yellow(),
+                            crate::storage::MessageRole::Tool => "Tool".magenta(),
                         };
                         let content = truncate_content(&msg.content, MESSAGE_PREVIEW_LENGTH);
                         println!("  [{}] {}", role, content.dimmed());
@@ -276,6 +325,10 @@ pub fn run(args: Args) -> Result<()> {
                         commits_display,
                         started_display.dimmed()
                     );
+                    if let Some(ref text) = summary.summary {
+                        let preview = truncate_text(text, SUMMARY_PREVIEW_LENGTH);
+                        println!("{:<10}{}", "", preview.dimmed());
+                    }
                 }
             }
         }
@@ -284,11 +337,126 @@ pub fn run(args: Args) -> Result<()> {
     Ok(())
 }
 
+/// Returns the root of the git repository containing `dir`, or `dir` itself
+/// outside a repository.
+fn repo_root(dir: &Path) -> String {
+    match crate::git::repo_info(dir) {
+        Ok(info) if !info.path.is_empty() => {
+            // The work directory ends in a separator, which would stop the
+            // prefix match from finding sessions recorded at the root
+            let trimmed = info.path.trim_end_matches(std::path::MAIN_SEPARATOR);
+            if trimmed.is_empty() {
+                info.path
+            } else {
+                trimmed.to_string()
+            }
+        }
+        _ => dir.to_string_lossy().to_string(),
+    }
+}
+
+/// Generates and stores a summary for each session that has none.
+///
+/// Sessions without messages are skipped, as are sessions the provider
+/// fails on, with a warning, so one failure does not hide the others.
+fn generate_missing_summaries(db: &Database, sessions: &[Session]) -> Result<()> {
+    for session in sessions {
+        if db.get_summary(&session.id)?.is_some() {
+            continue;
+        }
+        let short_id = &session.id.to_string()[..8];
+        let messages = db.get_messages(&session.id)?;
+        match generate_summary(&messages, None) {
+            Ok(generated) => {
+                db.insert_summary(&Summary {
+                    id: uuid::Uuid::new_v4(),
+                    session_id: session.id,
+                    content: generated.content,
+                    generated_at: chrono::Utc::now(),
+                    model: Some(generated.model),
+                })?;
+            }
+            Err(SummarizeError::NotConfigured) => {
+                bail!(
+                    "Summary provider not configured.\n\n\
+                     Configure a summary provider first:\n  \
+                     lore init --force       (guided setup with hidden key input)\n  \
+                     lore config set summary_provider <anthropic|openai|openrouter|gemini|openai-compatible>"
+                );
+            }
+            Err(SummarizeError::EmptySession) => {}
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to summarize session {short_id}: {e}",
+                    "Warning:".yellow()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Renders sessions and their summaries as Markdown.
+///
+/// `recent_messages`, when not empty, are listed after the sessions as the
+/// tail of the most recent conversation.
+fn render_markdown(
+    working_dir: &str,
+    summaries: &[SessionSummary],
+    recent_messages: &[Message],
+) -> String {
+    let mut out = format!("# Recent AI sessions in `{working_dir}`\n");
+
+    for summary in summaries {
+        let started = chrono::DateTime::parse_from_rfc3339(&summary.started_at)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|_| summary.started_at.clone());
+        out.push_str(&format!(
+            "\n## {started} - {} session {}\n\n",
+            summary.tool, summary.id_short
+        ));
+        if let Some(ref branch) = summary.git_branch {
+            out.push_str(&format!("- Branch: `{branch}`\n"));
+        }
+        out.push_str(&format!("- Messages: {}\n", summary.message_count));
+        if !summary.linked_commits.is_empty() {
+            let commits: Vec<String> = summary
+                .linked_commits
+                .iter()
+                .map(|sha| format!("`{sha}`"))
+                .collect();
+            out.push_str(&format!("- Commits: {}\n", commits.join(", ")));
+        }
+        out.push('\n');
+        match summary.summary {
+            Some(ref text) => out.push_str(&format!("{}\n", text.trim())),
+            None => out.push_str("_No summary stored._\n"),
+        }
+    }
+
+    if !recent_messages.is_empty() {
+        out.push_str("\n### Recent messages\n\n");
+        for msg in recent_messages {
+            let role = match msg.role {
+                crate::storage::MessageRole::User => "Human",
+                crate::storage::MessageRole::Assistant => "Assistant",
+                crate::storage::MessageRole::System => "System",
+                crate::storage::MessageRole::Tool => "Tool",
+            };
+            let content = truncate_content(&msg.content, MESSAGE_PREVIEW_LENGTH);
+            out.push_str(&format!("- **{role}:** {content}\n"));
+        }
+    }
+
+    out
+}
+
 /// Outputs detailed JSON for a single session in --last mode.
 fn output_detailed_json(
     db: &Database,
     session: &crate::storage::Session,
     summary_struct: &SessionSummary,
+    format: OutputFormat,
 ) -> Result<()> {
     let session_summary: Option<Summary> = db.get_summary(&session.id)?;
     let annotations = db.get_annotations(&session.id)?;
@@ -327,7 +495,7 @@ fn output_detailed_json(
         recent_messages,
     };
 
-    let json = serde_json::to_string_pretty(&output)?;
+    let json = format.json_string(&output)?;
     println!("{json}");
     Ok(())
 }
@@ -350,7 +518,11 @@ fn print_annotations(annotations: &[Annotation]) {
 
 /// Truncates message content for preview display.
 fn truncate_content(content: &MessageContent, max_len: usize) -> String {
-    let text = content.text();
+    truncate_text(&content.text(), max_len)
+}
+
+/// Collapses text onto one line and truncates it for preview display.
+fn truncate_text(text: &str, max_len: usize) -> String {
     // Replace newlines with spaces for single-line preview
     let text = text.replace('\n', " ");
     // Collapse multiple spaces
@@ -359,6 +531,55 @@ fn truncate_content(content: &MessageContent, max_len: usize) -> String {
     if text.len() <= max_len {
         text
     } else {
-        format!("{}...", &text[..max_len.saturating_sub(3)])
+        let mut end = max_len.saturating_sub(3);
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        format!("{}...", &text[..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(id_short: &str, summary: Option<&str>) -> SessionSummary {
+        SessionSummary {
+            id: format!("{id_short}-0000-0000-0000-000000000000"),
+            id_short: id_short.to_string(),
+            tool: "claude-code".to_string(),
+            started_at: "2026-03-02T14:30:00+00:00".to_string(),
+            message_count: 12,
+            linked_commits: vec!["abc12345".to_string()],
+            working_directory: "/home/user/project".to_string(),
+            git_branch: Some("main".to_string()),
+            summary: summary.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = render_markdown(
+            "/home/user/project",
+            &[
+                summary("aaaa1111", Some("Added retry logic to the sync push.\n")),
+                summary("bbbb2222", None),
+            ],
+            &[],
+        );
+
+        assert!(markdown.starts_with("# Recent AI sessions in `/home/user/project`\n"));
+        assert!(markdown.contains("\n## 2026-03-02 14:30 - claude-code session aaaa1111\n"));
+        assert!(markdown.contains("- Branch: `main`\n- Messages: 12\n- Commits: `abc12345`\n"));
+        assert!(markdown.contains("\n\nAdded retry logic to the sync push.\n"));
+        assert!(markdown.contains("session bbbb2222\n"));
+        assert!(markdown.ends_with("_No summary stored._\n"));
+        assert!(!markdown.contains("Recent messages"));
+    }
+
+    #[test]
+    fn test_truncate_text_respects_char_boundaries() {
+        assert_eq!(truncate_text("short\n  text", 20), "short text");
+        assert_eq!(truncate_text("héllo wörld", 5), "h...");
     }
 }
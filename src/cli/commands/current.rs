@@ -51,15 +51,16 @@ pub fn run(args: Args) -> Result<()> {
     };
 
     match args.format {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Jsonl => {
             let output = CurrentOutput {
                 session_id: session_id.clone(),
                 working_directory: working_dir,
                 source: source.to_string(),
             };
-            let json = serde_json::to_string_pretty(&output)?;
+            let json = args.format.json_string(&output)?;
             println!("{json}");
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("current")),
         OutputFormat::Text | OutputFormat::Markdown => {
             if let Some(id) = session_id {
                 let short_id = &id[..8.min(id.len())];
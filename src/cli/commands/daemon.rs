@@ -6,11 +6,15 @@
 use anyhow::{Context, Result};
 use clap::Subcommand;
 use colored::Colorize;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
 
-use crate::daemon::{send_command_sync, DaemonCommand, DaemonResponse, DaemonState};
+use crate::cli::OutputFormat;
+use crate::daemon::{send_command_sync, DaemonCommand, DaemonHealth, DaemonResponse, DaemonState};
 
 /// Daemon management subcommands.
 #[derive(Subcommand)]
@@ -35,22 +39,30 @@ pub enum DaemonSubcommand {
 
     /// Show daemon status and statistics
     #[command(long_about = "Shows whether the daemon is running, its PID, uptime,\n\
-        and statistics about watched files and imported sessions.")]
-    Status,
+        and statistics about watched files and imported sessions.\n\n\
+        The last activity time shows when a watcher last saw a file\n\
+        change, which helps spot a watcher that has silently stalled.")]
+    Status {
+        /// Output format: text (default), json
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
 
     /// Show daemon logs
     #[command(long_about = "Displays recent log output from the daemon. Use -f to\n\
         follow the log in real-time (like 'tail -f').")]
     Logs {
-        /// Number of lines to show
+        /// Number of lines to show, before following with -f
         #[arg(short = 'n', long, default_value = "20", value_name = "N")]
         lines: usize,
 
         /// Follow log output in real-time (like tail -f)
         #[arg(short, long)]
         #[arg(
-            long_help = "Continuously display new log lines as they are written.\n\
-            Press Ctrl+C to stop following."
+            long_help = "After the last --lines lines, continuously display new log\n\
+            lines as they are written. If the log file is truncated or\n\
+            replaced, as log rotation does, it is reopened and read from\n\
+            the start. Press Ctrl+C to stop following."
         )]
         follow: bool,
     },
@@ -60,15 +72,26 @@ pub enum DaemonSubcommand {
         long_about = "Installs the Lore daemon as a system service that starts\n\
         automatically on user login.\n\n\
         On macOS: Creates a launchd plist in ~/Library/LaunchAgents/\n\
-        On Linux: Creates a systemd user unit in ~/.config/systemd/user/"
+        On Linux: Creates a systemd user unit in ~/.config/systemd/user/\n\n\
+        The service runs 'lore daemon start --foreground' and is restarted\n\
+        if it exits with an error. Use --no-start to only write the file and\n\
+        print the launchctl/systemctl command to enable it yourself.",
+        visible_alias = "install-service"
     )]
-    Install,
+    Install {
+        /// Write the service file without loading or starting it
+        #[arg(long)]
+        #[arg(long_help = "Only write the plist or unit file. The service is not\n\
+            loaded or started; the command to enable it is printed instead.")]
+        no_start: bool,
+    },
 
     /// Uninstall the daemon system service
     #[command(
         long_about = "Removes the Lore daemon system service and stops it if running.\n\n\
         On macOS: Unloads and removes the launchd plist\n\
-        On Linux: Disables and removes the systemd user unit"
+        On Linux: Disables and removes the systemd user unit",
+        visible_alias = "uninstall-service"
     )]
     Uninstall,
 }
@@ -80,9 +103,11 @@ pub enum DaemonSubcommand {
     lore daemon start --foreground Run in foreground\n    \
     lore daemon stop              Stop the daemon\n    \
     lore daemon status            Check if running\n    \
+    lore daemon status --format json  Health as JSON for scripts\n    \
     lore daemon logs              Show recent logs\n    \
     lore daemon logs -f           Follow logs in real-time\n    \
     lore daemon install           Install as system service\n    \
+    lore daemon install --no-start Write the service file only\n    \
     lore daemon uninstall         Remove system service")]
 pub struct Args {
     /// Daemon subcommand to run
@@ -95,9 +120,9 @@ pub fn run(args: Args) -> Result<()> {
     match args.command {
         DaemonSubcommand::Start { foreground } => run_start(foreground),
         DaemonSubcommand::Stop => run_stop(),
-        DaemonSubcommand::Status => run_status(),
+        DaemonSubcommand::Status { format } => run_status(format),
         DaemonSubcommand::Logs { lines, follow } => run_logs(lines, follow),
-        DaemonSubcommand::Install => run_install(),
+        DaemonSubcommand::Install { no_start } => run_install(no_start),
         DaemonSubcommand::Uninstall => run_uninstall(),
     }
 }
@@ -118,9 +143,9 @@ fn run_start(foreground: bool) -> Result<()> {
     }
 
     if foreground {
-        println!("{}", "Starting daemon in foreground...".green());
-        println!("{}", "Press Ctrl+C to stop".dimmed());
-        println!();
+        note!("{}", "Starting daemon in foreground...".green());
+        note!("{}", "Press Ctrl+C to stop".dimmed());
+        note!();
 
         // Run the daemon in the current process
         let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
@@ -160,7 +185,7 @@ fn run_start(foreground: bool) -> Result<()> {
 ///
 /// This is the fallback method when the daemon is not configured as a managed service.
 fn start_daemon_directly(state: &DaemonState) -> Result<()> {
-    println!("{}", "Starting daemon in background...".green());
+    note!("{}", "Starting daemon in background...".green());
 
     let current_exe = std::env::current_exe().context("Failed to get current executable path")?;
 
@@ -176,12 +201,12 @@ fn start_daemon_directly(state: &DaemonState) -> Result<()> {
         .spawn()
         .context("Failed to spawn daemon process")?;
 
-    println!(
+    note!(
         "{} Daemon started with PID {}",
         "Success:".green(),
         child.id()
     );
-    println!(
+    note!(
         "{}",
         format!("Logs available at: {:?}", state.log_file).dimmed()
     );
@@ -237,7 +262,7 @@ fn try_stop_macos_service() -> Result<Option<bool>> {
 
     // Check for Homebrew-managed service first
     if brew_plist.exists() {
-        println!("{}", "Stopping Homebrew-managed service...".green());
+        note!("{}", "Stopping Homebrew-managed service...".green());
 
         let output = Command::new("brew")
             .args(["services", "stop", "lore"])
@@ -246,7 +271,7 @@ fn try_stop_macos_service() -> Result<Option<bool>> {
         match output {
             Ok(result) => {
                 if result.status.success() {
-                    println!("{} Homebrew service stopped", "Success:".green());
+                    note!("{} Homebrew service stopped", "Success:".green());
                     return Ok(Some(true));
                 } else {
                     let stderr = String::from_utf8_lossy(&result.stderr);
@@ -273,7 +298,7 @@ fn try_stop_macos_service() -> Result<Option<bool>> {
 
     // Check for native launchd service
     if native_plist.exists() {
-        println!("{}", "Stopping native launchd service...".green());
+        note!("{}", "Stopping native launchd service...".green());
 
         let output = Command::new("launchctl")
             .args(["unload", "-w"])
@@ -282,7 +307,7 @@ fn try_stop_macos_service() -> Result<Option<bool>> {
             .context("Failed to run launchctl unload")?;
 
         if output.status.success() {
-            println!("{} Launchd service stopped", "Success:".green());
+            note!("{} Launchd service stopped", "Success:".green());
             return Ok(Some(true));
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -315,7 +340,7 @@ fn try_start_macos_service() -> Result<Option<bool>> {
 
     // Check for Homebrew-managed service first
     if brew_plist.exists() {
-        println!("{}", "Starting Homebrew-managed service...".green());
+        note!("{}", "Starting Homebrew-managed service...".green());
 
         let output = Command::new("brew")
             .args(["services", "start", "lore"])
@@ -324,7 +349,7 @@ fn try_start_macos_service() -> Result<Option<bool>> {
         match output {
             Ok(result) => {
                 if result.status.success() {
-                    println!("{} Homebrew service started", "Success:".green());
+                    note!("{} Homebrew service started", "Success:".green());
                     return Ok(Some(true));
                 } else {
                     let stderr = String::from_utf8_lossy(&result.stderr);
@@ -351,7 +376,7 @@ fn try_start_macos_service() -> Result<Option<bool>> {
 
     // Check for native launchd service
     if native_plist.exists() {
-        println!("{}", "Starting native launchd service...".green());
+        note!("{}", "Starting native launchd service...".green());
 
         let output = Command::new("launchctl")
             .args(["load", "-w"])
@@ -360,12 +385,12 @@ fn try_start_macos_service() -> Result<Option<bool>> {
             .context("Failed to run launchctl load")?;
 
         if output.status.success() {
-            println!("{} Launchd service started", "Success:".green());
+            note!("{} Launchd service started", "Success:".green());
             return Ok(Some(true));
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
             if stderr.contains("service already loaded") {
-                println!("{} Launchd service already loaded", "Success:".green());
+                note!("{} Launchd service already loaded", "Success:".green());
                 return Ok(Some(true));
             }
             println!(
@@ -393,7 +418,7 @@ fn try_stop_linux_service() -> Result<Option<bool>> {
     let unit_path = get_systemd_unit_path()?;
 
     if unit_path.exists() {
-        println!("{}", "Stopping systemd user service...".green());
+        note!("{}", "Stopping systemd user service...".green());
 
         let output = Command::new("systemctl")
             .args(["--user", "stop", SYSTEMD_SERVICE_NAME])
@@ -401,7 +426,7 @@ fn try_stop_linux_service() -> Result<Option<bool>> {
             .context("Failed to run systemctl stop")?;
 
         if output.status.success() {
-            println!("{} Systemd service stopped", "Success:".green());
+            note!("{} Systemd service stopped", "Success:".green());
             return Ok(Some(true));
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -430,7 +455,7 @@ fn try_start_linux_service() -> Result<Option<bool>> {
     let unit_path = get_systemd_unit_path()?;
 
     if unit_path.exists() {
-        println!("{}", "Starting systemd user service...".green());
+        note!("{}", "Starting systemd user service...".green());
 
         let output = Command::new("systemctl")
             .args(["--user", "start", SYSTEMD_SERVICE_NAME])
@@ -438,7 +463,7 @@ fn try_start_linux_service() -> Result<Option<bool>> {
             .context("Failed to run systemctl start")?;
 
         if output.status.success() {
-            println!("{} Systemd service started", "Success:".green());
+            note!("{} Systemd service started", "Success:".green());
 
             // Get the PID from systemctl
             if let Ok(show_output) = Command::new("systemctl")
@@ -449,7 +474,7 @@ fn try_start_linux_service() -> Result<Option<bool>> {
                 if let Some(pid_str) = stdout.trim().strip_prefix("MainPID=") {
                     if let Ok(pid) = pid_str.parse::<u32>() {
                         if pid > 0 {
-                            println!("{}", format!("Daemon running with PID {}", pid).dimmed());
+                            note!("{}", format!("Daemon running with PID {}", pid).dimmed());
                         }
                     }
                 }
@@ -477,22 +502,22 @@ fn try_start_linux_service() -> Result<Option<bool>> {
 /// This is the fallback method when the daemon is not running as a managed service.
 fn stop_daemon_via_ipc(state: &DaemonState) -> Result<()> {
     let pid = state.get_pid().unwrap_or(0);
-    println!("Stopping daemon (PID {pid})...");
+    note!("Stopping daemon (PID {pid})...");
 
     // Try to send stop command via socket first
     match send_command_sync(&state.socket_path, DaemonCommand::Stop) {
         Ok(DaemonResponse::Stopping) => {
-            println!("{}", "Stop command sent".green());
+            note!("{}", "Stop command sent".green());
 
             // Wait for daemon to stop
             for i in 0..30 {
                 std::thread::sleep(std::time::Duration::from_millis(100));
                 if !state.is_running() {
-                    println!("{}", "Daemon stopped".green());
+                    note!("{}", "Daemon stopped".green());
                     return Ok(());
                 }
                 if i == 10 {
-                    println!("{}", "Waiting for daemon to stop...".dimmed());
+                    note!("{}", "Waiting for daemon to stop...".dimmed());
                 }
             }
 
@@ -521,7 +546,7 @@ fn stop_daemon_via_ipc(state: &DaemonState) -> Result<()> {
     if !state.is_running() {
         // Clean up any leftover files
         let _ = state.cleanup();
-        println!("{}", "Daemon stopped".green());
+        note!("{}", "Daemon stopped".green());
     } else {
         println!("{}", "Warning: Daemon may still be running".yellow());
     }
@@ -550,10 +575,95 @@ fn kill_process(pid: u32) -> Result<()> {
     Ok(())
 }
 
+/// Daemon status for JSON output.
+#[derive(Serialize)]
+struct DaemonStatusOutput {
+    running: bool,
+    pid: Option<u32>,
+    version: Option<String>,
+    /// Health snapshot, absent when the daemon is not running or the socket
+    /// does not answer the health command.
+    health: Option<DaemonHealth>,
+}
+
+/// Requests a health snapshot from the running daemon.
+///
+/// Returns `None` if the socket is unavailable or the daemon predates the
+/// health command.
+fn fetch_health(state: &DaemonState) -> Option<DaemonHealth> {
+    match send_command_sync(&state.socket_path, DaemonCommand::Health) {
+        Ok(DaemonResponse::Health(health)) => Some(health),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::debug!("Failed to get daemon health: {}", e);
+            None
+        }
+    }
+}
+
+/// Formats how long ago `at` was relative to `now`, e.g. "3m 12s ago".
+fn format_ago(at: chrono::DateTime<chrono::Utc>, now: chrono::DateTime<chrono::Utc>) -> String {
+    let seconds = now.signed_duration_since(at).num_seconds().max(0) as u64;
+    format!("{} ago", format_duration(seconds))
+}
+
+/// Prints the daemon status as JSON.
+fn print_status_json(state: &DaemonState, format: OutputFormat) -> Result<()> {
+    let running = state.is_running();
+    let mut output = DaemonStatusOutput {
+        running,
+        pid: if running { state.get_pid() } else { None },
+        version: None,
+        health: None,
+    };
+
+    if running {
+        if let Ok(DaemonResponse::Status { pid, version, .. }) =
+            send_command_sync(&state.socket_path, DaemonCommand::Status)
+        {
+            output.pid = Some(pid);
+            output.version = Some(version);
+        }
+        output.health = fetch_health(state);
+    }
+
+    println!("{}", format.json_string(&output)?);
+    Ok(())
+}
+
+/// Prints the health lines of the text status output.
+fn print_health(health: &DaemonHealth) {
+    let now = chrono::Utc::now();
+    println!("  {} {}", "Files parsed:".dimmed(), health.files_parsed);
+    match health.last_activity {
+        Some(at) => println!("  {} {}", "Last activity:".dimmed(), format_ago(at, now)),
+        None => println!(
+            "  {} {}",
+            "Last activity:".dimmed(),
+            "none since start".dimmed()
+        ),
+    }
+    if health.last_event_by_watcher.len() > 1 {
+        for (watcher, at) in &health.last_event_by_watcher {
+            println!(
+                "    {} {}",
+                format!("{watcher}:").dimmed(),
+                format_ago(*at, now)
+            );
+        }
+    }
+}
+
 /// Shows the daemon status.
-fn run_status() -> Result<()> {
+fn run_status(format: OutputFormat) -> Result<()> {
     let state = DaemonState::new()?;
 
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl => return print_status_json(&state, format),
+        OutputFormat::Csv => return Err(format.unsupported("daemon status")),
+        OutputFormat::Text | OutputFormat::Markdown => {}
+    }
+
     if !state.is_running() {
         println!("{}", "Daemon is not running".yellow());
 
@@ -611,6 +721,9 @@ fn run_status() -> Result<()> {
                         stats.errors.to_string().red()
                     );
                 }
+                if let Some(health) = fetch_health(&state) {
+                    print_health(&health);
+                }
             }
 
             // Show service installation status
@@ -727,32 +840,24 @@ fn run_logs(lines: usize, follow: bool) -> Result<()> {
     }
 
     if follow {
-        // Follow mode - continuously read new lines
-        println!("{}", format!("Following {:?}...", state.log_file).dimmed());
-        println!("{}", "Press Ctrl+C to stop".dimmed());
-        println!();
-
-        let file = File::open(&state.log_file).context("Failed to open log file")?;
-        let mut reader = BufReader::new(file);
-
-        // Seek to end
-        reader.seek(SeekFrom::End(0))?;
+        // Follow mode - show the backlog, then continuously read new lines
+        note!("{}", format!("Following {:?}...", state.log_file).dimmed());
+        note!("{}", "Press Ctrl+C to stop".dimmed());
+        note!();
+
+        let (mut follower, backlog) =
+            LogFollower::open(state.log_file.clone(), lines).context("Failed to open log file")?;
+        for line in &backlog {
+            println!("{line}");
+        }
 
+        let mut stdout = io::stdout();
         loop {
-            let mut line = String::new();
-            match reader.read_line(&mut line) {
-                Ok(0) => {
-                    // No new data, sleep briefly
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                }
-                Ok(_) => {
-                    print!("{line}");
-                }
-                Err(e) => {
-                    tracing::debug!("Error reading log: {}", e);
-                    break;
-                }
+            if let Err(e) = follower.poll(&mut stdout) {
+                tracing::debug!("Error reading log: {}", e);
+                break;
             }
+            std::thread::sleep(FOLLOW_POLL);
         }
     } else {
         // Show last N lines
@@ -775,6 +880,105 @@ fn run_logs(lines: usize, follow: bool) -> Result<()> {
     Ok(())
 }
 
+/// How often `logs --follow` checks the log file for new output.
+const FOLLOW_POLL: Duration = Duration::from_millis(100);
+
+/// Reads output appended to a log file, like `tail -F`.
+///
+/// The file is reopened and read from the start when it shrinks below the
+/// read position or, on Unix, when the path points at a different file, as
+/// happens when the log is truncated or rotated.
+struct LogFollower {
+    path: PathBuf,
+    reader: Option<BufReader<File>>,
+    position: u64,
+}
+
+impl LogFollower {
+    /// Opens the log at `path` for following from its current end.
+    ///
+    /// Returns the follower and up to `backlog` of the file's last lines.
+    fn open(path: PathBuf, backlog: usize) -> io::Result<(Self, Vec<String>)> {
+        let mut reader = BufReader::new(File::open(&path)?);
+        let mut contents = Vec::new();
+        reader.read_to_end(&mut contents)?;
+
+        let text = String::from_utf8_lossy(&contents);
+        let lines: Vec<&str> = text.lines().collect();
+        let start = lines.len().saturating_sub(backlog);
+        let backlog = lines[start..].iter().map(|l| l.to_string()).collect();
+
+        let follower = Self {
+            path,
+            reader: Some(reader),
+            position: contents.len() as u64,
+        };
+        Ok((follower, backlog))
+    }
+
+    /// Writes everything appended since the last call to `out`.
+    ///
+    /// Output left in a rotated file is written before switching to the new
+    /// one. A missing file is not an error; it is picked up once recreated.
+    fn poll(&mut self, out: &mut impl Write) -> io::Result<()> {
+        self.drain(out)?;
+        if self.was_rotated() {
+            self.reader = None;
+        }
+        if self.reader.is_none() {
+            match File::open(&self.path) {
+                Ok(file) => {
+                    self.reader = Some(BufReader::new(file));
+                    self.position = 0;
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+                Err(e) => return Err(e),
+            }
+            self.drain(out)?;
+        }
+        Ok(())
+    }
+
+    /// Copies what is available from the open file to `out`.
+    fn drain(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let Some(reader) = self.reader.as_mut() else {
+            return Ok(());
+        };
+        let mut buf = Vec::new();
+        while reader.read_until(b'\n', &mut buf)? > 0 {
+            self.position += buf.len() as u64;
+            out.write_all(&buf)?;
+            buf.clear();
+        }
+        out.flush()
+    }
+
+    /// Returns true if the file at the path was truncated or replaced since
+    /// it was opened.
+    fn was_rotated(&self) -> bool {
+        let Some(reader) = &self.reader else {
+            return false;
+        };
+        // While a rotated file has not been recreated, keep the old one
+        let Ok(current) = fs::metadata(&self.path) else {
+            return false;
+        };
+        if current.len() < self.position {
+            return true;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if let Ok(open) = reader.get_ref().metadata() {
+                return open.ino() != current.ino() || open.dev() != current.dev();
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = reader;
+        false
+    }
+}
+
 // Service installation constants
 #[cfg(any(target_os = "macos", test))]
 const LAUNCHD_LABEL: &str = "com.lore.daemon";
@@ -878,6 +1082,20 @@ fn get_systemd_unit_path() -> Result<std::path::PathBuf> {
         .join(format!("{SYSTEMD_SERVICE_NAME}.service")))
 }
 
+/// Returns the command that loads the launchd service from `plist_path`.
+#[cfg(any(target_os = "macos", test))]
+fn launchd_load_command(plist_path: &std::path::Path) -> String {
+    format!("launchctl load -w {}", plist_path.display())
+}
+
+/// Returns the command that enables and starts the systemd user service.
+#[cfg(any(target_os = "linux", test))]
+fn systemd_enable_command() -> String {
+    format!(
+        "systemctl --user daemon-reload && systemctl --user enable --now {SYSTEMD_SERVICE_NAME}"
+    )
+}
+
 /// Gets the logs directory path, creating it if necessary.
 #[cfg(target_os = "macos")]
 fn ensure_logs_dir() -> Result<std::path::PathBuf> {
@@ -888,22 +1106,25 @@ fn ensure_logs_dir() -> Result<std::path::PathBuf> {
 }
 
 /// Installs the daemon as a system service.
-fn run_install() -> Result<()> {
+///
+/// With `no_start`, only the service file is written and the command to
+/// enable it is printed for the user to run.
+fn run_install(no_start: bool) -> Result<()> {
     let lore_exe = std::env::current_exe().context("Failed to get current executable path")?;
 
     #[cfg(target_os = "macos")]
     {
-        install_launchd_service(&lore_exe)
+        install_launchd_service(&lore_exe, no_start)
     }
 
     #[cfg(target_os = "linux")]
     {
-        install_systemd_service(&lore_exe)
+        install_systemd_service(&lore_exe, no_start)
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
-        let _ = lore_exe; // Suppress unused variable warning
+        let _ = (lore_exe, no_start); // Suppress unused variable warning
         anyhow::bail!(
             "Service installation is not supported on this platform.\n\
              Supported platforms: macOS (launchd), Linux (systemd)"
@@ -913,7 +1134,7 @@ fn run_install() -> Result<()> {
 
 /// Installs the daemon as a macOS launchd service.
 #[cfg(target_os = "macos")]
-fn install_launchd_service(lore_exe: &std::path::Path) -> Result<()> {
+fn install_launchd_service(lore_exe: &std::path::Path, no_start: bool) -> Result<()> {
     let plist_path = get_launchd_plist_path()?;
     let logs_dir = ensure_logs_dir()?;
 
@@ -940,6 +1161,16 @@ fn install_launchd_service(lore_exe: &std::path::Path) -> Result<()> {
     let plist_content = generate_launchd_plist(lore_exe, &logs_dir);
     std::fs::write(&plist_path, &plist_content).context("Failed to write plist file")?;
 
+    if no_start {
+        println!("{}", "Service file written.".green());
+        println!();
+        println!("  {} {}", "Plist:".dimmed(), plist_path.display());
+        println!();
+        println!("To load the service and start it on login, run:");
+        println!("  {}", launchd_load_command(&plist_path).cyan());
+        return Ok(());
+    }
+
     println!("{}", "Installing Lore daemon service...".green());
 
     // Load the service immediately
@@ -979,7 +1210,7 @@ fn install_launchd_service(lore_exe: &std::path::Path) -> Result<()> {
 
 /// Installs the daemon as a Linux systemd user service.
 #[cfg(target_os = "linux")]
-fn install_systemd_service(lore_exe: &std::path::Path) -> Result<()> {
+fn install_systemd_service(lore_exe: &std::path::Path, no_start: bool) -> Result<()> {
     let unit_path = get_systemd_unit_path()?;
 
     // Check if already installed
@@ -1005,6 +1236,16 @@ fn install_systemd_service(lore_exe: &std::path::Path) -> Result<()> {
     let unit_content = generate_systemd_unit(lore_exe);
     std::fs::write(&unit_path, &unit_content).context("Failed to write unit file")?;
 
+    if no_start {
+        println!("{}", "Service file written.".green());
+        println!();
+        println!("  {} {}", "Unit file:".dimmed(), unit_path.display());
+        println!();
+        println!("To enable the service and start it now, run:");
+        println!("  {}", systemd_enable_command().cyan());
+        return Ok(());
+    }
+
     println!("{}", "Installing Lore daemon service...".green());
 
     // Reload systemd user daemon to pick up the new unit
@@ -1217,6 +1458,62 @@ fn uninstall_systemd_service() -> Result<()> {
 mod tests {
     use super::*;
 
+    fn append(path: &std::path::Path, text: &str) {
+        let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn poll(follower: &mut LogFollower) -> String {
+        let mut out = Vec::new();
+        follower.poll(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_log_follower_backlog_and_new_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon.log");
+        fs::write(&path, "one\ntwo\nthree\n").unwrap();
+
+        let (mut follower, backlog) = LogFollower::open(path.clone(), 2).unwrap();
+        assert_eq!(backlog, vec!["two", "three"]);
+        assert_eq!(poll(&mut follower), "");
+
+        append(&path, "four\nfi");
+        assert_eq!(poll(&mut follower), "four\nfi");
+        append(&path, "ve\n");
+        assert_eq!(poll(&mut follower), "ve\n");
+    }
+
+    #[test]
+    fn test_log_follower_reopens_truncated_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon.log");
+        fs::write(&path, "old line one\nold line two\n").unwrap();
+        let (mut follower, _) = LogFollower::open(path.clone(), 0).unwrap();
+
+        fs::write(&path, "new\n").unwrap();
+        assert_eq!(poll(&mut follower), "new\n");
+        append(&path, "next\n");
+        assert_eq!(poll(&mut follower), "next\n");
+    }
+
+    #[test]
+    fn test_log_follower_follows_rotated_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon.log");
+        fs::write(&path, "first\n").unwrap();
+        let (mut follower, _) = LogFollower::open(path.clone(), 0).unwrap();
+
+        // Lines written just before rotation are not lost
+        append(&path, "last before rotation\n");
+        fs::rename(&path, dir.path().join("daemon.log.1")).unwrap();
+        assert_eq!(poll(&mut follower), "last before rotation\n");
+
+        fs::write(&path, "after rotation, a longer first line\n").unwrap();
+        assert_eq!(poll(&mut follower), "after rotation, a longer first line\n");
+    }
+
     #[test]
     fn test_format_duration_seconds() {
         assert_eq!(format_duration(0), "0s");
@@ -1396,6 +1693,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_ago() {
+        let now = chrono::Utc::now();
+        assert_eq!(
+            format_ago(now - chrono::Duration::seconds(192), now),
+            "3m 12s ago"
+        );
+        // Clock skew never produces a negative age
+        assert_eq!(
+            format_ago(now + chrono::Duration::seconds(5), now),
+            "0s ago"
+        );
+    }
+
+    #[test]
+    fn test_daemon_status_output_json_shape() {
+        let output = DaemonStatusOutput {
+            running: false,
+            pid: None,
+            version: None,
+            health: None,
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["running"], false);
+        assert!(json["health"].is_null());
+    }
+
+    #[test]
+    fn test_service_enable_commands() {
+        let plist = std::path::Path::new("/Users/test/Library/LaunchAgents/com.lore.daemon.plist");
+        assert_eq!(
+            launchd_load_command(plist),
+            "launchctl load -w /Users/test/Library/LaunchAgents/com.lore.daemon.plist"
+        );
+        assert!(systemd_enable_command().ends_with("systemctl --user enable --now lore"));
+    }
+
     #[test]
     fn test_generate_launchd_plist_handles_special_paths() {
         // Test with path containing spaces
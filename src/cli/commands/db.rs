@@ -1,16 +1,24 @@
-//! Database management command - vacuum, prune, and stats.
+//! Database management command - vacuum, compact, prune, stats, repair,
+//! and reindex.
 //!
 //! Provides subcommands for managing the Lore database including:
 //! - vacuum: Reclaim disk space
+//! - compact: Compress stored message content
 //! - prune: Delete old sessions
 //! - stats: Show database statistics
+//! - repair: Recompute session message counts and end times, and rebuild
+//!   the file index
+//! - reindex: Rebuild the search index, optionally with a new tokenizer
 
 use std::io::{self, Write};
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use chrono::{Duration, Utc};
 use colored::Colorize;
 
+use crate::cli::format::{format_cost, format_token_usage};
+use crate::config::Config;
+use crate::storage::db::{ModelUsage, RepairReport, SearchTokenizer};
 use crate::storage::Database;
 
 /// Arguments for the db command.
@@ -18,8 +26,14 @@ use crate::storage::Database;
 #[command(after_help = "EXAMPLES:\n    \
     lore db stats                   Show database statistics\n    \
     lore db vacuum                  Reclaim unused space\n    \
+    lore db compact                 Compress large message content\n    \
+    lore db repair                  Fix stale session message counts\n    \
+    lore db reindex --tokenizer trigram\n    \
+                                    Rebuild search for substring matches\n    \
     lore db prune --older-than 90d  Delete sessions older than 90 days\n    \
-    lore db prune --older-than 6m --dry-run  Preview what would be deleted")]
+    lore db prune --older-than 6m --dry-run  Preview what would be deleted\n    \
+    lore db prune --older-than 30d --repo ~/scratch --tool cursor\n    \
+                                    Only prune one tool's sessions in one repo")]
 pub struct Args {
     /// Database subcommand to run
     #[command(subcommand)]
@@ -37,10 +51,21 @@ pub enum DbCommand {
     )]
     Vacuum,
 
+    /// Compress large message content already in the database
+    #[command(
+        long_about = "Compresses the stored content of messages written before\n\
+        compress_content was enabled, then runs VACUUM to return the\n\
+        freed space. Only content over a size threshold is compressed.\n\
+        Compressed messages read back unchanged whether or not\n\
+        compress_content stays enabled."
+    )]
+    Compact,
+
     /// Delete sessions older than a specified duration
     #[command(
         long_about = "Deletes sessions older than the specified duration along with\n\
-        all their messages and links. Use --dry-run to preview what\n\
+        all their messages and links. Use --repo and --tool to limit\n\
+        pruning to matching sessions, and --dry-run to preview what\n\
         would be deleted without making changes."
     )]
     Prune(PruneArgs),
@@ -54,16 +79,47 @@ pub enum DbCommand {
         - Breakdown by AI tool"
     )]
     Stats,
+
+    /// Recompute session message counts and end times from stored messages
+    #[command(
+        long_about = "Recomputes each session's message count from the messages\n\
+        table and corrects any that are out of date. Sessions with no\n\
+        end time get one from their latest message. Safe to run\n\
+        repeatedly; a second run reports nothing to fix. The index of\n\
+        files each session touched is also rebuilt from the messages."
+    )]
+    Repair,
+
+    /// Rebuild the full-text search index
+    #[command(
+        long_about = "Drops the full-text search index and rebuilds it from the\n\
+        stored messages and sessions, using the search_tokenizer setting.\n\
+        Changing search_tokenizer has no effect on an existing index until\n\
+        it is rebuilt this way."
+    )]
+    Reindex {
+        /// Tokenizer to rebuild with: porter, unicode61, or trigram
+        #[arg(long, value_name = "TOKENIZER")]
+        #[arg(
+            long_help = "Tokenizer to rebuild the message index with, saved as the\n\
+            search_tokenizer setting first:\n  \
+            - porter: words with English stemming (default)\n  \
+            - unicode61: words without stemming\n  \
+            - trigram: any substring of three or more characters"
+        )]
+        tokenizer: Option<String>,
+    },
 }
 
 /// Arguments for the prune subcommand.
 #[derive(clap::Args)]
 pub struct PruneArgs {
-    /// Delete sessions older than this duration (e.g., 90d, 6m, 1y)
+    /// Delete sessions older than this duration (e.g., 12h, 90d, 6m, 1y)
     #[arg(long, value_name = "DURATION")]
     #[arg(
         long_help = "Duration string specifying how old sessions must be to delete.\n\
         Supported formats:\n  \
+        - Nh: N hours (e.g., 12h)\n  \
         - Nd: N days (e.g., 90d)\n  \
         - Nw: N weeks (e.g., 12w)\n  \
         - Nm: N months (e.g., 6m)\n  \
@@ -71,6 +127,18 @@ pub struct PruneArgs {
     )]
     pub older_than: String,
 
+    /// Only prune sessions in this directory or below it
+    #[arg(long, value_name = "PATH")]
+    #[arg(
+        long_help = "Only prune sessions whose working directory is this path or\n\
+        a directory below it. Use '.' for the current directory."
+    )]
+    pub repo: Option<String>,
+
+    /// Only prune sessions from this tool (e.g., claude-code, cursor)
+    #[arg(long, value_name = "TOOL")]
+    pub tool: Option<String>,
+
     /// Show what would be deleted without actually deleting
     #[arg(long)]
     #[arg(long_help = "Preview mode: shows which sessions would be deleted\n\
@@ -78,7 +146,7 @@ pub struct PruneArgs {
     pub dry_run: bool,
 
     /// Skip the confirmation prompt
-    #[arg(long)]
+    #[arg(short = 'y', long, visible_alias = "yes")]
     #[arg(
         long_help = "Skip the confirmation prompt and proceed with deletion.\n\
         Use with caution as this operation cannot be undone."
@@ -90,8 +158,11 @@ pub struct PruneArgs {
 pub fn run(args: Args) -> Result<()> {
     match args.command {
         DbCommand::Vacuum => run_vacuum(),
+        DbCommand::Compact => run_compact(),
         DbCommand::Prune(prune_args) => run_prune(prune_args),
         DbCommand::Stats => run_stats(),
+        DbCommand::Repair => run_repair(),
+        DbCommand::Reindex { tokenizer } => run_reindex(tokenizer.as_deref()),
     }
 }
 
@@ -102,7 +173,7 @@ fn run_vacuum() -> Result<()> {
     // Get size before
     let size_before = db.file_size()?.unwrap_or(0);
 
-    println!("{}", "Running VACUUM...".dimmed());
+    note!("{}", "Running VACUUM...".dimmed());
 
     db.vacuum()?;
 
@@ -111,11 +182,46 @@ fn run_vacuum() -> Result<()> {
 
     let saved = size_before.saturating_sub(size_after);
 
-    println!("{} Database vacuumed successfully", "Done.".green().bold());
-    println!("  {} {}", "Before:".dimmed(), format_size(size_before));
-    println!("  {}  {}", "After:".dimmed(), format_size(size_after));
+    note!("{} Database vacuumed successfully", "Done.".green().bold());
+    note!("  {} {}", "Before:".dimmed(), format_size(size_before));
+    note!("  {}  {}", "After:".dimmed(), format_size(size_after));
     if saved > 0 {
-        println!("  {}  {}", "Saved:".dimmed(), format_size(saved).green());
+        note!("  {}  {}", "Saved:".dimmed(), format_size(saved).green());
+    }
+
+    Ok(())
+}
+
+/// Runs the compact subcommand.
+fn run_compact() -> Result<()> {
+    let mut db = Database::open_default()?;
+    let size_before = db.file_size()?.unwrap_or(0);
+
+    note!("{}", "Compressing message content...".dimmed());
+    let compressed = db.compress_message_content()?;
+    db.vacuum()?;
+
+    let size_after = db.file_size()?.unwrap_or(0);
+    let saved = size_before.saturating_sub(size_after);
+
+    note!(
+        "{} Compressed {} message{}",
+        "Done.".green().bold(),
+        compressed,
+        if compressed == 1 { "" } else { "s" }
+    );
+    note!("  {} {}", "Before:".dimmed(), format_size(size_before));
+    note!("  {}  {}", "After:".dimmed(), format_size(size_after));
+    if saved > 0 {
+        note!("  {}  {}", "Saved:".dimmed(), format_size(saved).green());
+    }
+
+    if !Config::load()?.compress_content {
+        note!();
+        note!(
+            "New messages are stored uncompressed. Run {} to compress them too.",
+            "lore config set compress_content true".cyan()
+        );
     }
 
     Ok(())
@@ -129,13 +235,31 @@ fn run_prune(args: PruneArgs) -> Result<()> {
     let duration = parse_duration(&args.older_than)?;
     let cutoff = Utc::now() - duration;
 
+    // Resolve repo path if provided
+    let working_dir = args.repo.map(|r| {
+        if r == "." {
+            std::env::current_dir()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| r)
+        } else {
+            r
+        }
+    });
+    let tool = args.tool.as_deref();
+    let working_dir = working_dir.as_deref();
+
     // Count sessions that would be deleted
-    let count = db.count_sessions_older_than(cutoff)?;
+    let count = db.count_sessions_older_than_filtered(cutoff, tool, working_dir)?;
 
     if count == 0 {
         println!(
             "{}",
-            format!("No sessions older than {} found.", args.older_than).dimmed()
+            format!(
+                "No sessions older than {}{} found.",
+                args.older_than,
+                filter_description(tool, working_dir)
+            )
+            .dimmed()
         );
         return Ok(());
     }
@@ -145,7 +269,7 @@ fn run_prune(args: PruneArgs) -> Result<()> {
 
     if args.dry_run {
         // Get the actual sessions for detailed listing
-        let sessions = db.get_sessions_older_than(cutoff)?;
+        let sessions = db.get_sessions_older_than_filtered(cutoff, tool, working_dir)?;
         println!(
             "Sessions that would be deleted ({} total):",
             count.to_string().yellow()
@@ -192,10 +316,11 @@ fn run_prune(args: PruneArgs) -> Result<()> {
     }
 
     println!(
-        "Found {} {} started before {}",
+        "Found {} {} started before {}{}",
         count.to_string().yellow(),
         if count == 1 { "session" } else { "sessions" },
-        cutoff_display.to_string().cyan()
+        cutoff_display.to_string().cyan(),
+        filter_description(tool, working_dir)
     );
 
     // Confirm unless --force
@@ -218,9 +343,9 @@ fn run_prune(args: PruneArgs) -> Result<()> {
     }
 
     // Delete the sessions
-    let deleted = db.delete_sessions_older_than(cutoff)?;
+    let deleted = db.delete_sessions_older_than_filtered(cutoff, tool, working_dir)?;
 
-    println!(
+    note!(
         "{} {} {}",
         "Deleted".green(),
         deleted,
@@ -230,6 +355,18 @@ fn run_prune(args: PruneArgs) -> Result<()> {
     Ok(())
 }
 
+/// Describes the prune filters for messages, e.g. " from cursor in /repo".
+fn filter_description(tool: Option<&str>, working_dir: Option<&str>) -> String {
+    let mut description = String::new();
+    if let Some(tool) = tool {
+        description.push_str(&format!(" from {tool}"));
+    }
+    if let Some(dir) = working_dir {
+        description.push_str(&format!(" in {dir}"));
+    }
+    description
+}
+
 /// Runs the stats subcommand.
 fn run_stats() -> Result<()> {
     let db = Database::open_default()?;
@@ -269,12 +406,151 @@ fn run_stats() -> Result<()> {
         }
     }
 
+    // Token usage, from the tools that report it
+    println!();
+    println!("{}", "Token Usage".bold());
+    let totals = usage_totals(&stats.usage_by_model, &Config::load()?);
+    match totals {
+        None => println!("  {}  n/a", "Tokens:".dimmed()),
+        Some(totals) => {
+            println!(
+                "  {}  {} ({} session(s) with usage)",
+                "Tokens:".dimmed(),
+                format_token_usage(Some(totals.input_tokens), Some(totals.output_tokens)),
+                totals.sessions
+            );
+            let mut cost = format_cost(totals.cost);
+            if totals.unpriced_sessions > 0 && totals.cost.is_some() {
+                cost.push_str(&format!(
+                    " (excludes {} session(s) with no known model rate)",
+                    totals.unpriced_sessions
+                ));
+            }
+            println!("  {}    {}", "Cost:".dimmed(), cost);
+        }
+    }
+
+    Ok(())
+}
+
+/// Token usage summed over all models, with its estimated cost.
+#[derive(Debug, PartialEq)]
+struct UsageTotals {
+    sessions: i32,
+    input_tokens: i64,
+    output_tokens: i64,
+    /// Estimated cost of the sessions whose model has a rate, or `None` if
+    /// no session's model has one.
+    cost: Option<f64>,
+    /// Sessions left out of the cost because their model has no rate.
+    unpriced_sessions: i32,
+}
+
+/// Sums per-model token usage and prices it with the configured rates.
+///
+/// Returns `None` when no session reports usage.
+fn usage_totals(usage: &[ModelUsage], config: &Config) -> Option<UsageTotals> {
+    if usage.is_empty() {
+        return None;
+    }
+    let mut totals = UsageTotals {
+        sessions: 0,
+        input_tokens: 0,
+        output_tokens: 0,
+        cost: None,
+        unpriced_sessions: 0,
+    };
+    for model in usage {
+        totals.sessions += model.sessions;
+        totals.input_tokens += model.input_tokens;
+        totals.output_tokens += model.output_tokens;
+        match config.estimate_cost(
+            model.model.as_deref(),
+            model.input_tokens,
+            model.output_tokens,
+        ) {
+            Some(cost) => *totals.cost.get_or_insert(0.0) += cost,
+            None => totals.unpriced_sessions += model.sessions,
+        }
+    }
+    Some(totals)
+}
+
+/// Runs the repair subcommand.
+fn run_repair() -> Result<()> {
+    let mut db = Database::open_default()?;
+
+    let report = db.repair_session_aggregates()?;
+    let files_indexed = db.rebuild_file_index()?;
+
+    note!("{} {}", "Done.".green().bold(), repair_summary(&report));
+    note!(
+        "  {} {}",
+        "Message counts fixed:".dimmed(),
+        report.message_counts_fixed
+    );
+    note!(
+        "  {}      {}",
+        "End times filled:".dimmed(),
+        report.ended_at_filled
+    );
+    note!("  {}         {}", "Files indexed:".dimmed(), files_indexed);
+
     Ok(())
 }
 
-/// Parses a duration string like "90d", "6m", "1y".
+/// Runs the reindex subcommand.
+///
+/// A tokenizer given on the command line is saved to the config before the
+/// rebuild, so indexes created later use it too.
+fn run_reindex(tokenizer: Option<&str>) -> Result<()> {
+    let mut config = Config::load()?;
+    if let Some(tokenizer) = tokenizer {
+        config.set("search_tokenizer", tokenizer)?;
+        config.save()?;
+    }
+    let tokenizer: SearchTokenizer = config
+        .get("search_tokenizer")
+        .unwrap_or_default()
+        .parse()
+        .map_err(|e: String| anyhow!("Invalid search_tokenizer setting: {e}"))?;
+
+    let mut db = Database::open_default()?;
+    db.set_search_tokenizer(tokenizer);
+
+    note!(
+        "{}",
+        format!("Rebuilding the search index with the {tokenizer} tokenizer...").dimmed()
+    );
+    let count = db.rebuild_search_index()?;
+    note!(
+        "{} Indexed {} message{}",
+        "Done.".green().bold(),
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+
+    Ok(())
+}
+
+/// Describes a repair run in one line.
+fn repair_summary(report: &RepairReport) -> String {
+    if report.message_counts_fixed == 0 && report.ended_at_filled == 0 {
+        return format!(
+            "Checked {} sessions, nothing to repair",
+            report.sessions_checked
+        );
+    }
+    format!(
+        "Checked {} sessions, corrected {} message counts and {} end times",
+        report.sessions_checked, report.message_counts_fixed, report.ended_at_filled
+    )
+}
+
+/// Parses a duration string like "12h", "90d", "6m", "1y".
 ///
 /// Supported formats:
+/// - Nh: N hours
 /// - Nd: N days
 /// - Nw: N weeks
 /// - Nm: N months (approximated as 30 days each)
@@ -292,7 +568,7 @@ fn parse_duration(s: &str) -> Result<Duration> {
 
     let number: i64 = number_part.parse().map_err(|_| {
         anyhow::anyhow!(
-            "Invalid duration '{}'. Expected format: <number><unit> (e.g., 90d, 6m, 1y)",
+            "Invalid duration '{}'. Expected format: <number><unit> (e.g., 12h, 90d, 6m, 1y)",
             s
         )
     })?;
@@ -301,22 +577,23 @@ fn parse_duration(s: &str) -> Result<Duration> {
         bail!("Duration must be a positive number");
     }
 
-    let days = match unit {
-        'd' => number,
-        'w' => number * 7,
-        'm' => number * 30,
-        'y' => number * 365,
+    let duration = match unit {
+        'h' => Duration::hours(number),
+        'd' => Duration::days(number),
+        'w' => Duration::days(number * 7),
+        'm' => Duration::days(number * 30),
+        'y' => Duration::days(number * 365),
         _ => bail!(
-            "Unknown duration unit '{}'. Supported units: d (days), w (weeks), m (months), y (years)",
+            "Unknown duration unit '{}'. Supported units: h (hours), d (days), w (weeks), m (months), y (years)",
             unit
         ),
     };
 
-    Ok(Duration::days(days))
+    Ok(duration)
 }
 
 /// Formats a file size in bytes as a human-readable string.
-fn format_size(bytes: u64) -> String {
+pub(super) fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -336,12 +613,84 @@ fn format_size(bytes: u64) -> String {
 mod tests {
     use super::*;
 
+    fn usage(model: Option<&str>, sessions: i32, input: i64, output: i64) -> ModelUsage {
+        ModelUsage {
+            model: model.map(|m| m.to_string()),
+            sessions,
+            input_tokens: input,
+            output_tokens: output,
+        }
+    }
+
+    #[test]
+    fn test_usage_totals_prices_known_models() {
+        let config = Config::default();
+        let by_model = vec![
+            usage(Some("claude-sonnet-4-20250514"), 2, 1_000_000, 100_000),
+            usage(Some("llama3:8b"), 1, 500, 50),
+            usage(None, 1, 10, 1),
+        ];
+
+        let totals = usage_totals(&by_model, &config).unwrap();
+        assert_eq!(totals.sessions, 4);
+        assert_eq!(totals.input_tokens, 1_000_510);
+        assert_eq!(totals.output_tokens, 100_051);
+        assert_eq!(totals.cost, Some(3.0 + 1.5));
+        assert_eq!(totals.unpriced_sessions, 2);
+    }
+
+    #[test]
+    fn test_usage_totals_without_usage_or_rates() {
+        let config = Config::default();
+        assert_eq!(usage_totals(&[], &config), None);
+
+        let totals = usage_totals(&[usage(None, 1, 10, 1)], &config).unwrap();
+        assert_eq!(totals.cost, None);
+    }
+
     #[test]
     fn test_parse_duration_days() {
         let d = parse_duration("90d").unwrap();
         assert_eq!(d.num_days(), 90);
     }
 
+    #[test]
+    fn test_parse_duration_hours() {
+        let d = parse_duration("12h").unwrap();
+        assert_eq!(d.num_hours(), 12);
+    }
+
+    #[test]
+    fn test_filter_description() {
+        assert_eq!(filter_description(None, None), "");
+        assert_eq!(
+            filter_description(Some("cursor"), Some("/work/experiment")),
+            " from cursor in /work/experiment"
+        );
+    }
+
+    #[test]
+    fn test_repair_summary() {
+        let clean = RepairReport {
+            sessions_checked: 12,
+            ..Default::default()
+        };
+        assert_eq!(
+            repair_summary(&clean),
+            "Checked 12 sessions, nothing to repair"
+        );
+
+        let fixed = RepairReport {
+            sessions_checked: 12,
+            message_counts_fixed: 3,
+            ended_at_filled: 1,
+        };
+        assert_eq!(
+            repair_summary(&fixed),
+            "Checked 12 sessions, corrected 3 message counts and 1 end times"
+        );
+    }
+
     #[test]
     fn test_parse_duration_weeks() {
         let d = parse_duration("4w").unwrap();
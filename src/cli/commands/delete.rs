@@ -5,10 +5,10 @@
 
 use std::io::{self, Write};
 
-use anyhow::{bail, Result};
+use anyhow::{Context, Result};
 use colored::Colorize;
 
-use crate::storage::Database;
+use crate::storage::{Database, Session};
 
 /// Arguments for the delete command.
 #[derive(clap::Args)]
@@ -40,75 +40,25 @@ pub struct Args {
 pub fn run(args: Args) -> Result<()> {
     let db = Database::open_default()?;
 
-    // Find session by prefix
-    let all_sessions = db.list_sessions(1000, None)?;
-    let matching: Vec<_> = all_sessions
-        .iter()
-        .filter(|s| s.id.to_string().starts_with(&args.session))
-        .collect();
-
-    if matching.is_empty() {
-        if all_sessions.is_empty() {
-            bail!(
-                "No session found matching '{}'. No sessions in database. \
-                 Run 'lore import' to import sessions first.",
-                args.session
-            );
-        } else {
-            bail!(
+    let session = db
+        .find_session_by_id_prefix(&args.session)?
+        .with_context(|| {
+            format!(
                 "No session found matching '{}'. \
                  Run 'lore sessions' to list available sessions.",
                 args.session
-            );
-        }
-    }
-
-    if matching.len() > 1 {
-        println!("{}", "Multiple sessions match that prefix:".yellow());
-        for s in &matching {
-            let id_short = &s.id.to_string()[..8];
-            println!(
-                "  {} - {}",
-                id_short.cyan(),
-                s.started_at.format("%Y-%m-%d %H:%M")
-            );
-        }
-        bail!(
-            "Multiple sessions match '{}'. Please use a more specific prefix from the list above.",
-            args.session
-        );
-    }
-
-    let session = matching[0];
+            )
+        })?;
     let session_short = &session.id.to_string()[..8];
 
     // Get counts for what will be deleted
     let messages = db.get_messages(&session.id)?;
     let links = db.get_links_by_session(&session.id)?;
 
-    // Show what will be deleted
-    println!();
-    println!("{} {}", "Session".bold(), session.id.to_string().cyan());
-    println!("  {}  {}", "Tool:".dimmed(), session.tool);
-    println!(
-        "  {}  {}",
-        "Started:".dimmed(),
-        session.started_at.format("%Y-%m-%d %H:%M:%S")
-    );
-    println!("  {}  {}", "Directory:".dimmed(), session.working_directory);
-    if let Some(ref branch) = session.git_branch {
-        println!("  {}  {}", "Branch:".dimmed(), branch);
+    // Show what will be deleted, unless there is no prompt to inform
+    if !(args.force && crate::cli::is_quiet()) {
+        print_preview(&session, messages.len(), links.len());
     }
-    println!();
-    println!(
-        "{}",
-        format!(
-            "This will permanently delete {} messages and {} links.",
-            messages.len(),
-            links.len()
-        )
-        .yellow()
-    );
 
     // Confirm unless --force
     if !args.force {
@@ -127,7 +77,7 @@ pub fn run(args: Args) -> Result<()> {
     // Delete the session
     let (messages_deleted, links_deleted) = db.delete_session(&session.id)?;
 
-    println!(
+    note!(
         "{} session {} ({} messages, {} links)",
         "Deleted".green(),
         session_short.cyan(),
@@ -137,3 +87,25 @@ pub fn run(args: Args) -> Result<()> {
 
     Ok(())
 }
+
+/// Prints the session about to be deleted and what goes with it.
+fn print_preview(session: &Session, message_count: usize, link_count: usize) {
+    println!();
+    println!("{} {}", "Session".bold(), session.id.to_string().cyan());
+    println!("  {}  {}", "Tool:".dimmed(), session.tool);
+    println!(
+        "  {}  {}",
+        "Started:".dimmed(),
+        session.started_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    println!("  {}  {}", "Directory:".dimmed(), session.working_directory);
+    if let Some(ref branch) = session.git_branch {
+        println!("  {}  {}", "Branch:".dimmed(), branch);
+    }
+    println!();
+    println!(
+        "{}",
+        format!("This will permanently delete {message_count} messages and {link_count} links.")
+            .yellow()
+    );
+}
@@ -0,0 +1,445 @@
+//! Diff command - compare the conversations of two sessions.
+//!
+//! Aligns the two sessions' messages by position and compares their text
+//! content, so redoing a task lets you see where the second attempt took a
+//! different path. Each aligned pair is reported as unchanged, changed
+//! (with a line diff), removed (only in the first session), or added (only
+//! in the second session).
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::cli::OutputFormat;
+use crate::storage::{Database, Message, MessageRole, Session};
+
+/// Lines of unchanged context shown around each change in a line diff.
+const CONTEXT_LINES: usize = 2;
+
+/// Maximum lines shown for a message that exists in only one session.
+const MAX_ONE_SIDED_LINES: usize = 10;
+
+/// Arguments for the diff command.
+#[derive(clap::Args)]
+#[command(after_help = "EXAMPLES:\n    \
+    lore diff abc123 def456             Compare two sessions\n    \
+    lore diff abc123 def456 -f json     Output added/removed/changed entries")]
+pub struct Args {
+    /// First session ID prefix
+    #[arg(value_name = "SESSION_A")]
+    pub session_a: String,
+
+    /// Second session ID prefix
+    #[arg(value_name = "SESSION_B")]
+    pub session_b: String,
+
+    /// Output format: text (default), json
+    #[arg(short, long, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+}
+
+/// How an aligned message pair differs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DiffStatus {
+    Unchanged,
+    Changed,
+    Removed,
+    Added,
+}
+
+/// Comparison of the messages at one position in both sessions.
+#[derive(Debug, Serialize)]
+struct DiffEntry {
+    /// Zero-based message position.
+    index: usize,
+    status: DiffStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role_a: Option<MessageRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role_b: Option<MessageRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text_a: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text_b: Option<String>,
+    /// Unified line diff of the text, for changed entries.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    line_diff: Vec<String>,
+}
+
+/// Basic identification of a compared session.
+#[derive(Serialize)]
+struct SessionRef {
+    id: String,
+    tool: String,
+    started_at: DateTime<Utc>,
+    message_count: usize,
+    working_directory: String,
+}
+
+impl SessionRef {
+    fn new(session: &Session, messages: &[Message]) -> Self {
+        Self {
+            id: session.id.to_string(),
+            tool: session.tool.clone(),
+            started_at: session.started_at,
+            message_count: messages.len(),
+            working_directory: session.working_directory.clone(),
+        }
+    }
+}
+
+/// Counts of each entry status.
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct DiffSummary {
+    unchanged: usize,
+    changed: usize,
+    removed: usize,
+    added: usize,
+}
+
+/// JSON output for the diff command. Unchanged entries are only counted.
+#[derive(Serialize)]
+struct DiffOutput {
+    session_a: SessionRef,
+    session_b: SessionRef,
+    /// Position of the first message that differs, if any.
+    diverged_at: Option<usize>,
+    summary: DiffSummary,
+    entries: Vec<DiffEntry>,
+}
+
+/// Executes the diff command.
+pub fn run(args: Args) -> Result<()> {
+    let db = Database::open_default()?;
+
+    let session_a = find_session(&db, &args.session_a)?;
+    let session_b = find_session(&db, &args.session_b)?;
+    let messages_a = db.get_messages(&session_a.id)?;
+    let messages_b = db.get_messages(&session_b.id)?;
+
+    let entries = diff_messages(&messages_a, &messages_b);
+    let summary = summarize(&entries);
+    let diverged_at = entries
+        .iter()
+        .find(|e| e.status != DiffStatus::Unchanged)
+        .map(|e| e.index);
+
+    match args.format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            let output = DiffOutput {
+                session_a: SessionRef::new(&session_a, &messages_a),
+                session_b: SessionRef::new(&session_b, &messages_b),
+                diverged_at,
+                summary,
+                entries: entries
+                    .into_iter()
+                    .filter(|e| e.status != DiffStatus::Unchanged)
+                    .collect(),
+            };
+            println!("{}", args.format.json_string(&output)?);
+        }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("diff")),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            print_diff(
+                &session_a,
+                &session_b,
+                &entries,
+                &summary,
+                diverged_at,
+                messages_a.len(),
+                messages_b.len(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Looks up a session by ID prefix, failing with a helpful message.
+fn find_session(db: &Database, id_prefix: &str) -> Result<Session> {
+    match db.find_session_by_id_prefix(id_prefix)? {
+        Some(session) => Ok(session),
+        None => anyhow::bail!(
+            "No session found matching '{id_prefix}'. \
+             Run 'lore sessions' to list available sessions."
+        ),
+    }
+}
+
+/// Aligns two conversations by position and compares each pair.
+///
+/// A pair is unchanged only if both the role and the text match.
+fn diff_messages(a: &[Message], b: &[Message]) -> Vec<DiffEntry> {
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|index| {
+            let left = a.get(index);
+            let right = b.get(index);
+            let text_a = left.map(|m| m.content.text());
+            let text_b = right.map(|m| m.content.text());
+
+            let status = match (left, right) {
+                (Some(l), Some(r)) if l.role == r.role && text_a == text_b => DiffStatus::Unchanged,
+                (Some(_), Some(_)) => DiffStatus::Changed,
+                (Some(_), None) => DiffStatus::Removed,
+                (None, _) => DiffStatus::Added,
+            };
+
+            let line_diff = match (&status, &text_a, &text_b) {
+                (DiffStatus::Changed, Some(ta), Some(tb)) => line_diff(ta, tb),
+                _ => Vec::new(),
+            };
+
+            DiffEntry {
+                index,
+                status,
+                role_a: left.map(|m| m.role.clone()),
+                role_b: right.map(|m| m.role.clone()),
+                text_a,
+                text_b,
+                line_diff,
+            }
+        })
+        .collect()
+}
+
+/// Returns unified diff hunks between two texts, without file headers.
+fn line_diff(a: &str, b: &str) -> Vec<String> {
+    let lines_a: Vec<&str> = a.lines().collect();
+    let lines_b: Vec<&str> = b.lines().collect();
+    difflib::unified_diff(&lines_a, &lines_b, "", "", "", "", CONTEXT_LINES)
+        .into_iter()
+        .skip(2)
+        .map(|line| line.trim_end_matches('\n').to_string())
+        .collect()
+}
+
+/// Counts entries by status.
+fn summarize(entries: &[DiffEntry]) -> DiffSummary {
+    let mut summary = DiffSummary::default();
+    for entry in entries {
+        match entry.status {
+            DiffStatus::Unchanged => summary.unchanged += 1,
+            DiffStatus::Changed => summary.changed += 1,
+            DiffStatus::Removed => summary.removed += 1,
+            DiffStatus::Added => summary.added += 1,
+        }
+    }
+    summary
+}
+
+/// Returns the display label for a role.
+fn role_label(role: &MessageRole) -> &'static str {
+    match role {
+        MessageRole::User => "Human",
+        MessageRole::Assistant => "Assistant",
+        MessageRole::System => "System",
+        MessageRole::Tool => "Tool",
+    }
+}
+
+/// Prints the diff as colored text, collapsing runs of unchanged messages.
+fn print_diff(
+    session_a: &Session,
+    session_b: &Session,
+    entries: &[DiffEntry],
+    summary: &DiffSummary,
+    diverged_at: Option<usize>,
+    count_a: usize,
+    count_b: usize,
+) {
+    let short_a = &session_a.id.to_string()[..8];
+    let short_b = &session_b.id.to_string()[..8];
+
+    println!(
+        "{} {} ({}, {} messages) {} {} ({}, {} messages)",
+        "Diff".bold(),
+        short_a.red(),
+        session_a.tool,
+        count_a,
+        "->".dimmed(),
+        short_b.green(),
+        session_b.tool,
+        count_b
+    );
+    match diverged_at {
+        Some(index) => println!("Diverged at message {}", (index + 1).to_string().yellow()),
+        None => println!("{}", "Conversations are identical.".dimmed()),
+    }
+    println!();
+
+    let mut i = 0;
+    while i < entries.len() {
+        let entry = &entries[i];
+        if entry.status == DiffStatus::Unchanged {
+            let start = i;
+            while i < entries.len() && entries[i].status == DiffStatus::Unchanged {
+                i += 1;
+            }
+            let run = i - start;
+            let noun = if run == 1 { "message" } else { "messages" };
+            println!(
+                "{}",
+                format!("  = {run} identical {noun} (#{}-#{})", start + 1, i).dimmed()
+            );
+            continue;
+        }
+
+        let position = format!("#{}", entry.index + 1);
+        match entry.status {
+            DiffStatus::Changed => {
+                let role_a = entry.role_a.as_ref().map(role_label).unwrap_or_default();
+                let role_b = entry.role_b.as_ref().map(role_label).unwrap_or_default();
+                let roles = if role_a == role_b {
+                    role_a.to_string()
+                } else {
+                    format!("{role_a} -> {role_b}")
+                };
+                println!("{} {} {}", "~".yellow().bold(), position.bold(), roles);
+                for line in &entry.line_diff {
+                    let colored = if line.starts_with('+') {
+                        line.green().to_string()
+                    } else if line.starts_with('-') {
+                        line.red().to_string()
+                    } else if line.starts_with("@@") {
+                        line.cyan().to_string()
+                    } else {
+                        line.dimmed().to_string()
+                    };
+                    println!("    {colored}");
+                }
+            }
+            DiffStatus::Removed | DiffStatus::Added => {
+                let (sign, role, text, short) = if entry.status == DiffStatus::Removed {
+                    ("-".red().bold(), &entry.role_a, &entry.text_a, short_a)
+                } else {
+                    ("+".green().bold(), &entry.role_b, &entry.text_b, short_b)
+                };
+                println!(
+                    "{} {} {} {}",
+                    sign,
+                    position.bold(),
+                    role.as_ref().map(role_label).unwrap_or_default(),
+                    format!("(only in {short})").dimmed()
+                );
+                let text = text.as_deref().unwrap_or_default();
+                for line in text.lines().take(MAX_ONE_SIDED_LINES) {
+                    println!("    {}", line.dimmed());
+                }
+                if text.lines().count() > MAX_ONE_SIDED_LINES {
+                    println!("    {}", "...".dimmed());
+                }
+            }
+            DiffStatus::Unchanged => unreachable!("unchanged runs are handled above"),
+        }
+        println!();
+        i += 1;
+    }
+
+    println!();
+    println!(
+        "{} {} unchanged, {} changed, {} removed, {} added",
+        "Summary:".bold(),
+        summary.unchanged,
+        summary.changed.to_string().yellow(),
+        summary.removed.to_string().red(),
+        summary.added.to_string().green()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MessageContent;
+    use uuid::Uuid;
+
+    fn message(index: i32, role: MessageRole, text: &str) -> Message {
+        Message {
+            id: Uuid::new_v4(),
+            session_id: Uuid::nil(),
+            parent_id: None,
+            index,
+            timestamp: Utc::now(),
+            role,
+            content: MessageContent::Text(text.to_string()),
+            model: None,
+            git_branch: None,
+            cwd: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_messages_statuses() {
+        let a = vec![
+            message(0, MessageRole::User, "Fix the flaky test"),
+            message(1, MessageRole::Assistant, "Add a retry\nRun tests"),
+            message(2, MessageRole::User, "Thanks"),
+        ];
+        let b = vec![
+            message(0, MessageRole::User, "Fix the flaky test"),
+            message(1, MessageRole::Assistant, "Mock the clock\nRun tests"),
+            message(2, MessageRole::User, "Thanks"),
+            message(3, MessageRole::Assistant, "Done"),
+        ];
+
+        let entries = diff_messages(&a, &b);
+        let statuses: Vec<DiffStatus> = entries.iter().map(|e| e.status).collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                DiffStatus::Unchanged,
+                DiffStatus::Changed,
+                DiffStatus::Unchanged,
+                DiffStatus::Added,
+            ]
+        );
+        assert_eq!(
+            summarize(&entries),
+            DiffSummary {
+                unchanged: 2,
+                changed: 1,
+                removed: 0,
+                added: 1,
+            }
+        );
+        assert!(entries[1].line_diff.contains(&"-Add a retry".to_string()));
+        assert!(entries[1]
+            .line_diff
+            .contains(&"+Mock the clock".to_string()));
+        assert!(entries[1].line_diff.contains(&" Run tests".to_string()));
+    }
+
+    #[test]
+    fn test_diff_messages_role_change_and_removed() {
+        let a = vec![
+            message(0, MessageRole::User, "same"),
+            message(1, MessageRole::Assistant, "gone"),
+        ];
+        let b = vec![message(0, MessageRole::Assistant, "same")];
+
+        let entries = diff_messages(&a, &b);
+
+        assert_eq!(entries[0].status, DiffStatus::Changed);
+        // Identical text yields no line diff, only the role change
+        assert!(entries[0].line_diff.is_empty());
+        assert_eq!(entries[1].status, DiffStatus::Removed);
+        assert_eq!(entries[1].text_a.as_deref(), Some("gone"));
+        assert!(entries[1].text_b.is_none());
+    }
+
+    #[test]
+    fn test_diff_entry_json_shape() {
+        let a = vec![message(0, MessageRole::User, "old")];
+        let b = vec![message(0, MessageRole::User, "new")];
+
+        let json = serde_json::to_value(diff_messages(&a, &b)).unwrap();
+
+        assert_eq!(json[0]["status"], "changed");
+        assert_eq!(json[0]["role_a"], "user");
+        assert_eq!(json[0]["text_b"], "new");
+        assert_eq!(json[0]["line_diff"][0], "@@ -1 +1 @@");
+    }
+}
@@ -10,6 +10,7 @@ use std::fs;
 use std::path::PathBuf;
 
 use crate::capture::watchers::{default_registry, WatcherRegistry};
+use crate::cli::format::format_number;
 use crate::cli::OutputFormat;
 use crate::config::Config;
 use crate::daemon::DaemonState;
@@ -118,7 +119,10 @@ pub fn run(args: Args) -> Result<()> {
     let config_result = Config::load();
 
     match args.format {
-        OutputFormat::Json => run_json(&registry, config_result.as_ref().ok()),
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            run_json(&registry, config_result.as_ref().ok(), args.format)
+        }
+        OutputFormat::Csv => Err(OutputFormat::Csv.unsupported("doctor")),
         OutputFormat::Text | OutputFormat::Markdown => {
             run_text(&registry, config_result.as_ref().ok())
         }
@@ -126,7 +130,11 @@ pub fn run(args: Args) -> Result<()> {
 }
 
 /// Runs doctor with JSON output.
-fn run_json(registry: &WatcherRegistry, config: Option<&Config>) -> Result<()> {
+fn run_json(
+    registry: &WatcherRegistry,
+    config: Option<&Config>,
+    format: OutputFormat,
+) -> Result<()> {
     let mut ok_count = 0;
     let mut warning_count = 0;
     let mut error_count = 0;
@@ -242,7 +250,7 @@ fn run_json(registry: &WatcherRegistry, config: Option<&Config>) -> Result<()> {
         },
     };
 
-    let json = serde_json::to_string_pretty(&output)?;
+    let json = format.json_string(&output)?;
     println!("{json}");
 
     std::process::exit(exit_code);
@@ -301,9 +309,9 @@ fn run_text(registry: &WatcherRegistry, config: Option<&Config>) -> Result<()> {
     );
 
     if db_status.status == CheckStatus::Ok {
-        println!("  Sessions:        {}", format_number(sessions));
-        println!("  Messages:        {}", format_number(messages));
-        println!("  Links:           {}", format_number(links));
+        println!("  Sessions:        {}", format_number(sessions.into()));
+        println!("  Messages:        {}", format_number(messages.into()));
+        println!("  Links:           {}", format_number(links.into()));
 
         if let (Some(r), Some(w)) = (readable, writable) {
             let perms = match (r, w) {
@@ -453,19 +461,6 @@ fn print_check(label: &str, result: &CheckResult) {
     }
 }
 
-/// Formats a number with comma separators.
-fn format_number(n: i32) -> String {
-    let s = n.to_string();
-    let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.push(',');
-        }
-        result.push(c);
-    }
-    result.chars().rev().collect()
-}
-
 // ==================== Individual Checks ====================
 
 /// Checks if the config file exists and is valid.
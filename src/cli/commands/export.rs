@@ -2,8 +2,15 @@
 //!
 //! Exports session data as markdown or JSON, with support for redacting
 //! sensitive information like API keys, tokens, passwords, and email addresses.
+//!
+//! Markdown exports begin with a YAML front-matter block so the transcript
+//! can be dropped into documentation sites that read page metadata.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use regex::Regex;
 use serde::Serialize;
 
@@ -15,6 +22,8 @@ use crate::storage::{ContentBlock, Database, Message, MessageContent, MessageRol
     lore export abc123                     Export as markdown (default)\n    \
     lore export abc123 --format json       Export as JSON\n    \
     lore export abc123 --redact            Redact sensitive data\n    \
+    lore export abc123 -o docs/session.md  Write to a file\n    \
+    lore export abc123 -o notes.md --force Overwrite an existing file\n    \
     lore export abc123 --redact-pattern 'secret_\\w+'  Custom redaction")]
 pub struct Args {
     /// Session ID prefix to export
@@ -45,7 +54,16 @@ pub struct Args {
 
     /// Write output to a file instead of stdout
     #[arg(short, long, value_name = "FILE")]
+    #[arg(
+        long_help = "Write the export to FILE instead of stdout. The file is\n\
+        written atomically (via a temporary file and rename), and an\n\
+        existing file is never replaced unless --force is given."
+    )]
     pub output: Option<String>,
+
+    /// Overwrite the output file if it already exists
+    #[arg(long, requires = "output")]
+    pub force: bool,
 }
 
 /// Export format options.
@@ -146,8 +164,7 @@ pub fn run(args: Args) -> Result<()> {
 
     // Write output
     if let Some(path) = args.output {
-        std::fs::write(&path, &output)
-            .map_err(|e| anyhow::anyhow!("Failed to write to {}: {}", path, e))?;
+        write_atomic(Path::new(&path), &output, args.force)?;
         eprintln!("Exported session to: {path}");
     } else {
         println!("{output}");
@@ -156,6 +173,43 @@ pub fn run(args: Args) -> Result<()> {
     Ok(())
 }
 
+/// Writes `contents` to `path` atomically.
+///
+/// The data is written to a temporary file in the same directory and then
+/// renamed into place, so readers never observe a partially written file.
+/// Fails if `path` already exists and `force` is false.
+fn write_atomic(path: &Path, contents: &str, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        anyhow::bail!(
+            "{} already exists. Use --force to overwrite it.",
+            path.display()
+        );
+    }
+
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid output path: {}", path.display()))?
+        .to_string_lossy();
+    let tmp_path = dir.join(format!(".{file_name}.{}.tmp", std::process::id()));
+
+    let result = (|| -> Result<()> {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result.with_context(|| format!("Failed to write to {}", path.display()))
+}
+
 /// Handles redaction of sensitive data.
 struct Redactor {
     patterns: Vec<Regex>,
@@ -242,7 +296,7 @@ fn export_markdown(
     summary: &Option<crate::storage::Summary>,
     redactor: &Redactor,
 ) -> String {
-    let mut output = String::new();
+    let mut output = markdown_front_matter(session, redactor);
 
     // Header
     output.push_str(&format!("# Session {}\n\n", session.id));
@@ -326,6 +380,7 @@ fn export_markdown(
             MessageRole::User => "Human",
             MessageRole::Assistant => "Assistant",
             MessageRole::System => "System",
+            MessageRole::Tool => "Tool",
         };
 
         let time = msg.timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
@@ -339,6 +394,61 @@ fn export_markdown(
     output
 }
 
+/// Builds the YAML front-matter block for a markdown export.
+///
+/// Values are emitted as JSON strings, which are valid YAML scalars and
+/// avoid quoting issues with paths, branch names, and model identifiers.
+fn markdown_front_matter(session: &Session, redactor: &Redactor) -> String {
+    fn quote(value: &str) -> String {
+        serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+    }
+
+    let mut fm = String::from("---\n");
+    fm.push_str(&format!("session: {}\n", quote(&session.id.to_string())));
+    fm.push_str(&format!("tool: {}\n", quote(&session.tool)));
+    if let Some(ref v) = session.tool_version {
+        fm.push_str(&format!("tool_version: {}\n", quote(v)));
+    }
+    if let Some(ref m) = session.model {
+        fm.push_str(&format!("model: {}\n", quote(m)));
+    }
+    if let Some(ref branch) = session.git_branch {
+        fm.push_str(&format!("branch: {}\n", quote(branch)));
+    }
+    fm.push_str(&format!(
+        "working_directory: {}\n",
+        quote(&redactor.redact(&session.working_directory))
+    ));
+    fm.push_str(&format!(
+        "started_at: {}\n",
+        quote(&session.started_at.to_rfc3339())
+    ));
+    if let Some(ended) = session.ended_at {
+        fm.push_str(&format!("ended_at: {}\n", quote(&ended.to_rfc3339())));
+    }
+    fm.push_str(&format!("message_count: {}\n", session.message_count));
+    fm.push_str("---\n\n");
+    fm
+}
+
+/// Returns a backtick fence long enough to wrap `content` safely.
+///
+/// Uses at least three backticks, and one more than the longest run of
+/// backticks inside the content, so embedded code fences are preserved.
+fn code_fence(content: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in content.chars() {
+        if ch == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
 /// Formats message content for markdown export.
 fn format_message_content_markdown(content: &MessageContent, redactor: &Redactor) -> String {
     match content {
@@ -357,20 +467,22 @@ fn format_message_content_markdown(content: &MessageContent, redactor: &Redactor
                         output.push_str("\n\n</details>\n\n");
                     }
                     ContentBlock::ToolUse { name, input, .. } => {
-                        output.push_str(&format!("**Tool: {name}**\n\n"));
-                        output.push_str("```json\n");
-                        let json = serde_json::to_string_pretty(input).unwrap_or_default();
-                        output.push_str(&redactor.redact(&json));
-                        output.push_str("\n```\n\n");
+                        let json = redactor
+                            .redact(&serde_json::to_string_pretty(input).unwrap_or_default());
+                        let fence = code_fence(&json);
+                        output.push_str(&format!("<details>\n<summary>Tool: {name}</summary>\n\n"));
+                        output.push_str(&format!("{fence}json\n{json}\n{fence}\n\n"));
+                        output.push_str("</details>\n\n");
                     }
                     ContentBlock::ToolResult {
                         content, is_error, ..
                     } => {
                         let label = if *is_error { "Error" } else { "Result" };
-                        output.push_str(&format!("**{label}:**\n\n"));
-                        output.push_str("```\n");
-                        output.push_str(&redactor.redact(content));
-                        output.push_str("\n```\n\n");
+                        let content = redactor.redact(content);
+                        let fence = code_fence(&content);
+                        output.push_str(&format!("<details>\n<summary>{label}</summary>\n\n"));
+                        output.push_str(&format!("{fence}\n{content}\n{fence}\n\n"));
+                        output.push_str("</details>\n\n");
                     }
                 }
             }
@@ -606,6 +718,116 @@ MIIEpAIBAAKCAQEA1234567890
         assert!(result.contains("Line 2"));
     }
 
+    fn sample_session() -> Session {
+        use chrono::TimeZone;
+
+        Session {
+            id: uuid::Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: Some("2.0.0".to_string()),
+            started_at: chrono::Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap(),
+            ended_at: Some(
+                chrono::Utc
+                    .with_ymd_and_hms(2025, 1, 15, 10, 30, 0)
+                    .unwrap(),
+            ),
+            model: Some("claude-opus-4".to_string()),
+            working_directory: "/home/user/project".to_string(),
+            git_branch: Some("feat/export: \"quoted\"".to_string()),
+            source_path: None,
+            message_count: 2,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_markdown_front_matter() {
+        let session = sample_session();
+        let redactor = Redactor::new(false, &[]).unwrap();
+        let fm = markdown_front_matter(&session, &redactor);
+
+        assert!(fm.starts_with("---\n"));
+        assert!(fm.ends_with("---\n\n"));
+        assert!(fm.contains("tool: \"claude-code\"\n"));
+        assert!(fm.contains("model: \"claude-opus-4\"\n"));
+        assert!(fm.contains("branch: \"feat/export: \\\"quoted\\\"\"\n"));
+        assert!(fm.contains("started_at: \"2025-01-15T10:00:00+00:00\"\n"));
+        assert!(fm.contains("ended_at: \"2025-01-15T10:30:00+00:00\"\n"));
+    }
+
+    #[test]
+    fn test_export_markdown_renders_tool_blocks_collapsible() {
+        let session = sample_session();
+        let redactor = Redactor::new(false, &[]).unwrap();
+        let messages = vec![Message {
+            id: uuid::Uuid::new_v4(),
+            session_id: session.id,
+            parent_id: None,
+            index: 0,
+            timestamp: session.started_at,
+            role: MessageRole::Assistant,
+            content: MessageContent::Blocks(vec![
+                ContentBlock::ToolUse {
+                    id: "tool_1".to_string(),
+                    name: "Read".to_string(),
+                    input: serde_json::json!({"file_path": "README.md"}),
+                },
+                ContentBlock::ToolResult {
+                    tool_use_id: "tool_1".to_string(),
+                    content: "```rust\nfn main() {}\n```".to_string(),
+                    is_error: false,
+                },
+            ]),
+            model: None,
+            git_branch: None,
+            cwd: None,
+        }];
+
+        let output = export_markdown(&session, &messages, &[], &[], &None, &redactor);
+
+        assert!(output.starts_with("---\n"));
+        assert!(output.contains("<summary>Tool: Read</summary>"));
+        assert!(output.contains("<summary>Result</summary>"));
+        // The embedded fence is wrapped in a longer fence so it survives intact
+        assert!(output.contains("````\n```rust\nfn main() {}\n```\n````"));
+    }
+
+    #[test]
+    fn test_code_fence_length() {
+        assert_eq!(code_fence("plain text"), "```");
+        assert_eq!(code_fence("has ``` fence"), "````");
+        assert_eq!(code_fence("has ````` run"), "``````");
+    }
+
+    #[test]
+    fn test_write_atomic_creates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.md");
+
+        write_atomic(&path, "hello", false).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello");
+        // No temporary files are left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_refuses_to_clobber_without_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.md");
+        fs::write(&path, "original").unwrap();
+
+        let err = write_atomic(&path, "new", false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "original");
+
+        write_atomic(&path, "new", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    }
+
     #[test]
     fn test_export_format_default() {
         let format = ExportFormat::default();
@@ -8,19 +8,30 @@
 //! The pre-push hook is the recommended way to automate `lore sync`: reasoning
 //! rides on the git pushes you already do, so the background daemon is optional
 //! for sync.
+//!
+//! A hook that Lore did not write is never overwritten silently. Install skips
+//! it unless `--chain` (keep it and run it before Lore's hook) or `--force`
+//! (replace it) is given; either way the original is kept as `<hook>.backup`
+//! and restored by uninstall.
 
 use anyhow::{Context, Result};
 use clap::Subcommand;
 use colored::Colorize;
+use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::cli::OutputFormat;
+
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
 /// Marker comment to identify Lore-managed hooks.
 const LORE_HOOK_MARKER: &str = "# Lore hook - managed by lore hooks install";
 
+/// Comment opening the block of a chained Lore hook that runs the original.
+const CHAINED_HOOK_MARKER: &str = "# Run the hook Lore was installed over";
+
 /// Post-commit hook script content.
 ///
 /// This hook runs after each commit and links any currently active AI
@@ -125,6 +136,46 @@ impl HookType {
         }
     }
 
+    /// Returns the line of the script after which a chained hook runs the
+    /// original hook.
+    ///
+    /// The pre-push hook runs it after the re-entry guard, so the original is
+    /// not run again for the push of Lore's own refs.
+    fn chain_after(&self) -> &'static str {
+        match self {
+            HookType::PrePush => "export LORE_SYNC_HOOK=1",
+            HookType::PostCommit | HookType::PrepareCommitMsg => LORE_HOOK_MARKER,
+        }
+    }
+
+    /// Returns the script content for this hook type, running the hook at
+    /// `original` first when it is given.
+    ///
+    /// The original hook receives git's arguments and standard input, and a
+    /// non-zero exit from it stops the hook with that status, so a check it
+    /// performed still blocks the commit or push.
+    fn script(&self, original: Option<&Path>) -> String {
+        let content = self.content();
+        let Some(original) = original else {
+            return content.to_string();
+        };
+        let quoted = format!(
+            "'{}'",
+            original.display().to_string().replace('\'', r"'\''")
+        );
+        let block = format!(
+            "\n{CHAINED_HOOK_MARKER}, keeping its exit status\n\
+             if [ -x {quoted} ]; then\n    \
+                 {quoted} \"$@\" || exit $?\n\
+             fi\n"
+        );
+        let at = content
+            .find(self.chain_after())
+            .and_then(|i| content[i..].find('\n').map(|end| i + end + 1))
+            .unwrap_or(0);
+        format!("{}{block}{}", &content[..at], &content[at..])
+    }
+
     /// Returns all managed hook types.
     fn all() -> &'static [HookType] {
         &[
@@ -144,14 +195,26 @@ pub enum HooksCommand {
         links sessions to commits using time and file overlap. The\n\
         pre-push hook best-effort syncs reasoning history when you push\n\
         (no daemon required); it never blocks the push.\n\
-        Existing hooks are backed up before being replaced.")]
+        Hooks not managed by Lore are skipped unless --chain or --force\n\
+        is given.")]
     Install {
         /// Overwrite existing hooks (backs up originals)
-        #[arg(long)]
+        #[arg(long, conflicts_with = "chain")]
         #[arg(long_help = "Replace existing hooks that are not managed by Lore.\n\
             The original hooks are saved as <hook>.backup and can\n\
             be restored with 'lore hooks uninstall'.")]
         force: bool,
+
+        /// Keep existing hooks and run them before Lore's
+        #[arg(long)]
+        #[arg(
+            long_help = "Install Lore's hook alongside an existing hook that is not\n\
+            managed by Lore. The original is moved to <hook>.backup and\n\
+            run first with the same arguments and input; if it fails,\n\
+            git sees its exit status as before. 'lore hooks uninstall'\n\
+            puts the original back."
+        )]
+        chain: bool,
     },
 
     /// Uninstall git hooks from the current repository
@@ -162,8 +225,13 @@ pub enum HooksCommand {
 
     /// Show status of installed hooks
     #[command(long_about = "Shows which git hooks are currently installed and\n\
-        whether they are managed by Lore or are third-party hooks.")]
-    Status,
+        whether they are managed by Lore, chained to an earlier hook, or\n\
+        are third-party hooks that install would leave in place.")]
+    Status {
+        /// Output format: text (default) or json
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
 }
 
 /// Arguments for the hooks command.
@@ -171,8 +239,10 @@ pub enum HooksCommand {
 #[command(after_help = "EXAMPLES:\n    \
     lore hooks install         Install hooks (skips existing)\n    \
     lore hooks install --force Replace existing hooks\n    \
+    lore hooks install --chain Run existing hooks before Lore's\n    \
     lore hooks uninstall       Remove Lore hooks\n    \
-    lore hooks status          Check installed hooks")]
+    lore hooks status          Check installed hooks\n    \
+    lore hooks status --format json  Hook status as JSON")]
 pub struct Args {
     /// Hooks subcommand to run
     #[command(subcommand)]
@@ -184,35 +254,49 @@ pub struct Args {
 /// Dispatches to the appropriate subcommand handler.
 pub fn run(args: Args) -> Result<()> {
     match args.command {
-        HooksCommand::Install { force } => run_install(force),
+        HooksCommand::Install { force, chain } => run_install(force, chain),
         HooksCommand::Uninstall => run_uninstall(),
-        HooksCommand::Status => run_status(),
+        HooksCommand::Status { format } => run_status(format),
     }
 }
 
 /// Installs Lore git hooks in the current repository.
 ///
 /// Creates hook scripts in `.git/hooks/` that integrate with Lore.
-/// Existing hooks are backed up before being replaced when using --force.
-fn run_install(force: bool) -> Result<()> {
+/// Existing hooks are backed up before being replaced when using --force,
+/// or before being chained when using --chain.
+fn run_install(force: bool, chain: bool) -> Result<()> {
     let hooks_dir = get_hooks_dir()?;
-    println!("Installing Lore hooks in {}", hooks_dir.display());
-    println!();
+    note!("Installing Lore hooks in {}", hooks_dir.display());
+    note!();
 
     let mut installed_count = 0;
     let mut skipped_count = 0;
 
     for hook_type in HookType::all() {
         let hook_path = hooks_dir.join(hook_type.filename());
-        let status = install_hook(&hook_path, *hook_type, force)?;
+        let status = if chain {
+            chain_hook(&hook_path, *hook_type)?
+        } else {
+            install_hook(&hook_path, *hook_type, force)?
+        };
 
         match status {
             InstallStatus::Installed => {
-                println!("  {} {}", "Installed".green(), hook_type.filename());
+                note!("  {} {}", "Installed".green(), hook_type.filename());
+                installed_count += 1;
+            }
+            InstallStatus::Chained => {
+                note!(
+                    "  {} {} (runs the existing hook first, saved as {}.backup)",
+                    "Chained".green(),
+                    hook_type.filename(),
+                    hook_type.filename()
+                );
                 installed_count += 1;
             }
             InstallStatus::Replaced => {
-                println!(
+                note!(
                     "  {} {} (backed up existing to {}.backup)",
                     "Replaced".yellow(),
                     hook_type.filename(),
@@ -222,7 +306,7 @@ fn run_install(force: bool) -> Result<()> {
             }
             InstallStatus::Skipped => {
                 println!(
-                    "  {} {} (use --force to overwrite)",
+                    "  {} {} (another hook is installed)",
                     "Skipped".yellow(),
                     hook_type.filename()
                 );
@@ -239,15 +323,18 @@ fn run_install(force: bool) -> Result<()> {
         }
     }
 
-    println!();
+    note!();
     if installed_count > 0 {
-        println!(
+        note!(
             "Successfully installed {} hook(s).",
             installed_count.to_string().green()
         );
     }
-    if skipped_count > 0 && !force {
-        println!("{}", "Use --force to overwrite existing hooks.".dimmed());
+    if skipped_count > 0 && !force && !chain {
+        note!(
+            "{}",
+            "Use --chain to run existing hooks before Lore's, or --force to replace them.".dimmed()
+        );
     }
 
     Ok(())
@@ -259,6 +346,8 @@ enum InstallStatus {
     Installed,
     /// Existing hook was backed up and replaced.
     Replaced,
+    /// Existing hook was backed up and is run by the Lore hook.
+    Chained,
     /// Hook already exists and was not replaced.
     Skipped,
     /// Hook is already a Lore-managed hook.
@@ -275,8 +364,11 @@ fn install_hook(hook_path: &Path, hook_type: HookType, force: bool) -> Result<In
             .with_context(|| format!("Failed to read existing hook: {}", hook_path.display()))?;
 
         if existing_content.contains(LORE_HOOK_MARKER) {
-            // Already a Lore hook, update it
-            write_hook(hook_path, hook_type)?;
+            // Already a Lore hook, update it, keeping any chained original
+            let chained = existing_content.contains(CHAINED_HOOK_MARKER);
+            let backup_path = hook_path.with_extension("backup");
+            let script = hook_type.script(chained.then_some(backup_path.as_path()));
+            write_script(hook_path, &script)?;
             return Ok(InstallStatus::AlreadyInstalled);
         }
 
@@ -297,11 +389,34 @@ fn install_hook(hook_path: &Path, hook_type: HookType, force: bool) -> Result<In
     }
 }
 
+/// Installs a single hook, chaining a hook not managed by Lore.
+///
+/// The existing hook is moved to `<hook>.backup` and the Lore hook runs it
+/// before its own commands. Without an existing foreign hook this behaves
+/// like [`install_hook`].
+fn chain_hook(hook_path: &Path, hook_type: HookType) -> Result<InstallStatus> {
+    if !matches!(get_hook_status(hook_path)?, HookStatus::Other) {
+        return install_hook(hook_path, hook_type, false);
+    }
+
+    let backup_path = hook_path.with_extension("backup");
+    fs::rename(hook_path, &backup_path)
+        .with_context(|| format!("Failed to backup hook to {}", backup_path.display()))?;
+
+    write_script(hook_path, &hook_type.script(Some(&backup_path)))?;
+    Ok(InstallStatus::Chained)
+}
+
 /// Writes a hook script to the specified path.
 ///
 /// Sets the executable bit on Unix systems.
 fn write_hook(hook_path: &Path, hook_type: HookType) -> Result<()> {
-    fs::write(hook_path, hook_type.content())
+    write_script(hook_path, hook_type.content())
+}
+
+/// Writes script content to a hook path and makes it executable on Unix.
+fn write_script(hook_path: &Path, script: &str) -> Result<()> {
+    fs::write(hook_path, script)
         .with_context(|| format!("Failed to write hook: {}", hook_path.display()))?;
 
     #[cfg(unix)]
@@ -321,8 +436,8 @@ fn write_hook(hook_path: &Path, hook_type: HookType) -> Result<()> {
 /// Restores backup hooks if they exist.
 fn run_uninstall() -> Result<()> {
     let hooks_dir = get_hooks_dir()?;
-    println!("Uninstalling Lore hooks from {}", hooks_dir.display());
-    println!();
+    note!("Uninstalling Lore hooks from {}", hooks_dir.display());
+    note!();
 
     let mut removed_count = 0;
     let mut restored_count = 0;
@@ -333,7 +448,7 @@ fn run_uninstall() -> Result<()> {
 
         match uninstall_hook(&hook_path)? {
             UninstallStatus::NotInstalled => {
-                println!(
+                note!(
                     "  {} {} (not installed)",
                     "Skipped".dimmed(),
                     hook_type.filename()
@@ -348,11 +463,11 @@ fn run_uninstall() -> Result<()> {
                 );
             }
             UninstallStatus::Removed => {
-                println!("  {} {}", "Removed".green(), hook_type.filename());
+                note!("  {} {}", "Removed".green(), hook_type.filename());
                 removed_count += 1;
             }
             UninstallStatus::RemovedAndRestored => {
-                println!(
+                note!(
                     "  {} {} (restored from backup)",
                     "Removed".green(),
                     hook_type.filename()
@@ -363,11 +478,11 @@ fn run_uninstall() -> Result<()> {
         }
     }
 
-    println!();
+    note!();
     if removed_count > 0 {
-        println!("Removed {} hook(s).", removed_count.to_string().green());
+        note!("Removed {} hook(s).", removed_count.to_string().green());
         if restored_count > 0 {
-            println!(
+            note!(
                 "Restored {} original hook(s) from backup.",
                 restored_count.to_string().green()
             );
@@ -421,29 +536,65 @@ fn uninstall_hook(hook_path: &Path) -> Result<UninstallStatus> {
     }
 }
 
+/// JSON output for one hook in `lore hooks status`.
+#[derive(Serialize)]
+struct HookStatusOutput {
+    hook: &'static str,
+    path: String,
+    status: HookStatus,
+}
+
 /// Shows the status of Lore git hooks.
 ///
 /// Reports which hooks are installed and whether they are Lore-managed.
-fn run_status() -> Result<()> {
+fn run_status(format: OutputFormat) -> Result<()> {
     let hooks_dir = get_hooks_dir()?;
 
+    let hooks = HookType::all()
+        .iter()
+        .map(|hook_type| {
+            let hook_path = hooks_dir.join(hook_type.filename());
+            Ok(HookStatusOutput {
+                hook: hook_type.filename(),
+                status: get_hook_status(&hook_path)?,
+                path: hook_path.display().to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            println!("{}", format.json_string(&hooks)?);
+            return Ok(());
+        }
+        OutputFormat::Csv => return Err(format.unsupported("hooks status")),
+        OutputFormat::Text | OutputFormat::Markdown => {}
+    }
+
     println!("Git hooks status:");
     println!();
 
-    for hook_type in HookType::all() {
-        let hook_path = hooks_dir.join(hook_type.filename());
-        let status = get_hook_status(&hook_path)?;
-
-        let status_str = match status {
+    for hook in &hooks {
+        let status_str = match hook.status {
             HookStatus::Lore => "installed".green().to_string(),
-            HookStatus::Other => "other hook installed".yellow().to_string(),
+            HookStatus::Chained => "installed, runs the previous hook first"
+                .green()
+                .to_string(),
+            HookStatus::Other => "other hook installed (install will skip it)"
+                .yellow()
+                .to_string(),
             HookStatus::None => "not installed".dimmed().to_string(),
         };
 
+        println!("  {:<20} {}", format!("{}:", hook.hook), status_str);
+    }
+
+    if hooks.iter().any(|h| h.status == HookStatus::Other) {
+        println!();
         println!(
-            "  {:<20} {}",
-            format!("{}:", hook_type.filename()),
-            status_str
+            "{}",
+            "Use 'lore hooks install --chain' to keep other hooks and run Lore's after them."
+                .dimmed()
         );
     }
 
@@ -451,9 +602,13 @@ fn run_status() -> Result<()> {
 }
 
 /// Status of a hook file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 enum HookStatus {
     /// Lore hook is installed.
     Lore,
+    /// Lore hook is installed and runs the hook it was installed over.
+    Chained,
     /// Another (non-Lore) hook is installed.
     Other,
     /// No hook is installed.
@@ -469,10 +624,12 @@ fn get_hook_status(hook_path: &Path) -> Result<HookStatus> {
     let content = fs::read_to_string(hook_path)
         .with_context(|| format!("Failed to read hook: {}", hook_path.display()))?;
 
-    if content.contains(LORE_HOOK_MARKER) {
-        Ok(HookStatus::Lore)
-    } else {
+    if !content.contains(LORE_HOOK_MARKER) {
         Ok(HookStatus::Other)
+    } else if content.contains(CHAINED_HOOK_MARKER) {
+        Ok(HookStatus::Chained)
+    } else {
+        Ok(HookStatus::Lore)
     }
 }
 
@@ -672,6 +829,111 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_chain_hook_keeps_and_restores_foreign_hook() -> Result<()> {
+        let (_temp_dir, hooks_dir) = create_test_repo()?;
+        let hook_path = hooks_dir.join("post-commit");
+        let backup_path = hooks_dir.join("post-commit.backup");
+        let foreign = "#!/bin/sh\necho 'husky hook'\n";
+        fs::write(&hook_path, foreign)?;
+        assert_eq!(get_hook_status(&hook_path)?, HookStatus::Other);
+
+        let status = chain_hook(&hook_path, HookType::PostCommit)?;
+        assert!(matches!(status, InstallStatus::Chained));
+        assert_eq!(fs::read_to_string(&backup_path)?, foreign);
+        assert_eq!(get_hook_status(&hook_path)?, HookStatus::Chained);
+        let content = fs::read_to_string(&hook_path)?;
+        assert!(content.contains(&format!("'{}' \"$@\" || exit $?", backup_path.display())));
+
+        // Reinstalling, with or without --chain, keeps the chain
+        install_hook(&hook_path, HookType::PostCommit, false)?;
+        assert_eq!(fs::read_to_string(&hook_path)?, content);
+        assert!(matches!(
+            chain_hook(&hook_path, HookType::PostCommit)?,
+            InstallStatus::AlreadyInstalled
+        ));
+        assert_eq!(fs::read_to_string(&backup_path)?, foreign);
+
+        let status = uninstall_hook(&hook_path)?;
+        assert!(matches!(status, UninstallStatus::RemovedAndRestored));
+        assert_eq!(fs::read_to_string(&hook_path)?, foreign);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_chain_hook_without_existing_hook_installs_plain() -> Result<()> {
+        let (_temp_dir, hooks_dir) = create_test_repo()?;
+        let hook_path = hooks_dir.join("pre-push");
+
+        let status = chain_hook(&hook_path, HookType::PrePush)?;
+        assert!(matches!(status, InstallStatus::Installed));
+        assert_eq!(fs::read_to_string(&hook_path)?, PRE_PUSH_HOOK);
+        assert_eq!(get_hook_status(&hook_path)?, HookStatus::Lore);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_chained_pre_push_runs_original_after_guard() {
+        let script = HookType::PrePush.script(Some(Path::new("/repo/.git/hooks/pre-push.backup")));
+        let guard = script.find("export LORE_SYNC_HOOK=1").unwrap();
+        let original = script
+            .find("'/repo/.git/hooks/pre-push.backup' \"$@\"")
+            .unwrap();
+        let sync = script.find("lore sync --remote").unwrap();
+        assert!(guard < original && original < sync);
+    }
+
+    #[test]
+    fn test_hook_status_serializes_snake_case() {
+        let json = serde_json::to_string(&[
+            HookStatus::Lore,
+            HookStatus::Chained,
+            HookStatus::Other,
+            HookStatus::None,
+        ])
+        .unwrap();
+        assert_eq!(json, r#"["lore","chained","other","none"]"#);
+    }
+
+    /// Runs a chained post-commit hook whose original exits with `exit_code`.
+    ///
+    /// Returns the hook's exit code and whether the original hook ran.
+    #[cfg(unix)]
+    fn run_chained_post_commit(exit_code: i32) -> (Option<i32>, bool) {
+        use std::process::Command;
+
+        let dir = TempDir::new().unwrap();
+        let marker = dir.path().join("original-ran");
+        let hook_path = dir.path().join("post-commit");
+        fs::write(
+            &hook_path,
+            format!("#!/bin/sh\n: > '{}'\nexit {exit_code}\n", marker.display()),
+        )
+        .unwrap();
+        let mut perms = fs::metadata(&hook_path).unwrap().permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&hook_path, perms).unwrap();
+
+        chain_hook(&hook_path, HookType::PostCommit).unwrap();
+
+        // An empty PATH keeps the hook from finding a real lore binary
+        let status = Command::new("/bin/sh")
+            .arg(&hook_path)
+            .env("PATH", "")
+            .status()
+            .unwrap();
+        (status.code(), marker.exists())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_chained_hook_runs_original_and_keeps_its_failure() {
+        assert_eq!(run_chained_post_commit(0), (Some(0), true));
+        assert_eq!(run_chained_post_commit(3), (Some(3), true));
+    }
+
     #[test]
     fn test_pre_push_hook_included_in_all() {
         assert!(
@@ -1,8 +1,13 @@
 //! Import command - import sessions from AI coding tools.
 //!
 //! Discovers and imports session files from multiple AI coding tools into the
-//! Lore database. Tracks which files have been imported to avoid duplicates
-//! on subsequent runs.
+//! Lore database. The import loop itself is
+//! [`crate::capture::import::import_all`]; this command chooses the watchers,
+//! prints progress and a per-tool summary, and adds `--path` imports of
+//! archived files. Records the modification time and size of each imported
+//! file, so later runs skip unchanged files and re-parse files that have
+//! grown, adding only their new messages. Sessions whose working directory
+//! matches a `.loreignore` rule are skipped.
 //!
 //! Supported tools:
 //! - Aider (markdown chat history files)
@@ -12,12 +17,23 @@
 //! - Continue.dev (JSON session files)
 //! - Gemini CLI (JSON files)
 
-use anyhow::Result;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
 
-use crate::capture::watchers::default_registry;
+pub use crate::capture::import::default_jobs;
+use crate::capture::import::{
+    guard_panic, import_all_with_progress, parse_in_parallel, source_unchanged, store_sessions,
+    IgnoredSession, ImportEvent, ParsedSessions, ToolImportStats,
+};
+use crate::capture::repositories::RepositoryRecorder;
+use crate::capture::watchers::{default_registry, Watcher, WatcherRegistry};
+use crate::capture::{ImportOptions, ImportReport};
 use crate::config::Config;
-use crate::storage::Database;
+use crate::storage::{Database, SourceStamp};
 
 /// Arguments for the import command.
 #[derive(clap::Args)]
@@ -37,15 +53,28 @@ use crate::storage::Database;
     after_help = "EXAMPLES:\n    \
         lore import              Import new sessions from all tools\n    \
         lore import --dry-run    Preview what would be imported\n    \
-        lore import --force      Re-import all sessions"
+        lore import --force      Re-import all sessions\n    \
+        lore import --tool claude-code\n    \
+                                 Import only Claude Code sessions\n    \
+        lore import --jobs 4     Parse at most 4 files at a time\n    \
+        lore import --show-ignored\n    \
+                                 List sessions skipped by .loreignore\n    \
+        lore import --path ~/backup/claude-projects\n    \
+                                 Import an archived session folder\n    \
+        lore import --path chat.jsonl --tool codex\n    \
+                                 Import one file with a chosen parser\n\n\
+        Progress updates in place on a terminal and prints every\n\
+        100 files otherwise. A file that fails to parse is reported\n\
+        and skipped without stopping the import."
 )]
 pub struct Args {
     /// Force re-import of already imported sessions
     #[arg(long)]
     #[arg(
         long_help = "By default, Lore tracks which session files have been imported\n\
-        and skips them on subsequent runs. Use this flag to re-import\n\
-        all sessions, which may update existing records."
+        and skips them on subsequent runs unless they have changed. Use\n\
+        this flag to re-import all sessions, which may update existing\n\
+        records."
     )]
     pub force: bool,
 
@@ -54,329 +83,803 @@ pub struct Args {
     #[arg(long_help = "Shows what sessions would be imported without actually\n\
         modifying the database. Useful for verifying before import.")]
     pub dry_run: bool,
-}
-
-/// Executes the import command.
-///
-/// Scans for session files from enabled AI coding tools, parses them,
-/// and stores sessions and messages in the database. Uses the configuration
-/// to determine which watchers are enabled.
-pub fn run(args: Args) -> Result<()> {
-    let db = Database::open_default()?;
-    let config = Config::load()?;
-    let registry = default_registry();
-
-    // Get enabled watchers from config
-    let watchers = registry.enabled_watchers(&config.watchers);
-
-    let mut total_imported = 0;
-    let mut total_skipped = 0;
-    let mut total_errors = 0;
-    let mut tools_imported_from = 0;
 
-    if watchers.is_empty() {
-        println!("{}", "No enabled watchers found.".yellow());
-        println!();
-        println!("Check your configuration with: {}", "lore config".cyan());
-        println!("Run {} to detect and enable watchers.", "lore init".cyan());
-        return Ok(());
-    }
-
-    for watcher in &watchers {
-        let info = watcher.info();
-        println!("{}", format!("Importing from {}...", info.name).dimmed());
-
-        // Find source files for this watcher
-        let sources = match watcher.find_sources() {
-            Ok(sources) => sources,
-            Err(e) => {
-                tracing::warn!("Failed to find sources for {}: {}", info.name, e);
-                println!("  {}", format!("Error finding sources: {e}").red());
-                total_errors += 1;
-                continue;
-            }
-        };
-
-        if sources.is_empty() {
-            println!("  {}", "No sessions found".dimmed());
-            continue;
-        }
-
-        println!("  Found {} source files", sources.len().to_string().green());
-
-        let mut watcher_imported = 0;
-        let mut watcher_skipped = 0;
-        let mut watcher_errors = 0;
+    /// Import from this file or directory instead of the tools' locations
+    #[arg(long, value_name = "PATH")]
+    #[arg(
+        long_help = "Import session files from this path instead of scanning each\n\
+        tool's default location, for example an archived copy of\n\
+        ~/.claude/projects. Directories are searched recursively for\n\
+        .json, .jsonl, and .md files. Each file's tool is detected from\n\
+        its shape unless --tool is given. Watchers do not need to be\n\
+        enabled to import this way."
+    )]
+    pub path: Option<PathBuf>,
 
-        for path in sources {
-            let path_str = path.to_string_lossy();
+    /// Import only from this tool, such as 'claude-code'
+    #[arg(long, value_name = "NAME")]
+    #[arg(
+        long_help = "Import only from this tool's watcher instead of scanning every\n\
+        enabled one, such as 'claude-code', 'codex', or 'aider'. The\n\
+        tool does not need to be enabled in the config, but its session\n\
+        storage must exist. With --path, parse the given files using\n\
+        this tool's parser instead of detecting it. Run 'lore status'\n\
+        to see the tool names."
+    )]
+    pub tool: Option<String>,
 
-            // Check if already imported
-            if !args.force && db.session_exists_by_source(&path_str)? {
-                watcher_skipped += 1;
-                tracing::debug!("Skipping already imported: {}", path_str);
-                continue;
-            }
+    /// Number of files to parse in parallel
+    #[arg(short, long, value_name = "N")]
+    #[arg(
+        long_help = "Parse up to N session files at the same time. Defaults to\n\
+        the number of CPUs. Sessions are still written to the database\n\
+        one at a time, in file order, so the result does not depend on\n\
+        this setting. Use --jobs 1 to import serially."
+    )]
+    pub jobs: Option<usize>,
 
-            // Parse the source file
-            match watcher.parse_source(&path) {
-                Ok(sessions) => {
-                    if sessions.is_empty() {
-                        tracing::debug!("No sessions in source: {}", path_str);
-                        watcher_skipped += 1;
-                        continue;
-                    }
+    /// List the sessions skipped by .loreignore rules
+    #[arg(long)]
+    #[arg(
+        long_help = "After importing, list each session that was skipped because\n\
+        its working directory matches a pattern in ~/.lore/.loreignore\n\
+        or in a .loreignore file in the directory or one of its parents.\n\
+        Files with ignored sessions are read again on every import, so\n\
+        removing a pattern takes effect without --force."
+    )]
+    pub show_ignored: bool,
+}
 
-                    for (session, messages) in sessions {
-                        if messages.is_empty() {
-                            tracing::debug!("Skipping empty session: {}", session.id);
-                            watcher_skipped += 1;
-                            continue;
-                        }
+/// Non-TTY output prints a progress line after this many files.
+const PROGRESS_LINE_INTERVAL: usize = 100;
 
-                        if args.dry_run {
-                            let dir = session
-                                .working_directory
-                                .split('/')
-                                .next_back()
-                                .unwrap_or(&session.working_directory);
-                            println!(
-                                "    {} {} ({} messages, {})",
-                                "Would import:".dimmed(),
-                                &session.id.to_string()[..8].cyan(),
-                                messages.len(),
-                                dir
-                            );
-                            watcher_imported += 1;
-                        } else {
-                            // Store session
-                            db.insert_session(&session)?;
-
-                            // Store messages
-                            for msg in &messages {
-                                db.insert_message(msg)?;
-                            }
-
-                            let dir = session
-                                .working_directory
-                                .split('/')
-                                .next_back()
-                                .unwrap_or(&session.working_directory);
-
-                            println!(
-                                "    {} {} ({} messages, {})",
-                                "Imported:".green(),
-                                &session.id.to_string()[..8].cyan(),
-                                messages.len(),
-                                dir
-                            );
-
-                            watcher_imported += 1;
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to parse {}: {}", path_str, e);
-                    watcher_errors += 1;
-                }
-            }
+/// Executes the import command.
+///
+/// Scans for session files from enabled AI coding tools, or only from the
+/// `--tool` watcher, parses them, and stores sessions and messages in the
+/// database. Uses the configuration to determine which watchers are
+/// enabled. Finishes with a per-tool summary
+/// and a list of any watchers that failed; fails only if all of them did.
+pub fn run(args: Args) -> Result<()> {
+    let jobs = match args.jobs {
+        Some(0) => bail!("--jobs must be at least 1"),
+        Some(jobs) => jobs,
+        None => default_jobs(),
+    };
+
+    let stats = if let Some(path) = &args.path {
+        run_path_import(path, args.tool.as_deref(), args.force, args.dry_run, jobs)?
+    } else if let Some(tool) = &args.tool {
+        run_tool_import(tool, args.force, args.dry_run, jobs)?
+    } else {
+        let config = Config::load()?;
+        if default_registry()
+            .enabled_watchers(&config.watchers)
+            .is_empty()
+        {
+            println!("{}", "No enabled watchers found.".yellow());
+            println!();
+            println!("Check your configuration with: {}", "lore config".cyan());
+            println!("Run {} to detect and enable watchers.", "lore init".cyan());
+            return Ok(());
         }
 
-        if watcher_imported > 0 {
-            tools_imported_from += 1;
-        }
-        total_imported += watcher_imported;
-        total_skipped += watcher_skipped;
-        total_errors += watcher_errors;
-    }
+        run_import(args.force, args.dry_run, jobs)?
+    };
 
-    println!();
+    if !crate::cli::is_quiet() {
+        println!();
+        print_tool_summary(&stats.by_tool);
+        println!();
+    }
+    if args.show_ignored {
+        print_ignored(&stats.by_tool);
+        println!();
+    }
 
     if args.dry_run {
         println!(
             "{}",
             format!(
-                "Dry run: would import {total_imported} sessions from {tools_imported_from} tools"
+                "Dry run: would import {} sessions from {} tools",
+                stats.imported, stats.tools_count
             )
             .bold()
         );
-        if total_skipped > 0 || total_errors > 0 {
-            println!("  ({total_skipped} skipped, {total_errors} errors)");
+        if stats.skipped > 0 || stats.errors > 0 {
+            println!("  ({} skipped, {} errors)", stats.skipped, stats.errors);
         }
     } else {
-        println!(
+        note!(
             "{}",
-            format!("Imported {total_imported} sessions from {tools_imported_from} tools").bold()
+            format!(
+                "Imported {} sessions from {} tools",
+                stats.imported, stats.tools_count
+            )
+            .bold()
         );
-        if total_skipped > 0 || total_errors > 0 {
-            println!("  ({total_skipped} skipped, {total_errors} errors)");
+        if stats.skipped > 0 || stats.errors > 0 {
+            note!("  ({} skipped, {} errors)", stats.skipped, stats.errors);
         }
 
-        if total_imported > 0 {
-            println!();
-            println!(
+        if stats.imported > 0 {
+            note!();
+            note!(
                 "{}",
                 "Run 'lore sessions' to see imported sessions".dimmed()
             );
         }
     }
 
-    Ok(())
-}
+    if !stats.failed.is_empty() {
+        println!();
+        println!("{}", "Failed watchers:".red().bold());
+        for (tool, error) in &stats.failed {
+            println!("  {}: {error}", tool.red());
+        }
+        if stats.all_failed() {
+            bail!("All {} watchers failed to import", stats.tools_attempted);
+        }
+    }
 
-/// Import statistics returned by the import operation.
-#[derive(Debug, Default)]
-pub struct ImportStats {
-    /// Number of sessions imported.
-    pub imported: usize,
-    /// Number of sessions skipped.
-    pub skipped: usize,
-    /// Number of errors encountered.
-    pub errors: usize,
-    /// Number of tools that had sessions imported.
-    pub tools_count: usize,
+    Ok(())
 }
 
 /// Runs the import operation and returns statistics.
 ///
 /// This is a lower-level function that can be called from other commands
-/// (like init) without printing output. The caller is responsible for
-/// displaying results.
-pub fn run_import(force: bool, dry_run: bool) -> Result<ImportStats> {
-    let db = Database::open_default()?;
+/// (like init). It prints per-tool progress while importing; the caller is
+/// responsible for displaying the final results. Up to `jobs` files are
+/// parsed at a time.
+pub fn run_import(force: bool, dry_run: bool, jobs: usize) -> Result<ImportReport> {
     let config = Config::load()?;
-    let registry = default_registry();
-
-    let watchers = registry.enabled_watchers(&config.watchers);
-
-    let mut stats = ImportStats::default();
+    import_with_config(&config, config.watchers.clone(), force, dry_run, jobs)
+}
 
-    for watcher in &watchers {
-        let info = watcher.info();
-        println!("{}", format!("Importing from {}...", info.name).dimmed());
+/// Runs the import for the single watcher named `tool`, whether or not it
+/// is enabled in the config.
+///
+/// Fails if no watcher has that name or its tool is not available on this
+/// system.
+pub fn run_tool_import(
+    tool: &str,
+    force: bool,
+    dry_run: bool,
+    jobs: usize,
+) -> Result<ImportReport> {
+    let config = Config::load()?;
+    available_watcher(&default_registry(), tool)?;
+    import_with_config(&config, vec![tool.to_string()], force, dry_run, jobs)
+}
 
-        let sources = match watcher.find_sources() {
-            Ok(sources) => sources,
-            Err(e) => {
-                tracing::warn!("Failed to find sources for {}: {}", info.name, e);
-                println!("  {}", format!("Error finding sources: {e}").red());
-                stats.errors += 1;
-                continue;
-            }
-        };
+/// Imports from the named watchers with the settings from `config`,
+/// printing progress as it goes.
+fn import_with_config(
+    config: &Config,
+    watchers: Vec<String>,
+    force: bool,
+    dry_run: bool,
+    jobs: usize,
+) -> Result<ImportReport> {
+    let db = Database::open_default()?;
+    let options = ImportOptions {
+        watchers,
+        force,
+        dry_run,
+        jobs,
+        ..ImportOptions::from_config(config)?
+    };
+    let mut progress = None;
+    Ok(import_all_with_progress(
+        &db,
+        &default_registry(),
+        options,
+        |event| print_event(&mut progress, event),
+    ))
+}
 
-        if sources.is_empty() {
-            println!("  {}", "No sessions found".dimmed());
-            continue;
-        }
+/// Looks up a watcher by name, listing the known names if there is none.
+fn named_watcher<'a>(registry: &'a WatcherRegistry, name: &str) -> Result<&'a dyn Watcher> {
+    registry.get_watcher(name).with_context(|| {
+        let known: Vec<&str> = registry
+            .all_watchers()
+            .iter()
+            .map(|w| w.info().name)
+            .collect();
+        format!("Unknown tool '{name}'. Known tools: {}", known.join(", "))
+    })
+}
 
-        println!("  Found {} source files", sources.len().to_string().green());
+/// Looks up a watcher by name and checks that its tool is available.
+fn available_watcher<'a>(registry: &'a WatcherRegistry, name: &str) -> Result<&'a dyn Watcher> {
+    let watcher = named_watcher(registry, name)?;
+    if !watcher.is_available() {
+        bail!(
+            "{name} is not available on this system: its session storage was not found. \
+             Run 'lore status' to see which tools were detected."
+        );
+    }
+    Ok(watcher)
+}
 
-        let mut watcher_imported = 0;
+/// Imports sessions from an explicit file or directory.
+///
+/// Each file is parsed by the `tool` watcher when one is named, or else by
+/// the first watcher matching the file's extension that finds sessions in
+/// it. Files imported before and unchanged since are skipped unless `force`
+/// is set. Up to `jobs` files are parsed at a time. Fails if no watcher can
+/// parse anything under `path`.
+pub fn run_path_import(
+    path: &Path,
+    tool: Option<&str>,
+    force: bool,
+    dry_run: bool,
+    jobs: usize,
+) -> Result<ImportReport> {
+    let registry = default_registry();
+    let watchers: Vec<&dyn Watcher> = match tool {
+        Some(name) => vec![named_watcher(&registry, name)?],
+        None => registry.all_watchers(),
+    };
 
-        for path in sources {
-            let path_str = path.to_string_lossy();
+    if !path.exists() {
+        bail!("Path not found: {}", path.display());
+    }
+    let files = collect_import_files(path)?;
+    if files.is_empty() {
+        bail!(
+            "No session files (.json, .jsonl, or .md) found under {}",
+            path.display()
+        );
+    }
 
-            if !force && db.session_exists_by_source(&path_str)? {
-                stats.skipped += 1;
-                tracing::debug!("Skipping already imported: {}", path_str);
-                continue;
+    let db = Database::open_default()?;
+    let options = ImportOptions::from_config(&Config::load()?)?;
+    let mut repositories = RepositoryRecorder::with_remote_names(options.remote_names.clone());
+
+    note!(
+        "{}",
+        format!("Importing from {}...", path.display()).dimmed()
+    );
+    note!("  Found {} files", files.len().to_string().green());
+
+    let mut stats = ImportReport::default();
+    let mut unparsed = Vec::new();
+    let mut progress = ImportProgress::new(files.len(), io::stdout().is_terminal());
+    let mut done = 0;
+
+    let mut pending = Vec::new();
+    for file in &files {
+        let stamp = SourceStamp::of(file).ok();
+        if !force && source_unchanged(&db, &file.to_string_lossy(), stamp.as_ref())? {
+            if let (Some(stamp), false) = (&stamp, dry_run) {
+                db.backfill_source_stamp(&file.to_string_lossy(), stamp)?;
             }
+            tracing::debug!("Skipping unchanged source: {}", file.display());
+            stats.skipped += 1;
+            done += 1;
+            progress.update(done, &path_import_totals(&stats, &unparsed));
+            continue;
+        }
+        pending.push((file, stamp));
+    }
 
-            match watcher.parse_source(&path) {
-                Ok(sessions) => {
-                    if sessions.is_empty() {
-                        tracing::debug!("No sessions in source: {}", path_str);
-                        stats.skipped += 1;
-                        continue;
-                    }
-
-                    for (session, messages) in sessions {
-                        if messages.is_empty() {
-                            tracing::debug!("Skipping empty session: {}", session.id);
-                            stats.skipped += 1;
-                            continue;
+    let timing = parse_in_parallel(
+        &pending,
+        jobs,
+        |(file, _)| parse_with_any(&watchers, file),
+        |(file, stamp), parsed| {
+            let path_str = file.to_string_lossy();
+            done += 1;
+
+            match parsed {
+                Ok((name, sessions)) => {
+                    let index = match stats.by_tool.iter().position(|t| t.tool == name) {
+                        Some(index) => index,
+                        None => {
+                            stats.by_tool.push(ToolImportStats {
+                                tool: name.to_string(),
+                                ..Default::default()
+                            });
+                            stats.by_tool.len() - 1
                         }
-
-                        if dry_run {
-                            let dir = session
-                                .working_directory
-                                .split('/')
-                                .next_back()
-                                .unwrap_or(&session.working_directory);
-                            println!(
-                                "    {} {} ({} messages, {})",
-                                "Would import:".dimmed(),
-                                &session.id.to_string()[..8].cyan(),
-                                messages.len(),
-                                dir
-                            );
-                            watcher_imported += 1;
-                        } else {
-                            db.insert_session(&session)?;
-
-                            for msg in &messages {
-                                db.insert_message(msg)?;
-                            }
-
-                            let dir = session
-                                .working_directory
-                                .split('/')
-                                .next_back()
-                                .unwrap_or(&session.working_directory);
-
-                            println!(
-                                "    {} {} ({} messages, {})",
-                                "Imported:".green(),
-                                &session.id.to_string()[..8].cyan(),
-                                messages.len(),
-                                dir
-                            );
-
-                            watcher_imported += 1;
+                    };
+                    let counts = &mut stats.by_tool[index];
+                    counts.files += 1;
+                    let ignored_before = counts.ignored.len();
+                    store_sessions(
+                        &db,
+                        options.redactor.as_ref(),
+                        &options.ignore,
+                        &mut repositories,
+                        sessions,
+                        dry_run,
+                        counts,
+                        &mut |event| progress.show(event),
+                    )?;
+                    if let (Some(stamp), false) = (stamp, dry_run) {
+                        if counts.ignored.len() == ignored_before {
+                            db.set_source_stamp(&path_str, stamp)?;
                         }
                     }
                 }
                 Err(e) => {
-                    tracing::warn!("Failed to parse {}: {}", path_str, e);
-                    stats.errors += 1;
+                    tracing::debug!("No watcher parsed {}: {}", path_str, e);
+                    progress.show(ImportEvent::Unreadable {
+                        path: file,
+                        error: &e,
+                    });
+                    unparsed.push(e);
                 }
             }
-        }
 
-        if watcher_imported > 0 {
+            progress.update(done, &path_import_totals(&stats, &unparsed));
+            Ok(())
+        },
+    )?;
+    progress.finish();
+    timing.log(&path.display().to_string(), pending.len(), jobs);
+
+    if stats.by_tool.is_empty() && stats.skipped == 0 {
+        let reason = match unparsed.as_slice() {
+            [only] => format!(": {only}"),
+            _ => String::new(),
+        };
+        let hint = if tool.is_none() {
+            " Use --tool <NAME> to choose a parser."
+        } else {
+            ""
+        };
+        bail!("No watcher could parse {}{reason}.{hint}", path.display());
+    }
+
+    for counts in &stats.by_tool {
+        if counts.imported > 0 {
             stats.tools_count += 1;
         }
-        stats.imported += watcher_imported;
+        stats.imported += counts.imported;
+        stats.skipped += counts.skipped;
     }
+    stats.errors = unparsed.len();
 
     Ok(stats)
 }
 
+/// Sums the per-tool counts of a path import for its progress line.
+fn path_import_totals(stats: &ImportReport, unparsed: &[anyhow::Error]) -> ToolImportStats {
+    ToolImportStats {
+        imported: stats.by_tool.iter().map(|t| t.imported).sum(),
+        skipped: stats.skipped + stats.by_tool.iter().map(|t| t.skipped).sum::<usize>(),
+        errors: unparsed.len(),
+        ..Default::default()
+    }
+}
+
+/// Returns the files to import from `path`.
+///
+/// A file is returned as is. A directory is searched recursively, skipping
+/// hidden entries, for files with a session file extension. Paths are sorted.
+fn collect_import_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries =
+            fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries {
+            let entry_path = entry?.path();
+            let hidden = entry_path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            if entry_path.is_dir() {
+                if !hidden {
+                    pending.push(entry_path);
+                }
+            } else if !shape_watchers(&entry_path).is_empty() {
+                files.push(entry_path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the watchers whose session files have the extension of `path`.
+fn shape_watchers(path: &Path) -> &'static [&'static str] {
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("jsonl") => &["claude-code", "codex"],
+        Some("json") => &[
+            "amp",
+            "cline",
+            "continue",
+            "gemini",
+            "kilo-code",
+            "opencode",
+            "roo-code",
+        ],
+        // Aider keeps its history in `.aider.chat.history.md`
+        Some("md") => &["aider"],
+        _ => &[],
+    }
+}
+
+/// Parses `path` with the first watcher that finds sessions in it.
+///
+/// Only the watchers matching the file's extension are tried; a file with
+/// another extension is offered to every watcher. Returns the watcher name
+/// with the sessions. With a single watcher its parse error is returned
+/// as is.
+fn parse_with_any(
+    watchers: &[&dyn Watcher],
+    path: &Path,
+) -> Result<(&'static str, ParsedSessions)> {
+    if let [watcher] = watchers {
+        let sessions = guard_panic(watcher.info().name, || watcher.parse_source(path))?;
+        if sessions.is_empty() {
+            bail!("no {} sessions found", watcher.info().name);
+        }
+        return Ok((watcher.info().name, sessions));
+    }
+
+    // Some parsers accept almost any text (Aider reads plain markdown), so
+    // only the watchers for a known extension are tried
+    let shape = shape_watchers(path);
+    let candidates = watchers
+        .iter()
+        .filter(|w| shape.is_empty() || shape.contains(&w.info().name));
+
+    for watcher in candidates {
+        match guard_panic(watcher.info().name, || watcher.parse_source(path)) {
+            Ok(sessions) if !sessions.is_empty() => return Ok((watcher.info().name, sessions)),
+            Ok(_) => {}
+            Err(e) => tracing::trace!(
+                "{} cannot parse {}: {e}",
+                watcher.info().name,
+                path.display()
+            ),
+        }
+    }
+    bail!("not recognized as a session file by any watcher")
+}
+
+/// Reports per-file progress while one tool's sources are imported.
+///
+/// On a terminal the progress line is redrawn in place; otherwise a line is
+/// printed every [`PROGRESS_LINE_INTERVAL`] files and after the last one.
+struct ImportProgress {
+    total: usize,
+    tty: bool,
+    /// Whether an in-place progress line is currently on screen.
+    drawn: bool,
+    /// The most recent progress line, redrawn after interleaved messages.
+    line: String,
+}
+
+impl ImportProgress {
+    fn new(total: usize, tty: bool) -> Self {
+        Self {
+            total,
+            tty,
+            drawn: false,
+            line: String::new(),
+        }
+    }
+
+    /// Records that `done` files have been processed.
+    fn update(&mut self, done: usize, counts: &ToolImportStats) {
+        self.line = progress_line(done, self.total, counts);
+        if crate::cli::is_quiet() {
+            return;
+        }
+        if self.tty {
+            self.redraw();
+        } else if done.is_multiple_of(PROGRESS_LINE_INTERVAL) || done == self.total {
+            println!("  {}", self.line);
+        }
+    }
+
+    /// Shows a file-level event: the progress line, a file that could not
+    /// be parsed, or a session a dry run would import.
+    fn show(&mut self, event: ImportEvent<'_>) {
+        match event {
+            ImportEvent::FileDone { done, counts, .. } => self.update(done, counts),
+            ImportEvent::Unreadable { path, error } => self.message(&format!(
+                "    {} {}: {error}",
+                "Skipped unreadable file".yellow(),
+                path.display()
+            )),
+            ImportEvent::WouldImport { session, messages } => {
+                let dir = session
+                    .working_directory
+                    .split('/')
+                    .next_back()
+                    .unwrap_or(&session.working_directory);
+                self.message(&format!(
+                    "    {} {} ({} messages, {})",
+                    "Would import:".dimmed(),
+                    &session.id.to_string()[..8].cyan(),
+                    messages,
+                    dir
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    /// Prints a line above the progress indicator.
+    fn message(&mut self, text: &str) {
+        self.clear();
+        println!("{text}");
+        if self.tty && !self.line.is_empty() {
+            self.redraw();
+        }
+    }
+
+    /// Leaves the final progress line on screen.
+    fn finish(&mut self) {
+        if self.drawn {
+            println!();
+            self.drawn = false;
+        }
+    }
+
+    fn redraw(&mut self) {
+        print!("\r\x1b[2K  {}", self.line);
+        let _ = io::stdout().flush();
+        self.drawn = true;
+    }
+
+    fn clear(&mut self) {
+        if self.drawn {
+            print!("\r\x1b[2K");
+            self.drawn = false;
+        }
+    }
+}
+
+/// Prints an event from a watcher import, keeping the progress indicator of
+/// the watcher being imported in `progress`.
+fn print_event(progress: &mut Option<ImportProgress>, event: ImportEvent<'_>) {
+    match event {
+        ImportEvent::WatcherStarted { tool } => {
+            note!("{}", format!("Importing from {tool}...").dimmed());
+        }
+        ImportEvent::SourcesFound { files: 0, .. } => {
+            note!("  {}", "No sessions found".dimmed());
+        }
+        ImportEvent::SourcesFound { files, .. } => {
+            note!("  Found {} source files", files.to_string().green());
+            *progress = Some(ImportProgress::new(files, io::stdout().is_terminal()));
+        }
+        ImportEvent::WatcherFinished { .. } => {
+            if let Some(mut finished) = progress.take() {
+                finished.finish();
+            }
+        }
+        ImportEvent::WatcherFailed { error, .. } => {
+            println!("  {}", format!("Import failed: {error:#}").red());
+        }
+        event => {
+            if let Some(progress) = progress {
+                progress.show(event);
+            }
+        }
+    }
+}
+
+/// Formats a progress line such as `120/3000 files: 80 imported, 38 skipped, 2 errors`.
+fn progress_line(done: usize, total: usize, counts: &ToolImportStats) -> String {
+    format!(
+        "{done}/{total} files: {} imported, {} skipped, {} errors",
+        counts.imported, counts.skipped, counts.errors
+    )
+}
+
+/// Prints the per-tool import counts as an aligned table.
+fn print_tool_summary(by_tool: &[ToolImportStats]) {
+    if by_tool.is_empty() {
+        return;
+    }
+
+    let width = by_tool
+        .iter()
+        .map(|t| t.tool.len())
+        .max()
+        .unwrap_or(0)
+        .max("Tool".len());
+
+    println!(
+        "{}",
+        format!(
+            "{:<width$}  {:>6}  {:>8}  {:>7}  {:>6}",
+            "Tool", "Files", "Imported", "Skipped", "Errors"
+        )
+        .bold()
+    );
+    for tool in by_tool {
+        println!(
+            "{:<width$}  {:>6}  {:>8}  {:>7}  {:>6}",
+            tool.tool, tool.files, tool.imported, tool.skipped, tool.errors
+        );
+    }
+}
+
+/// Lists the sessions skipped by `.loreignore` rules, for `--show-ignored`.
+fn print_ignored(by_tool: &[ToolImportStats]) {
+    let ignored: Vec<(&str, &IgnoredSession)> = by_tool
+        .iter()
+        .flat_map(|t| t.ignored.iter().map(move |s| (t.tool.as_str(), s)))
+        .collect();
+    if ignored.is_empty() {
+        println!(
+            "{}",
+            "No sessions were ignored by .loreignore rules.".dimmed()
+        );
+        return;
+    }
+
+    println!("{}", "Ignored by .loreignore:".bold());
+    for (tool, session) in ignored {
+        println!(
+            "  {}  {}  {}",
+            session.id.to_string()[..8].cyan(),
+            tool.dimmed(),
+            session.working_directory
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// A watcher with fixed sources that parses them as Claude Code files,
+    /// or fails in the requested way.
+    struct StubWatcher {
+        name: &'static str,
+        sources: Option<Vec<PathBuf>>,
+        panic_on_parse: bool,
+        available: bool,
+    }
+
+    impl Watcher for StubWatcher {
+        fn info(&self) -> crate::capture::watchers::WatcherInfo {
+            crate::capture::watchers::WatcherInfo {
+                name: self.name,
+                description: "Test",
+                default_paths: vec![],
+            }
+        }
+        fn is_available(&self) -> bool {
+            self.available
+        }
+        fn find_sources(&self) -> Result<Vec<PathBuf>> {
+            self.sources.clone().context("database is locked")
+        }
+        fn parse_source(&self, path: &Path) -> Result<ParsedSessions> {
+            if self.panic_on_parse {
+                panic!("unexpected schema");
+            }
+            default_registry()
+                .get_watcher("claude-code")
+                .unwrap()
+                .parse_source(path)
+        }
+        fn watch_paths(&self) -> Vec<PathBuf> {
+            vec![]
+        }
+    }
+
     #[test]
-    fn test_import_stats_default() {
-        let stats = ImportStats::default();
-        assert_eq!(stats.imported, 0);
-        assert_eq!(stats.skipped, 0);
-        assert_eq!(stats.errors, 0);
-        assert_eq!(stats.tools_count, 0);
+    fn test_available_watcher_rejects_unknown_and_unavailable_tools() {
+        let mut registry = WatcherRegistry::new();
+        for (name, available) in [("installed", true), ("missing", false)] {
+            registry.register(Box::new(StubWatcher {
+                name,
+                sources: Some(vec![]),
+                panic_on_parse: false,
+                available,
+            }));
+        }
+
+        let watcher = available_watcher(&registry, "installed").unwrap();
+        assert_eq!(watcher.info().name, "installed");
+
+        let Err(err) = available_watcher(&registry, "missing") else {
+            panic!("an unavailable tool was accepted");
+        };
+        assert!(err.to_string().contains("not available"));
+
+        let Err(err) = available_watcher(&registry, "cursor") else {
+            panic!("an unknown tool was accepted");
+        };
+        assert_eq!(
+            err.to_string(),
+            "Unknown tool 'cursor'. Known tools: installed, missing"
+        );
+        // --path imports parse with any known watcher, available or not
+        assert!(named_watcher(&registry, "missing").is_ok());
     }
 
     #[test]
-    fn test_import_stats_fields_are_accessible() {
-        let stats = ImportStats {
-            imported: 10,
-            skipped: 5,
+    fn test_progress_line() {
+        let counts = ToolImportStats {
+            tool: "claude-code".to_string(),
+            files: 3000,
+            imported: 80,
+            skipped: 38,
             errors: 2,
-            tools_count: 3,
+            ..Default::default()
         };
-        assert_eq!(stats.imported, 10);
-        assert_eq!(stats.skipped, 5);
-        assert_eq!(stats.errors, 2);
-        assert_eq!(stats.tools_count, 3);
+        assert_eq!(
+            progress_line(120, 3000, &counts),
+            "120/3000 files: 80 imported, 38 skipped, 2 errors"
+        );
+    }
+
+    #[test]
+    fn test_shape_watchers_by_extension() {
+        assert_eq!(
+            shape_watchers(Path::new("a/b.jsonl")),
+            ["claude-code", "codex"]
+        );
+        assert!(shape_watchers(Path::new("task/api.JSON")).contains(&"cline"));
+        assert_eq!(
+            shape_watchers(Path::new(".aider.chat.history.md")),
+            ["aider"]
+        );
+        assert!(shape_watchers(Path::new("notes.txt")).is_empty());
+    }
+
+    #[test]
+    fn test_collect_import_files_walks_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("project-a");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir_all(dir.path().join(".hidden")).unwrap();
+        fs::write(nested.join("s1.jsonl"), "").unwrap();
+        fs::write(dir.path().join("s2.json"), "").unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+        fs::write(dir.path().join(".hidden").join("s3.jsonl"), "").unwrap();
+
+        let files = collect_import_files(dir.path()).unwrap();
+        assert_eq!(
+            files,
+            vec![nested.join("s1.jsonl"), dir.path().join("s2.json")]
+        );
+
+        // A file path is returned as given, whatever its extension
+        let single = dir.path().join("notes.txt");
+        assert_eq!(collect_import_files(&single).unwrap(), vec![single]);
+    }
+
+    #[test]
+    fn test_parse_with_any_detects_claude_code_jsonl() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("archived.jsonl");
+        fs::write(
+            &file,
+            concat!(
+                r#"{"type":"user","sessionId":"6f1b2c3d-0000-4000-8000-000000000001","uuid":"6f1b2c3d-0000-4000-8000-000000000002","timestamp":"2025-01-15T10:00:00.000Z","cwd":"/archive/project","message":{"role":"user","content":"hello"}}"#,
+                "\n",
+                r#"{"type":"assistant","sessionId":"6f1b2c3d-0000-4000-8000-000000000001","uuid":"6f1b2c3d-0000-4000-8000-000000000003","parentUuid":"6f1b2c3d-0000-4000-8000-000000000002","timestamp":"2025-01-15T10:01:00.000Z","cwd":"/archive/project","message":{"role":"assistant","model":"claude-sonnet","content":"hi"}}"#,
+                "\n"
+            ),
+        )
+        .unwrap();
+
+        let registry = default_registry();
+        let (name, sessions) = parse_with_any(&registry.all_watchers(), &file).unwrap();
+        assert_eq!(name, "claude-code");
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].0.working_directory, "/archive/project");
+
+        let garbage = dir.path().join("garbage.json");
+        fs::write(&garbage, "{\"not\": \"a session\"}").unwrap();
+        assert!(parse_with_any(&registry.all_watchers(), &garbage).is_err());
     }
 }
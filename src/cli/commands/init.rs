@@ -7,6 +7,7 @@ use colored::Colorize;
 use std::io::{self, Write};
 use std::path::PathBuf;
 
+use crate::capture::redact::SecretRedactor;
 use crate::capture::watchers::aider::scan_directories_for_aider_files;
 use crate::capture::watchers::{default_registry, Watcher, WatcherRegistry};
 use crate::cli::commands::{completions, import};
@@ -218,7 +219,7 @@ pub fn run(args: Args) -> Result<()> {
         );
         if prompt_yes_no(&prompt, true)? {
             println!();
-            let stats = import::run_import(false, false)?;
+            let stats = import::run_import(false, false, import::default_jobs())?;
             println!();
             println!(
                 "{}",
@@ -1082,19 +1083,24 @@ fn offer_aider_scan(db: &Database) -> Result<()> {
 
     // Import the found files
     let watcher = crate::capture::watchers::aider::AiderWatcher;
+    let redactor = SecretRedactor::from_config(&Config::load()?)?;
     let mut imported = 0;
     let mut skipped = 0;
 
     for file_path in &found_files {
         match watcher.parse_source(file_path) {
             Ok(sessions) => {
-                for (session, messages) in sessions {
+                for (session, mut messages) in sessions {
                     // Check if already imported
                     if db.get_session(&session.id).ok().flatten().is_some() {
                         skipped += 1;
                         continue;
                     }
 
+                    if let Some(ref redactor) = redactor {
+                        redactor.redact_messages(&mut messages);
+                    }
+
                     // Import the session
                     if let Err(e) = db.insert_session(&session) {
                         println!("  {}: Failed to import session: {}", "Warning".yellow(), e);
@@ -1236,6 +1242,7 @@ fn prompt_provider_selection() -> Result<Option<String>> {
     println!("  [1] Anthropic (Claude)");
     println!("  [2] OpenAI (GPT)");
     println!("  [3] OpenRouter (multiple models)");
+    println!("  [4] Google Gemini");
     println!();
     print!("Provider [1]: ");
     io::stdout().flush()?;
@@ -1248,6 +1255,7 @@ fn prompt_provider_selection() -> Result<Option<String>> {
         "" | "1" => "anthropic",
         "2" => "openai",
         "3" => "openrouter",
+        "4" => "gemini",
         _ => {
             println!("{}: Invalid selection '{}'", "Warning".yellow(), input);
             return Ok(None);
@@ -1287,6 +1295,7 @@ fn capitalize_provider(provider: &str) -> &str {
         "anthropic" => "Anthropic",
         "openai" => "OpenAI",
         "openrouter" => "OpenRouter",
+        "gemini" => "Google Gemini",
         _ => provider,
     }
 }
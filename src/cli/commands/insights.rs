@@ -13,7 +13,6 @@ use std::path::Path;
 use crate::cli::OutputFormat;
 use crate::git;
 use crate::storage::db::Database;
-use crate::storage::models::extract_session_files;
 
 /// Arguments for the insights command.
 #[derive(clap::Args)]
@@ -191,7 +190,7 @@ fn period_description(since: Option<&DateTime<Utc>>) -> String {
     }
 }
 
-/// Gathers top files across sessions by loading messages and extracting file paths.
+/// Gathers top files across sessions from the session file index.
 fn gather_top_files(
     db: &Database,
     sessions: &[crate::storage::models::Session],
@@ -200,11 +199,8 @@ fn gather_top_files(
     let mut file_counts: HashMap<String, usize> = HashMap::new();
 
     for session in sessions {
-        let messages = db.get_messages(&session.id)?;
-        let files = extract_session_files(&messages, &session.working_directory);
-        // Count each file once per session (not once per mention)
-        let unique_files: std::collections::HashSet<String> = files.into_iter().collect();
-        for file in unique_files {
+        // The index holds each file once per session (not once per mention)
+        for file in db.get_session_files(&session.id)? {
             *file_counts.entry(file).or_insert(0) += 1;
         }
     }
@@ -261,7 +257,7 @@ pub fn run(args: Args) -> Result<()> {
     let period_desc = period_description(since.as_ref());
 
     match args.format {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Jsonl => {
             let output = InsightsOutput {
                 period: PeriodInfo {
                     since: since.map(|dt| dt.to_rfc3339()),
@@ -304,9 +300,10 @@ pub fn run(args: Args) -> Result<()> {
                     })
                     .collect(),
             };
-            let json = serde_json::to_string_pretty(&output)?;
+            let json = args.format.json_string(&output)?;
             println!("{json}");
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("insights")),
         OutputFormat::Text | OutputFormat::Markdown => {
             display_text(&DisplayData {
                 period_desc: &period_desc,
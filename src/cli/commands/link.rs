@@ -10,17 +10,67 @@ use colored::Colorize;
 use std::path::Path;
 use uuid::Uuid;
 
-use crate::storage::{Database, LinkCreator, LinkType, SessionLink};
+use crate::storage::db::LinkDecision;
+use crate::storage::{Database, ForgeRepo, LinkCreator, LinkType, Session, SessionLink};
 
+use crate::cli::table::{Cell, Column, Table};
 use crate::config::Config;
 use crate::git::{
-    calculate_link_confidence, get_commit_files, get_commit_info, get_commits_in_time_range,
+    get_commit_files, get_commit_info, get_commits_in_range, get_commits_in_time_range,
+    remote_for_path, resolve_commit_ref, score_link_weighted, CommitInfo, LinkScore, LinkWeights,
 };
-use crate::storage::extract_session_files;
 
 /// Default time window in minutes for finding sessions near a commit.
 const DEFAULT_WINDOW_MINUTES: i64 = 30;
 
+/// Time window in minutes for finding sessions near a commit with the
+/// file-overlap strategy, wide enough for a commit made the next day.
+const FILE_OVERLAP_WINDOW_MINUTES: i64 = 24 * 60;
+
+/// Remote name and forge repository recorded with a link.
+type LinkRemote = (Option<String>, Option<ForgeRepo>);
+
+/// Values for `--strategy`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LinkStrategy {
+    /// Weigh branch, file overlap, time proximity, and recent activity.
+    #[default]
+    Balanced,
+    /// Score on file overlap alone, ignoring when the commit was made.
+    FileOverlap,
+    /// Score on time proximity and recent activity alone.
+    Time,
+}
+
+impl LinkStrategy {
+    /// Returns the factor weights the strategy scores candidates with.
+    fn weights(self) -> LinkWeights {
+        match self {
+            LinkStrategy::Balanced => LinkWeights::BALANCED,
+            LinkStrategy::FileOverlap => LinkWeights::FILE_OVERLAP,
+            LinkStrategy::Time => LinkWeights::TIME,
+        }
+    }
+
+    /// Returns how many minutes either side of a commit to look for
+    /// candidate sessions.
+    fn window_minutes(self) -> i64 {
+        match self {
+            LinkStrategy::FileOverlap => FILE_OVERLAP_WINDOW_MINUTES,
+            LinkStrategy::Balanced | LinkStrategy::Time => DEFAULT_WINDOW_MINUTES,
+        }
+    }
+
+    /// Returns the strategy's name as given to `--strategy`.
+    fn name(self) -> &'static str {
+        match self {
+            LinkStrategy::Balanced => "balanced",
+            LinkStrategy::FileOverlap => "file-overlap",
+            LinkStrategy::Time => "time",
+        }
+    }
+}
+
 /// Arguments for the link command.
 #[derive(clap::Args)]
 #[command(after_help = "EXAMPLES:\n    \
@@ -30,6 +80,9 @@ const DEFAULT_WINDOW_MINUTES: i64 = 30;
     lore link abc123 --dry-run          Preview without linking\n    \
     lore link --auto                    Preview auto-link suggestions\n    \
     lore link --auto --yes              Apply auto-link suggestions\n    \
+    lore link --auto --range main..HEAD Auto-link every commit in a range\n    \
+    lore link --auto --explain          Show each candidate's score breakdown\n    \
+    lore link --auto --strategy file-overlap  Score on file overlap alone\n    \
     lore link --auto --backfill         Preview backfill suggestions\n    \
     lore link --auto --backfill --yes   Apply backfill suggestions\n    \
     lore link --current                 Link active sessions in this repo")]
@@ -63,10 +116,54 @@ pub struct Args {
     #[arg(long)]
     pub auto: bool,
 
+    /// Auto-link every commit in a range instead of a single commit
+    #[arg(
+        long,
+        value_name = "A..B",
+        requires = "auto",
+        conflicts_with_all = ["backfill", "commit"]
+    )]
+    #[arg(
+        long_help = "Auto-link each commit in the range <A>..<B>, the commits\n\
+        reachable from B but not from A (as in 'git log A..B'). Either side\n\
+        may be omitted and defaults to HEAD. Each commit is scored like\n\
+        'lore link --auto' and sessions already linked to it are skipped,\n\
+        so rerunning after a rebase only adds the missing links."
+    )]
+    pub range: Option<String>,
+
     /// Backfill session-to-commit links using session time windows
     #[arg(long)]
     pub backfill: bool,
 
+    /// Show the score breakdown of every auto-link candidate
+    #[arg(long, requires = "auto", conflicts_with = "backfill")]
+    #[arg(
+        long_help = "Print each candidate session's sub-scores for branch match,\n\
+        file overlap, time proximity, and recent activity, with the\n\
+        total and whether it met the threshold. Useful for tuning\n\
+        auto_link_threshold. Set auto_link_audit to also record every\n\
+        decision in the database."
+    )]
+    pub explain: bool,
+
+    /// Auto-link scoring strategy: balanced (default), file-overlap, time
+    #[arg(
+        long,
+        value_enum,
+        default_value = "balanced",
+        requires = "auto",
+        conflicts_with = "backfill"
+    )]
+    #[arg(
+        long_help = "How candidate sessions are scored against a commit. The\n\
+        threshold applies to the strategy's score. With file-overlap the\n\
+        score is the share of the commit's files the session touched, and\n\
+        sessions up to a day away from the commit are considered, so a\n\
+        commit made the next morning can still be linked."
+    )]
+    pub strategy: LinkStrategy,
+
     /// Auto-link confidence threshold (0.0 - 1.0)
     #[arg(long)]
     pub threshold: Option<f64>,
@@ -94,6 +191,8 @@ pub fn run(args: Args) -> Result<()> {
     } else if args.auto {
         if args.backfill {
             run_backfill_auto_link(args)
+        } else if let Some(range) = args.range.clone() {
+            run_range_auto_link(args, &range)
         } else {
             run_auto_link(args)
         }
@@ -116,7 +215,9 @@ fn run_manual_link(args: Args) -> Result<()> {
     // Resolve commit
     let commit_sha = resolve_commit(&args.commit)?;
     let short_sha = &commit_sha[..8.min(commit_sha.len())];
-    println!("Linking to commit {}", short_sha.yellow());
+    let config = Config::load()?;
+    let (remote, forge) = remote_for_path(Path::new("."), &config.git_remotes);
+    note!("Linking to commit {}", short_sha.yellow());
 
     // Find and link each session using efficient database lookup
     for session_prefix in &args.sessions {
@@ -154,15 +255,17 @@ fn run_manual_link(args: Args) -> Result<()> {
             link_type: LinkType::Commit,
             commit_sha: Some(commit_sha.clone()),
             branch: None,
-            remote: None,
+            remote: remote.clone(),
             created_at: Utc::now(),
             created_by: LinkCreator::User,
             confidence: None,
+            forge: forge.clone(),
+            factors: None,
         };
 
         db.insert_link(&link)?;
 
-        println!(
+        note!(
             "  {} session {} -> commit {}",
             "Linked".green(),
             &session.id.to_string()[..8].cyan(),
@@ -191,6 +294,8 @@ fn run_current_link(args: Args) -> Result<()> {
     // Resolve commit
     let commit_sha = resolve_commit(&args.commit)?;
     let short_sha = &commit_sha[..8.min(commit_sha.len())];
+    let config = Config::load()?;
+    let (remote, forge) = remote_for_path(Path::new("."), &config.git_remotes);
 
     // Find active sessions for this directory
     let sessions = db.find_active_sessions_for_directory(&repo_path, None)?;
@@ -201,7 +306,7 @@ fn run_current_link(args: Args) -> Result<()> {
         return Ok(());
     }
 
-    println!("Linking active sessions to commit {}", short_sha.yellow());
+    note!("Linking active sessions to commit {}", short_sha.yellow());
 
     let mut linked_count = 0;
     let mut skipped_existing = 0;
@@ -232,15 +337,17 @@ fn run_current_link(args: Args) -> Result<()> {
             link_type: LinkType::Commit,
             commit_sha: Some(commit_sha.clone()),
             branch: session.git_branch.clone(),
-            remote: None,
+            remote: remote.clone(),
             created_at: Utc::now(),
             created_by: LinkCreator::Auto,
             confidence: None, // Forward linking does not need confidence
+            forge: forge.clone(),
+            factors: None,
         };
 
         db.insert_link(&link)?;
 
-        println!(
+        note!(
             "  {} session {} -> commit {}",
             "Linked".green(),
             session_short_id.cyan(),
@@ -250,18 +357,18 @@ fn run_current_link(args: Args) -> Result<()> {
     }
 
     if linked_count > 0 || skipped_existing > 0 {
-        println!();
+        note!();
         if args.dry_run {
-            println!(
+            note!(
                 "Dry run complete: would link {} session(s)",
                 linked_count.to_string().green()
             );
         } else if linked_count > 0 {
-            println!("Linked {} session(s)", linked_count.to_string().green());
+            note!("Linked {} session(s)", linked_count.to_string().green());
         }
 
         if skipped_existing > 0 {
-            println!(
+            note!(
                 "Skipped {} already-linked session(s)",
                 skipped_existing.to_string().yellow()
             );
@@ -289,23 +396,25 @@ fn run_auto_link(args: Args) -> Result<()> {
 
     let short_sha = &commit_info.sha[..8.min(commit_info.sha.len())];
 
-    println!("Auto-linking to commit {}", short_sha.yellow());
-    println!(
+    note!("Auto-linking to commit {}", short_sha.yellow());
+    note!(
         "  Commit: {} ({})",
         commit_info.summary.dimmed(),
         commit_info.timestamp.format("%Y-%m-%d %H:%M")
     );
-    println!("  Files changed: {}", commit_files.len());
-    println!("  Threshold: {:.0}%", threshold * 100.0);
-    println!();
+    note!("  Files changed: {}", commit_files.len());
+    note!("  Threshold: {:.0}%", threshold * 100.0);
+    note!("  Strategy: {}", args.strategy.name());
+    note!();
 
     // Get working directory for filtering sessions
     let repo_path = get_repo_root(&cwd)?;
+    let (remote, forge) = remote_for_path(&cwd, &config.git_remotes);
 
     // Find sessions active near the commit time
     let candidates = db.find_sessions_near_commit_time(
         commit_info.timestamp,
-        DEFAULT_WINDOW_MINUTES,
+        args.strategy.window_minutes(),
         Some(&repo_path),
     )?;
 
@@ -317,42 +426,22 @@ fn run_auto_link(args: Args) -> Result<()> {
         return Ok(());
     }
 
-    println!("Found {} candidate session(s)", candidates.len());
-
-    // Score and filter sessions
-    let mut skipped_existing = 0;
-    let mut proposed: Vec<(String, Uuid, f64)> = Vec::new();
-
-    for session in &candidates {
-        // Check if already linked
-        if db.link_exists(&session.id, &commit_info.sha)? {
-            skipped_existing += 1;
-            continue;
-        }
-
-        // Get session files
-        let messages = db.get_messages(&session.id)?;
-        let session_files = extract_session_files(&messages, &session.working_directory);
-
-        // Calculate time difference in minutes
-        let session_end = session.ended_at.unwrap_or_else(Utc::now);
-        let time_diff = (commit_info.timestamp - session_end).num_minutes().abs();
-
-        // Calculate confidence score
-        let commit_branch = commit_info.branch.as_deref().unwrap_or("unknown");
-        let confidence = calculate_link_confidence(
-            session.git_branch.as_deref(),
-            &session_files,
-            commit_branch,
-            &commit_files,
-            time_diff,
-        );
-
-        let session_short_id = &session.id.to_string()[..8];
-
-        if confidence >= threshold {
-            proposed.push((session_short_id.to_string(), session.id, confidence));
-        } else {
+    note!("Found {} candidate session(s)", candidates.len());
+
+    let audit = config.auto_link_audit && !args.dry_run;
+    let scored = score_candidates(
+        &db,
+        &candidates,
+        &commit_info,
+        &commit_files,
+        args.strategy,
+        threshold,
+        audit,
+    )?;
+    if args.explain {
+        print_explanation(&scored, threshold, "  ");
+    } else {
+        for (session_short_id, confidence) in &scored.below_threshold {
             println!(
                 "  {} {} (confidence: {:.0}% < {:.0}%)",
                 "Skipped".dimmed(),
@@ -362,6 +451,8 @@ fn run_auto_link(args: Args) -> Result<()> {
             );
         }
     }
+    let proposed = scored.proposed;
+    let skipped_existing = scored.skipped_existing;
 
     println!();
     if proposed.is_empty() {
@@ -371,13 +462,14 @@ fn run_auto_link(args: Args) -> Result<()> {
             "{} session(s) meet the confidence threshold:",
             proposed.len().to_string().green()
         );
-        for (session_short_id, _session_id, confidence) in &proposed {
+        for (session_short_id, _session_id, confidence, _score) in &proposed {
             println!(
-                "  {} Would link {} -> {} (confidence: {:.0}%)",
+                "  {} Would link {} -> {} (confidence: {:.0}%, {})",
                 "[dry-run]".cyan(),
                 session_short_id.cyan(),
                 short_sha,
-                confidence * 100.0
+                confidence * 100.0,
+                args.strategy.name()
             );
         }
     }
@@ -406,24 +498,310 @@ fn run_auto_link(args: Args) -> Result<()> {
     }
 
     let mut linked_count = 0;
-    for (_session_short_id, session_id, confidence) in proposed {
+    for (_session_short_id, session_id, confidence, score) in proposed {
         let link = SessionLink {
             id: Uuid::new_v4(),
             session_id,
             link_type: LinkType::Commit,
             commit_sha: Some(commit_info.sha.clone()),
             branch: commit_info.branch.clone(),
-            remote: None,
+            remote: remote.clone(),
             created_at: Utc::now(),
             created_by: LinkCreator::Auto,
             confidence: Some(confidence),
+            forge: forge.clone(),
+            factors: Some(score.into()),
         };
 
         db.insert_link(&link)?;
         linked_count += 1;
     }
 
-    println!("Linked {} session(s)", linked_count.to_string().green());
+    note!(
+        "Linked {} session(s) with the {} strategy",
+        linked_count.to_string().green(),
+        args.strategy.name()
+    );
+
+    Ok(())
+}
+
+/// Auto-link candidates for one commit, split by the confidence threshold.
+struct ScoredCandidates {
+    /// Sessions to link: short ID, session ID, confidence, and score
+    /// breakdown.
+    proposed: Vec<(String, Uuid, f64, LinkScore)>,
+    /// Sessions below the threshold: short ID and confidence.
+    below_threshold: Vec<(String, f64)>,
+    /// Candidates already linked to the commit.
+    skipped_existing: usize,
+    /// Every scored candidate, in order: short ID and score breakdown.
+    scores: Vec<(String, LinkScore)>,
+}
+
+/// Scores candidate sessions against a commit with the weights of
+/// `strategy`.
+///
+/// Sessions already linked to the commit are counted and left out. With
+/// `audit`, each decision is recorded in the link_decisions table.
+fn score_candidates(
+    db: &Database,
+    candidates: &[Session],
+    commit_info: &CommitInfo,
+    commit_files: &[String],
+    strategy: LinkStrategy,
+    threshold: f64,
+    audit: bool,
+) -> Result<ScoredCandidates> {
+    let mut scored = ScoredCandidates {
+        proposed: Vec::new(),
+        below_threshold: Vec::new(),
+        skipped_existing: 0,
+        scores: Vec::new(),
+    };
+
+    for session in candidates {
+        // Check if already linked
+        if db.link_exists(&session.id, &commit_info.sha)? {
+            scored.skipped_existing += 1;
+            continue;
+        }
+
+        // Get session files from the index
+        let session_files = db.get_session_files(&session.id)?;
+
+        // Calculate time difference in minutes
+        let session_end = session.ended_at.unwrap_or_else(Utc::now);
+        let time_diff = (commit_info.timestamp - session_end).num_minutes().abs();
+
+        // Calculate confidence score
+        let commit_branch = commit_info.branch.as_deref().unwrap_or("unknown");
+        let score = score_link_weighted(
+            &strategy.weights(),
+            session.git_branch.as_deref(),
+            &session_files,
+            commit_branch,
+            commit_files,
+            time_diff,
+        );
+        let confidence = score.total();
+
+        let session_short_id = &session.id.to_string()[..8];
+        scored.scores.push((session_short_id.to_string(), score));
+        if audit {
+            db.insert_link_decision(&LinkDecision {
+                session_id: session.id,
+                commit_sha: commit_info.sha.clone(),
+                branch_score: score.branch,
+                file_score: score.files,
+                time_score: score.time,
+                recency_score: score.recency,
+                confidence,
+                threshold,
+                passed: confidence >= threshold,
+                decided_at: Utc::now(),
+            })?;
+        }
+
+        if confidence >= threshold {
+            scored
+                .proposed
+                .push((session_short_id.to_string(), session.id, confidence, score));
+        } else {
+            scored
+                .below_threshold
+                .push((session_short_id.to_string(), confidence));
+        }
+    }
+
+    Ok(scored)
+}
+
+/// Prints the score breakdown of every scored candidate as a table.
+fn print_explanation(scored: &ScoredCandidates, threshold: f64, indent: &str) {
+    if scored.scores.is_empty() {
+        return;
+    }
+    let percent = |value: f64| Cell::plain(format!("{:.0}%", value * 100.0));
+    let mut table = Table::new(vec![
+        Column::new("SESSION"),
+        Column::new("BRANCH").right(),
+        Column::new("FILES").right(),
+        Column::new("TIME").right(),
+        Column::new("RECENCY").right(),
+        Column::new("TOTAL").right(),
+        Column::new("RESULT"),
+    ]);
+    for (session_short_id, score) in &scored.scores {
+        let result = if score.total() >= threshold {
+            Cell::styled("link", |s| s.green())
+        } else {
+            Cell::styled("below threshold", |s| s.dimmed())
+        };
+        table.add_row(vec![
+            Cell::styled(session_short_id.clone(), |s| s.cyan()),
+            percent(score.branch),
+            percent(score.files),
+            percent(score.time),
+            percent(score.recency),
+            percent(score.total()),
+            result,
+        ]);
+    }
+    for line in table.render() {
+        println!("{indent}{line}");
+    }
+}
+
+/// Runs automatic linking for every commit in a range.
+///
+/// Each commit is scored like [`run_auto_link`], oldest first. Shows a
+/// per-commit preview and requires --yes (or confirmation) to apply.
+fn run_range_auto_link(args: Args, range: &str) -> Result<()> {
+    let db = Database::open_default()?;
+    let config = Config::load()?;
+    let threshold = args.threshold.unwrap_or(config.auto_link_threshold);
+
+    let cwd = std::env::current_dir()?;
+    let commits = get_commits_in_range(&cwd, range)?;
+    if commits.is_empty() {
+        println!("{}", format!("No commits in range {range}.").yellow());
+        return Ok(());
+    }
+
+    let repo_path = get_repo_root(&cwd)?;
+    let (remote, forge) = remote_for_path(&cwd, &config.git_remotes);
+
+    note!(
+        "Auto-linking {} commit(s) in {}",
+        commits.len(),
+        range.yellow()
+    );
+    note!("  Threshold: {:.0}%", threshold * 100.0);
+    note!("  Strategy: {}", args.strategy.name());
+    note!();
+
+    let mut planned: Vec<(CommitInfo, ScoredCandidates)> = Vec::new();
+    let mut skipped_existing = 0;
+
+    for sha in &commits {
+        let commit_info = get_commit_info(&cwd, sha)?;
+        let commit_files = get_commit_files(&cwd, sha)?;
+        let candidates = db.find_sessions_near_commit_time(
+            commit_info.timestamp,
+            args.strategy.window_minutes(),
+            Some(&repo_path),
+        )?;
+        let scored = score_candidates(
+            &db,
+            &candidates,
+            &commit_info,
+            &commit_files,
+            args.strategy,
+            threshold,
+            config.auto_link_audit && !args.dry_run,
+        )?;
+        skipped_existing += scored.skipped_existing;
+
+        let mut detail = format!("{} to link", scored.proposed.len());
+        if scored.skipped_existing > 0 {
+            detail.push_str(&format!(", {} already linked", scored.skipped_existing));
+        }
+        println!(
+            "  {} {}  {}",
+            commit_info.sha[..8].yellow(),
+            commit_info.summary.chars().take(50).collect::<String>(),
+            format!("({detail})").dimmed()
+        );
+        if args.explain {
+            print_explanation(&scored, threshold, "    ");
+        } else {
+            for (session_short_id, _session_id, confidence, _score) in &scored.proposed {
+                println!(
+                    "    {} Would link {} (confidence: {:.0}%, {})",
+                    "[dry-run]".cyan(),
+                    session_short_id.cyan(),
+                    confidence * 100.0,
+                    args.strategy.name()
+                );
+            }
+        }
+
+        if !scored.proposed.is_empty() {
+            planned.push((commit_info, scored));
+        }
+    }
+
+    let total: usize = planned
+        .iter()
+        .map(|(_, scored)| scored.proposed.len())
+        .sum();
+    println!();
+    if total == 0 {
+        println!("{}", "No sessions met the confidence threshold.".yellow());
+    } else {
+        println!(
+            "{} link(s) across {} commit(s) meet the confidence threshold",
+            total.to_string().green(),
+            planned.len()
+        );
+    }
+    if skipped_existing > 0 {
+        println!(
+            "Skipped {} already-linked session(s)",
+            skipped_existing.to_string().yellow()
+        );
+    }
+
+    if args.dry_run || total == 0 {
+        return Ok(());
+    }
+
+    if !args.yes {
+        let mut input = String::new();
+        print!("Apply these links? (y/N): ");
+        std::io::Write::flush(&mut std::io::stdout())?;
+        std::io::stdin().read_line(&mut input)?;
+        let trimmed = input.trim().to_lowercase();
+        if trimmed != "y" && trimmed != "yes" {
+            println!("{}", "Aborted; no links created.".yellow());
+            return Ok(());
+        }
+    }
+
+    let mut linked_count = 0;
+    for (commit_info, scored) in planned {
+        let commit_linked = scored.proposed.len();
+        for (_session_short_id, session_id, confidence, score) in scored.proposed {
+            let link = SessionLink {
+                id: Uuid::new_v4(),
+                session_id,
+                link_type: LinkType::Commit,
+                commit_sha: Some(commit_info.sha.clone()),
+                branch: commit_info.branch.clone(),
+                remote: remote.clone(),
+                created_at: Utc::now(),
+                created_by: LinkCreator::Auto,
+                confidence: Some(confidence),
+                forge: forge.clone(),
+                factors: Some(score.into()),
+            };
+            db.insert_link(&link)?;
+        }
+        note!(
+            "  {} {} session(s) -> commit {}",
+            "Linked".green(),
+            commit_linked,
+            &commit_info.sha[..8]
+        );
+        linked_count += commit_linked;
+    }
+
+    note!(
+        "Linked {} session(s) with the {} strategy",
+        linked_count.to_string().green(),
+        args.strategy.name()
+    );
 
     Ok(())
 }
@@ -433,6 +811,7 @@ fn run_auto_link(args: Args) -> Result<()> {
 /// This scans ended sessions and links commits that fall between
 /// started_at and ended_at for each session.
 fn run_backfill_auto_link(args: Args) -> Result<()> {
+    let config = Config::load()?;
     let db = Database::open_default()?;
     // Use a high limit to effectively scan all sessions
     let sessions = db.list_ended_sessions(1_000_000, None)?;
@@ -443,7 +822,7 @@ fn run_backfill_auto_link(args: Args) -> Result<()> {
         return Ok(());
     }
 
-    let mut proposed: Vec<(Uuid, String, String, String)> = Vec::new();
+    let mut proposed: Vec<(Uuid, String, String, String, LinkRemote)> = Vec::new();
     let mut skipped_existing = 0usize;
     let mut skipped_missing_dir = 0usize;
     let mut skipped_non_git = 0usize;
@@ -475,6 +854,7 @@ fn run_backfill_auto_link(args: Args) -> Result<()> {
         if commits.is_empty() {
             continue;
         }
+        let remote = remote_for_path(working_dir, &config.git_remotes);
 
         let session_short_id = session.id.to_string();
         let session_short_id = &session_short_id[..8.min(session_short_id.len())];
@@ -495,6 +875,7 @@ fn run_backfill_auto_link(args: Args) -> Result<()> {
                     commit_short,
                     commit.summary.chars().take(60).collect::<String>()
                 ),
+                remote.clone(),
             ));
         }
     }
@@ -506,7 +887,7 @@ fn run_backfill_auto_link(args: Args) -> Result<()> {
             "{} session-to-commit link(s) found:",
             proposed.len().to_string().green()
         );
-        for (_session_id, session_short_id, _commit_sha, commit_label) in &proposed {
+        for (_session_id, session_short_id, _commit_sha, commit_label, _remote) in &proposed {
             println!(
                 "  {} Would link {} -> {}",
                 "[dry-run]".cyan(),
@@ -516,9 +897,11 @@ fn run_backfill_auto_link(args: Args) -> Result<()> {
         }
     }
 
-    println!(
+    note!(
         "Scanned {} ended session(s); {} with existing directories; {} in git repos",
-        total_sessions, sessions_with_existing_dir, sessions_in_git_repo
+        total_sessions,
+        sessions_with_existing_dir,
+        sessions_in_git_repo
     );
 
     if skipped_existing > 0 {
@@ -557,42 +940,39 @@ fn run_backfill_auto_link(args: Args) -> Result<()> {
     }
 
     let mut linked_count = 0usize;
-    for (session_id, _session_short_id, commit_sha, _commit_label) in proposed {
+    for (session_id, _session_short_id, commit_sha, _commit_label, (remote, forge)) in proposed {
         let link = SessionLink {
             id: Uuid::new_v4(),
             session_id,
             link_type: LinkType::Commit,
             commit_sha: Some(commit_sha),
             branch: None,
-            remote: None,
+            remote,
             created_at: Utc::now(),
             created_by: LinkCreator::Auto,
             confidence: Some(1.0),
+            forge,
+            factors: None,
         };
 
         db.insert_link(&link)?;
         linked_count += 1;
     }
 
-    println!("Linked {} session(s)", linked_count.to_string().green());
+    note!("Linked {} session(s)", linked_count.to_string().green());
 
     Ok(())
 }
 
 /// Resolves a commit reference to a full SHA.
+///
+/// Accepts everything [`resolve_commit_ref`] does, including reflog
+/// references like `HEAD@{2}` and `@{-1}`.
 fn resolve_commit(commit_ref: &str) -> Result<String> {
-    let repo = git2::Repository::discover(".")
+    git2::Repository::discover(".")
         .context("Not in a git repository. Use --commit to specify a commit SHA.")?;
 
-    let obj = repo
-        .revparse_single(commit_ref)
-        .with_context(|| format!("Could not resolve commit: {commit_ref}"))?;
-
-    let commit = obj
-        .peel_to_commit()
-        .with_context(|| format!("{commit_ref} is not a commit"))?;
-
-    Ok(commit.id().to_string())
+    resolve_commit_ref(Path::new("."), commit_ref)
 }
 
 /// Gets the root path of the git repository.
@@ -0,0 +1,131 @@
+//! Machine command - show and label this machine's identity.
+//!
+//! Every machine has a UUID, generated on first use and stored as
+//! `machine_id` in the config, which is recorded on the sessions it
+//! captures. The label (`machine_name`, the hostname unless set) is shared
+//! through sync so other machines can show where a pulled session came from.
+
+use anyhow::{bail, Result};
+use chrono::Utc;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::cli::OutputFormat;
+use crate::config::{system_hostname, Config};
+use crate::storage::{Database, Machine};
+
+/// Arguments for the machine command.
+#[derive(clap::Args)]
+#[command(after_help = "EXAMPLES:\n    \
+    lore machine                    Show this machine's ID and label\n    \
+    lore whoami                     Same as 'lore machine show'\n    \
+    lore machine show --format json Output as JSON\n    \
+    lore machine set-label laptop   Label this machine 'laptop'")]
+pub struct Args {
+    /// Machine subcommand (defaults to show)
+    #[command(subcommand)]
+    pub command: Option<MachineCommand>,
+
+    /// Output format: text (default), json
+    #[arg(short, long, value_enum, default_value = "text", global = true)]
+    pub format: OutputFormat,
+}
+
+/// Machine subcommands.
+#[derive(clap::Subcommand)]
+pub enum MachineCommand {
+    /// Show this machine's ID, label, and hostname
+    Show,
+
+    /// Set the label other machines see for this one
+    #[command(
+        long_about = "Sets the human-readable label for this machine, stored as\n\
+        machine_name in the config. Sessions pulled from this machine on\n\
+        other machines show the label once this machine has synced again."
+    )]
+    SetLabel {
+        /// The new label, such as 'work-laptop'
+        label: String,
+    },
+}
+
+/// JSON output for `lore machine show`.
+#[derive(Serialize)]
+struct MachineOutput {
+    machine_id: String,
+    label: String,
+    label_is_set: bool,
+    hostname: Option<String>,
+}
+
+/// Executes the machine command.
+pub fn run(args: Args) -> Result<()> {
+    match args.command {
+        None | Some(MachineCommand::Show) => run_show(args.format),
+        Some(MachineCommand::SetLabel { label }) => run_set_label(&label),
+    }
+}
+
+/// Prints this machine's identity, generating its ID if needed.
+fn run_show(format: OutputFormat) -> Result<()> {
+    let mut config = Config::load()?;
+    let output = MachineOutput {
+        machine_id: config.get_or_create_machine_id()?,
+        label: config.get_machine_name(),
+        label_is_set: config.machine_name.is_some(),
+        hostname: system_hostname(),
+    };
+
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            println!("{}", format.json_string(&output)?);
+        }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("machine")),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            println!("{}   {}", "Machine ID:".bold(), output.machine_id.cyan());
+            if output.label_is_set {
+                println!("{}        {}", "Label:".bold(), output.label.green());
+            } else {
+                println!(
+                    "{}        {} {}",
+                    "Label:".bold(),
+                    output.label,
+                    "(hostname; set one with 'lore machine set-label')".dimmed()
+                );
+            }
+            println!(
+                "{}     {}",
+                "Hostname:".bold(),
+                output.hostname.as_deref().unwrap_or("unknown")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Saves a new label and records it in the local machine registry.
+fn run_set_label(label: &str) -> Result<()> {
+    let label = label.trim();
+    if label.is_empty() {
+        bail!("Machine label cannot be empty");
+    }
+
+    let mut config = Config::load()?;
+    config.set_machine_name(label)?;
+    let machine_id = config.get_or_create_machine_id()?;
+
+    let db = Database::open_default()?;
+    db.upsert_machine(&Machine {
+        id: machine_id,
+        name: label.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+    })?;
+
+    note!("{} Machine label set to {}", "Done.".green(), label.cyan());
+    note!(
+        "{}",
+        "Other machines see the new label after this machine next syncs.".dimmed()
+    );
+    Ok(())
+}
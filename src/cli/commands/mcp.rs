@@ -25,8 +25,11 @@ pub enum McpCommand {
         - lore_search: Search session messages\n  \
         - lore_get_session: Get full session details\n  \
         - lore_list_sessions: List recent sessions\n  \
+        - lore_list_repos: List repositories with session history\n  \
         - lore_get_context: Get repository context\n  \
         - lore_get_linked_sessions: Get sessions linked to a commit\n  \
+        - lore_get_summary: Get or generate a session summary\n  \
+        - lore_get_sessions_for_file: Get sessions that touched a file\n  \
         - lore_get_memories: Get a project's mirrored memories\n  \
         - lore_search_memories: Search a project's mirrored memories"
     )]
@@ -47,10 +47,11 @@ pub fn run(args: Args) -> Result<()> {
     let memories = db.get_memories(&project_key, CLAUDE_CODE_TOOL)?;
 
     match args.format {
-        OutputFormat::Json => {
-            let json = serde_json::to_string_pretty(&memories)?;
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            let json = args.format.json_string(&memories)?;
             println!("{json}");
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("memories")),
         OutputFormat::Text | OutputFormat::Markdown => {
             if memories.is_empty() {
                 println!("{}", "No memories found for this project.".dimmed());
@@ -0,0 +1,161 @@
+//! Merge-sessions command - combine fragmented sessions into one.
+//!
+//! Some tools split one logical conversation across several session files.
+//! This command folds the other sessions into a primary session: their
+//! messages are reassigned and reindexed in timestamp order, their links,
+//! tags, and annotations move over, and the emptied sessions are deleted.
+//! The whole merge runs in one database transaction.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+
+use crate::storage::{Database, Session};
+
+/// Arguments for the merge-sessions command.
+#[derive(clap::Args)]
+#[command(after_help = "EXAMPLES:\n    \
+    lore merge-sessions abc123 def456          Merge def456 into abc123\n    \
+    lore merge-sessions abc123 def456 789abc   Merge several sessions\n    \
+    lore merge-sessions abc123 def456 --force  Merge across directories")]
+pub struct Args {
+    /// Session ID prefix of the session to keep
+    #[arg(value_name = "PRIMARY")]
+    #[arg(long_help = "The session ID prefix of the session that receives the\n\
+        messages and links. Its ID is kept; use 'lore sessions' to find\n\
+        session IDs.")]
+    pub primary: String,
+
+    /// Session ID prefixes of the sessions to merge into the primary
+    #[arg(value_name = "OTHER", required = true)]
+    #[arg(
+        long_help = "The session ID prefixes of the sessions to merge into the\n\
+        primary. These sessions are deleted once their messages, links,\n\
+        tags, and annotations have moved."
+    )]
+    pub others: Vec<String>,
+
+    /// Merge sessions from different working directories
+    #[arg(long)]
+    #[arg(long_help = "Merge even if the sessions were recorded in different\n\
+        working directories. Without this flag such a merge is refused,\n\
+        since the sessions are likely separate conversations.")]
+    pub force: bool,
+}
+
+/// Executes the merge-sessions command.
+pub fn run(args: Args) -> Result<()> {
+    let mut db = Database::open_default()?;
+
+    let primary = resolve_session(&db, &args.primary)?;
+    let mut others: Vec<Session> = Vec::new();
+    for prefix in &args.others {
+        let session = resolve_session(&db, prefix)?;
+        if session.id == primary.id {
+            bail!(
+                "Session {} cannot be merged into itself",
+                short_id(&session)
+            );
+        }
+        if !others.iter().any(|s| s.id == session.id) {
+            others.push(session);
+        }
+    }
+
+    if !args.force {
+        if let Some(message) = directory_mismatch(&primary, &others) {
+            bail!("{message}. Use --force to merge them anyway.");
+        }
+    }
+
+    let other_ids: Vec<_> = others.iter().map(|s| s.id).collect();
+    let report = db.merge_sessions(&primary.id, &other_ids)?;
+
+    note!(
+        "{} {} session{} into {} ({} messages, {} links moved)",
+        "Merged".green(),
+        report.sessions_deleted,
+        if report.sessions_deleted == 1 {
+            ""
+        } else {
+            "s"
+        },
+        short_id(&primary).cyan(),
+        report.messages_moved,
+        report.links_moved
+    );
+
+    Ok(())
+}
+
+/// Resolves a session ID prefix to a session.
+fn resolve_session(db: &Database, prefix: &str) -> Result<Session> {
+    db.find_session_by_id_prefix(prefix)?.with_context(|| {
+        format!(
+            "No session found matching '{prefix}'. \
+             Run 'lore sessions' to list available sessions."
+        )
+    })
+}
+
+/// Describes the first session recorded in a different working directory
+/// than the primary, if any.
+fn directory_mismatch(primary: &Session, others: &[Session]) -> Option<String> {
+    others
+        .iter()
+        .find(|s| s.working_directory != primary.working_directory)
+        .map(|s| {
+            format!(
+                "Session {} is in {}, but {} is in {}",
+                short_id(s),
+                s.working_directory,
+                short_id(primary),
+                primary.working_directory
+            )
+        })
+}
+
+fn short_id(session: &Session) -> String {
+    session.id.to_string()[..8].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn session(working_directory: &str) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at: Utc::now(),
+            ended_at: None,
+            model: None,
+            working_directory: working_directory.to_string(),
+            git_branch: None,
+            source_path: None,
+            message_count: 0,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_directory_mismatch() {
+        let primary = session("/home/user/project");
+        let same = session("/home/user/project");
+        let other = session("/home/user/elsewhere");
+
+        assert_eq!(
+            directory_mismatch(&primary, std::slice::from_ref(&same)),
+            None
+        );
+
+        let message = directory_mismatch(&primary, &[same, other.clone()]).unwrap();
+        assert!(message.contains(&short_id(&other)));
+        assert!(message.contains("/home/user/elsewhere"));
+    }
+}
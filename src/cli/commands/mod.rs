@@ -27,6 +27,9 @@ pub mod daemon;
 /// Database management (vacuum, prune, stats).
 pub mod db;
 
+/// Compare the conversations of two sessions.
+pub mod diff;
+
 /// Diagnose Lore installation and configuration issues.
 pub mod doctor;
 
@@ -57,6 +60,18 @@ pub mod mcp;
 /// List a project's mirrored memories.
 pub mod memories;
 
+/// Show and label this machine's identity.
+pub mod machine;
+
+/// Merge fragmented sessions into one.
+pub mod merge_sessions;
+
+/// Open linked commits on the forge in a browser.
+pub mod open;
+
+/// Step through a session message by message.
+pub mod replay;
+
 /// Search session content using FTS5 full-text search.
 pub mod search;
 
@@ -78,5 +93,11 @@ pub mod status;
 /// Add or remove tags from sessions.
 pub mod tag;
 
+/// Show sessions grouped by calendar day.
+pub mod timeline;
+
 /// Remove session-to-commit links.
 pub mod unlink;
+
+/// Check the database for corruption and orphaned records.
+pub mod verify;
@@ -0,0 +1,240 @@
+//! Open command - view linked commits on the forge in a browser.
+//!
+//! Builds the web URL of a commit from the forge repository recorded on
+//! its link, or parsed from the preferred git remote, and opens it with the
+//! platform's URL handler (`open` on macOS, `start` on Windows,
+//! `xdg-open` elsewhere). The URL is printed instead when `--print` is
+//! given or no browser can be launched.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+
+use crate::config::Config;
+use crate::git::{forge_for_path, resolve_commit_ref};
+use crate::storage::{Database, ForgeRepo, SessionLink};
+
+/// Arguments for the open command.
+#[derive(clap::Args)]
+#[command(after_help = "EXAMPLES:\n    \
+    lore open abc123               Open the commits linked to a session\n    \
+    lore open --commit HEAD        Open the current commit\n    \
+    lore open --commit HEAD~2      Open a commit by any git reference\n    \
+    lore open abc123 --print       Print the URLs without opening them")]
+pub struct Args {
+    /// Session ID prefix whose linked commits to open
+    #[arg(value_name = "SESSION", required_unless_present = "commit")]
+    #[arg(
+        long_help = "The session ID prefix whose linked commits to open. Every\n\
+        commit linked to the session is opened. Use 'lore sessions' to\n\
+        find session IDs."
+    )]
+    pub session: Option<String>,
+
+    /// Open a commit instead of a session's links
+    #[arg(long, value_name = "REF", conflicts_with = "session")]
+    #[arg(
+        long_help = "Open this commit instead of a session's linked commits.\n\
+        Accepts any git reference (SHA, HEAD, branch, tag) and resolves\n\
+        it in the repository containing the current directory."
+    )]
+    pub commit: Option<String>,
+
+    /// Print the URLs instead of opening a browser
+    #[arg(long)]
+    pub print: bool,
+}
+
+/// Executes the open command.
+///
+/// Resolves the commit URLs for the session or commit and opens each one,
+/// printing any URL that could not be opened.
+pub fn run(args: Args) -> Result<()> {
+    let config = Config::load()?;
+    let remote_names = &config.git_remotes;
+    let urls = match (&args.commit, &args.session) {
+        (Some(reference), _) => vec![commit_ref_url(reference, remote_names)?],
+        (None, Some(session)) => session_urls(session, remote_names)?,
+        (None, None) => bail!("Specify a session ID or --commit <REF>"),
+    };
+
+    for url in &urls {
+        if args.print || !open_in_browser(url) {
+            println!("{url}");
+        } else {
+            println!("{} {}", "Opened".green(), url);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the web URL of a commit reference in the current repository.
+///
+/// The forge is read from the first of `remote_names` the repository has.
+fn commit_ref_url(reference: &str, remote_names: &[String]) -> Result<String> {
+    let cwd = Path::new(".");
+    let sha = resolve_commit_ref(cwd, reference)?;
+    let forge = match forge_for_path(cwd, remote_names) {
+        Some(forge) => forge,
+        None => {
+            // Fall back to a forge recorded when the commit was linked
+            let db = Database::open_default()?;
+            db.get_links_by_commit(&sha)?
+                .into_iter()
+                .find_map(|link| link.forge)
+                .ok_or_else(|| unknown_forge_error("the current repository"))?
+        }
+    };
+    Ok(forge.commit_url(&sha))
+}
+
+/// Returns the web URLs of the commits linked to a session.
+fn session_urls(id_prefix: &str, remote_names: &[String]) -> Result<Vec<String>> {
+    let db = Database::open_default()?;
+    let session = db.find_session_by_id_prefix(id_prefix)?.with_context(|| {
+        format!(
+            "No session found matching '{id_prefix}'. \
+             Run 'lore sessions' to list available sessions."
+        )
+    })?;
+    let short_id = &session.id.to_string()[..8];
+
+    let links = db.get_links_by_session(&session.id)?;
+    if !links.iter().any(|l| l.commit_sha.is_some()) {
+        bail!(
+            "Session {short_id} has no linked commits. \
+             Use 'lore link' to link it to a commit."
+        );
+    }
+
+    let fallback = forge_for_path(Path::new(&session.working_directory), remote_names);
+    let urls = link_urls(&links, fallback.as_ref());
+    if urls.is_empty() {
+        return Err(unknown_forge_error(&format!("session {short_id}")));
+    }
+    Ok(urls)
+}
+
+/// Builds commit URLs for links, in order and without duplicates.
+///
+/// Each link uses its recorded forge, or `fallback` when none was recorded.
+/// Links without a commit or without any forge are skipped.
+fn link_urls(links: &[SessionLink], fallback: Option<&ForgeRepo>) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for link in links {
+        let Some(sha) = &link.commit_sha else {
+            continue;
+        };
+        let Some(forge) = link.forge.as_ref().or(fallback) else {
+            continue;
+        };
+        let url = forge.commit_url(sha);
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+/// Error for commits whose remote is not a recognized forge.
+fn unknown_forge_error(subject: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Cannot build a commit URL for {subject}: the repository's remote is not on a \
+         recognized forge (GitHub, GitLab, Bitbucket, or Codeberg)"
+    )
+}
+
+/// Opens `url` with the platform's URL handler.
+///
+/// Returns false if no handler could be launched or it reported failure.
+fn open_in_browser(url: &str) -> bool {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        // Without a display server xdg-open falls back to a text browser
+        if std::env::var_os("DISPLAY").is_none() && std::env::var_os("WAYLAND_DISPLAY").is_none() {
+            return false;
+        }
+        Command::new("xdg-open")
+    };
+
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{LinkCreator, LinkType};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn link(sha: Option<&str>, forge: Option<ForgeRepo>) -> SessionLink {
+        SessionLink {
+            id: Uuid::new_v4(),
+            session_id: Uuid::nil(),
+            link_type: LinkType::Commit,
+            commit_sha: sha.map(|s| s.to_string()),
+            branch: None,
+            remote: None,
+            created_at: Utc::now(),
+            created_by: LinkCreator::User,
+            confidence: None,
+            forge,
+            factors: None,
+        }
+    }
+
+    fn forge(owner: &str) -> ForgeRepo {
+        ForgeRepo {
+            host: "github.com".to_string(),
+            owner: owner.to_string(),
+            repo: "lore".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_link_urls_prefers_recorded_forge() {
+        let links = vec![
+            link(Some("aaa"), Some(forge("recorded"))),
+            link(Some("bbb"), None),
+        ];
+
+        assert_eq!(
+            link_urls(&links, Some(&forge("origin"))),
+            vec![
+                "https://github.com/recorded/lore/commit/aaa",
+                "https://github.com/origin/lore/commit/bbb",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_link_urls_skips_links_without_commit_or_forge() {
+        let links = vec![
+            link(None, Some(forge("recorded"))),
+            link(Some("bbb"), None),
+            link(Some("ccc"), Some(forge("recorded"))),
+            link(Some("ccc"), Some(forge("recorded"))),
+        ];
+
+        assert_eq!(
+            link_urls(&links, None),
+            vec!["https://github.com/recorded/lore/commit/ccc"]
+        );
+        assert!(link_urls(&links[..2], None).is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+//! Replay command - step through a session message by message.
+//!
+//! Re-renders a session's conversation in order, one message at a time, so
+//! it can be read back the way it unfolded. On a terminal the replay waits
+//! for Enter before each message, or advances on its own every `--delay`
+//! milliseconds. When stdin or stdout is not a terminal, every message is
+//! printed at once.
+
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+
+use crate::storage::{ContentBlock, Database, Message, MessageContent, MessageRole, Session};
+
+/// Maximum characters of a tool result shown in a replayed message.
+const MAX_RESULT_CHARS: usize = 200;
+
+/// Arguments for the replay command.
+#[derive(clap::Args)]
+#[command(after_help = "EXAMPLES:\n    \
+    lore replay abc123              Press Enter to step through the session\n    \
+    lore replay abc123 --delay 1500 Advance every 1.5 seconds\n    \
+    lore replay abc123 --role user  Replay only the human's messages\n    \
+    lore replay abc123 | less       Print the whole session at once")]
+pub struct Args {
+    /// Session ID prefix
+    #[arg(value_name = "SESSION")]
+    pub session: String,
+
+    /// Advance automatically every N milliseconds instead of waiting
+    #[arg(long, value_name = "MS")]
+    #[arg(long_help = "Show the next message after N milliseconds instead of\n\
+        waiting for Enter. Only applies on a terminal; piped output is\n\
+        always printed at once.")]
+    pub delay: Option<u64>,
+
+    /// Replay only messages from one role (user, assistant, system, tool)
+    #[arg(long, value_name = "ROLE")]
+    #[arg(long_help = "Replay only messages from a specific role:\n\
+        - user: human messages\n\
+        - assistant: AI responses\n\
+        - system: system prompts\n\
+        - tool: tool output reported back to the AI")]
+    pub role: Option<String>,
+
+    /// Include AI thinking blocks in output
+    #[arg(long)]
+    pub thinking: bool,
+}
+
+/// How the replay moves from one message to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pacing {
+    /// Wait for Enter before each message.
+    Keypress,
+    /// Wait a fixed time before each message.
+    Delay(Duration),
+    /// Print every message at once.
+    Continuous,
+}
+
+impl Pacing {
+    /// Chooses the pacing from `--delay` and whether the replay runs on a
+    /// terminal.
+    fn new(delay: Option<u64>, interactive: bool) -> Self {
+        match (interactive, delay) {
+            (false, _) => Pacing::Continuous,
+            (true, Some(ms)) => Pacing::Delay(Duration::from_millis(ms)),
+            (true, None) => Pacing::Keypress,
+        }
+    }
+}
+
+/// Executes the replay command.
+pub fn run(args: Args) -> Result<()> {
+    let role = parse_role(args.role.as_deref())?;
+    let db = Database::open_default()?;
+    let session = find_session(&db, &args.session)?;
+    let messages: Vec<Message> = db
+        .get_messages(&session.id)?
+        .into_iter()
+        .filter(|m| role.as_ref().is_none_or(|r| m.role == *r))
+        .collect();
+
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    let pacing = Pacing::new(args.delay, interactive);
+
+    println!(
+        "{} {} ({} messages)",
+        "Replaying session".bold(),
+        session.id.to_string().cyan(),
+        messages.len()
+    );
+    if pacing == Pacing::Keypress {
+        note!(
+            "{}",
+            "Press Enter for the next message, or q and Enter to stop.".dimmed()
+        );
+    }
+    println!();
+
+    let total = messages.len();
+    for (i, message) in messages.iter().enumerate() {
+        match pacing {
+            Pacing::Keypress if i > 0 && !wait_for_enter()? => return Ok(()),
+            Pacing::Delay(delay) if i > 0 => thread::sleep(delay),
+            _ => {}
+        }
+        println!("{}", message_header(message, i + 1, total));
+        for line in render_content(&message.content, args.thinking) {
+            println!("{line}");
+        }
+        println!();
+    }
+
+    note!("{}", "End of session".dimmed());
+    Ok(())
+}
+
+/// Looks up a session by ID prefix, failing with a helpful message.
+fn find_session(db: &Database, id_prefix: &str) -> Result<Session> {
+    match db.find_session_by_id_prefix(id_prefix)? {
+        Some(session) => Ok(session),
+        None => bail!(
+            "No session found matching '{id_prefix}'. \
+             Run 'lore sessions' to list available sessions."
+        ),
+    }
+}
+
+/// Parses the `--role` filter.
+fn parse_role(role: Option<&str>) -> Result<Option<MessageRole>> {
+    let Some(role) = role else {
+        return Ok(None);
+    };
+    match role.to_lowercase().as_str() {
+        "user" => Ok(Some(MessageRole::User)),
+        "assistant" => Ok(Some(MessageRole::Assistant)),
+        "system" => Ok(Some(MessageRole::System)),
+        "tool" => Ok(Some(MessageRole::Tool)),
+        _ => bail!("Invalid role '{role}'. Use 'user', 'assistant', 'system', or 'tool'."),
+    }
+}
+
+/// Waits for the user to press Enter. Returns false when they typed `q`
+/// or stdin was closed.
+fn wait_for_enter() -> Result<bool> {
+    io::stdout().flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Ok(false);
+    }
+    Ok(!input.trim().eq_ignore_ascii_case("q"))
+}
+
+/// Formats the line shown above a message, such as `[Human 14:02:11] 3/40`.
+fn message_header(message: &Message, position: usize, total: usize) -> String {
+    let role = match message.role {
+        MessageRole::User => "Human".green().bold(),
+        MessageRole::Assistant => "Assistant".blue().bold(),
+        MessageRole::System => "System".yellow().bold(),
+        MessageRole::Tool => "Tool".magenta().bold(),
+    };
+    format!(
+        "[{} {}] {}",
+        role,
+        message.timestamp.format("%H:%M:%S").to_string().dimmed(),
+        format!("{position}/{total}").dimmed()
+    )
+}
+
+/// Renders message content as lines of text. Tool calls are shown by name
+/// and tool results are shortened to their first characters.
+fn render_content(content: &MessageContent, show_thinking: bool) -> Vec<String> {
+    let blocks = match content {
+        MessageContent::Text(text) => return vec![text.clone()],
+        MessageContent::Blocks(blocks) => blocks,
+    };
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::Text { text } => Some(text.clone()),
+            ContentBlock::Thinking { thinking } if show_thinking => {
+                Some(format!("{} {}", "[thinking]".dimmed(), thinking.dimmed()))
+            }
+            ContentBlock::Thinking { .. } => None,
+            ContentBlock::ToolUse { name, .. } => {
+                Some(format!("[tool: {name}]").yellow().to_string())
+            }
+            ContentBlock::ToolResult {
+                content, is_error, ..
+            } => {
+                let mut preview: String = content.chars().take(MAX_RESULT_CHARS).collect();
+                if preview.len() < content.len() {
+                    preview.push_str("...");
+                }
+                let label = if *is_error { "[error]" } else { "[result]" };
+                Some(format!("{} {}", label.dimmed(), preview))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    fn message(role: MessageRole, content: MessageContent) -> Message {
+        Message {
+            id: Uuid::new_v4(),
+            session_id: Uuid::nil(),
+            parent_id: None,
+            index: 0,
+            timestamp: Utc.with_ymd_and_hms(2026, 3, 1, 14, 2, 11).unwrap(),
+            role,
+            content,
+            model: None,
+            git_branch: None,
+            cwd: None,
+        }
+    }
+
+    #[test]
+    fn test_pacing_prints_everything_when_not_interactive() {
+        assert_eq!(Pacing::new(None, false), Pacing::Continuous);
+        assert_eq!(Pacing::new(Some(500), false), Pacing::Continuous);
+    }
+
+    #[test]
+    fn test_pacing_on_a_terminal() {
+        assert_eq!(Pacing::new(None, true), Pacing::Keypress);
+        assert_eq!(
+            Pacing::new(Some(500), true),
+            Pacing::Delay(Duration::from_millis(500))
+        );
+    }
+
+    #[test]
+    fn test_parse_role() {
+        assert_eq!(parse_role(None).unwrap(), None);
+        assert_eq!(parse_role(Some("User")).unwrap(), Some(MessageRole::User));
+        assert_eq!(parse_role(Some("tool")).unwrap(), Some(MessageRole::Tool));
+        let err = parse_role(Some("robot")).unwrap_err();
+        assert!(err.to_string().contains("Invalid role 'robot'"));
+    }
+
+    #[test]
+    fn test_message_header() {
+        colored::control::set_override(false);
+        let msg = message(MessageRole::User, MessageContent::Text("hi".to_string()));
+        assert_eq!(message_header(&msg, 3, 40), "[Human 14:02:11] 3/40");
+    }
+
+    #[test]
+    fn test_render_content_blocks() {
+        colored::control::set_override(false);
+        let content = MessageContent::Blocks(vec![
+            ContentBlock::Thinking {
+                thinking: "Check the tests first".to_string(),
+            },
+            ContentBlock::Text {
+                text: "Running the tests".to_string(),
+            },
+            ContentBlock::ToolUse {
+                id: "t1".to_string(),
+                name: "Bash".to_string(),
+                input: serde_json::json!({"command": "cargo test"}),
+            },
+            ContentBlock::ToolResult {
+                tool_use_id: "t1".to_string(),
+                content: "x".repeat(MAX_RESULT_CHARS + 10),
+                is_error: true,
+            },
+        ]);
+
+        let lines = render_content(&content, false);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "Running the tests");
+        assert_eq!(lines[1], "[tool: Bash]");
+        assert_eq!(
+            lines[2],
+            format!("[error] {}...", "x".repeat(MAX_RESULT_CHARS))
+        );
+
+        let lines = render_content(&content, true);
+        assert_eq!(lines[0], "[thinking] Check the tests first");
+    }
+}
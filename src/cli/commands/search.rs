@@ -1,6 +1,7 @@
 //! Search command - search session content.
 //!
-//! Provides full-text search across session messages using SQLite FTS5.
+//! Provides full-text search across session messages using SQLite FTS5, and
+//! regular expression search over message text with `--regex`.
 //! Supports filtering by repository, date, tool, project, branch, and message role.
 //! Also searches session metadata (working directory, branch, tool name).
 
@@ -11,10 +12,12 @@ use serde::Serialize;
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::cli::format::{format_relative_time, CsvWriter, JsonLinesWriter, PathRedactor};
 use crate::cli::OutputFormat;
+use crate::config::Config;
 use crate::storage::db::Database;
 use crate::storage::models::{
-    ContextMessage, MatchWithContext, MessageRole, SearchOptions, SearchResult,
+    ContextMessage, MatchWithContext, Message, MessageRole, SearchOptions, SearchResult,
     SearchResultWithContext,
 };
 
@@ -24,12 +27,17 @@ use crate::storage::models::{
     lore search \"auth\"                        Search for 'auth' in messages\n    \
     lore search \"bug fix\" --limit 20          Show up to 20 results\n    \
     lore search api --since 7d                Last 7 days only\n    \
+    lore search api --since 14d --until 7d    The week before last\n    \
     lore search error --role assistant        Only AI responses\n    \
     lore search test --repo /path             Filter by repository\n    \
     lore search auth --tool claude-code       Filter by AI tool\n    \
     lore search api --project myapp           Filter by project name\n    \
     lore search fix --branch feat/auth        Filter by git branch\n    \
-    lore search bug --context 2               Show 2 messages of context")]
+    lore search bug --context 2               Show 2 messages of context\n    \
+    lore search auth --format jsonl           One JSON object per matching session\n    \
+    lore search auth --format csv             One CSV row per matching message\n    \
+    lore search --regex 'TODO\\(\\w+\\)'        Messages matching a regex\n    \
+    lore search --regex 'v\\d+\\.\\d+' --prefilter release  Regex over FTS matches")]
 pub struct Args {
     /// Text to search for in session messages and metadata
     #[arg(value_name = "QUERY")]
@@ -41,6 +49,26 @@ pub struct Args {
     )]
     pub query: String,
 
+    /// Treat QUERY as a regular expression
+    #[arg(long)]
+    #[arg(
+        long_help = "Match QUERY as a regular expression against the full text of\n\
+        each message instead of using full-text search. Matching is case\n\
+        sensitive unless the pattern starts with (?i). Session metadata is\n\
+        not searched. Without --prefilter only the most recent messages are\n\
+        scanned, so add a word every match contains to search them all."
+    )]
+    pub regex: bool,
+
+    /// Full-text terms that narrow the messages a --regex search scans
+    #[arg(long, value_name = "TERMS", requires = "regex")]
+    #[arg(
+        long_help = "Only apply the --regex pattern to messages matching these\n\
+        full-text search terms. Use a word every match must contain to\n\
+        search all sessions quickly instead of only recent messages."
+    )]
+    pub prefilter: Option<String>,
+
     /// Maximum number of results to return
     #[arg(short, long, default_value = "10", value_name = "N")]
     pub limit: usize,
@@ -61,18 +89,20 @@ pub struct Args {
         - gemini: Gemini sessions")]
     pub tool: Option<String>,
 
-    /// Filter by date - sessions after this date (e.g., 7d, 2w, 1m, or 2024-01-01)
+    /// Filter by date - sessions after this date (e.g., 24h, 7d, 2w, 1m, or 2024-01-01)
     #[arg(long, value_name = "DATE")]
     #[arg(long_help = "Only search sessions from after this date. Accepts:\n\
-        - Relative: 7d (days), 2w (weeks), 1m (months)\n\
-        - Absolute: 2024-01-15 (ISO date format)")]
+        - Relative: 24h (hours), 7d (days), 2w (weeks), 1m (months)\n\
+        - Absolute: 2024-01-15 (ISO date format)\n\
+        - Timestamp: 2024-01-15T09:30:00Z (RFC3339)")]
     pub since: Option<String>,
 
-    /// Filter by date - sessions before this date (e.g., 7d, 2w, 1m, or 2024-12-31)
+    /// Filter by date - sessions before this date (e.g., 24h, 7d, 2w, 1m, or 2024-12-31)
     #[arg(long, value_name = "DATE")]
-    #[arg(long_help = "Only search sessions from before this date. Accepts:\n\
-        - Relative: 7d (days), 2w (weeks), 1m (months)\n\
-        - Absolute: 2024-12-31 (ISO date format)")]
+    #[arg(
+        long_help = "Only search sessions from before this date. Accepts the\n\
+        same formats as --since. Must not be earlier than --since."
+    )]
     pub until: Option<String>,
 
     /// Filter by project/directory name (partial match)
@@ -89,12 +119,13 @@ pub struct Args {
         feat/authentication, feat/api, etc.")]
     pub branch: Option<String>,
 
-    /// Filter by message role (user, assistant, system)
+    /// Filter by message role (user, assistant, system, tool)
     #[arg(long, value_name = "ROLE")]
     #[arg(long_help = "Only search messages from a specific role:\n\
         - user: human messages\n\
         - assistant: AI responses\n\
-        - system: system prompts")]
+        - system: system prompts\n\
+        - tool: tool output reported back to the AI")]
     pub role: Option<String>,
 
     /// Number of context messages to show before and after matches
@@ -105,8 +136,22 @@ pub struct Args {
     )]
     pub context: usize,
 
-    /// Output format: text (default), json
+    /// Show home directory paths as ~
+    #[arg(long)]
+    #[arg(long_help = "Replace home directory prefixes such as /home/alice or\n\
+        /Users/alice with ~ in paths and matched content. Filters still\n\
+        match the real paths. Set redact_paths in the config to make\n\
+        this the default.")]
+    pub redact_paths: bool,
+
+    /// Output format: text (default), json, jsonl, csv
     #[arg(short, long, value_enum, default_value = "text")]
+    #[arg(
+        long_help = "Output format. 'json' prints one document with all results;\n\
+        'jsonl' prints one JSON object per matching session, each on its\n\
+        own line, as the results are produced. 'csv' prints a header row\n\
+        and one row per matching message."
+    )]
     pub format: OutputFormat,
 }
 
@@ -118,15 +163,56 @@ struct SearchOutputWithContext {
     sessions: Vec<SearchResultWithContext>,
 }
 
+/// Number of recent messages a `--regex` search scans without a prefilter.
+const REGEX_SCAN_LIMIT: usize = 10_000;
+
+/// Header row of `--format csv` output.
+const SEARCH_CSV_COLUMNS: [&str; 8] = [
+    "session_id",
+    "message_id",
+    "role",
+    "timestamp",
+    "tool",
+    "working_directory",
+    "git_branch",
+    "snippet",
+];
+
+/// Returns a search result's fields in [`SEARCH_CSV_COLUMNS`] order.
+fn search_csv_row(result: &SearchResult) -> [String; 8] {
+    [
+        result.session_id.to_string(),
+        result.message_id.to_string(),
+        result.role.to_string(),
+        result.timestamp.to_rfc3339(),
+        result.tool.clone(),
+        result.working_directory.clone(),
+        result.git_branch.clone().unwrap_or_default(),
+        result.snippet.clone(),
+    ]
+}
+
 /// Parses a date filter string into a DateTime.
 ///
 /// Supports:
-/// - Relative formats: "7d" (7 days), "2w" (2 weeks), "1m" (1 month)
+/// - Relative formats: "24h" (24 hours), "7d" (7 days), "2w" (2 weeks), "1m" (1 month)
 /// - Absolute format: "2024-01-15" (ISO date)
+/// - RFC3339 timestamps: "2024-01-15T09:30:00Z"
 fn parse_date(date_str: &str) -> Result<chrono::DateTime<Utc>> {
+    if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(date_str.trim()) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+
     let date_str = date_str.trim().to_lowercase();
 
-    // Try relative format first (e.g., "7d", "2w", "1m")
+    // Try relative format first (e.g., "24h", "7d", "2w", "1m")
+    if date_str.ends_with('h') {
+        let hours: i64 = date_str[..date_str.len() - 1]
+            .parse()
+            .context("Invalid number of hours")?;
+        return Ok(Utc::now() - Duration::hours(hours));
+    }
+
     if date_str.ends_with('d') {
         let days: i64 = date_str[..date_str.len() - 1]
             .parse()
@@ -151,7 +237,9 @@ fn parse_date(date_str: &str) -> Result<chrono::DateTime<Utc>> {
 
     // Try absolute format (YYYY-MM-DD)
     let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-        .context("Invalid date format. Use YYYY-MM-DD or relative format like 7d, 2w, 1m")?;
+        .context(
+            "Invalid date format. Use YYYY-MM-DD, an RFC3339 timestamp, or relative format like 24h, 7d, 2w, 1m",
+        )?;
 
     // Midnight is always a valid time, so this should never fail
     let datetime = date
@@ -161,28 +249,26 @@ fn parse_date(date_str: &str) -> Result<chrono::DateTime<Utc>> {
     Ok(datetime.and_utc())
 }
 
-/// Formats a relative time string for display (e.g., "2 hours ago").
-fn format_relative_time(dt: &chrono::DateTime<Utc>) -> String {
-    let now = Utc::now();
-    let duration = now.signed_duration_since(*dt);
-
-    if duration.num_minutes() < 1 {
-        "just now".to_string()
-    } else if duration.num_minutes() < 60 {
-        let mins = duration.num_minutes();
-        format!("{} minute{} ago", mins, if mins == 1 { "" } else { "s" })
-    } else if duration.num_hours() < 24 {
-        let hours = duration.num_hours();
-        format!("{} hour{} ago", hours, if hours == 1 { "" } else { "s" })
-    } else if duration.num_days() < 7 {
-        let days = duration.num_days();
-        format!("{} day{} ago", days, if days == 1 { "" } else { "s" })
-    } else if duration.num_weeks() < 4 {
-        let weeks = duration.num_weeks();
-        format!("{} week{} ago", weeks, if weeks == 1 { "" } else { "s" })
-    } else {
-        dt.format("%Y-%m-%d").to_string()
+/// Optional lower and upper bounds on session start time.
+type DateRange = (Option<chrono::DateTime<Utc>>, Option<chrono::DateTime<Utc>>);
+
+/// Parses the `--since` and `--until` filters and checks that they form a
+/// valid range.
+fn parse_date_range(since: Option<&str>, until: Option<&str>) -> Result<DateRange> {
+    let since = since.map(parse_date).transpose()?;
+    let until = until.map(parse_date).transpose()?;
+
+    if let (Some(since), Some(until)) = (since, until) {
+        if until < since {
+            anyhow::bail!(
+                "Invalid date range: --until ({}) is earlier than --since ({})",
+                until.format("%Y-%m-%d %H:%M"),
+                since.format("%Y-%m-%d %H:%M")
+            );
+        }
     }
+
+    Ok((since, until))
 }
 
 /// Extracts the project name from a working directory path.
@@ -212,15 +298,28 @@ fn format_role(role: &MessageRole) -> colored::ColoredString {
         MessageRole::User => "user".blue(),
         MessageRole::Assistant => "assistant".green(),
         MessageRole::System => "system".yellow(),
+        MessageRole::Tool => "tool".magenta(),
     }
 }
 
 /// Groups search results by session and adds context messages.
+///
+/// Context messages are passed through `redactor`, when given, before they
+/// are truncated; the results themselves are expected to be redacted already.
 fn group_results_with_context(
     db: &Database,
     results: Vec<SearchResult>,
     context_count: usize,
+    redactor: Option<&PathRedactor>,
 ) -> Result<Vec<SearchResultWithContext>> {
+    let context_text = |message: &Message| {
+        let text = message.content.text();
+        match redactor {
+            Some(redactor) => truncate_content(&redactor.redact(&text), 200),
+            None => truncate_content(&text, 200),
+        }
+    };
+
     // Group results by session
     let mut session_groups: HashMap<Uuid, Vec<SearchResult>> = HashMap::new();
     for result in results {
@@ -252,8 +351,8 @@ fn group_results_with_context(
                 .into_iter()
                 .map(|m| ContextMessage {
                     id: m.id,
+                    content: context_text(&m),
                     role: m.role,
-                    content: truncate_content(&m.content.text(), 200),
                     index: m.index,
                     is_match: false,
                 })
@@ -264,8 +363,8 @@ fn group_results_with_context(
                 .into_iter()
                 .map(|m| ContextMessage {
                     id: m.id,
+                    content: context_text(&m),
                     role: m.role,
-                    content: truncate_content(&m.content.text(), 200),
                     index: m.index,
                     is_match: false,
                 })
@@ -341,7 +440,7 @@ fn display_results_with_context(
         );
 
         // Session metadata line
-        let relative_time = format_relative_time(&session.session_started_at);
+        let relative_time = format_relative_time(&session.session_started_at, Utc::now());
         let branch_info = session
             .git_branch
             .as_ref()
@@ -382,12 +481,14 @@ fn display_results_with_context(
             let role_str = format_role(&match_ctx.message.role);
             // Clean up FTS5 highlight markers
             let content = match_ctx.message.content.replace("**", "");
+            let message_prefix = &match_ctx.message.id.to_string()[..8];
             println!(
-                "  [{}] {}    {} {}",
+                "  [{}] {}    {} {} {}",
                 role_str,
                 content.white().bold(),
                 "<-".yellow(),
-                "match".yellow()
+                "match".yellow(),
+                format!("(message {message_prefix})").dimmed()
             );
 
             // After context (dimmed)
@@ -421,29 +522,41 @@ pub fn run(args: Args) -> Result<()> {
 
     // Check if search index needs rebuilding
     if db.search_index_needs_rebuild()? {
-        println!(
-            "{}",
-            "Building search index for existing messages...".yellow()
+        // Keep stdout clean for line-oriented consumers
+        let status = |line: String| {
+            if matches!(args.format, OutputFormat::Jsonl | OutputFormat::Csv) {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        };
+        status(
+            "Building search index for existing messages..."
+                .yellow()
+                .to_string(),
         );
         let count = db.rebuild_search_index()?;
-        println!("Indexed {count} messages.\n");
+        status(format!("Indexed {count} messages.\n"));
     }
 
     // Parse date filters
-    let since = args.since.as_ref().map(|s| parse_date(s)).transpose()?;
-    let until = args.until.as_ref().map(|s| parse_date(s)).transpose()?;
+    let (since, until) = parse_date_range(args.since.as_deref(), args.until.as_deref())?;
 
     // Validate role filter
     if let Some(ref role) = args.role {
         let role_lower = role.to_lowercase();
-        if role_lower != "user" && role_lower != "assistant" && role_lower != "system" {
-            anyhow::bail!("Invalid role '{role}'. Use 'user', 'assistant', or 'system'.");
+        if !["user", "assistant", "system", "tool"].contains(&role_lower.as_str()) {
+            anyhow::bail!("Invalid role '{role}'. Use 'user', 'assistant', 'system', or 'tool'.");
         }
     }
 
-    // Build search options
+    // Build search options; a regex search uses the prefilter as its query
     let options = SearchOptions {
-        query: args.query.clone(),
+        query: if args.regex {
+            args.prefilter.clone().unwrap_or_default()
+        } else {
+            args.query.clone()
+        },
         limit: args.limit,
         tool: args.tool.clone(),
         since,
@@ -456,13 +569,38 @@ pub fn run(args: Args) -> Result<()> {
     };
 
     // Execute the search
-    let results = db.search_with_options(&options)?;
+    let mut results = if args.regex {
+        let regex = regex::Regex::new(&args.query)
+            .with_context(|| format!("Invalid regular expression '{}'", args.query))?;
+        let search = db.search_regex(&options, &regex, REGEX_SCAN_LIMIT)?;
+        if search.truncated {
+            eprintln!(
+                "{}",
+                format!(
+                    "Warning: only the {REGEX_SCAN_LIMIT} most recent messages were scanned. \
+                     Add --prefilter with a word every match contains to search them all."
+                )
+                .yellow()
+            );
+        }
+        search.results
+    } else {
+        db.search_with_options(&options)?
+    };
     let total_matches = results.len();
+    let redactor = PathRedactor::from_settings(args.redact_paths, &Config::load()?);
+    if let Some(redactor) = &redactor {
+        for result in &mut results {
+            redactor.redact_in_place(&mut result.working_directory);
+            redactor.redact_in_place(&mut result.snippet);
+        }
+    }
 
     match args.format {
         OutputFormat::Json => {
             // Group results and add context for JSON output
-            let grouped = group_results_with_context(&db, results, args.context)?;
+            let grouped =
+                group_results_with_context(&db, results, args.context, redactor.as_ref())?;
             let output = SearchOutputWithContext {
                 query: args.query,
                 total_matches,
@@ -471,6 +609,26 @@ pub fn run(args: Args) -> Result<()> {
             let json = serde_json::to_string_pretty(&output)?;
             println!("{json}");
         }
+        OutputFormat::Jsonl => {
+            let grouped =
+                group_results_with_context(&db, results, args.context, redactor.as_ref())?;
+            let mut writer = JsonLinesWriter::new(std::io::stdout().lock());
+            for session in &grouped {
+                if !writer.write(session)? {
+                    break;
+                }
+            }
+        }
+        OutputFormat::Csv => {
+            let mut writer = CsvWriter::new(std::io::stdout().lock());
+            if writer.write_row(&SEARCH_CSV_COLUMNS)? {
+                for result in &results {
+                    if !writer.write_row(&search_csv_row(result))? {
+                        break;
+                    }
+                }
+            }
+        }
         OutputFormat::Text | OutputFormat::Markdown => {
             if results.is_empty() {
                 println!(
@@ -497,19 +655,24 @@ pub fn run(args: Args) -> Result<()> {
             }
 
             // Group results and add context
-            let grouped = group_results_with_context(&db, results, args.context)?;
+            let grouped =
+                group_results_with_context(&db, results, args.context, redactor.as_ref())?;
             display_results_with_context(&args.query, &grouped, total_matches);
 
-            // Display summary
-            if total_matches >= args.limit {
-                println!(
-                    "{}",
-                    format!(
-                        "Showing first {} results. Use --limit to see more.",
-                        args.limit
-                    )
-                    .dimmed()
-                );
+            // Only a full page can have more matches, so only then is
+            // the extra count query worth running. Regex matches cannot be
+            // counted without scanning every candidate.
+            if total_matches >= args.limit && !args.regex {
+                let count = db.search_count(&options)?;
+                if count > total_matches {
+                    println!(
+                        "{}",
+                        format!(
+                            "Showing {total_matches} of {count} matches. Use --limit to see more."
+                        )
+                        .dimmed()
+                    );
+                }
             }
 
             // Show tip for viewing full session
@@ -561,30 +724,32 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_date_invalid() {
-        assert!(parse_date("invalid").is_err());
-        assert!(parse_date("abc123").is_err());
+    fn test_parse_date_hours() {
+        let result = parse_date("24h").expect("Should parse 24h");
+        let expected = Utc::now() - Duration::hours(24);
+        assert!((result - expected).num_seconds().abs() < 2);
     }
 
     #[test]
-    fn test_format_relative_time_minutes() {
-        let dt = Utc::now() - Duration::minutes(5);
-        let result = format_relative_time(&dt);
-        assert!(result.contains("minute"));
+    fn test_parse_date_rfc3339() {
+        let result = parse_date("2024-01-15T09:30:00+02:00").expect("Should parse RFC3339");
+        assert_eq!(result.to_rfc3339(), "2024-01-15T07:30:00+00:00");
     }
 
     #[test]
-    fn test_format_relative_time_hours() {
-        let dt = Utc::now() - Duration::hours(3);
-        let result = format_relative_time(&dt);
-        assert!(result.contains("hour"));
+    fn test_parse_date_range_rejects_inverted_range() {
+        let (since, until) = parse_date_range(Some("14d"), Some("7d")).expect("valid range");
+        assert!(since.unwrap() < until.unwrap());
+
+        assert!(parse_date_range(Some("7d"), Some("14d")).is_err());
+        assert!(parse_date_range(Some("2024-02-01"), Some("2024-01-01")).is_err());
+        assert!(parse_date_range(None, Some("2024-01-01")).is_ok());
     }
 
     #[test]
-    fn test_format_relative_time_days() {
-        let dt = Utc::now() - Duration::days(2);
-        let result = format_relative_time(&dt);
-        assert!(result.contains("day"));
+    fn test_parse_date_invalid() {
+        assert!(parse_date("invalid").is_err());
+        assert!(parse_date("abc123").is_err());
     }
 
     #[test]
@@ -1,16 +1,26 @@
 //! Sessions command - list and filter sessions.
 //!
 //! Displays a list of imported sessions with filtering options.
-//! Sessions can be filtered by working directory and output in
-//! text, JSON, or markdown format.
+//! Sessions can be filtered by working directory, tag, branch, tool, and model,
+//! and output in text, JSON, newline-delimited JSON, CSV, or markdown format.
+//! Sessions captured on another machine show that machine's label.
 
 use std::collections::HashSet;
 
 use anyhow::Result;
 use colored::Colorize;
 
+use crate::cli::format::{CsvWriter, JsonLinesWriter, PathRedactor};
+use crate::cli::table::{Cell, Column, Table, Truncate};
 use crate::cli::OutputFormat;
-use crate::storage::Database;
+use crate::config::Config;
+use crate::storage::{get_machine_display_name, Database, Session};
+
+/// Widest the branch column gets before branch names are shortened.
+const BRANCH_WIDTH: usize = 24;
+
+/// Widest the directory column gets before paths are shortened in the middle.
+const DIRECTORY_WIDTH: usize = 48;
 
 /// Arguments for the sessions command.
 #[derive(clap::Args)]
@@ -20,7 +30,13 @@ use crate::storage::Database;
     lore sessions --repo .         Filter to current directory\n    \
     lore sessions --repo /path     Filter to specific path\n    \
     lore sessions --tag bug-fix    Filter to sessions with 'bug-fix' tag\n    \
-    lore sessions --format json    Output as JSON")]
+    lore sessions --branch feat/x  Filter to sessions that worked on feat/x\n    \
+    lore sessions --tool aider     Filter to sessions from Aider\n    \
+    lore sessions --model sonnet   Filter to sessions using a Sonnet model\n    \
+    lore sessions --full           Show full branch names and paths\n    \
+    lore sessions --format json    Output as JSON\n    \
+    lore sessions --format jsonl   Output one JSON object per line\n    \
+    lore sessions --format csv     Output comma-separated rows")]
 pub struct Args {
     /// Filter to sessions in this directory (prefix match)
     #[arg(short, long, value_name = "PATH")]
@@ -34,11 +50,52 @@ pub struct Args {
     #[arg(short, long, value_name = "LABEL")]
     pub tag: Option<String>,
 
+    /// Filter to sessions that worked on this branch
+    #[arg(short, long, value_name = "NAME")]
+    #[arg(
+        long_help = "Only list sessions recorded on this git branch, matched\n\
+        exactly. A session that switched branches partway through is\n\
+        listed under every branch its messages ran on."
+    )]
+    pub branch: Option<String>,
+
+    /// Filter to sessions from this tool
+    #[arg(long, value_name = "NAME")]
+    #[arg(long_help = "Only list sessions captured from this tool, such as\n\
+        'claude-code', 'aider', or 'codex'. Must match the tool name\n\
+        exactly as shown in JSON output.")]
+    pub tool: Option<String>,
+
+    /// Filter to sessions whose model contains this text
+    #[arg(long, value_name = "SUBSTR")]
+    #[arg(
+        long_help = "Only list sessions whose model name contains this text,\n\
+        ignoring case. For example, 'sonnet' matches any Sonnet model."
+    )]
+    pub model: Option<String>,
+
     /// Maximum number of sessions to display
     #[arg(short, long, default_value = "20", value_name = "N")]
     pub limit: usize,
 
-    /// Output format: text (default), json
+    /// Show full branch names and directories without truncation
+    #[arg(long)]
+    #[arg(
+        long_help = "Show branch histories and working directories in full. By\n\
+        default long branches are cut at the end and long paths in the\n\
+        middle so the table fits a typical terminal."
+    )]
+    pub full: bool,
+
+    /// Show home directory paths as ~
+    #[arg(long)]
+    #[arg(long_help = "Replace home directory prefixes such as /home/alice or\n\
+        /Users/alice with ~ in the listed paths. Filters still match the\n\
+        real paths. Set redact_paths in the config to make this the\n\
+        default.")]
+    pub redact_paths: bool,
+
+    /// Output format: text (default), json, jsonl, csv
     #[arg(short, long, value_enum, default_value = "text")]
     pub format: OutputFormat,
 }
@@ -46,9 +103,10 @@ pub struct Args {
 /// Executes the sessions command.
 ///
 /// Lists sessions from the database, optionally filtered by
-/// working directory prefix or tag.
+/// working directory prefix, tag, branch, tool, and model.
 pub fn run(args: Args) -> Result<()> {
     let db = Database::open_default()?;
+    let config = Config::load().unwrap_or_default();
 
     // Resolve repo path if provided
     let working_dir = args.repo.map(|r| {
@@ -61,19 +119,59 @@ pub fn run(args: Args) -> Result<()> {
         }
     });
 
-    // Get sessions - either filtered by tag or by normal query
-    let sessions = if let Some(ref tag_label) = args.tag {
+    // Get sessions - filtered by tag, by branch, or by the normal query
+    let mut sessions = if let Some(ref tag_label) = args.tag {
         let mut tagged_sessions = db.list_sessions_with_tag(tag_label, args.limit)?;
-        // If repo filter is also specified, filter further
+        // If other filters are also specified, filter further
+        if let Some(ref branch) = args.branch {
+            let on_branch: HashSet<_> = db
+                .sessions_for_branch(branch, None)?
+                .into_iter()
+                .map(|s| s.id)
+                .collect();
+            tagged_sessions.retain(|s| on_branch.contains(&s.id));
+        }
         if let Some(ref wd) = working_dir {
-            tagged_sessions.retain(|s| s.working_directory.starts_with(wd));
+            // Match any directory the session moved through, like the plain query
+            tagged_sessions.retain(|s| {
+                s.working_directory.starts_with(wd)
+                    || db
+                        .get_session_directories(&s.id)
+                        .is_ok_and(|dirs| dirs.iter().any(|d| d.starts_with(wd)))
+            });
         }
+        retain_tool_and_model(
+            &mut tagged_sessions,
+            args.tool.as_deref(),
+            args.model.as_deref(),
+        );
         tagged_sessions
+    } else if let Some(ref branch) = args.branch {
+        let mut branch_sessions = db.sessions_for_branch(branch, working_dir.as_deref())?;
+        retain_tool_and_model(
+            &mut branch_sessions,
+            args.tool.as_deref(),
+            args.model.as_deref(),
+        );
+        branch_sessions.truncate(args.limit);
+        branch_sessions
     } else {
-        db.list_sessions(args.limit, working_dir.as_deref())?
+        db.list_sessions_filtered(
+            args.limit,
+            working_dir.as_deref(),
+            args.tool.as_deref(),
+            args.model.as_deref(),
+        )?
     };
 
-    if sessions.is_empty() {
+    if let Some(redactor) = PathRedactor::from_settings(args.redact_paths, &config) {
+        for session in &mut sessions {
+            redactor.redact_session(session);
+        }
+    }
+
+    // An empty stream is the natural "no rows" for line-oriented consumers
+    if sessions.is_empty() && !matches!(args.format, OutputFormat::Jsonl | OutputFormat::Csv) {
         println!("{}", "No sessions found.".dimmed());
         println!();
         println!("Run 'lore import' to import sessions from Claude Code.");
@@ -85,21 +183,46 @@ pub fn run(args: Args) -> Result<()> {
             let json = serde_json::to_string_pretty(&sessions)?;
             println!("{json}");
         }
+        OutputFormat::Jsonl => {
+            let mut writer = JsonLinesWriter::new(std::io::stdout().lock());
+            for session in &sessions {
+                if !writer.write(session)? {
+                    break;
+                }
+            }
+        }
+        OutputFormat::Csv => {
+            let mut writer = CsvWriter::new(std::io::stdout().lock());
+            if writer.write_row(&SESSION_CSV_COLUMNS)? {
+                for session in &sessions {
+                    if !writer.write_row(&session_csv_row(session))? {
+                        break;
+                    }
+                }
+            }
+        }
         OutputFormat::Text | OutputFormat::Markdown => {
-            // Column widths for consistent alignment
-            const ID_WIDTH: usize = 12;
-            const STARTED_WIDTH: usize = 16;
-            const MESSAGES_WIDTH: usize = 8;
-            const BRANCH_WIDTH: usize = 24;
-
-            println!(
-                "{}",
-                format!(
-                    "{:<ID_WIDTH$}  {:<STARTED_WIDTH$}  {:>MESSAGES_WIDTH$}  {:<BRANCH_WIDTH$}  {}",
-                    "ID", "STARTED", "MESSAGES", "BRANCH", "DIRECTORY"
-                )
-                .bold()
-            );
+            // The machine column only appears when some session was pulled
+            // from another machine
+            let current_machine = config.machine_id;
+            let show_machine = sessions
+                .iter()
+                .any(|s| is_from_other_machine(s, current_machine.as_deref()));
+
+            let mut columns = vec![
+                Column::new("ID"),
+                Column::new("STARTED"),
+                Column::new("MESSAGES").right(),
+                Column::new("BRANCH").max_width(BRANCH_WIDTH, Truncate::End),
+                Column::new("DIRECTORY").max_width(DIRECTORY_WIDTH, Truncate::Middle),
+            ];
+            if show_machine {
+                columns.push(Column::new("MACHINE"));
+            }
+            let mut table = Table::new(columns);
+            if args.full {
+                table = table.no_truncation();
+            }
 
             let session_ids: Vec<uuid::Uuid> = sessions.iter().map(|s| s.id).collect();
             let sessions_with_summaries: HashSet<uuid::Uuid> = db
@@ -108,36 +231,93 @@ pub fn run(args: Args) -> Result<()> {
 
             for session in &sessions {
                 let id_short = &session.id.to_string()[..8];
-                let has_summary = sessions_with_summaries.contains(&session.id);
-                let id_display = if has_summary {
-                    format!("{} {}", id_short.cyan(), "[S]".green())
-                } else {
-                    format!("{}", id_short.cyan())
-                };
+                let mut id_cell = Cell::styled(id_short, |s| s.cyan());
+                if sessions_with_summaries.contains(&session.id) {
+                    id_cell = id_cell.then(" [S]", |s| s.green());
+                }
                 let started = session.started_at.format("%Y-%m-%d %H:%M").to_string();
                 let branch_history = db.get_session_branch_history(session.id)?;
-                let branch_display = format_branch_history(&branch_history, BRANCH_WIDTH);
-                let dir = session
-                    .working_directory
-                    .split('/')
-                    .next_back()
-                    .unwrap_or(&session.working_directory);
-
-                println!(
-                    "{:<ID_WIDTH$}  {:<STARTED_WIDTH$}  {:>MESSAGES_WIDTH$}  {:<BRANCH_WIDTH$}  {}",
-                    id_display,
-                    started.dimmed(),
-                    session.message_count,
-                    branch_display.yellow(),
-                    dir
-                );
+                let branch_width = if args.full { usize::MAX } else { BRANCH_WIDTH };
+                let branch_display = format_branch_history(&branch_history, branch_width);
+
+                let mut row = vec![
+                    id_cell,
+                    Cell::styled(started, |s| s.dimmed()),
+                    Cell::plain(session.message_count.to_string()),
+                    Cell::styled(branch_display, |s| s.yellow()),
+                    Cell::plain(session.working_directory.clone()),
+                ];
+                if show_machine {
+                    row.push(match &session.machine_id {
+                        Some(id) if is_from_other_machine(session, current_machine.as_deref()) => {
+                            Cell::styled(get_machine_display_name(&db, id), |s| s.magenta())
+                        }
+                        _ => Cell::styled("-", |s| s.dimmed()),
+                    });
+                }
+                table.add_row(row);
             }
+            table.print();
         }
     }
 
     Ok(())
 }
 
+/// Keeps the sessions matching the `--tool` and `--model` filters, for
+/// lists not fetched through [`Database::list_sessions_filtered`].
+fn retain_tool_and_model(sessions: &mut Vec<Session>, tool: Option<&str>, model: Option<&str>) {
+    if let Some(tool) = tool {
+        sessions.retain(|s| s.tool == tool);
+    }
+    if let Some(model) = model {
+        let model = model.to_lowercase();
+        sessions.retain(|s| {
+            s.model
+                .as_deref()
+                .is_some_and(|m| m.to_lowercase().contains(&model))
+        });
+    }
+}
+
+/// Returns true if the session was captured on a machine other than
+/// `current_machine`.
+///
+/// Sessions without a machine ID predate machine tracking and count as
+/// local.
+fn is_from_other_machine(session: &Session, current_machine: Option<&str>) -> bool {
+    session
+        .machine_id
+        .as_deref()
+        .is_some_and(|id| Some(id) != current_machine)
+}
+
+/// Header row of `--format csv` output.
+const SESSION_CSV_COLUMNS: [&str; 8] = [
+    "session_id",
+    "tool",
+    "started_at",
+    "ended_at",
+    "message_count",
+    "working_directory",
+    "git_branch",
+    "model",
+];
+
+/// Returns a session's fields in [`SESSION_CSV_COLUMNS`] order.
+fn session_csv_row(session: &Session) -> [String; 8] {
+    [
+        session.id.to_string(),
+        session.tool.clone(),
+        session.started_at.to_rfc3339(),
+        session.ended_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        session.message_count.to_string(),
+        session.working_directory.clone(),
+        session.git_branch.clone().unwrap_or_default(),
+        session.model.clone().unwrap_or_default(),
+    ]
+}
+
 /// Truncates a string to fit within a maximum width.
 ///
 /// If the string is longer than `max_width`, it is truncated and "..." is appended.
@@ -185,6 +365,36 @@ fn format_branch_history(branches: &[String], max_width: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_is_from_other_machine() {
+        let mut session = Session {
+            id: Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at: Utc::now(),
+            ended_at: None,
+            model: None,
+            working_directory: "/home/user/project".to_string(),
+            git_branch: None,
+            source_path: None,
+            message_count: 0,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        };
+        assert!(!is_from_other_machine(&session, Some("this-machine")));
+
+        session.machine_id = Some("this-machine".to_string());
+        assert!(!is_from_other_machine(&session, Some("this-machine")));
+
+        session.machine_id = Some("other-machine".to_string());
+        assert!(is_from_other_machine(&session, Some("this-machine")));
+        assert!(is_from_other_machine(&session, None));
+    }
 
     // Tests for truncate_to_width
 
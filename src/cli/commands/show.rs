@@ -2,22 +2,38 @@
 //!
 //! Displays the full conversation history for a session, or lists
 //! sessions linked to a specific commit. Supports truncation of
-//! long messages and optional display of AI thinking blocks.
+//! long messages, optional display of AI thinking blocks, and several
+//! styles of message timestamps. The session header counts messages by
+//! role and tool calls by tool, unless `--no-summary` is given. Tool
+//! calls show shell commands, file paths, and file changes directly, and
+//! other tool input as indented JSON.
 //!
 //! Supports multiple output formats:
 //! - Text: colored terminal output (default)
 //! - JSON: machine-readable structured output
 //! - Markdown: formatted for documentation or issue tracking
 
+use std::collections::HashMap;
 use std::env;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, Utc};
 use colored::Colorize;
 use serde::Serialize;
 
+use regex::Regex;
+
+use crate::cli::format::{
+    format_cost, format_relative_time, format_token_usage, JsonLinesWriter, PathRedactor,
+};
 use crate::cli::OutputFormat;
+use crate::config::Config;
 use crate::git;
-use crate::storage::{ContentBlock, Database, Message, MessageContent, MessageRole, Session, Tag};
+use crate::storage::db::StoredMessage;
+use crate::storage::{
+    ContentBlock, Database, LinkFactors, Message, MessageContent, MessageRole, MessageThread,
+    Session, Tag,
+};
 
 /// Safely truncates a string to at most `max_bytes` bytes at a character boundary.
 ///
@@ -34,21 +50,34 @@ fn truncate_str(s: &str, max_bytes: usize) -> &str {
     &s[..end]
 }
 
+/// Number of messages `lore show` displays without `--all`, `--head`, or `--tail`.
+const DEFAULT_TAIL: usize = 200;
+
 /// Arguments for the show command.
 #[derive(clap::Args)]
 #[command(after_help = "EXAMPLES:\n    \
     lore show abc123                View session by ID prefix\n    \
     lore show abc123 --full         Show full message content\n    \
     lore show abc123 --thinking     Include AI thinking blocks\n    \
+    lore show abc123 --tree         Show edits and regenerations as branches\n    \
+    lore show abc123 --grep 'panic|unwrap'  Only messages matching a regex\n    \
+    lore show abc123 --role user    Only the human's messages\n    \
+    lore show abc123 --head 20      Show only the first 20 messages\n    \
+    lore show abc123 --all          Show every message of a long session\n    \
+    lore show abc123 --timestamps elapsed  Show time between messages\n    \
+    lore show abc123 --raw          Dump messages as stored in the database\n    \
     lore show --commit HEAD         List sessions linked to HEAD\n    \
     lore show --commit abc123       List sessions linked to commit\n    \
+    lore show --commit HEAD -f markdown  Linked transcripts as one document\n    \
+    lore show --message 1f2e3d4c    Show one message with context\n    \
     lore show abc123 -f markdown    Output as markdown")]
 pub struct Args {
     /// Session ID prefix or commit SHA to look up
     #[arg(value_name = "ID")]
     #[arg(long_help = "The target to look up. By default this is treated as a\n\
         session ID prefix. Use --commit to interpret it as a git\n\
-        commit reference (SHA, HEAD, branch name, etc).")]
+        commit reference (SHA, HEAD, branch name, etc), or --message\n\
+        to interpret it as a message ID prefix.")]
     pub target: String,
 
     /// Treat the target as a commit and show linked sessions
@@ -56,10 +85,26 @@ pub struct Args {
     #[arg(
         long_help = "Interpret the target as a git commit reference instead of\n\
         a session ID. Shows all sessions linked to that commit.\n\
-        Accepts SHAs, HEAD, branch names, or any git ref."
+        Accepts SHAs, HEAD, branch names, or any git ref. With\n\
+        --format markdown, prints every linked session's transcript\n\
+        in one document, most confident link first."
     )]
     pub commit: bool,
 
+    /// Treat the target as a message ID and show it with context
+    #[arg(long, conflicts_with = "commit")]
+    #[arg(
+        long_help = "Interpret the target as a message ID instead of a session ID.\n\
+        Accepts a full UUID or a unique prefix, such as the 8-character\n\
+        short ID shown in search results. Prints that message along\n\
+        with the surrounding messages from its session."
+    )]
+    pub message: bool,
+
+    /// Number of messages to show before and after with --message
+    #[arg(short = 'C', long, default_value = "2", value_name = "N")]
+    pub context: usize,
+
     /// Show full message content without truncation
     #[arg(long)]
     #[arg(
@@ -68,6 +113,63 @@ pub struct Args {
     )]
     pub full: bool,
 
+    /// Show the conversation as a tree of branches
+    #[arg(long, conflicts_with_all = ["commit", "message", "grep", "role"])]
+    #[arg(
+        long_help = "Thread messages by their parent message instead of listing\n\
+        them in order. Edited prompts and regenerated responses appear\n\
+        as indented branches under the message they reply to, with each\n\
+        branch point marked. Sessions without branches display as usual.\n\
+        Subagent sessions spawned by the session are shown indented\n\
+        after the conversation. With --format json, 'tree' and\n\
+        'subagents' fields are added to the output."
+    )]
+    pub tree: bool,
+
+    /// Show only messages whose content matches a regular expression
+    #[arg(long, value_name = "REGEX", conflicts_with_all = ["commit", "message"])]
+    #[arg(
+        long_help = "Show only the messages whose displayed content matches a\n\
+        regular expression, with the matches highlighted. The whole\n\
+        session is searched in memory, so full regex syntax works,\n\
+        such as 'panic|unwrap' or '(?i)todo'. --head and --tail then\n\
+        apply to the matching messages."
+    )]
+    pub grep: Option<String>,
+
+    /// Show only messages from one role (user, assistant, system, tool)
+    #[arg(long, value_name = "ROLE", conflicts_with_all = ["commit", "message"])]
+    #[arg(long_help = "Show only messages from a specific role:\n\
+        - user: human messages\n\
+        - assistant: AI responses\n\
+        - system: system prompts\n\
+        - tool: tool output reported back to the AI\n\
+        Combines with --grep to match within that role.")]
+    pub role: Option<String>,
+
+    /// Show only the first N messages of the session
+    #[arg(long, value_name = "N", conflicts_with_all = ["tail", "all"])]
+    pub head: Option<usize>,
+
+    /// Show only the last N messages of the session [default: 200]
+    #[arg(long, value_name = "N", conflicts_with = "all")]
+    #[arg(
+        long_help = "Show only the last N messages of the session. Without --head,\n\
+        --tail, or --all, the last 200 messages are shown and a note\n\
+        reports how many were left out; JSON output sets \"truncated\"\n\
+        and gives \"total_messages\" instead."
+    )]
+    pub tail: Option<usize>,
+
+    /// Show every message, however long the session
+    #[arg(long)]
+    #[arg(
+        long_help = "Load and show every message of the session instead of the\n\
+        last 200. --tree always loads every message, since branches\n\
+        can start anywhere in the conversation."
+    )]
+    pub all: bool,
+
     /// Include AI thinking blocks in output
     #[arg(long)]
     #[arg(
@@ -77,19 +179,265 @@ pub struct Args {
     )]
     pub thinking: bool,
 
+    /// How to show message times: none (default), absolute, relative, elapsed
+    #[arg(long, value_enum, default_value = "none", value_name = "STYLE")]
+    #[arg(
+        long_help = "How to show the time of each message in text and markdown\n\
+        output. 'none' keeps the plain UTC time of day. 'absolute' shows\n\
+        the full date and time in the local time zone, 'relative' shows\n\
+        how long ago the message was sent (\"3 days ago\"), and 'elapsed'\n\
+        shows the time since the previous message (\"+2m\")."
+    )]
+    pub timestamps: TimestampStyle,
+
+    /// Leave the message and tool call counts out of the header
+    #[arg(long)]
+    #[arg(
+        long_help = "Leave out the header line counting messages by role and\n\
+        tool calls by tool, and the content_summary object in JSON\n\
+        output. Counting reads every message of the session, which\n\
+        is slower for very long sessions."
+    )]
+    pub no_summary: bool,
+
+    /// Show home directory paths as ~
+    #[arg(long)]
+    #[arg(long_help = "Replace home directory prefixes such as /home/alice or\n\
+        /Users/alice with ~ in paths and message content, so output\n\
+        can be shared without revealing usernames. Stored sessions are\n\
+        not changed. Set redact_paths in the config to make this the\n\
+        default.")]
+    pub redact_paths: bool,
+
+    /// Print each message's stored content JSON verbatim
+    #[arg(
+        long,
+        conflicts_with_all = ["commit", "message", "tree", "grep", "role", "head", "tail"]
+    )]
+    #[arg(
+        long_help = "Print every message of the session as it is stored in the\n\
+        database: its metadata columns and the serialized content JSON,\n\
+        unparsed and unrendered. Useful for reporting import problems.\n\
+        With --format json the rows are printed as one JSON document,\n\
+        and with --format jsonl one row per line, the content kept as\n\
+        a JSON string. --redact-paths still applies."
+    )]
+    pub raw: bool,
+
     /// Output format: text (default), json, or markdown
     #[arg(short, long, value_enum, default_value = "text")]
     pub format: OutputFormat,
 }
 
+/// How message times are shown, chosen with `--timestamps`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimestampStyle {
+    /// UTC time of day, as shown before `--timestamps` existed (default).
+    #[default]
+    None,
+    /// Full local date and time.
+    Absolute,
+    /// Time since the message was sent, such as "3 days ago".
+    Relative,
+    /// Time since the previous message, such as "+2m".
+    Elapsed,
+}
+
+/// Formats message times in one `--timestamps` style.
+///
+/// Remembers the last message labeled, so `elapsed` labels measure from
+/// the message printed before. Cloned at branch points of a conversation
+/// tree, so each branch measures from the message it replies to.
+#[derive(Debug, Clone)]
+struct MessageClock {
+    style: TimestampStyle,
+    previous: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+}
+
+impl MessageClock {
+    /// Creates a clock whose first elapsed label measures from `start`.
+    fn new(style: TimestampStyle, start: Option<DateTime<Utc>>) -> Self {
+        Self {
+            style,
+            previous: start,
+            now: Utc::now(),
+        }
+    }
+
+    /// Returns the time label for a message sent at `at`.
+    fn label(&mut self, at: DateTime<Utc>) -> String {
+        let label = match self.style {
+            TimestampStyle::None => at.format("%H:%M:%S").to_string(),
+            TimestampStyle::Absolute => at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            TimestampStyle::Relative => format_relative_time(&at, self.now),
+            TimestampStyle::Elapsed => {
+                let since = self.previous.unwrap_or(at);
+                format_elapsed(at.signed_duration_since(since).num_seconds())
+            }
+        };
+        self.previous = Some(at);
+        label
+    }
+}
+
+/// Formats seconds between messages in their largest unit, such as "+2m".
+///
+/// Negative gaps, from out-of-order timestamps, are shown as "+0s".
+fn format_elapsed(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    if seconds < 60 {
+        format!("+{seconds}s")
+    } else if seconds < 3600 {
+        format!("+{}m", seconds / 60)
+    } else if seconds < 86400 {
+        format!("+{}h", seconds / 3600)
+    } else {
+        format!("+{}d", seconds / 86400)
+    }
+}
+
 /// JSON output structure for a session with its messages.
 #[derive(Serialize)]
 struct SessionOutput {
     session: Session,
     messages: Vec<Message>,
+    /// Number of messages in the session, including any not shown.
+    total_messages: usize,
+    /// Number of messages skipped before the first one shown. With
+    /// --grep or --role, counted among the matching messages.
+    message_offset: usize,
+    /// Number of messages matching --grep and --role, when given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matching_messages: Option<usize>,
+    /// True when `messages` leaves some of the session (or of the matching
+    /// messages) out, as the default window of the last 200 does.
+    truncated: bool,
     links: Vec<LinkInfo>,
     tags: Vec<String>,
     summary: Option<String>,
+    /// Estimated cost in US dollars of the session's token usage, if the
+    /// tool reported usage and the model has a known rate.
+    estimated_cost_usd: Option<f64>,
+    /// Conversation tree, present with --tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tree: Option<MessageThread>,
+    /// Subagent sessions spawned by the session, present with --tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subagents: Option<Vec<SubagentOutput>>,
+    /// Message and tool call counts, left out with --no-summary.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_summary: Option<ContentSummary>,
+}
+
+/// A subagent session and its messages, for JSON output.
+#[derive(Serialize)]
+struct SubagentOutput {
+    session: Session,
+    messages: Vec<Message>,
+}
+
+/// Counts of a session's messages by role and tool calls by tool.
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct ContentSummary {
+    messages: usize,
+    user: usize,
+    assistant: usize,
+    system: usize,
+    tool: usize,
+    tool_calls: usize,
+    /// The most used tools, most calls first.
+    top_tools: Vec<ToolCount>,
+}
+
+/// Number of calls to one tool.
+#[derive(Debug, PartialEq, Serialize)]
+struct ToolCount {
+    name: String,
+    calls: usize,
+}
+
+/// How many tools `ContentSummary::top_tools` lists.
+const TOP_TOOLS: usize = 5;
+
+impl ContentSummary {
+    /// Counts messages by role and `ToolUse` blocks by tool name.
+    fn from_messages(messages: &[Message]) -> Self {
+        let mut summary = Self {
+            messages: messages.len(),
+            ..Default::default()
+        };
+        let mut tools: HashMap<&str, usize> = HashMap::new();
+        for message in messages {
+            match message.role {
+                MessageRole::User => summary.user += 1,
+                MessageRole::Assistant => summary.assistant += 1,
+                MessageRole::System => summary.system += 1,
+                MessageRole::Tool => summary.tool += 1,
+            }
+            if let MessageContent::Blocks(blocks) = &message.content {
+                for block in blocks {
+                    if let ContentBlock::ToolUse { name, .. } = block {
+                        summary.tool_calls += 1;
+                        *tools.entry(name.as_str()).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        let mut tools: Vec<(&str, usize)> = tools.into_iter().collect();
+        tools.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        summary.top_tools = tools
+            .into_iter()
+            .take(TOP_TOOLS)
+            .map(|(name, calls)| ToolCount {
+                name: name.to_string(),
+                calls,
+            })
+            .collect();
+        summary
+    }
+
+    /// Describes the counts on one line, such as
+    /// "48 messages (20 user, 28 assistant), 63 tool calls (Bash 40, Read 23)".
+    fn describe(&self) -> String {
+        let roles: Vec<String> = [
+            (self.user, "user"),
+            (self.assistant, "assistant"),
+            (self.system, "system"),
+            (self.tool, "tool"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, role)| format!("{count} {role}"))
+        .collect();
+
+        let mut text = format!(
+            "{} message{}",
+            self.messages,
+            if self.messages == 1 { "" } else { "s" }
+        );
+        if !roles.is_empty() {
+            text.push_str(&format!(" ({})", roles.join(", ")));
+        }
+        text.push_str(&format!(
+            ", {} tool call{}",
+            self.tool_calls,
+            if self.tool_calls == 1 { "" } else { "s" }
+        ));
+        if !self.top_tools.is_empty() {
+            let tools: Vec<String> = self
+                .top_tools
+                .iter()
+                .map(|t| format!("{} {}", t.name, t.calls))
+                .collect();
+            text.push_str(&format!(" ({})", tools.join(", ")));
+        }
+        text
+    }
 }
 
 /// Simplified link info for JSON output.
@@ -99,34 +447,169 @@ struct LinkInfo {
     confidence: Option<f64>,
 }
 
+/// Which messages of a session to load.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MessageWindow {
+    All,
+    Head(usize),
+    Tail(usize),
+}
+
+impl MessageWindow {
+    fn from_args(args: &Args) -> Self {
+        if args.all || args.tree {
+            MessageWindow::All
+        } else if let Some(n) = args.head {
+            MessageWindow::Head(n)
+        } else {
+            MessageWindow::Tail(args.tail.unwrap_or(DEFAULT_TAIL))
+        }
+    }
+
+    /// Returns the offset and limit of this window in a session of `total` messages.
+    fn bounds(self, total: usize) -> (usize, usize) {
+        match self {
+            MessageWindow::All => (0, total),
+            MessageWindow::Head(n) => (0, n.min(total)),
+            MessageWindow::Tail(n) => (total.saturating_sub(n), n.min(total)),
+        }
+    }
+}
+
+/// Message filter built from `--grep` and `--role`.
+struct MessageFilter {
+    pattern: Option<Regex>,
+    role: Option<String>,
+}
+
+impl MessageFilter {
+    /// Builds the filter, or returns `None` when neither flag is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is not a valid regex or the role is
+    /// not a known message role.
+    fn from_args(args: &Args) -> Result<Option<Self>> {
+        if args.grep.is_none() && args.role.is_none() {
+            return Ok(None);
+        }
+        let pattern = match &args.grep {
+            Some(p) => {
+                Some(Regex::new(p).with_context(|| format!("Invalid --grep pattern '{p}'"))?)
+            }
+            None => None,
+        };
+        let role = match &args.role {
+            Some(role) => {
+                let role_lower = role.to_lowercase();
+                if !["user", "assistant", "system", "tool"].contains(&role_lower.as_str()) {
+                    bail!("Invalid role '{role}'. Use 'user', 'assistant', 'system', or 'tool'.");
+                }
+                Some(role_lower)
+            }
+            None => None,
+        };
+        Ok(Some(Self { pattern, role }))
+    }
+
+    /// Returns true if the message has the role and its displayed content,
+    /// untruncated, matches the pattern.
+    fn matches(&self, message: &Message, show_thinking: bool) -> bool {
+        if let Some(role) = &self.role {
+            if message.role.to_string() != *role {
+                return false;
+            }
+        }
+        match &self.pattern {
+            Some(pattern) => render_message_content_text(&message.content, true, show_thinking)
+                .iter()
+                .any(|block| pattern.is_match(block)),
+            None => true,
+        }
+    }
+}
+
+/// Keeps the messages that match `filter`, in order.
+fn filter_messages(
+    messages: Vec<Message>,
+    filter: &MessageFilter,
+    show_thinking: bool,
+) -> Vec<Message> {
+    messages
+        .into_iter()
+        .filter(|m| filter.matches(m, show_thinking))
+        .collect()
+}
+
+/// Highlights every match of `pattern` in `text`.
+fn highlight_matches(text: &str, pattern: &Regex) -> String {
+    pattern
+        .replace_all(text, |caps: &regex::Captures| {
+            caps[0].black().on_yellow().to_string()
+        })
+        .into_owned()
+}
+
 /// Executes the show command.
 ///
-/// Either displays a session's conversation or lists sessions
-/// linked to a commit, depending on the --commit flag.
+/// Displays a session's conversation, lists sessions linked to a
+/// commit (--commit), or shows a single message in context (--message).
 pub fn run(args: Args) -> Result<()> {
     let db = Database::open_default()?;
+    let filter = MessageFilter::from_args(&args)?;
+    let redactor = PathRedactor::from_settings(args.redact_paths, &Config::load()?);
+    let redactor = redactor.as_ref();
 
     if args.commit {
         // Show sessions linked to a commit
-        show_commit_sessions(&db, &args.target, args.format)?;
+        show_commit_sessions(
+            &db,
+            &args.target,
+            args.full,
+            args.thinking,
+            args.timestamps,
+            redactor,
+            args.format,
+        )?;
+    } else if args.raw {
+        // Dump the stored rows of a session
+        show_raw(&db, &args.target, redactor, args.format)?;
+    } else if args.message {
+        // Show a single message with surrounding context
+        show_message(
+            &db,
+            &args.target,
+            args.context,
+            args.full,
+            args.thinking,
+            args.timestamps,
+            redactor,
+            args.format,
+        )?;
     } else {
         // Show a specific session
-        show_session(&db, &args.target, args.full, args.thinking, args.format)?;
+        show_session(
+            &db,
+            &args.target,
+            MessageWindow::from_args(&args),
+            filter.as_ref(),
+            args.full,
+            args.thinking,
+            args.tree,
+            !args.no_summary,
+            args.timestamps,
+            redactor,
+            args.format,
+        )?;
     }
 
     Ok(())
 }
 
-fn show_session(
-    db: &Database,
-    id_prefix: &str,
-    full: bool,
-    show_thinking: bool,
-    format: OutputFormat,
-) -> Result<()> {
-    // Find session by ID prefix using efficient database lookup
-    let session = match db.find_session_by_id_prefix(id_prefix)? {
-        Some(s) => s,
+/// Finds a session by ID prefix, with a hint when none matches.
+fn find_session(db: &Database, id_prefix: &str) -> Result<Session> {
+    match db.find_session_by_id_prefix(id_prefix)? {
+        Some(s) => Ok(s),
         None => {
             // Check if database is empty for a better error message
             if db.session_count()? == 0 {
@@ -141,18 +624,190 @@ fn show_session(
                 );
             }
         }
-    };
+    }
+}
+
+/// JSON output structure for `lore show --raw`.
+#[derive(Serialize)]
+struct RawSessionOutput {
+    session: Session,
+    messages: Vec<StoredMessage>,
+}
+
+/// Prints a session's message rows as stored, for `--raw`.
+fn show_raw(
+    db: &Database,
+    id_prefix: &str,
+    redactor: Option<&PathRedactor>,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut session = find_session(db, id_prefix)?;
+    let mut messages = db.get_stored_messages(&session.id)?;
+    if let Some(redactor) = redactor {
+        redactor.redact_session(&mut session);
+        for message in &mut messages {
+            redactor.redact_in_place(&mut message.content);
+            if let Some(cwd) = &mut message.cwd {
+                redactor.redact_in_place(cwd);
+            }
+        }
+    }
+
+    match format {
+        OutputFormat::Json => {
+            let output = RawSessionOutput { session, messages };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Jsonl => {
+            let mut writer = JsonLinesWriter::new(std::io::stdout().lock());
+            for message in &messages {
+                if !writer.write(message)? {
+                    break;
+                }
+            }
+        }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("show")),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            println!(
+                "{} {} ({} {})",
+                "Session".bold(),
+                session.id.to_string().cyan(),
+                session.tool,
+                session.tool_version.as_deref().unwrap_or("unknown version")
+            );
+            if let Some(source) = &session.source_path {
+                println!("{} {source}", "Source:".dimmed());
+            }
+            for message in &messages {
+                println!();
+                for line in raw_message_header(message) {
+                    println!("{}", line.dimmed());
+                }
+                println!("{}", message.content);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the metadata lines printed above a stored message's content.
+fn raw_message_header(message: &StoredMessage) -> Vec<String> {
+    let mut lines = vec![format!(
+        "--- [{}] {} {} {}",
+        message.index, message.id, message.role, message.timestamp
+    )];
+    let optional = [
+        ("parent_id", &message.parent_id),
+        ("model", &message.model),
+        ("git_branch", &message.git_branch),
+        ("cwd", &message.cwd),
+    ];
+    for (name, value) in optional {
+        if let Some(value) = value {
+            lines.push(format!("    {name}: {value}"));
+        }
+    }
+    if message.compressed {
+        lines.push("    stored compressed; shown decompressed".to_string());
+    }
+    lines
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_session(
+    db: &Database,
+    id_prefix: &str,
+    window: MessageWindow,
+    filter: Option<&MessageFilter>,
+    full: bool,
+    show_thinking: bool,
+    tree: bool,
+    with_summary: bool,
+    timestamps: TimestampStyle,
+    redactor: Option<&PathRedactor>,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut session = find_session(db, id_prefix)?;
 
-    let messages = db.get_messages(&session.id)?;
+    let total = db.session_message_count(&session.id)?;
+    // Every message is read when filtering, summarizing, or showing them all
+    let all_messages = if filter.is_some() || with_summary || window == MessageWindow::All {
+        Some(db.get_messages(&session.id)?)
+    } else {
+        None
+    };
+    let content_summary = all_messages
+        .as_deref()
+        .filter(|_| with_summary)
+        .map(ContentSummary::from_messages);
+    let mut matching = None;
+    let (offset, mut messages): (usize, Vec<Message>) = match (filter, all_messages) {
+        (Some(filter), Some(all)) => {
+            let matched = filter_messages(all, filter, show_thinking);
+            let (offset, limit) = window.bounds(matched.len());
+            matching = Some(matched.len());
+            (
+                offset,
+                matched.into_iter().skip(offset).take(limit).collect(),
+            )
+        }
+        (_, Some(all)) => {
+            let (offset, limit) = window.bounds(all.len());
+            (offset, all.into_iter().skip(offset).take(limit).collect())
+        }
+        (_, None) => {
+            let (offset, limit) = window.bounds(total);
+            (offset, db.get_messages_paged(&session.id, offset, limit)?)
+        }
+    };
+    let shown_of = matching.unwrap_or(total);
+    let omitted = shown_of - messages.len();
+    // Elapsed labels start at the session's start only when its first
+    // message is shown; otherwise the first shown message reads "+0s"
+    let clock_start = (offset == 0 && matching.is_none()).then_some(session.started_at);
+    let highlight = filter.and_then(|f| f.pattern.as_ref());
     let links = db.get_links_by_session(&session.id)?;
     let tags = db.get_tags(&session.id)?;
-    let summary = db.get_summary(&session.id)?;
+    let mut summary = db.get_summary(&session.id)?;
+    let cost = estimated_cost(&session, &Config::load()?);
+    let mut thread = if tree {
+        db.get_message_tree(&session.id)?
+    } else {
+        None
+    };
+    let mut subagents: Vec<(Session, Vec<Message>)> = Vec::new();
+    if tree {
+        for child in db.get_child_sessions(&session.id)? {
+            let child_messages = db.get_messages(&child.id)?;
+            subagents.push((child, child_messages));
+        }
+    }
+
+    if let Some(redactor) = redactor {
+        redactor.redact_session(&mut session);
+        redactor.redact_messages(&mut messages);
+        if let Some(summary) = &mut summary {
+            redactor.redact_in_place(&mut summary.content);
+        }
+        if let Some(thread) = &mut thread {
+            redactor.redact_thread(thread);
+        }
+        for (child, child_messages) in &mut subagents {
+            redactor.redact_session(child);
+            redactor.redact_messages(child_messages);
+        }
+    }
 
     match format {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Jsonl => {
             let output = SessionOutput {
                 session: session.clone(),
                 messages,
+                total_messages: total,
+                message_offset: offset,
+                matching_messages: matching,
+                truncated: omitted > 0,
                 links: links
                     .iter()
                     .map(|l| LinkInfo {
@@ -162,8 +817,17 @@ fn show_session(
                     .collect(),
                 tags: tags.iter().map(|t| t.label.clone()).collect(),
                 summary: summary.map(|s| s.content),
+                estimated_cost_usd: cost,
+                tree: thread,
+                subagents: tree.then(|| {
+                    subagents
+                        .into_iter()
+                        .map(|(session, messages)| SubagentOutput { session, messages })
+                        .collect()
+                }),
+                content_summary,
             };
-            let json = serde_json::to_string_pretty(&output)?;
+            let json = format.json_string(&output)?;
             println!("{json}");
         }
         OutputFormat::Markdown => {
@@ -173,20 +837,210 @@ fn show_session(
                 &links,
                 &tags,
                 &summary,
+                content_summary.as_ref(),
+                cost,
                 full,
                 show_thinking,
+                MessageClock::new(timestamps, clock_start),
             );
+            if let Some(count) = matching {
+                println!("_{}_", matching_note(count, total));
+            }
+            if omitted > 0 {
+                println!("_{}_", window_note(offset, messages.len(), shown_of));
+            }
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("show")),
         OutputFormat::Text => {
             print_session_text(
                 &session,
                 &messages,
+                thread.as_ref(),
+                highlight,
                 &links,
                 &tags,
                 &summary,
+                content_summary.as_ref(),
+                cost,
                 full,
                 show_thinking,
+                MessageClock::new(timestamps, clock_start),
+            );
+            if let Some(count) = matching {
+                println!("{}", matching_note(count, total).dimmed());
+            }
+            if omitted > 0 {
+                println!("{}", window_note(offset, messages.len(), shown_of).dimmed());
+            }
+            print_subagents_text(&session, &subagents, full, show_thinking, timestamps);
+        }
+    }
+
+    Ok(())
+}
+
+/// Estimates the cost of a session's token usage from the configured rates.
+fn estimated_cost(session: &Session, config: &Config) -> Option<f64> {
+    if session.input_tokens.is_none() && session.output_tokens.is_none() {
+        return None;
+    }
+    config.estimate_cost(
+        session.model.as_deref(),
+        session.input_tokens.unwrap_or(0),
+        session.output_tokens.unwrap_or(0),
+    )
+}
+
+/// Formats a session's token usage and estimated cost for display, or
+/// "n/a" when the tool reported no usage.
+fn usage_text(session: &Session, cost: Option<f64>) -> String {
+    let usage = format_token_usage(session.input_tokens, session.output_tokens);
+    if session.input_tokens.is_none() && session.output_tokens.is_none() {
+        return usage;
+    }
+    match cost {
+        Some(_) => format!("{usage} ({})", format_cost(cost)),
+        None => format!("{usage} (cost n/a)"),
+    }
+}
+
+/// Describes which messages were shown when a session is displayed in part.
+fn window_note(offset: usize, shown: usize, total: usize) -> String {
+    let range = if shown == 0 {
+        "no messages".to_string()
+    } else {
+        format!("messages {}-{}", offset + 1, offset + shown)
+    };
+    format!("Showing {range} of {total}. Use --all to show every message.")
+}
+
+/// Reports how many messages matched --grep or --role.
+fn matching_note(matching: usize, total: usize) -> String {
+    format!(
+        "{matching} of {total} message{} matched.",
+        if total == 1 { "" } else { "s" }
+    )
+}
+
+/// JSON output structure for a single message with its context.
+#[derive(Serialize)]
+struct MessageOutput {
+    session: Session,
+    message: Message,
+    before: Vec<Message>,
+    after: Vec<Message>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_message(
+    db: &Database,
+    id_prefix: &str,
+    context: usize,
+    full: bool,
+    show_thinking: bool,
+    timestamps: TimestampStyle,
+    redactor: Option<&PathRedactor>,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut message = match db.find_message_by_id_prefix(id_prefix)? {
+        Some(m) => m,
+        None => anyhow::bail!(
+            "No message found matching '{id_prefix}'. \
+             Run 'lore search' to find message IDs."
+        ),
+    };
+    let mut session = db
+        .get_session(&message.session_id)?
+        .ok_or_else(|| anyhow::anyhow!("Session {} not found", message.session_id))?;
+    let (mut before, mut after) = db.get_context_messages(&session.id, message.index, context)?;
+    if let Some(redactor) = redactor {
+        redactor.redact_session(&mut session);
+        redactor.redact_message(&mut message);
+        redactor.redact_messages(&mut before);
+        redactor.redact_messages(&mut after);
+    }
+    let mut clock = MessageClock::new(timestamps, None);
+
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            let output = MessageOutput {
+                session,
+                message,
+                before,
+                after,
+            };
+            println!("{}", format.json_string(&output)?);
+        }
+        OutputFormat::Markdown => {
+            println!("# Message {}", message.short_id());
+            println!();
+            println!(
+                "Message {} of session `{}` ({}, `{}`)",
+                message.index + 1,
+                &session.id.to_string()[..8],
+                session.tool,
+                session.working_directory
+            );
+            println!();
+            for msg in before.iter().chain(std::iter::once(&message)).chain(&after) {
+                let role = match msg.role {
+                    MessageRole::User => "Human",
+                    MessageRole::Assistant => "Assistant",
+                    MessageRole::System => "System",
+                    MessageRole::Tool => "Tool",
+                };
+                let time = clock.label(msg.timestamp);
+                let marker = if msg.id == message.id { " (match)" } else { "" };
+                println!("### [{role}] {time} `{}`{marker}", msg.short_id());
+                println!();
+                print_message_content_markdown(&msg.content, full, show_thinking);
+                println!();
+            }
+        }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("show")),
+        OutputFormat::Text => {
+            println!(
+                "{} {} {} {}",
+                "Message".bold(),
+                message.short_id().cyan(),
+                "in session".dimmed(),
+                session.id.to_string()[..8].cyan()
+            );
+            println!();
+            println!("  {}  {}", "Tool:".dimmed(), session.tool);
+            println!("  {}  {}", "Directory:".dimmed(), session.working_directory);
+            println!(
+                "  {}  {} of {}",
+                "Position:".dimmed(),
+                message.index + 1,
+                session.message_count
             );
+            println!();
+
+            for msg in before.iter().chain(std::iter::once(&message)).chain(&after) {
+                let role_str = match msg.role {
+                    MessageRole::User => "Human".green().bold(),
+                    MessageRole::Assistant => "Assistant".blue().bold(),
+                    MessageRole::System => "System".yellow().bold(),
+                    MessageRole::Tool => "Tool".magenta().bold(),
+                };
+                let time = clock.label(msg.timestamp);
+                let marker = if msg.id == message.id {
+                    format!("    {}", "<- match".yellow())
+                } else {
+                    String::new()
+                };
+                println!(
+                    "[{} {} {}]{}",
+                    role_str,
+                    time.dimmed(),
+                    msg.short_id().dimmed(),
+                    marker
+                );
+
+                print_message_content_text(&msg.content, full, show_thinking);
+                println!();
+            }
         }
     }
 
@@ -194,14 +1048,24 @@ fn show_session(
 }
 
 /// Prints session details in text format with colors.
+///
+/// When `thread` is given and the conversation branches, messages are
+/// rendered as a tree; otherwise they are listed in order. Matches of
+/// `highlight` in message content are highlighted.
+#[allow(clippy::too_many_arguments)]
 fn print_session_text(
     session: &Session,
     messages: &[Message],
+    thread: Option<&MessageThread>,
+    highlight: Option<&Regex>,
     links: &[crate::storage::SessionLink],
     tags: &[Tag],
     summary: &Option<crate::storage::Summary>,
+    content_summary: Option<&ContentSummary>,
+    cost: Option<f64>,
     full: bool,
     show_thinking: bool,
+    mut clock: MessageClock,
 ) {
     // Header
     println!("{} {}", "Session".bold(), session.id.to_string().cyan());
@@ -228,10 +1092,17 @@ fn print_session_text(
         );
     }
     println!("  {}  {}", "Messages:".dimmed(), session.message_count);
+    if let Some(content) = content_summary {
+        println!("  {}  {}", "Contents:".dimmed(), content.describe());
+    }
+    println!("  {}  {}", "Tokens:".dimmed(), usage_text(session, cost));
     println!("  {}  {}", "Directory:".dimmed(), session.working_directory);
     if let Some(ref branch) = session.git_branch {
         println!("  {}  {}", "Branch:".dimmed(), branch);
     }
+    if let Some(parent) = session.parent_session_id {
+        println!("  {}  {}", "Parent:".dimmed(), parent.to_string().cyan());
+    }
 
     // Display tags
     if !tags.is_empty() {
@@ -263,23 +1134,135 @@ fn print_session_text(
     println!("{}", "Conversation:".bold());
     println!();
 
+    if let Some(thread) = thread.filter(|t| t.has_branches()) {
+        print_thread_text(thread, 0, full, show_thinking, &clock);
+        return;
+    }
+
     for msg in messages {
         let role_str = match msg.role {
             MessageRole::User => "Human".green().bold(),
             MessageRole::Assistant => "Assistant".blue().bold(),
             MessageRole::System => "System".yellow().bold(),
+            MessageRole::Tool => "Tool".magenta().bold(),
         };
 
-        let time = msg.timestamp.format("%H:%M:%S").to_string();
+        let time = clock.label(msg.timestamp);
         println!("[{} {}]", role_str, time.dimmed());
 
-        print_message_content_text(&msg.content, full, show_thinking);
+        match highlight {
+            Some(pattern) => {
+                for block in render_message_content_text(&msg.content, full, show_thinking) {
+                    println!("{}", highlight_matches(&block, pattern));
+                }
+            }
+            None => print_message_content_text(&msg.content, full, show_thinking),
+        }
+        println!();
+    }
+}
+
+/// Prints the subagent sessions spawned by `parent`, each indented under a
+/// heading, with message times measured from the parent's start.
+fn print_subagents_text(
+    parent: &Session,
+    subagents: &[(Session, Vec<Message>)],
+    full: bool,
+    show_thinking: bool,
+    timestamps: TimestampStyle,
+) {
+    if subagents.is_empty() {
+        return;
+    }
+    println!("{}", "Subagents:".bold());
+    println!();
+
+    let clock = MessageClock::new(timestamps, Some(parent.started_at));
+    for (session, messages) in subagents {
+        println!(
+            "{}",
+            format!(
+                "-- Subagent {} ({} messages) --",
+                &session.id.to_string()[..8],
+                messages.len()
+            )
+            .yellow()
+        );
+        if let Some(thread) = MessageThread::from_messages(messages.clone()) {
+            print_thread_text(&thread, 1, full, show_thinking, &clock);
+        } else {
+            println!();
+        }
+    }
+}
+
+/// Prints a conversation tree, indenting each branch under its branch point.
+fn print_thread_text(
+    thread: &MessageThread,
+    depth: usize,
+    full: bool,
+    show_thinking: bool,
+    clock: &MessageClock,
+) {
+    let indent = "    ".repeat(depth);
+    let mut clock = clock.clone();
+
+    for msg in &thread.messages {
+        let role_str = match msg.role {
+            MessageRole::User => "Human".green().bold(),
+            MessageRole::Assistant => "Assistant".blue().bold(),
+            MessageRole::System => "System".yellow().bold(),
+            MessageRole::Tool => "Tool".magenta().bold(),
+        };
+        let time = clock.label(msg.timestamp);
+        println!(
+            "{indent}[{} {} {}]",
+            role_str,
+            time.dimmed(),
+            msg.short_id().dimmed()
+        );
+
+        for block in render_message_content_text(&msg.content, full, show_thinking) {
+            for line in block.lines() {
+                println!("{indent}{line}");
+            }
+        }
+        println!();
+    }
+
+    let count = thread.branches.len();
+    if count > 1 {
+        println!(
+            "{indent}{}",
+            format!("* Branch point: {count} alternative replies").yellow()
+        );
         println!();
     }
+    for (i, branch) in thread.branches.iter().enumerate() {
+        if count > 1 {
+            println!(
+                "{indent}{}",
+                format!("-- Branch {} of {count} --", i + 1).yellow()
+            );
+        }
+        print_thread_text(branch, depth + 1, full, show_thinking, &clock);
+    }
 }
 
 /// Prints message content in text format.
 fn print_message_content_text(content: &MessageContent, full: bool, show_thinking: bool) {
+    for line in render_message_content_text(content, full, show_thinking) {
+        println!("{line}");
+    }
+}
+
+/// Renders message content as text, one entry per displayed content block.
+fn render_message_content_text(
+    content: &MessageContent,
+    full: bool,
+    show_thinking: bool,
+) -> Vec<String> {
+    let mut out = Vec::new();
     match content {
         MessageContent::Text(text) => {
             let display = if full || text.len() < 500 {
@@ -287,7 +1270,7 @@ fn print_message_content_text(content: &MessageContent, full: bool, show_thinkin
             } else {
                 format!("{}...", truncate_str(text, 500))
             };
-            println!("{display}");
+            out.push(display);
         }
         MessageContent::Blocks(blocks) => {
             for block in blocks {
@@ -298,19 +1281,15 @@ fn print_message_content_text(content: &MessageContent, full: bool, show_thinkin
                         } else {
                             format!("{}...", truncate_str(text, 500))
                         };
-                        println!("{display}");
+                        out.push(display);
                     }
                     ContentBlock::Thinking { thinking } => {
                         if show_thinking {
-                            println!("{} {}", "<thinking>".dimmed(), thinking.dimmed());
+                            out.push(format!("{} {}", "<thinking>".dimmed(), thinking.dimmed()));
                         }
                     }
                     ContentBlock::ToolUse { name, input, .. } => {
-                        println!(
-                            "{} {}",
-                            format!("[Tool: {name}]").magenta(),
-                            serde_json::to_string(input).unwrap_or_default().dimmed()
-                        );
+                        out.push(render_tool_use_text(name, input, full));
                     }
                     ContentBlock::ToolResult {
                         content, is_error, ..
@@ -326,23 +1305,254 @@ fn print_message_content_text(content: &MessageContent, full: bool, show_thinkin
                         } else {
                             format!("{}...", truncate_str(&color_content, 200))
                         };
-                        println!("{} {}", format!("[{label}]").dimmed(), display);
+                        out.push(format!("{} {}", format!("[{label}]").dimmed(), display));
                     }
                 }
             }
         }
     }
+    out
+}
+
+/// Lines of tool input shown before the rest is cut off, unless `--full`.
+const TOOL_INPUT_PREVIEW_LINES: usize = 20;
+
+/// How a tool call's input is displayed.
+///
+/// Common Claude Code tools get a purpose-built view; everything else, and
+/// calls whose input lacks the expected fields, falls back to indented JSON.
+#[derive(Debug, PartialEq)]
+enum ToolInputView {
+    /// A shell command, from `Bash`.
+    Command {
+        command: String,
+        description: Option<String>,
+    },
+    /// A file read, from `Read`, with the requested line range if any.
+    FileRead { path: String, range: Option<String> },
+    /// A file change, from `Edit` and `Write`, as removed and added lines.
+    FileChange { path: String, lines: Vec<String> },
+    /// Any other input, as indented JSON.
+    Json(String),
+}
+
+impl ToolInputView {
+    fn new(name: &str, input: &serde_json::Value) -> Self {
+        let field = |key: &str| input.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        let view = match name {
+            "Bash" => field("command").map(|command| ToolInputView::Command {
+                command,
+                description: field("description"),
+            }),
+            "Read" => field("file_path").map(|path| ToolInputView::FileRead {
+                path,
+                range: read_range(input),
+            }),
+            "Edit" => match (field("file_path"), field("old_string"), field("new_string")) {
+                (Some(path), Some(old), Some(new)) => Some(ToolInputView::FileChange {
+                    path,
+                    lines: diff_lines('-', &old).chain(diff_lines('+', &new)).collect(),
+                }),
+                _ => None,
+            },
+            "Write" => match (field("file_path"), field("content")) {
+                (Some(path), Some(content)) => Some(ToolInputView::FileChange {
+                    path,
+                    lines: diff_lines('+', &content).collect(),
+                }),
+                _ => None,
+            },
+            _ => None,
+        };
+        view.unwrap_or_else(|| {
+            ToolInputView::Json(serde_json::to_string_pretty(input).unwrap_or_default())
+        })
+    }
+}
+
+/// Describes a `Read` call's `offset` and `limit` as a line range.
+fn read_range(input: &serde_json::Value) -> Option<String> {
+    let offset = input.get("offset").and_then(serde_json::Value::as_u64);
+    let limit = input.get("limit").and_then(serde_json::Value::as_u64);
+    match (offset, limit) {
+        (None, None) => None,
+        (offset, Some(limit)) => {
+            let start = offset.unwrap_or(1);
+            Some(format!("lines {start}-{}", start + limit.saturating_sub(1)))
+        }
+        (Some(offset), None) => Some(format!("from line {offset}")),
+    }
+}
+
+/// Prefixes each line of `text` with `marker`, as in a unified diff.
+fn diff_lines(marker: char, text: &str) -> impl Iterator<Item = String> + '_ {
+    text.lines().map(move |line| format!("{marker}{line}"))
+}
+
+/// Returns the lines to show, at most [`TOOL_INPUT_PREVIEW_LINES`] unless
+/// `full`, and how many were left out.
+fn preview_lines<S: AsRef<str>>(lines: &[S], full: bool) -> (Vec<&str>, usize) {
+    let shown = if full {
+        lines.len()
+    } else {
+        lines.len().min(TOOL_INPUT_PREVIEW_LINES)
+    };
+    let preview = lines[..shown].iter().map(AsRef::as_ref).collect();
+    (preview, lines.len() - shown)
+}
+
+/// Describes lines cut from a preview.
+fn omitted_note(omitted: usize) -> String {
+    format!(
+        "... {omitted} more line{}",
+        if omitted == 1 { "" } else { "s" }
+    )
+}
+
+/// Renders a tool call for the terminal: a header naming the tool and its
+/// target, then the input indented below it.
+fn render_tool_use_text(name: &str, input: &serde_json::Value, full: bool) -> String {
+    let header = format!("[Tool: {name}]").magenta().to_string();
+    let (mut out, body): (String, Vec<String>) = match ToolInputView::new(name, input) {
+        ToolInputView::Command {
+            command,
+            description,
+        } => {
+            let lines: Vec<&str> = command.lines().collect();
+            let (shown, omitted) = preview_lines(&lines, full);
+            let mut body: Vec<String> = shown
+                .iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    let prompt = if i == 0 { "$" } else { " " };
+                    format!("  {} {}", prompt.dimmed(), line.bold())
+                })
+                .collect();
+            if omitted > 0 {
+                body.push(format!("  {}", omitted_note(omitted).dimmed()));
+            }
+            let header = match description {
+                Some(description) => format!("{header} {}", description.dimmed()),
+                None => header,
+            };
+            (header, body)
+        }
+        ToolInputView::FileRead { path, range } => {
+            let mut header = format!("{header} {}", path.cyan().bold());
+            if let Some(range) = range {
+                header = format!("{header} {}", format!("({range})").dimmed());
+            }
+            (header, Vec::new())
+        }
+        ToolInputView::FileChange { path, lines } => {
+            let (shown, omitted) = preview_lines(&lines, full);
+            let mut body: Vec<String> = shown
+                .iter()
+                .map(|line| {
+                    let colored = if line.starts_with('-') {
+                        line.red()
+                    } else {
+                        line.green()
+                    };
+                    format!("  {colored}")
+                })
+                .collect();
+            if omitted > 0 {
+                body.push(format!("  {}", omitted_note(omitted).dimmed()));
+            }
+            (format!("{header} {}", path.cyan().bold()), body)
+        }
+        ToolInputView::Json(json) => {
+            let lines: Vec<&str> = json.lines().collect();
+            let (shown, omitted) = preview_lines(&lines, full);
+            let mut body: Vec<String> = shown
+                .iter()
+                .map(|line| format!("  {}", line.dimmed()))
+                .collect();
+            if omitted > 0 {
+                body.push(format!("  {}", omitted_note(omitted).dimmed()));
+            }
+            (header, body)
+        }
+    };
+    for line in body {
+        out.push('\n');
+        out.push_str(&line);
+    }
+    out
+}
+
+/// Renders a tool call as markdown: a bold header, then the input in a
+/// fenced code block tagged for highlighting.
+fn render_tool_use_markdown(name: &str, input: &serde_json::Value, full: bool) -> String {
+    let (header, language, body) = match ToolInputView::new(name, input) {
+        ToolInputView::Command {
+            command,
+            description,
+        } => {
+            let header = match description {
+                Some(description) => format!("**Tool: {name}** ({description})"),
+                None => format!("**Tool: {name}**"),
+            };
+            let lines: Vec<&str> = command.lines().collect();
+            (header, "bash", code_block_body(&lines, full))
+        }
+        ToolInputView::FileRead { path, range } => {
+            let header = match range {
+                Some(range) => format!("**Tool: {name}** `{path}` ({range})"),
+                None => format!("**Tool: {name}** `{path}`"),
+            };
+            return header;
+        }
+        ToolInputView::FileChange { path, lines } => (
+            format!("**Tool: {name}** `{path}`"),
+            "diff",
+            code_block_body(&lines, full),
+        ),
+        ToolInputView::Json(json) => {
+            let lines: Vec<&str> = json.lines().collect();
+            (
+                format!("**Tool: {name}**"),
+                "json",
+                code_block_body(&lines, full),
+            )
+        }
+    };
+    let fence = code_fence(&body);
+    format!("{header}\n\n{fence}{language}\n{body}\n{fence}")
+}
+
+/// Joins the preview of `lines` for a code block, noting any cut lines.
+fn code_block_body<S: AsRef<str>>(lines: &[S], full: bool) -> String {
+    let (shown, omitted) = preview_lines(lines, full);
+    let mut body = shown.join("\n");
+    if omitted > 0 {
+        body.push('\n');
+        body.push_str(&omitted_note(omitted));
+    }
+    body
+}
+
+/// Returns a backtick fence longer than any backtick run in `body`, so the
+/// block cannot be closed early by its own content.
+fn code_fence(body: &str) -> String {
+    let longest = body.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
 }
 
 /// Prints session details in markdown format.
+#[allow(clippy::too_many_arguments)]
 fn print_session_markdown(
     session: &Session,
     messages: &[Message],
     links: &[crate::storage::SessionLink],
     tags: &[Tag],
     summary: &Option<crate::storage::Summary>,
+    content_summary: Option<&ContentSummary>,
+    cost: Option<f64>,
     full: bool,
     show_thinking: bool,
+    clock: MessageClock,
 ) {
     // Header
     println!("# Session {}", session.id);
@@ -371,6 +1581,10 @@ fn print_session_markdown(
         );
     }
     println!("| Messages | {} |", session.message_count);
+    if let Some(content) = content_summary {
+        println!("| Contents | {} |", content.describe());
+    }
+    println!("| Tokens | {} |", usage_text(session, cost));
     println!("| Directory | `{}` |", session.working_directory);
     if let Some(ref branch) = session.git_branch {
         println!("| Branch | `{branch}` |");
@@ -410,15 +1624,27 @@ fn print_session_markdown(
     println!("## Conversation");
     println!();
 
+    print_conversation_markdown(messages, "###", full, show_thinking, clock);
+}
+
+/// Prints each message under its own `heading`-level markdown heading.
+fn print_conversation_markdown(
+    messages: &[Message],
+    heading: &str,
+    full: bool,
+    show_thinking: bool,
+    mut clock: MessageClock,
+) {
     for msg in messages {
         let role = match msg.role {
             MessageRole::User => "Human",
             MessageRole::Assistant => "Assistant",
             MessageRole::System => "System",
+            MessageRole::Tool => "Tool",
         };
 
-        let time = msg.timestamp.format("%H:%M:%S").to_string();
-        println!("### [{role}] {time}");
+        let time = clock.label(msg.timestamp);
+        println!("{heading} [{role}] {time}");
         println!();
 
         print_message_content_markdown(&msg.content, full, show_thinking);
@@ -460,14 +1686,7 @@ fn print_message_content_markdown(content: &MessageContent, full: bool, show_thi
                         }
                     }
                     ContentBlock::ToolUse { name, input, .. } => {
-                        println!("**Tool: {name}**");
-                        println!();
-                        println!("```json");
-                        println!(
-                            "{}",
-                            serde_json::to_string_pretty(input).unwrap_or_default()
-                        );
-                        println!("```");
+                        println!("{}", render_tool_use_markdown(name, input, full));
                         println!();
                     }
                     ContentBlock::ToolResult {
@@ -524,6 +1743,8 @@ struct CommitSessionsOutput {
     ref_name: Option<String>,
     commit_summary: Option<String>,
     commit_timestamp: Option<String>,
+    commit_author_name: Option<String>,
+    commit_author_email: Option<String>,
     sessions: Vec<CommitSessionInfo>,
 }
 
@@ -534,28 +1755,62 @@ struct CommitSessionInfo {
     started_at: String,
     message_count: i32,
     confidence: Option<f64>,
+    /// Factor scores behind the confidence, for scored auto-links.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    factors: Option<LinkFactors>,
+}
+
+/// Describes why an auto-link was made: the points each factor added to
+/// its confidence.
+fn link_reason(factors: &LinkFactors) -> String {
+    format!(
+        "branch {:.0}%, files {:.0}%, time {:.0}%, recency {:.0}%",
+        factors.branch * 100.0,
+        factors.files * 100.0,
+        factors.time * 100.0,
+        factors.recency * 100.0
+    )
 }
 
-fn show_commit_sessions(db: &Database, commit: &str, format: OutputFormat) -> Result<()> {
+fn show_commit_sessions(
+    db: &Database,
+    commit: &str,
+    full: bool,
+    show_thinking: bool,
+    timestamps: TimestampStyle,
+    redactor: Option<&PathRedactor>,
+    format: OutputFormat,
+) -> Result<()> {
     // Resolve the commit reference (handles HEAD, branch names, etc.)
-    let (resolved_sha, ref_name) = resolve_commit_reference(commit);
+    let (mut resolved_sha, ref_name) = resolve_commit_reference(commit);
+
+    // A short SHA git could not resolve is matched against linked commits,
+    // failing with the candidates if it matches more than one
+    if ref_name.is_none() && resolved_sha.len() < 40 {
+        if let Some(sha) = db.find_linked_commit_by_prefix(&resolved_sha)? {
+            resolved_sha = sha;
+        }
+    }
 
     // Query database with the resolved SHA
     let links = db.get_links_by_commit(&resolved_sha)?;
 
     if links.is_empty() {
         match format {
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::Jsonl => {
                 let output = CommitSessionsOutput {
                     commit_sha: resolved_sha.clone(),
                     ref_name,
                     commit_summary: None,
                     commit_timestamp: None,
+                    commit_author_name: None,
+                    commit_author_email: None,
                     sessions: vec![],
                 };
-                let json = serde_json::to_string_pretty(&output)?;
+                let json = format.json_string(&output)?;
                 println!("{json}");
             }
+            OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("show")),
             OutputFormat::Text | OutputFormat::Markdown => {
                 let display = if let Some(ref name) = ref_name {
                     format!("'{name}' ({})", &resolved_sha[..8.min(resolved_sha.len())])
@@ -598,29 +1853,38 @@ fn show_commit_sessions(db: &Database, commit: &str, format: OutputFormat) -> Re
     // Collect session info
     let mut session_infos = Vec::new();
     for link in &links {
-        if let Some(session) = db.get_session(&link.session_id)? {
-            session_infos.push((session, link.confidence));
+        if let Some(mut session) = db.get_session(&link.session_id)? {
+            if let Some(redactor) = redactor {
+                redactor.redact_session(&mut session);
+            }
+            session_infos.push((session, link.confidence, link.factors));
         }
     }
 
     match format {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Jsonl => {
             let output = CommitSessionsOutput {
                 commit_sha: resolved_sha.clone(),
                 ref_name,
                 commit_summary: commit_info.as_ref().map(|i| i.summary.clone()),
                 commit_timestamp: commit_info.as_ref().map(|i| i.timestamp.to_rfc3339()),
+                commit_author_name: commit_info.as_ref().map(|i| i.author_name.clone()),
+                commit_author_email: commit_info
+                    .as_ref()
+                    .map(|i| i.author_email.clone())
+                    .filter(|email| !email.is_empty()),
                 sessions: session_infos
                     .iter()
-                    .map(|(s, conf)| CommitSessionInfo {
+                    .map(|(s, conf, factors)| CommitSessionInfo {
                         session_id: s.id.to_string(),
                         started_at: s.started_at.to_rfc3339(),
                         message_count: s.message_count,
                         confidence: *conf,
+                        factors: *factors,
                     })
                     .collect(),
             };
-            let json = serde_json::to_string_pretty(&output)?;
+            let json = format.json_string(&output)?;
             println!("{json}");
         }
         OutputFormat::Markdown => {
@@ -628,30 +1892,61 @@ fn show_commit_sessions(db: &Database, commit: &str, format: OutputFormat) -> Re
             println!("# Commit `{short_sha}`");
             println!();
 
+            println!(
+                "Commit `{resolved_sha}` has {} linked {}.",
+                session_infos.len(),
+                if session_infos.len() == 1 {
+                    "session"
+                } else {
+                    "sessions"
+                }
+            );
+            println!();
+
             if let Some(ref info) = commit_info {
                 println!("**Summary:** {}", info.summary);
                 println!();
                 println!("**Date:** {}", info.timestamp.format("%Y-%m-%d %H:%M"));
                 println!();
+                println!("**Author:** {}", info.author());
+                println!();
             }
 
-            println!("## Linked Sessions ({})", session_infos.len());
-            println!();
-            println!("| Session ID | Started | Messages | Confidence |");
-            println!("|------------|---------|----------|------------|");
+            // Most confident links first, sessions without a score last
+            session_infos.sort_by(|a, b| {
+                b.1.unwrap_or(f64::MIN)
+                    .partial_cmp(&a.1.unwrap_or(f64::MIN))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
 
-            for (session, conf) in &session_infos {
-                let id_short = &session.id.to_string()[..8];
-                let started = session.started_at.format("%Y-%m-%d %H:%M").to_string();
+            for (session, conf, factors) in &session_infos {
                 let conf_str = conf
                     .map(|c| format!("{:.0}%", c * 100.0))
                     .unwrap_or_else(|| "-".to_string());
+                println!("## Session {} (confidence: {conf_str})", session.id);
+                println!();
+                if let Some(factors) = factors {
+                    println!("**Why this was linked:** {}", link_reason(factors));
+                    println!();
+                }
                 println!(
-                    "| `{id_short}` | {started} | {} | {conf_str} |",
-                    session.message_count
+                    "{}, started {}, {} messages in `{}`",
+                    session.tool,
+                    session.started_at.format("%Y-%m-%d %H:%M"),
+                    session.message_count,
+                    session.working_directory
                 );
+                println!();
+
+                let mut messages = db.get_messages(&session.id)?;
+                if let Some(redactor) = redactor {
+                    redactor.redact_messages(&mut messages);
+                }
+                let clock = MessageClock::new(timestamps, Some(session.started_at));
+                print_conversation_markdown(&messages, "###", full, show_thinking, clock);
             }
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("show")),
         OutputFormat::Text => {
             let short_sha = &resolved_sha[..8.min(resolved_sha.len())];
             if let Some(ref info) = commit_info {
@@ -667,7 +1962,11 @@ fn show_commit_sessions(db: &Database, commit: &str, format: OutputFormat) -> Re
                     ref_display.dimmed()
                 );
                 println!("  \"{}\"", info.summary);
-                println!("  {}", info.timestamp.format("%Y-%m-%d %H:%M"));
+                println!(
+                    "  {}  {}",
+                    info.timestamp.format("%Y-%m-%d %H:%M"),
+                    info.author().dimmed()
+                );
             } else {
                 let ref_display = ref_name
                     .as_ref()
@@ -687,7 +1986,7 @@ fn show_commit_sessions(db: &Database, commit: &str, format: OutputFormat) -> Re
                 format!("Linked sessions ({}):", session_infos.len()).bold()
             );
 
-            for (session, conf) in &session_infos {
+            for (session, conf, factors) in &session_infos {
                 let id_short = &session.id.to_string()[..8];
                 let started = session.started_at.format("%Y-%m-%d %H:%M").to_string();
 
@@ -701,6 +2000,13 @@ fn show_commit_sessions(db: &Database, commit: &str, format: OutputFormat) -> Re
                 if let Some(c) = conf {
                     println!("    {} {:.0}%", "confidence:".dimmed(), c * 100.0);
                 }
+                if let Some(factors) = factors {
+                    println!(
+                        "    {} {}",
+                        "why this was linked:".dimmed(),
+                        link_reason(factors)
+                    );
+                }
             }
 
             println!();
@@ -713,3 +2019,278 @@ fn show_commit_sessions(db: &Database, commit: &str, format: OutputFormat) -> Re
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_link_reason_lists_each_factor() {
+        let factors = LinkFactors {
+            branch: 0.2,
+            files: 0.3,
+            time: 0.25,
+            recency: 0.0,
+        };
+        assert_eq!(
+            link_reason(&factors),
+            "branch 20%, files 30%, time 25%, recency 0%"
+        );
+    }
+
+    #[test]
+    fn test_tool_input_view_special_cases_common_tools() {
+        let view = |name: &str, input: serde_json::Value| ToolInputView::new(name, &input);
+
+        assert_eq!(
+            view(
+                "Bash",
+                serde_json::json!({"command": "cargo test", "description": "Run tests"})
+            ),
+            ToolInputView::Command {
+                command: "cargo test".to_string(),
+                description: Some("Run tests".to_string()),
+            }
+        );
+        assert_eq!(
+            view(
+                "Read",
+                serde_json::json!({"file_path": "/src/lib.rs", "offset": 10, "limit": 5})
+            ),
+            ToolInputView::FileRead {
+                path: "/src/lib.rs".to_string(),
+                range: Some("lines 10-14".to_string()),
+            }
+        );
+        assert_eq!(
+            view(
+                "Edit",
+                serde_json::json!({
+                    "file_path": "/src/lib.rs",
+                    "old_string": "let a = 1;",
+                    "new_string": "let a = 2;\nlet b = 3;"
+                })
+            ),
+            ToolInputView::FileChange {
+                path: "/src/lib.rs".to_string(),
+                lines: vec![
+                    "-let a = 1;".to_string(),
+                    "+let a = 2;".to_string(),
+                    "+let b = 3;".to_string(),
+                ],
+            }
+        );
+
+        // Unknown tools, and known ones missing their fields, show JSON
+        assert_eq!(
+            view("Grep", serde_json::json!({"pattern": "fn main"})),
+            ToolInputView::Json("{\n  \"pattern\": \"fn main\"\n}".to_string())
+        );
+        assert!(matches!(
+            view("Bash", serde_json::json!({"cmd": "ls"})),
+            ToolInputView::Json(_)
+        ));
+    }
+
+    #[test]
+    fn test_render_tool_use_markdown() {
+        let bash = render_tool_use_markdown(
+            "Bash",
+            &serde_json::json!({"command": "cargo build"}),
+            false,
+        );
+        assert_eq!(bash, "**Tool: Bash**\n\n```bash\ncargo build\n```");
+
+        let write = render_tool_use_markdown(
+            "Write",
+            &serde_json::json!({"file_path": "README.md", "content": "# Title\n```sh\nls\n```"}),
+            false,
+        );
+        assert_eq!(
+            write,
+            "**Tool: Write** `README.md`\n\n````diff\n+# Title\n+```sh\n+ls\n+```\n````"
+        );
+
+        let read =
+            render_tool_use_markdown("Read", &serde_json::json!({"file_path": "a.rs"}), false);
+        assert_eq!(read, "**Tool: Read** `a.rs`");
+    }
+
+    #[test]
+    fn test_render_tool_use_text_previews_long_input() {
+        colored::control::set_override(false);
+        let content: Vec<String> = (1..=25).map(|i| format!("line {i}")).collect();
+        let input = serde_json::json!({"file_path": "notes.txt", "content": content.join("\n")});
+
+        let preview = render_tool_use_text("Write", &input, false);
+        let lines: Vec<&str> = preview.lines().collect();
+        assert_eq!(lines[0], "[Tool: Write] notes.txt");
+        assert_eq!(lines[1], "  +line 1");
+        assert_eq!(lines.len(), 1 + TOOL_INPUT_PREVIEW_LINES + 1);
+        assert_eq!(lines.last(), Some(&"  ... 5 more lines"));
+
+        assert_eq!(
+            render_tool_use_text("Write", &input, true).lines().count(),
+            26
+        );
+
+        let bash = render_tool_use_text("Bash", &serde_json::json!({"command": "ls\npwd"}), false);
+        assert_eq!(bash, "[Tool: Bash]\n  $ ls\n    pwd");
+    }
+
+    #[test]
+    fn test_raw_message_header_lists_stored_metadata() {
+        let mut message = StoredMessage {
+            id: "4b6c0d55-0000-0000-0000-000000000000".to_string(),
+            parent_id: None,
+            index: 3,
+            timestamp: "2024-01-15T09:30:00+00:00".to_string(),
+            role: "assistant".to_string(),
+            content: r#"{"Blocks":[]}"#.to_string(),
+            compressed: false,
+            model: Some("claude-opus-4".to_string()),
+            git_branch: None,
+            cwd: Some("/home/user/project".to_string()),
+        };
+        assert_eq!(
+            raw_message_header(&message),
+            vec![
+                "--- [3] 4b6c0d55-0000-0000-0000-000000000000 assistant 2024-01-15T09:30:00+00:00",
+                "    model: claude-opus-4",
+                "    cwd: /home/user/project",
+            ]
+        );
+
+        message.compressed = true;
+        assert_eq!(
+            raw_message_header(&message).last().unwrap(),
+            "    stored compressed; shown decompressed"
+        );
+    }
+
+    #[test]
+    fn test_format_elapsed_uses_largest_unit() {
+        assert_eq!(format_elapsed(42), "+42s");
+        assert_eq!(format_elapsed(150), "+2m");
+        assert_eq!(format_elapsed(3 * 3600 + 59), "+3h");
+        assert_eq!(format_elapsed(2 * 86400), "+2d");
+        assert_eq!(format_elapsed(-5), "+0s");
+    }
+
+    #[test]
+    fn test_message_clock_labels() {
+        let start = Utc::now() - Duration::days(3);
+
+        let mut elapsed = MessageClock::new(TimestampStyle::Elapsed, Some(start));
+        assert_eq!(elapsed.label(start + Duration::seconds(5)), "+5s");
+        assert_eq!(elapsed.label(start + Duration::minutes(3)), "+2m");
+
+        // A window that starts mid-session measures from its first message
+        let mut windowed = MessageClock::new(TimestampStyle::Elapsed, None);
+        assert_eq!(windowed.label(start + Duration::days(2)), "+0s");
+        assert_eq!(
+            windowed.label(start + Duration::days(2) + Duration::seconds(30)),
+            "+30s"
+        );
+
+        let mut relative = MessageClock::new(TimestampStyle::Relative, Some(start));
+        assert_eq!(relative.label(start), "3 days ago");
+
+        let mut none = MessageClock::new(TimestampStyle::None, None);
+        assert_eq!(none.label(start), start.format("%H:%M:%S").to_string());
+    }
+
+    fn message(index: i32, role: MessageRole, text: &str) -> Message {
+        Message {
+            id: uuid::Uuid::new_v4(),
+            session_id: uuid::Uuid::nil(),
+            parent_id: None,
+            index,
+            timestamp: Utc::now(),
+            role,
+            content: MessageContent::Text(text.to_string()),
+            model: None,
+            git_branch: None,
+            cwd: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_messages_by_grep_and_role() {
+        let messages = vec![
+            message(0, MessageRole::User, "Why does this panic?"),
+            message(1, MessageRole::Assistant, "The unwrap on line 4 panics."),
+            message(2, MessageRole::User, "Thanks"),
+            message(3, MessageRole::Assistant, "Anything else?"),
+        ];
+        let filter = MessageFilter {
+            pattern: Some(Regex::new("panic|unwrap").unwrap()),
+            role: None,
+        };
+        let indexes: Vec<i32> = filter_messages(messages.clone(), &filter, false)
+            .iter()
+            .map(|m| m.index)
+            .collect();
+        assert_eq!(indexes, vec![0, 1]);
+
+        let filter = MessageFilter {
+            pattern: Some(Regex::new("(?i)PANIC").unwrap()),
+            role: Some("assistant".to_string()),
+        };
+        let indexes: Vec<i32> = filter_messages(messages, &filter, false)
+            .iter()
+            .map(|m| m.index)
+            .collect();
+        assert_eq!(indexes, vec![1]);
+    }
+
+    #[test]
+    fn test_content_summary_counts_roles_and_tools() {
+        let tool_use = |name: &str| ContentBlock::ToolUse {
+            id: "t".to_string(),
+            name: name.to_string(),
+            input: serde_json::Value::Null,
+        };
+        let mut reply = message(1, MessageRole::Assistant, "");
+        reply.content = MessageContent::Blocks(vec![
+            ContentBlock::Text {
+                text: "Looking".to_string(),
+            },
+            tool_use("Read"),
+            tool_use("Bash"),
+            tool_use("Read"),
+        ]);
+        let messages = vec![
+            message(0, MessageRole::User, "Fix the build"),
+            reply,
+            message(2, MessageRole::Assistant, "Done"),
+        ];
+
+        let summary = ContentSummary::from_messages(&messages);
+        assert_eq!(summary.messages, 3);
+        assert_eq!((summary.user, summary.assistant), (1, 2));
+        assert_eq!(summary.tool_calls, 3);
+        assert_eq!(
+            summary.top_tools,
+            vec![
+                ToolCount {
+                    name: "Read".to_string(),
+                    calls: 2
+                },
+                ToolCount {
+                    name: "Bash".to_string(),
+                    calls: 1
+                },
+            ]
+        );
+        assert_eq!(
+            summary.describe(),
+            "3 messages (1 user, 2 assistant), 3 tool calls (Read 2, Bash 1)"
+        );
+        assert_eq!(
+            ContentSummary::from_messages(&[]).describe(),
+            "0 messages, 0 tool calls"
+        );
+    }
+}
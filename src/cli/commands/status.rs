@@ -22,8 +22,13 @@ const CLI_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[derive(clap::Args)]
 #[command(after_help = "EXAMPLES:\n    \
     lore status               Show status overview\n    \
-    lore status --format json Output as JSON")]
+    lore status --format json Output as JSON\n    \
+    lore status --recent 10   Include the 10 most recent sessions")]
 pub struct Args {
+    /// Number of recent sessions to include
+    #[arg(long, default_value = "5", value_name = "N")]
+    pub recent: usize,
+
     /// Output format: text (default), json
     #[arg(short, long, value_enum, default_value = "text")]
     pub format: OutputFormat,
@@ -43,6 +48,8 @@ struct StatusOutput {
 #[derive(Serialize)]
 struct DaemonStatus {
     running: bool,
+    /// Process ID of the running daemon, if any.
+    pid: Option<u32>,
     message: String,
 }
 
@@ -53,6 +60,8 @@ struct WatcherStatus {
     available: bool,
     enabled: bool,
     session_files: Option<usize>,
+    /// Sessions available to import, when the watcher is available.
+    sessions: Option<usize>,
 }
 
 /// Database statistics for JSON output.
@@ -61,6 +70,7 @@ struct DatabaseStats {
     sessions: i32,
     messages: i32,
     links: i32,
+    path: Option<String>,
     size_bytes: Option<u64>,
 }
 
@@ -92,11 +102,12 @@ pub fn run(args: Args) -> Result<()> {
     let config = Config::load()?;
 
     match args.format {
-        OutputFormat::Json => {
-            run_json(&db, &registry, &config)?;
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            run_json(&db, &registry, &config, args.recent, args.format)?;
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("status")),
         OutputFormat::Text | OutputFormat::Markdown => {
-            run_text(&db, &registry, &config)?;
+            run_text(&db, &registry, &config, args.recent)?;
         }
     }
 
@@ -104,22 +115,33 @@ pub fn run(args: Args) -> Result<()> {
 }
 
 /// Runs the status command with JSON output.
-fn run_json(db: &Database, registry: &WatcherRegistry, config: &Config) -> Result<()> {
+fn run_json(
+    db: &Database,
+    registry: &WatcherRegistry,
+    config: &Config,
+    recent_limit: usize,
+    format: OutputFormat,
+) -> Result<()> {
     // Collect watcher status
     let mut watchers = Vec::new();
     for watcher in registry.all_watchers() {
         let info = watcher.info();
         let is_enabled = config.watchers.iter().any(|w| w == info.name);
-        let session_files = if watcher.is_available() {
-            watcher.find_sources().ok().map(|s| s.len())
+        let available = watcher.is_available();
+        let (session_files, sessions) = if available {
+            (
+                watcher.find_sources().ok().map(|s| s.len()),
+                watcher.session_count().ok(),
+            )
         } else {
-            None
+            (None, None)
         };
         watchers.push(WatcherStatus {
             name: info.name.to_string(),
-            available: watcher.is_available(),
+            available,
             enabled: is_enabled,
             session_files,
+            sessions,
         });
     }
     // Add copilot placeholder
@@ -128,22 +150,24 @@ fn run_json(db: &Database, registry: &WatcherRegistry, config: &Config) -> Resul
         available: false,
         enabled: false,
         session_files: None,
+        sessions: None,
     });
 
     // Database stats
     let session_count = db.session_count()?;
     let message_count = db.message_count()?;
     let link_count = db.link_count()?;
-    let size_bytes = db
-        .db_path()
-        .and_then(|p| std::fs::metadata(&p).ok())
+    let db_path = db.db_path();
+    let size_bytes = db_path
+        .as_ref()
+        .and_then(|p| std::fs::metadata(p).ok())
         .map(|m| m.len());
 
     // Current commit
     let current_commit = get_current_commit_info(db)?;
 
     // Recent sessions
-    let recent = db.list_sessions(5, None)?;
+    let recent = db.list_sessions(recent_limit, None)?;
     let recent_sessions: Vec<RecentSessionInfo> = recent
         .into_iter()
         .map(|s| RecentSessionInfo {
@@ -156,26 +180,11 @@ fn run_json(db: &Database, registry: &WatcherRegistry, config: &Config) -> Resul
         .collect();
 
     // Check daemon status
-    let daemon_status = match DaemonState::new() {
-        Ok(state) => {
-            if state.is_running() {
-                let pid = state.get_pid().unwrap_or(0);
-                DaemonStatus {
-                    running: true,
-                    message: format!("running (PID {})", pid),
-                }
-            } else {
-                DaemonStatus {
-                    running: false,
-                    message: "not running".to_string(),
-                }
-            }
-        }
-        Err(_) => DaemonStatus {
-            running: false,
-            message: "not running".to_string(),
-        },
-    };
+    let running_pid = DaemonState::new()
+        .ok()
+        .filter(|state| state.is_running())
+        .map(|state| state.get_pid().unwrap_or(0));
+    let daemon_status = daemon_status_for(running_pid);
 
     let output = StatusOutput {
         daemon: daemon_status,
@@ -184,18 +193,35 @@ fn run_json(db: &Database, registry: &WatcherRegistry, config: &Config) -> Resul
             sessions: session_count,
             messages: message_count,
             links: link_count,
+            path: db_path.map(|p| p.display().to_string()),
             size_bytes,
         },
         current_commit,
         recent_sessions,
     };
 
-    let json = serde_json::to_string_pretty(&output)?;
+    let json = format.json_string(&output)?;
     println!("{json}");
 
     Ok(())
 }
 
+/// Builds the daemon status from the PID of the running daemon, if any.
+fn daemon_status_for(running_pid: Option<u32>) -> DaemonStatus {
+    match running_pid {
+        Some(pid) => DaemonStatus {
+            running: true,
+            pid: Some(pid),
+            message: format!("running (PID {pid})"),
+        },
+        None => DaemonStatus {
+            running: false,
+            pid: None,
+            message: "not running".to_string(),
+        },
+    }
+}
+
 /// Gets current commit info for JSON output.
 fn get_current_commit_info(db: &Database) -> Result<Option<CurrentCommitInfo>> {
     let cwd = std::env::current_dir()?;
@@ -220,7 +246,12 @@ fn get_current_commit_info(db: &Database) -> Result<Option<CurrentCommitInfo>> {
 }
 
 /// Runs the status command with text output.
-fn run_text(db: &Database, registry: &WatcherRegistry, config: &Config) -> Result<()> {
+fn run_text(
+    db: &Database,
+    registry: &WatcherRegistry,
+    config: &Config,
+    recent_limit: usize,
+) -> Result<()> {
     println!("{}", "Lore".bold().cyan());
     println!("{}", "Reasoning history for code".dimmed());
     println!();
@@ -242,7 +273,7 @@ fn run_text(db: &Database, registry: &WatcherRegistry, config: &Config) -> Resul
     let has_available_sources = registry
         .available_watchers()
         .iter()
-        .any(|w| w.find_sources().map(|s| !s.is_empty()).unwrap_or(false));
+        .any(|w| w.session_count().map(|n| n > 0).unwrap_or(false));
 
     if session_count == 0 && has_available_sources {
         println!();
@@ -253,7 +284,7 @@ fn run_text(db: &Database, registry: &WatcherRegistry, config: &Config) -> Resul
     }
 
     // Show recent sessions if any
-    print_recent_sessions(db)?;
+    print_recent_sessions(db, recent_limit)?;
 
     Ok(())
 }
@@ -312,8 +343,8 @@ fn print_daemon_status() {
 
 /// Prints the watchers availability section.
 ///
-/// Shows which session watchers are enabled, available, and how many session files
-/// each has discovered. Distinguishes between enabled (in config) and available
+/// Shows which session watchers are enabled, available, and how many sessions
+/// each has to import. Distinguishes between enabled (in config) and available
 /// (tool installed).
 fn print_watchers_status(registry: &WatcherRegistry, config: &Config) {
     println!("{}", "Watchers:".bold());
@@ -330,17 +361,22 @@ fn print_watchers_status(registry: &WatcherRegistry, config: &Config) {
                 "available (not enabled)".yellow().to_string()
             };
 
-            match watcher.find_sources() {
-                Ok(sources) if !sources.is_empty() => {
+            match watcher.session_count() {
+                Ok(0) => {
                     println!(
-                        "  {}: {} ({} session files)",
+                        "  {}: {} {}",
                         name.cyan(),
                         status_str,
-                        sources.len()
+                        "(0 sessions)".dimmed()
                     );
                 }
-                Ok(_) => {
-                    println!("  {}: {} (no sessions found)", name.cyan(), status_str);
+                Ok(count) => {
+                    println!(
+                        "  {}: {} ({})",
+                        name.cyan(),
+                        status_str,
+                        sessions_label(count)
+                    );
                 }
                 Err(_) => {
                     println!("  {}: {} (error reading sources)", name.cyan(), status_str);
@@ -357,9 +393,18 @@ fn print_watchers_status(registry: &WatcherRegistry, config: &Config) {
     println!();
 }
 
+/// Formats a session count as "1 session" or "N sessions".
+fn sessions_label(count: usize) -> String {
+    if count == 1 {
+        "1 session".to_string()
+    } else {
+        format!("{count} sessions")
+    }
+}
+
 /// Prints enhanced database statistics.
 ///
-/// Shows total sessions, messages, links, and database file size.
+/// Shows total sessions, messages, links, and the database file's path and size.
 fn print_database_stats(db: &Database) -> Result<()> {
     let session_count = db.session_count()?;
     let message_count = db.message_count()?;
@@ -372,6 +417,7 @@ fn print_database_stats(db: &Database) -> Result<()> {
 
     // Try to get database file size
     if let Some(db_path) = db.db_path() {
+        println!("  Path:     {}", db_path.display());
         if let Ok(metadata) = std::fs::metadata(&db_path) {
             let size_bytes = metadata.len();
             let size_str = format_file_size(size_bytes);
@@ -457,8 +503,8 @@ fn print_current_commit_links(db: &Database) -> Result<()> {
 }
 
 /// Prints the recent sessions section.
-fn print_recent_sessions(db: &Database) -> Result<()> {
-    let recent = db.list_sessions(5, None)?;
+fn print_recent_sessions(db: &Database, limit: usize) -> Result<()> {
+    let recent = db.list_sessions(limit, None)?;
     if recent.is_empty() {
         return Ok(());
     }
@@ -523,6 +569,25 @@ mod tests {
         assert_eq!(format_file_size(1024 * 1024 + 512 * 1024), "1.5 MB");
     }
 
+    #[test]
+    fn test_sessions_label() {
+        assert_eq!(sessions_label(0), "0 sessions");
+        assert_eq!(sessions_label(1), "1 session");
+        assert_eq!(sessions_label(128), "128 sessions");
+    }
+
+    #[test]
+    fn test_daemon_status_json_includes_pid() {
+        let running = serde_json::to_value(daemon_status_for(Some(4242))).unwrap();
+        assert_eq!(running["running"], true);
+        assert_eq!(running["pid"], 4242);
+        assert_eq!(running["message"], "running (PID 4242)");
+
+        let stopped = serde_json::to_value(daemon_status_for(None)).unwrap();
+        assert_eq!(stopped["running"], false);
+        assert!(stopped["pid"].is_null());
+    }
+
     #[test]
     fn test_format_file_size_gigabytes() {
         assert_eq!(format_file_size(1024 * 1024 * 1024), "1.0 GB");
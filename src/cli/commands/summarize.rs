@@ -3,29 +3,48 @@
 //! Manages session summaries that provide concise descriptions of what
 //! happened in a session. Summaries help with quickly understanding
 //! session context when continuing work or reviewing history.
+//!
+//! With `--all`, summaries are generated for every session that lacks one,
+//! several requests at a time. Each summary is stored as soon as it
+//! arrives, so an interrupted run resumes where it stopped when rerun.
+
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Instant;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 use uuid::Uuid;
 
-use crate::storage::{Database, Summary};
-use crate::summarize::{generate_summary, SummarizeError};
+use crate::cli::format::{format_cost, format_number};
+use crate::config::Config;
+use crate::storage::{Database, Message, Session, Summary};
+use crate::summarize::{
+    generate_summary, resolve_config, summarize_messages, GeneratedSummary, SummarizeError,
+};
+
+/// Default number of summaries `--all` requests at a time.
+const DEFAULT_CONCURRENCY: usize = 4;
 
 /// Arguments for the summarize command.
 #[derive(clap::Args)]
 #[command(after_help = "EXAMPLES:\n    \
     lore summarize abc123 \"Implemented auth feature\"  Add summary to session\n    \
     lore summarize abc123 --show                       View existing summary\n    \
-    lore summarize abc123 --generate                   Generate summary via LLM")]
+    lore summarize abc123 --generate                   Generate summary via LLM\n    \
+    lore summarize abc123 --generate --model gpt-4o    Generate with a specific model\n    \
+    lore summarize --all                               Summarize every session without one\n    \
+    lore summarize --all --repo . --concurrency 8      Summarize this repo's backlog")]
+#[command(group = clap::ArgGroup::new("generating").args(["generate", "all"]).multiple(true))]
 pub struct Args {
     /// Session ID prefix
-    #[arg(value_name = "SESSION")]
+    #[arg(value_name = "SESSION", required_unless_present = "all")]
     #[arg(
         long_help = "The session ID prefix to summarize. Must uniquely identify a\n\
         single session. Use 'lore sessions' to find session IDs."
     )]
-    pub session: String,
+    pub session: Option<String>,
 
     /// The summary text (required unless --show is used)
     #[arg(value_name = "SUMMARY")]
@@ -49,54 +68,71 @@ pub struct Args {
         Requires a summary provider to be configured via 'lore init --force'\n\
         or 'lore config set'. Cannot be used with manual summary text.")]
     pub generate: bool,
+
+    /// Model to generate the summary with, for this run only
+    #[arg(long, value_name = "NAME", requires = "generating")]
+    #[arg(
+        long_help = "Generate the summary with this model instead of the configured\n\
+        one. Takes precedence over summary_model_<provider> in the config\n\
+        file and the LORE_SUMMARY_MODEL environment variable, and does not\n\
+        change either."
+    )]
+    pub model: Option<String>,
+
+    /// Generate summaries for every session that has none
+    #[arg(long)]
+    #[arg(
+        long_help = "Generate a summary with the configured LLM provider for every\n\
+        session that has messages but no summary, newest first. Each\n\
+        summary is saved as soon as it is generated, so an interrupted run\n\
+        picks up where it stopped when run again. Rate-limited requests\n\
+        are retried with backoff; sessions that still fail are reported\n\
+        and left for the next run."
+    )]
+    pub all: bool,
+
+    /// With --all, only summarize sessions in this directory (prefix match)
+    #[arg(long, value_name = "PATH", requires = "all")]
+    #[arg(
+        long_help = "Only summarize sessions with a working directory matching\n\
+        this path prefix. Use '.' for the current directory."
+    )]
+    pub repo: Option<String>,
+
+    /// With --all, the number of summaries to request at a time
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = DEFAULT_CONCURRENCY,
+        requires = "all"
+    )]
+    pub concurrency: usize,
 }
 
 /// Executes the summarize command.
 ///
-/// Adds, updates, or displays a summary for a session.
+/// Adds, updates, or displays a summary for a session, or generates
+/// summaries for every session without one.
 pub fn run(args: Args) -> Result<()> {
-    let db = Database::open_default()?;
-
-    // Find session by prefix
-    let all_sessions = db.list_sessions(1000, None)?;
-    let matching: Vec<_> = all_sessions
-        .iter()
-        .filter(|s| s.id.to_string().starts_with(&args.session))
-        .collect();
-
-    if matching.is_empty() {
-        if all_sessions.is_empty() {
-            bail!(
-                "No session found matching '{}'. No sessions in database. \
-                 Run 'lore import' to import sessions first.",
-                args.session
-            );
-        } else {
-            bail!(
-                "No session found matching '{}'. \
-                 Run 'lore sessions' to list available sessions.",
-                args.session
-            );
+    if args.all {
+        // Not declared as clap conflicts: clap skips a `requires` whose
+        // target conflicts with a given argument, which would let --repo
+        // and --concurrency through without --all
+        if args.session.is_some() || args.show {
+            bail!("--all cannot be used with a session ID or --show.");
         }
+        return run_all(args);
     }
 
-    if matching.len() > 1 {
-        println!("{}", "Multiple sessions match that prefix:".yellow());
-        for s in &matching {
-            let id_short = &s.id.to_string()[..8];
-            println!(
-                "  {} - {}",
-                id_short.cyan(),
-                s.started_at.format("%Y-%m-%d %H:%M")
-            );
-        }
-        bail!(
-            "Multiple sessions match '{}'. Please use a more specific prefix from the list above.",
-            args.session
-        );
-    }
+    let db = Database::open_default()?;
 
-    let session = matching[0];
+    let prefix = args.session.as_deref().unwrap_or_default();
+    let session = db.find_session_by_id_prefix(prefix)?.with_context(|| {
+        format!(
+            "No session found matching '{prefix}'. \
+             Run 'lore sessions' to list available sessions."
+        )
+    })?;
     let session_id = session.id;
     let session_short = &session.id.to_string()[..8];
 
@@ -112,24 +148,24 @@ pub fn run(args: Args) -> Result<()> {
     } else if args.generate {
         // Generate summary via LLM
         let messages = db.get_messages(&session_id)?;
-        match generate_summary(&messages) {
-            Ok(summary_text) => {
-                add_or_update_summary(&db, &session_id, session_short, &summary_text)?;
-                println!(
-                    "{} {}",
+        match generate_summary(&messages, args.model.as_deref()) {
+            Ok(generated) => {
+                add_or_update_summary(
+                    &db,
+                    &session_id,
+                    session_short,
+                    &generated.content,
+                    Some(&generated.model),
+                )?;
+                note!(
+                    "{} {} {}",
                     "Generated summary for session".green(),
-                    session_short.cyan()
-                );
-                println!("{summary_text}");
-            }
-            Err(SummarizeError::NotConfigured) => {
-                bail!(
-                    "Summary provider not configured.\n\n\
-                     Configure a summary provider first:\n  \
-                     lore init --force       (guided setup with hidden key input)\n  \
-                     lore config set summary_provider <anthropic|openai|openrouter>"
+                    session_short.cyan(),
+                    format!("with {}", generated.model).dimmed()
                 );
+                println!("{}", generated.content);
             }
+            Err(SummarizeError::NotConfigured) => bail_not_configured()?,
             Err(SummarizeError::EmptySession) => {
                 bail!("Session has no messages to summarize.");
             }
@@ -139,7 +175,7 @@ pub fn run(args: Args) -> Result<()> {
         }
     } else if let Some(summary_text) = args.summary {
         // Add or update summary
-        add_or_update_summary(&db, &session_id, session_short, &summary_text)?;
+        add_or_update_summary(&db, &session_id, session_short, &summary_text, None)?;
     } else {
         // No summary text, not showing, not generating - error
         bail!("Please provide a summary text, use --show to view, or --generate to auto-generate.");
@@ -148,6 +184,241 @@ pub fn run(args: Args) -> Result<()> {
     Ok(())
 }
 
+/// Fails with instructions for configuring a summary provider.
+fn bail_not_configured() -> Result<()> {
+    bail!(
+        "Summary provider not configured.\n\n\
+         Configure a summary provider first:\n  \
+         lore init --force       (guided setup with hidden key input)\n  \
+         lore config set summary_provider <anthropic|openai|openrouter|gemini|openai-compatible>"
+    );
+}
+
+/// Generates summaries for every session without one and prints a report.
+fn run_all(args: Args) -> Result<()> {
+    let db = Database::open_default()?;
+    let mut config = match resolve_config() {
+        Ok(config) => config,
+        Err(SummarizeError::NotConfigured) => return bail_not_configured(),
+        Err(e) => bail!("Failed to generate summaries: {e}"),
+    };
+    if let Some(model) = args.model {
+        config.model = Some(model);
+    }
+    let model = config.model_name();
+
+    let working_dir = args.repo.map(|r| {
+        if r == "." {
+            std::env::current_dir()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or(r)
+        } else {
+            r
+        }
+    });
+    let sessions = db.list_sessions_without_summary(working_dir.as_deref())?;
+    if sessions.is_empty() {
+        println!("{}", "Every session already has a summary.".green());
+        return Ok(());
+    }
+
+    let concurrency = args.concurrency.max(1);
+    note!(
+        "Summarizing {} session(s) with {}, {} at a time",
+        sessions.len(),
+        model.cyan(),
+        concurrency
+    );
+    note!(
+        "{}",
+        "Summaries are saved as they arrive; rerun to resume after an interruption.".dimmed()
+    );
+
+    let started = Instant::now();
+    let total = sessions.len();
+    let report = summarize_batch(
+        &db,
+        &sessions,
+        concurrency,
+        |messages| summarize_messages(&config, messages),
+        |done, session, outcome| {
+            let short_id = &session.id.to_string()[..8];
+            match outcome {
+                Ok(()) => note!(
+                    "  [{done}/{total}] {} {}",
+                    "Summarized".green(),
+                    short_id.cyan()
+                ),
+                Err(SummarizeError::EmptySession) => {
+                    note!(
+                        "  [{done}/{total}] {} {short_id} (no text)",
+                        "Skipped".dimmed()
+                    )
+                }
+                Err(e) => note!("  [{done}/{total}] {} {short_id}: {e}", "Failed".red()),
+            }
+        },
+    )?;
+
+    println!();
+    println!(
+        "Generated {} summar{} in {}s",
+        report.generated.to_string().green(),
+        if report.generated == 1 { "y" } else { "ies" },
+        started.elapsed().as_secs()
+    );
+    if report.empty > 0 {
+        println!(
+            "Skipped {} session(s) with nothing to summarize",
+            report.empty.to_string().yellow()
+        );
+    }
+    let cost =
+        Config::load()?.estimate_cost(Some(&model), report.input_tokens, report.output_tokens);
+    println!(
+        "Estimated usage: ~{} input / ~{} output tokens, cost {}",
+        format_number(report.input_tokens),
+        format_number(report.output_tokens),
+        format_cost(cost)
+    );
+    if report.failed > 0 {
+        bail!(
+            "{} session(s) could not be summarized. Run 'lore summarize --all' again to retry them.",
+            report.failed
+        );
+    }
+    Ok(())
+}
+
+/// Totals of a [`summarize_batch`] run.
+#[derive(Debug, Default, PartialEq)]
+struct BatchReport {
+    /// Summaries generated and stored.
+    generated: usize,
+    /// Sessions with no text to summarize.
+    empty: usize,
+    /// Sessions whose summary request failed.
+    failed: usize,
+    /// Estimated prompt tokens across all generated summaries.
+    input_tokens: i64,
+    /// Estimated completion tokens across all generated summaries.
+    output_tokens: i64,
+}
+
+/// A session's messages, sent to a worker, and the worker's result.
+type BatchJob = (usize, Vec<Message>);
+type BatchResult = (usize, Result<GeneratedSummary, SummarizeError>);
+
+/// Generates and stores a summary for each of `sessions`, running up to
+/// `concurrency` `generate` calls at a time.
+///
+/// Messages are loaded and summaries stored on the calling thread, one
+/// session ahead of each free worker, so at most `concurrency` transcripts
+/// are held in memory. `on_done` is called as each session finishes, with
+/// the number finished so far. A failed request is counted and the batch
+/// goes on; only database errors stop it.
+fn summarize_batch(
+    db: &Database,
+    sessions: &[Session],
+    concurrency: usize,
+    generate: impl Fn(&[Message]) -> Result<GeneratedSummary, SummarizeError> + Sync,
+    mut on_done: impl FnMut(usize, &Session, Result<(), &SummarizeError>),
+) -> Result<BatchReport> {
+    let workers = concurrency.clamp(1, sessions.len().max(1));
+    let (job_sender, job_receiver) = mpsc::channel::<BatchJob>();
+    let (result_sender, result_receiver) = mpsc::channel::<BatchResult>();
+    let job_receiver = Mutex::new(job_receiver);
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let result_sender = result_sender.clone();
+            let (job_receiver, generate) = (&job_receiver, &generate);
+            scope.spawn(move || loop {
+                let job = job_receiver
+                    .lock()
+                    .map_err(|_| ())
+                    .and_then(|receiver| receiver.recv().map_err(|_| ()));
+                let Ok((index, messages)) = job else {
+                    break;
+                };
+                if result_sender.send((index, generate(&messages))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(result_sender);
+
+        // The job sender moves into the dispatch loop, so the workers stop
+        // once it returns, even on error
+        dispatch_batch(
+            db,
+            sessions,
+            workers,
+            job_sender,
+            &result_receiver,
+            &mut on_done,
+        )
+    })
+}
+
+/// Feeds `sessions` to the workers of [`summarize_batch`] and stores their
+/// results.
+fn dispatch_batch(
+    db: &Database,
+    sessions: &[Session],
+    workers: usize,
+    job_sender: mpsc::Sender<BatchJob>,
+    result_receiver: &mpsc::Receiver<BatchResult>,
+    on_done: &mut impl FnMut(usize, &Session, Result<(), &SummarizeError>),
+) -> Result<BatchReport> {
+    let mut report = BatchReport::default();
+    let mut next = 0;
+    let mut in_flight = 0;
+    let mut done = 0;
+    loop {
+        while in_flight < workers && next < sessions.len() {
+            let messages = db.get_messages(&sessions[next].id)?;
+            job_sender
+                .send((next, messages))
+                .context("Summary worker stopped unexpectedly")?;
+            next += 1;
+            in_flight += 1;
+        }
+        if in_flight == 0 {
+            return Ok(report);
+        }
+
+        let (index, result) = result_receiver
+            .recv()
+            .context("Summary worker stopped unexpectedly")?;
+        in_flight -= 1;
+        done += 1;
+        let session = &sessions[index];
+        match result {
+            Ok(generated) => {
+                db.insert_summary(&Summary {
+                    id: Uuid::new_v4(),
+                    session_id: session.id,
+                    content: generated.content,
+                    generated_at: Utc::now(),
+                    model: Some(generated.model),
+                })?;
+                report.generated += 1;
+                report.input_tokens += generated.estimated_input_tokens;
+                report.output_tokens += generated.estimated_output_tokens;
+                on_done(done, session, Ok(()));
+            }
+            Err(SummarizeError::EmptySession) => {
+                report.empty += 1;
+                on_done(done, session, Err(&SummarizeError::EmptySession));
+            }
+            Err(e) => {
+                report.failed += 1;
+                on_done(done, session, Err(&e));
+            }
+        }
+    }
+}
+
 /// Displays the existing summary for a session.
 fn show_summary(db: &Database, session_id: &Uuid, session_short: &str) -> Result<()> {
     match db.get_summary(session_id)? {
@@ -165,6 +436,9 @@ fn show_summary(db: &Database, session_id: &Uuid, session_short: &str) -> Result
                 )
                 .dimmed()
             );
+            if let Some(model) = &summary.model {
+                println!("{}", format!("Generated by: {model}").dimmed());
+            }
         }
         None => {
             println!(
@@ -182,19 +456,23 @@ fn show_summary(db: &Database, session_id: &Uuid, session_short: &str) -> Result
 }
 
 /// Adds a new summary or updates an existing one.
+///
+/// `model` is the model that generated `content`, or None for text given
+/// on the command line.
 fn add_or_update_summary(
     db: &Database,
     session_id: &Uuid,
     session_short: &str,
     content: &str,
+    model: Option<&str>,
 ) -> Result<()> {
     // Check if a summary already exists
     let existing = db.get_summary(session_id)?;
 
     if existing.is_some() {
         // Update existing summary
-        db.update_summary(session_id, content)?;
-        println!(
+        db.update_summary(session_id, content, model)?;
+        note!(
             "{} session {}",
             "Updated summary for".green(),
             session_short.cyan()
@@ -206,9 +484,10 @@ fn add_or_update_summary(
             session_id: *session_id,
             content: content.to_string(),
             generated_at: Utc::now(),
+            model: model.map(str::to_string),
         };
         db.insert_summary(&summary)?;
-        println!(
+        note!(
             "{} session {}",
             "Summary saved for".green(),
             session_short.cyan()
@@ -217,3 +496,108 @@ fn add_or_update_summary(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{MessageContent, MessageRole};
+
+    fn session_with_message(db: &Database, text: &str) -> Session {
+        let session = Session {
+            id: Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at: Utc::now(),
+            ended_at: None,
+            model: None,
+            working_directory: "/work/app".to_string(),
+            git_branch: None,
+            source_path: None,
+            message_count: 1,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        };
+        db.insert_session(&session).unwrap();
+        db.insert_message(&Message {
+            id: Uuid::new_v4(),
+            session_id: session.id,
+            parent_id: None,
+            index: 0,
+            timestamp: Utc::now(),
+            role: MessageRole::User,
+            content: MessageContent::Text(text.to_string()),
+            model: None,
+            git_branch: None,
+            cwd: None,
+        })
+        .unwrap();
+        session
+    }
+
+    /// Summarizes a message as its own text, failing on "rate limited" and
+    /// reporting "empty" as having nothing to summarize.
+    fn fake_generate(messages: &[Message]) -> Result<GeneratedSummary, SummarizeError> {
+        match messages[0].content.text().as_str() {
+            "rate limited" => Err(SummarizeError::HttpError {
+                status: 429,
+                body: "slow down".to_string(),
+                retry_after: None,
+            }),
+            "empty" => Err(SummarizeError::EmptySession),
+            text => Ok(GeneratedSummary {
+                content: format!("Summary of {text}"),
+                model: "fake-model".to_string(),
+                estimated_input_tokens: 100,
+                estimated_output_tokens: 10,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_summarize_batch_stores_summaries_and_resumes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("lore.db")).unwrap();
+        let texts = ["first", "rate limited", "second", "empty", "third"];
+        let sessions: Vec<Session> = texts
+            .iter()
+            .map(|text| session_with_message(&db, text))
+            .collect();
+
+        let mut finished = Vec::new();
+        let report = summarize_batch(&db, &sessions, 3, fake_generate, |done, session, _| {
+            finished.push((done, session.id))
+        })
+        .unwrap();
+
+        assert_eq!(
+            report,
+            BatchReport {
+                generated: 3,
+                empty: 1,
+                failed: 1,
+                input_tokens: 300,
+                output_tokens: 30,
+            }
+        );
+        assert_eq!(
+            finished.iter().map(|(done, _)| *done).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        let summary = db.get_summary(&sessions[2].id).unwrap().unwrap();
+        assert_eq!(summary.content, "Summary of second");
+        assert_eq!(summary.model.as_deref(), Some("fake-model"));
+
+        // A rerun only picks up the sessions still without a summary
+        let remaining: Vec<Uuid> = db
+            .list_sessions_without_summary(None)
+            .unwrap()
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.contains(&sessions[1].id));
+        assert!(remaining.contains(&sessions[3].id));
+    }
+}
@@ -13,13 +13,19 @@
 //!   passphrase, and write the salt to the ref.
 //! - `lore sync` - fetch, merge remote reasoning into the local database, then
 //!   build, commit, and push the updated store.
+//! - `lore sync --session <id>` - fetch and merge a single session from the
+//!   store without pushing.
 //! - `lore sync status` - report whether the store is set up, the unsynced
 //!   count, the last sync time, and local and remote ref state.
+//! - `lore sync delete` - remove sessions from the store while keeping the
+//!   local copies.
+//! - `lore sync rotate-key` - re-encrypt the store under a new passphrase and
+//!   salt.
 //!
 //! All git access shells out through [`crate::sync::gitref`], inheriting the
 //! user's authentication and remotes.
 
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -32,13 +38,14 @@ use uuid::Uuid;
 
 use crate::cli::OutputFormat;
 use crate::config::Config;
+use crate::storage::db::{ConflictStrategy, MergeOutcome, SyncTrack};
 use crate::storage::models::{Machine, Session, Tombstone};
 use crate::storage::Database;
 use crate::sync::gitref::{self, TreeEntry};
 use crate::sync::keystore::{derive_store_key, generate_store_salt, store_id_from_salt, KeyStore};
 use crate::sync::store::{
     decrypt_session_record, decrypt_tombstones, encrypt_session_record, encrypt_tombstones,
-    SessionRecord,
+    encrypted_record_size, SessionRecord,
 };
 use crate::sync::SyncError;
 
@@ -63,6 +70,13 @@ const MIN_PASSPHRASE_LEN: usize = 8;
 /// deleted_at}) so a deletion on one machine suppresses that record everywhere.
 const TOMBSTONES_PATH: &str = "meta/tombstones";
 
+/// Tree path of the plaintext list of sessions removed from the store.
+///
+/// A sorted JSON array of session UUIDs written by `lore sync delete`. Kept in
+/// plaintext like `meta/machines.json` (the ids are already visible in the
+/// session blob paths) so it dedups across syncs without re-encryption.
+const EXCLUDED_SESSIONS_PATH: &str = "meta/excluded_sessions.json";
+
 /// Maximum number of fetch/merge/build/push attempts before giving up.
 ///
 /// A concurrent local sync (compare-and-swap mismatch) or a remote that moved
@@ -75,7 +89,13 @@ const MAX_SYNC_ATTEMPTS: usize = 5;
     lore sync setup            Create or join this repo's encrypted lore store\n    \
     lore sync                  Fetch, merge, and push reasoning history\n    \
     lore sync status           Show sync state for this repo\n    \
-    lore sync --remote upstream  Sync against a non-default remote")]
+    lore sync status --verbose  Also list each pending session and its size\n    \
+    lore sync --remote upstream  Sync against a non-default remote\n    \
+    lore sync --on-conflict skip  Keep local copies and report conflicts\n    \
+    lore sync --session abc123  Pull one session from the store, push nothing\n    \
+    lore sync delete abc123    Remove a session from the store (keeps local copy)\n    \
+    lore sync delete --all-this-machine  Remove every session from this machine\n    \
+    lore sync rotate-key       Re-encrypt the store under a new passphrase")]
 pub struct Args {
     #[command(subcommand)]
     pub command: Option<SyncSubcommand>,
@@ -95,13 +115,55 @@ pub struct Args {
     #[arg(long, global = true)]
     pub global: bool,
 
-    /// Hook-friendly mode used by the pre-push hook.
-    ///
-    /// No-ops and exits 0 when this repo's store is not set up or no key is
-    /// stored on this machine, never prompts for a passphrase, and keeps output
-    /// minimal. Only affects a full sync (no subcommand).
-    #[arg(long)]
-    pub quiet: bool,
+    /// How to resolve a remote session that differs from the local copy.
+    #[arg(long, value_enum, default_value = "newest", value_name = "STRATEGY")]
+    #[arg(
+        long_help = "How to resolve a pulled session whose message count or end time\n\
+        differs from the local copy:\n  \
+        - newest: take the copy with more messages, then the later end time\n  \
+        - prefer-remote: always take the remote copy\n  \
+        - prefer-local: always keep the local copy\n  \
+        - skip: keep the local copy and report the session as a conflict\n\
+        Links, tags, annotations, and summaries are merged either way.\n\
+        Only affects a full sync (no subcommand)."
+    )]
+    pub on_conflict: OnConflict,
+
+    /// Pull only this session from the store, without pushing.
+    #[arg(long, value_name = "SESSION")]
+    #[arg(
+        long_help = "Fetch the store and merge only the session whose ID starts with\n\
+        this prefix, for example to restore one session on a new machine.\n\
+        Nothing is pushed. Fails when no session in the store matches, or\n\
+        when the prefix matches more than one. Only affects a full sync\n\
+        (no subcommand)."
+    )]
+    pub session: Option<String>,
+}
+
+/// Values for `--on-conflict`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OnConflict {
+    /// Keep the local copy and report the conflict.
+    Skip,
+    /// Always take the remote copy.
+    #[value(alias = "prefer-cloud")]
+    PreferRemote,
+    /// Always keep the local copy.
+    PreferLocal,
+    /// Take whichever copy is newer.
+    Newest,
+}
+
+impl From<OnConflict> for ConflictStrategy {
+    fn from(value: OnConflict) -> Self {
+        match value {
+            OnConflict::Skip => ConflictStrategy::Skip,
+            OnConflict::PreferRemote => ConflictStrategy::PreferRemote,
+            OnConflict::PreferLocal => ConflictStrategy::PreferLocal,
+            OnConflict::Newest => ConflictStrategy::Newest,
+        }
+    }
 }
 
 /// Sync subcommands. When omitted, a full sync runs.
@@ -120,13 +182,58 @@ pub enum SyncSubcommand {
     /// Show sync status for this repo's lore store.
     #[command(
         long_about = "Reports whether the store is set up, how many local sessions are\n\
-        pending sync, the last sync time, and the local and remote ref state."
+        pending sync, the last sync time, and the local and remote ref state.\n\
+        With the global --verbose flag, also lists each pending session with\n\
+        its message count and the size of its encrypted blob, largest first."
     )]
     Status {
         /// Output format: text (default) or json.
         #[arg(short, long, value_enum, default_value = "text")]
         format: OutputFormat,
     },
+
+    /// Remove sessions from the lore store, keeping the local copies.
+    #[command(
+        long_about = "Removes sessions from the shared store, for example one pushed by\n\
+        mistake. The local copy is kept, but the session is excluded from\n\
+        future pushes. The session is added to the store's exclusion list,\n\
+        which is synced immediately, so other machines stop importing and\n\
+        re-pushing the session once they sync; copies they already hold are\n\
+        not deleted. Prompts for confirmation unless --force is given."
+    )]
+    Delete {
+        /// Session ID prefix to remove from the store.
+        #[arg(
+            value_name = "SESSION",
+            required_unless_present = "all_this_machine",
+            conflicts_with = "all_this_machine"
+        )]
+        session: Option<String>,
+
+        /// Remove every session in this store captured on this machine.
+        #[arg(long)]
+        all_this_machine: bool,
+
+        /// Skip the confirmation prompt.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Re-encrypt the lore store under a new passphrase.
+    #[command(
+        long_about = "Changes the store passphrase, for example after it was exposed.\n\
+        Prompts for the current and the new passphrase, syncs so the store\n\
+        holds every session, then decrypts each session with the old key and\n\
+        re-encrypts it with a key derived from the new passphrase and a fresh\n\
+        salt. The re-encrypted sessions and the new salt are pushed in one\n\
+        commit, and the key stored on this machine is replaced only after\n\
+        that push succeeds.\n\n\
+        Other machines can no longer sync with the old key. Run\n\
+        'lore sync setup' on each of them and enter the new passphrase.\n\
+        Old commits of the store ref stay readable with the old passphrase\n\
+        to anyone who already fetched them."
+    )]
+    RotateKey,
 }
 
 /// A machine's identity for the store's machine registry.
@@ -165,6 +272,17 @@ struct SyncSummary {
     pulled: usize,
     /// Local unsynced sessions written into the store and pushed.
     pushed: usize,
+    /// Remote sessions left unmerged under `--on-conflict skip`.
+    conflicts: usize,
+}
+
+/// Per-session results of merging a remote store into the database.
+#[derive(Debug, Default)]
+struct MergeTally {
+    /// Sessions whose row was imported or updated.
+    pulled: usize,
+    /// Sessions left unmerged because they conflicted with the local copy.
+    conflicts: usize,
 }
 
 /// JSON output for `lore sync status`.
@@ -178,6 +296,8 @@ struct StatusOutput {
     local_ref: Option<String>,
     tracking_ref: Option<String>,
     remote: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pending: Option<Vec<PendingSession>>,
 }
 
 /// JSON output for `lore sync --global status`.
@@ -192,6 +312,19 @@ struct GlobalStatusOutput {
     tracking_ref: Option<String>,
     remote: Option<String>,
     store_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pending: Option<Vec<PendingSession>>,
+}
+
+/// A session waiting to be pushed, as listed by `lore sync status --verbose`.
+#[derive(Debug, Serialize)]
+struct PendingSession {
+    id: Uuid,
+    tool: String,
+    started_at: DateTime<Utc>,
+    message_count: i32,
+    /// Size of the session's encrypted blob in bytes.
+    encrypted_bytes: usize,
 }
 
 /// Identifies which lore store a sync operates on.
@@ -210,20 +343,68 @@ enum SyncStore {
 }
 
 /// Executes the sync command.
-pub fn run(args: Args) -> Result<()> {
+///
+/// `verbose` is the global `--verbose` flag; `status` lists the pending
+/// sessions when it is set.
+///
+/// With the global `--quiet` flag a full sync runs in the hook-friendly mode
+/// used by the pre-push hook: it no-ops and exits 0 when this repo's store is
+/// not set up or no key is stored on this machine, never prompts for a
+/// passphrase, and keeps output minimal.
+pub fn run(args: Args, verbose: bool) -> Result<()> {
     if args.global {
         return match args.command {
             Some(SyncSubcommand::Setup) => run_global_setup(),
-            Some(SyncSubcommand::Status { format }) => run_global_status(format),
-            None => run_global_sync(),
+            Some(SyncSubcommand::Status { format }) => run_global_status(format, verbose),
+            Some(SyncSubcommand::Delete {
+                session,
+                all_this_machine,
+                force,
+            }) => run_delete(
+                SyncStore::Global,
+                GLOBAL_REMOTE,
+                session.as_deref(),
+                all_this_machine,
+                force,
+            ),
+            Some(SyncSubcommand::RotateKey) => run_rotate_key(SyncStore::Global, GLOBAL_REMOTE),
+            None => match args.session {
+                Some(prefix) => run_pull_session(
+                    SyncStore::Global,
+                    GLOBAL_REMOTE,
+                    &prefix,
+                    args.on_conflict.into(),
+                ),
+                None => run_global_sync(args.on_conflict.into()),
+            },
         };
     }
 
     match args.command {
         Some(SyncSubcommand::Setup) => run_setup(&args.remote),
-        Some(SyncSubcommand::Status { format }) => run_status(&args.remote, format),
-        None if args.quiet => run_sync_quiet(&args.remote),
-        None => run_sync(&args.remote),
+        Some(SyncSubcommand::Status { format }) => run_status(&args.remote, format, verbose),
+        Some(SyncSubcommand::Delete {
+            session,
+            all_this_machine,
+            force,
+        }) => run_delete(
+            SyncStore::PerRepo,
+            &args.remote,
+            session.as_deref(),
+            all_this_machine,
+            force,
+        ),
+        Some(SyncSubcommand::RotateKey) => run_rotate_key(SyncStore::PerRepo, &args.remote),
+        None => match args.session {
+            Some(prefix) => run_pull_session(
+                SyncStore::PerRepo,
+                &args.remote,
+                &prefix,
+                args.on_conflict.into(),
+            ),
+            None if crate::cli::is_quiet() => run_sync_quiet(&args.remote),
+            None => run_sync(&args.remote, args.on_conflict.into()),
+        },
     }
 }
 
@@ -234,7 +415,7 @@ fn run_setup(remote: &str) -> Result<()> {
     let repo = current_repo()?;
     let mut config = Config::load()?;
     create_or_join_store(&repo, remote, &mut config)?;
-    println!("Run 'lore sync' to push your reasoning history.");
+    note!("Run 'lore sync' to push your reasoning history.");
     Ok(())
 }
 
@@ -254,13 +435,19 @@ fn create_or_join_store(repo: &Path, remote: &str, config: &mut Config) -> Resul
     gitref::fetch(repo, remote, SESSIONS_REF)
         .with_context(|| format!("Failed to reach remote '{remote}'"))?;
 
-    match read_store_salt(repo, remote)? {
+    // Prefer the remote's salt: a local ref left over from before a
+    // `lore sync rotate-key` on another machine still holds the old one.
+    let salt = match read_tracking_salt(repo, remote)? {
+        Some(salt) => Some(salt),
+        None => read_store_salt(repo, remote)?,
+    };
+    match salt {
         Some(salt) => {
             println!("{}", "An existing lore store was found. Joining it.".bold());
             println!("Enter the shared passphrase for this lore store.");
             let passphrase = prompt_passphrase()?;
             join_store(repo, remote, &keystore, &machine, &salt, &passphrase)?;
-            println!("{} Joined the lore store.", "Success!".green().bold());
+            note!("{} Joined the lore store.", "Success!".green().bold());
         }
         None => {
             println!("{}", "Setting up a new lore store.".bold());
@@ -271,7 +458,7 @@ fn create_or_join_store(repo: &Path, remote: &str, config: &mut Config) -> Resul
             println!();
             let passphrase = prompt_new_passphrase()?;
             create_store(repo, remote, &keystore, &machine, &passphrase)?;
-            println!("{} Created the lore store.", "Success!".green().bold());
+            note!("{} Created the lore store.", "Success!".green().bold());
         }
     }
 
@@ -364,7 +551,7 @@ fn establish_store(
 // ==================== sync ====================
 
 /// Performs a full sync of the current repository's lore store.
-fn run_sync(remote: &str) -> Result<()> {
+fn run_sync(remote: &str, strategy: ConflictStrategy) -> Result<()> {
     let repo = current_repo()?;
     let mut config = Config::load()?;
     let machine = machine_identity(&mut config)?;
@@ -376,17 +563,40 @@ fn run_sync(remote: &str) -> Result<()> {
     // Push only this repo's own sessions so cross-project history is never
     // written into (and shared through) this repo's store.
     let sessions = db.get_unsynced_sessions_for_repo(&repo)?;
-    let summary = perform_sync(&mut db, &repo, remote, &key, &salt, &machine, sessions)?;
+    let summary = perform_sync_in_store(
+        SyncStore::PerRepo,
+        &mut db,
+        &repo,
+        remote,
+        &key,
+        &salt,
+        &machine,
+        sessions,
+        strategy,
+    )?;
 
-    println!(
+    note!(
         "{} Pulled {}, pushed {}.",
         "Sync complete.".green().bold(),
         summary.pulled,
         summary.pushed
     );
+    print_conflicts(summary.conflicts);
     Ok(())
 }
 
+/// Reports sessions skipped under `--on-conflict skip`, if any.
+fn print_conflicts(conflicts: usize) {
+    if conflicts > 0 {
+        println!(
+            "{} Skipped {} conflicting session(s); local copies were kept. \
+             Re-run with --on-conflict newest or prefer-remote to merge them.",
+            "Warning:".yellow(),
+            conflicts
+        );
+    }
+}
+
 /// Performs a hook-friendly sync used by the pre-push hook.
 ///
 /// Skips silently (returns Ok without touching the config or database) when the
@@ -507,6 +717,8 @@ fn configured_remotes(repo: &Path) -> Result<Vec<String>> {
 /// Retries the whole cycle on a compare-and-swap mismatch (a concurrent local
 /// sync moved the ref) or a non-fast-forward push (the remote moved between our
 /// fetch and push), up to [`MAX_SYNC_ATTEMPTS`].
+///
+/// Conflicting sessions are resolved newer-wins ([`ConflictStrategy::Newest`]).
 fn perform_sync(
     db: &mut Database,
     repo: &Path,
@@ -525,6 +737,7 @@ fn perform_sync(
         salt,
         machine,
         sessions,
+        ConflictStrategy::Newest,
     )
 }
 
@@ -542,7 +755,9 @@ fn perform_sync(
 ///   calls [`Database::mark_global_synced`].
 ///
 /// The caller supplies `sessions`, the exact set to push (per-repo passes the
-/// repo-scoped unsynced set; global passes all unsynced-global sessions).
+/// repo-scoped unsynced set; global passes all unsynced-global sessions), and
+/// `strategy`, which decides how remote sessions that differ from the local
+/// copy are merged.
 #[allow(clippy::too_many_arguments)]
 fn perform_sync_in_store(
     store: SyncStore,
@@ -553,6 +768,7 @@ fn perform_sync_in_store(
     salt: &[u8],
     machine: &MachineIdentity,
     sessions: Vec<Session>,
+    strategy: ConflictStrategy,
 ) -> Result<SyncSummary> {
     let mut pulled_total = 0;
 
@@ -581,10 +797,18 @@ fn perform_sync_in_store(
         let remote_tombstones = read_remote_tombstones(repo, &tracking_entries, key)?;
         db.add_tombstones(&remote_tombstones)?;
 
-        // MERGE remote -> local database (full records, newer-wins). A wrong key
-        // surfaces here and aborts before anything is built or pushed. The store
-        // selects which sync-tracking column an imported session is marked on.
-        pulled_total += merge_remote_in_store(store, db, repo, &tracking_entries, key)?;
+        // EXCLUSIONS: union sessions removed from the store on any machine so
+        // the merge below never re-imports them and they are never pushed.
+        let remote_exclusions = read_excluded_sessions(repo, &tracking_entries)?;
+        db.exclude_sessions_from_sync(&remote_exclusions.iter().copied().collect::<Vec<_>>())?;
+
+        // MERGE remote -> local database (full records, resolved by `strategy`).
+        // A wrong key surfaces here and aborts before anything is built or
+        // pushed. The store selects which sync-tracking column an imported
+        // session is marked on. Conflicts are counted from the last attempt
+        // only, since a retry re-merges the same sessions.
+        let tally = merge_remote_in_store(store, db, repo, &tracking_entries, key, strategy, None)?;
+        pulled_total += tally.pulled;
         merge_machines(db, repo, &tracking_entries)?;
 
         // APPLY tombstones: remove any child that is present locally but has been
@@ -618,7 +842,17 @@ fn perform_sync_in_store(
         let tree_base = tracking_commit.clone();
         let commit_parent = tracking_commit.clone();
 
-        let mut changes = build_session_changes(db, repo, key, &sessions)?;
+        // Sessions removed from the store (`lore sync delete`, here or on
+        // another machine whose exclusions were just unioned in) are never
+        // pushed, and their artifacts are dropped from the outgoing tree.
+        let excluded = db.sync_excluded_session_ids()?;
+        let outgoing: Vec<Session> = sessions
+            .iter()
+            .filter(|s| !excluded.contains(&s.id))
+            .cloned()
+            .collect();
+
+        let mut changes = build_session_changes(db, repo, key, &outgoing)?;
 
         // Carry forward already-stored, in-scope session artifacts that live
         // only in the local ref so no stored in-scope session is silently
@@ -653,6 +887,9 @@ fn perform_sync_in_store(
             )?;
         }
 
+        let removals = excluded_session_paths(&excluded);
+        changes.retain(|path, _| !removals.contains(path));
+
         add_meta_changes(db, repo, tree_base.as_deref(), salt, machine, &mut changes)?;
 
         // Write the unioned tombstone set back to the store, but only when it
@@ -660,9 +897,11 @@ fn perform_sync_in_store(
         // keeps its existing content-addressed blob (no churn from re-encrypting
         // with a fresh nonce every sync).
         add_tombstone_changes(db, repo, &remote_tombstones, key, &mut changes)?;
+        add_exclusion_changes(repo, &excluded, &remote_exclusions, &mut changes)?;
 
-        let tree = gitref::build_tree(repo, tree_base.as_deref(), &changes)?;
-        let message = format!("lore: sync {} session(s)", sessions.len());
+        let tree =
+            gitref::build_tree_with_removals(repo, tree_base.as_deref(), &changes, &removals)?;
+        let message = format!("lore: sync {} session(s)", outgoing.len());
         let commit = gitref::commit_tree(repo, &tree, commit_parent.as_deref(), &message)?;
 
         // CAS-update the local ref, guarding against a concurrent local sync.
@@ -691,7 +930,8 @@ fn perform_sync_in_store(
 
         return Ok(SyncSummary {
             pulled: pulled_total,
-            pushed: sessions.len(),
+            pushed: outgoing.len(),
+            conflicts: tally.conflicts,
         });
     }
 
@@ -708,23 +948,34 @@ fn merge_remote(
     entries: &[TreeEntry],
     key: &[u8],
 ) -> Result<usize> {
-    merge_remote_in_store(SyncStore::PerRepo, db, repo, entries, key)
+    merge_remote_in_store(
+        SyncStore::PerRepo,
+        db,
+        repo,
+        entries,
+        key,
+        ConflictStrategy::Newest,
+        None,
+    )
+    .map(|tally| tally.pulled)
 }
 
 /// Merges every encrypted session in `entries` into the database.
 ///
 /// Each record is applied atomically by the merge writer: the session row and
-/// messages follow newer-wins (by message_count then ended_at) while links,
-/// tags, and annotations are always merged (additive, idempotent by id) so a
-/// remote addition to an already-synced session is not lost, and the summary is
-/// kept only when strictly newer. Returns the number of sessions whose row was
-/// imported or updated (the newer-wins branch ran).
+/// messages follow `strategy` (newer-wins by message_count then ended_at by
+/// default) while links, tags, and annotations are always merged (additive,
+/// idempotent by id) so a remote addition to an already-synced session is not
+/// lost, and the summary is kept only when strictly newer. Returns how many
+/// session rows were imported or updated and how many were skipped as
+/// conflicts.
 ///
 /// `store` selects which sync-tracking column an imported session is marked on:
-/// [`SyncStore::PerRepo`] marks `synced_at` (via [`Database::merge_remote_record`])
-/// and [`SyncStore::Global`] marks `global_synced_at` (via
-/// [`Database::merge_remote_record_global`]). Marking only the merging store's
-/// column keeps the two sync tracks independent.
+/// [`SyncStore::PerRepo`] marks `synced_at` and [`SyncStore::Global`] marks
+/// `global_synced_at` (via [`Database::merge_remote_record_with`]). Marking
+/// only the merging store's column keeps the two sync tracks independent.
+///
+/// When `session` is given, every other session in `entries` is left alone.
 ///
 /// A blob that cannot be decrypted is normally skipped (corruption or a single
 /// stray entry). But if the store held session blobs and NONE of them decrypted,
@@ -737,8 +988,10 @@ fn merge_remote_in_store(
     repo: &Path,
     entries: &[TreeEntry],
     key: &[u8],
-) -> Result<usize> {
-    let mut pulled = 0;
+    strategy: ConflictStrategy,
+    session: Option<Uuid>,
+) -> Result<MergeTally> {
+    let mut tally = MergeTally::default();
     let mut session_blobs = 0;
     let mut decrypted = 0;
 
@@ -746,6 +999,9 @@ fn merge_remote_in_store(
         if !is_session_blob(&entry.path) {
             continue;
         }
+        if session.is_some_and(|id| session_uuid_from_path(&entry.path) != Some(id)) {
+            continue;
+        }
         session_blobs += 1;
 
         let blob = gitref::read_blob(repo, &entry.sha)?;
@@ -761,29 +1017,25 @@ fn merge_remote_in_store(
         };
         decrypted += 1;
 
-        let now = Utc::now();
-        let imported = match store {
-            SyncStore::PerRepo => db.merge_remote_record(
-                &record.session,
-                &record.messages,
-                &record.links,
-                &record.tags,
-                &record.annotations,
-                record.summary.as_ref(),
-                now,
-            )?,
-            SyncStore::Global => db.merge_remote_record_global(
-                &record.session,
-                &record.messages,
-                &record.links,
-                &record.tags,
-                &record.annotations,
-                record.summary.as_ref(),
-                now,
-            )?,
+        let track = match store {
+            SyncStore::PerRepo => SyncTrack::PerRepo,
+            SyncStore::Global => SyncTrack::Global,
         };
-        if imported {
-            pulled += 1;
+        let outcome = db.merge_remote_record_with(
+            &record.session,
+            &record.messages,
+            &record.links,
+            &record.tags,
+            &record.annotations,
+            record.summary.as_ref(),
+            Utc::now(),
+            track,
+            strategy,
+        )?;
+        match outcome {
+            MergeOutcome::Imported => tally.pulled += 1,
+            MergeOutcome::Conflict => tally.conflicts += 1,
+            MergeOutcome::Unchanged | MergeOutcome::KeptLocal => {}
         }
     }
 
@@ -797,7 +1049,86 @@ fn merge_remote_in_store(
         );
     }
 
-    Ok(pulled)
+    Ok(tally)
+}
+
+// ==================== pull one session ====================
+
+/// Fetches the store and merges the one session matching `prefix`.
+///
+/// The prefix is matched against the sessions in the remote store rather
+/// than the local database, so a session this machine has never seen can be
+/// restored. Nothing is built or pushed.
+fn run_pull_session(
+    store: SyncStore,
+    remote: &str,
+    prefix: &str,
+    strategy: ConflictStrategy,
+) -> Result<()> {
+    let config = Config::load()?;
+    let repo = match store {
+        SyncStore::PerRepo => current_repo()?,
+        SyncStore::Global => {
+            let remote_url = config.sync_global_remote.clone().ok_or_else(|| {
+                anyhow!("The global store is not set up. Run 'lore sync --global setup' first.")
+            })?;
+            let repo = global_store_path()?;
+            ensure_global_repo(&repo, &remote_url)?;
+            repo
+        }
+    };
+    let keystore = KeyStore::with_keychain(config.use_keychain);
+    let (key, _salt) = load_store_credentials(&repo, remote, &keystore)?;
+
+    let fetched = gitref::fetch(&repo, remote, SESSIONS_REF)
+        .with_context(|| format!("Failed to fetch the lore store from '{remote}'"))?;
+    let entries = if fetched.is_some() {
+        gitref::read_tracking_tree(&repo, remote, SESSIONS_REF)?
+    } else {
+        Vec::new()
+    };
+    let id = find_store_session(&entries, prefix)?;
+
+    let mut db = Database::open_default()?;
+    // Children deleted on another machine stay deleted in the pulled copy
+    db.add_tombstones(&read_remote_tombstones(&repo, &entries, &key)?)?;
+    let tally = merge_remote_in_store(store, &mut db, &repo, &entries, &key, strategy, Some(id))?;
+
+    let short_id = id.to_string()[..8].to_string();
+    if tally.pulled > 0 {
+        note!(
+            "{} Pulled session {}.",
+            "Success!".green().bold(),
+            short_id.cyan()
+        );
+    } else if tally.conflicts == 0 {
+        note!("Session {} is already up to date.", short_id.cyan());
+    }
+    print_conflicts(tally.conflicts);
+    Ok(())
+}
+
+/// Returns the ID of the one session in a store tree that starts with
+/// `prefix`.
+fn find_store_session(entries: &[TreeEntry], prefix: &str) -> Result<Uuid> {
+    let matches: BTreeSet<Uuid> = entries
+        .iter()
+        .filter(|entry| is_session_blob(&entry.path))
+        .filter_map(|entry| session_uuid_from_path(&entry.path))
+        .filter(|id| id.to_string().starts_with(&prefix.to_lowercase()))
+        .collect();
+    let mut ids = matches.into_iter();
+    match (ids.next(), ids.next()) {
+        (Some(id), None) => Ok(id),
+        (None, _) => bail!(
+            "No session matching '{prefix}' in the lore store. \
+             Run 'lore sync' to pull every session."
+        ),
+        (Some(_), Some(_)) => bail!(
+            "'{prefix}' matches more than one session in the lore store. \
+             Use a longer prefix."
+        ),
+    }
 }
 
 /// Merges the remote machine registry into the local database.
@@ -925,6 +1256,39 @@ fn add_tombstone_changes(
     Ok(())
 }
 
+/// Reads the set of sessions removed from the store.
+///
+/// Returns an empty set when the store has no exclusion list (no session has
+/// ever been removed, or the store predates `lore sync delete`). A list that
+/// cannot be parsed is an error, so a sync never drops the removals it holds.
+fn read_excluded_sessions(repo: &Path, entries: &[TreeEntry]) -> Result<BTreeSet<Uuid>> {
+    match blob_at_path(repo, entries, EXCLUDED_SESSIONS_PATH)? {
+        Some(bytes) => serde_json::from_slice(&bytes)
+            .with_context(|| format!("Could not parse {EXCLUDED_SESSIONS_PATH} in the lore store")),
+        None => Ok(BTreeSet::new()),
+    }
+}
+
+/// Adds the exclusion list to the outgoing tree when it changed.
+///
+/// Nothing is written when the local set matches the remote one, so an
+/// unchanged list keeps its existing blob. The list is serialized sorted, so
+/// equal sets from different machines produce the same blob.
+fn add_exclusion_changes(
+    repo: &Path,
+    local: &HashSet<Uuid>,
+    remote: &BTreeSet<Uuid>,
+    changes: &mut BTreeMap<String, String>,
+) -> Result<()> {
+    let local: BTreeSet<Uuid> = local.iter().copied().collect();
+    if &local == remote {
+        return Ok(());
+    }
+    let sha = gitref::write_blob(repo, &serde_json::to_vec(&local)?)?;
+    changes.insert(EXCLUDED_SESSIONS_PATH.to_string(), sha);
+    Ok(())
+}
+
 /// Returns whether two tombstone sets hold the same `(child_id, kind)` keys.
 ///
 /// Only the identifying keys are compared (not `deleted_at` or `session_id`) so
@@ -954,10 +1318,264 @@ fn assemble_record(db: &Database, session: &Session) -> Result<SessionRecord> {
     })
 }
 
+// ==================== delete ====================
+
+/// Removes sessions from a lore store and syncs the removal.
+///
+/// Resolves the target sessions (one by id prefix, or every in-scope session
+/// captured on this machine), confirms unless `force`, excludes each from
+/// sync, then runs a full sync of the store so the blobs are dropped from the
+/// pushed tree and the sessions are added to the store's exclusion list
+/// (`EXCLUDED_SESSIONS_PATH`). Local session data is left intact.
+fn run_delete(
+    store: SyncStore,
+    remote: &str,
+    session: Option<&str>,
+    all_this_machine: bool,
+    force: bool,
+) -> Result<()> {
+    // Check the store is set up before recording anything, so an exclusion
+    // is never left waiting on a store that does not exist.
+    match store {
+        SyncStore::PerRepo => {
+            let repo = current_repo()?;
+            if read_store_salt(&repo, remote)?.is_none() {
+                bail!("This repo's lore store is not set up. Run 'lore sync setup' first.");
+            }
+        }
+        SyncStore::Global => {
+            if Config::load()?.sync_global_remote.is_none() {
+                bail!("The global store is not set up. Run 'lore sync --global setup' first.");
+            }
+        }
+    }
+
+    let mut config = Config::load()?;
+    let db = Database::open_default()?;
+
+    let targets = if all_this_machine {
+        let machine = machine_identity(&mut config)?;
+        let scope = match store {
+            SyncStore::PerRepo => db.get_session_ids_for_repo(&current_repo()?)?,
+            SyncStore::Global => db.get_all_session_ids()?,
+        };
+        sessions_for_removal(&db, &scope, &machine.id)?
+    } else {
+        let prefix = session.unwrap_or_default();
+        let session = db.find_session_by_id_prefix(prefix)?.ok_or_else(|| {
+            anyhow!(
+                "No session found matching '{prefix}'. \
+                 Run 'lore sessions' to list available sessions."
+            )
+        })?;
+        if db.sync_excluded_session_ids()?.contains(&session.id) {
+            println!(
+                "Session {} is already removed from the store.",
+                session.id.to_string()[..8].cyan()
+            );
+            return Ok(());
+        }
+        vec![session.id]
+    };
+
+    if targets.is_empty() {
+        println!("No sessions from this machine to remove from the store.");
+        return Ok(());
+    }
+
+    if !force {
+        print!(
+            "Remove {} session(s) from the lore store? Local copies are kept. [y/N] ",
+            targets.len()
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("{}", "Cancelled".dimmed());
+            return Ok(());
+        }
+    }
+
+    let removed = db.exclude_sessions_from_sync(&targets)?;
+    drop(db);
+    note!("Excluded {removed} session(s) from future pushes.");
+
+    match store {
+        SyncStore::PerRepo => run_sync(remote, ConflictStrategy::default()),
+        SyncStore::Global => run_global_sync(ConflictStrategy::default()),
+    }
+}
+
+/// Returns the in-scope sessions captured on `machine_id` that are still in
+/// the store (not already removed), sorted for stable output.
+fn sessions_for_removal(
+    db: &Database,
+    scope: &HashSet<Uuid>,
+    machine_id: &str,
+) -> Result<Vec<Uuid>> {
+    let excluded = db.sync_excluded_session_ids()?;
+    let mut ids: Vec<Uuid> = db
+        .get_session_ids_for_machine(machine_id)?
+        .into_iter()
+        .filter(|id| scope.contains(id) && !excluded.contains(id))
+        .collect();
+    ids.sort();
+    Ok(ids)
+}
+
+// ==================== rotate-key ====================
+
+/// Re-encrypts a lore store under a new passphrase.
+///
+/// Verifies the current passphrase against the stored key, runs a full sync
+/// with the old key so the store holds every session, then hands off to
+/// [`rotate_store_key`].
+fn run_rotate_key(store: SyncStore, remote: &str) -> Result<()> {
+    let mut config = Config::load()?;
+    let repo = match store {
+        SyncStore::PerRepo => current_repo()?,
+        SyncStore::Global => {
+            let remote_url = config.sync_global_remote.clone().ok_or_else(|| {
+                anyhow!("The global store is not set up. Run 'lore sync --global setup' first.")
+            })?;
+            let repo = global_store_path()?;
+            ensure_global_repo(&repo, &remote_url)?;
+            repo
+        }
+    };
+    let machine = machine_identity(&mut config)?;
+    let keystore = KeyStore::with_keychain(config.use_keychain);
+    let (key, salt) = load_store_credentials(&repo, remote, &keystore)?;
+
+    println!("Enter the current passphrase for this lore store.");
+    let old_passphrase = prompt_passphrase()?;
+    if derive_store_key(&old_passphrase, &salt)? != key {
+        bail!("Wrong passphrase: it does not match the key stored on this machine.");
+    }
+
+    println!("Choose a new passphrase.");
+    let new_passphrase = prompt_new_passphrase()?;
+    if new_passphrase == old_passphrase {
+        bail!("The new passphrase must differ from the current one.");
+    }
+
+    let mut db = Database::open_default()?;
+    let sessions = match store {
+        SyncStore::PerRepo => db.get_unsynced_sessions_for_repo(&repo)?,
+        SyncStore::Global => db.get_unsynced_global_sessions()?,
+    };
+    perform_sync_in_store(
+        store,
+        &mut db,
+        &repo,
+        remote,
+        &key,
+        &salt,
+        &machine,
+        sessions,
+        ConflictStrategy::default(),
+    )?;
+
+    let rotated = rotate_store_key(&repo, remote, &keystore, &key, &salt, &new_passphrase)?;
+
+    note!(
+        "{} Re-encrypted {rotated} session(s) under the new passphrase.",
+        "Key rotated.".green().bold()
+    );
+    note!(
+        "Other machines must run 'lore sync{} setup' and enter the new passphrase \
+         before they can sync again.",
+        if store == SyncStore::Global {
+            " --global"
+        } else {
+            ""
+        }
+    );
+    Ok(())
+}
+
+/// Re-encrypts the local store ref with a key derived from `new_passphrase`
+/// and a fresh salt, pushes it, and replaces the stored key.
+///
+/// Every session blob and the tombstone set are decrypted with `old_key` and
+/// re-encrypted before anything is written, so a blob that does not decrypt
+/// aborts the rotation with the store untouched. The new blobs and salt land
+/// in a single commit, so the remote sees either the old store or the fully
+/// rotated one. If the push fails, the local ref is reset to its old commit
+/// and the old key stays in place. Returns the number of sessions rotated.
+fn rotate_store_key(
+    repo: &Path,
+    remote: &str,
+    keystore: &KeyStore,
+    old_key: &[u8],
+    old_salt: &[u8],
+    new_passphrase: &str,
+) -> Result<usize> {
+    let old_commit = gitref::resolve_ref(repo, SESSIONS_REF)?.ok_or_else(|| {
+        anyhow!("This repo's lore store is not set up. Run 'lore sync setup' first.")
+    })?;
+    let entries = gitref::read_tree(repo, &old_commit)?;
+
+    let new_salt = generate_store_salt();
+    let new_key = derive_store_key(new_passphrase, &new_salt)?;
+
+    let mut changes = BTreeMap::new();
+    let mut rotated = 0;
+    for entry in &entries {
+        let blob = if is_session_blob(&entry.path) {
+            let record = decrypt_session_record(&gitref::read_blob(repo, &entry.sha)?, old_key)
+                .with_context(|| {
+                    format!(
+                        "Could not decrypt {} with the current key; the store was not changed",
+                        entry.path
+                    )
+                })?;
+            rotated += 1;
+            encrypt_session_record(&record, &new_key)?
+        } else if entry.path == TOMBSTONES_PATH {
+            let tombstones = decrypt_tombstones(&gitref::read_blob(repo, &entry.sha)?, old_key)
+                .context("Could not decrypt the store's tombstones; the store was not changed")?;
+            encrypt_tombstones(&tombstones, &new_key)?
+        } else {
+            continue;
+        };
+        changes.insert(entry.path.clone(), gitref::write_blob(repo, &blob)?);
+    }
+    changes.insert(
+        "meta/salt".to_string(),
+        gitref::write_blob(repo, &new_salt)?,
+    );
+
+    let tree = gitref::build_tree(repo, Some(&old_commit), &changes)?;
+    let commit = gitref::commit_tree(repo, &tree, Some(&old_commit), "lore: rotate store key")?;
+    gitref::update_ref_checked(repo, SESSIONS_REF, &commit, Some(&old_commit))?;
+
+    if let Err(e) = gitref::push(repo, remote, SESSIONS_REF) {
+        // Put the local ref back so it still matches the key stored here.
+        gitref::update_ref_checked(repo, SESSIONS_REF, &old_commit, Some(&commit))?;
+        return Err(anyhow::Error::from(e)).with_context(|| {
+            format!(
+                "Failed to push the re-encrypted store to '{remote}'. \
+                 The current passphrase is still in effect."
+            )
+        });
+    }
+
+    keystore.store_key(&store_id_from_salt(&new_salt), &new_key)?;
+    if let Err(e) = keystore.delete_key(&store_id_from_salt(old_salt)) {
+        tracing::debug!("Could not delete the old sync key: {e}");
+    }
+
+    Ok(rotated)
+}
+
 // ==================== status ====================
 
 /// Shows sync status for the current repository's lore store.
-fn run_status(remote: &str, format: OutputFormat) -> Result<()> {
+fn run_status(remote: &str, format: OutputFormat, verbose: bool) -> Result<()> {
     let repo = current_repo()?;
     let config = Config::load()?;
     let keystore = KeyStore::with_keychain(config.use_keychain);
@@ -977,9 +1595,17 @@ fn run_status(remote: &str, format: OutputFormat) -> Result<()> {
     let local_ref = gitref::resolve_ref(&repo, SESSIONS_REF)?;
     let tracking_ref =
         gitref::resolve_ref(&repo, &gitref::tracking_ref_name(remote, SESSIONS_REF)?)?;
+    let pending = if verbose {
+        Some(pending_sessions(
+            &db,
+            db.get_unsynced_sessions_for_repo(&repo)?,
+        )?)
+    } else {
+        None
+    };
 
     match format {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Jsonl => {
             let output = StatusOutput {
                 set_up,
                 keyed,
@@ -989,9 +1615,11 @@ fn run_status(remote: &str, format: OutputFormat) -> Result<()> {
                 local_ref: local_ref.clone(),
                 tracking_ref: tracking_ref.clone(),
                 remote: remote.to_string(),
+                pending,
             };
-            println!("{}", serde_json::to_string_pretty(&output)?);
+            println!("{}", format.json_string(&output)?);
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("sync status")),
         OutputFormat::Text | OutputFormat::Markdown => {
             println!("{}", "Lore Sync".bold());
             println!();
@@ -1026,12 +1654,61 @@ fn run_status(remote: &str, format: OutputFormat) -> Result<()> {
                 "  Tracking ref:   {}",
                 tracking_ref.as_deref().unwrap_or("none")
             );
+            if let Some(pending) = &pending {
+                print_pending_sessions(pending);
+            }
         }
     }
 
     Ok(())
 }
 
+/// Describes each pending session, largest encrypted blob first.
+///
+/// The size is computed from the full record the next sync would push, so a
+/// session too big to push comfortably stands out at the top.
+fn pending_sessions(db: &Database, sessions: Vec<Session>) -> Result<Vec<PendingSession>> {
+    let mut pending = sessions
+        .into_iter()
+        .map(|session| {
+            let record = assemble_record(db, &session)?;
+            Ok(PendingSession {
+                encrypted_bytes: encrypted_record_size(&record)?,
+                message_count: record.messages.len() as i32,
+                id: session.id,
+                tool: session.tool,
+                started_at: session.started_at,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    pending.sort_by(|a, b| {
+        b.encrypted_bytes
+            .cmp(&a.encrypted_bytes)
+            .then(a.started_at.cmp(&b.started_at))
+    });
+    Ok(pending)
+}
+
+/// Prints the pending-session list of `lore sync status --verbose`.
+fn print_pending_sessions(pending: &[PendingSession]) {
+    println!();
+    if pending.is_empty() {
+        println!("  {}", "No sessions pending sync.".dimmed());
+        return;
+    }
+    println!("  {}", "Pending sessions:".bold());
+    for session in pending {
+        println!(
+            "    {}  {:>5} messages  {:>10}  {}  {}",
+            session.id.to_string()[..8].cyan(),
+            session.message_count,
+            super::db::format_size(session.encrypted_bytes as u64),
+            session.started_at.format("%Y-%m-%d %H:%M"),
+            session.tool.dimmed()
+        );
+    }
+}
+
 // ==================== global store ====================
 
 /// Sets up the global personal store.
@@ -1057,13 +1734,13 @@ fn run_global_setup() -> Result<()> {
     ensure_global_repo(&repo, &remote_url)?;
     create_or_join_store(&repo, GLOBAL_REMOTE, &mut config)?;
 
-    println!("Run 'lore sync --global' to push your reasoning history.");
+    note!("Run 'lore sync --global' to push your reasoning history.");
     Ok(())
 }
 
 /// Performs a full sync of the global personal store, pushing all unsynced-global
 /// sessions regardless of their working directory.
-fn run_global_sync() -> Result<()> {
+fn run_global_sync(strategy: ConflictStrategy) -> Result<()> {
     let mut config = Config::load()?;
     let remote_url = config.sync_global_remote.clone().ok_or_else(|| {
         anyhow!("The global store is not set up. Run 'lore sync --global setup' first.")
@@ -1088,19 +1765,21 @@ fn run_global_sync() -> Result<()> {
         &salt,
         &machine,
         sessions,
+        strategy,
     )?;
 
-    println!(
+    note!(
         "{} Pulled {}, pushed {}.",
         "Global sync complete.".green().bold(),
         summary.pulled,
         summary.pushed
     );
+    print_conflicts(summary.conflicts);
     Ok(())
 }
 
 /// Shows sync status for the global personal store.
-fn run_global_status(format: OutputFormat) -> Result<()> {
+fn run_global_status(format: OutputFormat, verbose: bool) -> Result<()> {
     let config = Config::load()?;
     let keystore = KeyStore::with_keychain(config.use_keychain);
     let db = Database::open_default()?;
@@ -1136,9 +1815,14 @@ fn run_global_status(format: OutputFormat) -> Result<()> {
     } else {
         (false, None, None)
     };
+    let pending = if verbose {
+        Some(pending_sessions(&db, db.get_unsynced_global_sessions()?)?)
+    } else {
+        None
+    };
 
     match format {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Jsonl => {
             let output = GlobalStatusOutput {
                 set_up,
                 keyed,
@@ -1149,9 +1833,11 @@ fn run_global_status(format: OutputFormat) -> Result<()> {
                 tracking_ref: tracking_ref.clone(),
                 remote: remote_url.clone(),
                 store_path: repo.display().to_string(),
+                pending,
             };
-            println!("{}", serde_json::to_string_pretty(&output)?);
+            println!("{}", format.json_string(&output)?);
         }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("sync status")),
         OutputFormat::Text | OutputFormat::Markdown => {
             println!("{}", "Lore Global Sync".bold());
             println!();
@@ -1190,6 +1876,9 @@ fn run_global_status(format: OutputFormat) -> Result<()> {
                 "  Tracking ref:   {}",
                 tracking_ref.as_deref().unwrap_or("none")
             );
+            if let Some(pending) = &pending {
+                print_pending_sessions(pending);
+            }
         }
     }
 
@@ -1320,6 +2009,11 @@ fn read_store_salt(repo: &Path, remote: &str) -> Result<Option<Vec<u8>>> {
             return Ok(Some(bytes));
         }
     }
+    read_tracking_salt(repo, remote)
+}
+
+/// Reads the store salt from the remote-tracking ref only.
+fn read_tracking_salt(repo: &Path, remote: &str) -> Result<Option<Vec<u8>>> {
     let tracking = gitref::tracking_ref_name(remote, SESSIONS_REF)?;
     if gitref::ref_exists(repo, &tracking)? {
         if let Some(bytes) = read_ref_path(repo, &tracking, "meta/salt")? {
@@ -1419,6 +2113,19 @@ fn session_uuid_from_path(path: &str) -> Option<Uuid> {
     Uuid::parse_str(stem).ok()
 }
 
+/// Returns the tree paths of every artifact belonging to the given sessions.
+fn excluded_session_paths(session_ids: &HashSet<Uuid>) -> BTreeSet<String> {
+    session_ids
+        .iter()
+        .flat_map(|id| {
+            [
+                format!("sessions/{id}.enc"),
+                format!("sessions/{id}.meta.json"),
+            ]
+        })
+        .collect()
+}
+
 /// Carries forward in-scope, local-only session artifacts when rebasing on the
 /// remote.
 ///
@@ -1641,6 +2348,9 @@ mod tests {
             source_path: None,
             message_count: 1,
             machine_id: Some(machine_id.to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
         let message = Message {
             id: Uuid::new_v4(),
@@ -1667,6 +2377,8 @@ mod tests {
             created_at: Utc::now(),
             created_by: LinkCreator::User,
             confidence: Some(0.9),
+            forge: None,
+            factors: None,
         })
         .unwrap();
         db.insert_tag(&Tag {
@@ -1688,6 +2400,7 @@ mod tests {
             session_id: id,
             content: "fixed the parser".to_string(),
             generated_at: Utc::now(),
+            model: None,
         })
         .unwrap();
         id
@@ -1715,6 +2428,41 @@ mod tests {
             .clone()
     }
 
+    #[test]
+    fn test_pending_sessions_largest_first() {
+        let (mut db, _db_dir) = open_db();
+        let small = seed_full_session(&mut db, "m1", "/repo");
+        let large = seed_full_session(&mut db, "m1", "/repo");
+        let message = Message {
+            id: Uuid::new_v4(),
+            session_id: large,
+            parent_id: None,
+            index: 1,
+            timestamp: Utc::now(),
+            role: MessageRole::Assistant,
+            content: MessageContent::Text("x".repeat(4096)),
+            model: None,
+            git_branch: None,
+            cwd: None,
+        };
+        db.insert_message(&message).unwrap();
+
+        let pending = pending_sessions(&db, db.get_unsynced_sessions().unwrap()).unwrap();
+
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].id, large);
+        assert_eq!(pending[0].message_count, 2);
+        assert_eq!(pending[1].id, small);
+        assert_eq!(pending[1].message_count, 1);
+        assert!(pending[0].encrypted_bytes > pending[1].encrypted_bytes);
+
+        let record = assemble_record(&db, &db.get_session(&small).unwrap().unwrap()).unwrap();
+        assert_eq!(
+            pending[1].encrypted_bytes,
+            encrypted_record_size(&record).unwrap()
+        );
+    }
+
     #[test]
     fn test_create_store_writes_salt_and_pushes() {
         let (_remote_dir, remote_url) = init_bare_remote();
@@ -1899,6 +2647,143 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rotate_store_key_reencrypts_and_replaces_key() {
+        let (_remote_dir, remote_url) = init_bare_remote();
+
+        // Machine A creates the store and pushes a session.
+        let dir_a = tempfile::tempdir().unwrap();
+        let repo_a = dir_a.path();
+        init_repo(repo_a);
+        git(repo_a, &["remote", "add", "origin", &remote_url]);
+        let (keystore_a, _ka) = test_keystore();
+        let ma = machine("machine-a", "Machine A");
+        create_store(repo_a, "origin", &keystore_a, &ma, "old passphrase").unwrap();
+        let (mut db_a, _da) = open_db();
+        let session_id = seed_full_session(&mut db_a, "machine-a", &repo_dir(repo_a));
+        let (old_key, old_salt) = load_store_credentials(repo_a, "origin", &keystore_a).unwrap();
+        let sessions_a = scoped_unsynced(&db_a, repo_a);
+        perform_sync(
+            &mut db_a, repo_a, "origin", &old_key, &old_salt, &ma, sessions_a,
+        )
+        .unwrap();
+
+        // Machine B joins with the old passphrase.
+        let dir_b = tempfile::tempdir().unwrap();
+        let repo_b = dir_b.path();
+        init_repo(repo_b);
+        git(repo_b, &["remote", "add", "origin", &remote_url]);
+        let (keystore_b, _kb) = test_keystore();
+        let mb = machine("machine-b", "Machine B");
+        gitref::fetch(repo_b, "origin", SESSIONS_REF).unwrap();
+        join_store(
+            repo_b,
+            "origin",
+            &keystore_b,
+            &mb,
+            &old_salt,
+            "old passphrase",
+        )
+        .unwrap();
+
+        // A syncs first, as `run_rotate_key` does, to pick up B's join.
+        perform_sync(
+            &mut db_a,
+            repo_a,
+            "origin",
+            &old_key,
+            &old_salt,
+            &ma,
+            vec![],
+        )
+        .unwrap();
+        let rotated = rotate_store_key(
+            repo_a,
+            "origin",
+            &keystore_a,
+            &old_key,
+            &old_salt,
+            "new passphrase",
+        )
+        .unwrap();
+        assert_eq!(rotated, 1);
+
+        // A now holds only the key for the new salt.
+        let (new_key, new_salt) = load_store_credentials(repo_a, "origin", &keystore_a).unwrap();
+        assert_ne!(new_salt, old_salt);
+        assert_eq!(
+            new_key,
+            derive_store_key("new passphrase", &new_salt).unwrap()
+        );
+        assert!(keystore_a
+            .load_key(&store_id_from_salt(&old_salt))
+            .unwrap()
+            .is_none());
+        let record = decrypt_session_record(
+            &gitref::read_blob(repo_a, &session_blob_sha(repo_a)).unwrap(),
+            &new_key,
+        )
+        .unwrap();
+        assert_eq!(record.session.id, session_id);
+
+        // B's old key no longer syncs.
+        let (mut db_b, _db) = open_db();
+        let (b_key, b_salt) = load_store_credentials(repo_b, "origin", &keystore_b).unwrap();
+        assert!(perform_sync(&mut db_b, repo_b, "origin", &b_key, &b_salt, &mb, vec![]).is_err());
+
+        // Re-joining reads the new salt from the remote despite B's stale local
+        // ref, and the new passphrase pulls the session.
+        gitref::fetch(repo_b, "origin", SESSIONS_REF).unwrap();
+        let remote_salt = read_tracking_salt(repo_b, "origin").unwrap().unwrap();
+        assert_eq!(remote_salt, new_salt);
+        join_store(
+            repo_b,
+            "origin",
+            &keystore_b,
+            &mb,
+            &remote_salt,
+            "new passphrase",
+        )
+        .unwrap();
+        let (b_key, b_salt) = load_store_credentials(repo_b, "origin", &keystore_b).unwrap();
+        let summary =
+            perform_sync(&mut db_b, repo_b, "origin", &b_key, &b_salt, &mb, vec![]).unwrap();
+        assert_eq!(summary.pulled, 1);
+    }
+
+    #[test]
+    fn test_rotate_store_key_keeps_old_key_when_push_fails() {
+        let (_remote_dir, remote_url) = init_bare_remote();
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        init_repo(repo);
+        git(repo, &["remote", "add", "origin", &remote_url]);
+        let (keystore, _kd) = test_keystore();
+        let m = machine("machine-a", "Machine A");
+        create_store(repo, "origin", &keystore, &m, "old passphrase").unwrap();
+        let (mut db, _dd) = open_db();
+        seed_full_session(&mut db, "machine-a", &repo_dir(repo));
+        let (key, salt) = load_store_credentials(repo, "origin", &keystore).unwrap();
+        let sessions = scoped_unsynced(&db, repo);
+        perform_sync(&mut db, repo, "origin", &key, &salt, &m, sessions).unwrap();
+        let before = gitref::resolve_ref(repo, SESSIONS_REF).unwrap();
+
+        // Make the push fail.
+        git(
+            repo,
+            &["remote", "set-url", "origin", "/nonexistent/remote"],
+        );
+
+        let result = rotate_store_key(repo, "origin", &keystore, &key, &salt, "new passphrase");
+        assert!(result.is_err());
+
+        assert_eq!(gitref::resolve_ref(repo, SESSIONS_REF).unwrap(), before);
+        assert_eq!(
+            load_store_credentials(repo, "origin", &keystore).unwrap(),
+            (key, salt)
+        );
+    }
+
     #[test]
     fn test_merge_skips_older_remote_session() {
         // A remote record that is not newer than the local copy is left as-is.
@@ -1927,6 +2812,9 @@ mod tests {
             source_path: None,
             message_count: 2,
             machine_id: Some("machine-a".to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
         db.import_session_with_messages(&session, &[], Some(Utc::now()))
             .unwrap();
@@ -1954,6 +2842,184 @@ mod tests {
         assert_eq!(db.get_session(&id).unwrap().unwrap().message_count, 2);
     }
 
+    #[test]
+    fn test_read_excluded_sessions_rejects_unparseable_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        init_repo(repo);
+        let entry = |data: &[u8]| {
+            vec![TreeEntry {
+                mode: "100644".to_string(),
+                sha: gitref::write_blob(repo, data).unwrap(),
+                path: EXCLUDED_SESSIONS_PATH.to_string(),
+            }]
+        };
+
+        assert!(read_excluded_sessions(repo, &[]).unwrap().is_empty());
+        let id = Uuid::new_v4();
+        let listed = read_excluded_sessions(repo, &entry(&serde_json::to_vec(&[id]).unwrap()));
+        assert_eq!(listed.unwrap(), BTreeSet::from([id]));
+        assert!(read_excluded_sessions(repo, &entry(b"not json")).is_err());
+    }
+
+    #[test]
+    fn test_merge_pulls_only_the_requested_session() {
+        let (_remote_dir, remote_url) = init_bare_remote();
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        init_repo(repo);
+        git(repo, &["remote", "add", "origin", &remote_url]);
+        let (keystore, _kd) = test_keystore();
+        let m = machine("machine-a", "Machine A");
+        create_store(repo, "origin", &keystore, &m, "passphrase abcdefgh").unwrap();
+        let (key, _salt) = load_store_credentials(repo, "origin", &keystore).unwrap();
+
+        let wanted = Uuid::parse_str("aaaaaaaa-0000-4000-8000-000000000001").unwrap();
+        let other = Uuid::parse_str("abbbbbbb-0000-4000-8000-000000000002").unwrap();
+        let entries: Vec<TreeEntry> = [wanted, other]
+            .into_iter()
+            .map(|id| {
+                let record = SessionRecord {
+                    session: Session {
+                        id,
+                        tool: "claude-code".to_string(),
+                        tool_version: None,
+                        started_at: Utc::now(),
+                        ended_at: Some(Utc::now()),
+                        model: None,
+                        working_directory: "/proj".to_string(),
+                        git_branch: None,
+                        source_path: None,
+                        message_count: 1,
+                        machine_id: Some("machine-b".to_string()),
+                        input_tokens: None,
+                        output_tokens: None,
+                        parent_session_id: None,
+                    },
+                    messages: vec![],
+                    links: vec![],
+                    tags: vec![],
+                    annotations: vec![],
+                    summary: None,
+                };
+                let blob = encrypt_session_record(&record, &key).unwrap();
+                TreeEntry {
+                    mode: "100644".to_string(),
+                    sha: gitref::write_blob(repo, &blob).unwrap(),
+                    path: format!("sessions/{id}.enc"),
+                }
+            })
+            .collect();
+
+        assert_eq!(find_store_session(&entries, "AAAA").unwrap(), wanted);
+        let err = find_store_session(&entries, "a").unwrap_err();
+        assert!(err.to_string().contains("matches more than one session"));
+        let err = find_store_session(&entries, "cccc").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("No session matching 'cccc' in the lore store"));
+
+        let (mut db, _dd) = open_db();
+        let tally = merge_remote_in_store(
+            SyncStore::PerRepo,
+            &mut db,
+            repo,
+            &entries,
+            &key,
+            ConflictStrategy::Newest,
+            Some(wanted),
+        )
+        .unwrap();
+        assert_eq!(tally.pulled, 1);
+        assert!(db.get_session(&wanted).unwrap().is_some());
+        assert!(db.get_session(&other).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_merge_honors_conflict_strategy() {
+        let (_remote_dir, remote_url) = init_bare_remote();
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        init_repo(repo);
+        git(repo, &["remote", "add", "origin", &remote_url]);
+        let (keystore, _kd) = test_keystore();
+        let m = machine("machine-a", "Machine A");
+        create_store(repo, "origin", &keystore, &m, "passphrase abcdefgh").unwrap();
+        let (key, _salt) = load_store_credentials(repo, "origin", &keystore).unwrap();
+
+        let (mut db, _dd) = open_db();
+        let id = Uuid::new_v4();
+        let session = Session {
+            id,
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at: Utc::now(),
+            ended_at: Some(Utc::now()),
+            model: None,
+            working_directory: "/proj".to_string(),
+            git_branch: None,
+            source_path: None,
+            message_count: 2,
+            machine_id: Some("machine-a".to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        };
+        db.import_session_with_messages(&session, &[], Some(Utc::now()))
+            .unwrap();
+
+        // Encrypts a remote copy of the session with the given message count.
+        let remote_entries = |message_count: i32| {
+            let record = SessionRecord {
+                session: Session {
+                    message_count,
+                    ..session.clone()
+                },
+                messages: vec![],
+                links: vec![],
+                tags: vec![],
+                annotations: vec![],
+                summary: None,
+            };
+            let blob = encrypt_session_record(&record, &key).unwrap();
+            vec![TreeEntry {
+                mode: "100644".to_string(),
+                sha: gitref::write_blob(repo, &blob).unwrap(),
+                path: format!("sessions/{id}.enc"),
+            }]
+        };
+        let local_count = |db: &Database| db.get_session(&id).unwrap().unwrap().message_count;
+
+        let newer = remote_entries(3);
+        let merge = |db: &mut Database, entries: &[TreeEntry], strategy| {
+            merge_remote_in_store(SyncStore::PerRepo, db, repo, entries, &key, strategy, None)
+                .unwrap()
+        };
+
+        // Skip keeps the local copy and reports the conflict
+        let tally = merge(&mut db, &newer, ConflictStrategy::Skip);
+        assert_eq!((tally.pulled, tally.conflicts), (0, 1));
+        assert_eq!(local_count(&db), 2);
+
+        // prefer-local keeps the local copy without reporting a conflict
+        let tally = merge(&mut db, &newer, ConflictStrategy::PreferLocal);
+        assert_eq!((tally.pulled, tally.conflicts), (0, 0));
+        assert_eq!(local_count(&db), 2);
+
+        // prefer-remote takes an older remote copy
+        let older = remote_entries(1);
+        let tally = merge(&mut db, &older, ConflictStrategy::PreferRemote);
+        assert_eq!((tally.pulled, tally.conflicts), (1, 0));
+        assert_eq!(local_count(&db), 1);
+
+        // newest takes the newer remote copy, then ignores it once merged
+        let tally = merge(&mut db, &newer, ConflictStrategy::Newest);
+        assert_eq!((tally.pulled, tally.conflicts), (1, 0));
+        assert_eq!(local_count(&db), 3);
+        let tally = merge(&mut db, &newer, ConflictStrategy::Skip);
+        assert_eq!((tally.pulled, tally.conflicts), (0, 0));
+    }
+
     #[test]
     fn test_is_session_blob() {
         assert!(is_session_blob("sessions/abc.enc"));
@@ -2017,6 +3083,8 @@ mod tests {
             created_at: Utc::now(),
             created_by: LinkCreator::Auto,
             confidence: Some(0.8),
+            forge: None,
+            factors: None,
         })
         .unwrap();
 
@@ -2701,6 +3769,7 @@ mod tests {
             &salt_a,
             &ma,
             sessions_a,
+            ConflictStrategy::Newest,
         )
         .unwrap();
         assert_eq!(summary_a.pushed, 2, "global sync pushes all sessions");
@@ -2737,6 +3806,7 @@ mod tests {
             &salt_b2,
             &mb,
             sessions_b,
+            ConflictStrategy::Newest,
         )
         .unwrap();
         assert_eq!(summary_b.pulled, 2, "both sessions must be pulled");
@@ -2787,6 +3857,7 @@ mod tests {
             &salt,
             &m,
             sessions,
+            ConflictStrategy::Newest,
         )
         .unwrap();
         assert_eq!(summary.pushed, 2, "global sync pushes both sessions");
@@ -2864,6 +3935,7 @@ mod tests {
             &salt,
             &m,
             sessions,
+            ConflictStrategy::Newest,
         )
         .unwrap();
 
@@ -2976,6 +4048,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sync_delete_removes_session_from_store() {
+        // Machine A pushes two sessions, then removes one from the store. The
+        // removed session's blobs leave the tree, A keeps its local copy, and a
+        // machine joining afterwards never imports it.
+        let (_remote_dir, remote_url) = init_bare_remote();
+        let passphrase = "shared team passphrase";
+
+        let (_da, repo_a, keystore_a, _ka, ma) = setup_repo_with_store(&remote_url, passphrase);
+        let (mut db_a, _dba) = open_db();
+        let removed = seed_full_session(&mut db_a, "machine-a", &repo_dir(&repo_a));
+        let kept = seed_full_session(&mut db_a, "machine-a", &repo_dir(&repo_a));
+        let (key_a, salt_a) = load_store_credentials(&repo_a, "origin", &keystore_a).unwrap();
+        let sessions_a = scoped_unsynced(&db_a, &repo_a);
+        perform_sync(
+            &mut db_a, &repo_a, "origin", &key_a, &salt_a, &ma, sessions_a,
+        )
+        .unwrap();
+
+        assert_eq!(db_a.exclude_sessions_from_sync(&[removed]).unwrap(), 1);
+        // Excluding twice is a no-op.
+        assert_eq!(db_a.exclude_sessions_from_sync(&[removed]).unwrap(), 0);
+        let sessions_a = scoped_unsynced(&db_a, &repo_a);
+        perform_sync(
+            &mut db_a, &repo_a, "origin", &key_a, &salt_a, &ma, sessions_a,
+        )
+        .unwrap();
+
+        let paths: Vec<String> = gitref::read_tree(&repo_a, SESSIONS_REF)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.path)
+            .collect();
+        assert!(!paths.contains(&format!("sessions/{removed}.enc")));
+        assert!(!paths.contains(&format!("sessions/{removed}.meta.json")));
+        assert!(paths.contains(&format!("sessions/{kept}.enc")));
+        assert!(
+            db_a.get_session(&removed).unwrap().is_some(),
+            "the local copy must be kept"
+        );
+
+        // A local change to the removed session does not put it back.
+        db_a.insert_tag(&Tag {
+            id: Uuid::new_v4(),
+            session_id: removed,
+            label: "later".to_string(),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+        assert!(scoped_unsynced(&db_a, &repo_a)
+            .iter()
+            .all(|s| s.id != removed));
+
+        // Machine B joins after the removal and only receives the kept session.
+        let dir_b = tempfile::tempdir().unwrap();
+        let repo_b = dir_b.path();
+        init_repo(repo_b);
+        git(repo_b, &["remote", "add", "origin", &remote_url]);
+        let (keystore_b, _kb) = test_keystore();
+        let mb = machine("machine-b", "Machine B");
+        gitref::fetch(repo_b, "origin", SESSIONS_REF).unwrap();
+        let salt_b = read_store_salt(repo_b, "origin").unwrap().unwrap();
+        join_store(repo_b, "origin", &keystore_b, &mb, &salt_b, passphrase).unwrap();
+        let (mut db_b, _dbb) = open_db();
+        let (key_b, salt_b2) = load_store_credentials(repo_b, "origin", &keystore_b).unwrap();
+        let sessions_b = scoped_unsynced(&db_b, repo_b);
+        perform_sync(
+            &mut db_b, repo_b, "origin", &key_b, &salt_b2, &mb, sessions_b,
+        )
+        .unwrap();
+
+        assert!(db_b.get_session(&kept).unwrap().is_some());
+        assert!(db_b.get_session(&removed).unwrap().is_none());
+        assert!(db_b.sync_excluded_session_ids().unwrap().contains(&removed));
+    }
+
+    #[test]
+    fn test_sessions_for_removal_scopes_to_machine() {
+        let (mut db, _dir) = open_db();
+        let mine = seed_full_session(&mut db, "machine-a", "/repo");
+        let other = seed_full_session(&mut db, "machine-b", "/repo");
+        let out_of_scope = seed_full_session(&mut db, "machine-a", "/elsewhere");
+        let scope: HashSet<Uuid> = [mine, other].into_iter().collect();
+
+        assert_eq!(
+            sessions_for_removal(&db, &scope, "machine-a").unwrap(),
+            vec![mine]
+        );
+        assert!(!sessions_for_removal(&db, &scope, "machine-a")
+            .unwrap()
+            .contains(&out_of_scope));
+
+        db.exclude_sessions_from_sync(&[mine]).unwrap();
+        assert!(sessions_for_removal(&db, &scope, "machine-a")
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_concurrent_add_survives_remote_deletion() {
         // A deletes link X while B adds a different link Y to the same session.
@@ -3005,6 +4175,7 @@ mod tests {
                 &salt_a,
                 &ma,
                 sessions,
+                ConflictStrategy::Newest,
             )
             .unwrap();
         };
@@ -3039,6 +4210,7 @@ mod tests {
                 &salt_b2,
                 &mb,
                 sessions,
+                ConflictStrategy::Newest,
             )
             .unwrap();
         };
@@ -3062,6 +4234,8 @@ mod tests {
             created_at: Utc::now(),
             created_by: LinkCreator::User,
             confidence: Some(0.9),
+            forge: None,
+            factors: None,
         })
         .unwrap();
         run_b(&mut db_b);
@@ -3218,6 +4392,7 @@ mod tests {
                 &salt_a,
                 &ma,
                 sessions,
+                ConflictStrategy::Newest,
             )
             .unwrap();
         };
@@ -3252,6 +4427,7 @@ mod tests {
                 &salt_b2,
                 &mb,
                 sessions,
+                ConflictStrategy::Newest,
             )
             .unwrap();
         };
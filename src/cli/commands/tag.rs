@@ -4,7 +4,7 @@
 //! can have multiple tags, and the same tag label can be applied to
 //! multiple sessions.
 
-use anyhow::{bail, Result};
+use anyhow::{Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 use uuid::Uuid;
@@ -45,7 +45,7 @@ pub fn run(args: Args) -> Result<()> {
         // Remove the tag
         let deleted = db.delete_tag(&session_id, &args.label)?;
         if deleted {
-            println!(
+            note!(
                 "{}",
                 format!(
                     "Removed tag '{}' from session {}",
@@ -63,7 +63,7 @@ pub fn run(args: Args) -> Result<()> {
     } else {
         // Add the tag
         if db.tag_exists(&session_id, &args.label)? {
-            println!(
+            note!(
                 "{}",
                 format!(
                     "Session {} already has tag '{}'",
@@ -80,7 +80,7 @@ pub fn run(args: Args) -> Result<()> {
                 created_at: Utc::now(),
             };
             db.insert_tag(&tag)?;
-            println!(
+            note!(
                 "{}",
                 format!(
                     "Added tag '{}' to session {}",
@@ -95,29 +95,10 @@ pub fn run(args: Args) -> Result<()> {
     Ok(())
 }
 
-/// Finds a session by ID prefix.
-///
-/// Searches for sessions matching the given ID prefix and returns the full session ID.
+/// Finds a session by ID prefix and returns its full ID.
 fn find_session_by_prefix(db: &Database, id_prefix: &str) -> Result<Uuid> {
-    let sessions = db.list_sessions(100, None)?;
-    let matching: Vec<_> = sessions
-        .iter()
-        .filter(|s| s.id.to_string().starts_with(id_prefix))
-        .collect();
-
-    match matching.len() {
-        0 => bail!("No session found matching '{id_prefix}'"),
-        1 => Ok(matching[0].id),
-        _ => {
-            let ids: Vec<String> = matching
-                .iter()
-                .map(|s| s.id.to_string()[..8].to_string())
-                .collect();
-            bail!(
-                "Ambiguous session prefix '{}'. Matches: {}",
-                id_prefix,
-                ids.join(", ")
-            )
-        }
-    }
+    let session = db
+        .find_session_by_id_prefix(id_prefix)?
+        .with_context(|| format!("No session found matching '{id_prefix}'"))?;
+    Ok(session.id)
 }
@@ -0,0 +1,374 @@
+//! Timeline command - sessions grouped by calendar day.
+//!
+//! Groups recent sessions by the local calendar day they started on and
+//! shows per-day session and message counts along with the repositories
+//! touched. Output is either an indented text tree or a JSON array of
+//! day buckets.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::cli::OutputFormat;
+use crate::storage::{Database, Session};
+
+/// Arguments for the timeline command.
+#[derive(clap::Args)]
+#[command(after_help = "EXAMPLES:\n    \
+    lore timeline                  Recent sessions grouped by day\n    \
+    lore timeline --since 7d       Only the last week\n    \
+    lore timeline --repo .         Only sessions in the current directory\n    \
+    lore timeline --format json    Output day buckets as JSON")]
+pub struct Args {
+    /// Filter to sessions in this directory (prefix match)
+    #[arg(short, long, value_name = "PATH")]
+    #[arg(
+        long_help = "Filter sessions to those with a working directory matching\n\
+        this path prefix. Use '.' for the current directory."
+    )]
+    pub repo: Option<String>,
+
+    /// Only include sessions started on or after this date
+    #[arg(long, value_name = "DATE")]
+    #[arg(
+        long_help = "Only include sessions started on or after this date. Accepts\n\
+        relative formats (7d, 2w, 1m) or an absolute date (YYYY-MM-DD)."
+    )]
+    pub since: Option<String>,
+
+    /// Maximum number of sessions to include
+    #[arg(short, long, default_value = "100", value_name = "N")]
+    pub limit: usize,
+
+    /// Output format: text (default), json
+    #[arg(short, long, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+}
+
+/// All sessions that started on one local calendar day.
+#[derive(Debug, Serialize)]
+struct DayBucket {
+    /// The local date in YYYY-MM-DD format.
+    date: String,
+    /// Number of sessions that started on this day.
+    session_count: usize,
+    /// Total messages across the day's sessions.
+    message_count: i32,
+    /// Repository names touched on this day, sorted alphabetically.
+    repos: Vec<String>,
+    /// The day's sessions, most recent first.
+    sessions: Vec<TimelineSession>,
+}
+
+/// Summary of a single session within a day bucket.
+#[derive(Debug, Serialize)]
+struct TimelineSession {
+    id: String,
+    started_at: DateTime<Utc>,
+    tool: String,
+    message_count: i32,
+    repo: String,
+    working_directory: String,
+    git_branch: Option<String>,
+}
+
+/// Executes the timeline command.
+///
+/// Loads recent sessions, optionally filtered by working directory and start
+/// date, and displays them grouped by local calendar day.
+pub fn run(args: Args) -> Result<()> {
+    let db = Database::open_default()?;
+
+    // Resolve repo path if provided
+    let working_dir = args.repo.map(|r| {
+        if r == "." {
+            std::env::current_dir()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| r)
+        } else {
+            r
+        }
+    });
+
+    let since = args.since.as_deref().map(parse_date).transpose()?;
+
+    let mut sessions = db.list_sessions(args.limit, working_dir.as_deref())?;
+    if let Some(since) = since {
+        sessions.retain(|s| s.started_at >= since);
+    }
+
+    let buckets = group_by_day(&sessions, &Local);
+
+    match args.format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            println!("{}", args.format.json_string(&buckets)?);
+        }
+        OutputFormat::Csv => return Err(OutputFormat::Csv.unsupported("timeline")),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            if buckets.is_empty() {
+                println!("{}", "No sessions found.".dimmed());
+                println!();
+                println!("Run 'lore import' to import sessions from Claude Code.");
+                return Ok(());
+            }
+            print_timeline(&buckets, &Local);
+        }
+    }
+
+    Ok(())
+}
+
+/// Groups sessions into day buckets by their local start date.
+///
+/// Buckets are ordered newest day first, and sessions within a bucket are
+/// ordered most recent first. `tz` selects the calendar used for day
+/// boundaries, so a session just after local midnight lands on the new day.
+fn group_by_day<Tz: chrono::TimeZone>(sessions: &[Session], tz: &Tz) -> Vec<DayBucket> {
+    let mut sorted: Vec<&Session> = sessions.iter().collect();
+    sorted.sort_by_key(|s| std::cmp::Reverse(s.started_at));
+
+    let mut buckets: Vec<DayBucket> = Vec::new();
+    for session in sorted {
+        let date = session
+            .started_at
+            .with_timezone(tz)
+            .date_naive()
+            .format("%Y-%m-%d")
+            .to_string();
+
+        if buckets.last().map(|b| &b.date) != Some(&date) {
+            buckets.push(DayBucket {
+                date,
+                session_count: 0,
+                message_count: 0,
+                repos: Vec::new(),
+                sessions: Vec::new(),
+            });
+        }
+        let bucket = buckets.last_mut().expect("bucket was just pushed");
+
+        let repo = repo_name(&session.working_directory);
+        bucket.session_count += 1;
+        bucket.message_count += session.message_count;
+        bucket.sessions.push(TimelineSession {
+            id: session.id.to_string(),
+            started_at: session.started_at,
+            tool: session.tool.clone(),
+            message_count: session.message_count,
+            repo,
+            working_directory: session.working_directory.clone(),
+            git_branch: session.git_branch.clone(),
+        });
+    }
+
+    for bucket in &mut buckets {
+        let repos: BTreeSet<String> = bucket.sessions.iter().map(|s| s.repo.clone()).collect();
+        bucket.repos = repos.into_iter().collect();
+    }
+
+    buckets
+}
+
+/// Prints day buckets as an indented tree.
+fn print_timeline<Tz: chrono::TimeZone>(buckets: &[DayBucket], tz: &Tz)
+where
+    Tz::Offset: std::fmt::Display,
+{
+    for (i, bucket) in buckets.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let weekday = NaiveDate::parse_from_str(&bucket.date, "%Y-%m-%d")
+            .map(|d| d.format("%a").to_string())
+            .unwrap_or_default();
+        println!(
+            "{} {}  {}  {}",
+            bucket.date.bold(),
+            weekday.dimmed(),
+            format!(
+                "{} {}, {} messages",
+                bucket.session_count,
+                if bucket.session_count == 1 {
+                    "session"
+                } else {
+                    "sessions"
+                },
+                bucket.message_count
+            )
+            .dimmed(),
+            bucket.repos.join(", ").yellow()
+        );
+
+        for session in &bucket.sessions {
+            let time = session.started_at.with_timezone(tz).format("%H:%M");
+            let branch = session
+                .git_branch
+                .as_deref()
+                .map(|b| format!(" ({b})"))
+                .unwrap_or_default();
+            println!(
+                "  {}  {}  {:<12}  {:>5} msgs  {}{}",
+                time.to_string().dimmed(),
+                session.id[..8].cyan(),
+                session.tool,
+                session.message_count,
+                session.repo,
+                branch.dimmed()
+            );
+        }
+    }
+}
+
+/// Returns the last path component of a working directory.
+fn repo_name(working_directory: &str) -> String {
+    working_directory
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or(working_directory)
+        .to_string()
+}
+
+/// Parses a date filter string into a DateTime.
+///
+/// Supports relative formats (7d, 2w, 1m) and absolute (2025-01-15).
+fn parse_date(date_str: &str) -> Result<DateTime<Utc>> {
+    let date_str = date_str.trim().to_lowercase();
+
+    if let Some(days) = date_str.strip_suffix('d') {
+        let days: i64 = days.parse().context("Invalid number of days")?;
+        return Ok(Utc::now() - Duration::days(days));
+    }
+    if let Some(weeks) = date_str.strip_suffix('w') {
+        let weeks: i64 = weeks.parse().context("Invalid number of weeks")?;
+        return Ok(Utc::now() - Duration::weeks(weeks));
+    }
+    if let Some(months) = date_str.strip_suffix('m') {
+        let months: i64 = months.parse().context("Invalid number of months")?;
+        // Approximate months as 30 days
+        return Ok(Utc::now() - Duration::days(months * 30));
+    }
+
+    let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
+        .context("Invalid date format. Use YYYY-MM-DD or relative format like 7d, 2w, 1m")?;
+    let datetime = date
+        .and_hms_opt(0, 0, 0)
+        .ok_or_else(|| anyhow::anyhow!("Failed to create datetime from date {date_str}"))?;
+    Ok(datetime.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone};
+    use uuid::Uuid;
+
+    fn session_at(started_at: DateTime<Utc>, working_directory: &str, messages: i32) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at,
+            ended_at: None,
+            model: None,
+            working_directory: working_directory.to_string(),
+            git_branch: Some("main".to_string()),
+            source_path: None,
+            message_count: messages,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_group_by_day_buckets_and_totals() {
+        let sessions = vec![
+            session_at(
+                Utc.with_ymd_and_hms(2025, 1, 14, 9, 0, 0).unwrap(),
+                "/home/user/api",
+                5,
+            ),
+            session_at(
+                Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap(),
+                "/home/user/lore",
+                10,
+            ),
+            session_at(
+                Utc.with_ymd_and_hms(2025, 1, 15, 16, 0, 0).unwrap(),
+                "/home/user/api",
+                3,
+            ),
+        ];
+
+        let buckets = group_by_day(&sessions, &Utc);
+
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].date, "2025-01-15");
+        assert_eq!(buckets[0].session_count, 2);
+        assert_eq!(buckets[0].message_count, 13);
+        assert_eq!(buckets[0].repos, vec!["api", "lore"]);
+        // Most recent session first within the day
+        assert_eq!(buckets[0].sessions[0].message_count, 3);
+        assert_eq!(buckets[1].date, "2025-01-14");
+        assert_eq!(buckets[1].repos, vec!["api"]);
+    }
+
+    #[test]
+    fn test_group_by_day_uses_local_calendar() {
+        // 23:30 UTC on the 14th is already the 15th at UTC+2.
+        let sessions = vec![session_at(
+            Utc.with_ymd_and_hms(2025, 1, 14, 23, 30, 0).unwrap(),
+            "/repo",
+            1,
+        )];
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+
+        assert_eq!(group_by_day(&sessions, &Utc)[0].date, "2025-01-14");
+        assert_eq!(group_by_day(&sessions, &tz)[0].date, "2025-01-15");
+    }
+
+    #[test]
+    fn test_group_by_day_empty() {
+        assert!(group_by_day(&[], &Utc).is_empty());
+    }
+
+    #[test]
+    fn test_day_bucket_json_shape() {
+        let sessions = vec![session_at(
+            Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap(),
+            "/home/user/lore",
+            4,
+        )];
+        let json = serde_json::to_value(group_by_day(&sessions, &Utc)).unwrap();
+
+        assert_eq!(json[0]["date"], "2025-01-15");
+        assert_eq!(json[0]["session_count"], 1);
+        assert_eq!(json[0]["sessions"][0]["repo"], "lore");
+        assert_eq!(json[0]["sessions"][0]["git_branch"], "main");
+    }
+
+    #[test]
+    fn test_repo_name() {
+        assert_eq!(repo_name("/home/user/lore"), "lore");
+        assert_eq!(repo_name("/home/user/lore/"), "lore");
+        assert_eq!(repo_name("lore"), "lore");
+    }
+
+    #[test]
+    fn test_parse_date_relative_and_absolute() {
+        let week_ago = parse_date("7d").unwrap();
+        assert!((Utc::now() - week_ago).num_days() == 7);
+
+        let date = parse_date("2025-01-15").unwrap();
+        assert_eq!(date, Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap());
+
+        assert!(parse_date("yesterday").is_err());
+    }
+}
@@ -5,7 +5,7 @@
 
 use std::io::{self, Write};
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
 
 use crate::storage::Database;
@@ -48,53 +48,22 @@ pub struct Args {
 pub fn run(args: Args) -> Result<()> {
     let db = Database::open_default()?;
 
-    // Find session by prefix
-    let all_sessions = db.list_sessions(1000, None)?;
-    let matching: Vec<_> = all_sessions
-        .iter()
-        .filter(|s| s.id.to_string().starts_with(&args.session))
-        .collect();
-
-    if matching.is_empty() {
-        if all_sessions.is_empty() {
-            bail!(
-                "No session found matching '{}'. No sessions in database. \
-                 Run 'lore import' to import sessions first.",
-                args.session
-            );
-        } else {
-            bail!(
+    let session = db
+        .find_session_by_id_prefix(&args.session)?
+        .with_context(|| {
+            format!(
                 "No session found matching '{}'. \
                  Run 'lore sessions' to list available sessions.",
                 args.session
-            );
-        }
-    }
-
-    if matching.len() > 1 {
-        println!("{}", "Multiple sessions match that prefix:".yellow());
-        for s in &matching {
-            let id_short = &s.id.to_string()[..8];
-            println!(
-                "  {} - {}",
-                id_short.cyan(),
-                s.started_at.format("%Y-%m-%d %H:%M")
-            );
-        }
-        bail!(
-            "Multiple sessions match '{}'. Please use a more specific prefix from the list above.",
-            args.session
-        );
-    }
-
-    let session = matching[0];
+            )
+        })?;
     let session_short = &session.id.to_string()[..8];
 
     // Get existing links for the session
     let links = db.get_links_by_session(&session.id)?;
 
     if links.is_empty() {
-        println!(
+        note!(
             "{}",
             format!("Session {session_short} has no links to remove").dimmed()
         );
@@ -146,7 +115,7 @@ pub fn run(args: Args) -> Result<()> {
         let deleted = db.delete_link_by_session_and_commit(&session.id, commit_sha)?;
 
         if deleted {
-            println!(
+            note!(
                 "{} session {} from commit {}",
                 "Unlinked".green(),
                 session_short.cyan(),
@@ -189,14 +158,14 @@ pub fn run(args: Args) -> Result<()> {
                 .as_ref()
                 .map_or("unknown", |s| s.as_str());
             let short_sha = &sha[..8.min(sha.len())];
-            println!(
+            note!(
                 "{} session {} from commit {}",
                 "Unlinked".green(),
                 session_short.cyan(),
                 short_sha
             );
         } else {
-            println!(
+            note!(
                 "{} session {} from {} commits",
                 "Unlinked".green(),
                 session_short.cyan(),
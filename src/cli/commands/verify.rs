@@ -0,0 +1,163 @@
+//! Verify command - check the database for corruption and stale records.
+//!
+//! Runs SQLite's integrity check, looks for messages, links, and other
+//! session records whose session no longer exists, and checks whether the
+//! search index is missing entries. With `--fix`, orphaned records are
+//! deleted and the search index is rebuilt. A failed integrity check cannot
+//! be fixed this way and always exits non-zero.
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::storage::db::VerifyReport;
+use crate::storage::Database;
+
+/// Arguments for the verify command.
+#[derive(clap::Args)]
+#[command(after_help = "EXAMPLES:\n    \
+    lore verify          Check the database and report problems\n    \
+    lore verify --fix    Delete orphaned records and rebuild the search index")]
+pub struct Args {
+    /// Delete orphaned records and rebuild a stale search index
+    #[arg(long)]
+    #[arg(
+        long_help = "Repair the problems that can be repaired: delete messages,\n\
+        links, tags, annotations, and summaries whose session no longer\n\
+        exists, and rebuild the search index if it is missing entries.\n\
+        Integrity check failures are reported but cannot be fixed."
+    )]
+    pub fix: bool,
+}
+
+/// Executes the verify command.
+///
+/// Prints one line per check and a pass/fail summary. Exits with status 1
+/// when problems remain, either because `--fix` was not given or because
+/// they cannot be fixed.
+pub fn run(args: Args) -> Result<()> {
+    let mut db = Database::open_default()?;
+    if let Some(path) = db.db_path() {
+        note!("{}", format!("Verifying {}...", path.display()).dimmed());
+    }
+
+    let mut report = db.verify()?;
+    print_checks(&report);
+
+    if args.fix && !report.is_ok() {
+        println!();
+        if !report.orphans.is_empty() {
+            let deleted = db.delete_orphans()?;
+            println!("{} Deleted {deleted} orphaned rows", "Fixed:".green());
+        }
+        if report.search_index_stale {
+            let count = db.rebuild_search_index()?;
+            println!(
+                "{} Rebuilt the search index ({count} messages)",
+                "Fixed:".green()
+            );
+        }
+        report = db.verify()?;
+    }
+
+    println!();
+    let problems = problem_lines(&report);
+    if problems.is_empty() {
+        println!("{} No problems found.", "Passed:".green().bold());
+        return Ok(());
+    }
+
+    println!(
+        "{} {} problem{} found.",
+        "Failed:".red().bold(),
+        problems.len(),
+        if problems.len() == 1 { "" } else { "s" }
+    );
+    for problem in &problems {
+        println!("  - {problem}");
+    }
+    if !report.integrity_errors.is_empty() {
+        println!(
+            "{}",
+            "The database file is damaged. Restore it from a backup, or export \
+             what you can with 'lore export' before re-importing."
+                .yellow()
+        );
+    } else if !args.fix {
+        println!("Run {} to repair them.", "lore verify --fix".cyan());
+    }
+    std::process::exit(1);
+}
+
+/// Prints the outcome of each check.
+fn print_checks(report: &VerifyReport) {
+    let integrity = if report.integrity_errors.is_empty() {
+        "ok".green().to_string()
+    } else {
+        format!("{} errors", report.integrity_errors.len())
+            .red()
+            .to_string()
+    };
+    let orphans = if report.orphans.is_empty() {
+        "none".green().to_string()
+    } else {
+        let total: usize = report.orphans.iter().map(|(_, count)| count).sum();
+        format!("{total} rows").red().to_string()
+    };
+    let index = if report.search_index_stale {
+        "needs rebuild".yellow().to_string()
+    } else {
+        "up to date".green().to_string()
+    };
+
+    println!("  {}  {integrity}", "Integrity check:".bold());
+    println!("  {}    {orphans}", "Orphaned rows:".bold());
+    println!("  {}     {index}", "Search index:".bold());
+}
+
+/// Describes each problem in a report, one line per problem.
+fn problem_lines(report: &VerifyReport) -> Vec<String> {
+    let mut lines: Vec<String> = report
+        .integrity_errors
+        .iter()
+        .map(|error| format!("integrity check: {error}"))
+        .collect();
+    for (table, count) in &report.orphans {
+        lines.push(format!(
+            "{count} {table} {} a missing session",
+            if *count == 1 {
+                "row references"
+            } else {
+                "rows reference"
+            }
+        ));
+    }
+    if report.search_index_stale {
+        lines.push("the search index is missing entries".to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem_lines() {
+        assert!(problem_lines(&VerifyReport::default()).is_empty());
+
+        let report = VerifyReport {
+            integrity_errors: vec!["Page 4 is never used".to_string()],
+            orphans: vec![("session_links", 1), ("messages", 3)],
+            search_index_stale: true,
+        };
+        assert_eq!(
+            problem_lines(&report),
+            vec![
+                "integrity check: Page 4 is never used",
+                "1 session_links row references a missing session",
+                "3 messages rows reference a missing session",
+                "the search index is missing entries",
+            ]
+        );
+    }
+}
@@ -1,9 +1,21 @@
 //! Output formatting utilities for CLI commands.
 //!
 //! Provides a unified `OutputFormat` enum for consistent output formatting
-//! across all CLI commands.
+//! across all CLI commands, the global `--color` choice, and helpers for
+//! formatting numbers shared by several commands.
 
+use std::borrow::Cow;
+use std::io::{ErrorKind, IsTerminal, Write};
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
 use clap::ValueEnum;
+use regex::{Captures, Regex};
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::storage::{ContentBlock, Message, MessageContent, MessageThread, Session};
 
 /// Output format options for CLI commands.
 ///
@@ -11,6 +23,11 @@ use clap::ValueEnum;
 /// - `Text` for human-readable terminal output (default)
 /// - `Json` for machine-readable output and scripting
 /// - `Markdown` for documentation and copy-paste to issues
+/// - `Jsonl` for streaming into line-oriented tools like `jq -c`
+/// - `Csv` for spreadsheets (for sessions and search)
+///
+/// Commands that print a single JSON document print it on one line for
+/// `Jsonl`. Commands without tabular output reject `Csv`.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     /// Human-readable text output (default).
@@ -20,11 +37,370 @@ pub enum OutputFormat {
     Json,
     /// Markdown-formatted output (for show command).
     Markdown,
+    /// Newline-delimited JSON, one object per line.
+    #[value(alias = "json-lines")]
+    Jsonl,
+    /// Comma-separated values with a header row (for sessions and search).
+    Csv,
+}
+
+impl OutputFormat {
+    /// Serializes `value` as JSON: indented for `json`, on a single line
+    /// for `jsonl`.
+    pub fn json_string<T: Serialize + ?Sized>(self, value: &T) -> Result<String> {
+        let json = match self {
+            OutputFormat::Jsonl => serde_json::to_string(value)?,
+            _ => serde_json::to_string_pretty(value)?,
+        };
+        Ok(json)
+    }
+
+    /// Returns the error reported when `command` cannot produce this format.
+    pub fn unsupported(self, command: &str) -> anyhow::Error {
+        let name = self
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default();
+        anyhow::anyhow!("'lore {command}' does not support --format {name}")
+    }
+}
+
+/// When to color text output, chosen with the global `--color` flag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is not set (default).
+    #[default]
+    Auto,
+    /// Always color, even when piped or `NO_COLOR` is set.
+    Always,
+    /// Never color.
+    Never,
+}
+
+impl ColorChoice {
+    /// Returns whether output should be colored.
+    ///
+    /// `no_color` is whether `NO_COLOR` is set to a non-empty value, which
+    /// only `auto` honors.
+    pub fn enabled(self, no_color: bool, stdout_is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color && stdout_is_terminal,
+        }
+    }
+
+    /// Turns `colored` output on or off for the rest of the process.
+    ///
+    /// Returns whether color is enabled.
+    pub fn apply(self) -> bool {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let enabled = self.enabled(no_color, std::io::stdout().is_terminal());
+        colored::control::set_override(enabled);
+        enabled
+    }
+}
+
+/// Formats a relative time string for display (e.g., "2 hours ago").
+///
+/// Times four weeks or more before `now` are shown as a date.
+pub fn format_relative_time(dt: &DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let duration = now.signed_duration_since(*dt);
+
+    if duration.num_minutes() < 1 {
+        "just now".to_string()
+    } else if duration.num_minutes() < 60 {
+        let mins = duration.num_minutes();
+        format!("{} minute{} ago", mins, if mins == 1 { "" } else { "s" })
+    } else if duration.num_hours() < 24 {
+        let hours = duration.num_hours();
+        format!("{} hour{} ago", hours, if hours == 1 { "" } else { "s" })
+    } else if duration.num_days() < 7 {
+        let days = duration.num_days();
+        format!("{} day{} ago", days, if days == 1 { "" } else { "s" })
+    } else if duration.num_weeks() < 4 {
+        let weeks = duration.num_weeks();
+        format!("{} week{} ago", weeks, if weeks == 1 { "" } else { "s" })
+    } else {
+        dt.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Formats a number with comma separators.
+pub fn format_number(n: i64) -> String {
+    let s = n.unsigned_abs().to_string();
+    let mut result = String::new();
+    for (i, c) in s.chars().rev().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            result.push(',');
+        }
+        result.push(c);
+    }
+    if n < 0 {
+        result.push('-');
+    }
+    result.chars().rev().collect()
+}
+
+/// Formats an estimated cost in US dollars, e.g. `~$1.25`.
+pub fn format_cost(cost: Option<f64>) -> String {
+    match cost {
+        Some(cost) if cost > 0.0 && cost < 0.01 => "<$0.01".to_string(),
+        Some(cost) => format!("~${cost:.2}"),
+        None => "n/a".to_string(),
+    }
+}
+
+/// Formats token usage as `12,345 in / 678 out`, or "n/a" when the tool
+/// reported no usage.
+pub fn format_token_usage(input_tokens: Option<i64>, output_tokens: Option<i64>) -> String {
+    if input_tokens.is_none() && output_tokens.is_none() {
+        return "n/a".to_string();
+    }
+    let side = |tokens: Option<i64>| tokens.map_or_else(|| "n/a".to_string(), format_number);
+    format!("{} in / {} out", side(input_tokens), side(output_tokens))
+}
+
+/// Writes values as newline-delimited JSON, flushing after every line.
+///
+/// Each value is written as one compact JSON object followed by `\n`, so a
+/// consumer can process rows as they arrive.
+pub struct JsonLinesWriter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    /// Creates a writer over `out`.
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    /// Writes one value as a line.
+    ///
+    /// Returns `Ok(false)` once the reader has gone away (a broken pipe, as
+    /// with `| head`), so callers can stop producing rows.
+    pub fn write<T: Serialize>(&mut self, value: &T) -> Result<bool> {
+        let result = serde_json::to_writer(&mut self.out, value)
+            .map_err(std::io::Error::from)
+            .and_then(|()| self.out.write_all(b"\n"))
+            .and_then(|()| self.out.flush());
+        match result {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Writes rows as RFC 4180 comma-separated values.
+///
+/// Rows end with `\r\n` and are flushed as they are written. Fields are
+/// quoted only when they need to be, see [`csv_field`].
+pub struct CsvWriter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Creates a writer over `out`.
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    /// Writes one row of fields.
+    ///
+    /// Returns `Ok(false)` once the reader has gone away, like
+    /// [`JsonLinesWriter::write`].
+    pub fn write_row<S: AsRef<str>>(&mut self, fields: &[S]) -> Result<bool> {
+        let line = fields
+            .iter()
+            .map(|field| csv_field(field.as_ref()))
+            .collect::<Vec<_>>()
+            .join(",");
+        let result = self
+            .out
+            .write_all(line.as_bytes())
+            .and_then(|()| self.out.write_all(b"\r\n"))
+            .and_then(|()| self.out.flush());
+        match result {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Escapes one CSV field.
+///
+/// A field containing a comma, double quote, or line break is wrapped in
+/// double quotes, with embedded quotes doubled. Other fields are unchanged.
+pub fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Rewrites home directory paths in displayed output as `~`.
+///
+/// Enabled with `--redact-paths` or the `redact_paths` config key, so that
+/// shared `show`, `sessions`, and `search` output does not leak usernames.
+/// The current user's home directory and any `/home/<user>` or
+/// `/Users/<user>` prefix become `~`. Only output is rewritten; stored
+/// sessions keep their real paths.
+pub struct PathRedactor {
+    pattern: Regex,
+}
+
+impl PathRedactor {
+    /// Creates a redactor that also rewrites `home`, when given.
+    pub fn new(home: Option<&Path>) -> Self {
+        let mut alternatives = Vec::new();
+        if let Some(home) = home.map(|h| h.to_string_lossy()) {
+            let home = home.trim_end_matches('/');
+            if !home.is_empty() {
+                alternatives.push(regex::escape(home));
+            }
+        }
+        alternatives.push(r#"/(?:home|Users)/[^/\s'"`()<>\[\]{}:;,]+"#.to_string());
+        let pattern = Regex::new(&alternatives.join("|")).expect("path pattern is valid");
+        Self { pattern }
+    }
+
+    /// Returns a redactor if `flag` is set or `redact_paths` is enabled in
+    /// the config.
+    pub fn from_settings(flag: bool, config: &Config) -> Option<Self> {
+        (flag || config.redact_paths).then(|| Self::new(dirs::home_dir().as_deref()))
+    }
+
+    /// Returns `text` with every home directory prefix replaced by `~`.
+    ///
+    /// A match only counts at the start of a path, so `/mnt/home/alice` and
+    /// `/home/alice2` (when the home is `/home/alice`) are left alone.
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        self.pattern.replace_all(text, |caps: &Captures| {
+            let whole = caps.get(0).expect("match has a whole-match group");
+            let before = text[..whole.start()].chars().next_back();
+            let after = text[whole.end()..].chars().next();
+            if before.is_some_and(is_path_char)
+                || after.is_some_and(|c| c != '/' && is_path_char(c))
+            {
+                whole.as_str().to_string()
+            } else {
+                "~".to_string()
+            }
+        })
+    }
+
+    /// Redacts `text` in place.
+    pub fn redact_in_place(&self, text: &mut String) {
+        if let Cow::Owned(redacted) = self.redact(text) {
+            *text = redacted;
+        }
+    }
+
+    /// Redacts a session's working directory and source path.
+    pub fn redact_session(&self, session: &mut Session) {
+        self.redact_in_place(&mut session.working_directory);
+        if let Some(source_path) = &mut session.source_path {
+            self.redact_in_place(source_path);
+        }
+    }
+
+    /// Redacts a message's working directory and all text-bearing content,
+    /// including the string values inside tool call inputs.
+    pub fn redact_message(&self, message: &mut Message) {
+        if let Some(cwd) = &mut message.cwd {
+            self.redact_in_place(cwd);
+        }
+        match &mut message.content {
+            MessageContent::Text(text) => self.redact_in_place(text),
+            MessageContent::Blocks(blocks) => {
+                for block in blocks {
+                    match block {
+                        ContentBlock::Text { text } => self.redact_in_place(text),
+                        ContentBlock::Thinking { thinking } => self.redact_in_place(thinking),
+                        ContentBlock::ToolUse { input, .. } => self.redact_json(input),
+                        ContentBlock::ToolResult { content, .. } => self.redact_in_place(content),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Redacts each of `messages` in place.
+    pub fn redact_messages(&self, messages: &mut [Message]) {
+        for message in messages {
+            self.redact_message(message);
+        }
+    }
+
+    /// Redacts every message of a thread and its branches.
+    pub fn redact_thread(&self, thread: &mut MessageThread) {
+        self.redact_messages(&mut thread.messages);
+        for branch in &mut thread.branches {
+            self.redact_thread(branch);
+        }
+    }
+
+    /// Redacts every string value in a JSON tree.
+    fn redact_json(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(s) => self.redact_in_place(s),
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.redact_json(item);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                for item in map.values_mut() {
+                    self.redact_json(item);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns true for characters that can continue a path component.
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '/' | '.' | '_' | '-' | '~')
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_color_choice_enabled() {
+        assert!(ColorChoice::Auto.enabled(false, true));
+        assert!(!ColorChoice::Auto.enabled(false, false));
+        assert!(!ColorChoice::Auto.enabled(true, true));
+        assert!(ColorChoice::Always.enabled(true, false));
+        assert!(!ColorChoice::Never.enabled(false, true));
+    }
+
+    #[test]
+    fn test_format_relative_time_minutes() {
+        let dt = Utc::now() - Duration::minutes(5);
+        let result = format_relative_time(&dt, Utc::now());
+        assert!(result.contains("minute"));
+    }
+
+    #[test]
+    fn test_format_relative_time_hours() {
+        let dt = Utc::now() - Duration::hours(3);
+        let result = format_relative_time(&dt, Utc::now());
+        assert!(result.contains("hour"));
+    }
+
+    #[test]
+    fn test_format_relative_time_days() {
+        let dt = Utc::now() - Duration::days(2);
+        let result = format_relative_time(&dt, Utc::now());
+        assert!(result.contains("day"));
+    }
 
     #[test]
     fn test_output_format_default() {
@@ -46,5 +422,208 @@ mod tests {
             OutputFormat::from_str("markdown", false).unwrap(),
             OutputFormat::Markdown
         );
+        assert_eq!(
+            OutputFormat::from_str("jsonl", false).unwrap(),
+            OutputFormat::Jsonl
+        );
+        assert_eq!(
+            OutputFormat::from_str("json-lines", false).unwrap(),
+            OutputFormat::Jsonl
+        );
+    }
+
+    #[test]
+    fn test_json_string_is_one_line_for_jsonl() {
+        let value = serde_json::json!({"id": "abc", "count": 2});
+        assert_eq!(
+            OutputFormat::Jsonl.json_string(&value).unwrap(),
+            r#"{"count":2,"id":"abc"}"#
+        );
+        assert!(OutputFormat::Json
+            .json_string(&value)
+            .unwrap()
+            .contains('\n'));
+    }
+
+    #[test]
+    fn test_unsupported_names_command_and_format() {
+        let err = OutputFormat::Csv.unsupported("status");
+        assert_eq!(
+            err.to_string(),
+            "'lore status' does not support --format csv"
+        );
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(0), "0");
+        assert_eq!(format_number(999), "999");
+        assert_eq!(format_number(1_234_567), "1,234,567");
+        assert_eq!(format_number(-1200), "-1,200");
+    }
+
+    #[test]
+    fn test_format_token_usage_and_cost() {
+        assert_eq!(
+            format_token_usage(Some(12_345), Some(678)),
+            "12,345 in / 678 out"
+        );
+        assert_eq!(format_token_usage(None, None), "n/a");
+        assert_eq!(format_cost(Some(1.254)), "~$1.25");
+        assert_eq!(format_cost(Some(0.001)), "<$0.01");
+        assert_eq!(format_cost(Some(0.0)), "~$0.00");
+        assert_eq!(format_cost(None), "n/a");
+    }
+
+    #[test]
+    fn test_json_lines_writer_writes_one_object_per_line() {
+        let mut out = Vec::new();
+        let mut writer = JsonLinesWriter::new(&mut out);
+        assert!(writer
+            .write(&serde_json::json!({"id": 1, "text": "a\nb"}))
+            .unwrap());
+        assert!(writer.write(&serde_json::json!({"id": 2})).unwrap());
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for line in lines {
+            serde_json::from_str::<serde_json::Value>(line).unwrap();
+        }
+        assert!(text.ends_with('\n'));
+    }
+
+    /// Splits RFC 4180 text back into rows of fields.
+    fn parse_csv(text: &str) -> Vec<Vec<String>> {
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => row.push(std::mem::take(&mut field)),
+                '\r' if !quoted && chars.peek() == Some(&'\n') => {
+                    chars.next();
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                _ => field.push(c),
+            }
+        }
+        rows
+    }
+
+    #[test]
+    fn test_csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn test_csv_writer_round_trips_commas_and_quotes() {
+        let snippet = "Use \"foo, bar\" here,\r\nthen \"\"baz\"\", done";
+        let mut out = Vec::new();
+        let mut writer = CsvWriter::new(&mut out);
+        assert!(writer.write_row(&["session_id", "snippet"]).unwrap());
+        assert!(writer.write_row(&["abc", snippet]).unwrap());
+        assert!(writer.write_row(&["def", ""]).unwrap());
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            parse_csv(&text),
+            vec![
+                vec!["session_id".to_string(), "snippet".to_string()],
+                vec!["abc".to_string(), snippet.to_string()],
+                vec!["def".to_string(), String::new()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_path_redactor_replaces_home_prefixes() {
+        let redactor = PathRedactor::new(Some(Path::new("/root/")));
+
+        assert_eq!(redactor.redact("/home/alice/proj"), "~/proj");
+        assert_eq!(redactor.redact("/home/alice"), "~");
+        assert_eq!(redactor.redact("/Users/bob/src/app"), "~/src/app");
+        assert_eq!(redactor.redact("/root/work"), "~/work");
+        assert_eq!(
+            redactor.redact("cd '/home/alice/proj' && ls /Users/bob"),
+            "cd '~/proj' && ls ~"
+        );
+
+        // Only whole leading components are replaced
+        assert_eq!(redactor.redact("/mnt/home/alice/x"), "/mnt/home/alice/x");
+        assert_eq!(redactor.redact("/rootfs/etc"), "/rootfs/etc");
+        assert!(matches!(redactor.redact("/srv/app"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_path_redactor_rewrites_sessions_and_messages() {
+        let redactor = PathRedactor::new(None);
+        let mut session = Session {
+            id: uuid::Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at: Utc::now(),
+            ended_at: None,
+            model: None,
+            working_directory: "/home/alice/proj".to_string(),
+            git_branch: None,
+            source_path: Some("/home/alice/.claude/projects/a.jsonl".to_string()),
+            message_count: 1,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        };
+        redactor.redact_session(&mut session);
+        assert_eq!(session.working_directory, "~/proj");
+        assert_eq!(
+            session.source_path.as_deref(),
+            Some("~/.claude/projects/a.jsonl")
+        );
+
+        let mut message = Message {
+            id: uuid::Uuid::new_v4(),
+            session_id: session.id,
+            parent_id: None,
+            index: 0,
+            timestamp: Utc::now(),
+            role: crate::storage::MessageRole::Assistant,
+            content: MessageContent::Blocks(vec![
+                ContentBlock::Text {
+                    text: "Editing /home/alice/proj/main.rs".to_string(),
+                },
+                ContentBlock::ToolUse {
+                    id: "t1".to_string(),
+                    name: "Read".to_string(),
+                    input: serde_json::json!({"file_path": "/home/alice/proj/lib.rs"}),
+                },
+            ]),
+            model: None,
+            git_branch: None,
+            cwd: Some("/home/alice/proj".to_string()),
+        };
+        redactor.redact_message(&mut message);
+        assert_eq!(message.cwd.as_deref(), Some("~/proj"));
+        let MessageContent::Blocks(blocks) = &message.content else {
+            panic!("expected blocks");
+        };
+        assert!(
+            matches!(&blocks[0], ContentBlock::Text { text } if text == "Editing ~/proj/main.rs")
+        );
+        assert!(
+            matches!(&blocks[1], ContentBlock::ToolUse { input, .. } if input["file_path"] == "~/proj/lib.rs")
+        );
     }
 }
@@ -4,10 +4,41 @@
 //! database. Commands include session listing, viewing, linking,
 //! and importing from AI coding tools.
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the global `--quiet` flag was given.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Turns informational output on or off for the rest of the process.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Returns whether informational output is suppressed by `--quiet`.
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Prints a line like `println!`, unless `--quiet` is set.
+///
+/// For progress and confirmation messages only. The data a command was
+/// asked for, warnings, and errors are printed with `println!` or
+/// `eprintln!` so `--quiet` never hides them.
+macro_rules! note {
+    ($($arg:tt)*) => {
+        if !$crate::cli::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+
 /// Individual CLI command implementations.
 pub mod commands;
 
 /// Output formatting utilities.
 pub mod format;
 
-pub use format::OutputFormat;
+/// Aligned table rendering for text output.
+pub mod table;
+
+pub use format::{ColorChoice, OutputFormat};
@@ -0,0 +1,344 @@
+//! Aligned table rendering for text output.
+//!
+//! Column widths are computed from the plain cell text, and colors are
+//! applied only after padding, so columns stay aligned whether or not the
+//! output is colored (colors are disabled by `NO_COLOR`). Columns can be
+//! capped to a maximum width, with overlong cells truncated at the end or
+//! in the middle.
+
+use colored::{ColoredString, Colorize};
+
+/// Horizontal alignment of a column's cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Where an overlong cell is shortened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Truncate {
+    /// Keep the start of the text: `feat/long-bra...`.
+    End,
+    /// Keep both ends of the text, useful for paths: `/home/.../project`.
+    Middle,
+}
+
+/// A table column definition.
+#[derive(Debug, Clone)]
+pub struct Column {
+    header: String,
+    align: Align,
+    max_width: Option<usize>,
+    truncate: Truncate,
+}
+
+impl Column {
+    /// Creates a left-aligned column with no width limit.
+    pub fn new(header: &str) -> Self {
+        Self {
+            header: header.to_string(),
+            align: Align::Left,
+            max_width: None,
+            truncate: Truncate::End,
+        }
+    }
+
+    /// Right-aligns the column, for numbers.
+    pub fn right(mut self) -> Self {
+        self.align = Align::Right;
+        self
+    }
+
+    /// Caps the column at `width` characters, shortening cells as `truncate`.
+    pub fn max_width(mut self, width: usize, truncate: Truncate) -> Self {
+        self.max_width = Some(width);
+        self.truncate = truncate;
+        self
+    }
+}
+
+/// Styling applied to a span of cell text after padding.
+pub type Style = fn(&str) -> ColoredString;
+
+/// One table cell, made of one or more differently styled spans.
+#[derive(Debug, Clone)]
+pub struct Cell {
+    spans: Vec<(String, Option<Style>)>,
+}
+
+impl Cell {
+    /// Creates an unstyled cell.
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            spans: vec![(text.into(), None)],
+        }
+    }
+
+    /// Creates a cell whose text is drawn with `style`.
+    pub fn styled(text: impl Into<String>, style: Style) -> Self {
+        Self {
+            spans: vec![(text.into(), Some(style))],
+        }
+    }
+
+    /// Appends another styled span to the cell.
+    pub fn then(mut self, text: impl Into<String>, style: Style) -> Self {
+        self.spans.push((text.into(), Some(style)));
+        self
+    }
+
+    fn text(&self) -> String {
+        self.spans.iter().map(|(text, _)| text.as_str()).collect()
+    }
+
+    fn width(&self) -> usize {
+        self.spans
+            .iter()
+            .map(|(text, _)| text.chars().count())
+            .sum()
+    }
+
+    /// Renders the cell at most `width` characters wide, without padding.
+    fn render(&self, width: usize, truncate: Truncate) -> String {
+        if self.width() > width {
+            // A shortened cell keeps only the style of its first span
+            let text = match truncate {
+                Truncate::End => truncate_end(&self.text(), width),
+                Truncate::Middle => truncate_middle(&self.text(), width),
+            };
+            return apply(&text, self.spans.first().and_then(|(_, style)| *style));
+        }
+        self.spans
+            .iter()
+            .map(|(text, style)| apply(text, *style))
+            .collect()
+    }
+}
+
+fn apply(text: &str, style: Option<Style>) -> String {
+    match style {
+        Some(style) => style(text).to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// A table of rows rendered with aligned columns.
+#[derive(Debug, Clone)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<Cell>>,
+    truncate: bool,
+}
+
+impl Table {
+    /// Creates an empty table with the given columns.
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+            truncate: true,
+        }
+    }
+
+    /// Ignores column width caps, so no cell is truncated.
+    pub fn no_truncation(mut self) -> Self {
+        self.truncate = false;
+        self
+    }
+
+    /// Adds a row. Missing trailing cells are rendered empty.
+    pub fn add_row(&mut self, row: Vec<Cell>) {
+        self.rows.push(row);
+    }
+
+    /// Returns the rendered width of each column.
+    fn widths(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let content = self
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(Cell::width)
+                    .max()
+                    .unwrap_or(0);
+                let width = content.max(column.header.chars().count());
+                match column.max_width {
+                    Some(max) if self.truncate => width.min(max.max(column.header.chars().count())),
+                    _ => width,
+                }
+            })
+            .collect()
+    }
+
+    /// Renders the header and rows as lines, without trailing whitespace.
+    pub fn render(&self) -> Vec<String> {
+        let widths = self.widths();
+        let last = self.columns.len().saturating_sub(1);
+
+        let header: Vec<String> = self
+            .columns
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (column, &width))| {
+                let shown = column.header.chars().count();
+                let padded = pad(&column.header, shown, width, column.align, i == last);
+                padded.bold().to_string()
+            })
+            .collect();
+
+        let mut lines = vec![header.join("  ")];
+        for row in &self.rows {
+            let cells: Vec<String> = self
+                .columns
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (column, &width))| {
+                    let empty = Cell::plain("");
+                    let cell = row.get(i).unwrap_or(&empty);
+                    let shown = cell.width().min(width);
+                    let rendered = cell.render(width, column.truncate);
+                    pad(&rendered, shown, width, column.align, i == last)
+                })
+                .collect();
+            lines.push(cells.join("  ").trim_end().to_string());
+        }
+        lines
+    }
+
+    /// Prints the table to stdout.
+    pub fn print(&self) {
+        for line in self.render() {
+            println!("{line}");
+        }
+    }
+}
+
+/// Pads `rendered`, whose visible width is `shown`, to `width` characters.
+///
+/// The last left-aligned column is not padded, to avoid trailing spaces.
+fn pad(rendered: &str, shown: usize, width: usize, align: Align, last: bool) -> String {
+    let fill = " ".repeat(width.saturating_sub(shown));
+    match align {
+        Align::Left if last => rendered.to_string(),
+        Align::Left => format!("{rendered}{fill}"),
+        Align::Right => format!("{fill}{rendered}"),
+    }
+}
+
+/// Shortens `s` to `width` characters, ending with "...".
+pub fn truncate_end(s: &str, width: usize) -> String {
+    let len = s.chars().count();
+    if len <= width {
+        return s.to_string();
+    }
+    if width <= 3 {
+        return ".".repeat(width);
+    }
+    let kept: String = s.chars().take(width - 3).collect();
+    format!("{kept}...")
+}
+
+/// Shortens `s` to `width` characters by replacing its middle with "...".
+///
+/// Keeps slightly more of the end than the start, since the end of a path
+/// is usually the most telling part.
+pub fn truncate_middle(s: &str, width: usize) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() <= width {
+        return s.to_string();
+    }
+    if width <= 3 {
+        return ".".repeat(width);
+    }
+    let keep = width - 3;
+    let head = keep / 2;
+    let tail = keep - head;
+    let start: String = chars[..head].iter().collect();
+    let end: String = chars[chars.len() - tail..].iter().collect();
+    format!("{start}...{end}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain_table() -> Table {
+        colored::control::set_override(false);
+        let mut table = Table::new(vec![
+            Column::new("NAME"),
+            Column::new("COUNT").right(),
+            Column::new("PATH").max_width(12, Truncate::Middle),
+        ]);
+        table.add_row(vec![
+            Cell::plain("a"),
+            Cell::plain("5"),
+            Cell::plain("/short"),
+        ]);
+        table.add_row(vec![
+            Cell::plain("longer"),
+            Cell::plain("1200"),
+            Cell::plain("/home/user/projects/lore"),
+        ]);
+        table
+    }
+
+    #[test]
+    fn test_widths_follow_data_and_caps() {
+        let lines = plain_table().render();
+        assert_eq!(
+            lines,
+            vec![
+                "NAME    COUNT  PATH",
+                "a           5  /short",
+                "longer   1200  /hom.../lore",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_truncation_keeps_full_cells() {
+        let lines = plain_table().no_truncation().render();
+        assert_eq!(lines[2], "longer   1200  /home/user/projects/lore");
+    }
+
+    #[test]
+    fn test_styled_spans_do_not_affect_alignment() {
+        colored::control::set_override(false);
+        let mut table = Table::new(vec![Column::new("ID"), Column::new("N").right()]);
+        table.add_row(vec![
+            Cell::styled("abc", |s| s.cyan()).then(" [S]", |s| s.green()),
+            Cell::plain("1"),
+        ]);
+        table.add_row(vec![Cell::styled("def", |s| s.cyan()), Cell::plain("22")]);
+
+        assert_eq!(
+            table.render(),
+            vec!["ID        N", "abc [S]   1", "def      22"]
+        );
+    }
+
+    #[test]
+    fn test_truncate_end() {
+        assert_eq!(truncate_end("hello", 10), "hello");
+        assert_eq!(truncate_end("hello world", 8), "hello...");
+        assert_eq!(truncate_end("hello", 2), "..");
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!(truncate_middle("/a/b", 10), "/a/b");
+        assert_eq!(
+            truncate_middle("/home/user/projects/lore", 12),
+            "/hom.../lore"
+        );
+        assert_eq!(truncate_middle("/home/user/projects/lore", 12).len(), 12);
+        assert_eq!(truncate_middle("ñandú/ñandú", 7), "ña...dú");
+        assert_eq!(truncate_middle("abcdef", 3), "...");
+    }
+}
@@ -2,34 +2,127 @@
 //!
 //! Handles loading and saving Lore configuration from `~/.lore/config.yaml`.
 //!
+//! Every settable key can also be overridden for a single process with a
+//! `LORE_<KEY>` environment variable: the key in upper case with `.`
+//! replaced by `_`, such as `LORE_AUTO_LINK_THRESHOLD=0.8` or
+//! `LORE_DAEMON_DEBOUNCE_MS=0`. Precedence is environment, then config file,
+//! then built-in default. Overrides are parsed and validated like
+//! `lore config set`, and are never written back to the file.
+//!
 //! Note: Configuration options are planned for a future release. Currently
 //! this module provides path information only. The Config struct and its
 //! methods are preserved for future use.
 
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+use crate::storage::db::SearchTokenizer;
+use crate::summarize::provider::parse_base_url;
+use crate::summarize::TruncationStrategy;
+
+/// Schema version of the config file written by this release.
+///
+/// Bump it when a release renames a key or changes what a stored value
+/// means, and add the upgrade step to `Config::migrate`.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Top-level config keys this release understands.
+///
+/// Any other top-level key is kept in [`Config::unknown`] so that saving
+/// does not drop settings written by a newer release.
+const KNOWN_KEYS: &[&str] = &[
+    "version",
+    "watchers",
+    "auto_link",
+    "auto_link_threshold",
+    "auto_link_audit",
+    "commit_footer",
+    "git_remotes",
+    "machine_id",
+    "machine_name",
+    "encryption_salt",
+    "use_keychain",
+    "summary_provider",
+    "summary_api_key_anthropic",
+    "summary_api_key_openai",
+    "summary_api_key_openrouter",
+    "summary_api_key_gemini",
+    "summary_api_key_openai_compatible",
+    "summary_model",
+    "summary_model_anthropic",
+    "summary_model_openai",
+    "summary_model_openrouter",
+    "summary_model_gemini",
+    "summary_auto",
+    "summary_auto_threshold",
+    "summary_max_attempts",
+    "summary_truncation",
+    "summary_base_url",
+    "sync_global_remote",
+    "redact_secrets",
+    "redact_patterns",
+    "redact_paths",
+    "compress_content",
+    "search_tokenizer",
+    "token_rates",
+    "daemon",
+];
+
+/// A problem found in a config file by [`Config::validate_file`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigProblem {
+    /// The key the problem concerns, dotted for nested keys (`daemon.debounce_ms`).
+    /// `None` when the file as a whole could not be parsed.
+    pub key: Option<String>,
+    /// 1-based line number in the file, when it could be located.
+    pub line: Option<usize>,
+    /// What is wrong.
+    pub message: String,
+}
+
 /// Lore configuration settings.
 ///
 /// Controls watcher behavior, auto-linking, and commit integration.
 /// Loaded from `~/.lore/config.yaml` when available.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Config {
+    /// Schema version of the config file.
+    ///
+    /// Files written before versioning have no `version` key and load as
+    /// version 0; loading migrates them and saving writes [`CONFIG_VERSION`].
+    #[serde(default)]
+    pub version: u32,
+
     /// List of enabled watcher names (e.g., "claude-code", "cursor").
+    #[serde(default = "default_watchers")]
     pub watchers: Vec<String>,
 
     /// Whether to automatically link sessions to commits.
+    #[serde(default)]
     pub auto_link: bool,
 
     /// Minimum confidence score (0.0-1.0) required for auto-linking.
+    #[serde(default = "default_auto_link_threshold")]
     pub auto_link_threshold: f64,
 
+    /// Whether to record every auto-link decision, with its sub-scores, in
+    /// the database's link_decisions table.
+    #[serde(default)]
+    pub auto_link_audit: bool,
+
     /// Whether to append session references to commit messages.
+    #[serde(default)]
     pub commit_footer: bool,
 
+    /// Git remote names to read a repository's remote URL from, in order of
+    /// preference. When none of them exists, the first remote found is used.
+    #[serde(default = "default_git_remotes")]
+    pub git_remotes: Vec<String>,
+
     /// Unique machine identifier (UUID) for sync deduplication.
     ///
     /// Auto-generated on first access via `get_or_create_machine_id()`.
@@ -57,7 +150,8 @@ pub struct Config {
     #[serde(default)]
     pub use_keychain: bool,
 
-    /// LLM provider for summary generation ("anthropic", "openai", "openrouter").
+    /// LLM provider for summary generation ("anthropic", "openai", "openrouter",
+    /// "gemini", or "openai-compatible").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary_provider: Option<String>,
 
@@ -73,6 +167,20 @@ pub struct Config {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary_api_key_openrouter: Option<String>,
 
+    /// API key for Google Gemini summary provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary_api_key_gemini: Option<String>,
+
+    /// API key for an OpenAI-compatible summary server, sent as a bearer
+    /// token. Optional, since local servers usually need none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary_api_key_openai_compatible: Option<String>,
+
+    /// Model for summaries, used when no provider-specific model override
+    /// is set. Required for the "openai-compatible" provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary_model: Option<String>,
+
     /// Model override for Anthropic summary provider.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary_model_anthropic: Option<String>,
@@ -85,6 +193,10 @@ pub struct Config {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary_model_openrouter: Option<String>,
 
+    /// Model override for Google Gemini summary provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary_model_gemini: Option<String>,
+
     /// Whether to automatically generate summaries when sessions end.
     #[serde(default)]
     pub summary_auto: bool,
@@ -93,6 +205,20 @@ pub struct Config {
     #[serde(default = "default_summary_auto_threshold")]
     pub summary_auto_threshold: usize,
 
+    /// Maximum attempts per summary request when the provider returns 429 or 5xx.
+    #[serde(default = "default_summary_max_attempts")]
+    pub summary_max_attempts: u32,
+
+    /// How transcripts over the summary length limit are shortened ("head",
+    /// "tail", "head-and-tail", or "middle-out"). Defaults to "head-and-tail".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary_truncation: Option<String>,
+
+    /// API base URL to send summary requests to instead of the provider's
+    /// official host, such as an internal LLM gateway.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary_base_url: Option<String>,
+
     /// Remote URL of the user's private global personal store repository.
     ///
     /// The global store (`lore sync --global`) is a managed git repo at
@@ -101,15 +227,148 @@ pub struct Config {
     /// personal multi-machine backup and search.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sync_global_remote: Option<String>,
+
+    /// Whether to replace detected secrets with `[REDACTED]` before storing
+    /// imported message content.
+    #[serde(default)]
+    pub redact_secrets: bool,
+
+    /// Extra regex patterns redacted alongside the built-in secret patterns
+    /// when `redact_secrets` is enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redact_patterns: Vec<String>,
+
+    /// Whether `show`, `sessions`, and `search` display home directory
+    /// paths as `~` by default, as if `--redact-paths` were given.
+    #[serde(default)]
+    pub redact_paths: bool,
+
+    /// Whether to compress large message content in the database.
+    ///
+    /// Existing rows are left as they are; `lore db compact` compresses them.
+    #[serde(default)]
+    pub compress_content: bool,
+
+    /// FTS5 tokenizer for the message search index ("porter", "unicode61",
+    /// or "trigram"). Defaults to "porter".
+    ///
+    /// The existing index keeps its tokenizer; run `lore db reindex` after
+    /// changing this setting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_tokenizer: Option<String>,
+
+    /// Per-model token prices used for session cost estimates, keyed by
+    /// model name or model name prefix.
+    ///
+    /// Entries override or extend the built-in rates. Set them under
+    /// `token_rates:` in config.yaml, e.g. `claude-opus-4: {input: 15.0,
+    /// output: 75.0}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub token_rates: BTreeMap<String, TokenRate>,
+
+    /// Background daemon settings, stored under `daemon:` in config.yaml.
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+
+    /// Top-level keys this release does not recognize, such as settings
+    /// added by a newer release. Filled in by `load_from_path` and written
+    /// back unchanged on save.
+    #[serde(flatten, skip_deserializing)]
+    pub unknown: BTreeMap<String, serde_json::Value>,
+
+    /// Keys overridden by environment variables, with the serialized value
+    /// each had before the override (null when unset). Saving writes these
+    /// values instead, so overrides never end up in config.yaml.
+    #[serde(skip)]
+    pub(crate) env_overrides: BTreeMap<String, serde_json::Value>,
+}
+
+/// Settings for the background capture daemon.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DaemonConfig {
+    /// Quiet period in milliseconds before a changed session file is parsed.
+    ///
+    /// File events for the same path that arrive within this window are
+    /// collapsed into a single parse.
+    #[serde(default = "default_daemon_debounce_ms")]
+    pub debounce_ms: u64,
+
+    /// Directories to watch for session files in addition to those of the
+    /// registered watchers, such as an archive of old sessions.
+    ///
+    /// Files under these directories are parsed by the first watcher that
+    /// recognizes them. Directories that do not exist are skipped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_watch_paths: Vec<PathBuf>,
+
+    /// Age in days after which the daemon deletes sessions.
+    ///
+    /// Unset by default, which keeps sessions forever. Sessions that have
+    /// not been pushed to a sync store in use yet are kept regardless.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_days: Option<u32>,
+}
+
+/// Price of a model's tokens in US dollars per million tokens.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct TokenRate {
+    /// Price per million input (prompt) tokens.
+    pub input: f64,
+    /// Price per million output (completion) tokens.
+    pub output: f64,
+}
+
+impl TokenRate {
+    /// Returns the estimated cost in US dollars of the given token counts.
+    pub fn cost(&self, input_tokens: i64, output_tokens: i64) -> f64 {
+        (input_tokens as f64 * self.input + output_tokens as f64 * self.output) / 1_000_000.0
+    }
+}
+
+/// Built-in token rates, matched by model name prefix.
+///
+/// Prompt tokens read from a cache are not counted in a session's input
+/// tokens, since they are billed at a fraction of the input rate, so
+/// estimates for tools that cache heavily are a slight underestimate.
+const BUILTIN_TOKEN_RATES: &[(&str, f64, f64)] = &[
+    ("claude-opus-4-5", 5.0, 25.0),
+    ("claude-opus-4", 15.0, 75.0),
+    ("claude-sonnet-4", 3.0, 15.0),
+    ("claude-3-7-sonnet", 3.0, 15.0),
+    ("claude-3-5-sonnet", 3.0, 15.0),
+    ("claude-haiku-4-5", 1.0, 5.0),
+    ("claude-3-5-haiku", 0.8, 4.0),
+    ("gpt-5", 1.25, 10.0),
+    ("gpt-4.1-mini", 0.4, 1.6),
+    ("gpt-4.1", 2.0, 8.0),
+    ("gpt-4o-mini", 0.15, 0.6),
+    ("gpt-4o", 2.5, 10.0),
+    ("gemini-2.5-pro", 1.25, 10.0),
+    ("gemini-2.5-flash", 0.3, 2.5),
+    ("gemini-1.5-pro", 1.25, 5.0),
+    ("gemini-1.5-flash", 0.075, 0.3),
+];
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: default_daemon_debounce_ms(),
+            extra_watch_paths: Vec::new(),
+            retention_days: None,
+        }
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            watchers: vec!["claude-code".to_string()],
+            version: CONFIG_VERSION,
+            watchers: default_watchers(),
             auto_link: false,
-            auto_link_threshold: 0.7,
+            auto_link_threshold: default_auto_link_threshold(),
+            auto_link_audit: false,
             commit_footer: false,
+            git_remotes: default_git_remotes(),
             machine_id: None,
             machine_name: None,
             encryption_salt: None,
@@ -118,23 +377,82 @@ impl Default for Config {
             summary_api_key_anthropic: None,
             summary_api_key_openai: None,
             summary_api_key_openrouter: None,
+            summary_api_key_gemini: None,
+            summary_api_key_openai_compatible: None,
+            summary_model: None,
             summary_model_anthropic: None,
             summary_model_openai: None,
             summary_model_openrouter: None,
+            summary_model_gemini: None,
             summary_auto: false,
             summary_auto_threshold: 4,
+            summary_max_attempts: 4,
+            summary_truncation: None,
+            summary_base_url: None,
             sync_global_remote: None,
+            redact_secrets: false,
+            redact_patterns: Vec::new(),
+            redact_paths: false,
+            compress_content: false,
+            search_tokenizer: None,
+            token_rates: BTreeMap::new(),
+            daemon: DaemonConfig::default(),
+            unknown: BTreeMap::new(),
+            env_overrides: BTreeMap::new(),
         }
     }
 }
 
 impl Config {
-    /// Loads configuration from the default config file.
+    /// Loads configuration from the default config file, then applies
+    /// `LORE_<KEY>` environment overrides.
     ///
-    /// Returns default configuration if the file does not exist.
+    /// Returns default configuration (plus overrides) if the file does not
+    /// exist. Returns an error if an override has an invalid value.
     pub fn load() -> Result<Self> {
         let path = Self::config_path()?;
-        Self::load_from_path(&path)
+        let mut config = Self::load_from_path(&path)?;
+        config.apply_env_overrides(|name| std::env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// Returns the environment variable that overrides a config key.
+    pub fn env_var_name(key: &str) -> String {
+        format!("LORE_{}", key.to_uppercase().replace('.', "_"))
+    }
+
+    /// Applies environment overrides for every settable key, reading each
+    /// variable with `lookup`.
+    ///
+    /// Values are parsed and validated by [`Config::set`]. Empty values are
+    /// ignored, like unset variables.
+    fn apply_env_overrides(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<()> {
+        let keys = Self::valid_keys()
+            .iter()
+            .filter(|key| !matches!(**key, "machine_id" | "encryption_salt"));
+        for key in keys {
+            let name = Self::env_var_name(key);
+            let Some(value) = lookup(&name).filter(|v| !v.is_empty()) else {
+                continue;
+            };
+
+            let serialized = serde_json::to_value(&*self).context("Failed to serialize config")?;
+            let original = json_field(&serialized, key)
+                .cloned()
+                .unwrap_or(serde_json::Value::Null);
+            self.set(key, &value)
+                .with_context(|| format!("Invalid value in environment variable {name}"))?;
+            self.env_overrides
+                .entry(key.to_string())
+                .or_insert(original);
+        }
+        Ok(())
+    }
+
+    /// Returns the keys whose current values come from environment
+    /// variables.
+    pub fn env_overridden_keys(&self) -> impl Iterator<Item = &str> {
+        self.env_overrides.keys().map(String::as_str)
     }
 
     /// Saves configuration to the default config file.
@@ -147,7 +465,10 @@ impl Config {
 
     /// Loads configuration from a specific path.
     ///
-    /// Returns default configuration if the file does not exist.
+    /// Returns default configuration if the file does not exist. Files from
+    /// an older schema version are migrated in memory; keys this release
+    /// does not recognize are kept in [`Config::unknown`], and a file from a
+    /// newer release loads with a warning.
     pub fn load_from_path(path: &Path) -> Result<Self> {
         if !path.exists() {
             return Ok(Self::default());
@@ -160,15 +481,51 @@ impl Config {
             return Ok(Self::default());
         }
 
-        let config: Config = serde_saphyr::from_str(&content)
+        let mut config: Config = serde_saphyr::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        let raw: BTreeMap<String, serde_json::Value> = serde_saphyr::from_str(&content)
             .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
 
+        config.unknown = raw
+            .into_iter()
+            .filter(|(key, _)| !KNOWN_KEYS.contains(&key.as_str()))
+            .collect();
+
+        if config.version > CONFIG_VERSION {
+            tracing::warn!(
+                "Config file {} is version {}, newer than this release supports ({}); \
+                 unrecognized settings are preserved but ignored",
+                path.display(),
+                config.version,
+                CONFIG_VERSION
+            );
+        } else {
+            config.migrate();
+        }
+
         Ok(config)
     }
 
+    /// Upgrades a config loaded from an older schema version to
+    /// [`CONFIG_VERSION`], one version at a time.
+    fn migrate(&mut self) {
+        while self.version < CONFIG_VERSION {
+            match self.version {
+                // Version 0 predates the `version` key. Its files could omit
+                // keys that later became optional; serde fills those with
+                // their defaults, so only the version needs recording.
+                0 => {}
+                _ => unreachable!("no migration from config version {}", self.version),
+            }
+            self.version += 1;
+        }
+    }
+
     /// Saves configuration to a specific path.
     ///
-    /// Creates parent directories if they do not exist.
+    /// Creates parent directories if they do not exist. The file records the
+    /// config's schema version, which is [`CONFIG_VERSION`] unless the config
+    /// was loaded from a newer release's file.
     pub fn save_to_path(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).with_context(|| {
@@ -176,7 +533,12 @@ impl Config {
             })?;
         }
 
-        let content = serde_saphyr::to_string(self).context("Failed to serialize config")?;
+        let content = if self.env_overrides.is_empty() {
+            serde_saphyr::to_string(self)
+        } else {
+            serde_saphyr::to_string(&self.without_env_overrides()?)
+        }
+        .context("Failed to serialize config")?;
 
         fs::write(path, content)
             .with_context(|| format!("Failed to write config file: {}", path.display()))?;
@@ -184,6 +546,29 @@ impl Config {
         Ok(())
     }
 
+    /// Returns a copy of the config with environment overrides replaced by
+    /// the values they overrode.
+    fn without_env_overrides(&self) -> Result<Config> {
+        let mut value = serde_json::to_value(self).context("Failed to serialize config")?;
+        for (key, original) in &self.env_overrides {
+            let (parent, field) = key.rsplit_once('.').unwrap_or(("", key));
+            let object = if parent.is_empty() {
+                Some(&mut value)
+            } else {
+                parent
+                    .split('.')
+                    .try_fold(&mut value, |v, part| v.get_mut(part))
+            };
+            if let Some(serde_json::Value::Object(map)) = object {
+                map.insert(field.to_string(), original.clone());
+            }
+        }
+        let mut config: Config =
+            serde_json::from_value(value).context("Failed to serialize config")?;
+        config.unknown = self.unknown.clone();
+        Ok(config)
+    }
+
     /// Returns the machine UUID, generating and saving a new one if needed.
     ///
     /// If no machine_id exists in config, generates a new UUIDv4 and saves
@@ -193,10 +578,21 @@ impl Config {
         if let Some(ref id) = self.machine_id {
             return Ok(id.clone());
         }
+        self.get_or_create_machine_id_in(&Self::config_path()?)
+    }
+
+    /// Returns the machine UUID, saving a newly generated one to `path`.
+    ///
+    /// Like [`Config::get_or_create_machine_id`], but for a config file
+    /// outside the default location.
+    pub fn get_or_create_machine_id_in(&mut self, path: &Path) -> Result<String> {
+        if let Some(ref id) = self.machine_id {
+            return Ok(id.clone());
+        }
 
         let id = Uuid::new_v4().to_string();
         self.machine_id = Some(id.clone());
-        self.save()?;
+        self.save_to_path(path)?;
         Ok(id)
     }
 
@@ -209,10 +605,7 @@ impl Config {
             return name.clone();
         }
 
-        hostname::get()
-            .ok()
-            .and_then(|h| h.into_string().ok())
-            .unwrap_or_else(|| "unknown".to_string())
+        system_hostname().unwrap_or_else(|| "unknown".to_string())
     }
 
     /// Sets a custom machine name and saves the configuration.
@@ -222,6 +615,7 @@ impl Config {
     /// machine created a session.
     pub fn set_machine_name(&mut self, name: &str) -> Result<()> {
         self.machine_name = Some(name.to_string());
+        self.env_overrides.remove("machine_name");
         self.save()
     }
 
@@ -231,7 +625,9 @@ impl Config {
     /// - `watchers` - comma-separated list of enabled watchers
     /// - `auto_link` - "true" or "false"
     /// - `auto_link_threshold` - float between 0.0 and 1.0
+    /// - `auto_link_audit` - "true" or "false"
     /// - `commit_footer` - "true" or "false"
+    /// - `git_remotes` - comma-separated list of preferred git remote names
     /// - `machine_id` - the machine UUID (read-only, auto-generated)
     /// - `machine_name` - human-readable machine name
     /// - `encryption_salt` - salt for encryption key derivation (read-only)
@@ -239,12 +635,27 @@ impl Config {
     /// - `summary_api_key_anthropic` - Anthropic API key
     /// - `summary_api_key_openai` - OpenAI API key
     /// - `summary_api_key_openrouter` - OpenRouter API key
+    /// - `summary_api_key_gemini` - Google Gemini API key
+    /// - `summary_api_key_openai_compatible` - OpenAI-compatible server API key
+    /// - `summary_model` - model used when no provider-specific override is set
     /// - `summary_model_anthropic` - Anthropic model override
     /// - `summary_model_openai` - OpenAI model override
     /// - `summary_model_openrouter` - OpenRouter model override
+    /// - `summary_model_gemini` - Google Gemini model override
     /// - `summary_auto` - "true" or "false"
     /// - `summary_auto_threshold` - minimum messages for auto-summary
+    /// - `summary_max_attempts` - maximum attempts per summary request
+    /// - `summary_truncation` - transcript truncation strategy for summaries
+    /// - `summary_base_url` - API base URL replacing the provider's host
     /// - `sync_global_remote` - remote URL of the global personal store repo
+    /// - `redact_secrets` - "true" or "false"
+    /// - `redact_patterns` - extra redaction regexes, one per line
+    /// - `redact_paths` - "true" or "false"
+    /// - `compress_content` - "true" or "false"
+    /// - `search_tokenizer` - FTS5 tokenizer for the message search index
+    /// - `daemon.debounce_ms` - watcher debounce window in milliseconds
+    /// - `daemon.extra_watch_paths` - comma-separated extra directories to watch
+    /// - `daemon.retention_days` - age in days after which sessions are pruned
     ///
     /// Returns `None` if the key is not recognized.
     pub fn get(&self, key: &str) -> Option<String> {
@@ -252,7 +663,9 @@ impl Config {
             "watchers" => Some(self.watchers.join(",")),
             "auto_link" => Some(self.auto_link.to_string()),
             "auto_link_threshold" => Some(self.auto_link_threshold.to_string()),
+            "auto_link_audit" => Some(self.auto_link_audit.to_string()),
             "commit_footer" => Some(self.commit_footer.to_string()),
+            "git_remotes" => Some(self.git_remotes.join(",")),
             "machine_id" => self.machine_id.clone(),
             "machine_name" => Some(self.get_machine_name()),
             "encryption_salt" => self.encryption_salt.clone(),
@@ -261,12 +674,42 @@ impl Config {
             "summary_api_key_anthropic" => self.summary_api_key_anthropic.clone(),
             "summary_api_key_openai" => self.summary_api_key_openai.clone(),
             "summary_api_key_openrouter" => self.summary_api_key_openrouter.clone(),
+            "summary_api_key_gemini" => self.summary_api_key_gemini.clone(),
+            "summary_api_key_openai_compatible" => self.summary_api_key_openai_compatible.clone(),
+            "summary_model" => self.summary_model.clone(),
             "summary_model_anthropic" => self.summary_model_anthropic.clone(),
             "summary_model_openai" => self.summary_model_openai.clone(),
             "summary_model_openrouter" => self.summary_model_openrouter.clone(),
+            "summary_model_gemini" => self.summary_model_gemini.clone(),
             "summary_auto" => Some(self.summary_auto.to_string()),
             "summary_auto_threshold" => Some(self.summary_auto_threshold.to_string()),
+            "summary_max_attempts" => Some(self.summary_max_attempts.to_string()),
+            "summary_truncation" => Some(
+                self.summary_truncation
+                    .clone()
+                    .unwrap_or_else(|| TruncationStrategy::default().to_string()),
+            ),
+            "summary_base_url" => self.summary_base_url.clone(),
             "sync_global_remote" => self.sync_global_remote.clone(),
+            "redact_secrets" => Some(self.redact_secrets.to_string()),
+            "redact_paths" => Some(self.redact_paths.to_string()),
+            "compress_content" => Some(self.compress_content.to_string()),
+            "search_tokenizer" => Some(
+                self.search_tokenizer
+                    .clone()
+                    .unwrap_or_else(|| SearchTokenizer::default().to_string()),
+            ),
+            "redact_patterns" => Some(self.redact_patterns.join("\n")),
+            "daemon.debounce_ms" => Some(self.daemon.debounce_ms.to_string()),
+            "daemon.extra_watch_paths" => Some(
+                self.daemon
+                    .extra_watch_paths
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            "daemon.retention_days" => self.daemon.retention_days.map(|d| d.to_string()),
             _ => None,
         }
     }
@@ -277,23 +720,53 @@ impl Config {
     /// - `watchers` - comma-separated list of enabled watchers
     /// - `auto_link` - "true" or "false"
     /// - `auto_link_threshold` - float between 0.0 and 1.0 (inclusive)
+    /// - `auto_link_audit` - "true" or "false"
     /// - `commit_footer` - "true" or "false"
+    /// - `git_remotes` - comma-separated list of remote names, tried in order
     /// - `machine_name` - human-readable machine name
-    /// - `summary_provider` - "anthropic", "openai", or "openrouter"
+    /// - `summary_provider` - "anthropic", "openai", "openrouter", "gemini", or
+    ///   "openai-compatible"
     /// - `summary_api_key_anthropic` - Anthropic API key
     /// - `summary_api_key_openai` - OpenAI API key
     /// - `summary_api_key_openrouter` - OpenRouter API key
+    /// - `summary_api_key_gemini` - Google Gemini API key
+    /// - `summary_api_key_openai_compatible` - OpenAI-compatible server API key
+    /// - `summary_model` - model used when no provider-specific override is set
     /// - `summary_model_anthropic` - Anthropic model override
     /// - `summary_model_openai` - OpenAI model override
     /// - `summary_model_openrouter` - OpenRouter model override
+    /// - `summary_model_gemini` - Google Gemini model override
     /// - `summary_auto` - "true" or "false"
     /// - `summary_auto_threshold` - positive integer
+    /// - `summary_max_attempts` - positive integer
+    /// - `summary_truncation` - "head", "tail", "head-and-tail", or "middle-out"
+    /// - `summary_base_url` - an http or https URL, or empty to use the
+    ///   provider's official host
     /// - `sync_global_remote` - remote URL of the global personal store repo
+    /// - `redact_secrets` - "true" or "false"
+    /// - `redact_patterns` - a single extra redaction regex, or empty to clear;
+    ///   list several under `redact_patterns` in config.yaml
+    /// - `compress_content` - "true" or "false"
+    /// - `search_tokenizer` - "porter", "unicode61", or "trigram"; takes
+    ///   effect after `lore db reindex`
+    /// - `daemon.debounce_ms` - non-negative integer (0 disables debouncing)
+    /// - `daemon.extra_watch_paths` - comma-separated list of directories
+    /// - `daemon.retention_days` - positive number of days
     ///
     /// Note: `machine_id` and `encryption_salt` cannot be set manually.
     ///
+    /// A key set this way is saved even if it was overridden by an
+    /// environment variable when loaded.
+    ///
     /// Returns an error if the key is not recognized or the value is invalid.
     pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        self.set_value(key, value)?;
+        self.env_overrides.remove(key);
+        Ok(())
+    }
+
+    /// Parses, validates, and stores a value for `set`.
+    fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
         match key {
             "watchers" => {
                 self.watchers = value
@@ -315,10 +788,21 @@ impl Config {
                 }
                 self.auto_link_threshold = threshold;
             }
+            "auto_link_audit" => {
+                self.auto_link_audit = parse_bool(value)
+                    .with_context(|| format!("Invalid value for auto_link_audit: '{value}'"))?;
+            }
             "commit_footer" => {
                 self.commit_footer = parse_bool(value)
                     .with_context(|| format!("Invalid value for commit_footer: '{value}'"))?;
             }
+            "git_remotes" => {
+                self.git_remotes = value
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+            }
             "machine_name" => {
                 self.machine_name = Some(value.to_string());
             }
@@ -336,13 +820,14 @@ impl Config {
             "summary_provider" => {
                 let lower = value.to_lowercase();
                 match lower.as_str() {
-                    "anthropic" | "openai" | "openrouter" => {
+                    "anthropic" | "openai" | "openrouter" | "gemini" | "openai-compatible" => {
                         self.summary_provider = Some(lower);
                     }
                     _ => {
                         bail!(
                             "Invalid summary_provider: '{value}'. \
-                             Must be one of: anthropic, openai, openrouter"
+                             Must be one of: anthropic, openai, openrouter, gemini, \
+                             openai-compatible"
                         );
                     }
                 }
@@ -356,6 +841,15 @@ impl Config {
             "summary_api_key_openrouter" => {
                 self.summary_api_key_openrouter = Some(value.to_string());
             }
+            "summary_api_key_gemini" => {
+                self.summary_api_key_gemini = Some(value.to_string());
+            }
+            "summary_api_key_openai_compatible" => {
+                self.summary_api_key_openai_compatible = Some(value.to_string());
+            }
+            "summary_model" => {
+                self.summary_model = Some(value.to_string());
+            }
             "summary_model_anthropic" => {
                 self.summary_model_anthropic = Some(value.to_string());
             }
@@ -365,6 +859,9 @@ impl Config {
             "summary_model_openrouter" => {
                 self.summary_model_openrouter = Some(value.to_string());
             }
+            "summary_model_gemini" => {
+                self.summary_model_gemini = Some(value.to_string());
+            }
             "summary_auto" => {
                 self.summary_auto = parse_bool(value)
                     .with_context(|| format!("Invalid value for summary_auto: '{value}'"))?;
@@ -378,13 +875,162 @@ impl Config {
                 }
                 self.summary_auto_threshold = threshold;
             }
+            "summary_max_attempts" => {
+                let attempts: u32 = value.parse().with_context(|| {
+                    format!("Invalid value for summary_max_attempts: '{value}'")
+                })?;
+                if attempts == 0 {
+                    bail!("summary_max_attempts must be greater than 0, got {attempts}");
+                }
+                self.summary_max_attempts = attempts;
+            }
+            "summary_truncation" => {
+                let strategy: TruncationStrategy = value.parse().map_err(|e: String| {
+                    anyhow::anyhow!("Invalid value for summary_truncation: {e}")
+                })?;
+                self.summary_truncation = Some(strategy.to_string());
+            }
+            "summary_base_url" => {
+                if value.trim().is_empty() {
+                    self.summary_base_url = None;
+                } else {
+                    let url = parse_base_url(value)
+                        .map_err(|e| anyhow::anyhow!("Invalid value for summary_base_url: {e}"))?;
+                    self.summary_base_url = Some(url);
+                }
+            }
             "sync_global_remote" => {
                 self.sync_global_remote = Some(value.to_string());
             }
+            "redact_secrets" => {
+                self.redact_secrets = parse_bool(value)
+                    .with_context(|| format!("Invalid value for redact_secrets: '{value}'"))?;
+            }
+            "redact_paths" => {
+                self.redact_paths = parse_bool(value)
+                    .with_context(|| format!("Invalid value for redact_paths: '{value}'"))?;
+            }
+            "compress_content" => {
+                self.compress_content = parse_bool(value)
+                    .with_context(|| format!("Invalid value for compress_content: '{value}'"))?;
+            }
+            "search_tokenizer" => {
+                let tokenizer: SearchTokenizer = value.parse().map_err(|e: String| {
+                    anyhow::anyhow!("Invalid value for search_tokenizer: {e}")
+                })?;
+                self.search_tokenizer = Some(tokenizer.to_string());
+            }
+            "redact_patterns" => {
+                let value = value.trim();
+                if value.is_empty() {
+                    self.redact_patterns.clear();
+                } else {
+                    regex::Regex::new(value)
+                        .with_context(|| format!("Invalid regex for redact_patterns: '{value}'"))?;
+                    self.redact_patterns = vec![value.to_string()];
+                }
+            }
+            "daemon.debounce_ms" => {
+                self.daemon.debounce_ms = value
+                    .parse()
+                    .with_context(|| format!("Invalid value for daemon.debounce_ms: '{value}'"))?;
+            }
+            "daemon.extra_watch_paths" => {
+                self.daemon.extra_watch_paths = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(PathBuf::from)
+                    .collect();
+            }
+            "daemon.retention_days" => {
+                let days: u32 = value.parse().with_context(|| {
+                    format!("Invalid value for daemon.retention_days: '{value}'")
+                })?;
+                if days == 0 {
+                    bail!("daemon.retention_days must be at least 1");
+                }
+                self.daemon.retention_days = Some(days);
+            }
+            _ => {
+                bail!("Unknown configuration key: '{key}'");
+            }
+        }
+        Ok(())
+    }
+
+    /// Resets a configuration key to its default value.
+    ///
+    /// Accepts the same keys as [`Config::set`]. Optional keys are cleared,
+    /// and `watchers` goes back to the default watcher list. Like `set`, this
+    /// replaces any environment override of the key when saved.
+    ///
+    /// Returns an error if the key is not recognized or is read-only.
+    pub fn unset(&mut self, key: &str) -> Result<()> {
+        let default = Self::default();
+        match key {
+            "watchers" => self.watchers = default.watchers,
+            "auto_link" => self.auto_link = default.auto_link,
+            "auto_link_threshold" => self.auto_link_threshold = default.auto_link_threshold,
+            "auto_link_audit" => self.auto_link_audit = default.auto_link_audit,
+            "commit_footer" => self.commit_footer = default.commit_footer,
+            "git_remotes" => self.git_remotes = default.git_remotes,
+            "machine_name" => self.machine_name = default.machine_name,
+            "use_keychain" => self.use_keychain = default.use_keychain,
+            "summary_provider" => self.summary_provider = default.summary_provider,
+            "summary_api_key_anthropic" => {
+                self.summary_api_key_anthropic = default.summary_api_key_anthropic
+            }
+            "summary_api_key_openai" => {
+                self.summary_api_key_openai = default.summary_api_key_openai
+            }
+            "summary_api_key_openrouter" => {
+                self.summary_api_key_openrouter = default.summary_api_key_openrouter
+            }
+            "summary_api_key_gemini" => {
+                self.summary_api_key_gemini = default.summary_api_key_gemini
+            }
+            "summary_api_key_openai_compatible" => {
+                self.summary_api_key_openai_compatible = default.summary_api_key_openai_compatible
+            }
+            "summary_model" => self.summary_model = default.summary_model,
+            "summary_model_anthropic" => {
+                self.summary_model_anthropic = default.summary_model_anthropic
+            }
+            "summary_model_openai" => self.summary_model_openai = default.summary_model_openai,
+            "summary_model_openrouter" => {
+                self.summary_model_openrouter = default.summary_model_openrouter
+            }
+            "summary_model_gemini" => self.summary_model_gemini = default.summary_model_gemini,
+            "summary_auto" => self.summary_auto = default.summary_auto,
+            "summary_auto_threshold" => {
+                self.summary_auto_threshold = default.summary_auto_threshold
+            }
+            "summary_max_attempts" => self.summary_max_attempts = default.summary_max_attempts,
+            "summary_truncation" => self.summary_truncation = default.summary_truncation,
+            "summary_base_url" => self.summary_base_url = default.summary_base_url,
+            "sync_global_remote" => self.sync_global_remote = default.sync_global_remote,
+            "redact_secrets" => self.redact_secrets = default.redact_secrets,
+            "compress_content" => self.compress_content = default.compress_content,
+            "search_tokenizer" => self.search_tokenizer = default.search_tokenizer,
+            "redact_patterns" => self.redact_patterns = default.redact_patterns,
+            "redact_paths" => self.redact_paths = default.redact_paths,
+            "daemon.debounce_ms" => self.daemon.debounce_ms = default.daemon.debounce_ms,
+            "daemon.extra_watch_paths" => {
+                self.daemon.extra_watch_paths = default.daemon.extra_watch_paths
+            }
+            "daemon.retention_days" => self.daemon.retention_days = default.daemon.retention_days,
+            "machine_id" => {
+                bail!("machine_id cannot be unset; it is auto-generated");
+            }
+            "encryption_salt" => {
+                bail!("encryption_salt cannot be unset; it is auto-generated");
+            }
             _ => {
                 bail!("Unknown configuration key: '{key}'");
             }
         }
+        self.env_overrides.remove(key);
         Ok(())
     }
 
@@ -405,7 +1051,9 @@ impl Config {
             "watchers",
             "auto_link",
             "auto_link_threshold",
+            "auto_link_audit",
             "commit_footer",
+            "git_remotes",
             "machine_id",
             "machine_name",
             "encryption_salt",
@@ -414,34 +1062,275 @@ impl Config {
             "summary_api_key_anthropic",
             "summary_api_key_openai",
             "summary_api_key_openrouter",
+            "summary_api_key_gemini",
+            "summary_api_key_openai_compatible",
+            "summary_model",
             "summary_model_anthropic",
             "summary_model_openai",
             "summary_model_openrouter",
+            "summary_model_gemini",
             "summary_auto",
             "summary_auto_threshold",
+            "summary_max_attempts",
+            "summary_truncation",
+            "summary_base_url",
             "sync_global_remote",
+            "redact_secrets",
+            "redact_patterns",
+            "redact_paths",
+            "compress_content",
+            "search_tokenizer",
+            "daemon.debounce_ms",
+            "daemon.extra_watch_paths",
+            "daemon.retention_days",
         ]
     }
 
+    /// Checks a config file for problems without loading it.
+    ///
+    /// Reports YAML syntax errors, values of the wrong type, keys that are not
+    /// in [`Config::valid_keys`] (typos are otherwise kept silently as unknown
+    /// keys), out-of-range values, and watcher names not in `watcher_names`.
+    /// Problems carry the line of the offending key where it can be found.
+    ///
+    /// A missing or empty file has no problems. Returns an error only if the
+    /// file exists but cannot be read.
+    pub fn validate_file(path: &Path, watcher_names: &[&str]) -> Result<Vec<ConfigProblem>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let raw: BTreeMap<String, serde_json::Value> = match serde_saphyr::from_str(&content) {
+            Ok(raw) => raw,
+            Err(e) => return Ok(vec![parse_problem("Invalid YAML", &e.to_string())]),
+        };
+
+        let valid_keys = Self::valid_keys();
+        let is_valid = |key: &str| valid_keys.contains(&key) || KNOWN_KEYS.contains(&key);
+        let is_section = |key: &str| {
+            let prefix = format!("{key}.");
+            valid_keys.iter().any(|valid| valid.starts_with(&prefix))
+        };
+        let mut problems = Vec::new();
+        let mut problem = |key: String, message: String| {
+            let line = problem_line(&content, &key, &message);
+            problems.push(ConfigProblem {
+                key: Some(key),
+                line,
+                message,
+            });
+        };
+
+        for (key, value) in &raw {
+            match value.as_object() {
+                // Nested sections (`daemon:`) are checked key by key
+                Some(section) if is_section(key) => {
+                    for nested in section.keys() {
+                        let dotted = format!("{key}.{nested}");
+                        if !valid_keys.contains(&dotted.as_str()) {
+                            problem(dotted, "unknown key".to_string());
+                        }
+                    }
+                }
+                _ if !is_valid(key) => problem(key.clone(), "unknown key".to_string()),
+                _ => {}
+            }
+        }
+
+        let config: Config = match serde_saphyr::from_str(&content) {
+            Ok(config) => config,
+            Err(e) => {
+                problems.push(parse_problem("Invalid value", &e.to_string()));
+                return Ok(problems);
+            }
+        };
+
+        for (key, message) in config.value_problems(watcher_names) {
+            problem(key, message);
+        }
+        Ok(problems)
+    }
+
+    /// Returns `(key, message)` pairs for values `set` would have rejected.
+    fn value_problems(&self, watcher_names: &[&str]) -> Vec<(String, String)> {
+        let mut problems = Vec::new();
+
+        for watcher in &self.watchers {
+            if !watcher_names.contains(&watcher.as_str()) {
+                problems.push((
+                    "watchers".to_string(),
+                    format!(
+                        "unknown watcher '{watcher}' (known: {})",
+                        watcher_names.join(", ")
+                    ),
+                ));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.auto_link_threshold) {
+            problems.push((
+                "auto_link_threshold".to_string(),
+                format!(
+                    "must be between 0.0 and 1.0, got {}",
+                    self.auto_link_threshold
+                ),
+            ));
+        }
+        if let Some(provider) = &self.summary_provider {
+            if !matches!(
+                provider.as_str(),
+                "anthropic" | "openai" | "openrouter" | "gemini" | "openai-compatible"
+            ) {
+                problems.push((
+                    "summary_provider".to_string(),
+                    format!(
+                        "unknown provider '{provider}' \
+                         (must be one of: anthropic, openai, openrouter, gemini, \
+                         openai-compatible)"
+                    ),
+                ));
+            }
+        }
+        if self.summary_auto_threshold == 0 {
+            problems.push((
+                "summary_auto_threshold".to_string(),
+                "must be greater than 0".to_string(),
+            ));
+        }
+        if self.summary_max_attempts == 0 {
+            problems.push((
+                "summary_max_attempts".to_string(),
+                "must be greater than 0".to_string(),
+            ));
+        }
+        if let Some(strategy) = &self.summary_truncation {
+            if strategy.parse::<TruncationStrategy>().is_err() {
+                problems.push((
+                    "summary_truncation".to_string(),
+                    format!(
+                        "unknown strategy '{strategy}' (must be one of: {})",
+                        TruncationStrategy::NAMES.join(", ")
+                    ),
+                ));
+            }
+        }
+        if let Some(tokenizer) = &self.search_tokenizer {
+            if tokenizer.parse::<SearchTokenizer>().is_err() {
+                problems.push((
+                    "search_tokenizer".to_string(),
+                    format!(
+                        "unknown tokenizer '{tokenizer}' (must be one of: {})",
+                        SearchTokenizer::NAMES.join(", ")
+                    ),
+                ));
+            }
+        }
+        if let Some(url) = &self.summary_base_url {
+            if let Err(e) = parse_base_url(url) {
+                problems.push(("summary_base_url".to_string(), e));
+            }
+        }
+        for pattern in &self.redact_patterns {
+            if let Err(e) = regex::Regex::new(pattern) {
+                // Regex errors span several lines; the last one says what is wrong
+                let error = e.to_string();
+                let reason = error.lines().last().unwrap_or_default().to_string();
+                problems.push((
+                    "redact_patterns".to_string(),
+                    format!("invalid regex '{pattern}': {reason}"),
+                ));
+            }
+        }
+        for (model, rate) in &self.token_rates {
+            if rate.input < 0.0 || rate.output < 0.0 {
+                problems.push((
+                    "token_rates".to_string(),
+                    format!("rates for '{model}' must not be negative"),
+                ));
+            }
+        }
+
+        problems
+    }
+
     /// Returns the API key for the given summary provider.
     pub fn summary_api_key_for_provider(&self, provider: &str) -> Option<String> {
         match provider {
             "anthropic" => self.summary_api_key_anthropic.clone(),
             "openai" => self.summary_api_key_openai.clone(),
             "openrouter" => self.summary_api_key_openrouter.clone(),
+            "gemini" => self.summary_api_key_gemini.clone(),
+            "openai-compatible" => self.summary_api_key_openai_compatible.clone(),
             _ => None,
         }
     }
 
-    /// Returns the model override for the given summary provider.
+    /// Returns the model override for the given summary provider, falling
+    /// back to `summary_model`.
     pub fn summary_model_for_provider(&self, provider: &str) -> Option<String> {
-        match provider {
+        let specific = match provider {
             "anthropic" => self.summary_model_anthropic.clone(),
             "openai" => self.summary_model_openai.clone(),
             "openrouter" => self.summary_model_openrouter.clone(),
+            "gemini" => self.summary_model_gemini.clone(),
             _ => None,
-        }
+        };
+        specific.or_else(|| self.summary_model.clone())
     }
+
+    /// Returns the token rate for a model.
+    ///
+    /// Configured `token_rates` are searched before the built-in rates; in
+    /// each, the longest key that prefixes the model name wins. A provider
+    /// prefix such as `anthropic/` (OpenRouter model names) is ignored.
+    pub fn token_rate_for(&self, model: &str) -> Option<TokenRate> {
+        let model = model.rsplit('/').next().unwrap_or(model);
+        let configured = self
+            .token_rates
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, rate)| *rate);
+        configured.or_else(|| {
+            BUILTIN_TOKEN_RATES
+                .iter()
+                .filter(|(prefix, _, _)| model.starts_with(prefix))
+                .max_by_key(|(prefix, _, _)| prefix.len())
+                .map(|&(_, input, output)| TokenRate { input, output })
+        })
+    }
+
+    /// Estimates the cost in US dollars of a model's token usage.
+    ///
+    /// Returns `None` if the model is unknown or has no rate.
+    pub fn estimate_cost(
+        &self,
+        model: Option<&str>,
+        input_tokens: i64,
+        output_tokens: i64,
+    ) -> Option<f64> {
+        let rate = self.token_rate_for(model?)?;
+        Some(rate.cost(input_tokens, output_tokens))
+    }
+}
+
+/// Returns the default list of enabled watchers.
+fn default_watchers() -> Vec<String> {
+    vec!["claude-code".to_string()]
+}
+
+/// Returns the default git remote names.
+fn default_git_remotes() -> Vec<String> {
+    vec!["origin".to_string()]
+}
+
+/// Returns the default minimum confidence for auto-linking.
+fn default_auto_link_threshold() -> f64 {
+    0.7
 }
 
 /// Returns the default minimum message count for auto-summary generation.
@@ -449,6 +1338,86 @@ fn default_summary_auto_threshold() -> usize {
     4
 }
 
+/// Returns the default maximum attempts per summary request.
+fn default_summary_max_attempts() -> u32 {
+    4
+}
+
+/// Returns the default daemon watcher debounce window in milliseconds.
+fn default_daemon_debounce_ms() -> u64 {
+    500
+}
+
+/// Builds a whole-file problem from a YAML error, keeping the error's line.
+fn parse_problem(kind: &str, error: &str) -> ConfigProblem {
+    let line = error
+        .split_once("at line ")
+        .and_then(|(_, rest)| rest.split(|c: char| !c.is_ascii_digit()).next())
+        .and_then(|digits| digits.parse().ok());
+    ConfigProblem {
+        key: None,
+        line,
+        message: format!("{kind}: {error}"),
+    }
+}
+
+/// Returns the line a problem with `key` points at.
+///
+/// A problem naming a quoted value (`unknown watcher 'cursr'`) points at the
+/// first line from the key onwards that mentions the value, so list entries
+/// are located individually; other problems point at the key.
+fn problem_line(content: &str, key: &str, message: &str) -> Option<usize> {
+    let start = key_line(content, key)?;
+    let value = message.split('\'').nth(1).filter(|value| !value.is_empty());
+    value
+        .and_then(|value| {
+            content
+                .lines()
+                .enumerate()
+                .skip(start - 1)
+                .find(|(_, line)| line.contains(value))
+                .map(|(index, _)| index + 1)
+        })
+        .or(Some(start))
+}
+
+/// Returns the 1-based line on which `key` is defined in `content`.
+///
+/// A dotted key (`daemon.debounce_ms`) is looked up as an indented key
+/// below its section. Only block-style YAML keys are found.
+fn key_line(content: &str, key: &str) -> Option<usize> {
+    let (section, nested) = match key.split_once('.') {
+        Some((section, nested)) => (section, Some(nested)),
+        None => (key, None),
+    };
+    let defines = |line: &str, key: &str| {
+        line.strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with(':'))
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.iter().position(|line| defines(line, section))?;
+    let Some(nested) = nested else {
+        return Some(start + 1);
+    };
+    lines[start + 1..]
+        .iter()
+        .take_while(|line| line.is_empty() || line.starts_with([' ', '\t', '#']))
+        .position(|line| defines(line.trim_start(), nested))
+        .map(|offset| start + offset + 2)
+}
+
+/// Returns the value of a dotted config key, such as `daemon.debounce_ms`,
+/// in a serialized config.
+fn json_field<'a>(value: &'a serde_json::Value, key: &str) -> Option<&'a serde_json::Value> {
+    key.split('.').try_fold(value, |v, part| v.get(part))
+}
+
+/// Returns this machine's hostname, if it can be determined.
+pub fn system_hostname() -> Option<String> {
+    hostname::get().ok().and_then(|h| h.into_string().ok())
+}
+
 /// Parses a boolean value from a string.
 ///
 /// Accepts "true", "false", "1", "0", "yes", "no" (case-insensitive).
@@ -526,6 +1495,124 @@ mod tests {
         assert_eq!(config, Config::default());
     }
 
+    #[test]
+    fn test_load_v0_config_migrates() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+
+        // A pre-versioning file that sets only some keys
+        fs::write(&path, "auto_link: true\nmachine_name: laptop\n").unwrap();
+
+        let config = Config::load_from_path(&path).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(config.auto_link);
+        assert_eq!(config.machine_name, Some("laptop".to_string()));
+        assert_eq!(config.watchers, vec!["claude-code".to_string()]);
+        assert!((config.auto_link_threshold - 0.7).abs() < f64::EPSILON);
+        assert!(config.unknown.is_empty());
+
+        // Saving writes the current version
+        config.save_to_path(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains(&format!("version: {CONFIG_VERSION}")));
+        assert_eq!(Config::load_from_path(&path).unwrap(), config);
+    }
+
+    #[test]
+    fn test_unknown_keys_survive_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+
+        fs::write(
+            &path,
+            "version: 99\nauto_link: true\nfuture_flag: true\nfuture_section:\n  depth: 3\n",
+        )
+        .unwrap();
+
+        let mut config = Config::load_from_path(&path).unwrap();
+        assert_eq!(config.version, 99);
+        assert!(config.auto_link);
+        assert_eq!(config.unknown["future_flag"], serde_json::json!(true));
+        assert_eq!(
+            config.unknown["future_section"],
+            serde_json::json!({ "depth": 3 })
+        );
+
+        config.set("commit_footer", "true").unwrap();
+        config.save_to_path(&path).unwrap();
+
+        let reloaded = Config::load_from_path(&path).unwrap();
+        assert_eq!(reloaded.version, 99);
+        assert!(reloaded.commit_footer);
+        assert_eq!(reloaded.unknown, config.unknown);
+    }
+
+    #[test]
+    fn test_token_rate_for_uses_longest_prefix() {
+        let config = Config::default();
+
+        let opus = config.token_rate_for("claude-opus-4-20250514").unwrap();
+        assert_eq!(opus.input, 15.0);
+        let opus_45 = config.token_rate_for("claude-opus-4-5-20251101").unwrap();
+        assert_eq!(opus_45.input, 5.0);
+        let mini = config.token_rate_for("openai/gpt-4o-mini").unwrap();
+        assert_eq!(mini.output, 0.6);
+        assert!(config.token_rate_for("llama3:8b").is_none());
+    }
+
+    #[test]
+    fn test_configured_token_rates_override_builtin() {
+        let yaml = "token_rates:\n  claude-opus-4:\n    input: 1.0\n    output: 2.0\n  llama3:\n    input: 0.0\n    output: 0.0\n";
+        let config: Config = serde_saphyr::from_str(yaml).unwrap();
+
+        let opus = config.token_rate_for("claude-opus-4-20250514").unwrap();
+        assert_eq!((opus.input, opus.output), (1.0, 2.0));
+        assert_eq!(
+            config.estimate_cost(Some("claude-opus-4"), 1_000_000, 500_000),
+            Some(2.0)
+        );
+        assert_eq!(config.estimate_cost(Some("llama3:8b"), 10, 10), Some(0.0));
+        assert_eq!(config.estimate_cost(Some("unknown-model"), 10, 10), None);
+        assert_eq!(config.estimate_cost(None, 10, 10), None);
+    }
+
+    #[test]
+    fn test_known_keys_cover_serialized_fields() {
+        let config = Config {
+            machine_id: Some("id".to_string()),
+            machine_name: Some("name".to_string()),
+            encryption_salt: Some("salt".to_string()),
+            summary_provider: Some("openai".to_string()),
+            summary_api_key_anthropic: Some("k".to_string()),
+            summary_api_key_openai: Some("k".to_string()),
+            summary_api_key_openrouter: Some("k".to_string()),
+            summary_api_key_gemini: Some("k".to_string()),
+            summary_api_key_openai_compatible: Some("k".to_string()),
+            summary_model: Some("m".to_string()),
+            summary_model_anthropic: Some("m".to_string()),
+            summary_model_openai: Some("m".to_string()),
+            summary_model_openrouter: Some("m".to_string()),
+            summary_model_gemini: Some("m".to_string()),
+            summary_truncation: Some("tail".to_string()),
+            summary_base_url: Some("https://gateway".to_string()),
+            search_tokenizer: Some("trigram".to_string()),
+            sync_global_remote: Some("remote".to_string()),
+            redact_patterns: vec!["p".to_string()],
+            token_rates: BTreeMap::from([(
+                "m".to_string(),
+                TokenRate {
+                    input: 1.0,
+                    output: 2.0,
+                },
+            )]),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&config).unwrap();
+        for key in value.as_object().unwrap().keys() {
+            assert!(KNOWN_KEYS.contains(&key.as_str()), "{key} missing");
+        }
+    }
+
     #[test]
     fn test_get_returns_expected_values() {
         let config = Config {
@@ -588,6 +1675,140 @@ mod tests {
         assert_eq!(config.machine_name, Some("dev-workstation".to_string()));
     }
 
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: BTreeMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_env_overrides_file_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+        fs::write(
+            &path,
+            "auto_link: false\nauto_link_threshold: 0.5\nwatchers:\n  - aider\n",
+        )
+        .unwrap();
+
+        let mut config = Config::load_from_path(&path).unwrap();
+        config
+            .apply_env_overrides(env(&[
+                ("LORE_AUTO_LINK", "true"),
+                ("LORE_AUTO_LINK_THRESHOLD", "0.9"),
+                ("LORE_WATCHERS", "claude-code, codex"),
+                ("LORE_DAEMON_DEBOUNCE_MS", "0"),
+                ("LORE_MACHINE_NAME", ""),
+            ]))
+            .unwrap();
+
+        assert!(config.auto_link);
+        assert_eq!(config.auto_link_threshold, 0.9);
+        assert_eq!(config.watchers, vec!["claude-code", "codex"]);
+        assert_eq!(config.daemon.debounce_ms, 0);
+        // Empty variables are ignored
+        assert_eq!(config.machine_name, None);
+        assert_eq!(
+            config.env_overridden_keys().collect::<Vec<_>>(),
+            vec![
+                "auto_link",
+                "auto_link_threshold",
+                "daemon.debounce_ms",
+                "watchers"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_env_overrides_are_validated() {
+        let mut config = Config::default();
+        let err = config
+            .apply_env_overrides(env(&[("LORE_AUTO_LINK_THRESHOLD", "1.5")]))
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("LORE_AUTO_LINK_THRESHOLD"));
+
+        assert!(config
+            .apply_env_overrides(env(&[("LORE_SUMMARY_AUTO", "maybe")]))
+            .is_err());
+        // Read-only keys have no override
+        config
+            .apply_env_overrides(env(&[("LORE_MACHINE_ID", "abc")]))
+            .unwrap();
+        assert_eq!(config.machine_id, None);
+    }
+
+    #[test]
+    fn test_env_overrides_are_not_saved() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+        fs::write(
+            &path,
+            "auto_link_threshold: 0.5\ndaemon:\n  debounce_ms: 250\n",
+        )
+        .unwrap();
+
+        let mut config = Config::load_from_path(&path).unwrap();
+        config
+            .apply_env_overrides(env(&[
+                ("LORE_AUTO_LINK_THRESHOLD", "0.9"),
+                ("LORE_DAEMON_DEBOUNCE_MS", "0"),
+                ("LORE_SUMMARY_BASE_URL", "http://localhost:8080"),
+                ("LORE_COMMIT_FOOTER", "true"),
+            ]))
+            .unwrap();
+        // An explicit set is saved even though the key was overridden
+        config.set("commit_footer", "false").unwrap();
+        config.machine_id = Some("test-id".to_string());
+        config.save_to_path(&path).unwrap();
+
+        let saved = Config::load_from_path(&path).unwrap();
+        assert_eq!(saved.auto_link_threshold, 0.5);
+        assert_eq!(saved.daemon.debounce_ms, 250);
+        assert_eq!(saved.summary_base_url, None);
+        assert!(!saved.commit_footer);
+        assert_eq!(saved.machine_id.as_deref(), Some("test-id"));
+    }
+
+    #[test]
+    fn test_unset_restores_defaults() {
+        let mut config = Config::default();
+        config.set("watchers", "aider").unwrap();
+        config.set("git_remotes", "upstream, origin").unwrap();
+        assert_eq!(config.git_remotes, vec!["upstream", "origin"]);
+        config.set("auto_link_threshold", "0.9").unwrap();
+        config.set("summary_provider", "openai").unwrap();
+        config.set("daemon.debounce_ms", "0").unwrap();
+
+        for key in [
+            "watchers",
+            "git_remotes",
+            "auto_link_threshold",
+            "summary_provider",
+            "daemon.debounce_ms",
+        ] {
+            config.unset(key).unwrap();
+        }
+        assert_eq!(config, Config::default());
+
+        assert!(config.unset("not_a_key").is_err());
+        assert!(config.unset("machine_id").is_err());
+    }
+
+    #[test]
+    fn test_unset_covers_every_settable_key() {
+        let mut config = Config::default();
+        for key in Config::valid_keys() {
+            let result = config.unset(key);
+            assert_eq!(
+                result.is_ok(),
+                !matches!(*key, "machine_id" | "encryption_salt"),
+                "{key}"
+            );
+        }
+    }
+
     #[test]
     fn test_set_validates_threshold_range() {
         let mut config = Config::default();
@@ -623,6 +1844,110 @@ mod tests {
             .contains("cannot be set manually"));
     }
 
+    #[test]
+    fn test_set_compress_content() {
+        let mut config = Config::default();
+        assert!(!config.compress_content);
+
+        config.set("compress_content", "true").unwrap();
+        assert!(config.compress_content);
+        assert_eq!(config.get("compress_content"), Some("true".to_string()));
+        config.unset("compress_content").unwrap();
+        assert!(!config.compress_content);
+        assert!(config.set("compress_content", "sometimes").is_err());
+    }
+
+    #[test]
+    fn test_set_redaction_settings() {
+        let mut config = Config::default();
+        assert!(!config.redact_secrets);
+
+        config.set("redact_secrets", "true").unwrap();
+        assert!(config.redact_secrets);
+        assert_eq!(config.get("redact_secrets"), Some("true".to_string()));
+
+        config.set("redact_patterns", "internal-[0-9]{6}").unwrap();
+        assert_eq!(
+            config.redact_patterns,
+            vec!["internal-[0-9]{6}".to_string()]
+        );
+        assert!(config.set("redact_patterns", "(unclosed").is_err());
+
+        config.set("redact_patterns", "").unwrap();
+        assert!(config.redact_patterns.is_empty());
+
+        config.set("redact_paths", "yes").unwrap();
+        assert!(config.redact_paths);
+        assert!(config.set("redact_paths", "maybe").is_err());
+        config.unset("redact_paths").unwrap();
+        assert!(!config.redact_paths);
+    }
+
+    #[test]
+    fn test_get_set_daemon_extra_watch_paths() {
+        let mut config = Config::default();
+        assert_eq!(config.get("daemon.extra_watch_paths"), Some(String::new()));
+
+        config
+            .set("daemon.extra_watch_paths", "/archive/claude, /mnt/old ,")
+            .unwrap();
+        assert_eq!(
+            config.daemon.extra_watch_paths,
+            vec![PathBuf::from("/archive/claude"), PathBuf::from("/mnt/old")]
+        );
+        assert_eq!(
+            config.get("daemon.extra_watch_paths"),
+            Some("/archive/claude,/mnt/old".to_string())
+        );
+
+        config.unset("daemon.extra_watch_paths").unwrap();
+        assert!(config.daemon.extra_watch_paths.is_empty());
+    }
+
+    #[test]
+    fn test_get_set_daemon_retention_days() {
+        let mut config = Config::default();
+        assert_eq!(config.get("daemon.retention_days"), None);
+
+        config.set("daemon.retention_days", "90").unwrap();
+        assert_eq!(config.daemon.retention_days, Some(90));
+        assert_eq!(config.get("daemon.retention_days"), Some("90".to_string()));
+        assert!(config.set("daemon.retention_days", "0").is_err());
+        assert!(config.set("daemon.retention_days", "-5").is_err());
+        assert_eq!(config.daemon.retention_days, Some(90));
+
+        config.unset("daemon.retention_days").unwrap();
+        assert_eq!(config.daemon.retention_days, None);
+        let yaml = serde_saphyr::to_string(&config).unwrap();
+        assert!(!yaml.contains("retention_days"));
+    }
+
+    #[test]
+    fn test_get_set_daemon_debounce_ms() {
+        let mut config = Config::default();
+        assert_eq!(config.get("daemon.debounce_ms"), Some("500".to_string()));
+
+        config.set("daemon.debounce_ms", "250").unwrap();
+        assert_eq!(config.daemon.debounce_ms, 250);
+        assert!(config.set("daemon.debounce_ms", "-1").is_err());
+        assert!(config.set("daemon.debounce_ms", "soon").is_err());
+
+        // Stored as a nested `daemon:` section and defaulted when absent
+        let yaml = serde_saphyr::to_string(&config).unwrap();
+        assert!(yaml.contains("daemon:"));
+        assert!(yaml.contains("debounce_ms: 250"));
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+        fs::write(
+            &path,
+            "watchers: [claude-code]\nauto_link: false\nauto_link_threshold: 0.7\ncommit_footer: false\n",
+        )
+        .unwrap();
+        let loaded = Config::load_from_path(&path).unwrap();
+        assert_eq!(loaded.daemon.debounce_ms, 500);
+    }
+
     #[test]
     fn test_parse_bool_accepts_multiple_formats() {
         // Truthy values
@@ -642,6 +1967,22 @@ mod tests {
         assert!(parse_bool("invalid").is_err());
     }
 
+    #[test]
+    fn test_get_or_create_machine_id_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+
+        let mut config = Config::default();
+        let id = config.get_or_create_machine_id_in(&path).unwrap();
+        assert!(Uuid::parse_str(&id).is_ok());
+        assert_eq!(config.get_or_create_machine_id_in(&path).unwrap(), id);
+
+        // The generated id was saved, so a later load returns the same one
+        let mut reloaded = Config::load_from_path(&path).unwrap();
+        assert_eq!(reloaded.machine_id.as_deref(), Some(id.as_str()));
+        assert_eq!(reloaded.get_or_create_machine_id_in(&path).unwrap(), id);
+    }
+
     #[test]
     fn test_machine_name_fallback_to_hostname() {
         let config = Config::default();
@@ -706,6 +2047,15 @@ mod tests {
             config.get("summary_provider"),
             Some("openrouter".to_string())
         );
+
+        config.set("summary_provider", "gemini").unwrap();
+        assert_eq!(config.get("summary_provider"), Some("gemini".to_string()));
+
+        config.set("summary_provider", "OpenAI-Compatible").unwrap();
+        assert_eq!(
+            config.get("summary_provider"),
+            Some("openai-compatible".to_string())
+        );
     }
 
     #[test]
@@ -763,7 +2113,21 @@ mod tests {
             config.summary_api_key_for_provider("openrouter"),
             Some("sk-or-789".to_string())
         );
+
+        config.set("summary_api_key_gemini", "AIza-012").unwrap();
+        assert_eq!(
+            config.summary_api_key_for_provider("gemini"),
+            Some("AIza-012".to_string())
+        );
         assert_eq!(config.summary_api_key_for_provider("unknown"), None);
+
+        config
+            .set("summary_api_key_openai_compatible", "gsk-345")
+            .unwrap();
+        assert_eq!(
+            config.summary_api_key_for_provider("openai-compatible"),
+            Some("gsk-345".to_string())
+        );
     }
 
     #[test]
@@ -810,6 +2174,21 @@ mod tests {
             Some("gpt-4o".to_string())
         );
         assert_eq!(config.summary_model_for_provider("unknown"), None);
+
+        // summary_model fills in for providers without their own override
+        config.set("summary_model", "qwen2.5-7b-instruct").unwrap();
+        assert_eq!(
+            config.summary_model_for_provider("openai-compatible"),
+            Some("qwen2.5-7b-instruct".to_string())
+        );
+        assert_eq!(
+            config.summary_model_for_provider("gemini"),
+            Some("qwen2.5-7b-instruct".to_string())
+        );
+        assert_eq!(
+            config.summary_model_for_provider("openai"),
+            Some("gpt-4o".to_string())
+        );
     }
 
     #[test]
@@ -860,6 +2239,91 @@ mod tests {
         assert!(config.set("summary_auto_threshold", "abc").is_err());
     }
 
+    #[test]
+    fn test_get_set_summary_max_attempts() {
+        let mut config = Config::default();
+
+        // Default
+        assert_eq!(config.get("summary_max_attempts"), Some("4".to_string()));
+
+        // Set valid value
+        config.set("summary_max_attempts", "6").unwrap();
+        assert_eq!(config.summary_max_attempts, 6);
+        assert_eq!(config.get("summary_max_attempts"), Some("6".to_string()));
+
+        // Zero and non-numeric values are rejected
+        assert!(config.set("summary_max_attempts", "0").is_err());
+        assert!(config.set("summary_max_attempts", "-1").is_err());
+        assert!(config.set("summary_max_attempts", "abc").is_err());
+    }
+
+    #[test]
+    fn test_get_set_search_tokenizer() {
+        let mut config = Config::default();
+        assert_eq!(config.get("search_tokenizer"), Some("porter".to_string()));
+
+        config.set("search_tokenizer", "Trigram").unwrap();
+        assert_eq!(config.search_tokenizer, Some("trigram".to_string()));
+        assert_eq!(config.get("search_tokenizer"), Some("trigram".to_string()));
+
+        config.unset("search_tokenizer").unwrap();
+        assert_eq!(config.search_tokenizer, None);
+
+        assert!(config.set("search_tokenizer", "icu").is_err());
+        assert!(validate("search_tokenizer: icu\n")[0]
+            .message
+            .contains("unknown tokenizer"));
+    }
+
+    #[test]
+    fn test_get_set_summary_truncation() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.get("summary_truncation"),
+            Some("head-and-tail".to_string())
+        );
+
+        config.set("summary_truncation", "Middle-Out").unwrap();
+        assert_eq!(config.summary_truncation, Some("middle-out".to_string()));
+        assert_eq!(
+            config.get("summary_truncation"),
+            Some("middle-out".to_string())
+        );
+
+        assert!(config.set("summary_truncation", "sideways").is_err());
+        assert!(validate("summary_truncation: sideways\n")[0]
+            .message
+            .contains("unknown strategy"));
+    }
+
+    #[test]
+    fn test_get_set_summary_base_url() {
+        let mut config = Config::default();
+        assert_eq!(config.get("summary_base_url"), None);
+
+        config
+            .set("summary_base_url", "https://llm-gateway.internal/v1/")
+            .unwrap();
+        assert_eq!(
+            config.get("summary_base_url"),
+            Some("https://llm-gateway.internal/v1".to_string())
+        );
+
+        assert!(config
+            .set("summary_base_url", "ftp://llm-gateway.internal")
+            .is_err());
+        assert!(config
+            .set("summary_base_url", "llm-gateway.internal")
+            .is_err());
+
+        config.set("summary_base_url", "").unwrap();
+        assert_eq!(config.summary_base_url, None);
+
+        assert!(validate("summary_base_url: file:///tmp/gateway\n")[0]
+            .message
+            .contains("must use http or https"));
+    }
+
     #[test]
     fn test_summary_fields_yaml_serialization() {
         // When None, summary fields are omitted from YAML
@@ -912,4 +2376,76 @@ mod tests {
         assert!(loaded.summary_auto);
         assert_eq!(loaded.summary_auto_threshold, 8);
     }
+
+    fn validate(yaml: &str) -> Vec<ConfigProblem> {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+        fs::write(&path, yaml).unwrap();
+        Config::validate_file(&path, &["claude-code", "aider"]).unwrap()
+    }
+
+    #[test]
+    fn test_validate_file_accepts_valid_and_missing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+        assert!(Config::validate_file(&path, &[]).unwrap().is_empty());
+
+        Config::default().save_to_path(&path).unwrap();
+        assert!(Config::validate_file(&path, &["claude-code"])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_validate_file_reports_problems_with_lines() {
+        let problems = validate(
+            "watchers:\n  - claude-code\n  - cursr\nauto_link_treshold: 0.5\n\
+             auto_link_threshold: 1.5\ndaemon:\n  debounce_ms: 100\n  debounse: 3\n",
+        );
+
+        let found: Vec<(Option<&str>, Option<usize>)> = problems
+            .iter()
+            .map(|p| (p.key.as_deref(), p.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Some("auto_link_treshold"), Some(4)),
+                (Some("daemon.debounse"), Some(8)),
+                (Some("watchers"), Some(3)),
+                (Some("auto_link_threshold"), Some(5)),
+            ]
+        );
+        assert!(problems[2].message.contains("'cursr'"));
+        assert!(problems[3].message.contains("between 0.0 and 1.0"));
+    }
+
+    #[test]
+    fn test_validate_file_reports_parse_errors() {
+        let problems = validate("auto_link: [unclosed\n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].key, None);
+        assert!(problems[0].message.starts_with("Invalid YAML"));
+
+        let problems = validate("summary_max_attempts: many\n");
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.starts_with("Invalid value"));
+        assert_eq!(problems[0].line, Some(1));
+    }
+
+    #[test]
+    fn test_validate_file_checks_value_ranges() {
+        let problems = validate(
+            "summary_provider: bard\nsummary_auto_threshold: 0\nredact_patterns:\n  - \"(\"\n",
+        );
+        let keys: Vec<_> = problems.iter().filter_map(|p| p.key.as_deref()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "summary_provider",
+                "summary_auto_threshold",
+                "redact_patterns"
+            ]
+        );
+    }
 }
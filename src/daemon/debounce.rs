@@ -0,0 +1,173 @@
+//! Per-path debouncing for session file events.
+//!
+//! AI tools append to session files many times per second while a response
+//! streams in. Parsing the file on every event wastes CPU, so the watcher
+//! records events here and only parses a path once it has been quiet for the
+//! configured window (`daemon.debounce_ms`).
+//!
+//! A file that never goes quiet is still flushed once it has been pending
+//! for [`MAX_WAIT_FACTOR`] windows, so long-running sessions keep importing.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// A continuously changing path is flushed after this many debounce windows.
+const MAX_WAIT_FACTOR: u32 = 10;
+
+/// Pending event times for one path.
+struct Pending {
+    /// When the first event since the last flush arrived.
+    first: Instant,
+    /// When the most recent event arrived.
+    last: Instant,
+}
+
+/// Collapses bursts of file events into a single event per path.
+pub struct PathDebouncer {
+    window: Duration,
+    pending: HashMap<PathBuf, Pending>,
+}
+
+impl PathDebouncer {
+    /// Creates a debouncer with the given quiet window.
+    ///
+    /// A zero window makes every recorded path ready immediately.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Records an event for `path` at `now`.
+    pub fn record(&mut self, path: PathBuf, now: Instant) {
+        self.pending
+            .entry(path)
+            .and_modify(|p| p.last = now)
+            .or_insert(Pending {
+                first: now,
+                last: now,
+            });
+    }
+
+    /// Removes and returns the paths that are ready to be parsed at `now`.
+    ///
+    /// A path is ready once no event has arrived for the full window, or
+    /// once it has been pending for [`MAX_WAIT_FACTOR`] windows. Paths are
+    /// returned in sorted order.
+    pub fn take_ready(&mut self, now: Instant) -> Vec<PathBuf> {
+        let window = self.window;
+        let max_wait = window.saturating_mul(MAX_WAIT_FACTOR);
+
+        let mut ready: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| {
+                now.saturating_duration_since(p.last) >= window
+                    || now.saturating_duration_since(p.first) >= max_wait
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in &ready {
+            self.pending.remove(path);
+        }
+        ready.sort();
+        ready
+    }
+
+    /// Returns the number of paths waiting to be parsed.
+    #[cfg(test)]
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(500);
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_rapid_events_collapse_into_one_parse() {
+        let mut debouncer = PathDebouncer::new(WINDOW);
+        let path = PathBuf::from("/sessions/a.jsonl");
+        let start = Instant::now();
+        let mut parses = 0;
+
+        // 20 appends, 50ms apart, polled every 50ms like the watcher loop
+        for i in 0..40 {
+            let now = start + ms(i * 50);
+            if i < 20 {
+                debouncer.record(path.clone(), now);
+            }
+            parses += debouncer.take_ready(now).len();
+        }
+
+        assert_eq!(parses, 1);
+        assert_eq!(debouncer.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_waits_for_quiet_window() {
+        let mut debouncer = PathDebouncer::new(WINDOW);
+        let path = PathBuf::from("/sessions/a.jsonl");
+        let start = Instant::now();
+
+        debouncer.record(path.clone(), start);
+        debouncer.record(path.clone(), start + ms(300));
+
+        assert!(debouncer.take_ready(start + ms(600)).is_empty());
+        assert_eq!(debouncer.take_ready(start + ms(800)), vec![path]);
+        assert!(debouncer.take_ready(start + ms(2000)).is_empty());
+    }
+
+    #[test]
+    fn test_paths_debounce_independently() {
+        let mut debouncer = PathDebouncer::new(WINDOW);
+        let a = PathBuf::from("/sessions/a.jsonl");
+        let b = PathBuf::from("/sessions/b.jsonl");
+        let start = Instant::now();
+
+        debouncer.record(a.clone(), start);
+        debouncer.record(b.clone(), start + ms(400));
+
+        assert_eq!(debouncer.take_ready(start + ms(500)), vec![a]);
+        assert_eq!(debouncer.take_ready(start + ms(900)), vec![b]);
+    }
+
+    #[test]
+    fn test_continuous_events_flush_after_max_wait() {
+        let mut debouncer = PathDebouncer::new(WINDOW);
+        let path = PathBuf::from("/sessions/a.jsonl");
+        let start = Instant::now();
+        let mut flushed_at = None;
+
+        for i in 0..200 {
+            let now = start + ms(i * 100);
+            debouncer.record(path.clone(), now);
+            if !debouncer.take_ready(now).is_empty() {
+                flushed_at = Some(i * 100);
+                break;
+            }
+        }
+
+        assert_eq!(flushed_at, Some(5000));
+    }
+
+    #[test]
+    fn test_zero_window_is_immediate() {
+        let mut debouncer = PathDebouncer::new(Duration::ZERO);
+        let path = PathBuf::from("/sessions/a.jsonl");
+        let now = Instant::now();
+
+        debouncer.record(path.clone(), now);
+        assert_eq!(debouncer.take_ready(now), vec![path]);
+    }
+}
@@ -6,6 +6,7 @@
 //! - File watching for `~/.claude/projects/` directory
 //! - Incremental parsing of session files
 //! - Unix socket IPC for CLI communication
+//! - Pruning of sessions past `daemon.retention_days`, when set
 //! - Graceful shutdown handling
 //!
 //! # Architecture
@@ -22,6 +23,8 @@
 //! stopped via `lore daemon stop`. Use `lore daemon status` to check
 //! if the daemon is running.
 
+mod debounce;
+mod retention;
 pub mod server;
 pub mod state;
 pub mod watcher;
@@ -35,7 +38,7 @@ use tracing_appender::non_blocking::WorkerGuard;
 use crate::config::Config;
 
 pub use server::{send_command_sync, DaemonCommand, DaemonResponse};
-pub use state::{DaemonState, DaemonStats};
+pub use state::{DaemonHealth, DaemonState, DaemonStats};
 pub use watcher::SessionWatcher;
 
 /// Runs the daemon in the foreground.
@@ -124,6 +127,26 @@ pub async fn run_daemon() -> Result<()> {
         }
     });
 
+    // Start pruning old sessions if a retention period is configured
+    let retention_days = Config::load()
+        .map(|config| config.daemon.retention_days)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to load config, not pruning old sessions: {e}");
+            None
+        });
+    let retention_handle = match retention_days {
+        Some(days) => {
+            let db_config = watcher::DbConfig::default_config()?;
+            let retention_broadcast_rx = broadcast_tx.subscribe();
+            Some(tokio::spawn(retention::run_retention(
+                db_config,
+                days,
+                retention_broadcast_rx,
+            )))
+        }
+        None => None,
+    };
+
     // Wait for shutdown signal
     tokio::select! {
         _ = signal::ctrl_c() => {
@@ -143,6 +166,9 @@ pub async fn run_daemon() -> Result<()> {
     // Abort handles if they haven't finished
     server_handle.abort();
     watcher_handle.abort();
+    if let Some(handle) = retention_handle {
+        handle.abort();
+    }
 
     // Clean up state files
     state.cleanup()?;
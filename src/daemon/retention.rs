@@ -0,0 +1,184 @@
+//! Automatic pruning of old sessions.
+//!
+//! When `daemon.retention_days` is set, the daemon deletes sessions that
+//! started more than that many days ago, once at startup and then every
+//! [`PRUNE_INTERVAL`]. Sessions that still wait to be pushed to a sync store
+//! in use are kept (see [`Database::get_prunable_sessions_older_than`]), and
+//! a session whose last activity falls inside the retention window is never
+//! deleted, even if it started before the cutoff. Pruned sessions are
+//! recorded so the watcher, `lore import` and sync pulls do not bring them
+//! back from their source files or the sync store.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+
+use super::watcher::DbConfig;
+use crate::storage::{Database, Session};
+
+/// How often the daemon looks for sessions to prune.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Returns the start of the retention window ending at `now`.
+fn retention_cutoff(now: DateTime<Utc>, days: u32) -> DateTime<Utc> {
+    now - chrono::Duration::days(i64::from(days))
+}
+
+/// Deletes the sessions that fell out of a `days`-long retention window.
+///
+/// Returns the deleted sessions, oldest first.
+pub fn prune_expired(db: &Database, days: u32, now: DateTime<Utc>) -> Result<Vec<Session>> {
+    let cutoff = retention_cutoff(now, days);
+    let mut pruned = Vec::new();
+    for session in db.get_prunable_sessions_older_than(cutoff)? {
+        // A long-running session can start before the cutoff and still be
+        // active inside the window
+        if session.ended_at.unwrap_or(session.started_at) >= cutoff {
+            continue;
+        }
+        db.delete_session(&session.id)?;
+        db.record_pruned_session(&session.id, now)?;
+        pruned.push(session);
+    }
+    Ok(pruned)
+}
+
+/// Prunes expired sessions every [`PRUNE_INTERVAL`] until shutdown.
+///
+/// Failures are logged and retried at the next interval.
+pub async fn run_retention(
+    db_config: DbConfig,
+    days: u32,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) {
+    tracing::info!("Pruning sessions older than {days} days");
+
+    let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => prune_once(&db_config, days),
+            _ = shutdown_rx.recv() => break,
+        }
+    }
+}
+
+/// Runs one pruning pass on a fresh connection and logs what it deleted.
+fn prune_once(db_config: &DbConfig, days: u32) {
+    let pruned = match db_config
+        .open()
+        .and_then(|db| prune_expired(&db, days, Utc::now()))
+    {
+        Ok(pruned) => pruned,
+        Err(e) => {
+            tracing::warn!("Failed to prune old sessions: {e}");
+            return;
+        }
+    };
+
+    if pruned.is_empty() {
+        tracing::debug!("No sessions older than {days} days to prune");
+        return;
+    }
+    for session in &pruned {
+        tracing::info!(
+            "Pruned session {} ({}, started {})",
+            session.id,
+            session.tool,
+            session.started_at.format("%Y-%m-%d")
+        );
+    }
+    tracing::info!("Pruned {} session(s) older than {days} days", pruned.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::tempdir;
+    use uuid::Uuid;
+
+    fn session(started_at: DateTime<Utc>, ended_at: Option<DateTime<Utc>>) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at,
+            ended_at,
+            model: None,
+            working_directory: "/home/user/project".to_string(),
+            git_branch: None,
+            source_path: None,
+            message_count: 0,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        }
+    }
+
+    fn ids(sessions: &[Session]) -> Vec<Uuid> {
+        sessions.iter().map(|s| s.id).collect()
+    }
+
+    #[test]
+    fn test_prune_keeps_unsynced_sessions_when_sync_is_used() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        let now = Utc::now();
+
+        let synced = session(now - Duration::days(100), None);
+        let unsynced = session(now - Duration::days(100), None);
+        let recent = session(now - Duration::days(1), None);
+        for s in [&synced, &unsynced, &recent] {
+            db.insert_session(s).unwrap();
+        }
+
+        // Before anything is synced, old sessions are pruned regardless
+        let preview = db
+            .get_prunable_sessions_older_than(retention_cutoff(now, 30))
+            .unwrap();
+        assert_eq!(preview.len(), 2);
+
+        db.mark_sessions_synced(&[synced.id], now).unwrap();
+        let pruned = prune_expired(&db, 30, now).unwrap();
+        assert_eq!(ids(&pruned), vec![synced.id]);
+        assert!(db.get_session(&synced.id).unwrap().is_none());
+        assert!(db.get_session(&unsynced.id).unwrap().is_some());
+        assert!(db.get_session(&recent.id).unwrap().is_some());
+
+        // The global track counts on its own: synced to the repo store is
+        // not enough once the global store is in use
+        let other = session(now - Duration::days(100), None);
+        db.insert_session(&other).unwrap();
+        db.mark_sessions_synced(&[unsynced.id, other.id], now)
+            .unwrap();
+        db.mark_global_synced(&[other.id], now).unwrap();
+        let pruned = prune_expired(&db, 30, now).unwrap();
+        assert_eq!(ids(&pruned), vec![other.id]);
+        assert!(db.get_session(&unsynced.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_prune_never_deletes_sessions_active_inside_the_window() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        let now = Utc::now();
+
+        let expired = session(now - Duration::days(40), Some(now - Duration::days(39)));
+        let still_active = session(now - Duration::days(40), Some(now - Duration::days(2)));
+        db.insert_session(&expired).unwrap();
+        db.insert_session(&still_active).unwrap();
+
+        let pruned = prune_expired(&db, 30, now).unwrap();
+        assert_eq!(ids(&pruned), vec![expired.id]);
+        assert!(db.get_session(&still_active.id).unwrap().is_some());
+        assert!(db.is_pruned_session(&expired.id).unwrap());
+        assert!(!db.is_pruned_session(&still_active.id).unwrap());
+
+        // Nothing is left to prune on the next pass
+        assert!(prune_expired(&db, 30, now).unwrap().is_empty());
+    }
+}
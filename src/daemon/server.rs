@@ -2,7 +2,7 @@
 //!
 //! Provides a simple request/response protocol over Unix domain sockets
 //! for communicating with the running daemon. Supports commands like
-//! status, stop, and stats.
+//! status, stop, stats, and health.
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -12,7 +12,7 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::{oneshot, RwLock};
 
-use super::state::DaemonStats;
+use super::state::{DaemonHealth, DaemonStats};
 use crate::storage::Database;
 
 /// Commands that can be sent to the daemon via IPC.
@@ -25,6 +25,8 @@ pub enum DaemonCommand {
     Stop,
     /// Request runtime statistics from the daemon.
     Stats,
+    /// Request a health snapshot (uptime, last activity, parse and error counts).
+    Health,
     /// Ping to check if daemon is responsive.
     Ping,
     /// Request the current active session for a working directory.
@@ -49,6 +51,8 @@ pub enum DaemonResponse {
     Stopping,
     /// Runtime statistics.
     Stats(DaemonStats),
+    /// Health snapshot.
+    Health(DaemonHealth),
     /// Ping response.
     Pong,
     /// Current session response.
@@ -170,6 +174,10 @@ async fn handle_connection(
             let stats_guard = stats.read().await;
             DaemonResponse::Stats(stats_guard.clone())
         }
+        DaemonCommand::Health => {
+            let stats_guard = stats.read().await;
+            DaemonResponse::Health(stats_guard.health(chrono::Utc::now()))
+        }
         DaemonCommand::Ping => DaemonResponse::Pong,
         DaemonCommand::GetCurrentSession { working_directory } => {
             // Query the database for the most recent session in this directory
@@ -276,6 +284,7 @@ mod tests {
             DaemonCommand::Status,
             DaemonCommand::Stop,
             DaemonCommand::Stats,
+            DaemonCommand::Health,
             DaemonCommand::Ping,
         ];
 
@@ -336,6 +345,16 @@ mod tests {
         assert!(json.contains("\"files_watched\""));
     }
 
+    #[test]
+    fn test_daemon_response_health_serialization() {
+        let response = DaemonResponse::Health(DaemonStats::default().health(chrono::Utc::now()));
+
+        let json = serde_json::to_string(&response).expect("Failed to serialize");
+        assert!(json.contains("\"type\":\"health\""));
+        assert!(json.contains("\"last_activity\":null"));
+        assert!(json.contains("\"files_parsed\":0"));
+    }
+
     #[test]
     fn test_daemon_response_error_serialization() {
         let response = DaemonResponse::Error {
@@ -394,6 +413,23 @@ mod tests {
             _ => panic!("Expected Status response"),
         }
 
+        // Send health command
+        stats
+            .write()
+            .await
+            .record_event("claude-code", chrono::Utc::now());
+        let response = send_command(&socket_path, DaemonCommand::Health)
+            .await
+            .expect("Failed to send command");
+
+        match response {
+            DaemonResponse::Health(health) => {
+                assert!(health.last_activity.is_some());
+                assert!(health.last_event_by_watcher.contains_key("claude-code"));
+            }
+            _ => panic!("Expected Health response"),
+        }
+
         // Send stop command
         let response = send_command(&socket_path, DaemonCommand::Stop)
             .await
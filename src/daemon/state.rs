@@ -5,6 +5,8 @@
 //! is running and managing its lifecycle.
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::{Read, Write};
 use std::path::PathBuf;
@@ -156,6 +158,12 @@ pub struct DaemonStats {
     pub started_at: chrono::DateTime<chrono::Utc>,
     /// Number of errors encountered.
     pub errors: u64,
+    /// Number of session files parsed since daemon started.
+    #[serde(default)]
+    pub files_parsed: u64,
+    /// When each watcher last received a file event, keyed by watcher name.
+    #[serde(default)]
+    pub last_event_by_watcher: BTreeMap<String, DateTime<Utc>>,
 }
 
 impl Default for DaemonStats {
@@ -166,10 +174,59 @@ impl Default for DaemonStats {
             messages_imported: 0,
             started_at: chrono::Utc::now(),
             errors: 0,
+            files_parsed: 0,
+            last_event_by_watcher: BTreeMap::new(),
         }
     }
 }
 
+impl DaemonStats {
+    /// Records a file event for the named watcher at `at`.
+    pub fn record_event(&mut self, watcher: &str, at: DateTime<Utc>) {
+        self.last_event_by_watcher.insert(watcher.to_string(), at);
+    }
+
+    /// Returns the most recent file event across all watchers.
+    pub fn last_activity(&self) -> Option<DateTime<Utc>> {
+        self.last_event_by_watcher.values().max().copied()
+    }
+
+    /// Builds a health snapshot as of `now`.
+    pub fn health(&self, now: DateTime<Utc>) -> DaemonHealth {
+        DaemonHealth {
+            started_at: self.started_at,
+            uptime_seconds: now
+                .signed_duration_since(self.started_at)
+                .num_seconds()
+                .max(0) as u64,
+            last_activity: self.last_activity(),
+            last_event_by_watcher: self.last_event_by_watcher.clone(),
+            files_parsed: self.files_parsed,
+            errors: self.errors,
+        }
+    }
+}
+
+/// Health snapshot of a running daemon, returned for the `health` IPC command.
+///
+/// `last_activity` lets callers spot a watcher that has silently stalled
+/// while the process itself is still alive.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DaemonHealth {
+    /// Timestamp when the daemon started.
+    pub started_at: DateTime<Utc>,
+    /// Seconds since the daemon started.
+    pub uptime_seconds: u64,
+    /// Most recent file event across all watchers, if any.
+    pub last_activity: Option<DateTime<Utc>>,
+    /// When each watcher last received a file event, keyed by watcher name.
+    pub last_event_by_watcher: BTreeMap<String, DateTime<Utc>>,
+    /// Number of session files parsed since the daemon started.
+    pub files_parsed: u64,
+    /// Number of errors encountered since the daemon started.
+    pub errors: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,6 +334,49 @@ mod tests {
         assert_eq!(stats.sessions_imported, 0);
         assert_eq!(stats.messages_imported, 0);
         assert_eq!(stats.errors, 0);
+        assert_eq!(stats.files_parsed, 0);
+        assert!(stats.last_activity().is_none());
+    }
+
+    #[test]
+    fn test_daemon_stats_health() {
+        use chrono::TimeZone;
+
+        let started = Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+        let mut stats = DaemonStats {
+            started_at: started,
+            files_parsed: 7,
+            errors: 2,
+            ..Default::default()
+        };
+        stats.record_event("claude-code", started + chrono::Duration::minutes(5));
+        stats.record_event("aider", started + chrono::Duration::minutes(20));
+        stats.record_event("claude-code", started + chrono::Duration::minutes(10));
+
+        let health = stats.health(started + chrono::Duration::hours(1));
+
+        assert_eq!(health.uptime_seconds, 3600);
+        assert_eq!(health.files_parsed, 7);
+        assert_eq!(health.errors, 2);
+        assert_eq!(
+            health.last_activity,
+            Some(started + chrono::Duration::minutes(20))
+        );
+        assert_eq!(
+            health.last_event_by_watcher["claude-code"],
+            started + chrono::Duration::minutes(10)
+        );
+    }
+
+    #[test]
+    fn test_daemon_stats_deserializes_without_health_fields() {
+        // Stats from a daemon that predates the health fields
+        let json = r#"{"files_watched":3,"sessions_imported":1,"messages_imported":9,"started_at":"2025-01-15T10:00:00Z","errors":0}"#;
+        let stats: DaemonStats = serde_json::from_str(json).unwrap();
+
+        assert_eq!(stats.files_watched, 3);
+        assert_eq!(stats.files_parsed, 0);
+        assert!(stats.last_event_by_watcher.is_empty());
     }
 
     #[test]
@@ -14,17 +14,28 @@ use notify_debouncer_mini::{new_debouncer, DebouncedEvent};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, RwLock};
 use uuid::Uuid;
 
+use crate::capture::ignore::LoreIgnore;
+use crate::capture::import::skip_merged;
+use crate::capture::redact::SecretRedactor;
+use crate::capture::repositories::RepositoryRecorder;
 use crate::capture::watchers::{default_registry, Watcher};
-use crate::git::get_commits_in_time_range;
+use crate::git::{get_commits_in_time_range, remote_for_path};
 use crate::storage::models::{LinkCreator, LinkType, SessionLink};
 use crate::storage::Database;
 
+use super::debounce::PathDebouncer;
 use super::state::DaemonStats;
 
+/// How often the notify backend delivers batched file events.
+const NOTIFY_TICK: Duration = Duration::from_millis(100);
+
+/// How often pending paths are checked against the debounce window.
+const DEBOUNCE_POLL: Duration = Duration::from_millis(50);
+
 /// Database path for creating connections within the watcher.
 /// rusqlite connections are not thread-safe, so we create a new
 /// connection when needed rather than sharing one across threads.
@@ -55,29 +66,47 @@ pub struct SessionWatcher {
     file_positions: HashMap<PathBuf, u64>,
     /// Directories to watch for session files.
     watch_dirs: Vec<PathBuf>,
+    /// Existing directories from `daemon.extra_watch_paths`, whose files
+    /// are parsed by whichever watcher recognizes them.
+    extra_dirs: Vec<PathBuf>,
     /// Database configuration for creating connections.
     db_config: DbConfig,
+    /// Quiet period before a changed file is parsed (`daemon.debounce_ms`).
+    debounce: Duration,
 }
 
 impl SessionWatcher {
     /// Creates a new SessionWatcher.
     ///
     /// Uses the default watcher registry to determine which directories
-    /// to watch for session files.
+    /// to watch for session files, adding any `daemon.extra_watch_paths`,
+    /// and reads the debounce window from the `daemon.debounce_ms` config key.
     ///
     /// # Errors
     ///
     /// Returns an error if the database configuration cannot be created.
     pub fn new() -> Result<Self> {
         let registry = default_registry();
-        let watch_dirs = registry.all_watch_paths();
 
         let db_config = DbConfig::default_config()?;
 
+        let daemon_config = match crate::config::Config::load() {
+            Ok(config) => config.daemon,
+            Err(e) => {
+                tracing::warn!("Failed to load config, using default daemon settings: {e}");
+                crate::config::DaemonConfig::default()
+            }
+        };
+
+        let extra_dirs = existing_extra_paths(&daemon_config.extra_watch_paths);
+        let watch_dirs = with_extra_paths(registry.all_watch_paths(), &extra_dirs);
+
         Ok(Self {
             file_positions: HashMap::new(),
             watch_dirs,
+            extra_dirs,
             db_config,
+            debounce: Duration::from_millis(daemon_config.debounce_ms),
         })
     }
 
@@ -119,9 +148,10 @@ impl SessionWatcher {
         // Create a channel for file events
         let (tx, mut rx) = mpsc::channel::<Vec<DebouncedEvent>>(100);
 
-        // Create the debounced watcher
+        // Create the notify watcher; it batches raw events every tick and
+        // `pending` below applies the per-path debounce window.
         let mut debouncer = new_debouncer(
-            Duration::from_millis(500),
+            NOTIFY_TICK,
             move |events: Result<Vec<DebouncedEvent>, notify::Error>| {
                 if let Ok(events) = events {
                     // Filter for JSONL and SQLite database files
@@ -156,12 +186,23 @@ impl SessionWatcher {
         // Do an initial scan (sync, before entering async loop)
         self.initial_scan(&stats).await?;
 
+        let mut pending = PathDebouncer::new(self.debounce);
+        let mut poll = tokio::time::interval(DEBOUNCE_POLL);
+        poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        tracing::info!("Debouncing file events by {:?}", self.debounce);
+
         // Process events
         loop {
             tokio::select! {
                 Some(events) = rx.recv() => {
+                    let now = Instant::now();
                     for event in events {
-                        if let Err(e) = self.handle_file_event(&event.path, &stats).await {
+                        pending.record(event.path, now);
+                    }
+                }
+                _ = poll.tick() => {
+                    for path in pending.take_ready(Instant::now()) {
+                        if let Err(e) = self.handle_file_event(&path, &stats).await {
                             let error_msg = e.to_string();
                             // Database unavailable errors are transient (e.g., during lore init)
                             // Log at debug level to avoid spam
@@ -170,13 +211,13 @@ impl SessionWatcher {
                             {
                                 tracing::debug!(
                                     "Database temporarily unavailable for {:?}: {}",
-                                    event.path,
+                                    path,
                                     e
                                 );
                             } else {
                                 tracing::warn!(
                                     "Error handling file event for {:?}: {}",
-                                    event.path,
+                                    path,
                                     e
                                 );
                                 let mut stats_guard = stats.write().await;
@@ -195,6 +236,23 @@ impl SessionWatcher {
         Ok(())
     }
 
+    /// Rebuilds the search index when it is empty for existing data, as
+    /// after imports made before the index existed. Done once at start-up
+    /// rather than on every open, since the check counts every message.
+    fn rebuild_empty_search_index(&self) {
+        let rebuilt = self.open_db().and_then(|db| {
+            if !db.search_index_needs_rebuild()? {
+                return Ok(None);
+            }
+            db.rebuild_search_index().map(Some)
+        });
+        match rebuilt {
+            Ok(Some(count)) => tracing::info!("Rebuilt search index ({count} messages)"),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to rebuild search index: {e}"),
+        }
+    }
+
     /// Opens a database connection for this operation.
     fn open_db(&self) -> Result<Database> {
         self.db_config.open()
@@ -221,16 +279,60 @@ impl SessionWatcher {
         None
     }
 
+    /// Finds the watcher that should parse a file.
+    ///
+    /// Files under a watcher's own directories go to that watcher. Files
+    /// under an extra watch path carry no such hint, so the tool is inferred
+    /// by trying each watcher in turn and taking the first one that parses
+    /// sessions from the file.
+    fn resolve_watcher<'a>(
+        &self,
+        path: &Path,
+        available: &'a [&'a dyn Watcher],
+        all: &'a [&'a dyn Watcher],
+    ) -> Option<&'a dyn Watcher> {
+        if let Some(watcher) = Self::find_owning_watcher(path, available) {
+            return Some(watcher);
+        }
+        if !self.extra_dirs.iter().any(|dir| path.starts_with(dir)) {
+            return None;
+        }
+        all.iter().copied().find(|watcher| {
+            watcher
+                .parse_source(path)
+                .is_ok_and(|sessions| !sessions.is_empty())
+        })
+    }
+
+    /// Lists the session files currently under the extra watch paths.
+    fn extra_sources(&self) -> Vec<PathBuf> {
+        let mut sources = Vec::new();
+        for dir in &self.extra_dirs {
+            for ext in ["jsonl", "vscdb"] {
+                let pattern = dir.join("**").join(format!("*.{ext}"));
+                let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+                    continue;
+                };
+                sources.extend(paths.flatten().filter(|p| p.is_file()));
+            }
+        }
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
     /// Performs an initial scan of existing session files.
     ///
     /// Called when the watcher starts to import any sessions that were
     /// created while the daemon was not running. Uses the watcher registry
     /// to find session sources from all available watchers.
     async fn initial_scan(&mut self, stats: &Arc<RwLock<DaemonStats>>) -> Result<()> {
+        self.rebuild_empty_search_index();
         tracing::info!("Performing initial scan of session files...");
 
         let registry = default_registry();
         let mut total_files = 0;
+        let mut scan: Vec<PathBuf> = Vec::new();
 
         for watcher in registry.available_watchers() {
             let watcher_name = watcher.info().name;
@@ -238,26 +340,7 @@ impl SessionWatcher {
                 Ok(sources) => {
                     tracing::info!("Found {} sources for {}", sources.len(), watcher_name);
                     total_files += sources.len();
-
-                    for path in sources {
-                        // Process each file synchronously to avoid Send issues
-                        match self.process_file_sync(&path) {
-                            Ok(Some((sessions_imported, messages_imported))) => {
-                                let mut stats_guard = stats.write().await;
-                                stats_guard.sessions_imported += sessions_imported;
-                                stats_guard.messages_imported += messages_imported;
-                                stats_guard.files_watched = self.file_positions.len();
-                            }
-                            Ok(None) => {
-                                // File was already imported, just track position
-                            }
-                            Err(e) => {
-                                tracing::warn!("Failed to import {:?}: {}", path, e);
-                                let mut stats_guard = stats.write().await;
-                                stats_guard.errors += 1;
-                            }
-                        }
-                    }
+                    scan.extend(sources);
                 }
                 Err(e) => {
                     tracing::warn!("Failed to find sources for {}: {}", watcher_name, e);
@@ -265,6 +348,38 @@ impl SessionWatcher {
             }
         }
 
+        let extra: Vec<PathBuf> = self
+            .extra_sources()
+            .into_iter()
+            .filter(|path| !scan.contains(path))
+            .collect();
+        if !self.extra_dirs.is_empty() {
+            tracing::info!("Found {} sources in extra watch paths", extra.len());
+        }
+        total_files += extra.len();
+        scan.extend(extra);
+
+        for path in scan {
+            // Process each file synchronously to avoid Send issues
+            match self.process_file_sync(&path) {
+                Ok(Some((sessions_imported, messages_imported))) => {
+                    let mut stats_guard = stats.write().await;
+                    stats_guard.sessions_imported += sessions_imported;
+                    stats_guard.messages_imported += messages_imported;
+                    stats_guard.files_watched = self.file_positions.len();
+                    stats_guard.files_parsed += 1;
+                }
+                Ok(None) => {
+                    // File was already imported, just track position
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to import {:?}: {}", path, e);
+                    let mut stats_guard = stats.write().await;
+                    stats_guard.errors += 1;
+                }
+            }
+        }
+
         {
             let mut stats_guard = stats.write().await;
             stats_guard.files_watched = total_files;
@@ -286,11 +401,14 @@ impl SessionWatcher {
             return Ok(());
         }
 
-        // Skip agent files (Claude Code specific)
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with("agent-") {
-                return Ok(());
-            }
+        // Record the event against the owning watcher for health reporting
+        let watcher_name = {
+            let registry = default_registry();
+            let available = registry.available_watchers();
+            Self::find_owning_watcher(path, &available).map(|w| w.info().name)
+        };
+        if let Some(name) = watcher_name {
+            stats.write().await.record_event(name, Utc::now());
         }
 
         // Check if file exists (might be a delete event)
@@ -307,6 +425,7 @@ impl SessionWatcher {
                 stats_guard.sessions_imported += sessions_imported;
                 stats_guard.messages_imported += messages_imported;
                 stats_guard.files_watched = self.file_positions.len();
+                stats_guard.files_parsed += 1;
             }
             Ok(None) => {
                 // File unchanged or already processed
@@ -371,6 +490,26 @@ impl SessionWatcher {
         Ok(Some(result))
     }
 
+    /// Builds the secret redactor from the current config, if enabled.
+    ///
+    /// The config is reloaded on each import so toggling `redact_secrets`
+    /// takes effect without restarting the daemon. An invalid pattern is an
+    /// error rather than a silent fallback, so nothing is stored unredacted.
+    fn load_redactor() -> Result<Option<SecretRedactor>> {
+        let config = crate::config::Config::load()?;
+        SecretRedactor::from_config(&config)
+    }
+
+    /// Returns the configured git remote names, reloaded like the redactor
+    /// so edits to `git_remotes` apply without a restart.
+    ///
+    /// Falls back to the default names if the config cannot be loaded.
+    fn load_remote_names() -> Vec<String> {
+        crate::config::Config::load()
+            .map(|config| config.git_remotes)
+            .unwrap_or_else(|_| crate::config::Config::default().git_remotes)
+    }
+
     /// Updates an existing session by re-importing the file.
     ///
     /// Re-parses the session file and updates the database. The database layer
@@ -391,9 +530,10 @@ impl SessionWatcher {
         let path_buf = path.to_path_buf();
         let registry = default_registry();
         let available = registry.available_watchers();
+        let all = registry.all_watchers();
 
         // Find the watcher that owns this path
-        let owning_watcher = match Self::find_owning_watcher(path, &available) {
+        let owning_watcher = match self.resolve_watcher(path, &available, &all) {
             Some(w) => w,
             None => {
                 tracing::debug!("No watcher owns path {:?}", path);
@@ -424,26 +564,50 @@ impl SessionWatcher {
             return Ok((0, 0));
         }
 
+        let redactor = Self::load_redactor()?;
+        // Reloaded like the redactor, so edits apply without a restart
+        let ignore = LoreIgnore::load();
+        let mut repositories = RepositoryRecorder::with_remote_names(Self::load_remote_names());
         let mut total_messages = 0u64;
         let mut updated_session: Option<crate::storage::models::Session> = None;
 
-        for (session, messages) in parsed_sessions {
-            if messages.is_empty() {
+        for (session, mut messages) in parsed_sessions {
+            if messages.is_empty()
+                || skip_merged(db, &session)?
+                || db.is_pruned_session(&session.id)?
+            {
+                continue;
+            }
+            if ignore.is_ignored(&session.working_directory) {
+                tracing::debug!(
+                    "Skipping session {} in ignored directory {}",
+                    &session.id.to_string()[..8],
+                    session.working_directory
+                );
                 continue;
             }
+            // The stored messages are the first ones parsed; some watchers
+            // give messages new random IDs on every parse, so they cannot be
+            // told apart by ID
+            let stored = db.session_message_count(&session.id)?;
+            if stored >= messages.len() {
+                continue;
+            }
+            let mut messages = messages.split_off(stored);
+            if let Some(ref redactor) = redactor {
+                redactor.redact_messages(&mut messages);
+            }
 
             // Update session metadata (ended_at, message_count, git_branch)
             // insert_session uses ON CONFLICT to update these fields
             db.insert_session(&session)?;
+            repositories.record(db, &session)?;
 
             // Track the most recent branch from messages
             let mut latest_branch: Option<String> = None;
             let mut new_message_count = 0u64;
 
             for msg in &messages {
-                // insert_message uses ON CONFLICT DO NOTHING, so duplicates are skipped
-                // We don't have a reliable way to count only new messages, but we track
-                // the total for logging purposes
                 db.insert_message(msg)?;
                 new_message_count += 1;
 
@@ -452,6 +616,8 @@ impl SessionWatcher {
                 }
             }
 
+            db.recount_session_messages(&session.id)?;
+
             // Update session branch if messages show a different branch
             if let Some(ref new_branch) = latest_branch {
                 if session.git_branch.as_ref() != Some(new_branch) {
@@ -530,9 +696,10 @@ impl SessionWatcher {
         let path_buf = path.to_path_buf();
         let registry = default_registry();
         let available = registry.available_watchers();
+        let all = registry.all_watchers();
 
         // Find the watcher that owns this path
-        let owning_watcher = match Self::find_owning_watcher(path, &available) {
+        let owning_watcher = match self.resolve_watcher(path, &available, &all) {
             Some(w) => w,
             None => {
                 tracing::debug!("No watcher owns path {:?}", path);
@@ -563,18 +730,37 @@ impl SessionWatcher {
             return Ok((0, 0));
         }
 
+        let redactor = Self::load_redactor()?;
+        // Reloaded like the redactor, so edits apply without a restart
+        let ignore = LoreIgnore::load();
+        let mut repositories = RepositoryRecorder::with_remote_names(Self::load_remote_names());
         let mut total_sessions = 0u64;
         let mut total_messages = 0u64;
 
-        for (session, messages) in parsed_sessions {
-            if messages.is_empty() {
+        for (session, mut messages) in parsed_sessions {
+            if messages.is_empty()
+                || skip_merged(db, &session)?
+                || db.is_pruned_session(&session.id)?
+            {
+                continue;
+            }
+            if ignore.is_ignored(&session.working_directory) {
+                tracing::debug!(
+                    "Skipping session {} in ignored directory {}",
+                    &session.id.to_string()[..8],
+                    session.working_directory
+                );
                 continue;
             }
+            if let Some(ref redactor) = redactor {
+                redactor.redact_messages(&mut messages);
+            }
 
             let message_count = messages.len();
 
             // Store session
             db.insert_session(&session)?;
+            repositories.record(db, &session)?;
 
             // Store messages and track the most recent branch
             let mut latest_branch: Option<String> = None;
@@ -586,6 +772,8 @@ impl SessionWatcher {
                 }
             }
 
+            db.recount_session_messages(&session.id)?;
+
             // Update session branch if the latest message has a different branch
             // This handles the case where the user switches branches mid-session
             if let Some(ref new_branch) = latest_branch {
@@ -700,6 +888,7 @@ impl SessionWatcher {
             return Ok(0);
         }
 
+        let (remote, forge) = remote_for_path(working_dir, &Self::load_remote_names());
         let mut linked_count = 0;
 
         for commit in commits {
@@ -720,10 +909,12 @@ impl SessionWatcher {
                 link_type: LinkType::Commit,
                 commit_sha: Some(commit.sha.clone()),
                 branch: commit.branch.clone(),
-                remote: None,
+                remote: remote.clone(),
                 created_at: Utc::now(),
                 created_by: LinkCreator::Auto,
                 confidence: Some(1.0), // Direct time match is high confidence
+                forge: forge.clone(),
+                factors: None,
             };
 
             db.insert_link(&link)?;
@@ -788,13 +979,14 @@ impl SessionWatcher {
             }
         };
 
-        match crate::summarize::generate_summary(&messages) {
-            Ok(content) => {
+        match crate::summarize::generate_summary(&messages, None) {
+            Ok(generated) => {
                 let summary = crate::storage::models::Summary {
                     id: uuid::Uuid::new_v4(),
                     session_id: session.id,
-                    content,
+                    content: generated.content,
                     generated_at: chrono::Utc::now(),
+                    model: Some(generated.model),
                 };
                 if let Err(e) = db.insert_summary(&summary) {
                     tracing::warn!(
@@ -827,6 +1019,34 @@ impl SessionWatcher {
     }
 }
 
+/// Keeps the configured extra watch paths that exist.
+///
+/// A missing directory is logged and skipped rather than failing daemon
+/// startup, since extra paths often point at removable or network storage.
+fn existing_extra_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter(|path| {
+            let exists = path.is_dir();
+            if !exists {
+                tracing::warn!("Skipping extra watch path {:?}: not a directory", path);
+            }
+            exists
+        })
+        .cloned()
+        .collect()
+}
+
+/// Appends `extra` to the registry's watch directories, skipping duplicates.
+fn with_extra_paths(mut dirs: Vec<PathBuf>, extra: &[PathBuf]) -> Vec<PathBuf> {
+    for path in extra {
+        if !dirs.contains(path) {
+            dirs.push(path.clone());
+        }
+    }
+    dirs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -858,6 +1078,9 @@ mod tests {
             source_path: None,
             message_count: 0,
             machine_id: Some("test-machine".to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         }
     }
 
@@ -990,6 +1213,28 @@ mod tests {
         assert_eq!(watcher.file_positions.get(&path2), Some(&200));
     }
 
+    #[test]
+    fn test_extra_watch_paths_join_watch_set() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("archive");
+        std::fs::create_dir(&archive).unwrap();
+        let missing = dir.path().join("missing");
+        let default_dir = PathBuf::from("/home/user/.claude/projects");
+
+        let extra = existing_extra_paths(&[archive.clone(), missing]);
+        assert_eq!(extra, vec![archive.clone()]);
+
+        let dirs = with_extra_paths(vec![default_dir.clone()], &extra);
+        assert_eq!(dirs, vec![default_dir.clone(), archive.clone()]);
+
+        // A path already watched by a registered watcher is not added twice
+        let dirs = with_extra_paths(
+            vec![default_dir.clone()],
+            std::slice::from_ref(&default_dir),
+        );
+        assert_eq!(dirs, vec![default_dir]);
+    }
+
     // ==================== find_owning_watcher Tests ====================
 
     #[test]
@@ -1094,9 +1339,11 @@ mod tests {
         let watcher = SessionWatcher {
             file_positions: HashMap::new(),
             watch_dirs: vec![],
+            extra_dirs: vec![],
             db_config: DbConfig {
                 path: repo_path.join("test.db"),
             },
+            debounce: std::time::Duration::ZERO,
         };
 
         // Call auto_link_session_commits
@@ -1173,9 +1420,11 @@ mod tests {
         let watcher = SessionWatcher {
             file_positions: HashMap::new(),
             watch_dirs: vec![],
+            extra_dirs: vec![],
             db_config: DbConfig {
                 path: repo_path.join("test.db"),
             },
+            debounce: std::time::Duration::ZERO,
         };
 
         let linked_count = watcher
@@ -1254,6 +1503,8 @@ mod tests {
             created_at: Utc::now(),
             created_by: LinkCreator::Auto,
             confidence: Some(1.0),
+            forge: None,
+            factors: None,
         };
         db.insert_link(&existing_link)
             .expect("Failed to insert existing link");
@@ -1262,9 +1513,11 @@ mod tests {
         let watcher = SessionWatcher {
             file_positions: HashMap::new(),
             watch_dirs: vec![],
+            extra_dirs: vec![],
             db_config: DbConfig {
                 path: repo_path.join("test.db"),
             },
+            debounce: std::time::Duration::ZERO,
         };
 
         let linked_count = watcher
@@ -1318,9 +1571,11 @@ mod tests {
         let watcher = SessionWatcher {
             file_positions: HashMap::new(),
             watch_dirs: vec![],
+            extra_dirs: vec![],
             db_config: DbConfig {
                 path: non_repo_path.join("test.db"),
             },
+            debounce: std::time::Duration::ZERO,
         };
 
         let result = watcher.auto_link_session_commits(
@@ -1405,9 +1660,11 @@ mod tests {
         let watcher = SessionWatcher {
             file_positions: HashMap::new(),
             watch_dirs: vec![],
+            extra_dirs: vec![],
             db_config: DbConfig {
                 path: repo_path.join("test.db"),
             },
+            debounce: std::time::Duration::ZERO,
         };
 
         let linked_count = watcher
@@ -1480,6 +1737,9 @@ mod tests {
             source_path: Some("/test/session.jsonl".to_string()),
             message_count: 5,
             machine_id: Some("test-machine".to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
 
         db.insert_session(&ongoing_session)
@@ -1505,15 +1765,20 @@ mod tests {
             source_path: Some("/test/session.jsonl".to_string()),
             message_count: 10,
             machine_id: Some("test-machine".to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
 
         // Create watcher
         let watcher = SessionWatcher {
             file_positions: HashMap::new(),
             watch_dirs: vec![],
+            extra_dirs: vec![],
             db_config: DbConfig {
                 path: repo_path.join("test.db"),
             },
+            debounce: std::time::Duration::ZERO,
         };
 
         // Simulate what update_existing_session does:
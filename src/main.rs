@@ -1,5 +1,6 @@
 use anyhow::Result;
 use clap::{CommandFactory, Parser, Subcommand};
+use colored::Colorize;
 use std::io::{self, IsTerminal, Write};
 use std::sync::mpsc;
 use std::thread;
@@ -35,13 +36,6 @@ mod sync;
 use cli::commands;
 use config::Config;
 
-// ANSI escape codes for terminal colors
-const YELLOW: &str = "\x1b[33m";
-const GREEN: &str = "\x1b[32m";
-const RED: &str = "\x1b[31m";
-const BOLD: &str = "\x1b[1m";
-const RESET: &str = "\x1b[0m";
-
 /// Timeout duration for the init prompt in seconds.
 const PROMPT_TIMEOUT_SECS: u64 = 30;
 
@@ -61,6 +55,7 @@ const PROMPT_TIMEOUT_SECS: u64 = 30;
     lore sessions            List recent sessions\n    \
     lore show abc123         View session details\n    \
     lore show --commit HEAD  View sessions linked to HEAD\n    \
+    lore replay abc123       Step through a session\n    \
     lore link abc123         Link session to HEAD\n    \
     lore search \"auth\"       Search sessions for text\n    \
     lore insights            Show AI development insights\n    \
@@ -75,9 +70,36 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// Only print requested data, warnings, and errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    #[arg(
+        long_help = "Suppress progress and confirmation messages, and log only\n\
+        warnings and errors. The data a command prints, such as a session\n\
+        list or JSON output, is unaffected."
+    )]
+    quiet: bool,
+
     /// Skip the first-run setup prompt (useful for scripting)
     #[arg(long, global = true)]
     no_init: bool,
+
+    /// Use this database file instead of ~/.lore/lore.db
+    #[arg(long, global = true, value_name = "PATH")]
+    #[arg(
+        long_help = "Use this database file instead of ~/.lore/lore.db. Takes\n\
+        precedence over the LORE_DB_PATH environment variable. The parent\n\
+        directory is created if needed."
+    )]
+    db: Option<std::path::PathBuf>,
+
+    /// When to color output: auto (default), always, never
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    #[arg(
+        long_help = "When to color output. 'auto' colors only when stdout is a\n\
+        terminal and the NO_COLOR environment variable is unset or empty;\n\
+        'always' and 'never' override both."
+    )]
+    color: cli::ColorChoice,
 }
 
 /// Available CLI subcommands.
@@ -95,7 +117,8 @@ enum Commands {
     #[command(
         long_about = "Displays an overview of the Lore database including session counts,\n\
         watcher availability, daemon status, links to the current commit,\n\
-        and a list of recent sessions."
+        and a list of recent sessions. Use --format json for a structured\n\
+        object suitable for scripted health checks."
     )]
     Status(commands::status::Args),
 
@@ -110,8 +133,10 @@ enum Commands {
     /// Show recent sessions for quick orientation
     #[command(
         long_about = "Provides a summary of recent sessions for the current repository.\n\
-        Shows session ID, tool, start time, message count, and linked commits.\n\
-        Use --last for detailed info about the most recent session only."
+        Shows session ID, tool, start time, message count, linked commits, and\n\
+        stored summaries. Use --last for detailed info about the most recent\n\
+        session only, and --format markdown for a digest to paste into a new\n\
+        AI chat."
     )]
     Context(commands::context::Args),
 
@@ -123,10 +148,21 @@ enum Commands {
     )]
     Sessions(commands::sessions::Args),
 
+    /// Show sessions grouped by calendar day
+    #[command(
+        long_about = "Groups sessions by the local calendar day they started on, showing\n\
+        per-day session and message counts and the repositories touched.\n\
+        Supports filtering by repository path and start date, and JSON output\n\
+        as an array of day buckets."
+    )]
+    Timeline(commands::timeline::Args),
+
     /// Show session details or sessions linked to a commit
     #[command(
         long_about = "Displays the full conversation history for a session, or lists\n\
         all sessions linked to a specific commit when using --commit.\n\
+        Use --message to show one message with its surrounding context,\n\
+        or --tree to show edited and regenerated replies as branches.\n\
         \n\
         Supports multiple output formats:\n\
         - text: colored terminal output (default)\n\
@@ -135,6 +171,38 @@ enum Commands {
     )]
     Show(commands::show::Args),
 
+    /// Step through a session message by message
+    #[command(
+        long_about = "Replays a session's conversation one message at a time, in the\n\
+        order it happened. On a terminal each message waits for Enter, or\n\
+        --delay advances automatically. Use --role to replay only one\n\
+        side of the conversation. When output is piped, every message is\n\
+        printed at once."
+    )]
+    Replay(commands::replay::Args),
+
+    /// Compare the conversations of two sessions
+    #[command(
+        long_about = "Aligns the messages of two sessions by position and compares their\n\
+        text, showing where the conversations diverged. Each message pair\n\
+        is reported as unchanged, changed (with a line diff), removed (only\n\
+        in the first session), or added (only in the second session).\n\
+        Use --format json for structured added/removed/changed entries."
+    )]
+    Diff(commands::diff::Args),
+
+    /// Open a session's linked commits in the browser
+    #[command(
+        long_about = "Opens the web page of each commit linked to a session, or of a\n\
+        single commit with --commit, on its forge (GitHub, GitLab,\n\
+        Bitbucket, or Codeberg). The URL is built from the remote recorded\n\
+        on each link, or else from the first of the git_remotes config\n\
+        names the repository has (default \"origin\"), falling back to its\n\
+        first remote. The URL is printed instead when --print is given or\n\
+        no browser is available."
+    )]
+    Open(commands::open::Args),
+
     /// Link sessions to git commits
     #[command(
         long_about = "Creates associations between AI coding sessions and git commits.\n\
@@ -183,6 +251,16 @@ enum Commands {
     )]
     Delete(commands::delete::Args),
 
+    /// Merge fragmented sessions into one session
+    #[command(
+        long_about = "Folds one or more sessions into a primary session. Their\n\
+        messages are reassigned to the primary and reindexed in timestamp\n\
+        order, their links, tags, and annotations move over, and the emptied\n\
+        sessions are deleted, all in one transaction. Sessions from different\n\
+        working directories are only merged with --force."
+    )]
+    MergeSessions(commands::merge_sessions::Args),
+
     /// Show which AI session led to a specific line of code
     #[command(
         long_about = "Uses git blame to find the commit that introduced a specific\n\
@@ -196,7 +274,8 @@ enum Commands {
         long_about = "Exports session data as markdown or JSON. Supports redaction\n\
         of sensitive information like API keys, tokens, passwords,\n\
         and email addresses. Use --redact for built-in patterns or\n\
-        --redact-pattern for custom regex patterns."
+        --redact-pattern for custom regex patterns. Markdown exports\n\
+        include YAML front-matter; use --output to write a file."
     )]
     Export(commands::export::Args),
 
@@ -210,8 +289,12 @@ enum Commands {
 
     /// View and manage configuration settings
     #[command(
-        long_about = "Provides subcommands to show, get, and set configuration values.\n\
-        Configuration is stored in ~/.lore/config.yaml."
+        long_about = "Provides subcommands to show, get, set, and unset configuration values,\n\
+        and to list the models available from the summary provider.\n\
+        Configuration is stored in ~/.lore/config.yaml. Any key can be\n\
+        overridden with a LORE_<KEY> environment variable, such as\n\
+        LORE_AUTO_LINK_THRESHOLD=0.8; the environment takes precedence over\n\
+        the file, which takes precedence over the defaults."
     )]
     Config(commands::config::Args),
 
@@ -254,6 +337,15 @@ enum Commands {
     )]
     Daemon(commands::daemon::Args),
 
+    /// Show or label this machine's identity
+    #[command(
+        visible_alias = "whoami",
+        long_about = "Shows this machine's ID, label, and hostname, or sets the label.\n\
+        The ID is recorded on every session captured here, and the label\n\
+        identifies this machine on other machines after a sync."
+    )]
+    Machine(commands::machine::Args),
+
     /// Manage the database (vacuum, prune, stats)
     #[command(
         long_about = "Database management commands for maintenance and statistics.\n\
@@ -284,6 +376,16 @@ enum Commands {
     )]
     Doctor(commands::doctor::Args),
 
+    /// Check the database for corruption and orphaned records
+    #[command(
+        long_about = "Runs SQLite's integrity check on the database, looks for\n\
+        messages, links, and other records whose session no longer exists,\n\
+        and checks that the search index is complete. Use --fix to delete\n\
+        orphaned records and rebuild the index. Exits with code 1 when\n\
+        problems remain."
+    )]
+    Verify(commands::verify::Args),
+
     /// Run the MCP server for AI tool integration
     #[command(
         long_about = "Runs the MCP (Model Context Protocol) server on stdio.\n\
@@ -354,11 +456,6 @@ fn is_interactive() -> bool {
     io::stdin().is_terminal()
 }
 
-/// Checks if stdout is connected to a terminal (for color output).
-fn stdout_is_tty() -> bool {
-    io::stdout().is_terminal()
-}
-
 /// Result of the init prompt, including timeout case.
 #[derive(Debug, PartialEq)]
 enum PromptResult {
@@ -373,18 +470,14 @@ enum PromptResult {
 /// Prompts the user to run the init wizard with colored output and timeout.
 ///
 /// Returns the user's choice or timeout after 30 seconds.
-/// Uses ANSI colors if stdout is a TTY.
+/// Colors follow the global `--color` choice.
 fn prompt_for_init() -> Result<PromptResult> {
-    let use_color = stdout_is_tty();
-
-    // Build the prompt with optional colors
-    if use_color {
-        print!(
-            "{BOLD}{YELLOW}Lore isn't configured yet. Run setup?{RESET} [{GREEN}Y{RESET}/{RED}n{RESET}] "
-        );
-    } else {
-        print!("Lore isn't configured yet. Run setup? [Y/n] ");
-    }
+    print!(
+        "{} [{}/{}] ",
+        "Lore isn't configured yet. Run setup?".bold().yellow(),
+        "Y".green(),
+        "n".red()
+    );
     io::stdout().flush()?;
 
     // Use a channel to receive input with timeout
@@ -435,6 +528,10 @@ fn command_name(command: &Commands) -> &'static str {
         Commands::Current(_) => "current",
         Commands::Context(_) => "context",
         Commands::Sessions(_) => "sessions",
+        Commands::Timeline(_) => "timeline",
+        Commands::Replay(_) => "replay",
+        Commands::Diff(_) => "diff",
+        Commands::Open(_) => "open",
         Commands::Show(_) => "show",
         Commands::Link(_) => "link",
         Commands::Unlink(_) => "unlink",
@@ -442,6 +539,7 @@ fn command_name(command: &Commands) -> &'static str {
         Commands::Tag(_) => "tag",
         Commands::Summarize(_) => "summarize",
         Commands::Delete(_) => "delete",
+        Commands::MergeSessions(_) => "merge-sessions",
         Commands::Blame(_) => "blame",
         Commands::Export(_) => "export",
         Commands::Search(_) => "search",
@@ -450,9 +548,11 @@ fn command_name(command: &Commands) -> &'static str {
         Commands::Insights(_) => "insights",
         Commands::Hooks(_) => "hooks",
         Commands::Daemon(_) => "daemon",
+        Commands::Machine(_) => "machine",
         Commands::Db(_) => "db",
         Commands::Sync(_) => "sync",
         Commands::Doctor(_) => "doctor",
+        Commands::Verify(_) => "verify",
         Commands::Mcp(_) => "mcp",
         Commands::Memories(_) => "memories",
         Commands::Completions(_) => "completions",
@@ -465,10 +565,22 @@ fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
+    // Decide on color before anything is printed
+    let color = cli.color.apply();
+    cli::set_quiet(cli.quiet);
+
+    // Expose --db through the environment so every Database::open_default()
+    // call, and any daemon process spawned from here, uses the same file.
+    if let Some(ref db) = cli.db {
+        std::env::set_var(storage::db::DB_PATH_ENV, db);
+    }
+
     // Initialize logging (skip for daemon foreground mode - it sets up file logging)
     if !is_daemon_foreground(&cli.command) {
         let filter = if cli.verbose {
             "lore=debug"
+        } else if cli.quiet {
+            "lore=warn"
         } else {
             "lore=info"
         };
@@ -478,7 +590,13 @@ fn main() -> Result<()> {
                 tracing_subscriber::EnvFilter::try_from_default_env()
                     .unwrap_or_else(|_| filter.into()),
             )
-            .with(tracing_subscriber::fmt::layer().without_time())
+            .with(
+                // Log to stderr so messages never mix with data on stdout
+                tracing_subscriber::fmt::layer()
+                    .without_time()
+                    .with_ansi(color)
+                    .with_writer(io::stderr),
+            )
             .init();
     }
 
@@ -521,6 +639,10 @@ fn main() -> Result<()> {
         Commands::Current(args) => commands::current::run(args),
         Commands::Context(args) => commands::context::run(args),
         Commands::Sessions(args) => commands::sessions::run(args),
+        Commands::Timeline(args) => commands::timeline::run(args),
+        Commands::Replay(args) => commands::replay::run(args),
+        Commands::Diff(args) => commands::diff::run(args),
+        Commands::Open(args) => commands::open::run(args),
         Commands::Show(args) => commands::show::run(args),
         Commands::Link(args) => commands::link::run(args),
         Commands::Unlink(args) => commands::unlink::run(args),
@@ -528,6 +650,7 @@ fn main() -> Result<()> {
         Commands::Tag(args) => commands::tag::run(args),
         Commands::Summarize(args) => commands::summarize::run(args),
         Commands::Delete(args) => commands::delete::run(args),
+        Commands::MergeSessions(args) => commands::merge_sessions::run(args),
         Commands::Blame(args) => commands::blame::run(args),
         Commands::Export(args) => commands::export::run(args),
         Commands::Search(args) => commands::search::run(args),
@@ -536,9 +659,11 @@ fn main() -> Result<()> {
         Commands::Insights(args) => commands::insights::run(args),
         Commands::Hooks(args) => commands::hooks::run(args),
         Commands::Daemon(args) => commands::daemon::run(args),
+        Commands::Machine(args) => commands::machine::run(args),
         Commands::Db(args) => commands::db::run(args),
-        Commands::Sync(args) => commands::sync::run(args),
+        Commands::Sync(args) => commands::sync::run(args, cli.verbose),
         Commands::Doctor(args) => commands::doctor::run(args),
+        Commands::Verify(args) => commands::verify::run(args),
         Commands::Mcp(args) => commands::mcp::run(args),
         Commands::Memories(args) => commands::memories::run(args),
         Commands::Completions(args) => {
@@ -577,6 +702,7 @@ mod tests {
     #[test]
     fn test_should_not_skip_first_run_prompt_status() {
         let command = Commands::Status(commands::status::Args {
+            recent: 5,
             format: OutputFormat::Text,
         });
         assert!(!should_skip_first_run_prompt(&command));
@@ -587,7 +713,12 @@ mod tests {
         let command = Commands::Sessions(commands::sessions::Args {
             repo: None,
             tag: None,
+            branch: None,
+            tool: None,
+            model: None,
             limit: 20,
+            full: false,
+            redact_paths: false,
             format: OutputFormat::Text,
         });
         assert!(!should_skip_first_run_prompt(&command));
@@ -598,6 +729,10 @@ mod tests {
         let command = Commands::Import(commands::import::Args {
             force: false,
             dry_run: false,
+            path: None,
+            tool: None,
+            jobs: None,
+            show_ignored: false,
         });
         assert!(!should_skip_first_run_prompt(&command));
     }
@@ -622,6 +757,128 @@ mod tests {
         assert!(!cli.no_init);
     }
 
+    #[test]
+    fn test_cli_db_flag_is_global() {
+        use clap::Parser;
+        let cli = Cli::try_parse_from(["lore", "status", "--db", "/tmp/other.db"]).unwrap();
+        assert_eq!(cli.db, Some(std::path::PathBuf::from("/tmp/other.db")));
+
+        let cli = Cli::try_parse_from(["lore", "sessions"]).unwrap();
+        assert!(cli.db.is_none());
+    }
+
+    #[test]
+    fn test_cli_color_flag_is_global() {
+        use clap::Parser;
+        let cli = Cli::try_parse_from(["lore", "sessions", "--color", "never"]).unwrap();
+        assert_eq!(cli.color, cli::ColorChoice::Never);
+
+        let cli = Cli::try_parse_from(["lore", "status"]).unwrap();
+        assert_eq!(cli.color, cli::ColorChoice::Auto);
+
+        assert!(Cli::try_parse_from(["lore", "status", "--color", "sometimes"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_quiet_flag_is_global() {
+        use clap::Parser;
+        let cli = Cli::try_parse_from(["lore", "-q", "import"]).unwrap();
+        assert!(cli.quiet);
+
+        // The pre-push hook runs `lore sync --quiet`
+        let cli = Cli::try_parse_from(["lore", "sync", "--remote", "origin", "--quiet"]).unwrap();
+        assert!(cli.quiet);
+
+        let cli = Cli::try_parse_from(["lore", "status"]).unwrap();
+        assert!(!cli.quiet);
+
+        assert!(Cli::try_parse_from(["lore", "--quiet", "--verbose", "status"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_link_strategy() {
+        use clap::Parser;
+        let cli =
+            Cli::try_parse_from(["lore", "link", "--auto", "--strategy", "file-overlap"]).unwrap();
+        let Commands::Link(args) = cli.command else {
+            panic!("expected the link command");
+        };
+        assert_eq!(args.strategy, commands::link::LinkStrategy::FileOverlap);
+
+        let cli = Cli::try_parse_from(["lore", "link", "--auto"]).unwrap();
+        let Commands::Link(args) = cli.command else {
+            panic!("expected the link command");
+        };
+        assert_eq!(args.strategy, commands::link::LinkStrategy::Balanced);
+
+        assert!(Cli::try_parse_from(["lore", "link", "abc123", "--strategy", "time"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_summarize_all() {
+        use clap::Parser;
+        let cli = Cli::try_parse_from([
+            "lore",
+            "summarize",
+            "--all",
+            "--concurrency",
+            "8",
+            "--model",
+            "gpt-4o",
+        ])
+        .unwrap();
+        let Commands::Summarize(args) = cli.command else {
+            panic!("expected the summarize command");
+        };
+        assert!(args.all && args.session.is_none());
+        assert_eq!(args.concurrency, 8);
+
+        assert!(Cli::try_parse_from(["lore", "summarize"]).is_err());
+        assert!(Cli::try_parse_from(["lore", "summarize", "abc123", "--model", "x"]).is_err());
+        assert!(
+            Cli::try_parse_from(["lore", "summarize", "abc123", "--concurrency", "2"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_whoami_alias() {
+        use clap::Parser;
+        let cli = Cli::try_parse_from(["lore", "whoami"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Machine(commands::machine::Args { command: None, .. })
+        ));
+
+        let cli = Cli::try_parse_from(["lore", "machine", "show", "--format", "json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Machine(commands::machine::Args {
+                command: Some(commands::machine::MachineCommand::Show),
+                format: cli::OutputFormat::Json,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_daemon_install_service_alias() {
+        use clap::Parser;
+        let cli = Cli::try_parse_from(["lore", "daemon", "install-service", "--no-start"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Daemon(commands::daemon::Args {
+                command: commands::daemon::DaemonSubcommand::Install { no_start: true }
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["lore", "daemon", "uninstall-service"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Daemon(commands::daemon::Args {
+                command: commands::daemon::DaemonSubcommand::Uninstall
+            })
+        ));
+    }
+
     #[test]
     fn test_cli_no_init_flag_with_verbose() {
         use clap::Parser;
@@ -636,6 +893,7 @@ mod tests {
     #[test]
     fn test_command_name_status() {
         let command = Commands::Status(commands::status::Args {
+            recent: 5,
             format: OutputFormat::Text,
         });
         assert_eq!(command_name(&command), "status");
@@ -646,7 +904,12 @@ mod tests {
         let command = Commands::Sessions(commands::sessions::Args {
             repo: None,
             tag: None,
+            branch: None,
+            tool: None,
+            model: None,
             limit: 20,
+            full: false,
+            redact_paths: false,
             format: OutputFormat::Text,
         });
         assert_eq!(command_name(&command), "sessions");
@@ -657,6 +920,10 @@ mod tests {
         let command = Commands::Import(commands::import::Args {
             force: false,
             dry_run: false,
+            path: None,
+            tool: None,
+            jobs: None,
+            show_ignored: false,
         });
         assert_eq!(command_name(&command), "import");
     }
@@ -8,8 +8,11 @@
 //! - `lore_search`: Search sessions by query with filters
 //! - `lore_get_session`: Get full session transcript by ID
 //! - `lore_list_sessions`: List recent sessions with optional filters
+//! - `lore_list_repos`: List repositories with session counts and last activity
 //! - `lore_get_context`: Get recent session context for a repository
 //! - `lore_get_linked_sessions`: Get sessions linked to a commit
+//! - `lore_get_summary`: Get a session's summary, generating one on demand
+//! - `lore_get_sessions_for_file`: Get sessions that referenced a file path
 //! - `lore_get_memories`: Get a project's memories mirrored from a coding tool
 //! - `lore_search_memories`: Full-text search a project's mirrored memories
 
@@ -19,8 +19,9 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
 use crate::capture::memory::{resolve_project_path, MemoryMirror, CLAUDE_CODE_TOOL};
-use crate::storage::models::{Memory, Message, SearchOptions, Session};
+use crate::storage::models::{Memory, Message, SearchOptions, Session, Summary};
 use crate::storage::Database;
+use crate::summarize;
 
 // ============== Tool Parameter Types ==============
 
@@ -35,8 +36,9 @@ pub struct SearchParams {
     #[schemars(description = "Maximum number of results (default: 10)")]
     pub limit: Option<usize>,
 
-    /// Filter by repository path prefix.
-    #[schemars(description = "Filter by repository path prefix")]
+    /// Filter by repository path prefix. Also accepted as `working_dir`.
+    #[serde(alias = "working_dir")]
+    #[schemars(description = "Filter by repository or working directory path prefix")]
     pub repo: Option<String>,
 
     /// Filter by AI tool name (e.g., claude-code, aider).
@@ -46,8 +48,21 @@ pub struct SearchParams {
     /// Filter to sessions after this date (ISO 8601 or relative like 7d, 2w, 1m).
     #[schemars(description = "Filter to sessions after this date (ISO 8601 or 7d, 2w, 1m)")]
     pub since: Option<String>,
+
+    /// Filter to sessions before this date (ISO 8601 or relative like 7d, 2w, 1m).
+    #[schemars(description = "Filter to sessions before this date (ISO 8601 or 7d, 2w, 1m)")]
+    pub until: Option<String>,
+
+    /// Filter by message role (user, assistant, system, tool).
+    #[schemars(
+        description = "Only match messages with this role: user, assistant, system, or tool"
+    )]
+    pub role: Option<String>,
 }
 
+/// Message roles accepted by the lore_search `role` filter.
+const SEARCH_ROLES: [&str; 4] = ["user", "assistant", "system", "tool"];
+
 /// Parameters for the lore_get_session tool.
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetSessionParams {
@@ -84,6 +99,20 @@ pub struct GetContextParams {
     pub last: Option<bool>,
 }
 
+/// Parameters for the lore_get_summary tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetSummaryParams {
+    /// Session ID (full UUID or prefix).
+    #[schemars(description = "Session ID (full UUID or short prefix like abc123)")]
+    pub session_id: String,
+
+    /// Whether to generate a summary when none is stored.
+    #[schemars(
+        description = "Generate a summary via the configured LLM provider if none is stored (default: true)"
+    )]
+    pub generate: Option<bool>,
+}
+
 /// Parameters for the lore_get_linked_sessions tool.
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetLinkedSessionsParams {
@@ -92,6 +121,24 @@ pub struct GetLinkedSessionsParams {
     pub commit_sha: String,
 }
 
+/// Parameters for the lore_get_sessions_for_file tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetSessionsForFileParams {
+    /// File path to look up (relative to the repository or absolute).
+    #[schemars(
+        description = "File path, relative to the repository (e.g. src/auth.rs) or absolute"
+    )]
+    pub path: String,
+
+    /// Filter by repository path prefix.
+    #[schemars(description = "Only include sessions in this repository path")]
+    pub repo: Option<String>,
+
+    /// Maximum number of sessions to return.
+    #[schemars(description = "Maximum number of sessions (default: 10)")]
+    pub limit: Option<usize>,
+}
+
 /// Parameters for the lore_get_memories tool.
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetMemoriesParams {
@@ -120,6 +167,14 @@ pub struct SearchMemoriesParams {
     pub limit: Option<usize>,
 }
 
+/// Parameters for the lore_list_repos tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListReposParams {
+    /// Maximum number of repositories to return.
+    #[schemars(description = "Maximum number of repositories (default: 20)")]
+    pub limit: Option<usize>,
+}
+
 // ============== Result Types ==============
 
 /// A session in search results.
@@ -179,6 +234,19 @@ pub struct ContextResponse {
     pub recent_messages: Option<Vec<MessageInfo>>,
 }
 
+/// Summary response for a session.
+#[derive(Debug, Serialize)]
+pub struct SummaryResponse {
+    pub session_id: String,
+    pub summary: Option<String>,
+    /// Model that generated the summary; None for hand-written summaries
+    /// and ones stored before the model was recorded.
+    pub model: Option<String>,
+    /// True if the summary was read from the database rather than generated.
+    pub cached: bool,
+    pub generated_at: Option<String>,
+}
+
 /// Linked sessions response.
 #[derive(Debug, Serialize)]
 pub struct LinkedSessionsResponse {
@@ -186,6 +254,22 @@ pub struct LinkedSessionsResponse {
     pub sessions: Vec<SessionInfo>,
 }
 
+/// A session that referenced a file.
+#[derive(Debug, Serialize)]
+pub struct FileSessionInfo {
+    pub session: SessionInfo,
+    /// One-line description: the summary if present, else the first user prompt.
+    pub snippet: String,
+}
+
+/// Response for the lore_get_sessions_for_file tool.
+#[derive(Debug, Serialize)]
+pub struct SessionsForFileResponse {
+    pub path: String,
+    pub total: usize,
+    pub sessions: Vec<FileSessionInfo>,
+}
+
 /// A single mirrored memory in a response.
 #[derive(Debug, Serialize)]
 pub struct MemoryInfo {
@@ -216,6 +300,23 @@ pub struct SearchMemoriesResponse {
     pub memories: Vec<MemoryInfo>,
 }
 
+/// A repository with captured session history.
+#[derive(Debug, Serialize)]
+pub struct RepoInfo {
+    pub path: String,
+    pub name: String,
+    pub remote_url: Option<String>,
+    pub session_count: i32,
+    pub last_session_at: String,
+}
+
+/// Response for the lore_list_repos tool.
+#[derive(Debug, Serialize)]
+pub struct ReposResponse {
+    pub total: usize,
+    pub repositories: Vec<RepoInfo>,
+}
+
 // ============== Server Implementation ==============
 
 /// The Lore MCP server.
@@ -255,8 +356,10 @@ impl LoreServer {
     /// Search Lore sessions by query text with optional filters.
     ///
     /// Searches message content using full-text search. Supports filtering
-    /// by repository, tool, and date range.
-    #[tool(description = "Search Lore session messages for text content")]
+    /// by repository, tool, date range, and message role.
+    #[tool(
+        description = "Search Lore session messages for text content. Narrow results with repo (or working_dir), tool, since, until, and role."
+    )]
     async fn lore_search(
         &self,
         params: Parameters<SearchParams>,
@@ -314,6 +417,29 @@ impl LoreServer {
         }
     }
 
+    /// List repositories that have captured session history.
+    ///
+    /// Lets an assistant see which repositories Lore knows about before
+    /// querying their sessions.
+    #[tool(
+        description = "List repositories with captured Lore sessions, with session counts and last activity"
+    )]
+    async fn lore_list_repos(
+        &self,
+        params: Parameters<ListReposParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let result = list_repos_impl(params);
+        match result {
+            Ok(response) => {
+                let json = serde_json::to_string_pretty(&response)
+                    .unwrap_or_else(|e| format!("Error serializing response: {e}"));
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Err(mcp_error(&format!("List repos failed: {e}"))),
+        }
+    }
+
     /// Get recent session context for a repository.
     ///
     /// Provides a summary of recent sessions for quick orientation.
@@ -334,6 +460,32 @@ impl LoreServer {
         }
     }
 
+    /// Get the summary for a Lore session.
+    ///
+    /// Returns the stored summary if one exists, otherwise generates one via
+    /// the configured summary provider and stores it.
+    #[tool(
+        description = "Get a concise summary of a Lore session, generating one if none is stored"
+    )]
+    async fn lore_get_summary(
+        &self,
+        params: Parameters<GetSummaryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        // Generation makes a blocking HTTP request, so keep it off the async runtime.
+        let result = tokio::task::spawn_blocking(move || get_summary_impl(params))
+            .await
+            .map_err(|e| mcp_error(&format!("Get summary failed: {e}")))?;
+        match result {
+            Ok(response) => {
+                let json = serde_json::to_string_pretty(&response)
+                    .unwrap_or_else(|e| format!("Error serializing response: {e}"));
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Err(mcp_error(&format!("Get summary failed: {e}"))),
+        }
+    }
+
     /// Get sessions linked to a git commit.
     ///
     /// Returns all sessions that have been linked to the specified commit.
@@ -354,6 +506,27 @@ impl LoreServer {
         }
     }
 
+    /// Get sessions that referenced a file.
+    ///
+    /// Looks up the session file index populated on import, so it answers
+    /// questions like "which sessions touched src/auth.rs?".
+    #[tool(description = "Get Lore sessions whose tool calls read or edited a file path")]
+    async fn lore_get_sessions_for_file(
+        &self,
+        params: Parameters<GetSessionsForFileParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let result = get_sessions_for_file_impl(params);
+        match result {
+            Ok(response) => {
+                let json = serde_json::to_string_pretty(&response)
+                    .unwrap_or_else(|e| format!("Error serializing response: {e}"));
+                Ok(CallToolResult::success(vec![Content::text(json)]))
+            }
+            Err(e) => Err(mcp_error(&format!("Get sessions for file failed: {e}"))),
+        }
+    }
+
     /// Get the mirrored memories for a project.
     ///
     /// Reflects the coding tool's per-project memory store (currently Claude
@@ -407,8 +580,9 @@ impl ServerHandler for LoreServer {
             .with_protocol_version(ProtocolVersion::V_2024_11_05)
             .with_instructions(
                 "Lore is a reasoning history system for code. It captures AI coding sessions \
-                 and links them to git commits. Use these tools to search session history, \
-                 view session transcripts, and find sessions linked to commits.",
+                 and links them to git commits. Use these tools to list the repositories \
+                 with history, search session history, view session transcripts, and find \
+                 sessions linked to commits.",
             )
     }
 }
@@ -479,13 +653,33 @@ fn message_to_info(message: &Message) -> MessageInfo {
 /// Implementation of the search tool.
 fn search_impl(params: SearchParams) -> anyhow::Result<SearchResponse> {
     let db = Database::open_default()?;
+    search_from_db(&db, params)
+}
 
+/// Runs a search against the given database.
+fn search_from_db(db: &Database, params: SearchParams) -> anyhow::Result<SearchResponse> {
     // Build search index if needed
     if db.search_index_needs_rebuild()? {
         db.rebuild_search_index()?;
     }
 
     let since = params.since.as_ref().map(|s| parse_date(s)).transpose()?;
+    let until = params.until.as_ref().map(|s| parse_date(s)).transpose()?;
+    if let (Some(since), Some(until)) = (since, until) {
+        if until < since {
+            anyhow::bail!("Invalid date range: until is earlier than since");
+        }
+    }
+
+    let role = params.role.map(|role| role.to_lowercase());
+    if let Some(ref role) = role {
+        if !SEARCH_ROLES.contains(&role.as_str()) {
+            anyhow::bail!(
+                "Invalid role '{role}'. Use one of: {}",
+                SEARCH_ROLES.join(", ")
+            );
+        }
+    }
 
     let options = SearchOptions {
         query: params.query.clone(),
@@ -493,6 +687,8 @@ fn search_impl(params: SearchParams) -> anyhow::Result<SearchResponse> {
         repo: params.repo,
         tool: params.tool,
         since,
+        until,
+        role,
         ..Default::default()
     };
 
@@ -578,6 +774,33 @@ fn list_sessions_impl(params: ListSessionsParams) -> anyhow::Result<Vec<SessionI
     Ok(sessions.iter().map(session_to_info).collect())
 }
 
+/// Implementation of the list_repos tool.
+fn list_repos_impl(params: ListReposParams) -> anyhow::Result<ReposResponse> {
+    let db = Database::open_default()?;
+    list_repos_from_db(&db, params)
+}
+
+/// Lists repositories with session history, most recently used first.
+fn list_repos_from_db(db: &Database, params: ListReposParams) -> anyhow::Result<ReposResponse> {
+    let limit = params.limit.unwrap_or(20);
+    let repositories: Vec<RepoInfo> = db
+        .list_repository_activity(limit)?
+        .into_iter()
+        .map(|repo| RepoInfo {
+            path: repo.path,
+            name: repo.name,
+            remote_url: repo.remote_url,
+            session_count: repo.session_count,
+            last_session_at: repo.last_session_at.to_rfc3339(),
+        })
+        .collect();
+
+    Ok(ReposResponse {
+        total: repositories.len(),
+        repositories,
+    })
+}
+
 /// Implementation of the get_context tool.
 fn get_context_impl(params: GetContextParams) -> anyhow::Result<ContextResponse> {
     let db = Database::open_default()?;
@@ -609,6 +832,72 @@ fn get_context_impl(params: GetContextParams) -> anyhow::Result<ContextResponse>
     })
 }
 
+/// Implementation of the get_summary tool.
+fn get_summary_impl(params: GetSummaryParams) -> anyhow::Result<SummaryResponse> {
+    let db = Database::open_default()?;
+    get_summary_from_db(&db, params)
+}
+
+/// Returns the stored summary for a session, generating one when missing.
+///
+/// A stored summary that was generated by a different model than the one
+/// currently configured is regenerated, unless generation is disabled.
+fn get_summary_from_db(db: &Database, params: GetSummaryParams) -> anyhow::Result<SummaryResponse> {
+    let session_id = resolve_session_id(db, &params.session_id)?;
+    let generate = params.generate.unwrap_or(true);
+
+    let existing = db.get_summary(&session_id)?;
+    if let Some(summary) = &existing {
+        let stale = generate
+            && summary.model.as_deref().is_some_and(|model| {
+                summarize::resolve_config().is_ok_and(|config| config.model_name() != model)
+            });
+        if !stale {
+            return Ok(SummaryResponse {
+                session_id: session_id.to_string(),
+                summary: Some(summary.content.clone()),
+                model: summary.model.clone(),
+                cached: true,
+                generated_at: Some(summary.generated_at.to_rfc3339()),
+            });
+        }
+    }
+
+    if !generate {
+        return Ok(SummaryResponse {
+            session_id: session_id.to_string(),
+            summary: None,
+            model: None,
+            cached: false,
+            generated_at: None,
+        });
+    }
+
+    let messages = db.get_messages(&session_id)?;
+    let generated = summarize::generate_summary(&messages, None)?;
+
+    let summary = Summary {
+        id: uuid::Uuid::new_v4(),
+        session_id,
+        content: generated.content,
+        generated_at: chrono::Utc::now(),
+        model: Some(generated.model),
+    };
+    if existing.is_some() {
+        db.update_summary(&session_id, &summary.content, summary.model.as_deref())?;
+    } else {
+        db.insert_summary(&summary)?;
+    }
+
+    Ok(SummaryResponse {
+        session_id: session_id.to_string(),
+        summary: Some(summary.content),
+        model: summary.model,
+        cached: false,
+        generated_at: Some(summary.generated_at.to_rfc3339()),
+    })
+}
+
 /// Implementation of the get_linked_sessions tool.
 fn get_linked_sessions_impl(
     params: GetLinkedSessionsParams,
@@ -630,6 +919,65 @@ fn get_linked_sessions_impl(
     })
 }
 
+/// Maximum length of a file-session snippet, in characters.
+const SNIPPET_MAX_CHARS: usize = 120;
+
+/// Implementation of the get_sessions_for_file tool.
+fn get_sessions_for_file_impl(
+    params: GetSessionsForFileParams,
+) -> anyhow::Result<SessionsForFileResponse> {
+    let db = Database::open_default()?;
+    get_sessions_for_file_from_db(&db, params)
+}
+
+/// Looks up sessions referencing a file and builds a snippet for each.
+fn get_sessions_for_file_from_db(
+    db: &Database,
+    params: GetSessionsForFileParams,
+) -> anyhow::Result<SessionsForFileResponse> {
+    let limit = params.limit.unwrap_or(10);
+    let mut sessions = db.sessions_touching_file(&params.path, params.repo.as_deref())?;
+    sessions.truncate(limit);
+
+    let mut infos = Vec::with_capacity(sessions.len());
+    for session in &sessions {
+        let snippet = match db.get_summary(&session.id)? {
+            Some(summary) => summary.content,
+            None => db
+                .get_messages(&session.id)?
+                .iter()
+                .find(|m| m.role == crate::storage::MessageRole::User)
+                .map(|m| m.content.text())
+                .unwrap_or_default(),
+        };
+        infos.push(FileSessionInfo {
+            session: session_to_info(session),
+            snippet: one_line_snippet(&snippet),
+        });
+    }
+
+    Ok(SessionsForFileResponse {
+        path: params.path,
+        total: infos.len(),
+        sessions: infos,
+    })
+}
+
+/// Reduces text to its first non-empty line, truncated to [`SNIPPET_MAX_CHARS`].
+fn one_line_snippet(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    if line.chars().count() > SNIPPET_MAX_CHARS {
+        let truncated: String = line.chars().take(SNIPPET_MAX_CHARS).collect();
+        format!("{truncated}...")
+    } else {
+        line.to_string()
+    }
+}
+
 /// Converts a Memory to MemoryInfo.
 fn memory_to_info(memory: &Memory) -> MemoryInfo {
     MemoryInfo {
@@ -762,6 +1110,9 @@ mod tests {
             source_path: None,
             message_count: 10,
             machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
 
         let info = session_to_info(&session);
@@ -797,6 +1148,284 @@ mod tests {
         assert_eq!(info.content, "Hello, world!");
     }
 
+    fn create_db_with_session() -> (Database, tempfile::TempDir, Session) {
+        use chrono::Utc;
+        use uuid::Uuid;
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        let session = Session {
+            id: Uuid::new_v4(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+            started_at: Utc::now(),
+            ended_at: None,
+            model: None,
+            working_directory: "/home/user/project".to_string(),
+            git_branch: None,
+            source_path: None,
+            message_count: 0,
+            machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
+        };
+        db.insert_session(&session).unwrap();
+        (db, dir, session)
+    }
+
+    /// Creates a database with an older session in one repository and a
+    /// recent one in another, each with messages mentioning "deploy".
+    fn create_db_for_search() -> (Database, tempfile::TempDir, Session, Session) {
+        use crate::storage::models::{MessageContent, MessageRole};
+        use chrono::{Duration, Utc};
+
+        // The upsert keeps started_at, so add new sessions beside the one
+        // created at the current time
+        let (db, dir, template) = create_db_with_session();
+        let old = Session {
+            id: uuid::Uuid::new_v4(),
+            started_at: Utc::now() - Duration::days(30),
+            ..template.clone()
+        };
+        let recent = Session {
+            id: uuid::Uuid::new_v4(),
+            started_at: Utc::now() - Duration::days(1),
+            working_directory: "/home/user/other".to_string(),
+            ..template
+        };
+        db.insert_session(&old).unwrap();
+        db.insert_session(&recent).unwrap();
+
+        let messages = [
+            (&old, MessageRole::User, "Please deploy the service"),
+            (&old, MessageRole::Assistant, "The deploy finished"),
+            (&recent, MessageRole::User, "Deploy it again"),
+        ];
+        for (index, (session, role, text)) in messages.into_iter().enumerate() {
+            db.insert_message(&Message {
+                id: uuid::Uuid::new_v4(),
+                session_id: session.id,
+                parent_id: None,
+                index: index as i32,
+                timestamp: session.started_at,
+                role,
+                content: MessageContent::Text(text.to_string()),
+                model: None,
+                git_branch: None,
+                cwd: None,
+            })
+            .unwrap();
+        }
+        (db, dir, old, recent)
+    }
+
+    /// Runs lore_search with JSON arguments, as an MCP client sends them.
+    fn search(db: &Database, args: serde_json::Value) -> anyhow::Result<SearchResponse> {
+        search_from_db(db, serde_json::from_value(args).unwrap())
+    }
+
+    /// Returns the session ids of the matches, in result order.
+    fn match_sessions(response: &SearchResponse) -> Vec<String> {
+        response
+            .matches
+            .iter()
+            .map(|m| m.session.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_search_filters_by_repo_and_working_dir() {
+        let (db, _dir, old, _recent) = create_db_for_search();
+
+        let all = search(&db, serde_json::json!({"query": "deploy"})).unwrap();
+        assert_eq!(all.total_matches, 3);
+
+        let by_repo = search(
+            &db,
+            serde_json::json!({"query": "deploy", "repo": "/home/user/project"}),
+        )
+        .unwrap();
+        assert_eq!(match_sessions(&by_repo), vec![old.id.to_string(); 2]);
+
+        let by_working_dir = search(
+            &db,
+            serde_json::json!({"query": "deploy", "working_dir": "/home/user/project"}),
+        )
+        .unwrap();
+        assert_eq!(match_sessions(&by_working_dir), match_sessions(&by_repo));
+        assert!(by_working_dir
+            .matches
+            .iter()
+            .all(|m| m.snippet.to_lowercase().contains("deploy")));
+    }
+
+    #[test]
+    fn test_search_filters_by_since_and_until() {
+        let (db, _dir, old, recent) = create_db_for_search();
+
+        let since = search(&db, serde_json::json!({"query": "deploy", "since": "7d"})).unwrap();
+        assert_eq!(match_sessions(&since), vec![recent.id.to_string()]);
+
+        let until = search(&db, serde_json::json!({"query": "deploy", "until": "7d"})).unwrap();
+        assert_eq!(match_sessions(&until), vec![old.id.to_string(); 2]);
+
+        let inverted = search(
+            &db,
+            serde_json::json!({"query": "deploy", "since": "1d", "until": "7d"}),
+        );
+        assert!(inverted.is_err());
+    }
+
+    #[test]
+    fn test_search_filters_by_role() {
+        let (db, _dir, old, _recent) = create_db_for_search();
+
+        let response = search(
+            &db,
+            serde_json::json!({"query": "deploy", "role": "Assistant"}),
+        )
+        .unwrap();
+        assert_eq!(match_sessions(&response), vec![old.id.to_string()]);
+        assert_eq!(response.matches[0].role, "assistant");
+
+        let user = search(&db, serde_json::json!({"query": "deploy", "role": "user"})).unwrap();
+        assert_eq!(user.total_matches, 2);
+
+        let invalid = search(&db, serde_json::json!({"query": "deploy", "role": "robot"}));
+        assert!(invalid.unwrap_err().to_string().contains("Invalid role"));
+    }
+
+    #[test]
+    fn test_get_summary_returns_cached() {
+        let (db, _dir, session) = create_db_with_session();
+        db.insert_summary(&Summary {
+            id: uuid::Uuid::new_v4(),
+            session_id: session.id,
+            content: "Refactored the auth module.".to_string(),
+            generated_at: chrono::Utc::now(),
+            model: None,
+        })
+        .unwrap();
+
+        let params = GetSummaryParams {
+            session_id: session.id.to_string()[..8].to_string(),
+            generate: None,
+        };
+        let response = get_summary_from_db(&db, params).unwrap();
+
+        assert!(response.cached);
+        assert_eq!(
+            response.summary.as_deref(),
+            Some("Refactored the auth module.")
+        );
+        assert_eq!(response.session_id, session.id.to_string());
+    }
+
+    #[test]
+    fn test_list_repos_reports_session_counts() {
+        let (db, _dir, session) = create_db_with_session();
+        db.upsert_repository(
+            &session.working_directory,
+            "project",
+            Some("https://github.com/user/project.git"),
+            session.started_at,
+        )
+        .unwrap();
+
+        let response = list_repos_from_db(&db, ListReposParams { limit: None }).unwrap();
+
+        assert_eq!(response.total, 1);
+        let repo = &response.repositories[0];
+        assert_eq!(repo.path, "/home/user/project");
+        assert_eq!(repo.name, "project");
+        assert_eq!(
+            repo.remote_url.as_deref(),
+            Some("https://github.com/user/project.git")
+        );
+        assert_eq!(repo.session_count, 1);
+        assert_eq!(repo.last_session_at, session.started_at.to_rfc3339());
+    }
+
+    #[test]
+    fn test_get_summary_missing_without_generate() {
+        let (db, _dir, session) = create_db_with_session();
+
+        let params = GetSummaryParams {
+            session_id: session.id.to_string(),
+            generate: Some(false),
+        };
+        let response = get_summary_from_db(&db, params).unwrap();
+
+        assert!(!response.cached);
+        assert!(response.summary.is_none());
+        assert!(response.model.is_none());
+    }
+
+    #[test]
+    fn test_get_sessions_for_file() {
+        use crate::storage::models::{ContentBlock, MessageContent, MessageRole};
+
+        let (db, _dir, session) = create_db_with_session();
+        db.insert_message(&Message {
+            id: uuid::Uuid::new_v4(),
+            session_id: session.id,
+            parent_id: None,
+            index: 0,
+            timestamp: chrono::Utc::now(),
+            role: MessageRole::User,
+            content: MessageContent::Text("\nFix the login bug\nin auth".to_string()),
+            model: None,
+            git_branch: None,
+            cwd: None,
+        })
+        .unwrap();
+        db.insert_message(&Message {
+            id: uuid::Uuid::new_v4(),
+            session_id: session.id,
+            parent_id: None,
+            index: 1,
+            timestamp: chrono::Utc::now(),
+            role: MessageRole::Assistant,
+            content: MessageContent::Blocks(vec![ContentBlock::ToolUse {
+                id: "edit-1".to_string(),
+                name: "Edit".to_string(),
+                input: serde_json::json!({"file_path": "/home/user/project/src/auth.rs"}),
+            }]),
+            model: None,
+            git_branch: None,
+            cwd: None,
+        })
+        .unwrap();
+
+        let params = GetSessionsForFileParams {
+            path: "src/auth.rs".to_string(),
+            repo: Some("/home/user/project".to_string()),
+            limit: None,
+        };
+        let response = get_sessions_for_file_from_db(&db, params).unwrap();
+
+        assert_eq!(response.total, 1);
+        assert_eq!(response.sessions[0].session.id, session.id.to_string());
+        assert_eq!(response.sessions[0].snippet, "Fix the login bug");
+
+        let params = GetSessionsForFileParams {
+            path: "src/other.rs".to_string(),
+            repo: None,
+            limit: None,
+        };
+        assert_eq!(get_sessions_for_file_from_db(&db, params).unwrap().total, 0);
+    }
+
+    #[test]
+    fn test_one_line_snippet_truncates() {
+        let long = "x".repeat(200);
+        let snippet = one_line_snippet(&long);
+        assert_eq!(snippet.chars().count(), SNIPPET_MAX_CHARS + 3);
+        assert!(snippet.ends_with("..."));
+        assert_eq!(one_line_snippet(""), "");
+    }
+
     #[test]
     fn test_memory_to_info() {
         use chrono::Utc;
@@ -4,16 +4,24 @@
 //! messages, and session-to-commit links. Uses SQLite for local-first
 //! persistence with automatic schema migrations.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use chrono::{DateTime, Utc};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use rusqlite::types::{Value, ValueRef};
 use rusqlite::{params, Connection, OptionalExtension};
 use std::collections::HashSet;
+use std::fmt;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use uuid::Uuid;
 
 use super::models::{
-    Annotation, Machine, Memory, Message, MessageContent, MessageRole, SearchResult, Session,
-    SessionLink, Summary, Tag, Tombstone,
+    extract_session_files, Annotation, Machine, Memory, Message, MessageContent, MessageRole,
+    MessageThread, Repository, SearchResult, Session, SessionLink, SourceStamp, Summary, Tag,
+    Tombstone,
 };
 
 /// Tombstone kind for a deleted session-to-commit link.
@@ -25,6 +33,9 @@ const TOMBSTONE_KIND_ANNOTATION: &str = "annotation";
 /// Tombstone kind for a deleted summary.
 const TOMBSTONE_KIND_SUMMARY: &str = "summary";
 
+/// SQL predicate excluding sessions removed from the sync store.
+const NOT_SYNC_EXCLUDED: &str = "id NOT IN (SELECT session_id FROM sync_exclusions)";
+
 /// Which sync-tracking column a merge or import marks on write.
 ///
 /// A session carries two independent sync tracks: the per-repo store
@@ -50,6 +61,104 @@ impl SyncTrack {
     }
 }
 
+/// A session or commit prefix that matches more than one ID.
+///
+/// Returned (inside `anyhow::Error`) by the prefix lookups, so a caller can
+/// downcast it to get every candidate. Its message lists the first few, such
+/// as "Ambiguous session prefix 'ab', matches: ab12cd34..., ab34ef56...".
+#[derive(Debug, thiserror::Error)]
+#[error("Ambiguous {kind} prefix '{prefix}', matches: {}", list_candidates(.candidates))]
+pub struct AmbiguousPrefix {
+    /// What the prefix identifies, "session" or "commit".
+    pub kind: &'static str,
+    /// The prefix as given.
+    pub prefix: String,
+    /// Every full ID the prefix matches, most recent first.
+    pub candidates: Vec<String>,
+}
+
+/// How many candidates an [`AmbiguousPrefix`] message lists.
+const LISTED_CANDIDATES: usize = 5;
+
+/// Formats the first few candidates of an ambiguous prefix, shortened.
+fn list_candidates(candidates: &[String]) -> String {
+    let mut listed: Vec<String> = candidates
+        .iter()
+        .take(LISTED_CANDIDATES)
+        .map(|id| format!("{}...", id.chars().take(8).collect::<String>()))
+        .collect();
+    if candidates.len() > LISTED_CANDIDATES {
+        listed.push(format!("and {} more", candidates.len() - LISTED_CANDIDATES));
+    }
+    listed.join(", ")
+}
+
+/// How a sync pull resolves a remote session that differs from the local row.
+///
+/// Only the session row and its messages are subject to the strategy; links,
+/// tags, annotations, and summaries are always merged additively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictStrategy {
+    /// Keep the local copy and report the session as a conflict.
+    Skip,
+    /// Always take the remote copy.
+    PreferRemote,
+    /// Always keep the local copy.
+    PreferLocal,
+    /// Take whichever copy is newer (more messages, then later `ended_at`).
+    #[default]
+    Newest,
+}
+
+/// What a sync pull did with one remote session row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// The remote row and messages were written.
+    Imported,
+    /// The local row already matches the remote one.
+    Unchanged,
+    /// The rows differ and the local copy was kept by the strategy.
+    KeptLocal,
+    /// The rows differ and were left alone under [`ConflictStrategy::Skip`].
+    Conflict,
+}
+
+/// Decides how to merge a remote session row into an existing local one.
+///
+/// The rows conflict when their message counts or `ended_at` differ; rows
+/// that match are [`MergeOutcome::Unchanged`] under every strategy. For
+/// [`ConflictStrategy::Newest`], a strictly higher remote message count wins
+/// outright; otherwise a later remote `ended_at` wins (a remote `ended_at`
+/// against a local `None` also wins).
+pub fn resolve_session_conflict(
+    strategy: ConflictStrategy,
+    remote: &Session,
+    local: &Session,
+) -> MergeOutcome {
+    if remote.message_count == local.message_count && remote.ended_at == local.ended_at {
+        return MergeOutcome::Unchanged;
+    }
+
+    match strategy {
+        ConflictStrategy::Skip => MergeOutcome::Conflict,
+        ConflictStrategy::PreferRemote => MergeOutcome::Imported,
+        ConflictStrategy::PreferLocal => MergeOutcome::KeptLocal,
+        ConflictStrategy::Newest => {
+            let remote_newer = remote.message_count > local.message_count
+                || match (remote.ended_at, local.ended_at) {
+                    (Some(r), Some(l)) => r > l,
+                    (Some(_), None) => true,
+                    _ => false,
+                };
+            if remote_newer {
+                MergeOutcome::Imported
+            } else {
+                MergeOutcome::KeptLocal
+            }
+        }
+    }
+}
+
 /// Builds the SQL parameters for a path-boundary directory match.
 ///
 /// Returns `(exact, trailing, like_pattern)` for matching a
@@ -62,9 +171,9 @@ impl SyncTrack {
 /// LIKE metacharacters are escaped so a directory containing `%` or `_` cannot
 /// widen the match.
 ///
-/// Shared by [`Database::find_active_sessions_for_directory`] and
-/// [`Database::get_unsynced_sessions_for_repo`] so both scope sessions to a
-/// directory the same way.
+/// Shared by [`Database::find_active_sessions_for_directory`],
+/// [`Database::get_unsynced_sessions_for_repo`] and the prune queries so all
+/// scope sessions to a directory the same way.
 fn directory_match_params(directory: &str) -> (String, String, String) {
     fn escape_like(input: &str) -> String {
         let mut escaped = String::with_capacity(input.len());
@@ -151,6 +260,51 @@ fn repo_scope_predicate(repo_path: &Path) -> (String, Vec<String>) {
     (clauses.join(" OR "), binds)
 }
 
+/// Builds an `AND` condition matching sessions (aliased `s`) whose working
+/// directory, or any directory their messages ran in, matches the prefix
+/// pattern bound at `?{param}`.
+fn session_directory_filter(param: usize) -> String {
+    format!(
+        " AND (s.working_directory LIKE ?{param} OR s.id IN \
+         (SELECT session_id FROM session_directories WHERE directory LIKE ?{param}))"
+    )
+}
+
+/// Builds the WHERE clause and bind values for selecting sessions to prune.
+///
+/// Always bounds `started_at` by `before`; `tool` matches exactly and
+/// `working_dir` matches the directory itself or anything below it (see
+/// [`directory_match_params`]).
+fn prune_conditions(
+    before: DateTime<Utc>,
+    tool: Option<&str>,
+    working_dir: Option<&str>,
+) -> (String, Vec<Box<dyn rusqlite::types::ToSql>>) {
+    let mut conditions = vec!["started_at < ?1".to_string()];
+    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> =
+        vec![Box::new(before.to_rfc3339())];
+
+    if let Some(tool) = tool {
+        conditions.push(format!("tool = ?{}", param_values.len() + 1));
+        param_values.push(Box::new(tool.to_string()));
+    }
+    if let Some(wd) = working_dir {
+        let next = param_values.len() + 1;
+        conditions.push(format!(
+            "(working_directory = ?{} OR working_directory = ?{} OR working_directory LIKE ?{} ESCAPE '|')",
+            next,
+            next + 1,
+            next + 2
+        ));
+        let (exact, trailing, like_pattern) = directory_match_params(wd);
+        param_values.push(Box::new(exact));
+        param_values.push(Box::new(trailing));
+        param_values.push(Box::new(like_pattern));
+    }
+
+    (conditions.join(" AND "), param_values)
+}
+
 /// Parses a UUID from a string, converting errors to rusqlite errors.
 ///
 /// Used in row mapping functions where we need to return rusqlite::Result.
@@ -171,6 +325,74 @@ fn parse_datetime(s: &str) -> rusqlite::Result<DateTime<Utc>> {
         })
 }
 
+/// Parses a stored message role. Unknown roles read back as user messages.
+fn role_from_str(role: &str) -> MessageRole {
+    match role {
+        "assistant" => MessageRole::Assistant,
+        "system" => MessageRole::System,
+        "tool" => MessageRole::Tool,
+        _ => MessageRole::User,
+    }
+}
+
+/// First byte of message content stored compressed.
+///
+/// Plain content is stored as JSON text, which never starts with this byte,
+/// so rows written before compression was enabled read back unchanged.
+const COMPRESSED_CONTENT_HEADER: u8 = 0x01;
+
+/// Serialized message content at least this many bytes long is compressed
+/// when content compression is enabled; shorter content gains little.
+pub const COMPRESS_CONTENT_THRESHOLD: usize = 1024;
+
+/// Encodes serialized message content for the `messages.content` column.
+///
+/// With `compress` set, JSON of at least [`COMPRESS_CONTENT_THRESHOLD`] bytes
+/// is stored as a blob of [`COMPRESSED_CONTENT_HEADER`] followed by the
+/// zlib-compressed JSON. Anything else is stored as plain text.
+fn encode_content(json: String, compress: bool) -> Result<Value> {
+    if !compress || json.len() < COMPRESS_CONTENT_THRESHOLD {
+        return Ok(Value::Text(json));
+    }
+    let mut encoder = ZlibEncoder::new(vec![COMPRESSED_CONTENT_HEADER], Compression::default());
+    encoder.write_all(json.as_bytes())?;
+    Ok(Value::Blob(encoder.finish()?))
+}
+
+/// Returns the serialized JSON of a `messages.content` value written by
+/// [`encode_content`], decompressing it if needed.
+fn decode_content(value: ValueRef<'_>) -> rusqlite::Result<String> {
+    let conversion_error = |e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(0, value.data_type(), e)
+    };
+    match value {
+        ValueRef::Blob([COMPRESSED_CONTENT_HEADER, compressed @ ..]) => {
+            let mut json = String::new();
+            ZlibDecoder::new(compressed)
+                .read_to_string(&mut json)
+                .map_err(|e| conversion_error(Box::new(e)))?;
+            Ok(json)
+        }
+        _ => value
+            .as_str()
+            .map(str::to_string)
+            .map_err(|e| conversion_error(Box::new(e))),
+    }
+}
+
+/// Returns true if an error indicates a corrupt or malformed FTS index.
+fn is_fts_corruption(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<rusqlite::Error>() {
+        Some(rusqlite::Error::SqliteFailure(e, msg)) => {
+            e.code == rusqlite::ErrorCode::DatabaseCorrupt
+                || msg
+                    .as_deref()
+                    .is_some_and(|m| m.contains("malformed") || m.contains("fts5: corrupt"))
+        }
+        _ => false,
+    }
+}
+
 /// Escapes a query string for FTS5 by wrapping each word in double quotes.
 ///
 /// FTS5 has special syntax characters (e.g., /, *, AND, OR, NOT) that need
@@ -187,21 +409,141 @@ fn escape_fts5_query(query: &str) -> String {
         .join(" ")
 }
 
-/// Returns the default database path at `~/.lore/lore.db`.
+/// Number of characters kept on each side of a regex match in its snippet.
+const REGEX_SNIPPET_CONTEXT: usize = 60;
+
+/// Builds a search snippet around the match at `start..end` of `text`.
+///
+/// Uses the `**` highlight markers and `...` ellipses of FTS5 snippets, so
+/// regex results display like full-text ones.
+fn regex_snippet(text: &str, start: usize, end: usize) -> String {
+    let before: String = {
+        let mut chars: Vec<char> = text[..start]
+            .chars()
+            .rev()
+            .take(REGEX_SNIPPET_CONTEXT + 1)
+            .collect();
+        let cut = chars.len() > REGEX_SNIPPET_CONTEXT;
+        chars.truncate(REGEX_SNIPPET_CONTEXT);
+        let kept: String = chars.into_iter().rev().collect();
+        if cut {
+            format!("...{kept}")
+        } else {
+            kept
+        }
+    };
+    let after: String = {
+        let mut chars = text[end..].chars();
+        let kept: String = chars.by_ref().take(REGEX_SNIPPET_CONTEXT).collect();
+        if chars.next().is_some() {
+            format!("{kept}...")
+        } else {
+            kept
+        }
+    };
+    format!("{before}**{}**{after}", &text[start..end])
+}
+
+/// Environment variable that overrides the database location.
+///
+/// The global `--db` flag sets this variable, so the flag takes precedence
+/// over a value inherited from the environment.
+pub const DB_PATH_ENV: &str = "LORE_DB_PATH";
+
+/// Returns the database path, `~/.lore/lore.db` unless overridden.
 ///
-/// Creates the `.lore` directory if it does not exist.
+/// When `LORE_DB_PATH` is set to a non-empty value, that path is used
+/// instead. Creates the database's parent directory if it does not exist.
 pub fn default_db_path() -> Result<PathBuf> {
-    let config_dir = dirs::home_dir()
-        .context("Could not find home directory. Ensure your HOME environment variable is set.")?
-        .join(".lore");
-
-    std::fs::create_dir_all(&config_dir).with_context(|| {
-        format!(
-            "Failed to create Lore data directory at {}. Check directory permissions.",
-            config_dir.display()
-        )
-    })?;
-    Ok(config_dir.join("lore.db"))
+    let override_path = std::env::var_os(DB_PATH_ENV)
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from);
+    resolve_db_path(override_path)
+}
+
+/// Resolves the database path from an optional override.
+///
+/// Relative overrides are made absolute against the current directory so
+/// the daemon, which may run from elsewhere, opens the same file.
+fn resolve_db_path(override_path: Option<PathBuf>) -> Result<PathBuf> {
+    let db_path = match override_path {
+        Some(path) => std::path::absolute(&path)
+            .with_context(|| format!("Invalid database path {}", path.display()))?,
+        None => dirs::home_dir()
+            .context(
+                "Could not find home directory. Ensure your HOME environment variable is set.",
+            )?
+            .join(".lore")
+            .join("lore.db"),
+    };
+
+    if let Some(data_dir) = db_path.parent() {
+        std::fs::create_dir_all(data_dir).with_context(|| {
+            format!(
+                "Failed to create Lore data directory at {}. Check directory permissions.",
+                data_dir.display()
+            )
+        })?;
+    }
+    Ok(db_path)
+}
+
+/// FTS5 tokenizer used for the message search index.
+///
+/// Set with the `search_tokenizer` config key. The tokenizer is fixed when
+/// the index is created, so changing the setting only takes effect after
+/// `lore db reindex`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchTokenizer {
+    /// Unicode word tokens with English stemming, so "running" matches
+    /// "run" (default).
+    #[default]
+    Porter,
+    /// Unicode word tokens without stemming.
+    Unicode61,
+    /// Three-character sequences, so any substring of three or more
+    /// characters matches, such as part of an identifier or a path.
+    Trigram,
+}
+
+impl SearchTokenizer {
+    /// Names accepted by `search_tokenizer`, in declaration order.
+    pub const NAMES: &'static [&'static str] = &["porter", "unicode61", "trigram"];
+
+    /// Returns the FTS5 `tokenize` option for this tokenizer.
+    fn fts5_option(self) -> &'static str {
+        match self {
+            SearchTokenizer::Porter => "porter unicode61",
+            SearchTokenizer::Unicode61 => "unicode61",
+            SearchTokenizer::Trigram => "trigram",
+        }
+    }
+}
+
+impl fmt::Display for SearchTokenizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchTokenizer::Porter => write!(f, "porter"),
+            SearchTokenizer::Unicode61 => write!(f, "unicode61"),
+            SearchTokenizer::Trigram => write!(f, "trigram"),
+        }
+    }
+}
+
+impl FromStr for SearchTokenizer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "porter" => Ok(SearchTokenizer::Porter),
+            "unicode61" => Ok(SearchTokenizer::Unicode61),
+            "trigram" => Ok(SearchTokenizer::Trigram),
+            other => Err(format!(
+                "Unknown search tokenizer: '{other}'. Expected one of: {}",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
 }
 
 /// SQLite database connection wrapper.
@@ -211,25 +553,71 @@ pub fn default_db_path() -> Result<PathBuf> {
 /// when opening the database.
 pub struct Database {
     conn: Connection,
+    /// Whether large message content is compressed when it is written.
+    compress_content: bool,
+    /// Tokenizer for the message search index when it is (re)created.
+    search_tokenizer: SearchTokenizer,
 }
 
 impl Database {
     /// Opens or creates a database at the specified path.
     ///
-    /// Runs schema migrations automatically to ensure tables exist.
+    /// Runs schema migrations automatically to ensure tables exist. Opening
+    /// does not check the search index, since that counts every message;
+    /// `lore search`, `lore verify` and daemon start-up check it with
+    /// [`Database::search_index_needs_rebuild`].
     pub fn open(path: &PathBuf) -> Result<Self> {
+        Self::open_with_tokenizer(path, SearchTokenizer::default())
+    }
+
+    /// Opens or creates a database, creating a missing message search index
+    /// with the given tokenizer.
+    ///
+    /// An existing index keeps the tokenizer it was built with until
+    /// [`Database::rebuild_search_index`] recreates it.
+    pub fn open_with_tokenizer(path: &PathBuf, search_tokenizer: SearchTokenizer) -> Result<Self> {
         let conn = Connection::open(path)?;
-        let db = Self { conn };
+        let db = Self {
+            conn,
+            compress_content: false,
+            search_tokenizer,
+        };
         db.migrate()?;
         Ok(db)
     }
 
-    /// Opens the default database at `~/.lore/lore.db`.
+    /// Opens the default database at `~/.lore/lore.db`, or at the path
+    /// given by `--db` or `LORE_DB_PATH`.
     ///
     /// Creates the database file and directory if they do not exist.
+    /// Message content is compressed on write when the `compress_content`
+    /// setting is enabled, and the search index uses the `search_tokenizer`
+    /// setting when it is created.
     pub fn open_default() -> Result<Self> {
         let path = default_db_path()?;
-        Self::open(&path)
+        let config = crate::config::Config::load().ok();
+        let tokenizer = config
+            .as_ref()
+            .and_then(|c| c.search_tokenizer.as_deref())
+            .and_then(|name| name.parse().ok())
+            .unwrap_or_default();
+        let mut db = Self::open_with_tokenizer(&path, tokenizer)?;
+        db.set_compress_content(config.is_some_and(|c| c.compress_content));
+        Ok(db)
+    }
+
+    /// Sets whether message content of at least
+    /// [`COMPRESS_CONTENT_THRESHOLD`] bytes is compressed when written.
+    ///
+    /// Reads are unaffected: compressed and plain rows are always readable.
+    pub fn set_compress_content(&mut self, enabled: bool) {
+        self.compress_content = enabled;
+    }
+
+    /// Sets the tokenizer used the next time the message search index is
+    /// created, such as by [`Database::rebuild_search_index`].
+    pub fn set_search_tokenizer(&mut self, tokenizer: SearchTokenizer) {
+        self.search_tokenizer = tokenizer;
     }
 
     /// Runs database schema migrations.
@@ -237,6 +625,9 @@ impl Database {
     /// Creates tables for sessions, messages, session_links, and repositories
     /// if they do not already exist. Also creates indexes for common queries.
     fn migrate(&self) -> Result<()> {
+        let had_session_files = self.table_exists("session_files")?;
+        let had_session_directories = self.table_exists("session_directories")?;
+
         self.conn.execute_batch(
             r#"
             CREATE TABLE IF NOT EXISTS schema_version (
@@ -255,7 +646,10 @@ impl Database {
                 source_path TEXT,
                 message_count INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL DEFAULT (datetime('now')),
-                machine_id TEXT
+                machine_id TEXT,
+                input_tokens INTEGER,
+                output_tokens INTEGER,
+                parent_session_id TEXT
             );
 
             CREATE TABLE IF NOT EXISTS messages (
@@ -282,6 +676,13 @@ impl Database {
                 created_at TEXT NOT NULL,
                 created_by TEXT NOT NULL,
                 confidence REAL,
+                forge_host TEXT,
+                forge_owner TEXT,
+                forge_repo TEXT,
+                branch_score REAL,
+                file_score REAL,
+                time_score REAL,
+                recency_score REAL,
                 FOREIGN KEY (session_id) REFERENCES sessions(id)
             );
 
@@ -316,6 +717,7 @@ impl Database {
                 session_id TEXT NOT NULL UNIQUE,
                 content TEXT NOT NULL,
                 generated_at TEXT NOT NULL,
+                model TEXT,
                 FOREIGN KEY (session_id) REFERENCES sessions(id)
             );
 
@@ -356,10 +758,75 @@ impl Database {
                 PRIMARY KEY (child_id, kind)
             );
 
+            -- Sessions removed from the sync store by `lore sync delete`. Kept
+            -- apart from tombstones (which only cover child records) because a
+            -- session exclusion leaves the local session intact and only stops
+            -- it from being pushed or re-imported.
+            CREATE TABLE IF NOT EXISTS sync_exclusions (
+                session_id TEXT PRIMARY KEY,
+                excluded_at TEXT NOT NULL
+            );
+
+            -- File paths referenced by each session's tool calls, as extracted by
+            -- models::extract_session_files. Paths are stored relative to the
+            -- session's working directory when possible so file-based lookups
+            -- don't need to re-scan message content.
+            CREATE TABLE IF NOT EXISTS session_files (
+                session_id TEXT NOT NULL,
+                path TEXT NOT NULL,
+                PRIMARY KEY (session_id, path),
+                FOREIGN KEY (session_id) REFERENCES sessions(id)
+            );
+
+            -- Distinct working directories seen in each session's messages.
+            -- A session can move between repos mid-conversation, so repo
+            -- filters match a session if any of its directories match, not
+            -- only the single sessions.working_directory.
+            CREATE TABLE IF NOT EXISTS session_directories (
+                session_id TEXT NOT NULL,
+                directory TEXT NOT NULL,
+                PRIMARY KEY (session_id, directory),
+                FOREIGN KEY (session_id) REFERENCES sessions(id)
+            );
+
+            -- Sessions folded into another session by `lore merge-sessions`.
+            -- The merged session's row is deleted, so import and the daemon
+            -- check this table to avoid re-creating it from its source file.
+            CREATE TABLE IF NOT EXISTS merged_sessions (
+                session_id TEXT PRIMARY KEY,
+                merged_into TEXT NOT NULL,
+                merged_at TEXT NOT NULL
+            );
+
+            -- Sessions deleted by the daemon's retention pruning. Their source
+            -- files usually still exist, so import, the daemon and sync pulls
+            -- check this table to avoid bringing them back.
+            CREATE TABLE IF NOT EXISTS pruned_sessions (
+                session_id TEXT PRIMARY KEY,
+                pruned_at TEXT NOT NULL
+            );
+
+            -- Auto-link decisions, recorded when auto_link_audit is enabled
+            CREATE TABLE IF NOT EXISTS link_decisions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                commit_sha TEXT NOT NULL,
+                branch_score REAL NOT NULL,
+                file_score REAL NOT NULL,
+                time_score REAL NOT NULL,
+                recency_score REAL NOT NULL,
+                confidence REAL NOT NULL,
+                threshold REAL NOT NULL,
+                passed INTEGER NOT NULL,
+                decided_at TEXT NOT NULL
+            );
+
             -- Indexes for common queries
             CREATE INDEX IF NOT EXISTS idx_sessions_started_at ON sessions(started_at);
+            CREATE INDEX IF NOT EXISTS idx_link_decisions_commit ON link_decisions(commit_sha);
             CREATE INDEX IF NOT EXISTS idx_sessions_working_directory ON sessions(working_directory);
             CREATE INDEX IF NOT EXISTS idx_messages_session_id ON messages(session_id);
+            CREATE INDEX IF NOT EXISTS idx_messages_git_branch ON messages(git_branch);
             CREATE INDEX IF NOT EXISTS idx_session_links_session_id ON session_links(session_id);
             CREATE INDEX IF NOT EXISTS idx_session_links_commit_sha ON session_links(commit_sha);
             CREATE INDEX IF NOT EXISTS idx_annotations_session_id ON annotations(session_id);
@@ -367,36 +834,12 @@ impl Database {
             CREATE INDEX IF NOT EXISTS idx_tags_label ON tags(label);
             CREATE INDEX IF NOT EXISTS idx_tombstones_deleted_at ON tombstones(deleted_at);
             CREATE INDEX IF NOT EXISTS idx_memories_project ON memories(project_path, source_tool);
+            CREATE INDEX IF NOT EXISTS idx_session_files_path ON session_files(path);
+            CREATE INDEX IF NOT EXISTS idx_session_directories_directory ON session_directories(directory);
             "#,
         )?;
 
-        // Create FTS5 virtual table for full-text search on message content.
-        // This is a standalone FTS table (not content-synced) because we need to
-        // store extracted text content, not the raw JSON from the messages table.
-        // The message_id column stores the UUID string for joining back to messages.
-        self.conn.execute_batch(
-            r#"
-            CREATE VIRTUAL TABLE IF NOT EXISTS messages_fts USING fts5(
-                message_id,
-                text_content,
-                tokenize='porter unicode61'
-            );
-            "#,
-        )?;
-
-        // Create FTS5 virtual table for session metadata search.
-        // Allows searching by project name, branch, tool, and working directory.
-        self.conn.execute_batch(
-            r#"
-            CREATE VIRTUAL TABLE IF NOT EXISTS sessions_fts USING fts5(
-                session_id,
-                tool,
-                working_directory,
-                git_branch,
-                tokenize='porter unicode61'
-            );
-            "#,
-        )?;
+        self.create_search_tables()?;
 
         // Create FTS5 virtual table for full-text search over mirrored memories.
         // The memory_id column stores the UUID string for joining back to the
@@ -423,9 +866,91 @@ impl Database {
         // Migration: Add global_synced_at column for the global personal store.
         self.migrate_add_global_synced_at()?;
 
+        // Migration: Add forge columns to session_links.
+        self.migrate_add_link_forge()?;
+
+        // Migration: Add auto-link factor scores to session_links.
+        self.migrate_add_link_factors()?;
+
+        // Migration: Add token usage columns to sessions.
+        self.migrate_add_session_usage()?;
+
+        // Migration: Add the generating model to summaries.
+        self.migrate_add_summary_model()?;
+
+        // Migration: Add the source file stamp to sessions.
+        self.migrate_add_source_stamp()?;
+
+        // Migration: Add the parent session link to sessions.
+        self.migrate_add_parent_session()?;
+
+        // Migration: Add the content dedup key to sessions.
+        self.migrate_add_dedup_key()?;
+
+        // Note: the `tool` message role needs no migration. Roles are stored
+        // as TEXT, and rows written before it existed keep `user` or
+        // `assistant`, which still parse as before.
+
+        // Migration: Backfill the session file index for databases created
+        // before it existed.
+        if !had_session_files {
+            Self::rebuild_file_index_in(&self.conn)?;
+        }
+
+        // Migration: Backfill session directories from the message cwd column.
+        if !had_session_directories {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO session_directories (session_id, directory)
+                 SELECT DISTINCT session_id, cwd FROM messages
+                 WHERE cwd IS NOT NULL AND cwd != ''",
+                [],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates the FTS5 tables backing message and session metadata search.
+    fn create_search_tables(&self) -> Result<()> {
+        // Create FTS5 virtual table for full-text search on message content.
+        // This is a standalone FTS table (not content-synced) because we need to
+        // store extracted text content, not the raw JSON from the messages table.
+        // The message_id column stores the UUID string for joining back to messages.
+        self.conn.execute_batch(&format!(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS messages_fts USING fts5(
+                message_id,
+                text_content,
+                tokenize='{}'
+            );",
+            self.search_tokenizer.fts5_option()
+        ))?;
+
+        // Create FTS5 virtual table for session metadata search.
+        // Allows searching by project name, branch, tool, and working directory.
+        self.conn.execute_batch(
+            r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS sessions_fts USING fts5(
+                session_id,
+                tool,
+                working_directory,
+                git_branch,
+                tokenize='porter unicode61'
+            );
+            "#,
+        )?;
         Ok(())
     }
 
+    /// Returns true if a table with the given name exists.
+    fn table_exists(&self, name: &str) -> Result<bool> {
+        let count: i32 = self.conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            params![name],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
     /// Adds the machine_id column to the sessions table if it does not exist,
     /// and backfills NULL values with the current machine's UUID.
     ///
@@ -507,72 +1032,235 @@ impl Database {
         Ok(())
     }
 
-    // ==================== Sessions ====================
-
-    /// Inserts a new session or updates an existing one.
+    /// Adds the forge_host, forge_owner, and forge_repo columns to the
+    /// session_links table if they do not exist.
     ///
-    /// If a session with the same ID already exists, updates the `ended_at`
-    /// and `message_count` fields. Resets both `synced_at` and
-    /// `global_synced_at` to NULL if either the message_count or ended_at has
-    /// changed (a local content change invalidates both the per-repo and global
-    /// sync tracks, so the session is re-exported to each store on the next
-    /// sync). Also updates the sessions_fts index for full-text search on
-    /// session metadata.
-    pub fn insert_session(&self, session: &Session) -> Result<()> {
-        let rows_changed = self.conn.execute(
-            r#"
-            INSERT INTO sessions (id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
-            ON CONFLICT(id) DO UPDATE SET
-                ended_at = ?5,
-                message_count = ?10,
-                synced_at = CASE
-                    WHEN message_count != ?10 THEN NULL
-                    WHEN (ended_at IS NULL AND ?5 IS NOT NULL) THEN NULL
-                    WHEN (ended_at IS NOT NULL AND ?5 IS NULL) THEN NULL
-                    WHEN ended_at != ?5 THEN NULL
-                    ELSE synced_at
-                END,
-                global_synced_at = CASE
-                    WHEN message_count != ?10 THEN NULL
-                    WHEN (ended_at IS NULL AND ?5 IS NOT NULL) THEN NULL
-                    WHEN (ended_at IS NOT NULL AND ?5 IS NULL) THEN NULL
-                    WHEN ended_at != ?5 THEN NULL
-                    ELSE global_synced_at
-                END
-            "#,
-            params![
-                session.id.to_string(),
-                session.tool,
-                session.tool_version,
-                session.started_at.to_rfc3339(),
-                session.ended_at.map(|t| t.to_rfc3339()),
-                session.model,
-                session.working_directory,
-                session.git_branch,
-                session.source_path,
-                session.message_count,
-                session.machine_id,
-            ],
-        )?;
-
-        // Insert into sessions_fts for metadata search (only on new inserts)
-        if rows_changed > 0 {
-            // Check if already in FTS (for ON CONFLICT case)
-            let fts_count: i32 = self.conn.query_row(
-                "SELECT COUNT(*) FROM sessions_fts WHERE session_id = ?1",
-                params![session.id.to_string()],
-                |row| row.get(0),
-            )?;
+    /// Links created before these columns existed keep NULL forge values.
+    fn migrate_add_link_forge(&self) -> Result<()> {
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(session_links)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>, _>>()?;
 
-            if fts_count == 0 {
+        for column in ["forge_host", "forge_owner", "forge_repo"] {
+            if !columns.iter().any(|c| c == column) {
                 self.conn.execute(
-                    "INSERT INTO sessions_fts (session_id, tool, working_directory, git_branch) VALUES (?1, ?2, ?3, ?4)",
-                    params![
-                        session.id.to_string(),
-                        session.tool,
-                        session.working_directory,
-                        session.git_branch.as_deref().unwrap_or(""),
+                    &format!("ALTER TABLE session_links ADD COLUMN {column} TEXT"),
+                    [],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the branch_score, file_score, time_score, and recency_score
+    /// columns to the session_links table if they do not exist.
+    ///
+    /// Links created before these columns existed keep NULL scores.
+    fn migrate_add_link_factors(&self) -> Result<()> {
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(session_links)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for column in ["branch_score", "file_score", "time_score", "recency_score"] {
+            if !columns.iter().any(|c| c == column) {
+                self.conn.execute(
+                    &format!("ALTER TABLE session_links ADD COLUMN {column} REAL"),
+                    [],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the input_tokens and output_tokens columns to the sessions table
+    /// if they do not exist.
+    ///
+    /// Sessions imported before these columns existed keep NULL usage, which
+    /// is shown as "n/a" until the session is re-imported.
+    fn migrate_add_session_usage(&self) -> Result<()> {
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(sessions)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for column in ["input_tokens", "output_tokens"] {
+            if !columns.iter().any(|c| c == column) {
+                self.conn.execute(
+                    &format!("ALTER TABLE sessions ADD COLUMN {column} INTEGER"),
+                    [],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the model column to the summaries table if it does not exist.
+    ///
+    /// Summaries generated before the column existed keep a NULL model and
+    /// are treated like hand-written summaries.
+    fn migrate_add_summary_model(&self) -> Result<()> {
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(summaries)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !columns.iter().any(|c| c == "model") {
+            self.conn
+                .execute("ALTER TABLE summaries ADD COLUMN model TEXT", [])?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds the source_mtime and source_size columns to the sessions table
+    /// if they do not exist.
+    ///
+    /// Sessions imported before these columns existed have no stamp, so
+    /// their source files are parsed once more on the next import.
+    fn migrate_add_source_stamp(&self) -> Result<()> {
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(sessions)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for column in ["source_mtime", "source_size"] {
+            if !columns.iter().any(|c| c == column) {
+                self.conn.execute(
+                    &format!("ALTER TABLE sessions ADD COLUMN {column} INTEGER"),
+                    [],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the parent_session_id column to the sessions table if it does
+    /// not exist.
+    ///
+    /// Subagent sessions imported before the column existed stay unlinked
+    /// until they are re-imported.
+    fn migrate_add_parent_session(&self) -> Result<()> {
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(sessions)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !columns.iter().any(|c| c == "parent_session_id") {
+            self.conn
+                .execute("ALTER TABLE sessions ADD COLUMN parent_session_id TEXT", [])?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds the dedup_key column and its index to the sessions table if they
+    /// do not exist.
+    ///
+    /// Sessions imported before the column existed have no key until they
+    /// are re-imported, so moving their files can still duplicate them once.
+    fn migrate_add_dedup_key(&self) -> Result<()> {
+        let columns: Vec<String> = self
+            .conn
+            .prepare("PRAGMA table_info(sessions)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !columns.iter().any(|c| c == "dedup_key") {
+            self.conn
+                .execute("ALTER TABLE sessions ADD COLUMN dedup_key TEXT", [])?;
+        }
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_sessions_dedup_key ON sessions(dedup_key)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    // ==================== Sessions ====================
+
+    /// Inserts a new session or updates an existing one.
+    ///
+    /// If a session with the same ID already exists, updates the `ended_at`
+    /// and `message_count` fields. Resets both `synced_at` and
+    /// `global_synced_at` to NULL if either the message_count or ended_at has
+    /// changed (a local content change invalidates both the per-repo and global
+    /// sync tracks, so the session is re-exported to each store on the next
+    /// sync). Also updates the sessions_fts index for full-text search on
+    /// session metadata.
+    pub fn insert_session(&self, session: &Session) -> Result<()> {
+        let rows_changed = self.conn.execute(
+            r#"
+            INSERT INTO sessions (id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+            ON CONFLICT(id) DO UPDATE SET
+                ended_at = ?5,
+                message_count = ?10,
+                input_tokens = COALESCE(?12, input_tokens),
+                output_tokens = COALESCE(?13, output_tokens),
+                parent_session_id = COALESCE(?14, parent_session_id),
+                synced_at = CASE
+                    WHEN message_count != ?10 THEN NULL
+                    WHEN (ended_at IS NULL AND ?5 IS NOT NULL) THEN NULL
+                    WHEN (ended_at IS NOT NULL AND ?5 IS NULL) THEN NULL
+                    WHEN ended_at != ?5 THEN NULL
+                    ELSE synced_at
+                END,
+                global_synced_at = CASE
+                    WHEN message_count != ?10 THEN NULL
+                    WHEN (ended_at IS NULL AND ?5 IS NOT NULL) THEN NULL
+                    WHEN (ended_at IS NOT NULL AND ?5 IS NULL) THEN NULL
+                    WHEN ended_at != ?5 THEN NULL
+                    ELSE global_synced_at
+                END
+            "#,
+            params![
+                session.id.to_string(),
+                session.tool,
+                session.tool_version,
+                session.started_at.to_rfc3339(),
+                session.ended_at.map(|t| t.to_rfc3339()),
+                session.model,
+                session.working_directory,
+                session.git_branch,
+                session.source_path,
+                session.message_count,
+                session.machine_id,
+                session.input_tokens,
+                session.output_tokens,
+                session.parent_session_id.map(|id| id.to_string()),
+            ],
+        )?;
+
+        // Insert into sessions_fts for metadata search (only on new inserts)
+        if rows_changed > 0 {
+            // Check if already in FTS (for ON CONFLICT case)
+            let fts_count: i32 = self.conn.query_row(
+                "SELECT COUNT(*) FROM sessions_fts WHERE session_id = ?1",
+                params![session.id.to_string()],
+                |row| row.get(0),
+            )?;
+
+            if fts_count == 0 {
+                self.conn.execute(
+                    "INSERT INTO sessions_fts (session_id, tool, working_directory, git_branch) VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        session.id.to_string(),
+                        session.tool,
+                        session.working_directory,
+                        session.git_branch.as_deref().unwrap_or(""),
                     ],
                 )?;
             }
@@ -587,7 +1275,7 @@ impl Database {
     pub fn get_session(&self, id: &Uuid) -> Result<Option<Session>> {
         self.conn
             .query_row(
-                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id FROM sessions WHERE id = ?1",
+                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id FROM sessions WHERE id = ?1",
                 params![id.to_string()],
                 Self::row_to_session,
             )
@@ -600,28 +1288,62 @@ impl Database {
     /// Optionally filters by working directory prefix. Returns at most
     /// `limit` sessions.
     pub fn list_sessions(&self, limit: usize, working_dir: Option<&str>) -> Result<Vec<Session>> {
-        let mut stmt = if working_dir.is_some() {
-            self.conn.prepare(
-                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
-                 FROM sessions
-                 WHERE working_directory LIKE ?1
-                 ORDER BY started_at DESC
-                 LIMIT ?2"
-            )?
-        } else {
-            self.conn.prepare(
-                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
-                 FROM sessions
-                 ORDER BY started_at DESC
-                 LIMIT ?1"
-            )?
-        };
+        self.list_sessions_filtered(limit, working_dir, None, None)
+    }
 
-        let rows = if let Some(wd) = working_dir {
-            stmt.query_map(params![format!("{}%", wd), limit], Self::row_to_session)?
+    /// Lists sessions matching all given filters, most recent first.
+    ///
+    /// `working_dir` is a path prefix matched against every directory the
+    /// session's messages ran in, `tool` an exact tool name, and `model`
+    /// a case-insensitive substring of the session's model. Filters that are
+    /// `None` are ignored. Returns at most `limit` sessions.
+    pub fn list_sessions_filtered(
+        &self,
+        limit: usize,
+        working_dir: Option<&str>,
+        tool: Option<&str>,
+        model: Option<&str>,
+    ) -> Result<Vec<Session>> {
+        let mut conditions: Vec<String> = Vec::new();
+        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        if let Some(wd) = working_dir {
+            conditions.push(format!(
+                "(working_directory LIKE ?{n} OR id IN \
+                 (SELECT session_id FROM session_directories WHERE directory LIKE ?{n}))",
+                n = param_values.len() + 1
+            ));
+            param_values.push(Box::new(format!("{wd}%")));
+        }
+        if let Some(tool) = tool {
+            conditions.push(format!("tool = ?{}", param_values.len() + 1));
+            param_values.push(Box::new(tool.to_string()));
+        }
+        if let Some(model) = model {
+            conditions.push(format!("model LIKE ?{}", param_values.len() + 1));
+            param_values.push(Box::new(format!("%{model}%")));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
         } else {
-            stmt.query_map(params![limit], Self::row_to_session)?
+            format!("WHERE {}", conditions.join(" AND "))
         };
+        let sql = format!(
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
+             FROM sessions
+             {where_clause}
+             ORDER BY started_at DESC
+             LIMIT ?{}",
+            param_values.len() + 1
+        );
+        param_values.push(Box::new(limit as i64));
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(param_values.iter().map(|p| p.as_ref())),
+            Self::row_to_session,
+        )?;
 
         rows.collect::<Result<Vec<_>, _>>()
             .context("Failed to list sessions")
@@ -637,7 +1359,7 @@ impl Database {
     ) -> Result<Vec<Session>> {
         let mut stmt = if working_dir.is_some() {
             self.conn.prepare(
-                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
+                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
                  FROM sessions
                  WHERE ended_at IS NOT NULL
                    AND working_directory LIKE ?1
@@ -646,7 +1368,7 @@ impl Database {
             )?
         } else {
             self.conn.prepare(
-                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
+                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
                  FROM sessions
                  WHERE ended_at IS NOT NULL
                  ORDER BY started_at DESC
@@ -665,8 +1387,7 @@ impl Database {
     }
 
     /// Checks if a session with the given source path already exists.
-    ///
-    /// Used to detect already-imported sessions during import operations.
+    #[allow(dead_code)]
     pub fn session_exists_by_source(&self, source_path: &str) -> Result<bool> {
         let count: i32 = self.conn.query_row(
             "SELECT COUNT(*) FROM sessions WHERE source_path = ?1",
@@ -676,6 +1397,73 @@ impl Database {
         Ok(count > 0)
     }
 
+    /// Checks if a session with the given content dedup key already exists.
+    ///
+    /// The key comes from [`session_dedup_key`](super::models::session_dedup_key)
+    /// and does not depend on the session's source path or ID.
+    pub fn session_exists_by_key(&self, dedup_key: &str) -> Result<bool> {
+        let count: i32 = self.conn.query_row(
+            "SELECT COUNT(*) FROM sessions WHERE dedup_key = ?1",
+            params![dedup_key],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Records the content dedup key of a stored session.
+    pub fn set_session_dedup_key(&self, session_id: &Uuid, dedup_key: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE sessions SET dedup_key = ?2 WHERE id = ?1",
+            params![session_id.to_string(), dedup_key],
+        )?;
+        Ok(())
+    }
+
+    /// Checks if a source file was imported and has not changed since.
+    ///
+    /// True when at least one session came from `source_path` and every
+    /// such session records `stamp`. Used to skip files during import while
+    /// still re-parsing files that grew after they were imported. Sessions
+    /// stored before stamps were recorded count as unchanged; see
+    /// [`Database::backfill_source_stamp`].
+    pub fn source_unchanged(&self, source_path: &str, stamp: &SourceStamp) -> Result<bool> {
+        let (total, stale): (i64, i64) = self.conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(CASE
+                        WHEN source_mtime IS NULL THEN 0
+                        WHEN source_mtime = ?2 AND source_size = ?3 THEN 0
+                        ELSE 1
+                    END), 0)
+             FROM sessions WHERE source_path = ?1",
+            params![source_path, stamp.mtime_ns, stamp.size],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(total > 0 && stale == 0)
+    }
+
+    /// Records the stamp of a source file on every session imported from it.
+    pub fn set_source_stamp(&self, source_path: &str, stamp: &SourceStamp) -> Result<()> {
+        self.conn.execute(
+            "UPDATE sessions SET source_mtime = ?2, source_size = ?3 WHERE source_path = ?1",
+            params![source_path, stamp.mtime_ns, stamp.size],
+        )?;
+        Ok(())
+    }
+
+    /// Records `stamp` on sessions from `source_path` that have none yet.
+    ///
+    /// Sessions imported before stamps were recorded take the file's current
+    /// stamp when an import first skips them, so a later change to the file
+    /// is noticed.
+    pub fn backfill_source_stamp(&self, source_path: &str, stamp: &SourceStamp) -> Result<()> {
+        self.conn.execute(
+            "UPDATE sessions SET source_mtime = ?2, source_size = ?3
+             WHERE source_path = ?1 AND source_mtime IS NULL",
+            params![source_path, stamp.mtime_ns, stamp.size],
+        )?;
+        Ok(())
+    }
+
     /// Retrieves a session by its source path.
     ///
     /// Returns `None` if no session with the given source path exists.
@@ -683,7 +1471,7 @@ impl Database {
     pub fn get_session_by_source(&self, source_path: &str) -> Result<Option<Session>> {
         self.conn
             .query_row(
-                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id FROM sessions WHERE source_path = ?1",
+                "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id FROM sessions WHERE source_path = ?1",
                 params![source_path],
                 Self::row_to_session,
             )
@@ -705,44 +1493,39 @@ impl Database {
     ///
     /// * `Ok(Some(session))` - If exactly one session matches the prefix
     /// * `Ok(None)` - If no sessions match the prefix
-    /// * `Err` - If multiple sessions match (ambiguous prefix) or database error
+    /// * `Err` - If multiple sessions match or on a database error. An
+    ///   ambiguous prefix is reported as an [`AmbiguousPrefix`] error.
     pub fn find_session_by_id_prefix(&self, prefix: &str) -> Result<Option<Session>> {
         // First try parsing as a full UUID
         if let Ok(uuid) = Uuid::parse_str(prefix) {
             return self.get_session(&uuid);
         }
+        // Session IDs are hex and dashes; anything else, including LIKE
+        // wildcards, cannot match
+        if !prefix.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+            return Ok(None);
+        }
 
         // Search by prefix using LIKE
         let pattern = format!("{prefix}%");
+        let ids: Vec<String> = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT id FROM sessions WHERE id LIKE ?1 ORDER BY started_at DESC")?;
+            let rows = stmt.query_map(params![pattern], |row| row.get(0))?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .context("Failed to find session by prefix")?
+        };
 
-        // First, count how many sessions match
-        let count: i32 = self.conn.query_row(
-            "SELECT COUNT(*) FROM sessions WHERE id LIKE ?1",
-            params![pattern],
-            |row| row.get(0),
-        )?;
-
-        match count {
-            0 => Ok(None),
-            1 => {
-                // Exactly one match, retrieve it
-                self.conn
-                    .query_row(
-                        "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
-                         FROM sessions
-                         WHERE id LIKE ?1",
-                        params![pattern],
-                        Self::row_to_session,
-                    )
-                    .optional()
-                    .context("Failed to find session by prefix")
-            }
-            n => {
-                // Multiple matches - return an error indicating ambiguity
-                anyhow::bail!(
-                    "Ambiguous session ID prefix '{prefix}' matches {n} sessions. Use a longer prefix."
-                )
+        match ids.as_slice() {
+            [] => Ok(None),
+            [id] => self.get_session(&parse_uuid(id)?),
+            _ => Err(AmbiguousPrefix {
+                kind: "session",
+                prefix: prefix.to_string(),
+                candidates: ids,
             }
+            .into()),
         }
     }
 
@@ -776,6 +1559,10 @@ impl Database {
             Some(s) => Some(parse_datetime(&s)?),
             None => None,
         };
+        let parent_session_id = match row.get::<_, Option<String>>(13)? {
+            Some(s) => Some(parse_uuid(&s)?),
+            None => None,
+        };
 
         Ok(Session {
             id: parse_uuid(&row.get::<_, String>(0)?)?,
@@ -789,6 +1576,9 @@ impl Database {
             source_path: row.get(8)?,
             message_count: row.get(9)?,
             machine_id: row.get(10)?,
+            input_tokens: row.get(11)?,
+            output_tokens: row.get(12)?,
+            parent_session_id,
         })
     }
 
@@ -797,10 +1587,14 @@ impl Database {
     /// Inserts a message into the database.
     ///
     /// If a message with the same ID already exists, the insert is ignored.
-    /// Message content is serialized to JSON for storage. Also inserts
-    /// extracted text content into the FTS index for full-text search.
+    /// Message content is serialized to JSON for storage, compressed when
+    /// large and compression is enabled. Also inserts extracted text content
+    /// into the FTS index for full-text search.
     pub fn insert_message(&self, message: &Message) -> Result<()> {
-        let content_json = serde_json::to_string(&message.content)?;
+        let content_json = encode_content(
+            serde_json::to_string(&message.content)?,
+            self.compress_content,
+        )?;
 
         let rows_changed = self.conn.execute(
             r#"
@@ -831,11 +1625,67 @@ impl Database {
                     params![message.id.to_string(), text_content],
                 )?;
             }
+            Self::index_message_files(&self.conn, message)?;
+            Self::index_message_directory(&self.conn, message)?;
         }
 
         Ok(())
     }
 
+    /// Adds the file paths referenced by a message to the session file index.
+    ///
+    /// Paths are made relative to the owning session's working directory, so
+    /// the session row must already exist; messages for unknown sessions are
+    /// skipped. Messages without tool calls add nothing.
+    fn index_message_files(conn: &Connection, message: &Message) -> Result<()> {
+        if !matches!(message.content, MessageContent::Blocks(_)) {
+            return Ok(());
+        }
+
+        let working_directory: Option<String> = conn
+            .query_row(
+                "SELECT working_directory FROM sessions WHERE id = ?1",
+                params![message.session_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(working_directory) = working_directory else {
+            return Ok(());
+        };
+
+        let files = extract_session_files(std::slice::from_ref(message), &working_directory);
+        for path in files {
+            conn.execute(
+                "INSERT OR IGNORE INTO session_files (session_id, path) VALUES (?1, ?2)",
+                params![message.session_id.to_string(), path],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Records the message's working directory in the session directory set.
+    fn index_message_directory(conn: &Connection, message: &Message) -> Result<()> {
+        if let Some(cwd) = message.cwd.as_deref().filter(|cwd| !cwd.is_empty()) {
+            conn.execute(
+                "INSERT OR IGNORE INTO session_directories (session_id, directory) VALUES (?1, ?2)",
+                params![message.session_id.to_string(), cwd],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the distinct working directories seen in a session's messages,
+    /// sorted. Sessions whose tool does not record a per-message directory
+    /// have none.
+    pub fn get_session_directories(&self, session_id: &Uuid) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT directory FROM session_directories WHERE session_id = ?1 ORDER BY directory",
+        )?;
+        let rows = stmt.query_map(params![session_id.to_string()], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to get session directories")
+    }
+
     /// Imports a session with all its messages in a single transaction.
     ///
     /// This is much faster than calling `insert_session` and `insert_message`
@@ -851,8 +1701,16 @@ impl Database {
         messages: &[Message],
         synced_at: Option<DateTime<Utc>>,
     ) -> Result<()> {
+        let compress = self.compress_content;
         let tx = self.conn.transaction()?;
-        Self::write_session_with_messages(&tx, session, messages, synced_at, SyncTrack::PerRepo)?;
+        Self::write_session_with_messages(
+            &tx,
+            session,
+            messages,
+            synced_at,
+            SyncTrack::PerRepo,
+            compress,
+        )?;
         tx.commit()?;
         Ok(())
     }
@@ -867,24 +1725,29 @@ impl Database {
     /// `track` selects which sync-tracking column the supplied timestamp is
     /// written into: the per-repo `synced_at` or the global `global_synced_at`.
     /// Only that column is touched, so marking a session synced for one store
-    /// never affects the other store's track.
+    /// never affects the other store's track. `compress` compresses large
+    /// message content as in [`Self::insert_message`].
     fn write_session_with_messages(
         conn: &Connection,
         session: &Session,
         messages: &[Message],
         synced_at: Option<DateTime<Utc>>,
         track: SyncTrack,
+        compress: bool,
     ) -> Result<()> {
         // Insert session. The tracking column is chosen by `track`; the SQL is
         // otherwise identical for both stores.
         let col = track.column();
         let insert_sql = format!(
-            "INSERT INTO sessions (id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, {col})
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+            "INSERT INTO sessions (id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, {col}, input_tokens, output_tokens, parent_session_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
             ON CONFLICT(id) DO UPDATE SET
                 ended_at = ?5,
                 message_count = ?10,
-                {col} = COALESCE(?12, {col})"
+                {col} = COALESCE(?12, {col}),
+                input_tokens = COALESCE(?13, input_tokens),
+                output_tokens = COALESCE(?14, output_tokens),
+                parent_session_id = COALESCE(?15, parent_session_id)"
         );
         conn.execute(
             &insert_sql,
@@ -901,6 +1764,9 @@ impl Database {
                 session.message_count,
                 session.machine_id,
                 synced_at.map(|t| t.to_rfc3339()),
+                session.input_tokens,
+                session.output_tokens,
+                session.parent_session_id.map(|id| id.to_string()),
             ],
         )?;
 
@@ -924,7 +1790,7 @@ impl Database {
 
         // Insert all messages
         for message in messages {
-            let content_json = serde_json::to_string(&message.content)?;
+            let content_json = encode_content(serde_json::to_string(&message.content)?, compress)?;
 
             let rows_changed = conn.execute(
                 r#"
@@ -955,6 +1821,8 @@ impl Database {
                         params![message.id.to_string(), text_content],
                     )?;
                 }
+                Self::index_message_files(conn, message)?;
+                Self::index_message_directory(conn, message)?;
             }
         }
 
@@ -1145,6 +2013,49 @@ impl Database {
         Ok(rows)
     }
 
+    /// Removes sessions from the sync store while keeping the local copies.
+    ///
+    /// Records each id in `sync_exclusions`. The next sync deletes the
+    /// session's blobs from the store tree and publishes the exclusion, and
+    /// from then on the session is never pushed or re-imported by any machine
+    /// that has pulled it. Local session data is not touched. Returns the
+    /// number of sessions newly excluded.
+    pub fn exclude_sessions_from_sync(&self, session_ids: &[Uuid]) -> Result<usize> {
+        let now = Utc::now().to_rfc3339();
+        let mut excluded = 0;
+        for id in session_ids {
+            excluded += self.conn.execute(
+                "INSERT OR IGNORE INTO sync_exclusions (session_id, excluded_at) VALUES (?1, ?2)",
+                params![id.to_string(), now],
+            )?;
+        }
+        Ok(excluded)
+    }
+
+    /// Returns the ids of sessions removed from the sync store.
+    pub fn sync_excluded_session_ids(&self) -> Result<HashSet<Uuid>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT session_id FROM sync_exclusions")?;
+        let rows = stmt.query_map([], |row| {
+            let id: String = row.get(0)?;
+            parse_uuid(&id)
+        })?;
+
+        rows.collect::<rusqlite::Result<HashSet<Uuid>>>()
+            .context("Failed to get sync-excluded session ids")
+    }
+
+    /// Returns whether a session has been removed from the sync store.
+    fn is_sync_excluded(conn: &Connection, session_id: &Uuid) -> Result<bool> {
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sync_exclusions WHERE session_id = ?1",
+            params![session_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
     /// Merges a full remote reasoning record into the database atomically.
     ///
     /// Used by git-ref sync when pulling a remote store. The session row,
@@ -1174,8 +2085,11 @@ impl Database {
     ///
     /// This marks imported sessions on the per-repo `synced_at` track. The
     /// global store uses [`Self::merge_remote_record_global`], which shares the
-    /// identical merge logic but marks the `global_synced_at` track.
+    /// identical merge logic but marks the `global_synced_at` track. Use
+    /// [`Self::merge_remote_record_with`] to pick a different
+    /// [`ConflictStrategy`].
     #[allow(clippy::too_many_arguments)]
+    #[allow(dead_code)]
     pub fn merge_remote_record(
         &mut self,
         session: &Session,
@@ -1186,7 +2100,7 @@ impl Database {
         summary: Option<&Summary>,
         synced_at: DateTime<Utc>,
     ) -> Result<bool> {
-        self.merge_remote_record_tracked(
+        let outcome = self.merge_remote_record_with(
             session,
             messages,
             links,
@@ -1195,7 +2109,9 @@ impl Database {
             summary,
             synced_at,
             SyncTrack::PerRepo,
-        )
+            ConflictStrategy::Newest,
+        )?;
+        Ok(outcome == MergeOutcome::Imported)
     }
 
     /// Global-store counterpart of [`Self::merge_remote_record`].
@@ -1204,6 +2120,7 @@ impl Database {
     /// imported session on the global `global_synced_at` track so a global pull
     /// does not affect the per-repo `synced_at` track (and vice versa).
     #[allow(clippy::too_many_arguments)]
+    #[allow(dead_code)]
     pub fn merge_remote_record_global(
         &mut self,
         session: &Session,
@@ -1214,7 +2131,7 @@ impl Database {
         summary: Option<&Summary>,
         synced_at: DateTime<Utc>,
     ) -> Result<bool> {
-        self.merge_remote_record_tracked(
+        let outcome = self.merge_remote_record_with(
             session,
             messages,
             links,
@@ -1223,16 +2140,19 @@ impl Database {
             summary,
             synced_at,
             SyncTrack::Global,
-        )
+            ConflictStrategy::Newest,
+        )?;
+        Ok(outcome == MergeOutcome::Imported)
     }
 
     /// Shared implementation for the per-repo and global merge paths.
     ///
     /// `track` selects which sync-tracking column an imported session row is
-    /// marked on. Everything else (newer-wins session/message import plus
-    /// additive, idempotent child merges) is identical across both stores.
+    /// marked on, and `strategy` decides whether a remote row that differs
+    /// from the local one replaces it (see [`resolve_session_conflict`]).
+    /// Child records are merged additively under every strategy.
     #[allow(clippy::too_many_arguments)]
-    fn merge_remote_record_tracked(
+    pub fn merge_remote_record_with(
         &mut self,
         session: &Session,
         messages: &[Message],
@@ -1242,28 +2162,47 @@ impl Database {
         summary: Option<&Summary>,
         synced_at: DateTime<Utc>,
         track: SyncTrack,
-    ) -> Result<bool> {
+        strategy: ConflictStrategy,
+    ) -> Result<MergeOutcome> {
+        let compress = self.compress_content;
         let tx = self.conn.transaction()?;
 
-        // Read the local session's newer-wins keys (if it exists) inside the
-        // transaction so the decision and the writes are one atomic unit.
-        let existing: Option<(i32, Option<String>)> = tx
+        // A session removed from the store is never re-imported from a stale
+        // blob, on this machine or any other that has pulled the tombstone.
+        if Self::is_sync_excluded(&tx, &session.id)? {
+            return Ok(MergeOutcome::Unchanged);
+        }
+        // Nor is a session this machine pruned for being past retention
+        if Self::is_pruned(&tx, &session.id)? {
+            return Ok(MergeOutcome::Unchanged);
+        }
+
+        // Read the local session row (if it exists) inside the transaction so
+        // the decision and the writes are one atomic unit.
+        let existing: Option<Session> = tx
             .query_row(
-                "SELECT message_count, ended_at FROM sessions WHERE id = ?1",
+                "SELECT id, tool, tool_version, started_at, ended_at, model,
+                        working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
+                 FROM sessions WHERE id = ?1",
                 params![session.id.to_string()],
-                |row| Ok((row.get(0)?, row.get(1)?)),
+                Self::row_to_session,
             )
             .optional()?;
 
-        let import_session = match &existing {
-            None => true,
-            Some((local_count, local_ended)) => {
-                Self::remote_row_is_newer(session, *local_count, local_ended.as_deref())?
-            }
+        let outcome = match &existing {
+            None => MergeOutcome::Imported,
+            Some(local) => resolve_session_conflict(strategy, session, local),
         };
 
-        if import_session {
-            Self::write_session_with_messages(&tx, session, messages, Some(synced_at), track)?;
+        if outcome == MergeOutcome::Imported {
+            Self::write_session_with_messages(
+                &tx,
+                session,
+                messages,
+                Some(synced_at),
+                track,
+                compress,
+            )?;
         }
 
         // Child records are additive and idempotent by id: merge them so a
@@ -1294,32 +2233,7 @@ impl Database {
         }
 
         tx.commit()?;
-        Ok(import_session)
-    }
-
-    /// Newer-wins comparison for a remote session against a local row.
-    ///
-    /// Mirrors the historical sync rule: a strictly higher remote message count
-    /// wins outright; otherwise a later `ended_at` wins (a remote `ended_at`
-    /// against a local NULL also wins). `local_ended` is the stored RFC3339
-    /// string, parsed for the comparison.
-    fn remote_row_is_newer(
-        remote: &Session,
-        local_count: i32,
-        local_ended: Option<&str>,
-    ) -> Result<bool> {
-        if remote.message_count > local_count {
-            return Ok(true);
-        }
-        let local_ended = match local_ended {
-            Some(s) => Some(parse_datetime(s)?),
-            None => None,
-        };
-        Ok(match (remote.ended_at, local_ended) {
-            (Some(r), Some(l)) => r > l,
-            (Some(_), None) => true,
-            _ => false,
-        })
+        Ok(outcome)
     }
 
     /// Retrieves all messages for a session, ordered by index.
@@ -1335,7 +2249,7 @@ impl Database {
 
         let rows = stmt.query_map(params![session_id.to_string()], |row| {
             let role_str: String = row.get(5)?;
-            let content_str: String = row.get(6)?;
+            let content_str = decode_content(row.get_ref(6)?)?;
 
             let parent_id_str: Option<String> = row.get(2)?;
             let parent_id = match parent_id_str {
@@ -1349,12 +2263,7 @@ impl Database {
                 parent_id,
                 index: row.get(3)?,
                 timestamp: parse_datetime(&row.get::<_, String>(4)?)?,
-                role: match role_str.as_str() {
-                    "user" => MessageRole::User,
-                    "assistant" => MessageRole::Assistant,
-                    "system" => MessageRole::System,
-                    _ => MessageRole::User,
-                },
+                role: role_from_str(&role_str),
                 content: serde_json::from_str(&content_str)
                     .unwrap_or(MessageContent::Text(content_str)),
                 model: row.get(7)?,
@@ -1364,7 +2273,106 @@ impl Database {
         })?;
 
         rows.collect::<Result<Vec<_>, _>>()
-            .context("Failed to get messages")
+            .context("Failed to get messages")
+    }
+
+    /// Retrieves a session's message rows as stored, ordered by index.
+    ///
+    /// Unlike [`Database::get_messages`], nothing is parsed: the content is
+    /// the serialized `MessageContent` JSON exactly as written (decompressed
+    /// if it was stored compressed), so parser problems can be inspected.
+    pub fn get_stored_messages(&self, session_id: &Uuid) -> Result<Vec<StoredMessage>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, parent_id, idx, timestamp, role, content, model, git_branch, cwd
+             FROM messages
+             WHERE session_id = ?1
+             ORDER BY idx",
+        )?;
+
+        let rows = stmt.query_map(params![session_id.to_string()], |row| {
+            let content = row.get_ref(5)?;
+            Ok(StoredMessage {
+                id: row.get(0)?,
+                parent_id: row.get(1)?,
+                index: row.get(2)?,
+                timestamp: row.get(3)?,
+                role: row.get(4)?,
+                compressed: matches!(content, ValueRef::Blob([COMPRESSED_CONTENT_HEADER, ..])),
+                content: decode_content(content)?,
+                model: row.get(6)?,
+                git_branch: row.get(7)?,
+                cwd: row.get(8)?,
+            })
+        })?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to get stored messages")
+    }
+
+    /// Retrieves one page of a session's messages, ordered by index.
+    ///
+    /// Skips the first `offset` messages and returns at most `limit`, so
+    /// large sessions can be displayed without loading every message.
+    /// Use [`Database::get_messages`] when the full conversation is needed.
+    pub fn get_messages_paged(
+        &self,
+        session_id: &Uuid,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Message>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, session_id, parent_id, idx, timestamp, role, content, model, git_branch, cwd
+             FROM messages
+             WHERE session_id = ?1
+             ORDER BY idx
+             LIMIT ?2 OFFSET ?3",
+        )?;
+
+        let rows = stmt.query_map(
+            params![session_id.to_string(), limit as i64, offset as i64],
+            Self::row_to_message,
+        )?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to get messages")
+    }
+
+    /// Returns the number of messages stored for a session.
+    pub fn session_message_count(&self, session_id: &Uuid) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM messages WHERE session_id = ?1",
+            params![session_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Retrieves a session's messages as a conversation tree.
+    ///
+    /// Messages are threaded by `parent_id`, so edited prompts and
+    /// regenerated responses appear as branches instead of one flat list.
+    /// See [`MessageThread::from_messages`] for how missing parents are
+    /// handled. Returns `None` if the session has no messages.
+    pub fn get_message_tree(&self, session_id: &Uuid) -> Result<Option<MessageThread>> {
+        Ok(MessageThread::from_messages(self.get_messages(session_id)?))
+    }
+
+    /// Returns the subagent sessions spawned by a session, oldest first.
+    ///
+    /// These are the sessions whose `parent_session_id` is `parent_id`, such
+    /// as Claude Code sidechains.
+    pub fn get_child_sessions(&self, parent_id: &Uuid) -> Result<Vec<Session>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
+             FROM sessions
+             WHERE parent_session_id = ?1
+             ORDER BY started_at ASC",
+        )?;
+
+        let rows = stmt.query_map(params![parent_id.to_string()], Self::row_to_session)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to get child sessions")
     }
 
     /// Returns the ordered list of distinct branches for a session.
@@ -1399,6 +2407,43 @@ impl Database {
         Ok(branches)
     }
 
+    /// Lists sessions that worked on a branch, most recent first.
+    ///
+    /// A session matches when its `git_branch` is `branch` or any of its
+    /// messages ran on `branch`, so a session that switched branches partway
+    /// through is listed under each of them. `working_dir` is a path prefix
+    /// matched like [`Self::list_sessions_filtered`] does.
+    pub fn sessions_for_branch(
+        &self,
+        branch: &str,
+        working_dir: Option<&str>,
+    ) -> Result<Vec<Session>> {
+        let mut sql = String::from(
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
+             FROM sessions
+             WHERE (git_branch = ?1
+                    OR id IN (SELECT session_id FROM messages WHERE git_branch = ?1))",
+        );
+        let mut param_values = vec![branch.to_string()];
+        if let Some(wd) = working_dir {
+            sql.push_str(
+                " AND (working_directory LIKE ?2 OR id IN \
+                 (SELECT session_id FROM session_directories WHERE directory LIKE ?2))",
+            );
+            param_values.push(format!("{wd}%"));
+        }
+        sql.push_str(" ORDER BY started_at DESC");
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(param_values.iter()),
+            Self::row_to_session,
+        )?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to list sessions for branch")
+    }
+
     // ==================== Session Links ====================
 
     /// Inserts a link between a session and a git commit.
@@ -1421,14 +2466,14 @@ impl Database {
     fn write_link(conn: &Connection, link: &SessionLink, ignore_conflict: bool) -> Result<()> {
         let sql = if ignore_conflict {
             r#"
-            INSERT INTO session_links (id, session_id, link_type, commit_sha, branch, remote, created_at, created_by, confidence)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            INSERT INTO session_links (id, session_id, link_type, commit_sha, branch, remote, created_at, created_by, confidence, forge_host, forge_owner, forge_repo, branch_score, file_score, time_score, recency_score)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
             ON CONFLICT(id) DO NOTHING
             "#
         } else {
             r#"
-            INSERT INTO session_links (id, session_id, link_type, commit_sha, branch, remote, created_at, created_by, confidence)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            INSERT INTO session_links (id, session_id, link_type, commit_sha, branch, remote, created_at, created_by, confidence, forge_host, forge_owner, forge_repo, branch_score, file_score, time_score, recency_score)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
             "#
         };
         conn.execute(
@@ -1443,18 +2488,63 @@ impl Database {
                 link.created_at.to_rfc3339(),
                 format!("{:?}", link.created_by).to_lowercase(),
                 link.confidence,
+                link.forge.as_ref().map(|f| f.host.as_str()),
+                link.forge.as_ref().map(|f| f.owner.as_str()),
+                link.forge.as_ref().map(|f| f.repo.as_str()),
+                link.factors.map(|f| f.branch),
+                link.factors.map(|f| f.files),
+                link.factors.map(|f| f.time),
+                link.factors.map(|f| f.recency),
             ],
         )?;
         Ok(())
     }
 
+    /// Finds the full SHA of a linked commit from a SHA prefix.
+    ///
+    /// Only commits that have session links are searched, so this resolves
+    /// short SHAs outside the repository they came from.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(sha))` - If exactly one linked commit matches the prefix
+    /// * `Ok(None)` - If no linked commit matches the prefix
+    /// * `Err` - An [`AmbiguousPrefix`] error if several commits match, or a
+    ///   database error
+    pub fn find_linked_commit_by_prefix(&self, prefix: &str) -> Result<Option<String>> {
+        if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(None);
+        }
+        let mut stmt = self.conn.prepare(
+            "SELECT commit_sha FROM session_links
+             WHERE commit_sha LIKE ?1
+             GROUP BY commit_sha
+             ORDER BY MAX(created_at) DESC",
+        )?;
+        let shas = stmt
+            .query_map(params![format!("{prefix}%")], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()
+            .context("Failed to find commit by prefix")?;
+
+        match shas.len() {
+            0 => Ok(None),
+            1 => Ok(shas.into_iter().next()),
+            _ => Err(AmbiguousPrefix {
+                kind: "commit",
+                prefix: prefix.to_string(),
+                candidates: shas,
+            }
+            .into()),
+        }
+    }
+
     /// Retrieves all session links for a commit.
     ///
     /// Supports prefix matching on the commit SHA, allowing short SHAs
     /// (e.g., first 8 characters) to be used for lookup.
     pub fn get_links_by_commit(&self, commit_sha: &str) -> Result<Vec<SessionLink>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, session_id, link_type, commit_sha, branch, remote, created_at, created_by, confidence 
+            "SELECT id, session_id, link_type, commit_sha, branch, remote, created_at, created_by, confidence, forge_host, forge_owner, forge_repo, branch_score, file_score, time_score, recency_score
              FROM session_links 
              WHERE commit_sha LIKE ?1"
         )?;
@@ -1472,7 +2562,7 @@ impl Database {
     /// several git operations.
     pub fn get_links_by_session(&self, session_id: &Uuid) -> Result<Vec<SessionLink>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, session_id, link_type, commit_sha, branch, remote, created_at, created_by, confidence 
+            "SELECT id, session_id, link_type, commit_sha, branch, remote, created_at, created_by, confidence, forge_host, forge_owner, forge_repo, branch_score, file_score, time_score, recency_score
              FROM session_links 
              WHERE session_id = ?1"
         )?;
@@ -1484,10 +2574,32 @@ impl Database {
     }
 
     fn row_to_link(row: &rusqlite::Row) -> rusqlite::Result<SessionLink> {
-        use super::models::{LinkCreator, LinkType};
+        use super::models::{ForgeRepo, LinkCreator, LinkFactors, LinkType};
 
         let link_type_str: String = row.get(2)?;
         let created_by_str: String = row.get(7)?;
+        let forge = match (
+            row.get::<_, Option<String>>(9)?,
+            row.get::<_, Option<String>>(10)?,
+            row.get::<_, Option<String>>(11)?,
+        ) {
+            (Some(host), Some(owner), Some(repo)) => Some(ForgeRepo { host, owner, repo }),
+            _ => None,
+        };
+        let factors = match (
+            row.get::<_, Option<f64>>(12)?,
+            row.get::<_, Option<f64>>(13)?,
+            row.get::<_, Option<f64>>(14)?,
+            row.get::<_, Option<f64>>(15)?,
+        ) {
+            (Some(branch), Some(files), Some(time), Some(recency)) => Some(LinkFactors {
+                branch,
+                files,
+                time,
+                recency,
+            }),
+            _ => None,
+        };
 
         Ok(SessionLink {
             id: parse_uuid(&row.get::<_, String>(0)?)?,
@@ -1507,6 +2619,8 @@ impl Database {
                 _ => LinkCreator::User,
             },
             confidence: row.get(8)?,
+            forge,
+            factors,
         })
     }
 
@@ -1616,7 +2730,7 @@ impl Database {
     ///
     /// Optional filters:
     /// - `working_dir`: Filter by working directory prefix
-    /// - `since`: Filter by minimum timestamp
+    /// - `since`/`until`: Filter by session start time range
     /// - `role`: Filter by message role
     ///
     /// Note: This is the legacy search API. For new code, use `search_with_options`.
@@ -1627,6 +2741,7 @@ impl Database {
         limit: usize,
         working_dir: Option<&str>,
         since: Option<chrono::DateTime<chrono::Utc>>,
+        until: Option<chrono::DateTime<chrono::Utc>>,
         role: Option<&str>,
     ) -> Result<Vec<SearchResult>> {
         use super::models::SearchOptions;
@@ -1637,6 +2752,7 @@ impl Database {
             limit,
             repo: working_dir.map(|s| s.to_string()),
             since,
+            until,
             role: role.map(|s| s.to_string()),
             ..Default::default()
         };
@@ -1657,10 +2773,191 @@ impl Database {
     /// - `branch`: Filter by git branch (partial match)
     /// - `role`: Filter by message role
     /// - `repo`: Filter by working directory prefix
+    ///
+    /// If the query fails because the index is corrupt, the index is rebuilt
+    /// once and the query retried, so a damaged index never leaves search
+    /// broken.
     pub fn search_with_options(
         &self,
         options: &super::models::SearchOptions,
     ) -> Result<Vec<SearchResult>> {
+        match self.search_with_options_once(options) {
+            Err(e) if is_fts_corruption(&e) => {
+                tracing::warn!("Search index appears corrupt ({e}); rebuilding and retrying");
+                self.rebuild_search_index()
+                    .context("Failed to rebuild corrupt search index")?;
+                self.search_with_options_once(options)
+            }
+            result => result,
+        }
+    }
+
+    /// Counts all results of a search, ignoring its limit.
+    ///
+    /// Runs the same match and filters as [`Self::search_with_options`], so
+    /// the count agrees with the results it would return without a limit.
+    /// Callers that only need the results can skip this extra query.
+    pub fn search_count(&self, options: &super::models::SearchOptions) -> Result<usize> {
+        let (sql, params_vec, _) = Self::search_sql(options);
+        let sql = format!("SELECT COUNT(*) FROM ({sql})");
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+
+        let count: i64 = self
+            .conn
+            .query_row(&sql, params_refs.as_slice(), |row| row.get(0))
+            .context("Failed to count search results")?;
+        Ok(count as usize)
+    }
+
+    /// Searches message text with a regular expression.
+    ///
+    /// FTS5 cannot express arbitrary patterns, so candidates are fetched
+    /// first and the regex is applied to their full text. When
+    /// `options.query` is not empty it is used as an FTS prefilter, and only
+    /// messages matching it are candidates. Otherwise the `scan_limit` most
+    /// recent messages passing the filters are scanned, and the result's
+    /// `truncated` flag tells whether older messages were left out.
+    ///
+    /// Session metadata is not searched. Results are newest first, at most
+    /// `options.limit` of them, with the first match of each message
+    /// highlighted in its snippet.
+    pub fn search_regex(
+        &self,
+        options: &super::models::SearchOptions,
+        regex: &regex::Regex,
+        scan_limit: usize,
+    ) -> Result<RegexSearch> {
+        let prefilter = !options.query.trim().is_empty();
+        let mut sql = String::from(
+            r#"
+            SELECT
+                m.session_id,
+                m.id,
+                m.role,
+                m.content,
+                m.timestamp,
+                s.working_directory,
+                s.tool,
+                s.git_branch,
+                s.message_count,
+                s.started_at,
+                m.idx
+            FROM messages m
+            JOIN sessions s ON m.session_id = s.id
+            "#,
+        );
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        let mut first_param = 1;
+        if prefilter {
+            sql.push_str(
+                " WHERE m.id IN (SELECT message_id FROM messages_fts WHERE messages_fts MATCH ?1)",
+            );
+            params_vec.push(Box::new(escape_fts5_query(&options.query)));
+            first_param = 2;
+        } else {
+            sql.push_str(" WHERE 1 = 1");
+        }
+        let (filters, filter_params, next_param) = Self::message_filters(options, first_param);
+        sql.push_str(&filters);
+        params_vec.extend(filter_params);
+        if prefilter {
+            sql.push_str(" ORDER BY m.timestamp DESC");
+        } else {
+            // One extra row tells whether the limit cut anything off
+            sql.push_str(&format!(" ORDER BY m.timestamp DESC LIMIT ?{next_param}"));
+            params_vec.push(Box::new(scan_limit as i64 + 1));
+        }
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+        let mut rows = stmt.query(params_refs.as_slice())?;
+
+        let mut search = RegexSearch::default();
+        while let Some(row) = rows.next()? {
+            if !prefilter && search.scanned == scan_limit {
+                search.truncated = true;
+                break;
+            }
+            search.scanned += 1;
+
+            let content_json = decode_content(row.get_ref(3)?)?;
+            let text = serde_json::from_str::<MessageContent>(&content_json)
+                .unwrap_or(MessageContent::Text(content_json))
+                .text();
+            let Some(found) = regex.find(&text) else {
+                continue;
+            };
+
+            let role_str: String = row.get(2)?;
+            let started_at_str: Option<String> = row.get(9)?;
+            search.results.push(SearchResult {
+                session_id: parse_uuid(&row.get::<_, String>(0)?)?,
+                message_id: parse_uuid(&row.get::<_, String>(1)?)?,
+                role: role_from_str(&role_str),
+                snippet: regex_snippet(&text, found.start(), found.end()),
+                timestamp: parse_datetime(&row.get::<_, String>(4)?)?,
+                working_directory: row.get(5)?,
+                tool: row.get(6)?,
+                git_branch: row.get(7)?,
+                session_message_count: row.get(8)?,
+                session_started_at: started_at_str.map(|s| parse_datetime(&s)).transpose()?,
+                message_index: row.get(10)?,
+            });
+            if search.results.len() >= options.limit {
+                break;
+            }
+        }
+
+        Ok(search)
+    }
+
+    /// Runs a single search query without any index recovery.
+    fn search_with_options_once(
+        &self,
+        options: &super::models::SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
+        let (mut sql, mut params_vec, param_idx) = Self::search_sql(options);
+        sql.push_str(&format!(" ORDER BY timestamp DESC LIMIT ?{param_idx}"));
+        params_vec.push(Box::new(options.limit as i64));
+
+        // Prepare and execute
+        let mut stmt = self.conn.prepare(&sql)?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+
+        let rows = stmt.query_map(params_refs.as_slice(), |row| {
+            let role_str: String = row.get(2)?;
+            let git_branch: Option<String> = row.get(7)?;
+            let started_at_str: Option<String> = row.get(9)?;
+
+            Ok(SearchResult {
+                session_id: parse_uuid(&row.get::<_, String>(0)?)?,
+                message_id: parse_uuid(&row.get::<_, String>(1)?)?,
+                role: role_from_str(&role_str),
+                snippet: row.get(3)?,
+                timestamp: parse_datetime(&row.get::<_, String>(4)?)?,
+                working_directory: row.get(5)?,
+                tool: row.get(6)?,
+                git_branch,
+                session_message_count: row.get(8)?,
+                session_started_at: started_at_str.map(|s| parse_datetime(&s)).transpose()?,
+                message_index: row.get(10)?,
+            })
+        })?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to search messages")
+    }
+
+    /// Builds the search query for `options`, without ordering or limit.
+    ///
+    /// Returns the SQL, its parameters, and the number of the next free
+    /// parameter.
+    fn search_sql(
+        options: &super::models::SearchOptions,
+    ) -> (String, Vec<Box<dyn rusqlite::ToSql>>, usize) {
         // Escape the query for FTS5 to handle special characters
         let escaped_query = escape_fts5_query(&options.query);
 
@@ -1688,60 +2985,9 @@ impl Database {
         );
 
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(escaped_query.clone())];
-        let mut param_idx = 2;
-
-        // Add filters
-        if options.repo.is_some() {
-            sql.push_str(&format!(" AND s.working_directory LIKE ?{param_idx}"));
-            param_idx += 1;
-        }
-        if options.tool.is_some() {
-            sql.push_str(&format!(" AND LOWER(s.tool) = LOWER(?{param_idx})"));
-            param_idx += 1;
-        }
-        if options.since.is_some() {
-            sql.push_str(&format!(" AND s.started_at >= ?{param_idx}"));
-            param_idx += 1;
-        }
-        if options.until.is_some() {
-            sql.push_str(&format!(" AND s.started_at <= ?{param_idx}"));
-            param_idx += 1;
-        }
-        if options.project.is_some() {
-            sql.push_str(&format!(" AND s.working_directory LIKE ?{param_idx}"));
-            param_idx += 1;
-        }
-        if options.branch.is_some() {
-            sql.push_str(&format!(" AND s.git_branch LIKE ?{param_idx}"));
-            param_idx += 1;
-        }
-        if options.role.is_some() {
-            sql.push_str(&format!(" AND m.role = ?{param_idx}"));
-            param_idx += 1;
-        }
-
-        // Build first SELECT parameter list (after the FTS query param which is already in params_vec)
-        if let Some(ref wd) = options.repo {
-            params_vec.push(Box::new(format!("{wd}%")));
-        }
-        if let Some(ref tool) = options.tool {
-            params_vec.push(Box::new(tool.clone()));
-        }
-        if let Some(ts) = options.since {
-            params_vec.push(Box::new(ts.to_rfc3339()));
-        }
-        if let Some(ts) = options.until {
-            params_vec.push(Box::new(ts.to_rfc3339()));
-        }
-        if let Some(ref project) = options.project {
-            params_vec.push(Box::new(format!("%{project}%")));
-        }
-        if let Some(ref branch) = options.branch {
-            params_vec.push(Box::new(format!("%{branch}%")));
-        }
-        if let Some(ref role) = options.role {
-            params_vec.push(Box::new(role.clone()));
-        }
+        let (filters, filter_params, mut param_idx) = Self::message_filters(options, 2);
+        sql.push_str(&filters);
+        params_vec.extend(filter_params);
 
         // Add UNION for session metadata search (only if not filtering by role)
         // This finds sessions where the metadata matches, returning the first message as representative
@@ -1787,7 +3033,7 @@ impl Database {
 
             // Re-apply session-level filters to the UNION query
             if let Some(repo) = &options.repo {
-                sql.push_str(&format!(" AND s.working_directory LIKE ?{param_idx}"));
+                sql.push_str(&session_directory_filter(param_idx));
                 params_vec.push(Box::new(format!("{}%", repo)));
                 param_idx += 1;
             }
@@ -1818,41 +3064,75 @@ impl Database {
             }
         }
 
-        sql.push_str(&format!(" ORDER BY timestamp DESC LIMIT ?{param_idx}"));
-        params_vec.push(Box::new(options.limit as i64));
+        (sql, params_vec, param_idx)
+    }
 
-        // Prepare and execute
-        let mut stmt = self.conn.prepare(&sql)?;
-        let params_refs: Vec<&dyn rusqlite::ToSql> =
-            params_vec.iter().map(|p| p.as_ref()).collect();
+    /// Builds the message-level filters of `options` for a query that joins
+    /// messages as `m` and sessions as `s`, numbering parameters from
+    /// `first_param`.
+    ///
+    /// Returns the `AND ...` conditions, their parameters, and the number of
+    /// the next free parameter.
+    fn message_filters(
+        options: &super::models::SearchOptions,
+        first_param: usize,
+    ) -> (String, Vec<Box<dyn rusqlite::ToSql>>, usize) {
+        let mut filters = String::new();
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        let mut param_idx = first_param;
 
-        let rows = stmt.query_map(params_refs.as_slice(), |row| {
-            let role_str: String = row.get(2)?;
-            let git_branch: Option<String> = row.get(7)?;
-            let started_at_str: Option<String> = row.get(9)?;
+        if options.repo.is_some() {
+            filters.push_str(&session_directory_filter(param_idx));
+            param_idx += 1;
+        }
+        if options.tool.is_some() {
+            filters.push_str(&format!(" AND LOWER(s.tool) = LOWER(?{param_idx})"));
+            param_idx += 1;
+        }
+        if options.since.is_some() {
+            filters.push_str(&format!(" AND s.started_at >= ?{param_idx}"));
+            param_idx += 1;
+        }
+        if options.until.is_some() {
+            filters.push_str(&format!(" AND s.started_at <= ?{param_idx}"));
+            param_idx += 1;
+        }
+        if options.project.is_some() {
+            filters.push_str(&format!(" AND s.working_directory LIKE ?{param_idx}"));
+            param_idx += 1;
+        }
+        if options.branch.is_some() {
+            filters.push_str(&format!(" AND s.git_branch LIKE ?{param_idx}"));
+            param_idx += 1;
+        }
+        if options.role.is_some() {
+            filters.push_str(&format!(" AND m.role = ?{param_idx}"));
+            param_idx += 1;
+        }
 
-            Ok(SearchResult {
-                session_id: parse_uuid(&row.get::<_, String>(0)?)?,
-                message_id: parse_uuid(&row.get::<_, String>(1)?)?,
-                role: match role_str.as_str() {
-                    "user" => MessageRole::User,
-                    "assistant" => MessageRole::Assistant,
-                    "system" => MessageRole::System,
-                    _ => MessageRole::User,
-                },
-                snippet: row.get(3)?,
-                timestamp: parse_datetime(&row.get::<_, String>(4)?)?,
-                working_directory: row.get(5)?,
-                tool: row.get(6)?,
-                git_branch,
-                session_message_count: row.get(8)?,
-                session_started_at: started_at_str.map(|s| parse_datetime(&s)).transpose()?,
-                message_index: row.get(10)?,
-            })
-        })?;
+        if let Some(ref wd) = options.repo {
+            params_vec.push(Box::new(format!("{wd}%")));
+        }
+        if let Some(ref tool) = options.tool {
+            params_vec.push(Box::new(tool.clone()));
+        }
+        if let Some(ts) = options.since {
+            params_vec.push(Box::new(ts.to_rfc3339()));
+        }
+        if let Some(ts) = options.until {
+            params_vec.push(Box::new(ts.to_rfc3339()));
+        }
+        if let Some(ref project) = options.project {
+            params_vec.push(Box::new(format!("%{project}%")));
+        }
+        if let Some(ref branch) = options.branch {
+            params_vec.push(Box::new(format!("%{branch}%")));
+        }
+        if let Some(ref role) = options.role {
+            params_vec.push(Box::new(role.clone()));
+        }
 
-        rows.collect::<Result<Vec<_>, _>>()
-            .context("Failed to search messages")
+        (filters, params_vec, param_idx)
     }
 
     /// Gets messages around a specific message for context.
@@ -1905,6 +3185,61 @@ impl Database {
         Ok((before, after))
     }
 
+    /// Gets a single message by its ID.
+    pub fn get_message(&self, id: &Uuid) -> Result<Option<Message>> {
+        self.conn
+            .query_row(
+                "SELECT id, session_id, parent_id, idx, timestamp, role, content, model, git_branch, cwd
+                 FROM messages
+                 WHERE id = ?1",
+                params![id.to_string()],
+                Self::row_to_message,
+            )
+            .optional()
+            .context("Failed to get message")
+    }
+
+    /// Finds a message by ID prefix, searching all messages in the database.
+    ///
+    /// Works like [`Database::find_session_by_id_prefix`]: a full UUID is
+    /// looked up directly, and a shorter prefix must match exactly one message.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(message))` - If exactly one message matches the prefix
+    /// * `Ok(None)` - If no messages match the prefix
+    /// * `Err` - If multiple messages match (ambiguous prefix) or database error
+    pub fn find_message_by_id_prefix(&self, prefix: &str) -> Result<Option<Message>> {
+        if let Ok(uuid) = Uuid::parse_str(prefix) {
+            return self.get_message(&uuid);
+        }
+
+        let pattern = format!("{prefix}%");
+        let count: i32 = self.conn.query_row(
+            "SELECT COUNT(*) FROM messages WHERE id LIKE ?1",
+            params![pattern],
+            |row| row.get(0),
+        )?;
+
+        match count {
+            0 => Ok(None),
+            1 => self
+                .conn
+                .query_row(
+                    "SELECT id, session_id, parent_id, idx, timestamp, role, content, model, git_branch, cwd
+                     FROM messages
+                     WHERE id LIKE ?1",
+                    params![pattern],
+                    Self::row_to_message,
+                )
+                .optional()
+                .context("Failed to find message by prefix"),
+            n => anyhow::bail!(
+                "Ambiguous message ID prefix '{prefix}' matches {n} messages. Use a longer prefix."
+            ),
+        }
+    }
+
     /// Gets a single message by its index within a session.
     #[allow(dead_code)]
     pub fn get_message_by_index(&self, session_id: &Uuid, index: i32) -> Result<Option<Message>> {
@@ -1922,7 +3257,7 @@ impl Database {
 
     fn row_to_message(row: &rusqlite::Row) -> rusqlite::Result<Message> {
         let role_str: String = row.get(5)?;
-        let content_str: String = row.get(6)?;
+        let content_str = decode_content(row.get_ref(6)?)?;
 
         let parent_id_str: Option<String> = row.get(2)?;
         let parent_id = match parent_id_str {
@@ -1936,12 +3271,7 @@ impl Database {
             parent_id,
             index: row.get(3)?,
             timestamp: parse_datetime(&row.get::<_, String>(4)?)?,
-            role: match role_str.as_str() {
-                "user" => MessageRole::User,
-                "assistant" => MessageRole::Assistant,
-                "system" => MessageRole::System,
-                _ => MessageRole::User,
-            },
+            role: role_from_str(&role_str),
             content: serde_json::from_str(&content_str)
                 .unwrap_or(MessageContent::Text(content_str)),
             model: row.get(7)?,
@@ -1955,19 +3285,26 @@ impl Database {
     /// This should be called when:
     /// - Upgrading from a database without FTS support
     /// - The FTS index becomes corrupted or out of sync
+    /// - The search tokenizer changed
+    ///
+    /// The message index is recreated with the tokenizer this database was
+    /// opened with (see [`Database::set_search_tokenizer`]).
     ///
     /// Returns the number of messages indexed.
     pub fn rebuild_search_index(&self) -> Result<usize> {
-        // Clear existing FTS data
-        self.conn.execute("DELETE FROM messages_fts", [])?;
-        self.conn.execute("DELETE FROM sessions_fts", [])?;
+        // Drop and recreate the FTS tables rather than deleting their rows:
+        // DELETE has to read the existing index, which fails when it is corrupt.
+        self.conn.execute_batch(
+            "DROP TABLE IF EXISTS messages_fts; DROP TABLE IF EXISTS sessions_fts;",
+        )?;
+        self.create_search_tables()?;
 
         // Reindex all messages
         let mut msg_stmt = self.conn.prepare("SELECT id, content FROM messages")?;
 
         let rows = msg_stmt.query_map([], |row| {
             let id: String = row.get(0)?;
-            let content_json: String = row.get(1)?;
+            let content_json = decode_content(row.get_ref(1)?)?;
             Ok((id, content_json))
         })?;
 
@@ -2201,7 +3538,7 @@ impl Database {
     #[allow(dead_code)]
     pub fn get_unsynced_sessions(&self) -> Result<Vec<Session>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
              FROM sessions
              WHERE synced_at IS NULL
              ORDER BY started_at ASC"
@@ -2229,10 +3566,11 @@ impl Database {
     pub fn get_unsynced_sessions_for_repo(&self, repo_path: &Path) -> Result<Vec<Session>> {
         let (predicate, binds) = repo_scope_predicate(repo_path);
         let sql = format!(
-            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
              FROM sessions
              WHERE synced_at IS NULL
                AND ({predicate})
+               AND {NOT_SYNC_EXCLUDED}
              ORDER BY started_at ASC"
         );
 
@@ -2287,6 +3625,20 @@ impl Database {
             .context("Failed to get all session ids")
     }
 
+    /// Returns the ids of all sessions captured on the given machine.
+    pub fn get_session_ids_for_machine(&self, machine_id: &str) -> Result<HashSet<Uuid>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM sessions WHERE machine_id = ?1")?;
+        let rows = stmt.query_map(params![machine_id], |row| {
+            let id: String = row.get(0)?;
+            parse_uuid(&id)
+        })?;
+
+        rows.collect::<rusqlite::Result<HashSet<Uuid>>>()
+            .context("Failed to get session ids for machine")
+    }
+
     /// Returns sessions that have not been synced to the global personal store.
     ///
     /// Unsynced-global sessions are those where `global_synced_at` is NULL. Unlike
@@ -2294,12 +3646,14 @@ impl Database {
     /// repository: the global store holds every session regardless of working
     /// directory. Returns sessions ordered by start time (oldest first).
     pub fn get_unsynced_global_sessions(&self) -> Result<Vec<Session>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
+        let sql = format!(
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
              FROM sessions
              WHERE global_synced_at IS NULL
+               AND {NOT_SYNC_EXCLUDED}
              ORDER BY started_at ASC"
-        )?;
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
 
         let rows = stmt.query_map([], Self::row_to_session)?;
 
@@ -2310,7 +3664,9 @@ impl Database {
     /// Returns the count of sessions not yet synced to the global personal store.
     pub fn unsynced_global_count(&self) -> Result<i32> {
         let count: i32 = self.conn.query_row(
-            "SELECT COUNT(*) FROM sessions WHERE global_synced_at IS NULL",
+            &format!(
+                "SELECT COUNT(*) FROM sessions WHERE global_synced_at IS NULL AND {NOT_SYNC_EXCLUDED}"
+            ),
             [],
             |row| row.get(0),
         )?;
@@ -2375,7 +3731,8 @@ impl Database {
         let sql = format!(
             "SELECT COUNT(*) FROM sessions
              WHERE synced_at IS NULL
-               AND ({predicate})"
+               AND ({predicate})
+               AND {NOT_SYNC_EXCLUDED}"
         );
 
         let count: i32 =
@@ -2498,7 +3855,7 @@ impl Database {
         let sql = if working_dir.is_some() {
             r#"
             SELECT id, tool, tool_version, started_at, ended_at, model,
-                   working_directory, git_branch, source_path, message_count, machine_id
+                   working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
             FROM sessions
             WHERE working_directory LIKE ?1
               AND (
@@ -2515,7 +3872,7 @@ impl Database {
         } else {
             r#"
             SELECT id, tool, tool_version, started_at, ended_at, model,
-                   working_directory, git_branch, source_path, message_count, machine_id
+                   working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
             FROM sessions
             WHERE
               -- Session started before or during the window
@@ -2560,6 +3917,58 @@ impl Database {
         Ok(count > 0)
     }
 
+    /// Records one auto-link decision for the link audit.
+    pub fn insert_link_decision(&self, decision: &LinkDecision) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO link_decisions (session_id, commit_sha, branch_score, file_score, time_score, recency_score, confidence, threshold, passed, decided_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                decision.session_id.to_string(),
+                decision.commit_sha,
+                decision.branch_score,
+                decision.file_score,
+                decision.time_score,
+                decision.recency_score,
+                decision.confidence,
+                decision.threshold,
+                decision.passed,
+                decision.decided_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Returns the recorded auto-link decisions for a commit, oldest first.
+    ///
+    /// `commit_sha` may be a prefix of the full SHA. The audit is meant to
+    /// be read from the database directly, so this accessor is exercised
+    /// only by the storage tests.
+    #[allow(dead_code)]
+    pub fn get_link_decisions(&self, commit_sha: &str) -> Result<Vec<LinkDecision>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT session_id, commit_sha, branch_score, file_score, time_score, recency_score, confidence, threshold, passed, decided_at
+             FROM link_decisions
+             WHERE commit_sha LIKE ?1
+             ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map(params![format!("{commit_sha}%")], |row| {
+            Ok(LinkDecision {
+                session_id: parse_uuid(&row.get::<_, String>(0)?)?,
+                commit_sha: row.get(1)?,
+                branch_score: row.get(2)?,
+                file_score: row.get(3)?,
+                time_score: row.get(4)?,
+                recency_score: row.get(5)?,
+                confidence: row.get(6)?,
+                threshold: row.get(7)?,
+                passed: row.get(8)?,
+                decided_at: parse_datetime(&row.get::<_, String>(9)?)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to get link decisions")
+    }
+
     /// Finds sessions that are currently active or recently ended for a directory.
     ///
     /// This is used by forward auto-linking to find sessions to link when a commit
@@ -2589,7 +3998,7 @@ impl Database {
 
         let sql = r#"
             SELECT id, tool, tool_version, started_at, ended_at, model,
-                   working_directory, git_branch, source_path, message_count, machine_id
+                   working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
             FROM sessions
             WHERE (working_directory = ?1
                OR working_directory = ?2
@@ -2608,6 +4017,130 @@ impl Database {
             .context("Failed to find active sessions for directory")
     }
 
+    // ==================== Session Files ====================
+
+    /// Returns the indexed file paths for a session, sorted alphabetically.
+    pub fn get_session_files(&self, session_id: &Uuid) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path FROM session_files WHERE session_id = ?1 ORDER BY path")?;
+        let rows = stmt.query_map(params![session_id.to_string()], |row| row.get(0))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to get session files")
+    }
+
+    /// Rebuilds the session file index from stored messages.
+    ///
+    /// Re-extracts referenced file paths for every session. The migration
+    /// that creates the index backfills it the same way; `lore db repair`
+    /// calls this to fix an index that has drifted from message content.
+    /// Runs in a single transaction.
+    ///
+    /// Returns the number of file entries indexed.
+    pub fn rebuild_file_index(&mut self) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let count = Self::rebuild_file_index_in(&tx)?;
+        tx.commit()?;
+        Ok(count)
+    }
+
+    /// Rebuilds the session file index using the given connection.
+    fn rebuild_file_index_in(conn: &Connection) -> Result<usize> {
+        conn.execute("DELETE FROM session_files", [])?;
+
+        let sessions: Vec<(String, String)> = conn
+            .prepare("SELECT id, working_directory FROM sessions")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut msg_stmt = conn.prepare("SELECT content FROM messages WHERE session_id = ?1")?;
+        let mut insert_stmt =
+            conn.prepare("INSERT OR IGNORE INTO session_files (session_id, path) VALUES (?1, ?2)")?;
+
+        let mut count = 0;
+        for (session_id, working_directory) in sessions {
+            let contents: Vec<String> = msg_stmt
+                .query_map(params![session_id], |row| decode_content(row.get_ref(0)?))?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // extract_session_files only inspects message content, so build
+            // lightweight messages from the stored JSON.
+            let messages: Vec<Message> = contents
+                .into_iter()
+                .filter_map(|json| serde_json::from_str::<MessageContent>(&json).ok())
+                .filter(|content| matches!(content, MessageContent::Blocks(_)))
+                .map(|content| Message {
+                    id: Uuid::nil(),
+                    session_id: Uuid::nil(),
+                    parent_id: None,
+                    index: 0,
+                    timestamp: Utc::now(),
+                    role: MessageRole::Assistant,
+                    content,
+                    model: None,
+                    git_branch: None,
+                    cwd: None,
+                })
+                .collect();
+
+            for path in extract_session_files(&messages, &working_directory) {
+                count += insert_stmt.execute(params![session_id, path])?;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Finds sessions whose tool calls referenced the given file path.
+    ///
+    /// `path` may be relative (matched against the stored relative path, or
+    /// as a trailing path component sequence of a stored absolute path) or
+    /// absolute (matched against the session's working directory joined with
+    /// the stored relative path). When `working_dir` is set, only sessions
+    /// in that directory or below are returned. Results are ordered by start
+    /// time, most recent first.
+    pub fn sessions_touching_file(
+        &self,
+        path: &str,
+        working_dir: Option<&str>,
+    ) -> Result<Vec<Session>> {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        let suffix_pattern = format!(
+            "%/{}",
+            path.trim_start_matches('/')
+                .replace('|', "||")
+                .replace('%', "|%")
+                .replace('_', "|_")
+        );
+
+        let mut sql = String::from(
+            "SELECT DISTINCT s.id, s.tool, s.tool_version, s.started_at, s.ended_at, s.model, s.working_directory, s.git_branch, s.source_path, s.message_count, s.machine_id, s.input_tokens, s.output_tokens, s.parent_session_id
+             FROM sessions s
+             JOIN session_files f ON f.session_id = s.id
+             WHERE (f.path = ?1
+                    OR f.path LIKE ?2 ESCAPE '|'
+                    OR rtrim(s.working_directory, '/') || '/' || f.path = ?1)",
+        );
+        let mut binds = vec![path.to_string(), suffix_pattern];
+
+        if let Some(wd) = working_dir {
+            let (exact, trailing, like_pattern) = directory_match_params(wd);
+            sql.push_str(
+                " AND (s.working_directory = ?3 OR s.working_directory = ?4 OR s.working_directory LIKE ?5 ESCAPE '|')",
+            );
+            binds.extend([exact, trailing, like_pattern]);
+        }
+        sql.push_str(" ORDER BY s.started_at DESC");
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(binds.iter()),
+            Self::row_to_session,
+        )?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to find sessions touching file")
+    }
+
     // ==================== Session Deletion ====================
 
     /// Deletes a session and all its associated data.
@@ -2657,6 +4190,24 @@ impl Database {
             params![session_id_str],
         )?;
 
+        // Delete file index entries
+        self.conn.execute(
+            "DELETE FROM session_files WHERE session_id = ?1",
+            params![session_id_str],
+        )?;
+
+        // Delete auto-link audit entries
+        self.conn.execute(
+            "DELETE FROM link_decisions WHERE session_id = ?1",
+            params![session_id_str],
+        )?;
+
+        // Delete directory entries
+        self.conn.execute(
+            "DELETE FROM session_directories WHERE session_id = ?1",
+            params![session_id_str],
+        )?;
+
         // Delete from sessions_fts
         self.conn.execute(
             "DELETE FROM sessions_fts WHERE session_id = ?1",
@@ -2672,6 +4223,215 @@ impl Database {
         Ok((messages_deleted, links_deleted))
     }
 
+    // ==================== Session Merging ====================
+
+    /// Merges `others` into the `primary` session, in one transaction.
+    ///
+    /// Messages of all sessions are reassigned to the primary and reindexed
+    /// in timestamp order; messages with equal timestamps keep the order of
+    /// `primary` followed by `others`, then their original index. The
+    /// primary's message count, time range, and token totals are updated to
+    /// cover the merged messages. Links, annotations, tags, and indexed files
+    /// and directories move to the primary, skipping any the primary already
+    /// has (a link duplicates another when it points at the same commit). A
+    /// summary moves only if the primary has none. The other sessions are
+    /// then deleted and recorded as merged, so re-importing their source
+    /// files does not bring them back.
+    pub fn merge_sessions(
+        &mut self,
+        primary: &Uuid,
+        others: &[Uuid],
+    ) -> Result<SessionMergeReport> {
+        if others.is_empty() {
+            bail!("No sessions to merge into {primary}");
+        }
+        if others.contains(primary) {
+            bail!("Cannot merge session {primary} into itself");
+        }
+
+        let tx = self.conn.transaction()?;
+        let primary_str = primary.to_string();
+
+        let mut sessions = Vec::with_capacity(others.len() + 1);
+        for id in std::iter::once(primary).chain(others) {
+            let session = tx
+                .query_row(
+                    "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id FROM sessions WHERE id = ?1",
+                    params![id.to_string()],
+                    Self::row_to_session,
+                )
+                .optional()?
+                .with_context(|| format!("Session {id} not found"))?;
+            sessions.push(session);
+        }
+
+        // (timestamp, session position, original index, message id)
+        let mut order: Vec<(DateTime<Utc>, usize, i64, String)> = Vec::new();
+        {
+            let mut stmt =
+                tx.prepare("SELECT id, idx, timestamp FROM messages WHERE session_id = ?1")?;
+            for (position, session) in sessions.iter().enumerate() {
+                let rows = stmt.query_map(params![session.id.to_string()], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                })?;
+                for row in rows {
+                    let (id, idx, timestamp) = row?;
+                    let timestamp = DateTime::parse_from_rfc3339(&timestamp)
+                        .map(|t| t.with_timezone(&Utc))
+                        .unwrap_or(session.started_at);
+                    order.push((timestamp, position, idx, id));
+                }
+            }
+        }
+        order.sort();
+
+        let mut report = SessionMergeReport::default();
+        {
+            let mut update =
+                tx.prepare("UPDATE messages SET session_id = ?1, idx = ?2 WHERE id = ?3")?;
+            for (idx, (_, position, _, id)) in order.iter().enumerate() {
+                update.execute(params![primary_str, idx as i64, id])?;
+                if *position > 0 {
+                    report.messages_moved += 1;
+                }
+            }
+        }
+
+        let started_at = sessions.iter().map(|s| s.started_at).min();
+        let ended_at = sessions.iter().filter_map(|s| s.ended_at).max();
+        let sum_tokens = |tokens: fn(&Session) -> Option<i64>| -> Option<i64> {
+            sessions
+                .iter()
+                .filter_map(tokens)
+                .fold(None, |total, n| Some(total.unwrap_or(0) + n))
+        };
+        tx.execute(
+            "UPDATE sessions SET message_count = ?1, started_at = ?2, ended_at = ?3, input_tokens = ?4, output_tokens = ?5, synced_at = NULL, global_synced_at = NULL WHERE id = ?6",
+            params![
+                order.len() as i64,
+                started_at.map(|t| t.to_rfc3339()),
+                ended_at.map(|t| t.to_rfc3339()),
+                sum_tokens(|s| s.input_tokens),
+                sum_tokens(|s| s.output_tokens),
+                primary_str,
+            ],
+        )?;
+
+        let merged_at = Utc::now().to_rfc3339();
+        for other in others {
+            let other_str = other.to_string();
+
+            tx.execute(
+                "DELETE FROM session_links WHERE session_id = ?1 AND commit_sha IN
+                 (SELECT commit_sha FROM session_links WHERE session_id = ?2 AND commit_sha IS NOT NULL)",
+                params![other_str, primary_str],
+            )?;
+            report.links_moved += tx.execute(
+                "UPDATE session_links SET session_id = ?1 WHERE session_id = ?2",
+                params![primary_str, other_str],
+            )?;
+            tx.execute(
+                "UPDATE annotations SET session_id = ?1 WHERE session_id = ?2",
+                params![primary_str, other_str],
+            )?;
+            tx.execute(
+                "UPDATE link_decisions SET session_id = ?1 WHERE session_id = ?2",
+                params![primary_str, other_str],
+            )?;
+            // Rows the primary already has are left behind and deleted below
+            for table in ["tags", "summaries", "session_files", "session_directories"] {
+                tx.execute(
+                    &format!("UPDATE OR IGNORE {table} SET session_id = ?1 WHERE session_id = ?2"),
+                    params![primary_str, other_str],
+                )?;
+                tx.execute(
+                    &format!("DELETE FROM {table} WHERE session_id = ?1"),
+                    params![other_str],
+                )?;
+            }
+
+            tx.execute(
+                "DELETE FROM sessions_fts WHERE session_id = ?1",
+                params![other_str],
+            )?;
+            tx.execute("DELETE FROM sessions WHERE id = ?1", params![other_str])?;
+            // Subagent sessions spawned by the merged session now belong to
+            // the primary
+            tx.execute(
+                "UPDATE sessions SET parent_session_id = ?1 WHERE parent_session_id = ?2",
+                params![primary_str, other_str],
+            )?;
+            tx.execute(
+                "INSERT OR REPLACE INTO merged_sessions (session_id, merged_into, merged_at) VALUES (?1, ?2, ?3)",
+                params![other_str, primary_str, merged_at],
+            )?;
+            // Sessions merged into this one earlier now live in the primary
+            tx.execute(
+                "UPDATE merged_sessions SET merged_into = ?1 WHERE merged_into = ?2",
+                params![primary_str, other_str],
+            )?;
+            report.sessions_deleted += 1;
+        }
+
+        tx.commit()?;
+        Ok(report)
+    }
+
+    /// Returns when the session was merged into another session, if it was.
+    /// Merged sessions are not imported again.
+    pub fn merged_away_at(&self, session_id: &Uuid) -> Result<Option<DateTime<Utc>>> {
+        let merged_at: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT merged_at FROM merged_sessions WHERE session_id = ?1",
+                params![session_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(merged_at.map(|s| parse_datetime(&s)).transpose()?)
+    }
+
+    /// Returns when another session was last merged into this one, if any
+    /// was.
+    pub fn last_merged_into_at(&self, session_id: &Uuid) -> Result<Option<DateTime<Utc>>> {
+        let merged_at: Option<String> = self.conn.query_row(
+            "SELECT MAX(merged_at) FROM merged_sessions WHERE merged_into = ?1",
+            params![session_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(merged_at.map(|s| parse_datetime(&s)).transpose()?)
+    }
+
+    /// Records that retention pruning deleted the session, so it is not
+    /// imported again from its source file.
+    pub fn record_pruned_session(&self, session_id: &Uuid, pruned_at: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO pruned_sessions (session_id, pruned_at) VALUES (?1, ?2)",
+            params![session_id.to_string(), pruned_at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Returns true if the session was deleted by retention pruning and
+    /// should not be imported again.
+    pub fn is_pruned_session(&self, session_id: &Uuid) -> Result<bool> {
+        Self::is_pruned(&self.conn, session_id)
+    }
+
+    /// Returns whether a session was deleted by retention pruning.
+    fn is_pruned(conn: &Connection, session_id: &Uuid) -> Result<bool> {
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM pruned_sessions WHERE session_id = ?1",
+            params![session_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
     // ==================== Annotations ====================
 
     /// Inserts a new annotation for a session.
@@ -2942,7 +4702,7 @@ impl Database {
     pub fn list_sessions_with_tag(&self, label: &str, limit: usize) -> Result<Vec<Session>> {
         let mut stmt = self.conn.prepare(
             "SELECT s.id, s.tool, s.tool_version, s.started_at, s.ended_at, s.model,
-                    s.working_directory, s.git_branch, s.source_path, s.message_count, s.machine_id
+                    s.working_directory, s.git_branch, s.source_path, s.message_count, s.machine_id, s.input_tokens, s.output_tokens, s.parent_session_id
              FROM sessions s
              INNER JOIN tags t ON s.id = t.session_id
              WHERE t.label = ?1
@@ -2965,14 +4725,15 @@ impl Database {
     pub fn insert_summary(&self, summary: &Summary) -> Result<()> {
         self.conn.execute(
             r#"
-            INSERT INTO summaries (id, session_id, content, generated_at)
-            VALUES (?1, ?2, ?3, ?4)
+            INSERT INTO summaries (id, session_id, content, generated_at, model)
+            VALUES (?1, ?2, ?3, ?4, ?5)
             "#,
             params![
                 summary.id.to_string(),
                 summary.session_id.to_string(),
                 summary.content,
                 summary.generated_at.to_rfc3339(),
+                summary.model,
             ],
         )?;
         // Local edit: re-open the parent session for the next sync.
@@ -2991,11 +4752,12 @@ impl Database {
     fn write_summary_newer(conn: &Connection, summary: &Summary) -> Result<()> {
         conn.execute(
             r#"
-            INSERT INTO summaries (id, session_id, content, generated_at)
-            VALUES (?1, ?2, ?3, ?4)
+            INSERT INTO summaries (id, session_id, content, generated_at, model)
+            VALUES (?1, ?2, ?3, ?4, ?5)
             ON CONFLICT(session_id) DO UPDATE SET
                 content = excluded.content,
-                generated_at = excluded.generated_at
+                generated_at = excluded.generated_at,
+                model = excluded.model
             WHERE excluded.generated_at > generated_at
             "#,
             params![
@@ -3003,6 +4765,7 @@ impl Database {
                 summary.session_id.to_string(),
                 summary.content,
                 summary.generated_at.to_rfc3339(),
+                summary.model,
             ],
         )?;
         Ok(())
@@ -3012,7 +4775,7 @@ impl Database {
     pub fn get_summary(&self, session_id: &Uuid) -> Result<Option<Summary>> {
         self.conn
             .query_row(
-                "SELECT id, session_id, content, generated_at
+                "SELECT id, session_id, content, generated_at, model
                  FROM summaries
                  WHERE session_id = ?1",
                 params![session_id.to_string()],
@@ -3022,6 +4785,7 @@ impl Database {
                         session_id: parse_uuid(&row.get::<_, String>(1)?)?,
                         content: row.get(2)?,
                         generated_at: parse_datetime(&row.get::<_, String>(3)?)?,
+                        model: row.get(4)?,
                     })
                 },
             )
@@ -3065,15 +4829,55 @@ impl Database {
         Ok(result)
     }
 
+    /// Lists sessions that have messages but no summary, most recent first.
+    ///
+    /// `working_dir` is a path prefix matched like
+    /// [`Self::list_sessions_filtered`] does. Used to summarize a backlog of
+    /// sessions; sessions summarized by an earlier, interrupted run are left
+    /// out, so a rerun resumes where it stopped.
+    pub fn list_sessions_without_summary(&self, working_dir: Option<&str>) -> Result<Vec<Session>> {
+        let mut sql = String::from(
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
+             FROM sessions
+             WHERE message_count > 0
+               AND id NOT IN (SELECT session_id FROM summaries)",
+        );
+        let mut param_values: Vec<String> = Vec::new();
+        if let Some(wd) = working_dir {
+            sql.push_str(
+                " AND (working_directory LIKE ?1 OR id IN \
+                 (SELECT session_id FROM session_directories WHERE directory LIKE ?1))",
+            );
+            param_values.push(format!("{wd}%"));
+        }
+        sql.push_str(" ORDER BY started_at DESC");
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(param_values.iter()),
+            Self::row_to_session,
+        )?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to list sessions without a summary")
+    }
+
     /// Updates the summary for a session.
     ///
-    /// Updates the content and generated_at timestamp for an existing summary.
+    /// Updates the content, model, and generated_at timestamp for an existing
+    /// summary. `model` is the model that generated the new content, or None
+    /// for hand-written text.
     /// Returns `true` if a summary was updated, `false` if no summary exists.
-    pub fn update_summary(&self, session_id: &Uuid, content: &str) -> Result<bool> {
+    pub fn update_summary(
+        &self,
+        session_id: &Uuid,
+        content: &str,
+        model: Option<&str>,
+    ) -> Result<bool> {
         let now = chrono::Utc::now().to_rfc3339();
         let rows_affected = self.conn.execute(
-            "UPDATE summaries SET content = ?1, generated_at = ?2 WHERE session_id = ?3",
-            params![content, now, session_id.to_string()],
+            "UPDATE summaries SET content = ?1, generated_at = ?2, model = ?3 WHERE session_id = ?4",
+            params![content, now, model, session_id.to_string()],
         )?;
         if rows_affected > 0 {
             // Local edit: re-open the parent session for the next sync.
@@ -3101,20 +4905,113 @@ impl Database {
             "DELETE FROM summaries WHERE session_id = ?1",
             params![session_id.to_string()],
         )?;
-        if rows_affected > 0 {
-            if let Some(id) = id {
-                Self::record_tombstone(
-                    &self.conn,
-                    &id,
-                    TOMBSTONE_KIND_SUMMARY,
-                    session_id,
-                    Utc::now(),
-                )?;
-            }
-            // Local edit: re-open the parent session for the next sync.
-            self.mark_session_unsynced(session_id)?;
-        }
-        Ok(rows_affected > 0)
+        if rows_affected > 0 {
+            if let Some(id) = id {
+                Self::record_tombstone(
+                    &self.conn,
+                    &id,
+                    TOMBSTONE_KIND_SUMMARY,
+                    session_id,
+                    Utc::now(),
+                )?;
+            }
+            // Local edit: re-open the parent session for the next sync.
+            self.mark_session_unsynced(session_id)?;
+        }
+        Ok(rows_affected > 0)
+    }
+
+    // ==================== Repositories ====================
+
+    /// Records that a session ran in the repository at `path`.
+    ///
+    /// Creates the repository row on first sight. Later calls update the name,
+    /// keep the known remote URL when `remote_url` is None, and only move
+    /// `last_session_at` forward, so importing old sessions never makes a
+    /// repository look less recently used.
+    pub fn upsert_repository(
+        &self,
+        path: &str,
+        name: &str,
+        remote_url: Option<&str>,
+        session_at: DateTime<Utc>,
+    ) -> Result<()> {
+        self.conn.execute(
+            r#"
+            INSERT INTO repositories (id, path, name, remote_url, created_at, last_session_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(path) DO UPDATE SET
+                name = excluded.name,
+                remote_url = COALESCE(excluded.remote_url, remote_url),
+                last_session_at = MAX(COALESCE(last_session_at, ''), excluded.last_session_at)
+            "#,
+            params![
+                Uuid::new_v4().to_string(),
+                path,
+                name,
+                remote_url,
+                Utc::now().to_rfc3339(),
+                session_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Lists the directories with captured sessions, most recently used first.
+    ///
+    /// Every distinct session working directory is included, joined on path
+    /// with its `repositories` row when one exists to take the name and
+    /// remote URL from it. Directories without a row, such as those of
+    /// sessions imported before the table was populated, still appear, named
+    /// after the last path component.
+    pub fn list_repository_activity(&self, limit: usize) -> Result<Vec<RepositoryActivity>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.working_directory, r.name, r.remote_url, COUNT(*), MAX(s.started_at)
+             FROM sessions s
+             LEFT JOIN repositories r ON r.path = s.working_directory
+             GROUP BY s.working_directory
+             ORDER BY MAX(s.started_at) DESC
+             LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], |row| {
+            let path: String = row.get(0)?;
+            let name: Option<String> = row.get(1)?;
+            Ok(RepositoryActivity {
+                name: name.unwrap_or_else(|| repository_name(&path)),
+                path,
+                remote_url: row.get(2)?,
+                session_count: row.get(3)?,
+                last_session_at: parse_datetime(&row.get::<_, String>(4)?)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to list repositories")
+    }
+
+    /// Lists the recorded repositories, most recently used first.
+    ///
+    /// Unlike [`Database::list_repository_activity`], only directories that
+    /// have a `repositories` row are returned.
+    #[allow(dead_code)]
+    pub fn list_repositories(&self) -> Result<Vec<Repository>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, path, name, remote_url, created_at, last_session_at
+             FROM repositories
+             ORDER BY last_session_at DESC, path",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let last_session_at: Option<String> = row.get(5)?;
+            Ok(Repository {
+                id: parse_uuid(&row.get::<_, String>(0)?)?,
+                path: row.get(1)?,
+                name: row.get(2)?,
+                remote_url: row.get(3)?,
+                created_at: parse_datetime(&row.get::<_, String>(4)?)?,
+                last_session_at: last_session_at.as_deref().map(parse_datetime).transpose()?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to list repositories")
     }
 
     // ==================== Machines ====================
@@ -3139,7 +5036,6 @@ impl Database {
     /// Gets a machine by ID.
     ///
     /// Returns `None` if no machine with the given ID exists.
-    #[allow(dead_code)]
     pub fn get_machine(&self, id: &str) -> Result<Option<Machine>> {
         self.conn
             .query_row(
@@ -3161,7 +5057,6 @@ impl Database {
     ///
     /// Returns the machine name if found, otherwise returns a truncated UUID
     /// (first 8 characters) for readability.
-    #[allow(dead_code)]
     pub fn get_machine_name(&self, id: &str) -> Result<String> {
         if let Some(machine) = self.get_machine(id)? {
             Ok(machine.name)
@@ -3207,7 +5102,7 @@ impl Database {
         self.conn
             .query_row(
                 "SELECT id, tool, tool_version, started_at, ended_at, model,
-                        working_directory, git_branch, source_path, message_count, machine_id
+                        working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
                  FROM sessions
                  WHERE working_directory LIKE ?1
                  ORDER BY started_at DESC
@@ -3230,6 +5125,48 @@ impl Database {
         Ok(())
     }
 
+    /// Compresses the stored content of every message that is still plain
+    /// text and at least [`COMPRESS_CONTENT_THRESHOLD`] bytes long.
+    ///
+    /// Works through the messages table in batches within one transaction.
+    /// The search index holds extracted text, not the stored JSON, so it is
+    /// left as is. Returns the number of messages compressed.
+    pub fn compress_message_content(&mut self) -> Result<usize> {
+        const BATCH_SIZE: i64 = 500;
+
+        let tx = self.conn.transaction()?;
+        let mut compressed = 0;
+        {
+            let mut select = tx.prepare(
+                "SELECT rowid, content FROM messages
+                 WHERE rowid > ?1 AND typeof(content) = 'text'
+                   AND length(CAST(content AS BLOB)) >= ?2
+                 ORDER BY rowid
+                 LIMIT ?3",
+            )?;
+            let mut update = tx.prepare("UPDATE messages SET content = ?1 WHERE rowid = ?2")?;
+            let mut last_rowid = 0i64;
+            loop {
+                let batch: Vec<(i64, String)> = select
+                    .query_map(
+                        params![last_rowid, COMPRESS_CONTENT_THRESHOLD as i64, BATCH_SIZE],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )?
+                    .collect::<Result<Vec<_>, _>>()?;
+                let Some(&(rowid, _)) = batch.last() else {
+                    break;
+                };
+                last_rowid = rowid;
+                for (rowid, json) in batch {
+                    update.execute(params![encode_content(json, true)?, rowid])?;
+                    compressed += 1;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(compressed)
+    }
+
     /// Returns the file size of the database in bytes.
     ///
     /// Returns `None` for in-memory databases.
@@ -3242,6 +5179,126 @@ impl Database {
         }
     }
 
+    /// Recomputes the stored aggregates for one session from its messages.
+    ///
+    /// Sets `message_count` to the number of rows in the messages table and,
+    /// when `ended_at` is missing, fills it from the latest message
+    /// timestamp. Running it again on a repaired session changes nothing.
+    pub fn recount_session_messages(&self, session_id: &Uuid) -> Result<SessionRecount> {
+        Self::recount_in(&self.conn, &session_id.to_string())
+    }
+
+    /// Applies [`Database::recount_session_messages`] on `conn`, so a
+    /// transaction can repair many sessions at once.
+    fn recount_in(conn: &Connection, session_id: &str) -> Result<SessionRecount> {
+        let counted = conn.execute(
+            "UPDATE sessions
+             SET message_count = (SELECT COUNT(*) FROM messages WHERE session_id = ?1)
+             WHERE id = ?1
+               AND message_count != (SELECT COUNT(*) FROM messages WHERE session_id = ?1)",
+            params![session_id],
+        )?;
+        let ended = conn.execute(
+            "UPDATE sessions
+             SET ended_at = (SELECT MAX(timestamp) FROM messages WHERE session_id = ?1)
+             WHERE id = ?1
+               AND ended_at IS NULL
+               AND EXISTS (SELECT 1 FROM messages WHERE session_id = ?1)",
+            params![session_id],
+        )?;
+        Ok(SessionRecount {
+            message_count_fixed: counted > 0,
+            ended_at_filled: ended > 0,
+        })
+    }
+
+    /// Recomputes `message_count` and missing `ended_at` for every session.
+    ///
+    /// Applies [`Database::recount_session_messages`] to each session in one
+    /// transaction. Running it again on a repaired database changes nothing.
+    pub fn repair_session_aggregates(&mut self) -> Result<RepairReport> {
+        let tx = self.conn.transaction()?;
+
+        let ids: Vec<String> = {
+            let mut stmt = tx.prepare("SELECT id FROM sessions")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+        let mut report = RepairReport {
+            sessions_checked: ids.len(),
+            ..RepairReport::default()
+        };
+        for id in &ids {
+            let recount = Self::recount_in(&tx, id)?;
+            report.message_counts_fixed += usize::from(recount.message_count_fixed);
+            report.ended_at_filled += usize::from(recount.ended_at_filled);
+        }
+
+        tx.commit()?;
+        Ok(report)
+    }
+
+    /// Checks the database file and the consistency of its records.
+    ///
+    /// Runs SQLite's `PRAGMA integrity_check`, counts rows in each
+    /// [`SESSION_CHILD_TABLES`] table whose session no longer exists, and
+    /// reports whether the search index needs rebuilding.
+    pub fn verify(&self) -> Result<VerifyReport> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let integrity_errors = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|line| line != "ok")
+            .collect();
+
+        let mut orphans = Vec::new();
+        for table in SESSION_CHILD_TABLES {
+            let count: i64 = self.conn.query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM {table}
+                     WHERE session_id NOT IN (SELECT id FROM sessions)"
+                ),
+                [],
+                |row| row.get(0),
+            )?;
+            if count > 0 {
+                orphans.push((table, count as usize));
+            }
+        }
+
+        Ok(VerifyReport {
+            integrity_errors,
+            orphans,
+            search_index_stale: self.search_index_needs_rebuild()?,
+        })
+    }
+
+    /// Deletes rows whose session no longer exists from every
+    /// [`SESSION_CHILD_TABLES`] table, in one transaction.
+    ///
+    /// Orphaned messages are also removed from the search index. Returns the
+    /// number of rows deleted.
+    pub fn delete_orphans(&mut self) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM messages_fts WHERE message_id IN
+             (SELECT id FROM messages WHERE session_id NOT IN (SELECT id FROM sessions))",
+            [],
+        )?;
+        let mut deleted = 0;
+        for table in SESSION_CHILD_TABLES {
+            deleted += tx.execute(
+                &format!("DELETE FROM {table} WHERE session_id NOT IN (SELECT id FROM sessions)"),
+                [],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(deleted)
+    }
+
     /// Deletes sessions older than the specified date.
     ///
     /// Also deletes all associated messages, links, and FTS entries.
@@ -3253,15 +5310,35 @@ impl Database {
     /// # Returns
     ///
     /// The number of sessions deleted.
+    #[allow(dead_code)]
     pub fn delete_sessions_older_than(&self, before: DateTime<Utc>) -> Result<usize> {
-        let before_str = before.to_rfc3339();
+        self.delete_sessions_older_than_filtered(before, None, None)
+    }
+
+    /// Deletes sessions older than the specified date that match the filters.
+    ///
+    /// Like [`Database::delete_sessions_older_than`], but only sessions from
+    /// `tool` (when given) whose working directory starts with `working_dir`
+    /// (when given) are deleted.
+    ///
+    /// # Returns
+    ///
+    /// The number of sessions deleted.
+    pub fn delete_sessions_older_than_filtered(
+        &self,
+        before: DateTime<Utc>,
+        tool: Option<&str>,
+        working_dir: Option<&str>,
+    ) -> Result<usize> {
+        let (where_clause, param_values) = prune_conditions(before, tool, working_dir);
 
         // Get session IDs to delete
         let mut stmt = self
             .conn
-            .prepare("SELECT id FROM sessions WHERE started_at < ?1")?;
+            .prepare(&format!("SELECT id FROM sessions WHERE {where_clause}"))?;
+        let params = rusqlite::params_from_iter(param_values.iter().map(|p| p.as_ref()));
         let session_ids: Vec<String> = stmt
-            .query_map(params![before_str], |row| row.get(0))?
+            .query_map(params, |row| row.get(0))?
             .collect::<Result<Vec<_>, _>>()?;
 
         if session_ids.is_empty() {
@@ -3270,7 +5347,7 @@ impl Database {
 
         let count = session_ids.len();
 
-        // Delete associated data for each session
+        // Delete associated data for each session, then the session itself
         for session_id_str in &session_ids {
             // Delete from messages_fts
             self.conn.execute(
@@ -3308,18 +5385,36 @@ impl Database {
                 params![session_id_str],
             )?;
 
+            // Delete file index entries
+            self.conn.execute(
+                "DELETE FROM session_files WHERE session_id = ?1",
+                params![session_id_str],
+            )?;
+
+            // Delete auto-link audit entries
+            self.conn.execute(
+                "DELETE FROM link_decisions WHERE session_id = ?1",
+                params![session_id_str],
+            )?;
+
+            // Delete directory entries
+            self.conn.execute(
+                "DELETE FROM session_directories WHERE session_id = ?1",
+                params![session_id_str],
+            )?;
+
             // Delete from sessions_fts
             self.conn.execute(
                 "DELETE FROM sessions_fts WHERE session_id = ?1",
                 params![session_id_str],
             )?;
-        }
 
-        // Delete the sessions
-        self.conn.execute(
-            "DELETE FROM sessions WHERE started_at < ?1",
-            params![before_str],
-        )?;
+            // Delete the session
+            self.conn.execute(
+                "DELETE FROM sessions WHERE id = ?1",
+                params![session_id_str],
+            )?;
+        }
 
         Ok(count)
     }
@@ -3333,11 +5428,25 @@ impl Database {
     /// # Returns
     ///
     /// The number of sessions that would be deleted.
+    #[allow(dead_code)]
     pub fn count_sessions_older_than(&self, before: DateTime<Utc>) -> Result<i32> {
-        let before_str = before.to_rfc3339();
+        self.count_sessions_older_than_filtered(before, None, None)
+    }
+
+    /// Counts sessions older than the specified date that match the filters.
+    ///
+    /// Uses the same filters as [`Database::delete_sessions_older_than_filtered`].
+    pub fn count_sessions_older_than_filtered(
+        &self,
+        before: DateTime<Utc>,
+        tool: Option<&str>,
+        working_dir: Option<&str>,
+    ) -> Result<i32> {
+        let (where_clause, param_values) = prune_conditions(before, tool, working_dir);
+        let params = rusqlite::params_from_iter(param_values.iter().map(|p| p.as_ref()));
         let count: i32 = self.conn.query_row(
-            "SELECT COUNT(*) FROM sessions WHERE started_at < ?1",
-            params![before_str],
+            &format!("SELECT COUNT(*) FROM sessions WHERE {where_clause}"),
+            params,
             |row| row.get(0),
         )?;
         Ok(count)
@@ -3352,21 +5461,69 @@ impl Database {
     /// # Returns
     ///
     /// A vector of sessions that would be deleted, ordered by start date.
+    #[allow(dead_code)]
     pub fn get_sessions_older_than(&self, before: DateTime<Utc>) -> Result<Vec<Session>> {
-        let before_str = before.to_rfc3339();
-        let mut stmt = self.conn.prepare(
-            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
+        self.get_sessions_older_than_filtered(before, None, None)
+    }
+
+    /// Returns sessions older than the specified date that match the filters.
+    ///
+    /// Uses the same filters as [`Database::delete_sessions_older_than_filtered`].
+    pub fn get_sessions_older_than_filtered(
+        &self,
+        before: DateTime<Utc>,
+        tool: Option<&str>,
+        working_dir: Option<&str>,
+    ) -> Result<Vec<Session>> {
+        let (where_clause, param_values) = prune_conditions(before, tool, working_dir);
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
              FROM sessions
-             WHERE started_at < ?1
-             ORDER BY started_at ASC",
-        )?;
+             WHERE {where_clause}
+             ORDER BY started_at ASC"
+        ))?;
 
-        let rows = stmt.query_map(params![before_str], Self::row_to_session)?;
+        let params = rusqlite::params_from_iter(param_values.iter().map(|p| p.as_ref()));
+        let rows = stmt.query_map(params, Self::row_to_session)?;
 
         rows.collect::<Result<Vec<_>, _>>()
             .context("Failed to get sessions older than cutoff")
     }
 
+    /// Returns sessions older than the specified date that are safe to prune.
+    ///
+    /// Like [`Database::get_sessions_older_than`], but leaves out sessions
+    /// still waiting to be pushed. A sync track counts as in use once any
+    /// session has been synced on it; on a track in use, a session that is
+    /// not excluded from sync and has no sync timestamp is kept. Tracks that
+    /// were never used do not hold sessions back.
+    pub fn get_prunable_sessions_older_than(&self, before: DateTime<Utc>) -> Result<Vec<Session>> {
+        let (mut where_clause, param_values) = prune_conditions(before, None, None);
+        if self.last_sync_time()?.is_some() {
+            where_clause.push_str(&format!(
+                " AND (synced_at IS NOT NULL OR NOT ({NOT_SYNC_EXCLUDED}))"
+            ));
+        }
+        if self.last_global_sync_time()?.is_some() {
+            where_clause.push_str(&format!(
+                " AND (global_synced_at IS NOT NULL OR NOT ({NOT_SYNC_EXCLUDED}))"
+            ));
+        }
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
+             FROM sessions
+             WHERE {where_clause}
+             ORDER BY started_at ASC"
+        ))?;
+
+        let params = rusqlite::params_from_iter(param_values.iter().map(|p| p.as_ref()));
+        let rows = stmt.query_map(params, Self::row_to_session)?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .context("Failed to get prunable sessions")
+    }
+
     /// Returns database statistics including counts and date ranges.
     ///
     /// # Returns
@@ -3408,6 +5565,24 @@ impl Database {
             .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
             .collect::<Result<Vec<_>, _>>()?;
 
+        // Get token usage by model, counting only sessions that report usage
+        let mut stmt = self.conn.prepare(
+            "SELECT model, COUNT(*), COALESCE(SUM(input_tokens), 0), COALESCE(SUM(output_tokens), 0)
+             FROM sessions
+             WHERE input_tokens IS NOT NULL OR output_tokens IS NOT NULL
+             GROUP BY model ORDER BY model",
+        )?;
+        let usage_by_model: Vec<ModelUsage> = stmt
+            .query_map([], |row| {
+                Ok(ModelUsage {
+                    model: row.get(0)?,
+                    sessions: row.get(1)?,
+                    input_tokens: row.get(2)?,
+                    output_tokens: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(DatabaseStats {
             session_count,
             message_count,
@@ -3415,6 +5590,7 @@ impl Database {
             oldest_session,
             newest_session,
             sessions_by_tool,
+            usage_by_model,
         })
     }
 
@@ -3457,7 +5633,7 @@ impl Database {
         };
 
         let sql = format!(
-            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id
+            "SELECT id, tool, tool_version, started_at, ended_at, model, working_directory, git_branch, source_path, message_count, machine_id, input_tokens, output_tokens, parent_session_id
              FROM sessions{}
              ORDER BY started_at DESC",
             where_clause
@@ -3663,13 +5839,174 @@ pub struct DatabaseStats {
     pub newest_session: Option<DateTime<Utc>>,
     /// Session counts grouped by tool name.
     pub sessions_by_tool: Vec<(String, i32)>,
+    /// Token usage grouped by model, for sessions that report usage.
+    pub usage_by_model: Vec<ModelUsage>,
+}
+
+/// Session activity in one working directory, from
+/// [`Database::list_repository_activity`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryActivity {
+    /// Working directory path.
+    pub path: String,
+    /// Repository name, or the last path component when none is recorded.
+    pub name: String,
+    /// Remote URL if recorded.
+    pub remote_url: Option<String>,
+    /// Number of sessions in the directory.
+    pub session_count: i32,
+    /// Start time of the most recent session.
+    pub last_session_at: DateTime<Utc>,
+}
+
+/// Returns the display name of a repository: the last component of its path.
+pub fn repository_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Token usage totals for the sessions of one model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelUsage {
+    /// Model name, or `None` for sessions without a recorded model.
+    pub model: Option<String>,
+    /// Number of sessions that report usage.
+    pub sessions: i32,
+    /// Total input tokens.
+    pub input_tokens: i64,
+    /// Total output tokens.
+    pub output_tokens: i64,
+}
+
+/// Tables whose rows belong to a session through a `session_id` column.
+pub const SESSION_CHILD_TABLES: [&str; 8] = [
+    "messages",
+    "session_links",
+    "annotations",
+    "tags",
+    "summaries",
+    "session_files",
+    "session_directories",
+    "link_decisions",
+];
+
+/// One auto-link decision recorded for the link audit.
+///
+/// Each score is the weighted contribution of one factor, as computed by
+/// [`crate::git::score_link`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkDecision {
+    pub session_id: Uuid,
+    pub commit_sha: String,
+    pub branch_score: f64,
+    pub file_score: f64,
+    pub time_score: f64,
+    pub recency_score: f64,
+    pub confidence: f64,
+    /// The threshold the confidence was compared against.
+    pub threshold: f64,
+    /// Whether the confidence met the threshold.
+    pub passed: bool,
+    pub decided_at: DateTime<Utc>,
+}
+
+/// Result of [`Database::merge_sessions`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionMergeReport {
+    /// Messages reassigned from the other sessions to the primary.
+    pub messages_moved: usize,
+    /// Links reassigned to the primary, not counting dropped duplicates.
+    pub links_moved: usize,
+    /// Sessions deleted after their records were moved.
+    pub sessions_deleted: usize,
+}
+
+/// Result of [`Database::verify`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Problems reported by `PRAGMA integrity_check`, empty when it passed.
+    pub integrity_errors: Vec<String>,
+    /// Tables with rows whose session does not exist, with the row counts.
+    pub orphans: Vec<(&'static str, usize)>,
+    /// Whether the search index is missing entries and needs rebuilding.
+    pub search_index_stale: bool,
+}
+
+impl VerifyReport {
+    /// Returns true if no problem was found.
+    pub fn is_ok(&self) -> bool {
+        self.integrity_errors.is_empty() && self.orphans.is_empty() && !self.search_index_stale
+    }
+}
+
+/// A message row as stored, from [`Database::get_stored_messages`].
+///
+/// Fields hold the column values unparsed, so they show exactly what was
+/// persisted.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct StoredMessage {
+    /// Message ID.
+    pub id: String,
+    /// ID of the message this one replies to.
+    pub parent_id: Option<String>,
+    /// Position within the session.
+    pub index: i32,
+    /// Timestamp text.
+    pub timestamp: String,
+    /// Role text.
+    pub role: String,
+    /// Serialized `MessageContent` JSON.
+    pub content: String,
+    /// Whether the content is stored compressed. `content` holds the
+    /// decompressed JSON either way.
+    pub compressed: bool,
+    /// Model that generated the message.
+    pub model: Option<String>,
+    /// Git branch recorded with the message.
+    pub git_branch: Option<String>,
+    /// Working directory recorded with the message.
+    pub cwd: Option<String>,
+}
+
+/// Result of [`Database::search_regex`].
+#[derive(Debug, Clone, Default)]
+pub struct RegexSearch {
+    /// Matching messages, newest first.
+    pub results: Vec<SearchResult>,
+    /// Number of candidate messages the regex was applied to.
+    pub scanned: usize,
+    /// Whether the scan limit was reached with older candidates left
+    /// unscanned. Only set when there was no FTS prefilter.
+    pub truncated: bool,
+}
+
+/// Result of [`Database::recount_session_messages`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionRecount {
+    /// Whether the stored message count was corrected.
+    pub message_count_fixed: bool,
+    /// Whether the missing end time was filled from the last message.
+    pub ended_at_filled: bool,
+}
+
+/// Result of [`Database::repair_session_aggregates`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Number of sessions examined.
+    pub sessions_checked: usize,
+    /// Sessions whose stored message count did not match their messages.
+    pub message_counts_fixed: usize,
+    /// Sessions whose missing end time was filled from their last message.
+    pub ended_at_filled: usize,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::storage::models::{
-        LinkCreator, LinkType, MessageContent, MessageRole, SearchOptions,
+        ForgeRepo, LinkCreator, LinkFactors, LinkType, MessageContent, MessageRole, SearchOptions,
     };
     use chrono::{Duration, Utc};
     use tempfile::tempdir;
@@ -3702,6 +6039,9 @@ mod tests {
             source_path: source_path.map(|s| s.to_string()),
             message_count: 0,
             machine_id: Some("test-machine".to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         }
     }
 
@@ -3742,6 +6082,8 @@ mod tests {
             created_at: Utc::now(),
             created_by: LinkCreator::Auto,
             confidence: Some(0.95),
+            forge: None,
+            factors: None,
         }
     }
 
@@ -3845,47 +6187,139 @@ mod tests {
             .list_ended_sessions(100, None)
             .expect("Failed to list ended sessions");
 
-        assert_eq!(sessions.len(), 1);
-        assert_eq!(sessions[0].id, ended.id);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, ended.id);
+    }
+
+    #[test]
+    fn test_list_sessions_with_working_dir_filter() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+
+        let session1 = create_test_session(
+            "claude-code",
+            "/home/user/project-a",
+            now - Duration::hours(1),
+            None,
+        );
+        let session2 = create_test_session("claude-code", "/home/user/project-b", now, None);
+        let session3 = create_test_session("claude-code", "/other/path", now, None);
+
+        db.insert_session(&session1)
+            .expect("Failed to insert session1");
+        db.insert_session(&session2)
+            .expect("Failed to insert session2");
+        db.insert_session(&session3)
+            .expect("Failed to insert session3");
+
+        // Filter by working directory prefix
+        let sessions = db
+            .list_sessions(10, Some("/home/user"))
+            .expect("Failed to list sessions");
+
+        assert_eq!(
+            sessions.len(),
+            2,
+            "Should have 2 sessions matching /home/user prefix"
+        );
+
+        // Verify both matching sessions are returned
+        let ids: Vec<Uuid> = sessions.iter().map(|s| s.id).collect();
+        assert!(ids.contains(&session1.id), "Should contain session1");
+        assert!(ids.contains(&session2.id), "Should contain session2");
+        assert!(!ids.contains(&session3.id), "Should not contain session3");
+    }
+
+    /// Inserts sessions covering each tool/model/directory combination used
+    /// by the filtered listing tests.
+    fn insert_filter_test_sessions(db: &Database) -> Vec<Session> {
+        let now = Utc::now();
+        let specs = [
+            ("claude-code", "claude-opus-4", "/home/user/api"),
+            ("claude-code", "claude-sonnet-4", "/home/user/web"),
+            ("aider", "gpt-4o", "/home/user/api"),
+            ("aider", "claude-sonnet-4", "/other/api"),
+        ];
+        specs
+            .iter()
+            .enumerate()
+            .map(|(i, (tool, model, wd))| {
+                let mut session =
+                    create_test_session(tool, wd, now - Duration::minutes(i as i64), None);
+                session.model = Some(model.to_string());
+                db.insert_session(&session).expect("insert session");
+                session
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_list_sessions_filtered_by_tool() {
+        let (db, _dir) = create_test_db();
+        let sessions = insert_filter_test_sessions(&db);
+
+        let found = db
+            .list_sessions_filtered(10, None, Some("aider"), None)
+            .unwrap();
+        let ids: Vec<Uuid> = found.iter().map(|s| s.id).collect();
+
+        assert_eq!(ids, vec![sessions[2].id, sessions[3].id]);
+    }
+
+    #[test]
+    fn test_list_sessions_filtered_by_model() {
+        let (db, _dir) = create_test_db();
+        let sessions = insert_filter_test_sessions(&db);
+
+        // Substring match, case-insensitive
+        let found = db
+            .list_sessions_filtered(10, None, None, Some("SONNET"))
+            .unwrap();
+        let ids: Vec<Uuid> = found.iter().map(|s| s.id).collect();
+
+        assert_eq!(ids, vec![sessions[1].id, sessions[3].id]);
+    }
+
+    #[test]
+    fn test_list_sessions_filtered_combined() {
+        let (db, _dir) = create_test_db();
+        let sessions = insert_filter_test_sessions(&db);
+
+        let found = db
+            .list_sessions_filtered(10, None, Some("claude-code"), Some("sonnet"))
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, sessions[1].id);
+
+        // Repo prefix and limit apply together with the other filters
+        let found = db
+            .list_sessions_filtered(10, Some("/home/user"), None, Some("claude"))
+            .unwrap();
+        let ids: Vec<Uuid> = found.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![sessions[0].id, sessions[1].id]);
+
+        let found = db
+            .list_sessions_filtered(1, Some("/home/user"), None, Some("claude"))
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, sessions[0].id);
+
+        assert!(db
+            .list_sessions_filtered(10, None, Some("aider"), Some("opus"))
+            .unwrap()
+            .is_empty());
     }
 
     #[test]
-    fn test_list_sessions_with_working_dir_filter() {
+    fn test_session_exists_by_key() {
         let (db, _dir) = create_test_db();
-        let now = Utc::now();
-
-        let session1 = create_test_session(
-            "claude-code",
-            "/home/user/project-a",
-            now - Duration::hours(1),
-            None,
-        );
-        let session2 = create_test_session("claude-code", "/home/user/project-b", now, None);
-        let session3 = create_test_session("claude-code", "/other/path", now, None);
-
-        db.insert_session(&session1)
-            .expect("Failed to insert session1");
-        db.insert_session(&session2)
-            .expect("Failed to insert session2");
-        db.insert_session(&session3)
-            .expect("Failed to insert session3");
-
-        // Filter by working directory prefix
-        let sessions = db
-            .list_sessions(10, Some("/home/user"))
-            .expect("Failed to list sessions");
-
-        assert_eq!(
-            sessions.len(),
-            2,
-            "Should have 2 sessions matching /home/user prefix"
-        );
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
 
-        // Verify both matching sessions are returned
-        let ids: Vec<Uuid> = sessions.iter().map(|s| s.id).collect();
-        assert!(ids.contains(&session1.id), "Should contain session1");
-        assert!(ids.contains(&session2.id), "Should contain session2");
-        assert!(!ids.contains(&session3.id), "Should not contain session3");
+        assert!(!db.session_exists_by_key("abc123").unwrap());
+        db.set_session_dedup_key(&session.id, "abc123").unwrap();
+        assert!(db.session_exists_by_key("abc123").unwrap());
+        assert!(!db.session_exists_by_key("def456").unwrap());
     }
 
     #[test]
@@ -3920,6 +6354,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_source_unchanged_compares_stamp() {
+        let (db, _dir) = create_test_db();
+        let source_path = "/path/to/session.jsonl";
+        let stamp = SourceStamp {
+            mtime_ns: 1_700_000_000_000_000_000,
+            size: 512,
+        };
+        assert!(!db.source_unchanged(source_path, &stamp).unwrap());
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), Some(source_path));
+        db.insert_session(&session).unwrap();
+        // Sessions imported before stamps were recorded count as unchanged
+        assert!(db.source_unchanged(source_path, &stamp).unwrap());
+
+        db.backfill_source_stamp(source_path, &stamp).unwrap();
+        assert!(db.source_unchanged(source_path, &stamp).unwrap());
+
+        let grown = SourceStamp { size: 640, ..stamp };
+        assert!(!db.source_unchanged(source_path, &grown).unwrap());
+
+        // A recorded stamp is not replaced by a backfill
+        db.backfill_source_stamp(source_path, &grown).unwrap();
+        assert!(db.source_unchanged(source_path, &stamp).unwrap());
+
+        db.set_source_stamp(source_path, &grown).unwrap();
+        assert!(db.source_unchanged(source_path, &grown).unwrap());
+    }
+
     #[test]
     fn test_get_session_by_source() {
         let (db, _dir) = create_test_db();
@@ -4093,6 +6556,87 @@ mod tests {
         );
     }
 
+    fn content_type(db: &Database, message_id: &Uuid) -> String {
+        db.conn
+            .query_row(
+                "SELECT typeof(content) FROM messages WHERE id = ?1",
+                params![message_id.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compressed_content_round_trips() {
+        let (mut db, _dir) = create_test_db();
+        db.set_compress_content(true);
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        let large_text = "a long tool result line\n".repeat(100);
+        let small = create_test_message(session.id, 0, MessageRole::User, "Hello");
+        let large = create_test_message(session.id, 1, MessageRole::Assistant, &large_text);
+        db.insert_message(&small).unwrap();
+        db.insert_message(&large).unwrap();
+
+        assert_eq!(content_type(&db, &small.id), "text");
+        assert_eq!(content_type(&db, &large.id), "blob");
+
+        let messages = db.get_messages(&session.id).unwrap();
+        assert_eq!(messages[0].content.text(), "Hello");
+        assert_eq!(messages[1].content.text(), large_text);
+        assert_eq!(db.rebuild_search_index().unwrap(), 2);
+        assert_eq!(db.rebuild_file_index().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_get_stored_messages_returns_content_as_written() {
+        let (mut db, _dir) = create_test_db();
+        db.set_compress_content(true);
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        let large_text = "a long tool result line\n".repeat(100);
+        let small = create_test_message(session.id, 0, MessageRole::User, "Hello");
+        let large = create_test_message(session.id, 1, MessageRole::Assistant, &large_text);
+        db.insert_message(&small).unwrap();
+        db.insert_message(&large).unwrap();
+
+        let stored = db.get_stored_messages(&session.id).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].id, small.id.to_string());
+        assert_eq!(stored[0].role, "user");
+        assert_eq!(stored[0].content, r#""Hello""#);
+        assert!(!stored[0].compressed);
+        assert_eq!(
+            stored[1].content,
+            serde_json::to_string(&large.content).unwrap()
+        );
+        assert!(stored[1].compressed);
+    }
+
+    #[test]
+    fn test_compress_message_content_backfills_plain_rows() {
+        let (mut db, _dir) = create_test_db();
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        let large_text = "x".repeat(COMPRESS_CONTENT_THRESHOLD);
+        let small = create_test_message(session.id, 0, MessageRole::User, "Hello");
+        let large = create_test_message(session.id, 1, MessageRole::Assistant, &large_text);
+        db.insert_message(&small).unwrap();
+        db.insert_message(&large).unwrap();
+        assert_eq!(content_type(&db, &large.id), "text");
+
+        assert_eq!(db.compress_message_content().unwrap(), 1);
+        assert_eq!(content_type(&db, &small.id), "text");
+        assert_eq!(content_type(&db, &large.id), "blob");
+        assert_eq!(db.compress_message_content().unwrap(), 0);
+
+        let messages = db.get_messages(&session.id).unwrap();
+        assert_eq!(messages[1].content.text(), large_text);
+    }
+
     #[test]
     fn test_messages_ordered_by_index() {
         let (db, _dir) = create_test_db();
@@ -4321,6 +6865,101 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_messages_paged_order_and_bounds() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session)
+            .expect("Failed to insert session");
+
+        // Insert out of order to check the page follows idx, not rowid
+        for idx in [3, 0, 4, 1, 2] {
+            let msg = create_test_message(session.id, idx, MessageRole::User, "hi");
+            db.insert_message(&msg).expect("Failed to insert message");
+        }
+        let indexes = |offset, limit| -> Vec<i32> {
+            db.get_messages_paged(&session.id, offset, limit)
+                .expect("Failed to get page")
+                .iter()
+                .map(|m| m.index)
+                .collect()
+        };
+
+        assert_eq!(db.session_message_count(&session.id).unwrap(), 5);
+        assert_eq!(indexes(0, 2), vec![0, 1]);
+        assert_eq!(indexes(2, 2), vec![2, 3]);
+        assert_eq!(indexes(3, 10), vec![3, 4]);
+        assert!(indexes(5, 2).is_empty());
+        assert!(indexes(0, 0).is_empty());
+        assert_eq!(db.session_message_count(&Uuid::new_v4()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_link_forge_round_trips() {
+        let (db, _dir) = create_test_db();
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session)
+            .expect("Failed to insert session");
+
+        let forge = ForgeRepo {
+            host: "github.com".to_string(),
+            owner: "varalys".to_string(),
+            repo: "lore".to_string(),
+        };
+        let mut with_forge = create_test_link(session.id, Some("abc123"), LinkType::Commit);
+        with_forge.forge = Some(forge.clone());
+        let without_forge = create_test_link(session.id, Some("def456"), LinkType::Commit);
+        db.insert_link(&with_forge).expect("Failed to insert link");
+        db.insert_link(&without_forge)
+            .expect("Failed to insert link");
+
+        let by_commit = db
+            .get_links_by_commit("abc123")
+            .expect("Failed to get links by commit");
+        assert_eq!(by_commit[0].forge, Some(forge.clone()));
+
+        let by_session = db
+            .get_links_by_session(&session.id)
+            .expect("Failed to get links by session");
+        let forges: Vec<_> = by_session.iter().map(|l| l.forge.clone()).collect();
+        assert!(forges.contains(&Some(forge)));
+        assert!(forges.contains(&None));
+    }
+
+    #[test]
+    fn test_link_factors_round_trip() {
+        let (db, _dir) = create_test_db();
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session)
+            .expect("Failed to insert session");
+
+        let factors = LinkFactors {
+            branch: 0.2,
+            files: 0.4,
+            time: 0.25,
+            recency: 0.1,
+        };
+        let mut scored = create_test_link(session.id, Some("abc123"), LinkType::Commit);
+        scored.factors = Some(factors);
+        let mut manual = create_test_link(session.id, Some("def456"), LinkType::Commit);
+        manual.created_by = LinkCreator::User;
+        manual.confidence = None;
+        db.insert_link(&scored).expect("Failed to insert link");
+        db.insert_link(&manual).expect("Failed to insert link");
+
+        let by_commit = db
+            .get_links_by_commit("abc123")
+            .expect("Failed to get links by commit");
+        assert_eq!(by_commit[0].factors, Some(factors));
+
+        let by_commit = db
+            .get_links_by_commit("def456")
+            .expect("Failed to get links by commit");
+        assert_eq!(by_commit[0].factors, None);
+    }
+
     #[test]
     fn test_link_count() {
         let (db, _dir) = create_test_db();
@@ -4407,7 +7046,7 @@ mod tests {
 
         // Search for "error"
         let results = db
-            .search_messages("error", 10, None, None, None)
+            .search_messages("error", 10, None, None, None, None)
             .expect("Failed to search");
 
         assert_eq!(
@@ -4430,7 +7069,7 @@ mod tests {
 
         // Search for something not in the messages
         let results = db
-            .search_messages("nonexistent_term_xyz", 10, None, None, None)
+            .search_messages("nonexistent_term_xyz", 10, None, None, None, None)
             .expect("Failed to search");
 
         assert!(results.is_empty(), "Should find no results");
@@ -4464,7 +7103,7 @@ mod tests {
 
         // Search with user role filter
         let user_results = db
-            .search_messages("programming", 10, None, None, Some("user"))
+            .search_messages("programming", 10, None, None, None, Some("user"))
             .expect("Failed to search");
 
         assert_eq!(user_results.len(), 1, "Should find 1 user message");
@@ -4476,7 +7115,7 @@ mod tests {
 
         // Search with assistant role filter
         let assistant_results = db
-            .search_messages("programming", 10, None, None, Some("assistant"))
+            .search_messages("programming", 10, None, None, None, Some("assistant"))
             .expect("Failed to search");
 
         assert_eq!(
@@ -4491,6 +7130,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tool_role_round_trips_and_filters() {
+        let (db, _dir) = create_test_db();
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session)
+            .expect("Failed to insert session");
+
+        let prompt = create_test_message(session.id, 0, MessageRole::User, "run cargo build");
+        let output = create_test_message(
+            session.id,
+            1,
+            MessageRole::Tool,
+            "cargo build finished with 0 errors",
+        );
+        db.insert_message(&prompt).expect("Failed to insert prompt");
+        db.insert_message(&output).expect("Failed to insert output");
+
+        let messages = db
+            .get_messages(&session.id)
+            .expect("Failed to get messages");
+        assert_eq!(messages[1].role, MessageRole::Tool);
+
+        let tool_results = db
+            .search_messages("cargo", 10, None, None, None, Some("tool"))
+            .expect("Failed to search");
+        assert_eq!(tool_results.len(), 1);
+        assert_eq!(tool_results[0].role, MessageRole::Tool);
+
+        let user_results = db
+            .search_messages("cargo", 10, None, None, None, Some("user"))
+            .expect("Failed to search");
+        assert_eq!(user_results.len(), 1);
+        assert_eq!(user_results[0].role, MessageRole::User);
+    }
+
     #[test]
     fn test_search_messages_with_repo_filter() {
         let (db, _dir) = create_test_db();
@@ -4509,7 +7184,7 @@ mod tests {
 
         // Search with repo filter
         let results = db
-            .search_messages("Hello", 10, Some("/home/user/project-a"), None, None)
+            .search_messages("Hello", 10, Some("/home/user/project-a"), None, None, None)
             .expect("Failed to search");
 
         assert_eq!(results.len(), 1, "Should find 1 message in project-a");
@@ -4539,12 +7214,145 @@ mod tests {
 
         // Search with limit of 3
         let results = db
-            .search_messages("test", 3, None, None, None)
+            .search_messages("test", 3, None, None, None, None)
             .expect("Failed to search");
 
         assert_eq!(results.len(), 3, "Should respect limit of 3");
     }
 
+    #[test]
+    fn test_search_count_ignores_limit_and_applies_filters() {
+        use super::super::models::SearchOptions;
+
+        let (db, _dir) = create_test_db();
+        let old = Utc::now() - Duration::days(30);
+        let project_a = create_test_session("claude-code", "/home/user/project-a", old, None);
+        let project_b =
+            create_test_session("claude-code", "/home/user/project-b", Utc::now(), None);
+        db.insert_session(&project_a).expect("insert a");
+        db.insert_session(&project_b).expect("insert b");
+
+        for i in 0..4 {
+            let role = if i % 2 == 0 {
+                MessageRole::User
+            } else {
+                MessageRole::Assistant
+            };
+            for session in [&project_a, &project_b] {
+                let msg =
+                    create_test_message(session.id, i, role.clone(), &format!("widget note {i}"));
+                db.insert_message(&msg).expect("insert message");
+            }
+        }
+
+        let options = SearchOptions {
+            query: "widget".to_string(),
+            limit: 3,
+            ..Default::default()
+        };
+        assert_eq!(db.search_with_options(&options).unwrap().len(), 3);
+        assert_eq!(db.search_count(&options).unwrap(), 8);
+
+        let filtered = [
+            SearchOptions {
+                repo: Some("/home/user/project-a".to_string()),
+                ..options.clone()
+            },
+            SearchOptions {
+                since: Some(Utc::now() - Duration::days(1)),
+                ..options.clone()
+            },
+            SearchOptions {
+                role: Some("user".to_string()),
+                ..options.clone()
+            },
+        ];
+        for options in filtered {
+            let unlimited = SearchOptions {
+                limit: 100,
+                ..options.clone()
+            };
+            assert_eq!(db.search_count(&options).unwrap(), 4);
+            assert_eq!(
+                db.search_count(&options).unwrap(),
+                db.search_with_options(&unlimited).unwrap().len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_search_regex_matches_patterns_fts_cannot_express() {
+        use super::super::models::SearchOptions;
+
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/home/user/project", Utc::now(), None);
+        db.insert_session(&session).expect("insert session");
+        let texts = [
+            "Bumped the version to v1.2.3 for the release",
+            "Which version should v2 use?",
+            "Tagged release v2.0.10 today",
+            "No version here",
+        ];
+        let start = Utc::now() - Duration::minutes(10);
+        for (i, text) in texts.iter().enumerate() {
+            let mut msg = create_test_message(session.id, i as i32, MessageRole::User, text);
+            msg.timestamp = start + Duration::minutes(i as i64);
+            db.insert_message(&msg).expect("insert message");
+        }
+
+        let regex = regex::Regex::new(r"v\d+\.\d+\.\d+").unwrap();
+        let options = SearchOptions {
+            limit: 10,
+            ..Default::default()
+        };
+        let search = db.search_regex(&options, &regex, 100).unwrap();
+        let snippets: Vec<&str> = search.results.iter().map(|r| r.snippet.as_str()).collect();
+        assert_eq!(
+            snippets,
+            vec![
+                "Tagged release **v2.0.10** today",
+                "Bumped the version to **v1.2.3** for the release",
+            ]
+        );
+        assert_eq!(search.scanned, 4);
+        assert!(!search.truncated);
+
+        // The prefilter narrows the candidates through the FTS index
+        let search = db
+            .search_regex(
+                &SearchOptions {
+                    query: "tagged".to_string(),
+                    ..options.clone()
+                },
+                &regex,
+                100,
+            )
+            .unwrap();
+        assert_eq!(search.results.len(), 1);
+        assert_eq!(search.scanned, 1);
+
+        // Without a prefilter only the most recent messages are scanned
+        let search = db.search_regex(&options, &regex, 2).unwrap();
+        assert_eq!(search.scanned, 2);
+        assert!(search.truncated);
+        assert_eq!(search.results.len(), 1);
+    }
+
+    #[test]
+    fn test_regex_snippet_trims_long_text() {
+        let text = format!("{}needle{}", "a".repeat(100), "b".repeat(100));
+        let snippet = regex_snippet(&text, 100, 106);
+        assert_eq!(
+            snippet,
+            format!(
+                "...{}**needle**{}...",
+                "a".repeat(REGEX_SNIPPET_CONTEXT),
+                "b".repeat(REGEX_SNIPPET_CONTEXT)
+            )
+        );
+        assert_eq!(regex_snippet("a needle", 2, 8), "a **needle**");
+    }
+
     #[test]
     fn test_search_index_needs_rebuild_empty_db() {
         let (db, _dir) = create_test_db();
@@ -4579,27 +7387,164 @@ mod tests {
             .execute("DELETE FROM messages_fts", [])
             .expect("clear fts");
 
-        // Index should now need rebuilding
-        assert!(
-            db.search_index_needs_rebuild().expect("check rebuild"),
-            "Should need rebuild after clearing FTS"
-        );
+        // Index should now need rebuilding
+        assert!(
+            db.search_index_needs_rebuild().expect("check rebuild"),
+            "Should need rebuild after clearing FTS"
+        );
+
+        // Rebuild
+        let count = db.rebuild_search_index().expect("rebuild");
+        assert_eq!(count, 2, "Should have indexed 2 messages");
+
+        // Index should no longer need rebuilding
+        assert!(
+            !db.search_index_needs_rebuild().expect("check rebuild"),
+            "Should not need rebuild after rebuilding"
+        );
+
+        // Search should work
+        let results = db
+            .search_messages("test", 10, None, None, None, None)
+            .expect("search");
+        assert_eq!(results.len(), 2, "Should find 2 results after rebuild");
+    }
+
+    #[test]
+    fn test_rebuild_search_index_switches_tokenizer() {
+        let (mut db, _dir) = create_test_db();
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).expect("insert session");
+        let msg = create_test_message(
+            session.id,
+            0,
+            MessageRole::User,
+            "Call parse_session_metadata and check the running total",
+        );
+        db.insert_message(&msg).expect("insert msg");
+
+        // The default porter tokenizer stems whole words but cannot match
+        // inside an identifier
+        let search = |db: &Database, query: &str| {
+            db.search_messages(query, 10, None, None, None, None)
+                .expect("search")
+                .len()
+        };
+        assert_eq!(search(&db, "run"), 1);
+        assert_eq!(search(&db, "session_meta"), 0);
+
+        db.set_search_tokenizer(SearchTokenizer::Trigram);
+        assert_eq!(db.rebuild_search_index().expect("rebuild"), 1);
+        assert_eq!(search(&db, "session_meta"), 1);
+        assert_eq!(search(&db, "run"), 1);
+
+        // Reopening with another tokenizer keeps the existing index
+        let path = db.db_path().unwrap();
+        drop(db);
+        let db = Database::open_with_tokenizer(&path, SearchTokenizer::Unicode61).unwrap();
+        assert_eq!(search(&db, "session_meta"), 1);
+    }
+
+    #[test]
+    fn test_search_tokenizer_names_round_trip() {
+        for name in SearchTokenizer::NAMES {
+            let tokenizer: SearchTokenizer = name.parse().unwrap();
+            assert_eq!(tokenizer.to_string(), *name);
+        }
+        assert_eq!("Trigram".parse(), Ok(SearchTokenizer::Trigram));
+        assert!("icu".parse::<SearchTokenizer>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_db_path_override_creates_parent() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("profiles").join("work.db");
+
+        let resolved = resolve_db_path(Some(path.clone())).unwrap();
+
+        assert_eq!(resolved, path);
+        assert!(dir.path().join("profiles").is_dir());
+
+        let db = Database::open(&resolved).unwrap();
+        assert_eq!(
+            db.db_path().unwrap().canonicalize().unwrap(),
+            path.canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_db_path_makes_relative_override_absolute() {
+        let resolved = resolve_db_path(Some(PathBuf::from("target/test-relative.db"))).unwrap();
+        assert!(resolved.is_absolute());
+        assert!(resolved.ends_with("target/test-relative.db"));
+    }
+
+    #[test]
+    fn test_open_leaves_cleared_index_to_explicit_rebuild() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("test.db");
+        {
+            let db = Database::open(&path).expect("open");
+            let session = create_test_session("claude-code", "/project", Utc::now(), None);
+            db.insert_session(&session).expect("insert session");
+            db.insert_message(&create_test_message(
+                session.id,
+                0,
+                MessageRole::User,
+                "find the parser bug",
+            ))
+            .expect("insert message");
+            db.conn
+                .execute("DELETE FROM messages_fts", [])
+                .expect("clear fts");
+            db.conn
+                .execute("DELETE FROM sessions_fts", [])
+                .expect("clear fts");
+        }
+
+        // Reopening does not touch the index; callers check and rebuild it
+        let db = Database::open(&path).expect("reopen");
+        assert!(db.search_index_needs_rebuild().expect("check rebuild"));
+        db.rebuild_search_index().expect("rebuild");
+        let results = db
+            .search_messages("parser", 10, None, None, None, None)
+            .expect("search");
+        assert_eq!(results.len(), 1);
+        assert!(!db.search_index_needs_rebuild().expect("check rebuild"));
+    }
 
-        // Rebuild
-        let count = db.rebuild_search_index().expect("rebuild");
-        assert_eq!(count, 2, "Should have indexed 2 messages");
+    #[test]
+    fn test_search_recovers_from_corrupt_index() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).expect("insert session");
+        db.insert_message(&create_test_message(
+            session.id,
+            0,
+            MessageRole::User,
+            "refactor the tokenizer",
+        ))
+        .expect("insert message");
 
-        // Index should no longer need rebuilding
+        // Wipe the FTS5 segment data behind the index's back.
+        db.conn
+            .execute("DELETE FROM messages_fts_data WHERE id > 10", [])
+            .expect("corrupt fts");
+        let raw = db.search_with_options_once(&crate::storage::models::SearchOptions {
+            query: "tokenizer".to_string(),
+            limit: 10,
+            ..Default::default()
+        });
         assert!(
-            !db.search_index_needs_rebuild().expect("check rebuild"),
-            "Should not need rebuild after rebuilding"
+            raw.as_ref().is_err_and(is_fts_corruption),
+            "expected a corruption error, got {raw:?}"
         );
 
-        // Search should work
         let results = db
-            .search_messages("test", 10, None, None, None)
-            .expect("search");
-        assert_eq!(results.len(), 2, "Should find 2 results after rebuild");
+            .search_messages("tokenizer", 10, None, None, None, None)
+            .expect("search should rebuild and retry");
+        assert_eq!(results.len(), 1);
     }
 
     #[test]
@@ -4638,7 +7583,7 @@ mod tests {
 
         // Search should find text from blocks
         let results = db
-            .search_messages("database", 10, None, None, None)
+            .search_messages("database", 10, None, None, None, None)
             .expect("search");
 
         assert_eq!(results.len(), 1, "Should find message with block content");
@@ -4655,7 +7600,7 @@ mod tests {
         db.insert_message(&msg).expect("insert message");
 
         let results = db
-            .search_messages("Search", 10, None, None, None)
+            .search_messages("Search", 10, None, None, None, None)
             .expect("search");
 
         assert_eq!(results.len(), 1, "Should find 1 result");
@@ -5584,6 +8529,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_search_with_since_and_until_excludes_outside_range() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+
+        for (days_ago, dir) in [(30, "/old"), (10, "/middle"), (1, "/new")] {
+            let session =
+                create_test_session("claude-code", dir, now - Duration::days(days_ago), None);
+            db.insert_session(&session).expect("insert session");
+            let msg = create_test_message(session.id, 0, MessageRole::User, "range message");
+            db.insert_message(&msg).expect("insert message");
+        }
+
+        let since = Some(now - Duration::days(14));
+        let until = Some(now - Duration::days(5));
+
+        let options = SearchOptions {
+            query: "range".to_string(),
+            limit: 10,
+            since,
+            until,
+            ..Default::default()
+        };
+        let results = db.search_with_options(&options).expect("search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].working_directory, "/middle");
+
+        let results = db
+            .search_messages("range", 10, None, since, until, None)
+            .expect("search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].working_directory, "/middle");
+
+        // An upper bound alone drops only the newer sessions
+        let results = db
+            .search_messages("range", 10, None, None, until, None)
+            .expect("search");
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.working_directory != "/new"));
+    }
+
     #[test]
     fn test_search_with_project_filter() {
         let (db, _dir) = create_test_db();
@@ -5634,6 +8620,9 @@ mod tests {
             source_path: None,
             message_count: 0,
             machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
         let session2 = Session {
             id: Uuid::new_v4(),
@@ -5647,6 +8636,9 @@ mod tests {
             source_path: None,
             message_count: 0,
             machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
 
         db.insert_session(&session1).expect("insert session1");
@@ -5719,6 +8711,9 @@ mod tests {
             source_path: None,
             message_count: 5,
             machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
         db.insert_session(&session).expect("insert session");
 
@@ -5780,6 +8775,114 @@ mod tests {
         assert_eq!(after[0].index, 3, "After message should be index 3");
     }
 
+    #[test]
+    fn test_get_message_tree_forked_thread() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+
+        // 0 -> 1 -> 2, then the prompt is edited: 1 -> 3 -> 4
+        let mut messages: Vec<Message> = (0..5)
+            .map(|i| {
+                let role = if i % 2 == 0 {
+                    MessageRole::User
+                } else {
+                    MessageRole::Assistant
+                };
+                create_test_message(session.id, i, role, &format!("message {i}"))
+            })
+            .collect();
+        messages[1].parent_id = Some(messages[0].id);
+        messages[2].parent_id = Some(messages[1].id);
+        messages[3].parent_id = Some(messages[1].id);
+        messages[4].parent_id = Some(messages[3].id);
+        for message in &messages {
+            db.insert_message(message).unwrap();
+        }
+
+        let tree = db.get_message_tree(&session.id).unwrap().unwrap();
+        let indices = |thread: &MessageThread| -> Vec<i32> {
+            thread.messages.iter().map(|m| m.index).collect()
+        };
+
+        assert!(tree.has_branches());
+        assert_eq!(indices(&tree), vec![0, 1]);
+        assert_eq!(tree.branches.len(), 2);
+        assert_eq!(indices(&tree.branches[0]), vec![2]);
+        assert_eq!(indices(&tree.branches[1]), vec![3, 4]);
+        assert!(tree.branches.iter().all(|b| b.branches.is_empty()));
+    }
+
+    #[test]
+    fn test_get_message_tree_linear_without_parents() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("aider", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        for i in 0..3 {
+            let message = create_test_message(session.id, i, MessageRole::User, "hi");
+            db.insert_message(&message).unwrap();
+        }
+
+        let tree = db.get_message_tree(&session.id).unwrap().unwrap();
+        assert!(!tree.has_branches());
+        assert_eq!(tree.messages.len(), 3);
+
+        let empty = create_test_session("aider", "/empty", Utc::now(), None);
+        db.insert_session(&empty).unwrap();
+        assert!(db.get_message_tree(&empty.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_find_message_by_id_prefix() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        let message = create_test_message(session.id, 0, MessageRole::User, "hello");
+        db.insert_message(&message).unwrap();
+
+        let by_id = db.get_message(&message.id).unwrap().unwrap();
+        assert_eq!(by_id.session_id, session.id);
+
+        let by_short = db
+            .find_message_by_id_prefix(&message.short_id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_short.id, message.id);
+
+        let by_full = db
+            .find_message_by_id_prefix(&message.id.to_string())
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_full.id, message.id);
+
+        assert!(db.find_message_by_id_prefix("zzzzzzzz").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_find_message_by_id_prefix_ambiguous() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        for (idx, id) in [
+            "abcd0000-0000-0000-0000-000000000001",
+            "abcd0000-0000-0000-0000-000000000002",
+        ]
+        .iter()
+        .enumerate()
+        {
+            let mut message = create_test_message(session.id, idx as i32, MessageRole::User, "hi");
+            message.id = Uuid::parse_str(id).unwrap();
+            db.insert_message(&message).unwrap();
+        }
+
+        let err = db.find_message_by_id_prefix("abcd").unwrap_err();
+        assert!(err.to_string().contains("Ambiguous message ID prefix"));
+        assert!(db
+            .find_message_by_id_prefix("abcd0000-0000-0000-0000-000000000002")
+            .unwrap()
+            .is_some());
+    }
+
     #[test]
     fn test_get_context_messages_at_start() {
         let (db, _dir) = create_test_db();
@@ -5846,6 +8949,9 @@ mod tests {
             source_path: None,
             message_count: 1,
             machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
         let session2 = Session {
             id: Uuid::new_v4(),
@@ -5859,6 +8965,9 @@ mod tests {
             source_path: None,
             message_count: 1,
             machine_id: None,
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
 
         db.insert_session(&session1).expect("insert session1");
@@ -5957,6 +9066,122 @@ mod tests {
         assert!(db.get_session(&session2.id).expect("get").is_some());
     }
 
+    #[test]
+    fn test_get_child_sessions_returns_linked_subagents() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+        let parent = create_test_session("claude-code", "/project", now, None);
+        let mut late = create_test_session("claude-code", "/project", now, None);
+        late.started_at = now + Duration::minutes(5);
+        late.parent_session_id = Some(parent.id);
+        let mut early = create_test_session("claude-code", "/project", now, None);
+        early.started_at = now + Duration::minutes(1);
+        early.parent_session_id = Some(parent.id);
+        let unrelated = create_test_session("claude-code", "/project", now, None);
+        for session in [&parent, &late, &early, &unrelated] {
+            db.insert_session(session).unwrap();
+        }
+
+        let children: Vec<Uuid> = db
+            .get_child_sessions(&parent.id)
+            .unwrap()
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(children, vec![early.id, late.id]);
+
+        let stored = db.get_session(&late.id).unwrap().unwrap();
+        assert_eq!(stored.parent_session_id, Some(parent.id));
+        assert!(db.get_child_sessions(&unrelated.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_merge_sessions_interleaves_messages_and_keeps_links() {
+        let (mut db, _dir) = create_test_db();
+        let start = Utc::now() - Duration::hours(1);
+
+        let mut primary = create_test_session("claude-code", "/project", start, None);
+        primary.message_count = 2;
+        primary.input_tokens = Some(100);
+        let mut other = create_test_session(
+            "claude-code",
+            "/project",
+            start + Duration::minutes(5),
+            Some("/logs/part2.jsonl"),
+        );
+        other.message_count = 2;
+        other.ended_at = Some(start + Duration::minutes(30));
+        db.insert_session(&primary).unwrap();
+        db.insert_session(&other).unwrap();
+
+        let at = |minutes, session_id, index, content| {
+            let mut message = create_test_message(session_id, index, MessageRole::User, content);
+            message.timestamp = start + Duration::minutes(minutes);
+            message
+        };
+        for message in [
+            at(0, primary.id, 0, "first"),
+            at(20, primary.id, 1, "fourth"),
+            at(5, other.id, 0, "second"),
+            at(10, other.id, 1, "third"),
+        ] {
+            db.insert_message(&message).unwrap();
+        }
+
+        db.insert_link(&create_test_link(primary.id, Some("aaa"), LinkType::Commit))
+            .unwrap();
+        db.insert_link(&create_test_link(other.id, Some("aaa"), LinkType::Commit))
+            .unwrap();
+        db.insert_link(&create_test_link(other.id, Some("bbb"), LinkType::Commit))
+            .unwrap();
+
+        let report = db.merge_sessions(&primary.id, &[other.id]).unwrap();
+        assert_eq!(
+            report,
+            SessionMergeReport {
+                messages_moved: 2,
+                links_moved: 1,
+                sessions_deleted: 1,
+            }
+        );
+
+        let messages = db.get_messages(&primary.id).unwrap();
+        let order: Vec<(i32, String)> = messages
+            .iter()
+            .map(|m| (m.index, m.content.text()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (0, "first".to_string()),
+                (1, "second".to_string()),
+                (2, "third".to_string()),
+                (3, "fourth".to_string()),
+            ]
+        );
+
+        let mut shas: Vec<String> = db
+            .get_links_by_session(&primary.id)
+            .unwrap()
+            .into_iter()
+            .filter_map(|l| l.commit_sha)
+            .collect();
+        shas.sort();
+        assert_eq!(shas, vec!["aaa", "bbb"]);
+
+        let merged = db.get_session(&primary.id).unwrap().unwrap();
+        assert_eq!(merged.message_count, 4);
+        assert_eq!(merged.ended_at, other.ended_at);
+        assert_eq!(merged.input_tokens, Some(100));
+
+        assert!(db.get_session(&other.id).unwrap().is_none());
+        assert!(db.merged_away_at(&other.id).unwrap().is_some());
+        assert!(db.merged_away_at(&primary.id).unwrap().is_none());
+        assert!(db.last_merged_into_at(&primary.id).unwrap().is_some());
+        assert!(db.verify().unwrap().is_ok());
+        assert!(db.merge_sessions(&primary.id, &[primary.id]).is_err());
+    }
+
     // ==================== Database Maintenance Tests ====================
 
     #[test]
@@ -5976,6 +9201,192 @@ mod tests {
         db.vacuum().expect("vacuum should succeed");
     }
 
+    #[test]
+    fn test_recount_session_messages() {
+        let (db, _dir) = create_test_db();
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).expect("insert session");
+        let first = create_test_message(session.id, 0, MessageRole::User, "hello");
+        let mut last = create_test_message(session.id, 1, MessageRole::Assistant, "hi");
+        last.timestamp = first.timestamp + Duration::minutes(5);
+        db.insert_message(&first).expect("insert first");
+        db.insert_message(&last).expect("insert last");
+
+        let recount = db.recount_session_messages(&session.id).expect("recount");
+        assert_eq!(
+            recount,
+            SessionRecount {
+                message_count_fixed: true,
+                ended_at_filled: true,
+            }
+        );
+
+        let repaired = db.get_session(&session.id).expect("get").expect("exists");
+        assert_eq!(repaired.message_count, 2);
+        assert_eq!(
+            repaired.ended_at.map(|t| t.timestamp()),
+            Some(last.timestamp.timestamp())
+        );
+
+        // Already consistent, so nothing changes the second time
+        let recount = db.recount_session_messages(&session.id).expect("recount");
+        assert_eq!(recount, SessionRecount::default());
+    }
+
+    #[test]
+    fn test_repair_session_aggregates_is_idempotent() {
+        let (mut db, _dir) = create_test_db();
+        let now = Utc::now();
+
+        // Stored count too low, ended_at missing
+        let stale = create_test_session("claude-code", "/project1", now, None);
+        db.insert_session(&stale).expect("insert stale");
+        db.insert_message(&create_test_message(stale.id, 0, MessageRole::User, "a"))
+            .expect("insert message");
+
+        // Already consistent
+        let mut good = create_test_session("claude-code", "/project2", now, None);
+        good.message_count = 1;
+        good.ended_at = Some(now);
+        db.insert_session(&good).expect("insert good");
+        db.insert_message(&create_test_message(good.id, 0, MessageRole::User, "b"))
+            .expect("insert message");
+
+        // No messages: count stays 0 and ended_at stays empty
+        let empty = create_test_session("claude-code", "/project3", now, None);
+        db.insert_session(&empty).expect("insert empty");
+
+        let report = db.repair_session_aggregates().expect("repair");
+        assert_eq!(
+            report,
+            RepairReport {
+                sessions_checked: 3,
+                message_counts_fixed: 1,
+                ended_at_filled: 1,
+            }
+        );
+        let stale = db.get_session(&stale.id).expect("get").expect("exists");
+        assert_eq!(stale.message_count, 1);
+        assert!(stale.ended_at.is_some());
+        let empty = db.get_session(&empty.id).expect("get").expect("exists");
+        assert!(empty.ended_at.is_none());
+
+        let again = db.repair_session_aggregates().expect("repair again");
+        assert_eq!(again.message_counts_fixed, 0);
+        assert_eq!(again.ended_at_filled, 0);
+    }
+
+    /// Deletes only the session row, bypassing foreign keys, the way an
+    /// older or interrupted delete could leave its records behind.
+    fn remove_session_row(db: &Database, id: &Uuid) {
+        db.conn
+            .execute_batch(&format!(
+                "PRAGMA foreign_keys = OFF;
+                 DELETE FROM sessions WHERE id = '{id}';
+                 DELETE FROM sessions_fts WHERE session_id = '{id}';
+                 PRAGMA foreign_keys = ON;"
+            ))
+            .expect("remove session row");
+    }
+
+    #[test]
+    fn test_link_decisions_round_trip_and_follow_session_deletion() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+
+        let decision = LinkDecision {
+            session_id: session.id,
+            commit_sha: "abc123def456".to_string(),
+            branch_score: 0.2,
+            file_score: 0.1,
+            time_score: 0.15,
+            recency_score: 0.0,
+            confidence: 0.45,
+            threshold: 0.5,
+            passed: false,
+            decided_at: Utc::now(),
+        };
+        db.insert_link_decision(&decision).unwrap();
+
+        let stored = db.get_link_decisions("abc123").unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].session_id, session.id);
+        assert_eq!(stored[0].file_score, 0.1);
+        assert!(!stored[0].passed);
+        assert!(db.get_link_decisions("fff").unwrap().is_empty());
+
+        db.delete_session(&session.id).unwrap();
+        assert!(db.get_link_decisions("abc123").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_verify_detects_orphan_link() {
+        let (mut db, _dir) = create_test_db();
+
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).expect("insert session");
+        db.insert_message(&create_test_message(session.id, 0, MessageRole::User, "a"))
+            .expect("insert message");
+        db.insert_link(&create_test_link(
+            session.id,
+            Some("abc123"),
+            LinkType::Commit,
+        ))
+        .expect("insert link");
+        assert!(db.verify().expect("verify").is_ok());
+
+        // A link left behind by a session removed while foreign keys were off
+        let gone = create_test_session("claude-code", "/gone", Utc::now(), None);
+        db.insert_session(&gone).expect("insert gone");
+        db.insert_link(&create_test_link(gone.id, Some("def456"), LinkType::Commit))
+            .expect("insert orphan link");
+        remove_session_row(&db, &gone.id);
+
+        let report = db.verify().expect("verify");
+        assert!(!report.is_ok());
+        assert!(report.integrity_errors.is_empty());
+        assert!(!report.search_index_stale);
+        assert_eq!(report.orphans, vec![("session_links", 1)]);
+
+        assert_eq!(db.delete_orphans().expect("delete orphans"), 1);
+        assert!(db.verify().expect("verify again").is_ok());
+        assert_eq!(
+            db.get_links_by_session(&session.id).expect("links").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_delete_orphans_removes_messages_from_search_index() {
+        let (mut db, _dir) = create_test_db();
+
+        let gone = create_test_session("claude-code", "/gone", Utc::now(), None);
+        db.insert_session(&gone).expect("insert gone");
+        db.insert_message(&create_test_message(
+            gone.id,
+            0,
+            MessageRole::User,
+            "stranded words",
+        ))
+        .expect("insert orphan message");
+        remove_session_row(&db, &gone.id);
+        let report = db.verify().expect("verify");
+        assert_eq!(
+            report.orphans,
+            vec![("messages", 1), ("session_directories", 1)]
+        );
+
+        assert_eq!(db.delete_orphans().expect("delete orphans"), 2);
+        let indexed: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM messages_fts", [], |row| row.get(0))
+            .expect("count fts");
+        assert_eq!(indexed, 0);
+        assert!(db.verify().expect("verify again").is_ok());
+    }
+
     #[test]
     fn test_count_sessions_older_than() {
         let (db, _dir) = create_test_db();
@@ -6021,6 +9432,23 @@ mod tests {
         db.insert_message(&msg1).expect("insert msg1");
         db.insert_message(&msg2).expect("insert msg2");
 
+        // Record an auto-link decision for each
+        for (session, sha) in [(&old_session, "aaa111"), (&recent_session, "bbb222")] {
+            db.insert_link_decision(&LinkDecision {
+                session_id: session.id,
+                commit_sha: sha.to_string(),
+                branch_score: 0.0,
+                file_score: 0.0,
+                time_score: 0.0,
+                recency_score: 0.0,
+                confidence: 0.0,
+                threshold: 0.5,
+                passed: false,
+                decided_at: now,
+            })
+            .expect("insert decision");
+        }
+
         // Delete sessions older than 30 days
         let cutoff = now - Duration::days(30);
         let deleted = db.delete_sessions_older_than(cutoff).expect("delete");
@@ -6031,8 +9459,89 @@ mod tests {
         assert!(db.get_session(&recent_session.id).expect("get").is_some());
         assert!(db.get_session(&old_session.id).expect("get").is_none());
 
-        // Verify messages were also deleted
+        // Verify messages and link decisions were also deleted
         assert_eq!(db.message_count().expect("count"), 1);
+        assert!(db.get_link_decisions("aaa111").expect("get").is_empty());
+        assert_eq!(db.get_link_decisions("bbb222").expect("get").len(), 1);
+    }
+
+    #[test]
+    fn test_prune_filtered_by_tool_and_directory() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+        let old = now - Duration::days(100);
+
+        let noisy_cursor = create_test_session("cursor", "/work/experiment", old, None);
+        let noisy_claude = create_test_session("claude-code", "/work/experiment", old, None);
+        let other_cursor = create_test_session("cursor", "/work/product", old, None);
+        let recent_cursor =
+            create_test_session("cursor", "/work/experiment", now - Duration::days(1), None);
+        // A sibling sharing the prefix, and one matched only by an unescaped `_`
+        let sibling_cursor = create_test_session("cursor", "/work/experiment-2", old, None);
+        let wildcard_cursor = create_test_session("cursor", "/work/aXb", old, None);
+        for session in [
+            &noisy_cursor,
+            &noisy_claude,
+            &other_cursor,
+            &recent_cursor,
+            &sibling_cursor,
+            &wildcard_cursor,
+        ] {
+            db.insert_session(session).expect("insert session");
+        }
+        db.insert_message(&create_test_message(
+            noisy_cursor.id,
+            0,
+            MessageRole::User,
+            "noise",
+        ))
+        .expect("insert message");
+
+        let cutoff = now - Duration::days(30);
+        let filters = (Some("cursor"), Some("/work/experiment"));
+
+        assert_eq!(
+            db.count_sessions_older_than_filtered(cutoff, filters.0, filters.1)
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            db.count_sessions_older_than_filtered(cutoff, None, filters.1)
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            db.count_sessions_older_than_filtered(cutoff, filters.0, None)
+                .unwrap(),
+            4
+        );
+        let preview = db
+            .get_sessions_older_than_filtered(cutoff, filters.0, filters.1)
+            .unwrap();
+        assert_eq!(preview.len(), 1);
+        assert_eq!(preview[0].id, noisy_cursor.id);
+
+        let deleted = db
+            .delete_sessions_older_than_filtered(cutoff, filters.0, filters.1)
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(db.get_session(&noisy_cursor.id).unwrap().is_none());
+        assert!(db.get_session(&noisy_claude.id).unwrap().is_some());
+        assert!(db.get_session(&other_cursor.id).unwrap().is_some());
+        assert!(db.get_session(&recent_cursor.id).unwrap().is_some());
+        assert!(db.get_session(&sibling_cursor.id).unwrap().is_some());
+        assert_eq!(db.message_count().unwrap(), 0);
+
+        assert_eq!(
+            db.count_sessions_older_than_filtered(cutoff, filters.0, Some("/work/a_b"))
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            db.count_sessions_older_than_filtered(cutoff, filters.0, Some("/work/aXb/"))
+                .unwrap(),
+            1
+        );
     }
 
     #[test]
@@ -6100,6 +9609,7 @@ mod tests {
         assert!(empty_stats.oldest_session.is_none());
         assert!(empty_stats.newest_session.is_none());
         assert!(empty_stats.sessions_by_tool.is_empty());
+        assert!(empty_stats.usage_by_model.is_empty());
 
         // Add some data
         let session1 =
@@ -6132,6 +9642,53 @@ mod tests {
         assert_eq!(stats.sessions_by_tool[0].1, 2);
         assert_eq!(stats.sessions_by_tool[1].0, "aider");
         assert_eq!(stats.sessions_by_tool[1].1, 1);
+
+        // No session reports usage
+        assert!(stats.usage_by_model.is_empty());
+    }
+
+    #[test]
+    fn test_session_usage_round_trips_and_aggregates() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+
+        let mut with_usage = create_test_session("claude-code", "/project", now, None);
+        with_usage.model = Some("claude-opus-4".to_string());
+        with_usage.input_tokens = Some(1000);
+        with_usage.output_tokens = Some(200);
+        let mut more_usage = create_test_session("claude-code", "/project", now, None);
+        more_usage.model = Some("claude-opus-4".to_string());
+        more_usage.input_tokens = Some(500);
+        more_usage.output_tokens = Some(50);
+        let without_usage = create_test_session("aider", "/project", now, None);
+
+        db.insert_session(&with_usage).unwrap();
+        db.insert_session(&more_usage).unwrap();
+        db.insert_session(&without_usage).unwrap();
+
+        let stored = db.get_session(&with_usage.id).unwrap().unwrap();
+        assert_eq!(stored.input_tokens, Some(1000));
+        assert_eq!(stored.output_tokens, Some(200));
+        let stored = db.get_session(&without_usage.id).unwrap().unwrap();
+        assert_eq!(stored.input_tokens, None);
+        assert_eq!(stored.output_tokens, None);
+
+        // Re-import with newer totals replaces them
+        with_usage.input_tokens = Some(1500);
+        db.insert_session(&with_usage).unwrap();
+        let stored = db.get_session(&with_usage.id).unwrap().unwrap();
+        assert_eq!(stored.input_tokens, Some(1500));
+
+        let stats = db.stats().unwrap();
+        assert_eq!(
+            stats.usage_by_model,
+            vec![ModelUsage {
+                model: Some("claude-opus-4".to_string()),
+                sessions: 2,
+                input_tokens: 2000,
+                output_tokens: 250,
+            }]
+        );
     }
 
     // ==================== Branch History Tests ====================
@@ -6197,6 +9754,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sessions_for_branch_exact_match() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+        let mut on_feature =
+            create_test_session("claude-code", "/work/app", now - Duration::hours(2), None);
+        on_feature.git_branch = Some("feature/x".to_string());
+        let mut in_other_repo =
+            create_test_session("claude-code", "/work/lib", now - Duration::hours(1), None);
+        in_other_repo.git_branch = Some("feature/x".to_string());
+        let mut prefixed = create_test_session("claude-code", "/work/app", now, None);
+        prefixed.git_branch = Some("feature/x-2".to_string());
+        let on_main = create_test_session("claude-code", "/work/app", now, None);
+        for session in [&on_feature, &in_other_repo, &prefixed, &on_main] {
+            db.insert_session(session).unwrap();
+        }
+
+        let ids = |sessions: Vec<Session>| sessions.iter().map(|s| s.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(db.sessions_for_branch("feature/x", None).unwrap()),
+            vec![in_other_repo.id, on_feature.id]
+        );
+        assert_eq!(
+            ids(db
+                .sessions_for_branch("feature/x", Some("/work/app"))
+                .unwrap()),
+            vec![on_feature.id]
+        );
+        assert!(db.sessions_for_branch("feature", None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sessions_for_branch_includes_mid_session_switch() {
+        let (db, _dir) = create_test_db();
+        // The session started on main and switched to feature/x partway
+        let session = create_test_session("claude-code", "/work/app", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        for (i, branch) in ["main", "main", "feature/x"].iter().enumerate() {
+            let mut msg = create_test_message(session.id, i as i32, MessageRole::User, "test");
+            msg.git_branch = Some(branch.to_string());
+            db.insert_message(&msg).unwrap();
+        }
+
+        for branch in ["main", "feature/x"] {
+            let found = db.sessions_for_branch(branch, None).unwrap();
+            assert_eq!(found.len(), 1, "expected the session under {branch}");
+            assert_eq!(found[0].id, session.id);
+        }
+        assert!(db.sessions_for_branch("develop", None).unwrap().is_empty());
+    }
+
     #[test]
     fn test_get_session_branch_history_with_none_branches() {
         let (db, _dir) = create_test_db();
@@ -6592,6 +10200,7 @@ mod tests {
             session_id: session.id,
             content: "Test summary content".to_string(),
             generated_at: Utc::now(),
+            model: None,
         };
         db.insert_summary(&summary).expect("insert summary");
 
@@ -6602,6 +10211,45 @@ mod tests {
         assert_eq!(retrieved.session_id, session.id);
     }
 
+    #[test]
+    fn test_list_sessions_without_summary() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+        let mut sessions = Vec::new();
+        for (dir, minutes_ago) in [("/work/app", 10), ("/work/app", 5), ("/work/lib", 1)] {
+            let mut session = create_test_session(
+                "claude-code",
+                dir,
+                now - Duration::minutes(minutes_ago),
+                None,
+            );
+            session.message_count = 2;
+            db.insert_session(&session).unwrap();
+            sessions.push(session);
+        }
+        let empty = create_test_session("claude-code", "/work/app", now, None);
+        db.insert_session(&empty).unwrap();
+
+        db.insert_summary(&Summary {
+            id: Uuid::new_v4(),
+            session_id: sessions[1].id,
+            content: "Done".to_string(),
+            generated_at: now,
+            model: None,
+        })
+        .unwrap();
+
+        let ids = |found: Vec<Session>| found.iter().map(|s| s.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(db.list_sessions_without_summary(None).unwrap()),
+            vec![sessions[2].id, sessions[0].id]
+        );
+        assert_eq!(
+            ids(db.list_sessions_without_summary(Some("/work/app")).unwrap()),
+            vec![sessions[0].id]
+        );
+    }
+
     #[test]
     fn test_get_summary_nonexistent() {
         let (db, _dir) = create_test_db();
@@ -6623,18 +10271,29 @@ mod tests {
             session_id: session.id,
             content: "Original content".to_string(),
             generated_at: Utc::now(),
+            model: Some("model-a".to_string()),
         };
         db.insert_summary(&summary).expect("insert summary");
+        assert_eq!(
+            db.get_summary(&session.id)
+                .unwrap()
+                .unwrap()
+                .model
+                .as_deref(),
+            Some("model-a")
+        );
 
         // Update the summary
         let updated = db
-            .update_summary(&session.id, "Updated content")
+            .update_summary(&session.id, "Updated content", Some("model-b"))
             .expect("update summary");
         assert!(updated);
 
         let retrieved = db.get_summary(&session.id).expect("get summary");
         assert!(retrieved.is_some());
-        assert_eq!(retrieved.unwrap().content, "Updated content");
+        let retrieved = retrieved.unwrap();
+        assert_eq!(retrieved.content, "Updated content");
+        assert_eq!(retrieved.model.as_deref(), Some("model-b"));
     }
 
     #[test]
@@ -6645,7 +10304,7 @@ mod tests {
 
         // Try to update a summary that does not exist
         let updated = db
-            .update_summary(&session.id, "New content")
+            .update_summary(&session.id, "New content", None)
             .expect("update summary");
         assert!(!updated);
     }
@@ -6661,6 +10320,7 @@ mod tests {
             session_id: session.id,
             content: "To be deleted".to_string(),
             generated_at: Utc::now(),
+            model: None,
         };
         db.insert_summary(&summary).expect("insert summary");
 
@@ -6684,6 +10344,7 @@ mod tests {
             session_id: session.id,
             content: "Session summary".to_string(),
             generated_at: Utc::now(),
+            model: None,
         };
         db.insert_summary(&summary).expect("insert summary");
 
@@ -6848,6 +10509,84 @@ mod tests {
 
     // ==================== Session ID Prefix Lookup Tests ====================
 
+    #[test]
+    fn test_find_linked_commit_by_prefix_lists_candidates() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        for sha in ["abc1230000", "abc4560000", "def7890000"] {
+            db.insert_link(&create_test_link(session.id, Some(sha), LinkType::Commit))
+                .unwrap();
+        }
+
+        assert_eq!(
+            db.find_linked_commit_by_prefix("def").unwrap().as_deref(),
+            Some("def7890000")
+        );
+        assert_eq!(db.find_linked_commit_by_prefix("fff").unwrap(), None);
+
+        let error = db.find_linked_commit_by_prefix("abc").unwrap_err();
+        let ambiguous = error.downcast_ref::<AmbiguousPrefix>().unwrap();
+        let mut candidates = ambiguous.candidates.clone();
+        candidates.sort();
+        assert_eq!(candidates, vec!["abc1230000", "abc4560000"]);
+        assert_eq!(ambiguous.kind, "commit");
+    }
+
+    #[test]
+    fn test_ambiguous_prefix_message_caps_listed_candidates() {
+        let error = AmbiguousPrefix {
+            kind: "session",
+            prefix: "a".to_string(),
+            candidates: (0..7).map(|i| format!("a{i}234567-rest")).collect(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Ambiguous session prefix 'a', matches: a0234567..., a1234567..., \
+             a2234567..., a3234567..., a4234567..., and 2 more"
+        );
+    }
+
+    #[test]
+    fn test_find_session_by_id_prefix_exact_prefix_ambiguous_and_missing() {
+        let (db, _dir) = create_test_db();
+        let mut first = create_test_session("claude-code", "/project", Utc::now(), None);
+        first.id = Uuid::parse_str("abcd1111-0000-4000-8000-000000000001").unwrap();
+        let mut second = create_test_session("claude-code", "/project", Utc::now(), None);
+        second.id = Uuid::parse_str("abcd2222-0000-4000-8000-000000000002").unwrap();
+        db.insert_session(&first).unwrap();
+        db.insert_session(&second).unwrap();
+
+        let exact = db.find_session_by_id_prefix(&first.id.to_string()).unwrap();
+        assert_eq!(exact.map(|s| s.id), Some(first.id));
+
+        let prefix = db.find_session_by_id_prefix("abcd2").unwrap();
+        assert_eq!(prefix.map(|s| s.id), Some(second.id));
+
+        let error = db.find_session_by_id_prefix("abcd").unwrap_err();
+        let ambiguous = error.downcast_ref::<AmbiguousPrefix>().unwrap();
+        let mut candidates = ambiguous.candidates.clone();
+        candidates.sort();
+        assert_eq!(
+            candidates,
+            vec![first.id.to_string(), second.id.to_string()]
+        );
+        let message = error.to_string();
+        assert!(
+            message.starts_with("Ambiguous session prefix 'abcd', matches: abcd"),
+            "{message}"
+        );
+        assert!(
+            message.contains("abcd1111...") && message.contains("abcd2222..."),
+            "{message}"
+        );
+
+        assert!(db.find_session_by_id_prefix("abce").unwrap().is_none());
+        // LIKE wildcards are not treated as patterns
+        assert!(db.find_session_by_id_prefix("abcd_").unwrap().is_none());
+        assert!(db.find_session_by_id_prefix("%").unwrap().is_none());
+    }
+
     #[test]
     fn test_find_session_by_id_prefix_full_uuid() {
         let (db, _dir) = create_test_db();
@@ -7466,6 +11205,61 @@ mod tests {
         assert_eq!(annotations[0].content, "important");
     }
 
+    #[test]
+    fn test_resolve_session_conflict() {
+        let now = Utc::now();
+        let mut local = create_test_session("claude-code", "/project", now, None);
+        local.message_count = 4;
+        local.ended_at = Some(now);
+
+        // Identical rows never conflict
+        for strategy in [
+            ConflictStrategy::Skip,
+            ConflictStrategy::PreferRemote,
+            ConflictStrategy::PreferLocal,
+            ConflictStrategy::Newest,
+        ] {
+            assert_eq!(
+                resolve_session_conflict(strategy, &local, &local),
+                MergeOutcome::Unchanged
+            );
+        }
+
+        let mut more_messages = local.clone();
+        more_messages.message_count = 5;
+        let mut later_end = local.clone();
+        later_end.ended_at = Some(now + Duration::minutes(1));
+        let mut older = local.clone();
+        older.message_count = 3;
+
+        let newest = ConflictStrategy::Newest;
+        assert_eq!(
+            resolve_session_conflict(newest, &more_messages, &local),
+            MergeOutcome::Imported
+        );
+        assert_eq!(
+            resolve_session_conflict(newest, &later_end, &local),
+            MergeOutcome::Imported
+        );
+        assert_eq!(
+            resolve_session_conflict(newest, &older, &local),
+            MergeOutcome::KeptLocal
+        );
+
+        assert_eq!(
+            resolve_session_conflict(ConflictStrategy::PreferRemote, &older, &local),
+            MergeOutcome::Imported
+        );
+        assert_eq!(
+            resolve_session_conflict(ConflictStrategy::PreferLocal, &more_messages, &local),
+            MergeOutcome::KeptLocal
+        );
+        assert_eq!(
+            resolve_session_conflict(ConflictStrategy::Skip, &more_messages, &local),
+            MergeOutcome::Conflict
+        );
+    }
+
     #[test]
     fn test_merge_summary_newer_wins() {
         let (mut db, _dir) = create_test_db();
@@ -7477,6 +11271,7 @@ mod tests {
             session_id: session.id,
             content: "first".to_string(),
             generated_at: base,
+            model: None,
         };
         db.merge_remote_record(&session, &[], &[], &[], &[], Some(&summary), Utc::now())
             .unwrap();
@@ -7487,6 +11282,7 @@ mod tests {
             session_id: session.id,
             content: "second".to_string(),
             generated_at: base + Duration::seconds(1),
+            model: None,
         };
         db.merge_remote_record(&session, &[], &[], &[], &[], Some(&updated), Utc::now())
             .unwrap();
@@ -7509,6 +11305,7 @@ mod tests {
             session_id: session.id,
             content: "newer local".to_string(),
             generated_at: base,
+            model: None,
         };
         db.merge_remote_record(&session, &[], &[], &[], &[], Some(&newer), Utc::now())
             .unwrap();
@@ -7519,6 +11316,7 @@ mod tests {
             session_id: session.id,
             content: "older remote".to_string(),
             generated_at: base - Duration::seconds(60),
+            model: None,
         };
         db.merge_remote_record(&session, &[], &[], &[], &[], Some(&older), Utc::now())
             .unwrap();
@@ -7558,6 +11356,8 @@ mod tests {
             created_at: Utc::now(),
             created_by: LinkCreator::User,
             confidence: None,
+            forge: None,
+            factors: None,
         })
         .unwrap();
 
@@ -7591,6 +11391,8 @@ mod tests {
             created_at: Utc::now(),
             created_by: LinkCreator::Auto,
             confidence: None,
+            forge: None,
+            factors: None,
         };
         let imported = db
             .merge_remote_record(&session, &[], &[link], &[], &[], None, Utc::now())
@@ -7609,6 +11411,20 @@ mod tests {
         assert_eq!(links[0].commit_sha, Some("cafe".to_string()));
     }
 
+    #[test]
+    fn test_merge_remote_record_skips_pruned_session() {
+        let (mut db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/project", Utc::now(), None);
+        db.record_pruned_session(&session.id, Utc::now()).unwrap();
+
+        let imported = db
+            .merge_remote_record(&session, &[], &[], &[], &[], None, Utc::now())
+            .unwrap();
+
+        assert!(!imported);
+        assert!(db.get_session(&session.id).unwrap().is_none());
+    }
+
     #[test]
     fn test_merge_remote_record_persists_full_record() {
         // A brand-new record merges its session, messages, and every child record
@@ -7647,6 +11463,7 @@ mod tests {
             session_id: session.id,
             content: "did the thing".to_string(),
             generated_at: Utc::now(),
+            model: None,
         };
 
         let imported = db
@@ -7905,6 +11722,7 @@ mod tests {
             session_id: session.id,
             content: "summary".to_string(),
             generated_at: Utc::now(),
+            model: None,
         };
         db.insert_summary(&summary).expect("insert summary");
 
@@ -8204,4 +12022,311 @@ mod tests {
         assert_eq!(remaining.len(), 1);
         assert_eq!(remaining[0].child_id, fresh_child);
     }
+
+    // ==================== Session Files Tests ====================
+
+    /// Indexes `paths` as the files a session touched.
+    fn index_session_files(db: &Database, session_id: &Uuid, paths: &[&str]) {
+        for path in paths {
+            db.conn
+                .execute(
+                    "INSERT OR IGNORE INTO session_files (session_id, path) VALUES (?1, ?2)",
+                    params![session_id.to_string(), path],
+                )
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_get_session_files_sorted() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/home/user/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+
+        index_session_files(&db, &session.id, &["src/b.rs", "src/a.rs"]);
+        assert_eq!(
+            db.get_session_files(&session.id).unwrap(),
+            vec!["src/a.rs".to_string(), "src/b.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_sessions_touching_file_matches_relative_and_absolute() {
+        let (db, _dir) = create_test_db();
+        let older = create_test_session(
+            "claude-code",
+            "/home/user/project",
+            Utc::now() - Duration::hours(2),
+            None,
+        );
+        let newer = create_test_session("aider", "/home/user/project", Utc::now(), None);
+        let unrelated = create_test_session("claude-code", "/home/user/other", Utc::now(), None);
+        for s in [&older, &newer, &unrelated] {
+            db.insert_session(s).unwrap();
+        }
+        index_session_files(&db, &older.id, &["src/auth.rs"]);
+        index_session_files(&db, &newer.id, &["/home/user/project/src/auth.rs"]);
+        index_session_files(&db, &unrelated.id, &["src/main.rs"]);
+
+        // Relative lookup matches both the relative and the absolute stored path
+        let found = db.sessions_touching_file("src/auth.rs", None).unwrap();
+        let ids: Vec<Uuid> = found.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![newer.id, older.id], "most recent first");
+
+        // Absolute lookup matches a relative path joined with the working directory
+        let found = db
+            .sessions_touching_file("/home/user/project/src/auth.rs", None)
+            .unwrap();
+        assert_eq!(found.len(), 2);
+
+        // A partial file name does not match
+        assert!(db.sessions_touching_file("auth.rs", None).unwrap().len() == 2);
+        assert!(db.sessions_touching_file("th.rs", None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sessions_touching_file_scopes_to_working_dir() {
+        let (db, _dir) = create_test_db();
+        let in_repo = create_test_session("claude-code", "/home/user/foo", Utc::now(), None);
+        let sibling = create_test_session("claude-code", "/home/user/foobar", Utc::now(), None);
+        db.insert_session(&in_repo).unwrap();
+        db.insert_session(&sibling).unwrap();
+        index_session_files(&db, &in_repo.id, &["lib.rs"]);
+        index_session_files(&db, &sibling.id, &["lib.rs"]);
+
+        let found = db
+            .sessions_touching_file("lib.rs", Some("/home/user/foo"))
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, in_repo.id);
+    }
+
+    #[test]
+    fn test_delete_session_removes_session_files() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/home/user/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        index_session_files(&db, &session.id, &["src/lib.rs"]);
+
+        db.delete_session(&session.id).unwrap();
+
+        assert!(db.get_session_files(&session.id).unwrap().is_empty());
+        assert!(db
+            .sessions_touching_file("src/lib.rs", None)
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Creates an assistant message that reads the given file.
+    fn create_test_read_message(session_id: Uuid, index: i32, file_path: &str) -> Message {
+        Message {
+            id: Uuid::new_v4(),
+            session_id,
+            parent_id: None,
+            index,
+            timestamp: Utc::now(),
+            role: MessageRole::Assistant,
+            content: MessageContent::Blocks(vec![crate::storage::models::ContentBlock::ToolUse {
+                id: format!("tool_{index}"),
+                name: "Read".to_string(),
+                input: serde_json::json!({ "file_path": file_path }),
+            }]),
+            model: None,
+            git_branch: None,
+            cwd: None,
+        }
+    }
+
+    #[test]
+    fn test_insert_message_indexes_session_files() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/home/user/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+
+        db.insert_message(&create_test_read_message(
+            session.id,
+            0,
+            "/home/user/project/src/main.rs",
+        ))
+        .unwrap();
+        db.insert_message(&create_test_message(
+            session.id,
+            1,
+            MessageRole::User,
+            "thanks",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            db.get_session_files(&session.id).unwrap(),
+            vec!["src/main.rs".to_string()]
+        );
+        let found = db.sessions_touching_file("src/main.rs", None).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, session.id);
+    }
+
+    #[test]
+    fn test_rebuild_file_index_backfills_from_messages() {
+        let (mut db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/home/user/project", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        db.insert_message(&create_test_read_message(
+            session.id,
+            0,
+            "/home/user/project/src/lib.rs",
+        ))
+        .unwrap();
+
+        // Simulate a database populated before the index existed
+        db.conn.execute("DELETE FROM session_files", []).unwrap();
+        assert!(db.get_session_files(&session.id).unwrap().is_empty());
+
+        let count = db.rebuild_file_index().unwrap();
+
+        assert_eq!(count, 1);
+        assert_eq!(
+            db.get_session_files(&session.id).unwrap(),
+            vec!["src/lib.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_session_spanning_two_directories_matches_either() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/home/user/api", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+
+        let mut first = create_test_message(session.id, 0, MessageRole::User, "fix the api");
+        first.cwd = Some("/home/user/api".to_string());
+        let mut second =
+            create_test_message(session.id, 1, MessageRole::User, "now update the frontend");
+        second.cwd = Some("/home/user/web".to_string());
+        db.insert_message(&first).unwrap();
+        db.insert_message(&second).unwrap();
+
+        assert_eq!(
+            db.get_session_directories(&session.id).unwrap(),
+            vec!["/home/user/api".to_string(), "/home/user/web".to_string()]
+        );
+        for dir in ["/home/user/api", "/home/user/web"] {
+            let found = db.list_sessions(10, Some(dir)).unwrap();
+            assert_eq!(found.len(), 1, "no session listed for {dir}");
+            assert_eq!(found[0].id, session.id);
+        }
+        assert!(db
+            .list_sessions(10, Some("/home/user/other"))
+            .unwrap()
+            .is_empty());
+
+        let results = db
+            .search_messages("frontend", 10, Some("/home/user/web"), None, None, None)
+            .unwrap();
+        assert!(results.iter().any(|r| r.session_id == session.id));
+        let results = db
+            .search_messages("frontend", 10, Some("/home/user/other"), None, None, None)
+            .unwrap();
+        assert!(results.is_empty());
+
+        db.delete_session(&session.id).unwrap();
+        assert!(db.get_session_directories(&session.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_backfills_session_directories() {
+        let (db, _dir) = create_test_db();
+        let session = create_test_session("claude-code", "/home/user/api", Utc::now(), None);
+        db.insert_session(&session).unwrap();
+        let mut message = create_test_message(session.id, 0, MessageRole::User, "hello");
+        message.cwd = Some("/home/user/web".to_string());
+        db.insert_message(&message).unwrap();
+
+        // Simulate a database created before the directory set existed
+        db.conn
+            .execute("DROP TABLE session_directories", [])
+            .unwrap();
+        db.migrate().unwrap();
+
+        assert_eq!(
+            db.get_session_directories(&session.id).unwrap(),
+            vec!["/home/user/web".to_string()]
+        );
+    }
+
+    // ==================== Repository Tests ====================
+
+    #[test]
+    fn test_upsert_repository_keeps_latest_session_and_remote() {
+        let (db, _dir) = create_test_db();
+        let newer = Utc::now();
+        let older = newer - Duration::days(3);
+
+        db.upsert_repository("/home/user/lore", "lore", Some("git@host:lore.git"), newer)
+            .unwrap();
+        db.upsert_repository("/home/user/lore", "lore", None, older)
+            .unwrap();
+
+        let (remote, last): (Option<String>, String) = db
+            .conn
+            .query_row(
+                "SELECT remote_url, last_session_at FROM repositories WHERE path = ?1",
+                params!["/home/user/lore"],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(remote.as_deref(), Some("git@host:lore.git"));
+        assert_eq!(last, newer.to_rfc3339());
+    }
+
+    #[test]
+    fn test_list_repository_activity_counts_sessions_per_directory() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+        for (dir, started) in [
+            ("/home/user/api", now - Duration::days(2)),
+            ("/home/user/api", now - Duration::hours(1)),
+            ("/home/user/web", now - Duration::days(5)),
+        ] {
+            db.insert_session(&create_test_session("claude-code", dir, started, None))
+                .unwrap();
+        }
+        db.upsert_repository("/home/user/web", "frontend", None, now)
+            .unwrap();
+
+        let repos = db.list_repository_activity(10).unwrap();
+
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0].path, "/home/user/api");
+        assert_eq!(repos[0].name, "api");
+        assert_eq!(repos[0].session_count, 2);
+        assert_eq!(
+            repos[0].last_session_at.timestamp(),
+            (now - Duration::hours(1)).timestamp()
+        );
+        assert_eq!(repos[1].name, "frontend");
+        assert_eq!(repos[1].session_count, 1);
+        assert_eq!(db.list_repository_activity(1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_list_repositories_orders_by_last_session() {
+        let (db, _dir) = create_test_db();
+        let now = Utc::now();
+        db.upsert_repository("/home/user/api", "api", None, now - Duration::days(1))
+            .unwrap();
+        db.upsert_repository("/home/user/web", "web", Some("git@host:web.git"), now)
+            .unwrap();
+
+        let repos = db.list_repositories().unwrap();
+
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0].name, "web");
+        assert_eq!(repos[0].remote_url.as_deref(), Some("git@host:web.git"));
+        assert_eq!(
+            repos[0].last_session_at.map(|t| t.timestamp()),
+            Some(now.timestamp())
+        );
+        assert_eq!(repos[1].path, "/home/user/api");
+        assert_eq!(repos[1].remote_url, None);
+    }
 }
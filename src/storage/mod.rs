@@ -20,15 +20,20 @@ pub mod models;
 pub use db::Database;
 // DatabaseStats is also available at crate::storage::db::DatabaseStats if needed
 pub use models::{
-    extract_session_files, Annotation, ContentBlock, LinkCreator, LinkType, Machine, Memory,
+    Annotation, ContentBlock, ForgeRepo, LinkCreator, LinkFactors, LinkType, Machine, Memory,
     MessageContent, MessageRole, SessionLink, Summary, Tag,
 };
 
+// File extraction now runs inside the database layer when messages are
+// stored; kept public for library users.
+#[allow(unused_imports)]
+pub use models::extract_session_files;
+
 // Re-exported for use by integration tests. These types are used through the
 // storage module in tests/cli_integration.rs even though they're not directly
 // used in the binary crate itself.
 #[allow(unused_imports)]
-pub use models::{Message, Session};
+pub use models::{session_dedup_key, Message, MessageThread, Session, SourceStamp};
 
 /// Returns the machine UUID for the current machine.
 ///
@@ -50,7 +55,6 @@ pub fn get_machine_id() -> Option<String> {
 ///
 /// This function is designed for use in session listings to show human-readable
 /// machine names instead of UUIDs.
-#[allow(dead_code)]
 pub fn get_machine_display_name(db: &Database, machine_id: &str) -> String {
     // First try to get from the machines table
     if let Ok(name) = db.get_machine_name(machine_id) {
@@ -45,6 +45,82 @@ pub struct Session {
     /// Used for sync to identify which machine created the session.
     /// Optional for backwards compatibility with existing sessions.
     pub machine_id: Option<String>,
+
+    /// Input (prompt) tokens used, including prompt tokens written to a
+    /// cache but not those read from it, for tools that report usage.
+    /// `None` when the tool reports no usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<i64>,
+
+    /// Output (completion) tokens used, for tools that report usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<i64>,
+
+    /// The session that spawned this one, for subagent sessions such as
+    /// Claude Code sidechains. `None` for top-level sessions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_session_id: Option<Uuid>,
+}
+
+/// The modification time and size of a session source file when it was
+/// imported.
+///
+/// Import compares the stamp recorded on a file's sessions with the file on
+/// disk, and parses the file again when either has changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceStamp {
+    /// Modification time in nanoseconds since the Unix epoch.
+    pub mtime_ns: i64,
+    /// File size in bytes.
+    pub size: i64,
+}
+
+impl SourceStamp {
+    /// Reads the stamp of the file at `path`.
+    pub fn of(path: &std::path::Path) -> std::io::Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        let mtime_ns = metadata
+            .modified()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| i64::try_from(d.as_nanos()).unwrap_or(i64::MAX))
+            .unwrap_or(0);
+        Ok(Self {
+            mtime_ns,
+            size: i64::try_from(metadata.len()).unwrap_or(i64::MAX),
+        })
+    }
+}
+
+/// Returns a key identifying a session by its content rather than by the
+/// file it was read from.
+///
+/// The key hashes the tool, start time, working directory, and first
+/// message, which stay the same when a tool's session directory is moved or
+/// a file is parsed again under a new session ID. Import uses it to skip
+/// sessions it already stored from another path.
+pub fn session_dedup_key(session: &Session, messages: &[Message]) -> String {
+    use sha2::{Digest, Sha256};
+
+    let first = messages
+        .iter()
+        .min_by_key(|m| m.index)
+        .map(|m| m.content.text())
+        .unwrap_or_default();
+    let mut hasher = Sha256::new();
+    for part in [
+        session.tool.as_str(),
+        &session.started_at.to_rfc3339(),
+        session.working_directory.as_str(),
+        first.as_str(),
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
 }
 
 /// A single message in a session
@@ -81,6 +157,91 @@ pub struct Message {
     pub cwd: Option<String>,
 }
 
+impl Message {
+    /// Returns the stable short form of the message ID (its first 8 hex digits).
+    ///
+    /// Accepted anywhere a message ID prefix is, such as `lore show --message`.
+    pub fn short_id(&self) -> String {
+        self.id.to_string()[..8].to_string()
+    }
+}
+
+/// A linear run of messages in a conversation tree, followed by its branches.
+///
+/// Each message in `messages` is the only reply to the one before it. When a
+/// message has several replies (an edited prompt or regenerated response),
+/// the run ends there and each reply starts one of the `branches`. Storing
+/// runs rather than one node per message keeps nesting proportional to the
+/// number of branch points instead of the conversation length.
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageThread {
+    /// Messages in this run, in conversation order.
+    pub messages: Vec<Message>,
+    /// Alternative continuations after the last message, ordered by index.
+    /// Empty at a leaf; never exactly one.
+    pub branches: Vec<MessageThread>,
+}
+
+impl MessageThread {
+    /// Builds the conversation tree for one session's messages.
+    ///
+    /// Messages are linked by `parent_id`. A message whose parent is missing,
+    /// not part of the session, or not earlier in the conversation is
+    /// attached to the message just before it, so sessions without parent
+    /// data form a single linear run. Returns `None` if `messages` is empty.
+    pub fn from_messages(mut messages: Vec<Message>) -> Option<Self> {
+        if messages.is_empty() {
+            return None;
+        }
+        messages.sort_by_key(|m| m.index);
+
+        let positions: std::collections::HashMap<Uuid, usize> = messages
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.id, i))
+            .collect();
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); messages.len()];
+        for (i, message) in messages.iter().enumerate().skip(1) {
+            let parent = message
+                .parent_id
+                .and_then(|id| positions.get(&id).copied())
+                .filter(|&p| p < i)
+                .unwrap_or(i - 1);
+            children[parent].push(i);
+        }
+
+        let mut slots: Vec<Option<Message>> = messages.into_iter().map(Some).collect();
+        Some(Self::build(0, &children, &mut slots))
+    }
+
+    /// Builds the run starting at position `start`.
+    fn build(start: usize, children: &[Vec<usize>], slots: &mut [Option<Message>]) -> Self {
+        let mut messages = Vec::new();
+        let mut current = start;
+        loop {
+            if let Some(message) = slots[current].take() {
+                messages.push(message);
+            }
+            match children[current].as_slice() {
+                [only] => current = *only,
+                replies => {
+                    let branches = replies
+                        .iter()
+                        .map(|&reply| Self::build(reply, children, slots))
+                        .collect();
+                    return Self { messages, branches };
+                }
+            }
+        }
+    }
+
+    /// Returns true if the conversation forks anywhere in this tree.
+    pub fn has_branches(&self) -> bool {
+        !self.branches.is_empty()
+    }
+}
+
 /// The role of a message sender in a conversation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -91,6 +252,11 @@ pub enum MessageRole {
     Assistant,
     /// A system prompt or instruction.
     System,
+    /// A turn that only carries tool output reported back to the assistant.
+    ///
+    /// Stored as `tool`. Messages imported before this role existed keep
+    /// their stored `user` or `assistant` role.
+    Tool,
 }
 
 impl std::fmt::Display for MessageRole {
@@ -99,6 +265,7 @@ impl std::fmt::Display for MessageRole {
             MessageRole::User => write!(f, "user"),
             MessageRole::Assistant => write!(f, "assistant"),
             MessageRole::System => write!(f, "system"),
+            MessageRole::Tool => write!(f, "tool"),
         }
     }
 }
@@ -217,6 +384,67 @@ pub struct SessionLink {
 
     /// Confidence score for auto-links (0.0 - 1.0)
     pub confidence: Option<f64>,
+
+    /// Forge repository the commit lives in, when the remote is a known host
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge: Option<ForgeRepo>,
+
+    /// Factor scores behind `confidence`, for links made by scored
+    /// auto-linking. `None` for manual links and older links.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factors: Option<LinkFactors>,
+}
+
+/// The points each factor contributed to an auto-link's confidence.
+///
+/// Stored with the link so `lore show --commit` can explain why a session
+/// was linked. The fields add up to the confidence before capping at 1.0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LinkFactors {
+    /// Branch match.
+    pub branch: f64,
+    /// File overlap.
+    pub files: f64,
+    /// Time proximity.
+    pub time: f64,
+    /// Recent activity bonus.
+    pub recency: f64,
+}
+
+/// A repository on a code forge (GitHub, GitLab, ...), parsed from a remote URL.
+///
+/// Holds enough to build web URLs for linked commits.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ForgeRepo {
+    /// Forge host name, for example `github.com`.
+    pub host: String,
+
+    /// Owning user or organization. For GitLab this may include subgroups
+    /// (`group/subgroup`).
+    pub owner: String,
+
+    /// Repository name without a `.git` suffix.
+    pub repo: String,
+}
+
+impl ForgeRepo {
+    /// Returns the web URL of a commit in this repository.
+    ///
+    /// Uses the host's commit path layout: `/-/commit/` for GitLab hosts,
+    /// `/commits/` for Bitbucket, and `/commit/` otherwise.
+    pub fn commit_url(&self, sha: &str) -> String {
+        let path = if self.host.starts_with("gitlab.") {
+            "-/commit"
+        } else if self.host == "bitbucket.org" {
+            "commits"
+        } else {
+            "commit"
+        };
+        format!(
+            "https://{}/{}/{}/{path}/{sha}",
+            self.host, self.owner, self.repo
+        )
+    }
 }
 
 /// The type of link between a session and git history.
@@ -448,6 +676,11 @@ pub struct Summary {
 
     /// When the summary was generated or last updated.
     pub generated_at: DateTime<Utc>,
+
+    /// The model that generated the summary, or None for summaries written
+    /// by hand (and ones generated before the model was recorded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
 }
 
 /// A per-project memory mirrored from a coding tool's memory store.
@@ -540,14 +773,10 @@ pub struct Tombstone {
 
 /// A tracked git repository.
 ///
-/// Repositories are discovered when sessions reference working directories
-/// that are inside git repositories.
-///
-/// Note: This struct is defined for future use when repository tracking
-/// is implemented. Currently, sessions link directly to commits without
-/// explicit repository records.
+/// A row is recorded for each distinct session working directory when
+/// sessions are imported, with the git remote when the directory is
+/// inside a git repository.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[allow(dead_code)]
 pub struct Repository {
     /// Unique identifier
     pub id: Uuid,
@@ -735,6 +964,39 @@ fn make_relative(path: &str, working_directory: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    fn thread_message(index: i32, parent_id: Option<Uuid>) -> Message {
+        Message {
+            id: Uuid::new_v4(),
+            session_id: Uuid::nil(),
+            parent_id,
+            index,
+            timestamp: Utc::now(),
+            role: MessageRole::User,
+            content: MessageContent::Text(format!("message {index}")),
+            model: None,
+            git_branch: None,
+            cwd: None,
+        }
+    }
+
+    #[test]
+    fn test_message_thread_unknown_parents_stay_linear() {
+        let first = thread_message(0, None);
+        // A parent outside the session and a parent later in the conversation
+        // both fall back to the preceding message.
+        let second = thread_message(1, Some(Uuid::new_v4()));
+        let mut third = thread_message(2, None);
+        let fourth = thread_message(3, Some(third.id));
+        third.parent_id = Some(fourth.id);
+
+        let thread = MessageThread::from_messages(vec![fourth, third, second, first]).unwrap();
+
+        assert!(!thread.has_branches());
+        let indices: Vec<i32> = thread.messages.iter().map(|m| m.index).collect();
+        assert_eq!(indices, vec![0, 1, 2, 3]);
+        assert!(MessageThread::from_messages(Vec::new()).is_none());
+    }
+
     #[test]
     fn test_extract_session_files_read_tool() {
         let messages = vec![Message {
@@ -952,4 +1214,26 @@ mod tests {
         // Should return the absolute path as-is
         assert_eq!(result, Some("/other/path/file.rs".to_string()));
     }
+
+    #[test]
+    fn test_forge_commit_url_per_host() {
+        let forge = |host: &str, owner: &str| ForgeRepo {
+            host: host.to_string(),
+            owner: owner.to_string(),
+            repo: "lore".to_string(),
+        };
+
+        assert_eq!(
+            forge("github.com", "varalys").commit_url("abc123"),
+            "https://github.com/varalys/lore/commit/abc123"
+        );
+        assert_eq!(
+            forge("gitlab.com", "group/sub").commit_url("abc123"),
+            "https://gitlab.com/group/sub/lore/-/commit/abc123"
+        );
+        assert_eq!(
+            forge("bitbucket.org", "team").commit_url("abc123"),
+            "https://bitbucket.org/team/lore/commits/abc123"
+        );
+    }
 }
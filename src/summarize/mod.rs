@@ -2,8 +2,8 @@
 //!
 //! This module provides the ability to generate summaries of AI-assisted
 //! development sessions using various LLM providers (Anthropic, OpenAI,
-//! OpenRouter). It includes provider configuration, API communication,
-//! and error handling.
+//! OpenRouter, Gemini, or any OpenAI-compatible server). It includes provider configuration, API
+//! communication, and error handling.
 //!
 //! # Usage
 //!
@@ -11,19 +11,32 @@
 //! configuration and calls the appropriate LLM API. Configuration is read
 //! from `~/.lore/config.yaml` with environment variable overrides.
 
+pub mod models;
 pub mod prompt;
 pub mod provider;
+pub mod retry;
 
 use std::env;
+use std::time::Duration;
 
 use crate::config::Config;
 use crate::storage::models::Message;
 
-pub use provider::{create_provider, SummaryProvider, SummaryProviderKind};
+pub use prompt::TruncationStrategy;
+pub use provider::{create_provider, SummaryProviderKind};
+pub use retry::{summarize_with_retry, RetryPolicy};
+
+// Re-exported for library users implementing custom providers; the binary
+// only calls providers through `summarize_with_retry`.
+#[allow(unused_imports)]
+pub use provider::SummaryProvider;
 
 /// Maximum character limit for the conversation transcript sent to the LLM.
 const MAX_CONVERSATION_CHARS: usize = 100_000;
 
+/// Rough number of characters per token, used to estimate token usage.
+const CHARS_PER_TOKEN: usize = 4;
+
 /// Resolved summary configuration from config file and environment variables.
 #[derive(Debug, Clone)]
 pub struct SummaryConfig {
@@ -33,6 +46,35 @@ pub struct SummaryConfig {
     pub api_key: String,
     /// Optional model override (uses provider default if None).
     pub model: Option<String>,
+    /// Maximum number of attempts per summary request, including the first.
+    pub max_attempts: u32,
+    /// How transcripts longer than the character limit are shortened.
+    pub truncation: TruncationStrategy,
+    /// API base URL replacing the provider's official host, if set.
+    pub base_url: Option<String>,
+}
+
+impl SummaryConfig {
+    /// Returns the model requests are sent to: the configured model, or the
+    /// provider's default when none is set.
+    pub fn model_name(&self) -> String {
+        self.model
+            .clone()
+            .unwrap_or_else(|| provider::default_model(self.kind).to_string())
+    }
+}
+
+/// A generated summary together with the model that wrote it.
+#[derive(Debug, Clone)]
+pub struct GeneratedSummary {
+    /// The summary text, with whitespace normalized.
+    pub content: String,
+    /// The model the summary was requested from.
+    pub model: String,
+    /// Estimated prompt tokens, from the length of the prompt sent.
+    pub estimated_input_tokens: i64,
+    /// Estimated completion tokens, from the length of the summary.
+    pub estimated_output_tokens: i64,
 }
 
 /// Resolves summary configuration from the config file and environment variables.
@@ -41,8 +83,12 @@ pub struct SummaryConfig {
 /// - `LORE_SUMMARY_PROVIDER` overrides `summary_provider`
 /// - `LORE_SUMMARY_API_KEY` overrides the provider-specific API key
 /// - `LORE_SUMMARY_MODEL` overrides the provider-specific model
+/// - `LORE_SUMMARY_BASE_URL` overrides `summary_base_url`
 ///
-/// Returns `NotConfigured` if no provider or API key is set.
+/// Returns `NotConfigured` if no provider or API key is set, and
+/// `InvalidBaseUrl` if the base URL is not an http or https URL. The
+/// `openai-compatible` provider needs no API key but returns
+/// `MissingSetting` unless both a base URL and a model are set.
 pub fn resolve_config() -> Result<SummaryConfig, SummarizeError> {
     let config = Config::load().map_err(|_| SummarizeError::NotConfigured)?;
 
@@ -64,50 +110,139 @@ pub fn resolve_config() -> Result<SummaryConfig, SummarizeError> {
         .ok()
         .or_else(|| config.summary_api_key_for_provider(&provider_str));
 
-    let api_key = api_key.ok_or(SummarizeError::NotConfigured)?;
-
-    if api_key.is_empty() {
-        return Err(SummarizeError::NotConfigured);
-    }
+    let api_key = match api_key.filter(|key| !key.is_empty()) {
+        Some(key) => key,
+        None if !kind.requires_api_key() => String::new(),
+        None => return Err(SummarizeError::NotConfigured),
+    };
 
-    // Model: env var > provider-specific config key
+    // Model: env var > provider-specific config key > summary_model
     let model = env::var("LORE_SUMMARY_MODEL")
         .ok()
         .or_else(|| config.summary_model_for_provider(&provider_str));
 
+    // An invalid strategy is reported by `lore config validate`; summaries
+    // still go ahead with the default
+    let truncation = match config.summary_truncation.as_deref().map(str::parse) {
+        Some(Ok(strategy)) => strategy,
+        Some(Err(e)) => {
+            tracing::warn!("{e}; using the default truncation strategy");
+            TruncationStrategy::default()
+        }
+        None => TruncationStrategy::default(),
+    };
+
+    let base_url = resolve_base_url(&config)?;
+    if kind == SummaryProviderKind::OpenAiCompatible {
+        if base_url.is_none() {
+            return Err(SummarizeError::MissingSetting {
+                provider: kind,
+                setting: "summary_base_url (or LORE_SUMMARY_BASE_URL)",
+            });
+        }
+        if model.as_deref().is_none_or(str::is_empty) {
+            return Err(SummarizeError::MissingSetting {
+                provider: kind,
+                setting: "summary_model (or LORE_SUMMARY_MODEL)",
+            });
+        }
+    }
+
     Ok(SummaryConfig {
         kind,
         api_key,
         model,
+        max_attempts: config.summary_max_attempts,
+        truncation,
+        base_url,
     })
 }
 
+/// Resolves the summary API base URL: `LORE_SUMMARY_BASE_URL`, then
+/// `summary_base_url`.
+///
+/// Returns `None` when neither is set, so the provider's official host is
+/// used. An empty value counts as unset.
+pub fn resolve_base_url(config: &Config) -> Result<Option<String>, SummarizeError> {
+    env::var("LORE_SUMMARY_BASE_URL")
+        .ok()
+        .or_else(|| config.summary_base_url.clone())
+        .filter(|url| !url.trim().is_empty())
+        .map(|url| provider::parse_base_url(&url).map_err(SummarizeError::InvalidBaseUrl))
+        .transpose()
+}
+
 /// Generates a summary for a set of session messages using the configured LLM provider.
 ///
 /// This is the main entry point for summary generation. It:
 /// 1. Resolves the provider configuration
 /// 2. Prepares the conversation transcript from messages
-/// 3. Calls the LLM API to generate a summary
+/// 3. Calls the LLM API to generate a summary, retrying rate-limited and
+///    transient server errors with exponential backoff
+///
+/// `model` overrides the resolved model (including `LORE_SUMMARY_MODEL`)
+/// for this call only.
 ///
 /// Returns `EmptySession` if there are no messages or all messages are empty.
 /// Returns `NotConfigured` if no provider is set up.
-pub fn generate_summary(messages: &[Message]) -> Result<String, SummarizeError> {
+pub fn generate_summary(
+    messages: &[Message],
+    model: Option<&str>,
+) -> Result<GeneratedSummary, SummarizeError> {
     if messages.is_empty() {
         return Err(SummarizeError::EmptySession);
     }
 
-    let config = resolve_config()?;
+    let mut config = resolve_config()?;
+    if let Some(model) = model {
+        config.model = Some(model.to_string());
+    }
+    summarize_messages(&config, messages)
+}
+
+/// Generates a summary for a set of session messages with an already
+/// resolved configuration.
+///
+/// Behaves like [`generate_summary`] without reading the config file, so
+/// a batch of sessions can share one [`SummaryConfig`].
+pub fn summarize_messages(
+    config: &SummaryConfig,
+    messages: &[Message],
+) -> Result<GeneratedSummary, SummarizeError> {
+    if messages.is_empty() {
+        return Err(SummarizeError::EmptySession);
+    }
+    let model = config.model_name();
 
-    let conversation = prompt::prepare_conversation(messages, MAX_CONVERSATION_CHARS);
+    let conversation =
+        prompt::prepare_conversation(messages, MAX_CONVERSATION_CHARS, config.truncation);
     if conversation.is_empty() {
         return Err(SummarizeError::EmptySession);
     }
 
     let system = prompt::system_prompt();
-    let provider = create_provider(config.kind, config.api_key, config.model);
+    let provider = create_provider(
+        config.kind,
+        config.api_key.clone(),
+        Some(model.clone()),
+        config.base_url.clone(),
+    );
+
+    let policy = RetryPolicy::with_max_attempts(config.max_attempts);
+
+    let response = summarize_with_retry(provider.as_ref(), &policy, system, &conversation)?;
+    let content = normalize_whitespace(&response.content);
+    Ok(GeneratedSummary {
+        estimated_input_tokens: estimate_tokens(system.len() + conversation.len()),
+        estimated_output_tokens: estimate_tokens(content.len()),
+        content,
+        model,
+    })
+}
 
-    let response = provider.summarize(system, &conversation)?;
-    Ok(normalize_whitespace(&response.content))
+/// Estimates the number of tokens in `chars` characters of text.
+fn estimate_tokens(chars: usize) -> i64 {
+    i64::try_from(chars.div_ceil(CHARS_PER_TOKEN)).unwrap_or(i64::MAX)
 }
 
 /// Normalizes whitespace in a summary string.
@@ -156,11 +291,12 @@ pub enum SummarizeError {
         status: u16,
         /// Response body text.
         body: String,
+        /// Delay requested by the server's `Retry-After` header, if any.
+        retry_after: Option<Duration>,
     },
 
     /// The provider API returned an error in its JSON response.
     #[error("API error ({status}): {message}")]
-    #[allow(dead_code)]
     ApiError {
         /// HTTP status code.
         status: u16,
@@ -175,6 +311,23 @@ pub enum SummarizeError {
     /// The session has no content to summarize.
     #[error("Session has no content to summarize")]
     EmptySession,
+
+    /// The provider has no endpoint for listing models.
+    #[error("This summary provider does not support listing models")]
+    ListModelsUnsupported,
+
+    /// The configured API base URL is not usable.
+    #[error("Invalid summary base URL: {0}")]
+    InvalidBaseUrl(String),
+
+    /// The provider needs a setting that is not configured.
+    #[error("The {provider} summary provider requires {setting} to be set")]
+    MissingSetting {
+        /// The configured provider.
+        provider: SummaryProviderKind,
+        /// The missing config key, with its environment variable.
+        setting: &'static str,
+    },
 }
 
 #[cfg(test)]
@@ -190,6 +343,22 @@ mod tests {
         assert!(err.to_string().contains("not configured"));
     }
 
+    #[test]
+    fn test_model_name_falls_back_to_provider_default() {
+        let mut config = SummaryConfig {
+            kind: SummaryProviderKind::OpenAI,
+            api_key: "key".to_string(),
+            model: None,
+            max_attempts: 1,
+            truncation: TruncationStrategy::default(),
+            base_url: None,
+        };
+        assert_eq!(config.model_name(), "gpt-4o-mini");
+
+        config.model = Some("gpt-4o".to_string());
+        assert_eq!(config.model_name(), "gpt-4o");
+    }
+
     #[test]
     fn test_summarize_error_display_request_failed() {
         let err = SummarizeError::RequestFailed("connection refused".to_string());
@@ -201,6 +370,7 @@ mod tests {
         let err = SummarizeError::HttpError {
             status: 429,
             body: "rate limited".to_string(),
+            retry_after: None,
         };
         let msg = err.to_string();
         assert!(msg.contains("429"));
@@ -233,7 +403,7 @@ mod tests {
     #[test]
     fn test_generate_summary_empty_messages() {
         let messages: Vec<Message> = vec![];
-        let result = generate_summary(&messages);
+        let result = generate_summary(&messages, None);
         assert!(result.is_err());
         match result.unwrap_err() {
             SummarizeError::EmptySession => {}
@@ -262,7 +432,7 @@ mod tests {
             cwd: None,
         }];
 
-        let result = generate_summary(&messages);
+        let result = generate_summary(&messages, None);
         // Without a configured provider, this should fail. But if the conversation
         // text is empty, it should return EmptySession before trying the provider.
         match result {
@@ -280,6 +450,9 @@ mod tests {
             kind: SummaryProviderKind::Anthropic,
             api_key: "sk-test".to_string(),
             model: Some("claude-haiku-4-5-20241022".to_string()),
+            max_attempts: 4,
+            truncation: TruncationStrategy::default(),
+            base_url: None,
         };
         let debug = format!("{config:?}");
         assert!(debug.contains("Anthropic"));
@@ -0,0 +1,139 @@
+//! Cached model listings for summary providers.
+//!
+//! `lore config summary-models` and the model check in `lore config set`
+//! both need the provider's model list. Listings are cached in
+//! `~/.lore/cache/models-<provider>.json` for [`CACHE_TTL`] so repeated
+//! commands do not hit the provider API every time.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::provider::{create_provider, SummaryProviderKind};
+use super::SummarizeError;
+
+/// How long a fetched model list stays fresh.
+pub const CACHE_TTL: Duration = Duration::hours(1);
+
+/// On-disk form of a cached model list.
+#[derive(Debug, Serialize, Deserialize)]
+struct ModelCache {
+    fetched_at: DateTime<Utc>,
+    models: Vec<String>,
+}
+
+/// Returns the cache file for a provider's model list.
+fn cache_path(kind: SummaryProviderKind) -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        home.join(".lore")
+            .join("cache")
+            .join(format!("models-{kind}.json"))
+    })
+}
+
+/// Reads a cached model list if it was fetched within `ttl` of `now`.
+fn read_cache(path: &Path, now: DateTime<Utc>, ttl: Duration) -> Option<Vec<String>> {
+    let contents = fs::read_to_string(path).ok()?;
+    let cache: ModelCache = serde_json::from_str(&contents).ok()?;
+    if now - cache.fetched_at > ttl || cache.fetched_at > now {
+        return None;
+    }
+    Some(cache.models)
+}
+
+/// Writes a model list to the cache, creating the cache directory if needed.
+fn write_cache(path: &Path, models: &[String], now: DateTime<Utc>) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let cache = ModelCache {
+        fetched_at: now,
+        models: models.to_vec(),
+    };
+    fs::write(path, serde_json::to_string(&cache)?)
+}
+
+/// Returns the models available from a provider, sorted.
+///
+/// Serves a cached list when one younger than [`CACHE_TTL`] exists, unless
+/// `refresh` is set. `base_url` overrides the provider's API host as in
+/// [`create_provider`]. A failure to write the cache is logged and ignored.
+pub fn list_models(
+    kind: SummaryProviderKind,
+    api_key: String,
+    base_url: Option<String>,
+    refresh: bool,
+) -> Result<Vec<String>, SummarizeError> {
+    let now = Utc::now();
+    let path = cache_path(kind);
+
+    if !refresh {
+        if let Some(models) = path.as_deref().and_then(|p| read_cache(p, now, CACHE_TTL)) {
+            return Ok(models);
+        }
+    }
+
+    let models = create_provider(kind, api_key, None, base_url).list_models()?;
+
+    if let Some(path) = path {
+        if let Err(e) = write_cache(&path, &models, now) {
+            tracing::debug!("Failed to cache model list at {}: {e}", path.display());
+        }
+    }
+
+    Ok(models)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn models() -> Vec<String> {
+        vec!["a/model".to_string(), "b/model".to_string()]
+    }
+
+    #[test]
+    fn test_cache_round_trip_within_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("models-openrouter.json");
+        let now = Utc::now();
+
+        write_cache(&path, &models(), now).unwrap();
+
+        assert_eq!(
+            read_cache(&path, now + Duration::minutes(30), CACHE_TTL),
+            Some(models())
+        );
+    }
+
+    #[test]
+    fn test_cache_expires_after_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("models-openrouter.json");
+        let now = Utc::now();
+
+        write_cache(&path, &models(), now).unwrap();
+
+        assert_eq!(
+            read_cache(&path, now + Duration::minutes(61), CACHE_TTL),
+            None
+        );
+        // A timestamp in the future (clock change) is not trusted either
+        assert_eq!(
+            read_cache(&path, now - Duration::minutes(5), CACHE_TTL),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cache_missing_or_corrupt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("models-openai.json");
+        assert_eq!(read_cache(&path, Utc::now(), CACHE_TTL), None);
+
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(read_cache(&path, Utc::now(), CACHE_TTL), None);
+    }
+}
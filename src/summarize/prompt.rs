@@ -3,6 +3,9 @@
 //! Provides the system prompt and message formatting logic needed to
 //! generate concise summaries of AI-assisted development sessions.
 
+use std::fmt;
+use std::str::FromStr;
+
 use crate::storage::models::{Message, MessageRole};
 
 /// Returns the system prompt that instructs the LLM how to summarize a session.
@@ -22,19 +25,73 @@ pub fn system_prompt() -> &'static str {
      - Start bullet points with a dash (-)."
 }
 
+/// How an oversized transcript is cut down to the character limit.
+///
+/// Set with the `summary_truncation` config key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TruncationStrategy {
+    /// Keep the first messages that fit and drop the rest.
+    Head,
+    /// Keep the last messages that fit and drop the rest.
+    Tail,
+    /// Keep the first 20% and last 30% of messages and drop the middle
+    /// (default).
+    #[default]
+    HeadAndTail,
+    /// Drop messages from the middle outwards until the rest fits, keeping
+    /// as much of both ends as the limit allows.
+    MiddleOut,
+}
+
+impl TruncationStrategy {
+    /// Names accepted by `summary_truncation`, in declaration order.
+    pub const NAMES: &'static [&'static str] = &["head", "tail", "head-and-tail", "middle-out"];
+}
+
+impl fmt::Display for TruncationStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TruncationStrategy::Head => write!(f, "head"),
+            TruncationStrategy::Tail => write!(f, "tail"),
+            TruncationStrategy::HeadAndTail => write!(f, "head-and-tail"),
+            TruncationStrategy::MiddleOut => write!(f, "middle-out"),
+        }
+    }
+}
+
+impl FromStr for TruncationStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "head" => Ok(TruncationStrategy::Head),
+            "tail" => Ok(TruncationStrategy::Tail),
+            "head-and-tail" => Ok(TruncationStrategy::HeadAndTail),
+            "middle-out" => Ok(TruncationStrategy::MiddleOut),
+            other => Err(format!(
+                "Unknown truncation strategy: '{other}'. Expected one of: {}",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
 /// Formats session messages into a conversation transcript for the LLM.
 ///
 /// Each message is rendered with a role tag and, for user messages, a UTC
 /// timestamp. Tool calls and thinking blocks are excluded via
 /// `MessageContent::text()`.
 ///
-/// If the formatted output exceeds `max_chars`, the middle portion of the
-/// conversation is replaced with an omission marker. The first 20% and
-/// last 30% of messages are kept to preserve context from both the beginning
-/// and end of the session.
+/// If the formatted output exceeds `max_chars`, messages are dropped as
+/// `strategy` directs and an omission marker takes their place. A
+/// `max_chars` of 0 disables truncation.
 ///
 /// Returns an empty string when the message slice is empty.
-pub fn prepare_conversation(messages: &[Message], max_chars: usize) -> String {
+pub fn prepare_conversation(
+    messages: &[Message],
+    max_chars: usize,
+    strategy: TruncationStrategy,
+) -> String {
     if messages.is_empty() {
         return String::new();
     }
@@ -45,8 +102,12 @@ pub fn prepare_conversation(messages: &[Message], max_chars: usize) -> String {
         return formatted;
     }
 
-    // Truncation: keep first 20% and last 30% of messages
-    truncate_conversation(messages, max_chars)
+    match strategy {
+        TruncationStrategy::HeadAndTail => truncate_conversation(messages, max_chars),
+        TruncationStrategy::Head | TruncationStrategy::Tail | TruncationStrategy::MiddleOut => {
+            truncate_to_fit(messages, max_chars, strategy)
+        }
+    }
 }
 
 /// Formats a slice of messages into the conversation transcript.
@@ -66,9 +127,9 @@ fn format_messages(messages: &[Message]) -> String {
 
 /// Formats a single message with its role header and content.
 ///
-/// User and system messages include a UTC timestamp. Assistant messages
-/// show only the role tag. Returns an empty string if the message text
-/// is empty (e.g., messages containing only tool blocks).
+/// User and system messages include a UTC timestamp. Assistant and tool
+/// messages show only the role tag. Returns an empty string if the message
+/// text is empty (e.g., messages containing only tool blocks).
 fn format_single_message(msg: &Message, text: &str) -> String {
     if text.is_empty() {
         return String::new();
@@ -80,6 +141,7 @@ fn format_single_message(msg: &Message, text: &str) -> String {
             format!("[User] ({ts})")
         }
         MessageRole::Assistant => "[Assistant]".to_string(),
+        MessageRole::Tool => "[Tool]".to_string(),
         MessageRole::System => {
             let ts = msg.timestamp.format("%Y-%m-%d %H:%M UTC");
             format!("[System] ({ts})")
@@ -89,10 +151,11 @@ fn format_single_message(msg: &Message, text: &str) -> String {
     format!("{header}\n{text}")
 }
 
-/// Applies the head+tail truncation strategy when the full transcript is too long.
+/// Applies the head-and-tail truncation strategy when the full transcript is too long.
 ///
 /// Keeps the first 20% of messages and the last 30%, replacing the middle
-/// with an omission marker indicating how many messages were skipped.
+/// with an omission marker indicating how many messages were skipped. If
+/// that is still too long, falls back to middle-out truncation.
 fn truncate_conversation(messages: &[Message], max_chars: usize) -> String {
     let count = messages.len();
 
@@ -113,20 +176,94 @@ fn truncate_conversation(messages: &[Message], max_chars: usize) -> String {
     let tail_msgs = &messages[count - tail_count..];
 
     let head_text = format_messages(head_msgs);
-    let marker = format!("[... {omitted} messages omitted ...]");
+    let marker = omission_marker(omitted);
     let tail_text = format_messages(tail_msgs);
 
     let result = format!("{head_text}\n\n{marker}\n\n{tail_text}");
 
-    // If still over the limit after truncation, hard-truncate the result
+    // If still over the limit, drop more of the middle rather than cutting
+    // off the end of the session
     if result.len() > max_chars {
-        let truncated: String = result.chars().take(max_chars).collect();
-        truncated
+        truncate_to_fit(messages, max_chars, TruncationStrategy::MiddleOut)
     } else {
         result
     }
 }
 
+/// Keeps whole messages chosen by `strategy` until the next would not fit.
+///
+/// `Head` keeps from the start, `Tail` from the end, and `MiddleOut` from
+/// both ends in turn, so the dropped messages are the middle ones. The
+/// dropped run is replaced with an omission marker. If not even one message
+/// fits, the transcript is cut to `max_chars` at the end it keeps.
+fn truncate_to_fit(messages: &[Message], max_chars: usize, strategy: TruncationStrategy) -> String {
+    let parts: Vec<String> = messages
+        .iter()
+        .map(|msg| format_single_message(msg, &msg.content.text()))
+        .filter(|part| !part.is_empty())
+        .collect();
+    let count = parts.len();
+
+    let mut head = 0;
+    let mut tail = 0;
+    // Length of the kept parts, each followed by its "\n\n" separator
+    let mut used = 0;
+    let marker_len = omission_marker(count).len();
+    loop {
+        let take_head = match strategy {
+            TruncationStrategy::Head => true,
+            TruncationStrategy::Tail => false,
+            _ => head <= tail,
+        };
+        if head + tail == count {
+            break;
+        }
+        let next = if take_head {
+            &parts[head]
+        } else {
+            &parts[count - 1 - tail]
+        };
+        if used + next.len() + 2 + marker_len > max_chars {
+            break;
+        }
+        used += next.len() + 2;
+        if take_head {
+            head += 1;
+        } else {
+            tail += 1;
+        }
+    }
+
+    let omitted = count - head - tail;
+    if head + tail == 0 {
+        let all = parts.join("\n\n");
+        return match strategy {
+            TruncationStrategy::Tail => {
+                let skip = all.chars().count().saturating_sub(max_chars);
+                all.chars().skip(skip).collect()
+            }
+            _ => all.chars().take(max_chars).collect(),
+        };
+    }
+
+    let mut sections = Vec::with_capacity(3);
+    if head > 0 {
+        sections.push(parts[..head].join("\n\n"));
+    }
+    if omitted > 0 {
+        sections.push(omission_marker(omitted));
+    }
+    if tail > 0 {
+        sections.push(parts[count - tail..].join("\n\n"));
+    }
+    sections.join("\n\n")
+}
+
+/// Returns the marker that stands in for `omitted` dropped messages.
+fn omission_marker(omitted: usize) -> String {
+    format!("[... {omitted} messages omitted ...]")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,7 +322,7 @@ mod tests {
             make_message(MessageRole::Assistant, "I found the issue in auth.rs", 1),
         ];
 
-        let result = prepare_conversation(&messages, 10_000);
+        let result = prepare_conversation(&messages, 10_000, TruncationStrategy::default());
 
         assert!(result.contains("[User] (2024-01-15 14:30 UTC)"));
         assert!(result.contains("Fix the login bug"));
@@ -203,7 +340,7 @@ mod tests {
             0,
         )];
 
-        let result = prepare_conversation(&messages, 10_000);
+        let result = prepare_conversation(&messages, 10_000, TruncationStrategy::default());
 
         assert!(result.contains("[System] (2024-01-15 14:30 UTC)"));
         assert!(result.contains("You are a coding assistant"));
@@ -212,7 +349,7 @@ mod tests {
     #[test]
     fn test_empty_messages_returns_empty_string() {
         let messages: Vec<Message> = vec![];
-        let result = prepare_conversation(&messages, 10_000);
+        let result = prepare_conversation(&messages, 10_000, TruncationStrategy::default());
         assert!(result.is_empty());
     }
 
@@ -220,7 +357,7 @@ mod tests {
     fn test_single_message_formatting() {
         let messages = vec![make_message(MessageRole::User, "Hello", 0)];
 
-        let result = prepare_conversation(&messages, 10_000);
+        let result = prepare_conversation(&messages, 10_000, TruncationStrategy::default());
 
         assert_eq!(result, "[User] (2024-01-15 14:30 UTC)\nHello");
     }
@@ -233,7 +370,7 @@ mod tests {
             make_message(MessageRole::Assistant, "Done reading", 2),
         ];
 
-        let result = prepare_conversation(&messages, 10_000);
+        let result = prepare_conversation(&messages, 10_000, TruncationStrategy::default());
 
         // The tool-only message should be skipped (empty text)
         assert!(result.contains("Read that file"));
@@ -258,7 +395,7 @@ mod tests {
 
         // Use a limit smaller than the full output (~800 chars) but large enough
         // to contain the truncated head+marker+tail (~450 chars).
-        let result = prepare_conversation(&messages, 500);
+        let result = prepare_conversation(&messages, 500, TruncationStrategy::HeadAndTail);
 
         assert!(result.contains("Message number 0"));
         assert!(result.contains("[... 10 messages omitted ...]"));
@@ -272,7 +409,7 @@ mod tests {
             make_message(MessageRole::Assistant, "Reply", 1),
         ];
 
-        let result = prepare_conversation(&messages, 10_000);
+        let result = prepare_conversation(&messages, 10_000, TruncationStrategy::default());
 
         assert!(!result.contains("omitted"));
     }
@@ -287,7 +424,7 @@ mod tests {
 
         // Full output is ~380 chars. Use 250 to trigger truncation but
         // leave enough room for the head+marker+tail (~220 chars).
-        let result = prepare_conversation(&messages, 250);
+        let result = prepare_conversation(&messages, 250, TruncationStrategy::HeadAndTail);
 
         // Head messages
         assert!(result.contains("msg-0"));
@@ -304,7 +441,7 @@ mod tests {
     fn test_all_tool_only_messages_produce_empty_output() {
         let messages = vec![make_tool_only_message(0), make_tool_only_message(1)];
 
-        let result = prepare_conversation(&messages, 10_000);
+        let result = prepare_conversation(&messages, 10_000, TruncationStrategy::default());
 
         assert!(result.is_empty());
     }
@@ -316,7 +453,7 @@ mod tests {
             make_message(MessageRole::Assistant, "World", 1),
         ];
 
-        let result = prepare_conversation(&messages, 0);
+        let result = prepare_conversation(&messages, 0, TruncationStrategy::HeadAndTail);
 
         assert!(result.contains("Hello"));
         assert!(result.contains("World"));
@@ -333,8 +470,8 @@ mod tests {
 
         // Even with a small limit, the truncation logic sees that
         // head+tail covers all messages and returns the full output.
-        let full = prepare_conversation(&two_messages, 10_000);
-        let truncated = prepare_conversation(&two_messages, 1);
+        let full = prepare_conversation(&two_messages, 10_000, TruncationStrategy::HeadAndTail);
+        let truncated = prepare_conversation(&two_messages, 1, TruncationStrategy::HeadAndTail);
 
         // The result is the full text (possibly hard-truncated), but
         // no omission marker because there are too few messages to split.
@@ -377,10 +514,11 @@ mod tests {
             ),
         ];
 
-        let full = prepare_conversation(&messages, 10_000);
+        let full = prepare_conversation(&messages, 10_000, TruncationStrategy::default());
         // Truncated output replaces 2 messages (~140 chars) with a marker (~30 chars)
         // so it should be shorter. Use a limit that triggers truncation.
-        let result = prepare_conversation(&messages, full.len() - 1);
+        let result =
+            prepare_conversation(&messages, full.len() - 1, TruncationStrategy::HeadAndTail);
 
         assert!(result.contains("The very first user message"));
         assert!(result.contains("penultimate message"));
@@ -390,4 +528,84 @@ mod tests {
         assert!(!result.contains("A long middle response"));
         assert!(!result.contains("Another middle message"));
     }
+
+    /// Eight user messages `step-0`..`step-7`, 36 characters each when
+    /// formatted, 302 characters in all with separators.
+    fn numbered_steps() -> Vec<Message> {
+        (0..8)
+            .map(|i| make_message(MessageRole::User, &format!("step-{i}"), i))
+            .collect()
+    }
+
+    fn kept_steps(result: &str) -> Vec<i32> {
+        (0..8)
+            .filter(|i| {
+                result.contains(&format!("step-{i}\n")) || result.ends_with(&format!("step-{i}"))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_truncation_strategies_keep_expected_messages() {
+        let messages = numbered_steps();
+        // Room for four messages with separators and the omission marker
+        let limit = 4 * 38 + 30;
+
+        let cases = [
+            (TruncationStrategy::Head, vec![0, 1, 2, 3]),
+            (TruncationStrategy::Tail, vec![4, 5, 6, 7]),
+            (TruncationStrategy::MiddleOut, vec![0, 1, 6, 7]),
+            // 20% + 30% of messages does not fit, so more of the middle goes
+            (TruncationStrategy::HeadAndTail, vec![0, 1, 6, 7]),
+        ];
+        for (strategy, expected) in cases {
+            let result = prepare_conversation(&messages, limit, strategy);
+            assert!(
+                result.len() <= limit,
+                "{strategy} is {} chars",
+                result.len()
+            );
+            assert_eq!(kept_steps(&result), expected, "{strategy}");
+            let omitted = 8 - expected.len();
+            assert!(
+                result.contains(&format!("[... {omitted} messages omitted ...]")),
+                "{strategy}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncation_marker_position() {
+        let messages = numbered_steps();
+        let head = prepare_conversation(&messages, 200, TruncationStrategy::Head);
+        assert!(head.starts_with("[User]"));
+        assert!(head.ends_with("messages omitted ...]"));
+
+        let tail = prepare_conversation(&messages, 200, TruncationStrategy::Tail);
+        assert!(tail.starts_with("[... "));
+        assert!(tail.ends_with("step-7"));
+    }
+
+    #[test]
+    fn test_truncation_when_no_message_fits() {
+        let messages = numbered_steps();
+        let head = prepare_conversation(&messages, 20, TruncationStrategy::Head);
+        assert_eq!(head, "[User] (2024-01-15 1");
+
+        let tail = prepare_conversation(&messages, 6, TruncationStrategy::Tail);
+        assert_eq!(tail, "step-7");
+    }
+
+    #[test]
+    fn test_truncation_strategy_round_trips_names() {
+        for name in TruncationStrategy::NAMES {
+            let strategy: TruncationStrategy = name.parse().unwrap();
+            assert_eq!(strategy.to_string(), *name);
+        }
+        assert_eq!(
+            TruncationStrategy::default(),
+            TruncationStrategy::HeadAndTail
+        );
+        assert!("sideways".parse::<TruncationStrategy>().is_err());
+    }
 }
@@ -1,15 +1,19 @@
 //! LLM provider integrations for session summary generation.
 //!
-//! Supports Anthropic, OpenAI, and OpenRouter as summary providers.
+//! Supports Anthropic, OpenAI, OpenRouter, and Google Gemini as summary
+//! providers, plus any server with an OpenAI-compatible Chat Completions
+//! API, such as LM Studio, vLLM, or Groq.
 //! Each provider implements the [`SummaryProvider`] trait, and the
 //! [`create_provider`] factory builds the appropriate provider from
-//! configuration.
+//! configuration. Requests go to each provider's official API unless a base
+//! URL is given, for example to route traffic through a proxy.
 
 use std::fmt;
 use std::str::FromStr;
 use std::time::Duration;
 
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::RETRY_AFTER;
 use serde_json::Value;
 
 use super::SummarizeError;
@@ -20,6 +24,18 @@ const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
 /// Timeout for the entire request including response (120 seconds).
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
 
+/// Base URL of the Anthropic API.
+const ANTHROPIC_API_BASE: &str = "https://api.anthropic.com/v1";
+
+/// Base URL of the OpenAI API.
+const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
+
+/// Base URL of the OpenRouter API.
+const OPENROUTER_API_BASE: &str = "https://openrouter.ai/api/v1";
+
+/// Base URL of the Google Generative Language API.
+const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
+
 // ==================== Types ====================
 
 /// Supported LLM provider kinds.
@@ -31,6 +47,22 @@ pub enum SummaryProviderKind {
     OpenAI,
     /// OpenRouter unified API.
     OpenRouter,
+    /// Google Gemini (Generative Language API).
+    Gemini,
+    /// Any server implementing the OpenAI Chat Completions API, such as LM
+    /// Studio, vLLM, or Groq. Needs a base URL and model; the API key is
+    /// optional.
+    OpenAiCompatible,
+}
+
+impl SummaryProviderKind {
+    /// Returns whether requests need an API key.
+    ///
+    /// Local OpenAI-compatible servers usually accept unauthenticated
+    /// requests, so the key is optional for them.
+    pub fn requires_api_key(self) -> bool {
+        !matches!(self, SummaryProviderKind::OpenAiCompatible)
+    }
 }
 
 impl fmt::Display for SummaryProviderKind {
@@ -39,6 +71,8 @@ impl fmt::Display for SummaryProviderKind {
             SummaryProviderKind::Anthropic => write!(f, "anthropic"),
             SummaryProviderKind::OpenAI => write!(f, "openai"),
             SummaryProviderKind::OpenRouter => write!(f, "openrouter"),
+            SummaryProviderKind::Gemini => write!(f, "gemini"),
+            SummaryProviderKind::OpenAiCompatible => write!(f, "openai-compatible"),
         }
     }
 }
@@ -51,7 +85,9 @@ impl FromStr for SummaryProviderKind {
             "anthropic" => Ok(SummaryProviderKind::Anthropic),
             "openai" => Ok(SummaryProviderKind::OpenAI),
             "openrouter" => Ok(SummaryProviderKind::OpenRouter),
-            other => Err(format!("Unknown summary provider: '{other}'. Expected one of: anthropic, openai, openrouter")),
+            "gemini" => Ok(SummaryProviderKind::Gemini),
+            "openai-compatible" => Ok(SummaryProviderKind::OpenAiCompatible),
+            other => Err(format!("Unknown summary provider: '{other}'. Expected one of: anthropic, openai, openrouter, gemini, openai-compatible")),
         }
     }
 }
@@ -79,6 +115,14 @@ pub trait SummaryProvider {
         system_prompt: &str,
         user_content: &str,
     ) -> Result<SummaryResponse, SummarizeError>;
+
+    /// Lists the model identifiers the provider accepts, sorted.
+    ///
+    /// Providers without a model listing endpoint return
+    /// [`SummarizeError::ListModelsUnsupported`].
+    fn list_models(&self) -> Result<Vec<String>, SummarizeError> {
+        Err(SummarizeError::ListModelsUnsupported)
+    }
 }
 
 // ==================== Anthropic ====================
@@ -91,18 +135,27 @@ pub(crate) struct AnthropicProvider {
     api_key: String,
     /// Model identifier (e.g., "claude-haiku-4-5").
     model: String,
+    /// API base URL, without a trailing slash.
+    base_url: String,
 }
 
 impl AnthropicProvider {
-    /// Creates a new Anthropic provider.
+    /// Creates a new Anthropic provider that calls the official API.
     pub(crate) fn new(client: Client, api_key: String, model: String) -> Self {
         Self {
             client,
             api_key,
             model,
+            base_url: ANTHROPIC_API_BASE.to_string(),
         }
     }
 
+    /// Sends requests to `base_url` instead of the official API.
+    pub(crate) fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
     /// Builds the JSON request body for the Anthropic Messages API.
     fn build_request_body(&self, system_prompt: &str, user_content: &str) -> Value {
         serde_json::json!({
@@ -117,6 +170,16 @@ impl AnthropicProvider {
             ]
         })
     }
+
+    /// Builds the summary request, ready to send.
+    fn build_request(&self, system_prompt: &str, user_content: &str) -> RequestBuilder {
+        self.client
+            .post(format!("{}/messages", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&self.build_request_body(system_prompt, user_content))
+    }
 }
 
 impl SummaryProvider for AnthropicProvider {
@@ -125,29 +188,12 @@ impl SummaryProvider for AnthropicProvider {
         system_prompt: &str,
         user_content: &str,
     ) -> Result<SummaryResponse, SummarizeError> {
-        let body = self.build_request_body(system_prompt, user_content);
-
         let response = self
-            .client
-            .post("https://api.anthropic.com/v1/messages")
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&body)
+            .build_request(system_prompt, user_content)
             .send()
             .map_err(|e| SummarizeError::RequestFailed(e.to_string()))?;
 
-        let status = response.status();
-        if !status.is_success() {
-            let status_code = status.as_u16();
-            let body_text = response
-                .text()
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(SummarizeError::HttpError {
-                status: status_code,
-                body: body_text,
-            });
-        }
+        let response = check_status(response)?;
 
         let json: Value = response
             .json()
@@ -169,6 +215,24 @@ impl SummaryProvider for AnthropicProvider {
             content: content.to_string(),
         })
     }
+
+    fn list_models(&self) -> Result<Vec<String>, SummarizeError> {
+        let response = self
+            .client
+            .get(format!("{}/models?limit=1000", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .send()
+            .map_err(|e| SummarizeError::RequestFailed(e.to_string()))?;
+
+        let response = check_status(response)?;
+
+        let json: Value = response
+            .json()
+            .map_err(|e| SummarizeError::ParseError(e.to_string()))?;
+
+        parse_model_list(&json)
+    }
 }
 
 // ==================== OpenAI ====================
@@ -181,18 +245,27 @@ pub(crate) struct OpenAIProvider {
     api_key: String,
     /// Model identifier (e.g., "gpt-4o-mini").
     model: String,
+    /// API base URL, without a trailing slash.
+    base_url: String,
 }
 
 impl OpenAIProvider {
-    /// Creates a new OpenAI provider.
+    /// Creates a new OpenAI provider that calls the official API.
     pub(crate) fn new(client: Client, api_key: String, model: String) -> Self {
         Self {
             client,
             api_key,
             model,
+            base_url: OPENAI_API_BASE.to_string(),
         }
     }
 
+    /// Sends requests to `base_url` instead of the official API.
+    pub(crate) fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
     /// Builds the JSON request body for the OpenAI Chat Completions API.
     fn build_request_body(&self, system_prompt: &str, user_content: &str) -> Value {
         serde_json::json!({
@@ -210,6 +283,26 @@ impl OpenAIProvider {
             ]
         })
     }
+
+    /// Builds the summary request, ready to send.
+    fn build_request(&self, system_prompt: &str, user_content: &str) -> RequestBuilder {
+        self.authorize(
+            self.client
+                .post(format!("{}/chat/completions", self.base_url)),
+        )
+        .header("content-type", "application/json")
+        .json(&self.build_request_body(system_prompt, user_content))
+    }
+
+    /// Adds bearer authentication, unless the provider has no API key, as
+    /// for a local OpenAI-compatible server.
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        if self.api_key.is_empty() {
+            request
+        } else {
+            request.header("Authorization", format!("Bearer {}", self.api_key))
+        }
+    }
 }
 
 impl SummaryProvider for OpenAIProvider {
@@ -218,28 +311,12 @@ impl SummaryProvider for OpenAIProvider {
         system_prompt: &str,
         user_content: &str,
     ) -> Result<SummaryResponse, SummarizeError> {
-        let body = self.build_request_body(system_prompt, user_content);
-
         let response = self
-            .client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("content-type", "application/json")
-            .json(&body)
+            .build_request(system_prompt, user_content)
             .send()
             .map_err(|e| SummarizeError::RequestFailed(e.to_string()))?;
 
-        let status = response.status();
-        if !status.is_success() {
-            let status_code = status.as_u16();
-            let body_text = response
-                .text()
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(SummarizeError::HttpError {
-                status: status_code,
-                body: body_text,
-            });
-        }
+        let response = check_status(response)?;
 
         let json: Value = response
             .json()
@@ -247,6 +324,21 @@ impl SummaryProvider for OpenAIProvider {
 
         parse_openai_response(&json)
     }
+
+    fn list_models(&self) -> Result<Vec<String>, SummarizeError> {
+        let response = self
+            .authorize(self.client.get(format!("{}/models", self.base_url)))
+            .send()
+            .map_err(|e| SummarizeError::RequestFailed(e.to_string()))?;
+
+        let response = check_status(response)?;
+
+        let json: Value = response
+            .json()
+            .map_err(|e| SummarizeError::ParseError(e.to_string()))?;
+
+        parse_model_list(&json)
+    }
 }
 
 // ==================== OpenRouter ====================
@@ -262,18 +354,27 @@ pub(crate) struct OpenRouterProvider {
     api_key: String,
     /// Model identifier (e.g., "meta-llama/llama-3.1-8b-instruct:free").
     model: String,
+    /// API base URL, without a trailing slash.
+    base_url: String,
 }
 
 impl OpenRouterProvider {
-    /// Creates a new OpenRouter provider.
+    /// Creates a new OpenRouter provider that calls the official API.
     pub(crate) fn new(client: Client, api_key: String, model: String) -> Self {
         Self {
             client,
             api_key,
             model,
+            base_url: OPENROUTER_API_BASE.to_string(),
         }
     }
 
+    /// Sends requests to `base_url` instead of the official API.
+    pub(crate) fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
     /// Builds the JSON request body for the OpenRouter API.
     ///
     /// Uses the same format as OpenAI Chat Completions.
@@ -293,6 +394,16 @@ impl OpenRouterProvider {
             ]
         })
     }
+
+    /// Builds the summary request, ready to send.
+    fn build_request(&self, system_prompt: &str, user_content: &str) -> RequestBuilder {
+        self.client
+            .post(format!("{}/chat/completions", self.base_url))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("HTTP-Referer", "https://lore.varalys.com")
+            .header("content-type", "application/json")
+            .json(&self.build_request_body(system_prompt, user_content))
+    }
 }
 
 impl SummaryProvider for OpenRouterProvider {
@@ -301,44 +412,282 @@ impl SummaryProvider for OpenRouterProvider {
         system_prompt: &str,
         user_content: &str,
     ) -> Result<SummaryResponse, SummarizeError> {
-        let body = self.build_request_body(system_prompt, user_content);
+        let response = self
+            .build_request(system_prompt, user_content)
+            .send()
+            .map_err(|e| SummarizeError::RequestFailed(e.to_string()))?;
+
+        let response = check_status(response)?;
+
+        let json: Value = response
+            .json()
+            .map_err(|e| SummarizeError::ParseError(e.to_string()))?;
+
+        parse_openai_response(&json)
+    }
 
+    fn list_models(&self) -> Result<Vec<String>, SummarizeError> {
         let response = self
             .client
-            .post("https://openrouter.ai/api/v1/chat/completions")
+            .get(format!("{}/models", self.base_url))
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("HTTP-Referer", "https://lore.varalys.com")
-            .header("content-type", "application/json")
-            .json(&body)
             .send()
             .map_err(|e| SummarizeError::RequestFailed(e.to_string()))?;
 
-        let status = response.status();
-        if !status.is_success() {
-            let status_code = status.as_u16();
-            let body_text = response
-                .text()
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(SummarizeError::HttpError {
-                status: status_code,
-                body: body_text,
-            });
+        let response = check_status(response)?;
+
+        let json: Value = response
+            .json()
+            .map_err(|e| SummarizeError::ParseError(e.to_string()))?;
+
+        parse_model_list(&json)
+    }
+}
+
+// ==================== Gemini ====================
+
+/// Google Gemini provider using the Generative Language API.
+///
+/// The model is part of the request URL rather than the body, and errors
+/// are reported as `{"error": {"code", "message", "status"}}`.
+pub(crate) struct GeminiProvider {
+    /// HTTP client instance.
+    client: Client,
+    /// Google AI Studio API key.
+    api_key: String,
+    /// Model identifier (e.g., "gemini-1.5-flash").
+    model: String,
+    /// API base URL, without a trailing slash.
+    base_url: String,
+}
+
+impl GeminiProvider {
+    /// Creates a new Gemini provider that calls the official API.
+    pub(crate) fn new(client: Client, api_key: String, model: String) -> Self {
+        Self {
+            client,
+            api_key,
+            model,
+            base_url: GEMINI_API_BASE.to_string(),
         }
+    }
+
+    /// Sends requests to `base_url` instead of the official API.
+    pub(crate) fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Returns the `generateContent` endpoint for the configured model.
+    fn generate_url(&self) -> String {
+        // Model names from the listing endpoint carry a "models/" prefix
+        let model = self.model.strip_prefix("models/").unwrap_or(&self.model);
+        format!("{}/models/{model}:generateContent", self.base_url)
+    }
+
+    /// Builds the JSON request body for the Gemini `generateContent` API.
+    fn build_request_body(&self, system_prompt: &str, user_content: &str) -> Value {
+        serde_json::json!({
+            "systemInstruction": {
+                "parts": [{ "text": system_prompt }]
+            },
+            "contents": [
+                {
+                    "role": "user",
+                    "parts": [{ "text": user_content }]
+                }
+            ],
+            "generationConfig": {
+                "maxOutputTokens": 1024
+            }
+        })
+    }
+
+    /// Builds the summary request, ready to send.
+    fn build_request(&self, system_prompt: &str, user_content: &str) -> RequestBuilder {
+        self.client
+            .post(self.generate_url())
+            .header("x-goog-api-key", &self.api_key)
+            .header("content-type", "application/json")
+            .json(&self.build_request_body(system_prompt, user_content))
+    }
+}
+
+impl SummaryProvider for GeminiProvider {
+    fn summarize(
+        &self,
+        system_prompt: &str,
+        user_content: &str,
+    ) -> Result<SummaryResponse, SummarizeError> {
+        let response = self
+            .build_request(system_prompt, user_content)
+            .send()
+            .map_err(|e| SummarizeError::RequestFailed(e.to_string()))?;
+
+        let response = check_status(response).map_err(map_gemini_error)?;
 
         let json: Value = response
             .json()
             .map_err(|e| SummarizeError::ParseError(e.to_string()))?;
 
-        parse_openai_response(&json)
+        parse_gemini_response(&json)
+    }
+
+    fn list_models(&self) -> Result<Vec<String>, SummarizeError> {
+        let response = self
+            .client
+            .get(format!("{}/models?pageSize=1000", self.base_url))
+            .header("x-goog-api-key", &self.api_key)
+            .send()
+            .map_err(|e| SummarizeError::RequestFailed(e.to_string()))?;
+
+        let response = check_status(response).map_err(map_gemini_error)?;
+
+        let json: Value = response
+            .json()
+            .map_err(|e| SummarizeError::ParseError(e.to_string()))?;
+
+        parse_gemini_model_list(&json)
+    }
+}
+
+/// Parses a Gemini `generateContent` response.
+///
+/// Joins the text of all parts in `candidates[0].content.parts` and trims
+/// surrounding whitespace, so the result matches the other providers.
+fn parse_gemini_response(json: &Value) -> Result<SummaryResponse, SummarizeError> {
+    let parts = json
+        .get("candidates")
+        .and_then(|c| c.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|candidate| candidate.get("content"))
+        .and_then(|content| content.get("parts"))
+        .and_then(|p| p.as_array())
+        .ok_or_else(|| {
+            SummarizeError::ParseError(
+                "Missing candidates[0].content.parts in Gemini response".to_string(),
+            )
+        })?;
+
+    let content: String = parts
+        .iter()
+        .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+        .collect();
+    let content = content.trim();
+    if content.is_empty() {
+        return Err(SummarizeError::ParseError(
+            "Gemini response contained no text".to_string(),
+        ));
+    }
+
+    Ok(SummaryResponse {
+        content: content.to_string(),
+    })
+}
+
+/// Parses a Gemini model listing response.
+///
+/// Returns the names of models that support `generateContent`, without
+/// their "models/" prefix, sorted and deduplicated.
+fn parse_gemini_model_list(json: &Value) -> Result<Vec<String>, SummarizeError> {
+    let data = json
+        .get("models")
+        .and_then(|d| d.as_array())
+        .ok_or_else(|| {
+            SummarizeError::ParseError("Missing models array in model list response".to_string())
+        })?;
+
+    let mut models: Vec<String> = data
+        .iter()
+        .filter(|model| {
+            model
+                .get("supportedGenerationMethods")
+                .and_then(|m| m.as_array())
+                .is_none_or(|methods| methods.iter().any(|m| m == "generateContent"))
+        })
+        .filter_map(|model| model.get("name").and_then(|n| n.as_str()))
+        .map(|name| name.strip_prefix("models/").unwrap_or(name).to_string())
+        .collect();
+    models.sort();
+    models.dedup();
+
+    Ok(models)
+}
+
+/// Maps a Gemini error response into [`SummarizeError::ApiError`].
+///
+/// Rate-limit and server errors are left as [`SummarizeError::HttpError`]
+/// so the retry layer still retries them and honors `Retry-After`. Bodies
+/// that are not Gemini error JSON are also left unchanged.
+fn map_gemini_error(err: SummarizeError) -> SummarizeError {
+    let SummarizeError::HttpError { status, body, .. } = &err else {
+        return err;
+    };
+    if *status == 429 || (500..600).contains(status) {
+        return err;
+    }
+
+    let Some(error) = serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|json| json.get("error").cloned())
+    else {
+        return err;
+    };
+    let Some(message) = error.get("message").and_then(|m| m.as_str()) else {
+        return err;
+    };
+    let message = match error.get("status").and_then(|s| s.as_str()) {
+        Some(code) => format!("{message} ({code})"),
+        None => message.to_string(),
+    };
+
+    SummarizeError::ApiError {
+        status: *status,
+        message,
     }
 }
 
 // ==================== Shared Helpers ====================
 
+/// Converts a non-success HTTP response into [`SummarizeError::HttpError`].
+///
+/// Captures the `Retry-After` header so the retry layer can honor the
+/// server's requested delay on 429 responses.
+fn check_status(response: Response) -> Result<Response, SummarizeError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after);
+    let body_text = response
+        .text()
+        .unwrap_or_else(|_| "Unknown error".to_string());
+
+    Err(SummarizeError::HttpError {
+        status: status.as_u16(),
+        body: body_text,
+        retry_after,
+    })
+}
+
+/// Parses a `Retry-After` header value given in delta-seconds.
+///
+/// The HTTP-date form is not supported and yields `None`, in which case
+/// the retry layer falls back to its own backoff schedule.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
 /// Parses a response in the OpenAI Chat Completions format.
 ///
 /// Extracts `choices[0].message.content` from the JSON response.
-/// Used by both OpenAI and OpenRouter providers.
+/// Used by the OpenAI, OpenRouter, and OpenAI-compatible providers.
 fn parse_openai_response(json: &Value) -> Result<SummaryResponse, SummarizeError> {
     let content = json
         .get("choices")
@@ -356,25 +705,88 @@ fn parse_openai_response(json: &Value) -> Result<SummaryResponse, SummarizeError
     })
 }
 
+/// Parses a model listing response.
+///
+/// Anthropic, OpenAI, and OpenRouter all return `{"data": [{"id": ...}]}`.
+/// The returned identifiers are sorted and deduplicated.
+fn parse_model_list(json: &Value) -> Result<Vec<String>, SummarizeError> {
+    let data = json.get("data").and_then(|d| d.as_array()).ok_or_else(|| {
+        SummarizeError::ParseError("Missing data array in model list response".to_string())
+    })?;
+
+    let mut models: Vec<String> = data
+        .iter()
+        .filter_map(|model| model.get("id").and_then(|id| id.as_str()))
+        .map(|id| id.to_string())
+        .collect();
+    models.sort();
+    models.dedup();
+
+    Ok(models)
+}
+
 // ==================== Factory ====================
 
 /// Returns the default model for the given provider kind.
+///
+/// OpenAI-compatible servers have no default model and return an empty
+/// string; a model must be configured for them.
 pub fn default_model(kind: SummaryProviderKind) -> &'static str {
     match kind {
         SummaryProviderKind::Anthropic => "claude-haiku-4-5",
         SummaryProviderKind::OpenAI => "gpt-4o-mini",
         SummaryProviderKind::OpenRouter => "meta-llama/llama-3.1-8b-instruct:free",
+        SummaryProviderKind::Gemini => "gemini-1.5-flash",
+        SummaryProviderKind::OpenAiCompatible => "",
+    }
+}
+
+/// Returns the official API base URL for the given provider kind.
+///
+/// OpenAI-compatible servers have no official host and return an empty
+/// string; a base URL must be configured for them.
+pub fn default_base_url(kind: SummaryProviderKind) -> &'static str {
+    match kind {
+        SummaryProviderKind::Anthropic => ANTHROPIC_API_BASE,
+        SummaryProviderKind::OpenAI => OPENAI_API_BASE,
+        SummaryProviderKind::OpenRouter => OPENROUTER_API_BASE,
+        SummaryProviderKind::Gemini => GEMINI_API_BASE,
+        SummaryProviderKind::OpenAiCompatible => "",
     }
 }
 
+/// Checks a summary base URL and returns it without a trailing slash.
+///
+/// The URL must be absolute and use `http` or `https`, such as
+/// `https://llm-gateway.example.com/v1`.
+pub fn parse_base_url(url: &str) -> Result<String, String> {
+    let parsed =
+        reqwest::Url::parse(url.trim()).map_err(|e| format!("'{url}' is not a valid URL: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!(
+            "'{url}' must use http or https, not '{}'",
+            parsed.scheme()
+        ));
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err(format!("'{url}' has no host"));
+    }
+    Ok(url.trim().trim_end_matches('/').to_string())
+}
+
 /// Creates a summary provider for the given kind.
 ///
 /// If `model` is `None`, uses the default model for the provider kind.
-/// The returned provider is ready to make API calls.
+/// If `base_url` is `None`, requests go to the provider's official API;
+/// otherwise endpoints are appended to it, so it should be a URL already
+/// checked by [`parse_base_url`]. The returned provider is ready to make
+/// API calls. OpenAI-compatible providers send no `Authorization` header
+/// when `api_key` is empty.
 pub fn create_provider(
     kind: SummaryProviderKind,
     api_key: String,
     model: Option<String>,
+    base_url: Option<String>,
 ) -> Box<dyn SummaryProvider> {
     let client = Client::builder()
         .connect_timeout(CONNECT_TIMEOUT)
@@ -383,12 +795,20 @@ pub fn create_provider(
         .expect("Failed to build HTTP client");
 
     let model = model.unwrap_or_else(|| default_model(kind).to_string());
+    let base_url = base_url.unwrap_or_else(|| default_base_url(kind).to_string());
 
     match kind {
-        SummaryProviderKind::Anthropic => Box::new(AnthropicProvider::new(client, api_key, model)),
-        SummaryProviderKind::OpenAI => Box::new(OpenAIProvider::new(client, api_key, model)),
+        SummaryProviderKind::Anthropic => {
+            Box::new(AnthropicProvider::new(client, api_key, model).with_base_url(base_url))
+        }
+        SummaryProviderKind::OpenAI | SummaryProviderKind::OpenAiCompatible => {
+            Box::new(OpenAIProvider::new(client, api_key, model).with_base_url(base_url))
+        }
         SummaryProviderKind::OpenRouter => {
-            Box::new(OpenRouterProvider::new(client, api_key, model))
+            Box::new(OpenRouterProvider::new(client, api_key, model).with_base_url(base_url))
+        }
+        SummaryProviderKind::Gemini => {
+            Box::new(GeminiProvider::new(client, api_key, model).with_base_url(base_url))
         }
     }
 }
@@ -429,6 +849,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_default_model_gemini() {
+        assert_eq!(
+            default_model(SummaryProviderKind::Gemini),
+            "gemini-1.5-flash"
+        );
+    }
+
     // ==================== SummaryProviderKind Display tests ====================
 
     #[test]
@@ -446,6 +874,11 @@ mod tests {
         assert_eq!(SummaryProviderKind::OpenRouter.to_string(), "openrouter");
     }
 
+    #[test]
+    fn test_provider_kind_display_gemini() {
+        assert_eq!(SummaryProviderKind::Gemini.to_string(), "gemini");
+    }
+
     // ==================== SummaryProviderKind FromStr tests ====================
 
     #[test]
@@ -472,6 +905,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_provider_kind_from_str_gemini() {
+        assert_eq!(
+            SummaryProviderKind::from_str("gemini").unwrap(),
+            SummaryProviderKind::Gemini
+        );
+    }
+
     #[test]
     fn test_provider_kind_from_str_case_insensitive() {
         assert_eq!(
@@ -488,24 +929,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_provider_kind_openai_compatible_round_trips() {
+        let kind = SummaryProviderKind::from_str("openai-compatible").unwrap();
+        assert_eq!(kind, SummaryProviderKind::OpenAiCompatible);
+        assert_eq!(kind.to_string(), "openai-compatible");
+        assert!(!kind.requires_api_key());
+        assert!(SummaryProviderKind::OpenAI.requires_api_key());
+    }
+
     #[test]
     fn test_provider_kind_from_str_unknown() {
-        let err = SummaryProviderKind::from_str("gemini").unwrap_err();
+        let err = SummaryProviderKind::from_str("mistral").unwrap_err();
         assert!(err.contains("Unknown summary provider"));
-        assert!(err.contains("gemini"));
+        assert!(err.contains("mistral"));
     }
 
     // ==================== create_provider tests ====================
 
     #[test]
     fn test_create_provider_anthropic_does_not_panic() {
-        let _provider =
-            create_provider(SummaryProviderKind::Anthropic, "test-key".to_string(), None);
+        let _provider = create_provider(
+            SummaryProviderKind::Anthropic,
+            "test-key".to_string(),
+            None,
+            None,
+        );
     }
 
     #[test]
     fn test_create_provider_openai_does_not_panic() {
-        let _provider = create_provider(SummaryProviderKind::OpenAI, "test-key".to_string(), None);
+        let _provider = create_provider(
+            SummaryProviderKind::OpenAI,
+            "test-key".to_string(),
+            None,
+            None,
+        );
     }
 
     #[test]
@@ -514,6 +973,17 @@ mod tests {
             SummaryProviderKind::OpenRouter,
             "test-key".to_string(),
             None,
+            None,
+        );
+    }
+
+    #[test]
+    fn test_create_provider_gemini_does_not_panic() {
+        let _provider = create_provider(
+            SummaryProviderKind::Gemini,
+            "test-key".to_string(),
+            None,
+            None,
         );
     }
 
@@ -523,6 +993,7 @@ mod tests {
             SummaryProviderKind::Anthropic,
             "test-key".to_string(),
             Some("claude-sonnet-4-20250514".to_string()),
+            None,
         );
     }
 
@@ -590,6 +1061,183 @@ mod tests {
         assert_eq!(messages[1]["content"], "Summarize this session.");
     }
 
+    #[test]
+    fn test_gemini_request_body() {
+        let provider = GeminiProvider::new(
+            build_client(),
+            "test-key".to_string(),
+            "gemini-1.5-flash".to_string(),
+        );
+
+        let body = provider.build_request_body("Be concise.", "Summarize this session.");
+
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "Be concise.");
+        assert_eq!(body["generationConfig"]["maxOutputTokens"], 1024);
+        assert!(body.get("model").is_none());
+
+        let contents = body["contents"].as_array().unwrap();
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents[0]["role"], "user");
+        assert_eq!(contents[0]["parts"][0]["text"], "Summarize this session.");
+    }
+
+    #[test]
+    fn test_gemini_generate_url() {
+        let provider = GeminiProvider::new(
+            build_client(),
+            "test-key".to_string(),
+            "gemini-1.5-flash".to_string(),
+        );
+        assert_eq!(
+            provider.generate_url(),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-1.5-flash:generateContent"
+        );
+
+        let prefixed = GeminiProvider::new(
+            build_client(),
+            "test-key".to_string(),
+            "models/gemini-1.5-pro".to_string(),
+        );
+        assert!(prefixed
+            .generate_url()
+            .ends_with("/models/gemini-1.5-pro:generateContent"));
+    }
+
+    #[test]
+    fn test_requests_use_official_hosts_by_default() {
+        let anthropic = AnthropicProvider::new(build_client(), "k".to_string(), "m".to_string());
+        let request = anthropic.build_request("s", "u").build().unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.anthropic.com/v1/messages"
+        );
+
+        let openai = OpenAIProvider::new(build_client(), "k".to_string(), "m".to_string());
+        let request = openai.build_request("s", "u").build().unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.openai.com/v1/chat/completions"
+        );
+    }
+
+    /// Serves one HTTP request with `body` as a JSON response, returning the
+    /// server's base URL and a handle yielding the raw request it received.
+    fn serve_once(body: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}/v1", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut payload = vec![0; content_length];
+            reader.read_exact(&mut payload).unwrap();
+            request.push_str(&String::from_utf8(payload).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base, handle)
+    }
+
+    #[test]
+    fn test_openai_compatible_provider_against_mock_server() {
+        let (base, server) = serve_once(
+            r#"{"id":"chatcmpl-1","object":"chat.completion","created":1,"model":"qwen2.5-7b","choices":[{"index":0,"message":{"role":"assistant","content":"Fixed the login bug."},"finish_reason":"stop"}],"usage":{"prompt_tokens":10,"completion_tokens":5,"total_tokens":15}}"#,
+        );
+
+        let provider = create_provider(
+            SummaryProviderKind::OpenAiCompatible,
+            String::new(),
+            Some("qwen2.5-7b".to_string()),
+            Some(parse_base_url(&base).unwrap()),
+        );
+        let response = provider.summarize("Be concise.", "Summarize.").unwrap();
+        assert_eq!(response.content, "Fixed the login bug.");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /v1/chat/completions HTTP/1.1"));
+        assert!(request.contains(r#""model":"qwen2.5-7b""#));
+        // Without an API key no bearer token is sent
+        assert!(!request.to_lowercase().contains("authorization:"));
+    }
+
+    #[test]
+    fn test_openai_compatible_provider_sends_bearer_key_when_set() {
+        let base = parse_base_url("https://api.groq.com/openai/v1").unwrap();
+        let provider = OpenAIProvider::new(build_client(), "gsk-1".to_string(), "m".to_string())
+            .with_base_url(base);
+        let request = provider.build_request("s", "u").build().unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.groq.com/openai/v1/chat/completions"
+        );
+        assert_eq!(request.headers()["Authorization"], "Bearer gsk-1");
+    }
+
+    #[test]
+    fn test_requests_use_overridden_base_url() {
+        let base = parse_base_url("https://llm-gateway.internal/anthropic/").unwrap();
+        let anthropic = AnthropicProvider::new(build_client(), "k".to_string(), "m".to_string())
+            .with_base_url(base);
+        let request = anthropic.build_request("s", "u").build().unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://llm-gateway.internal/anthropic/messages"
+        );
+        assert_eq!(request.headers()["x-api-key"], "k");
+
+        let base = parse_base_url("http://localhost:8080/v1").unwrap();
+        let openai = OpenAIProvider::new(build_client(), "k".to_string(), "m".to_string())
+            .with_base_url(base.clone());
+        let request = openai.build_request("s", "u").build().unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "http://localhost:8080/v1/chat/completions"
+        );
+
+        let gemini = GeminiProvider::new(
+            build_client(),
+            "k".to_string(),
+            "gemini-1.5-flash".to_string(),
+        )
+        .with_base_url(base);
+        assert_eq!(
+            gemini.generate_url(),
+            "http://localhost:8080/v1/models/gemini-1.5-flash:generateContent"
+        );
+    }
+
+    #[test]
+    fn test_parse_base_url_requires_http_scheme() {
+        assert_eq!(
+            parse_base_url(" https://gateway.example.com/v1/ ").unwrap(),
+            "https://gateway.example.com/v1"
+        );
+        assert!(parse_base_url("ftp://gateway.example.com")
+            .unwrap_err()
+            .contains("http or https"));
+        assert!(parse_base_url("gateway.example.com/v1").is_err());
+        assert!(parse_base_url("file:///etc/hosts").is_err());
+    }
+
     // ==================== Response parsing tests ====================
 
     #[test]
@@ -672,6 +1320,164 @@ mod tests {
         assert_eq!(content, "This session refactored the database layer.");
     }
 
+    #[test]
+    fn test_parse_model_list_sorts_ids() {
+        let json = serde_json::json!({
+            "data": [
+                {"id": "openai/gpt-4o-mini", "name": "GPT-4o mini"},
+                {"id": "anthropic/claude-haiku-4-5"},
+                {"name": "missing id"},
+                {"id": "anthropic/claude-haiku-4-5"}
+            ]
+        });
+        assert_eq!(
+            parse_model_list(&json).unwrap(),
+            vec!["anthropic/claude-haiku-4-5", "openai/gpt-4o-mini"]
+        );
+    }
+
+    #[test]
+    fn test_parse_model_list_missing_data() {
+        let json = serde_json::json!({"error": "unauthorized"});
+        assert!(matches!(
+            parse_model_list(&json),
+            Err(SummarizeError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_gemini_response_joins_parts() {
+        let json = serde_json::json!({
+            "candidates": [
+                {
+                    "content": {
+                        "role": "model",
+                        "parts": [
+                            {"text": "This session added "},
+                            {"text": "a Gemini provider.\n"}
+                        ]
+                    },
+                    "finishReason": "STOP"
+                }
+            ]
+        });
+
+        let result = parse_gemini_response(&json).unwrap();
+        assert_eq!(result.content, "This session added a Gemini provider.");
+    }
+
+    #[test]
+    fn test_parse_gemini_response_without_text() {
+        let missing = serde_json::json!({"promptFeedback": {"blockReason": "SAFETY"}});
+        assert!(matches!(
+            parse_gemini_response(&missing),
+            Err(SummarizeError::ParseError(_))
+        ));
+
+        let empty = serde_json::json!({
+            "candidates": [{"content": {"parts": []}}]
+        });
+        assert!(matches!(
+            parse_gemini_response(&empty),
+            Err(SummarizeError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_gemini_model_list() {
+        let json = serde_json::json!({
+            "models": [
+                {
+                    "name": "models/gemini-1.5-pro",
+                    "supportedGenerationMethods": ["generateContent", "countTokens"]
+                },
+                {
+                    "name": "models/text-embedding-004",
+                    "supportedGenerationMethods": ["embedContent"]
+                },
+                {
+                    "name": "models/gemini-1.5-flash",
+                    "supportedGenerationMethods": ["generateContent"]
+                }
+            ]
+        });
+        assert_eq!(
+            parse_gemini_model_list(&json).unwrap(),
+            vec!["gemini-1.5-flash", "gemini-1.5-pro"]
+        );
+        assert!(matches!(
+            parse_gemini_model_list(&serde_json::json!({})),
+            Err(SummarizeError::ParseError(_))
+        ));
+    }
+
+    // ==================== Gemini error mapping tests ====================
+
+    fn http_error(status: u16, body: &str) -> SummarizeError {
+        SummarizeError::HttpError {
+            status,
+            body: body.to_string(),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn test_map_gemini_error_to_api_error() {
+        let body = r#"{
+            "error": {
+                "code": 400,
+                "message": "API key not valid. Please pass a valid API key.",
+                "status": "INVALID_ARGUMENT"
+            }
+        }"#;
+
+        match map_gemini_error(http_error(400, body)) {
+            SummarizeError::ApiError { status, message } => {
+                assert_eq!(status, 400);
+                assert_eq!(
+                    message,
+                    "API key not valid. Please pass a valid API key. (INVALID_ARGUMENT)"
+                );
+            }
+            other => panic!("Expected ApiError, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_map_gemini_error_keeps_retryable_and_unknown_bodies() {
+        let body = r#"{"error": {"code": 429, "message": "Quota exceeded", "status": "RESOURCE_EXHAUSTED"}}"#;
+        assert!(matches!(
+            map_gemini_error(http_error(429, body)),
+            SummarizeError::HttpError { status: 429, .. }
+        ));
+        assert!(matches!(
+            map_gemini_error(http_error(503, body)),
+            SummarizeError::HttpError { status: 503, .. }
+        ));
+        assert!(matches!(
+            map_gemini_error(http_error(404, "<html>Not Found</html>")),
+            SummarizeError::HttpError { status: 404, .. }
+        ));
+        assert!(matches!(
+            map_gemini_error(SummarizeError::RequestFailed("timeout".to_string())),
+            SummarizeError::RequestFailed(_)
+        ));
+    }
+
+    // ==================== Retry-After parsing tests ====================
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        assert_eq!(parse_retry_after("7"), Some(Duration::from_secs(7)));
+        assert_eq!(parse_retry_after(" 0 "), Some(Duration::from_secs(0)));
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date_unsupported() {
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(parse_retry_after(""), None);
+    }
+
     // ==================== Timeout constant tests ====================
 
     #[test]
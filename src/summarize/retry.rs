@@ -0,0 +1,281 @@
+//! Retry with exponential backoff for summary provider calls.
+//!
+//! Rate-limited (429) and transient server (5xx) responses are retried
+//! with exponential backoff plus jitter. When the provider sends a
+//! `Retry-After` header, that delay is used instead of the computed
+//! backoff, capped at the same maximum. All other errors, including 4xx responses other than 429,
+//! fail immediately.
+
+use std::thread;
+use std::time::Duration;
+
+use rand::Rng;
+
+use super::provider::{SummaryProvider, SummaryResponse};
+use super::SummarizeError;
+
+/// Default maximum number of attempts, including the initial request.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 4;
+
+/// Base delay for the first retry (doubled on each subsequent retry).
+const BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound on the computed backoff delay.
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Controls how failed summary requests are retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the initial request.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each retry after that.
+    pub base_delay: Duration,
+    /// Upper bound on the computed backoff delay (before jitter) and on a
+    /// server-supplied `Retry-After` delay.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::with_max_attempts(DEFAULT_MAX_ATTEMPTS)
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy with the default delays and the given attempt limit.
+    ///
+    /// A limit of 0 is treated as 1 so the request is always sent once.
+    pub fn with_max_attempts(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay: BASE_DELAY,
+            max_delay: MAX_DELAY,
+        }
+    }
+
+    /// Returns the delay to wait before retry number `retry` (1-based).
+    ///
+    /// Uses the server's `Retry-After` value when present, capped at
+    /// `max_delay` so a bogus header cannot stall the caller. Otherwise the
+    /// delay is `base_delay * 2^(retry - 1)`, capped at `max_delay`, with
+    /// up to 50% random jitter added to spread out concurrent clients.
+    fn delay_for(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(delay) = retry_after {
+            return delay.min(self.max_delay);
+        }
+
+        let exponent = retry.saturating_sub(1).min(16);
+        let backoff = self
+            .base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay);
+
+        let jitter_ms = (backoff.as_millis() / 2) as u64;
+        let jitter = if jitter_ms > 0 {
+            Duration::from_millis(rand::thread_rng().gen_range(0..=jitter_ms))
+        } else {
+            Duration::ZERO
+        };
+
+        backoff + jitter
+    }
+}
+
+/// Returns true if the error is worth retrying.
+///
+/// Only 429 (rate limited) and 5xx (server error) responses are retried.
+fn is_retryable(err: &SummarizeError) -> bool {
+    match err {
+        SummarizeError::HttpError { status, .. } => *status == 429 || (500..600).contains(status),
+        _ => false,
+    }
+}
+
+/// Calls the provider, retrying rate-limited and server errors per `policy`.
+///
+/// Returns the first successful response, the first non-retryable error,
+/// or the last error once `policy.max_attempts` attempts are exhausted.
+pub fn summarize_with_retry(
+    provider: &dyn SummaryProvider,
+    policy: &RetryPolicy,
+    system_prompt: &str,
+    user_content: &str,
+) -> Result<SummaryResponse, SummarizeError> {
+    let mut attempt = 1;
+    loop {
+        match provider.summarize(system_prompt, user_content) {
+            Ok(response) => return Ok(response),
+            Err(err) if attempt < policy.max_attempts && is_retryable(&err) => {
+                let retry_after = match &err {
+                    SummarizeError::HttpError { retry_after, .. } => *retry_after,
+                    _ => None,
+                };
+                let delay = policy.delay_for(attempt, retry_after);
+                tracing::debug!(
+                    "Summary request failed (attempt {attempt}/{}): {err}; retrying in {delay:?}",
+                    policy.max_attempts
+                );
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+
+    /// Provider that replays a scripted sequence of results.
+    struct MockProvider {
+        responses: RefCell<VecDeque<Result<SummaryResponse, SummarizeError>>>,
+        calls: Cell<u32>,
+    }
+
+    impl MockProvider {
+        fn new(responses: Vec<Result<SummaryResponse, SummarizeError>>) -> Self {
+            Self {
+                responses: RefCell::new(responses.into()),
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl SummaryProvider for MockProvider {
+        fn summarize(&self, _: &str, _: &str) -> Result<SummaryResponse, SummarizeError> {
+            self.calls.set(self.calls.get() + 1);
+            self.responses
+                .borrow_mut()
+                .pop_front()
+                .expect("MockProvider called more times than scripted")
+        }
+    }
+
+    fn http_error(status: u16) -> Result<SummaryResponse, SummarizeError> {
+        Err(SummarizeError::HttpError {
+            status,
+            body: "error".to_string(),
+            retry_after: None,
+        })
+    }
+
+    fn ok(content: &str) -> Result<SummaryResponse, SummarizeError> {
+        Ok(SummaryResponse {
+            content: content.to_string(),
+        })
+    }
+
+    /// Policy with no delays so tests run instantly.
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_retries_429_until_success() {
+        let provider = MockProvider::new(vec![http_error(429), http_error(429), ok("done")]);
+
+        let response = summarize_with_retry(&provider, &fast_policy(4), "sys", "user").unwrap();
+
+        assert_eq!(response.content, "done");
+        assert_eq!(provider.calls.get(), 3);
+    }
+
+    #[test]
+    fn test_retries_server_errors() {
+        let provider = MockProvider::new(vec![http_error(503), ok("done")]);
+
+        let response = summarize_with_retry(&provider, &fast_policy(4), "sys", "user").unwrap();
+
+        assert_eq!(response.content, "done");
+        assert_eq!(provider.calls.get(), 2);
+    }
+
+    #[test]
+    fn test_other_4xx_fails_fast() {
+        let provider = MockProvider::new(vec![http_error(401), ok("unreachable")]);
+
+        let err = summarize_with_retry(&provider, &fast_policy(4), "sys", "user").unwrap_err();
+
+        assert!(matches!(err, SummarizeError::HttpError { status: 401, .. }));
+        assert_eq!(provider.calls.get(), 1);
+    }
+
+    #[test]
+    fn test_non_http_error_fails_fast() {
+        let provider = MockProvider::new(vec![Err(SummarizeError::ParseError("bad".to_string()))]);
+
+        let err = summarize_with_retry(&provider, &fast_policy(4), "sys", "user").unwrap_err();
+
+        assert!(matches!(err, SummarizeError::ParseError(_)));
+        assert_eq!(provider.calls.get(), 1);
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts() {
+        let provider = MockProvider::new(vec![http_error(429), http_error(429), http_error(429)]);
+
+        let err = summarize_with_retry(&provider, &fast_policy(3), "sys", "user").unwrap_err();
+
+        assert!(matches!(err, SummarizeError::HttpError { status: 429, .. }));
+        assert_eq!(provider.calls.get(), 3);
+    }
+
+    #[test]
+    fn test_with_max_attempts_zero_sends_once() {
+        let policy = RetryPolicy::with_max_attempts(0);
+        assert_eq!(policy.max_attempts, 1);
+    }
+
+    #[test]
+    fn test_delay_honors_retry_after() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.delay_for(1, Some(Duration::from_secs(3))),
+            Duration::from_secs(3)
+        );
+    }
+
+    #[test]
+    fn test_delay_caps_retry_after_at_max_delay() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.delay_for(1, Some(Duration::from_secs(86400))),
+            MAX_DELAY
+        );
+    }
+
+    #[test]
+    fn test_delay_grows_exponentially_with_jitter() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+        };
+
+        for (retry, base_ms) in [(1, 100), (2, 200), (3, 400)] {
+            let delay = policy.delay_for(retry, None);
+            assert!(delay >= Duration::from_millis(base_ms));
+            assert!(delay <= Duration::from_millis(base_ms + base_ms / 2));
+        }
+    }
+
+    #[test]
+    fn test_delay_capped_at_max_delay() {
+        let policy = RetryPolicy {
+            max_attempts: 20,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+
+        let delay = policy.delay_for(15, None);
+        assert!(delay >= Duration::from_secs(1));
+        assert!(delay <= Duration::from_millis(1500));
+    }
+}
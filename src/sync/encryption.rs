@@ -23,6 +23,9 @@ pub const KEY_SIZE: usize = 32;
 /// Size of the nonce in bytes (96 bits for AES-GCM).
 pub const NONCE_SIZE: usize = 12;
 
+/// Size of the AES-GCM authentication tag appended to the ciphertext.
+pub const TAG_SIZE: usize = 16;
+
 /// Size of the salt for key derivation.
 pub const SALT_SIZE: usize = 16;
 
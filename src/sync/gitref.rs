@@ -12,7 +12,7 @@
 //! working directory, capture stderr, and return a [`SyncError::Git`] with the
 //! command and stderr on failure.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -108,11 +108,25 @@ pub fn build_tree(
     repo: &Path,
     base: Option<&str>,
     changes: &BTreeMap<String, String>,
+) -> Result<String, SyncError> {
+    build_tree_with_removals(repo, base, changes, &BTreeSet::new())
+}
+
+/// Builds a tree like [`build_tree`], additionally dropping `removals`.
+///
+/// Each path in `removals` is removed from the base tree before `changes` are
+/// applied. Paths absent from the base are ignored, so callers can pass the
+/// full set of paths that must not appear without checking the base first.
+pub fn build_tree_with_removals(
+    repo: &Path,
+    base: Option<&str>,
+    changes: &BTreeMap<String, String>,
+    removals: &BTreeSet<String>,
 ) -> Result<String, SyncError> {
     let git_dir = absolute_git_dir(repo)?;
     let index_path = git_dir.join(format!("lore-index-{}", Uuid::new_v4()));
 
-    let result = build_tree_with_index(repo, &index_path, base, changes);
+    let result = build_tree_with_index(repo, &index_path, base, changes, removals);
 
     // Best-effort cleanup of the temporary index regardless of outcome.
     let _ = std::fs::remove_file(&index_path);
@@ -126,12 +140,22 @@ fn build_tree_with_index(
     index_path: &Path,
     base: Option<&str>,
     changes: &BTreeMap<String, String>,
+    removals: &BTreeSet<String>,
 ) -> Result<String, SyncError> {
     // Seed the temporary index from the base tree, if any.
     if let Some(base_ref) = base {
         run_git_index(repo, index_path, &["read-tree", base_ref])?;
     }
 
+    // Drop removed entries; --force-remove ignores paths not in the index.
+    for path in removals {
+        run_git_index(
+            repo,
+            index_path,
+            &["update-index", "--force-remove", "--", path],
+        )?;
+    }
+
     // Overwrite or add only the changed entries.
     for (path, sha) in changes {
         let cacheinfo = format!("100644,{sha},{path}");
@@ -623,6 +647,34 @@ mod tests {
         assert_eq!(second_entry.sha, second_sha);
     }
 
+    #[test]
+    fn test_build_tree_with_removals_drops_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        init_repo(repo);
+
+        let keep_sha = write_blob(repo, b"keep").unwrap();
+        let drop_sha = write_blob(repo, b"drop").unwrap();
+
+        let mut changes = BTreeMap::new();
+        changes.insert("sessions/keep.enc".to_string(), keep_sha.clone());
+        changes.insert("sessions/drop.enc".to_string(), drop_sha);
+        let tree1 = build_tree(repo, None, &changes).unwrap();
+
+        // Removing a present path and a path that was never stored.
+        let removals: BTreeSet<String> = ["sessions/drop.enc", "sessions/missing.enc"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let tree2 =
+            build_tree_with_removals(repo, Some(&tree1), &BTreeMap::new(), &removals).unwrap();
+
+        let entries = read_tree(repo, &tree2).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "sessions/keep.enc");
+        assert_eq!(entries[0].sha, keep_sha);
+    }
+
     #[test]
     fn test_push_and_fetch_between_repos() {
         let remote_dir = tempfile::tempdir().unwrap();
@@ -9,6 +9,11 @@
 //! The on-disk blob pipeline is `serde_json -> gzip -> encrypt`. Compression
 //! happens before encryption because ciphertext does not compress. The output
 //! is raw bytes suitable for writing directly as a git blob (no base64).
+//!
+//! The gzip magic bytes double as the payload format marker: on decrypt, a
+//! plaintext that starts with them is decompressed, and anything else is
+//! treated as uncompressed JSON. This keeps blobs written without compression
+//! readable without a separate version byte that older clients would reject.
 
 use std::io::{Read, Write};
 
@@ -17,10 +22,13 @@ use flate2::write::GzEncoder;
 use flate2::Compression;
 use serde::{Deserialize, Serialize};
 
-use super::encryption::{decrypt_data, encrypt_data};
+use super::encryption::{decrypt_data, encrypt_data, NONCE_SIZE, TAG_SIZE};
 use super::SyncError;
 use crate::storage::models::{Annotation, Message, Session, SessionLink, Summary, Tag, Tombstone};
 
+/// Leading bytes of every gzip stream (RFC 1952).
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
 /// The complete reasoning record for a single session.
 ///
 /// This is the unit that gets serialized, compressed, encrypted, and written as
@@ -86,6 +94,18 @@ pub fn encrypt_session_record(record: &SessionRecord, key: &[u8]) -> Result<Vec<
     encrypt_data(&compressed, key)
 }
 
+/// Returns the size in bytes of the blob [`encrypt_session_record`] would
+/// produce for `record`, without needing the store key.
+///
+/// AES-GCM does not change the length of its input, so the blob is the
+/// compressed record plus the nonce and authentication tag.
+pub fn encrypted_record_size(record: &SessionRecord) -> Result<usize, SyncError> {
+    let json = serde_json::to_vec(record)
+        .map_err(|e| SyncError::Serialization(format!("Failed to serialize record: {e}")))?;
+
+    Ok(NONCE_SIZE + gzip_compress(&json)?.len() + TAG_SIZE)
+}
+
 /// Decrypts and deserializes a session record from git-blob bytes.
 ///
 /// Inverse of [`encrypt_session_record`]: `decrypt_data -> gunzip ->
@@ -96,9 +116,9 @@ pub fn encrypt_session_record(record: &SessionRecord, key: &[u8]) -> Result<Vec<
 /// * `blob` - The raw blob bytes as read from `git cat-file blob`
 /// * `key` - The 32-byte encryption key derived from the store passphrase
 pub fn decrypt_session_record(blob: &[u8], key: &[u8]) -> Result<SessionRecord, SyncError> {
-    let compressed = decrypt_data(blob, key)?;
+    let payload = decrypt_data(blob, key)?;
 
-    let json = gzip_decompress(&compressed)?;
+    let json = decode_payload(&payload)?;
 
     serde_json::from_slice(&json)
         .map_err(|e| SyncError::Serialization(format!("Failed to deserialize record: {e}")))
@@ -122,9 +142,9 @@ pub fn encrypt_tombstones(tombstones: &[Tombstone], key: &[u8]) -> Result<Vec<u8
 ///
 /// Inverse of [`encrypt_tombstones`].
 pub fn decrypt_tombstones(blob: &[u8], key: &[u8]) -> Result<Vec<Tombstone>, SyncError> {
-    let compressed = decrypt_data(blob, key)?;
+    let payload = decrypt_data(blob, key)?;
 
-    let json = gzip_decompress(&compressed)?;
+    let json = decode_payload(&payload)?;
 
     serde_json::from_slice(&json)
         .map_err(|e| SyncError::Serialization(format!("Failed to deserialize tombstones: {e}")))
@@ -141,6 +161,18 @@ fn gzip_compress(data: &[u8]) -> Result<Vec<u8>, SyncError> {
         .map_err(|e| SyncError::Compression(format!("Gzip finish failed: {e}")))
 }
 
+/// Returns the JSON bytes of a decrypted payload.
+///
+/// Gzip payloads (identified by [`GZIP_MAGIC`]) are decompressed; any other
+/// payload is returned unchanged as uncompressed JSON.
+fn decode_payload(data: &[u8]) -> Result<Vec<u8>, SyncError> {
+    if data.starts_with(&GZIP_MAGIC) {
+        gzip_decompress(data)
+    } else {
+        Ok(data.to_vec())
+    }
+}
+
 /// Decompresses gzip bytes produced by [`gzip_compress`].
 fn gzip_decompress(data: &[u8]) -> Result<Vec<u8>, SyncError> {
     let mut decoder = GzDecoder::new(data);
@@ -173,6 +205,9 @@ mod tests {
             source_path: Some("/sessions/a.jsonl".to_string()),
             message_count: 2,
             machine_id: Some("machine-1".to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            parent_session_id: None,
         };
 
         let messages = vec![
@@ -212,6 +247,8 @@ mod tests {
             created_at: Utc::now(),
             created_by: LinkCreator::User,
             confidence: Some(0.95),
+            forge: None,
+            factors: None,
         }];
 
         let tags = vec![Tag {
@@ -233,6 +270,7 @@ mod tests {
             session_id,
             content: "Fixed a bug in the parser".to_string(),
             generated_at: Utc::now(),
+            model: None,
         });
 
         SessionRecord {
@@ -261,6 +299,70 @@ mod tests {
         assert!(compressed.len() < data.len());
     }
 
+    #[test]
+    fn test_decode_payload_passes_through_uncompressed_json() {
+        let json = br#"{"a":1}"#;
+        assert_eq!(decode_payload(json).unwrap(), json.to_vec());
+
+        let compressed = gzip_compress(json).unwrap();
+        assert_eq!(decode_payload(&compressed).unwrap(), json.to_vec());
+    }
+
+    #[test]
+    fn test_decrypt_record_from_uncompressed_payload() {
+        let salt = generate_salt();
+        let key = derive_key("test passphrase", &salt).unwrap();
+
+        let record = sample_record();
+        let json = serde_json::to_vec(&record).unwrap();
+        let blob = encrypt_data(&json, &key).unwrap();
+
+        let decoded = decrypt_session_record(&blob, &key).unwrap();
+        assert_eq!(decoded.session.id, record.session.id);
+        assert_eq!(decoded.messages.len(), record.messages.len());
+    }
+
+    #[test]
+    fn test_large_record_compresses_and_roundtrips() {
+        let salt = generate_salt();
+        let key = derive_key("test passphrase", &salt).unwrap();
+
+        let mut record = sample_record();
+        let session_id = record.session.id;
+        let transcript = "cargo test output line with a stack trace\n".repeat(200);
+        record.messages = (0..500)
+            .map(|i| Message {
+                id: Uuid::new_v4(),
+                session_id,
+                parent_id: None,
+                index: i,
+                timestamp: Utc::now(),
+                role: if i % 2 == 0 {
+                    MessageRole::User
+                } else {
+                    MessageRole::Assistant
+                },
+                content: MessageContent::Text(format!("message {i}: {transcript}")),
+                model: None,
+                git_branch: Some("main".to_string()),
+                cwd: Some("/home/user/project".to_string()),
+            })
+            .collect();
+        record.session.message_count = record.messages.len() as i32;
+
+        let json = serde_json::to_vec(&record).unwrap();
+        let blob = encrypt_session_record(&record, &key).unwrap();
+        assert!(
+            blob.len() * 10 < json.len(),
+            "expected at least 10x compression, got {} -> {}",
+            json.len(),
+            blob.len()
+        );
+
+        let decoded = decrypt_session_record(&blob, &key).unwrap();
+        assert_eq!(serde_json::to_vec(&decoded).unwrap(), json);
+    }
+
     #[test]
     fn test_encrypt_decrypt_record_roundtrip() {
         let salt = generate_salt();
@@ -283,6 +385,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encrypted_record_size_matches_blob() {
+        let key = derive_key("test passphrase", &generate_salt()).unwrap();
+        let record = sample_record();
+
+        let blob = encrypt_session_record(&record, &key).unwrap();
+        assert_eq!(encrypted_record_size(&record).unwrap(), blob.len());
+    }
+
     #[test]
     fn test_full_record_serialization_preserves_all_fields() {
         // Serialize to JSON and back without the crypto layer to verify the